@@ -44,6 +44,12 @@ impl Db {
              ON discord_user_profiles(public_address)",
             [],
         )?;
+        // Address lookups compare case-insensitively, which the plain index can't serve
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_discord_profiles_address_lower
+             ON discord_user_profiles(LOWER(public_address))",
+            [],
+        )?;
         Ok(())
     }
 
@@ -104,6 +110,33 @@ impl Db {
         }
     }
 
+    /// All registered profiles whose address matches (case-insensitive).
+    /// Normally at most one, but nothing prevents two users registering the same address.
+    pub fn list_profiles_by_address(
+        &self,
+        address: &str,
+    ) -> Result<Vec<DiscordUserProfile>, String> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, discord_user_id, discord_username, public_address,
+                        registration_status, registered_at, last_interaction_at,
+                        created_at, updated_at
+                 FROM discord_user_profiles
+                 WHERE LOWER(public_address) = LOWER(?1)
+                   AND registration_status = 'registered'
+                 ORDER BY registered_at ASC",
+            )
+            .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+        let profiles = stmt
+            .query_map(rusqlite::params![address], |row| row_to_profile(row))
+            .map_err(|e| format!("Failed to query: {}", e))?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(profiles)
+    }
+
     pub fn register_address(
         &self,
         discord_user_id: &str,
@@ -45,6 +45,7 @@ async fn main() {
         .route("/rpc/profile/unregister", axum::routing::post(routes::unregister_address))
         .route("/rpc/profiles/all", axum::routing::get(routes::list_all))
         .route("/rpc/profiles/registered", axum::routing::get(routes::list_registered))
+        .route("/rpc/profiles/by_address", axum::routing::post(routes::list_by_address))
         .route("/rpc/stats", axum::routing::get(routes::stats))
         .route("/rpc/status", axum::routing::get(routes::status))
         .route("/rpc/backup/export", axum::routing::post(routes::backup_export))
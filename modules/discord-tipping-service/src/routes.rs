@@ -46,6 +46,17 @@ pub async fn get_by_address(
     }
 }
 
+// POST /rpc/profiles/by_address
+pub async fn list_by_address(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<GetProfileByAddressRequest>,
+) -> (StatusCode, Json<RpcResponse<Vec<DiscordUserProfile>>>) {
+    match state.db.list_profiles_by_address(&req.address) {
+        Ok(p) => (StatusCode::OK, Json(RpcResponse::ok(p))),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, Json(RpcResponse::err(e))),
+    }
+}
+
 // POST /rpc/profile/register
 pub async fn register_address(
     State(state): State<Arc<AppState>>,
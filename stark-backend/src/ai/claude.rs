@@ -1,8 +1,8 @@
 use crate::ai::types::{
     AiError, AiResponse, ClaudeContentBlock, ClaudeMessage as TypedClaudeMessage,
-    ClaudeMessageContent, ClaudeTool, ThinkingLevel, ToolCall, ToolResponse,
+    ClaudeMessageContent, ClaudeTool, ThinkingLevel, TokenUsage, ToolCall, ToolResponse,
 };
-use crate::ai::{Message, MessageRole};
+use crate::ai::{merge_consecutive_roles, Message, MessageRole};
 use crate::gateway::events::EventBroadcaster;
 use crate::gateway::protocol::GatewayEvent;
 use crate::tools::ToolDefinition;
@@ -13,11 +13,22 @@ use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
+/// Anthropic Messages API endpoint
+pub const DEFAULT_ENDPOINT: &str = "https://api.anthropic.com/v1/messages";
+
+/// Response token limit when none is configured
+const DEFAULT_MAX_TOKENS: u32 = 4096;
+
+/// Headroom kept above the thinking budget (the API requires max_tokens > budget_tokens)
+const THINKING_RESPONSE_HEADROOM: u32 = 4096;
+
 pub struct ClaudeClient {
     client: Client,
     auth_headers: header::HeaderMap,
     endpoint: String,
     model: String,
+    /// Response token limit (raised automatically to fit the thinking budget)
+    max_tokens: u32,
     /// Thinking budget in tokens (0 = disabled)
     thinking_budget: AtomicU32,
     /// Optional broadcaster for emitting retry events
@@ -33,6 +44,7 @@ impl Clone for ClaudeClient {
             auth_headers: self.auth_headers.clone(),
             endpoint: self.endpoint.clone(),
             model: self.model.clone(),
+            max_tokens: self.max_tokens,
             thinking_budget: AtomicU32::new(self.thinking_budget.load(Ordering::SeqCst)),
             broadcaster: self.broadcaster.clone(),
             channel_id: self.channel_id,
@@ -99,6 +111,16 @@ struct ClaudeCompletionResponse {
     content: Vec<ClaudeResponseContent>,
     #[serde(default)]
     stop_reason: Option<String>,
+    #[serde(default)]
+    usage: Option<ClaudeUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClaudeUsage {
+    #[serde(default)]
+    input_tokens: u32,
+    #[serde(default)]
+    output_tokens: u32,
 }
 
 #[derive(Debug, Deserialize)]
@@ -127,6 +149,16 @@ struct ClaudeError {
 
 impl ClaudeClient {
     pub fn new(api_key: &str, endpoint: Option<&str>, model: Option<&str>) -> Result<Self, String> {
+        Self::new_with_max_tokens(api_key, endpoint, model, None)
+    }
+
+    /// Create a client with a response token limit (agent settings' max_response_tokens)
+    pub fn new_with_max_tokens(
+        api_key: &str,
+        endpoint: Option<&str>,
+        model: Option<&str>,
+        max_tokens: Option<u32>,
+    ) -> Result<Self, String> {
         let mut auth_headers = header::HeaderMap::new();
         auth_headers.insert(
             header::CONTENT_TYPE,
@@ -145,9 +177,12 @@ impl ClaudeClient {
             client: crate::http::shared_client().clone(),
             auth_headers,
             endpoint: endpoint
-                .unwrap_or("https://api.anthropic.com/v1/messages")
+                .map(str::trim)
+                .filter(|e| !e.is_empty())
+                .unwrap_or(DEFAULT_ENDPOINT)
                 .to_string(),
             model: model.unwrap_or("claude-sonnet-4-20250514").to_string(),
+            max_tokens: max_tokens.filter(|&t| t > 0).unwrap_or(DEFAULT_MAX_TOKENS),
             thinking_budget: AtomicU32::new(0),
             broadcaster: None,
             channel_id: None,
@@ -200,20 +235,24 @@ impl ClaudeClient {
         }
     }
 
+    /// max_tokens for a request, kept above the thinking budget when thinking is enabled
+    fn request_max_tokens(&self) -> u32 {
+        let budget = self.get_thinking_budget();
+        if budget > 0 && self.max_tokens <= budget {
+            budget.saturating_add(THINKING_RESPONSE_HEADROOM)
+        } else {
+            self.max_tokens
+        }
+    }
+
     pub async fn generate_text(&self, messages: Vec<Message>) -> Result<String, String> {
-        // Extract system message if present
-        let mut system_message = None;
-        let filtered_messages: Vec<Message> = messages
-            .into_iter()
-            .filter(|m| {
-                if m.role == MessageRole::System {
-                    system_message = Some(m.content.clone());
-                    false
-                } else {
-                    true
-                }
-            })
-            .collect();
+        self.generate_text_response(messages).await.map(|response| response.content)
+    }
+
+    /// Plain text generation, keeping the provider's token usage alongside the text
+    pub async fn generate_text_response(&self, messages: Vec<Message>) -> Result<AiResponse, String> {
+        let (system_message, filtered_messages) = split_system_messages(messages);
+        let filtered_messages = merge_consecutive_roles(filtered_messages);
 
         let api_messages: Vec<SimpleClaudeMessage> = filtered_messages
             .into_iter()
@@ -227,7 +266,7 @@ impl ClaudeClient {
         let request = ClaudeCompletionRequest {
             model: self.model.clone(),
             messages: api_messages,
-            max_tokens: 4096,
+            max_tokens: self.request_max_tokens(),
             system: system_message,
             thinking,
         };
@@ -342,7 +381,11 @@ impl ClaudeClient {
             return Err("Claude API returned no content".to_string());
         }
 
-        Ok(content)
+        Ok(AiResponse::text(content).with_usage(
+            response_data
+                .usage
+                .map(|u| TokenUsage::new(u.input_tokens, u.output_tokens)),
+        ))
     }
 
     /// Generate a response with tool support
@@ -352,19 +395,8 @@ impl ClaudeClient {
         tool_messages: Vec<TypedClaudeMessage>,
         tools: Vec<ToolDefinition>,
     ) -> Result<AiResponse, AiError> {
-        // Extract system message if present
-        let mut system_message = None;
-        let filtered_messages: Vec<Message> = messages
-            .into_iter()
-            .filter(|m| {
-                if m.role == MessageRole::System {
-                    system_message = Some(m.content.clone());
-                    false
-                } else {
-                    true
-                }
-            })
-            .collect();
+        let (system_message, filtered_messages) = split_system_messages(messages);
+        let filtered_messages = merge_consecutive_roles(filtered_messages);
 
         // Convert regular messages to typed messages
         let mut api_messages: Vec<TypedClaudeMessage> = filtered_messages
@@ -378,22 +410,14 @@ impl ClaudeClient {
         // Add tool messages (assistant tool_use + user tool_result pairs)
         api_messages.extend(tool_messages);
 
-        // Convert tool definitions to Claude format
-        let claude_tools: Vec<ClaudeTool> = tools
-            .into_iter()
-            .map(|t| ClaudeTool {
-                name: t.name,
-                description: t.description,
-                input_schema: serde_json::to_value(t.input_schema).unwrap_or_default(),
-            })
-            .collect();
+        let claude_tools = to_claude_tools(tools);
 
         let thinking = self.build_thinking_config();
         let has_tools = !claude_tools.is_empty();
         let request = ClaudeToolRequest {
             model: self.model.clone(),
             messages: api_messages,
-            max_tokens: 4096,
+            max_tokens: self.request_max_tokens(),
             system: system_message,
             tools: if has_tools {
                 Some(claude_tools)
@@ -517,37 +541,16 @@ impl ClaudeClient {
             }
         })?;
 
-        // Parse the response content
-        let mut text_content = String::new();
-        let mut tool_calls = Vec::new();
-
-        for content in response_data.content {
-            match content.content_type.as_str() {
-                "text" => {
-                    if let Some(text) = content.text {
-                        text_content.push_str(&text);
-                    }
-                }
-                "tool_use" => {
-                    if let (Some(id), Some(name), Some(input)) =
-                        (content.id, content.name, content.input)
-                    {
-                        tool_calls.push(ToolCall {
-                            id,
-                            name,
-                            arguments: input,
-                        });
-                    }
-                }
-                _ => {}
-            }
-        }
+        let (text_content, tool_calls) = parse_response_content(response_data.content);
 
         Ok(AiResponse {
             content: text_content,
             tool_calls,
             stop_reason: response_data.stop_reason,
             x402_payment: None, // Claude doesn't use x402
+            usage: response_data
+                .usage
+                .map(|u| TokenUsage::new(u.input_tokens, u.output_tokens)),
         })
     }
 
@@ -582,3 +585,113 @@ impl ClaudeClient {
         ]
     }
 }
+
+/// Pull system messages out for the top-level `system` field (Claude rejects a
+/// "system" role in `messages`). Multiple system messages are joined in order.
+fn split_system_messages(messages: Vec<Message>) -> (Option<String>, Vec<Message>) {
+    let (system, rest): (Vec<Message>, Vec<Message>) = messages
+        .into_iter()
+        .partition(|m| m.role == MessageRole::System);
+
+    let system = system
+        .into_iter()
+        .map(|m| m.content)
+        .filter(|c| !c.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    ((!system.is_empty()).then_some(system), rest)
+}
+
+/// Map our tool definitions to Claude's `input_schema` format
+fn to_claude_tools(tools: Vec<ToolDefinition>) -> Vec<ClaudeTool> {
+    tools
+        .into_iter()
+        .map(|t| ClaudeTool {
+            name: t.name,
+            description: t.description,
+            input_schema: serde_json::to_value(t.input_schema).unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// Collect text and `tool_use` blocks from a response.
+/// Thinking blocks are skipped; a tool_use without `input` gets empty arguments.
+fn parse_response_content(content: Vec<ClaudeResponseContent>) -> (String, Vec<ToolCall>) {
+    let mut text_content = String::new();
+    let mut tool_calls = Vec::new();
+
+    for block in content {
+        match block.content_type.as_str() {
+            "text" => {
+                if let Some(text) = block.text {
+                    text_content.push_str(&text);
+                }
+            }
+            "tool_use" => match (block.id, block.name) {
+                (Some(id), Some(name)) => tool_calls.push(ToolCall {
+                    id,
+                    name,
+                    arguments: block.input.unwrap_or_else(|| serde_json::json!({})),
+                }),
+                _ => log::warn!("[CLAUDE] Ignoring tool_use block without an id or name"),
+            },
+            _ => {}
+        }
+    }
+
+    (text_content, tool_calls)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn msg(role: MessageRole, content: &str) -> Message {
+        Message { role, content: content.to_string() }
+    }
+
+    #[test]
+    fn test_split_system_messages_joins_all_system_prompts() {
+        let (system, rest) = split_system_messages(vec![
+            msg(MessageRole::System, "You are StarkBot."),
+            msg(MessageRole::User, "hi"),
+            msg(MessageRole::System, "Context: channel #general"),
+        ]);
+        assert_eq!(system.as_deref(), Some("You are StarkBot.\n\nContext: channel #general"));
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest[0].role, MessageRole::User);
+
+        let (none, _) = split_system_messages(vec![msg(MessageRole::User, "hi")]);
+        assert!(none.is_none());
+    }
+
+    #[test]
+    fn test_parse_response_content_maps_tool_use_blocks() {
+        let content: Vec<ClaudeResponseContent> = serde_json::from_value(json!([
+            {"type": "thinking", "thinking": "..."},
+            {"type": "text", "text": "Checking the balance."},
+            {"type": "tool_use", "id": "toolu_1", "name": "token_lookup", "input": {"symbol": "ETH"}},
+            {"type": "tool_use", "id": "toolu_2", "name": "get_time"}
+        ]))
+        .unwrap();
+
+        let (text, calls) = parse_response_content(content);
+        assert_eq!(text, "Checking the balance.");
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].id, "toolu_1");
+        assert_eq!(calls[0].arguments, json!({"symbol": "ETH"}));
+        assert_eq!(calls[1].arguments, json!({}));
+    }
+
+    #[test]
+    fn test_max_tokens_and_endpoint_defaults() {
+        let client = ClaudeClient::new_with_max_tokens("key", Some(" "), None, Some(0)).unwrap();
+        assert_eq!(client.endpoint, DEFAULT_ENDPOINT);
+        assert_eq!(client.request_max_tokens(), DEFAULT_MAX_TOKENS);
+
+        client.set_thinking_level(ThinkingLevel::High);
+        assert!(client.request_max_tokens() > client.get_thinking_budget());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::ai::types::{AiResponse, ToolCall};
+use crate::ai::types::{AiResponse, TokenUsage, ToolCall};
 use crate::ai::Message;
 use crate::gateway::events::EventBroadcaster;
 use crate::gateway::protocol::GatewayEvent;
@@ -72,6 +72,22 @@ struct OllamaChatResponse {
     message: OllamaResponseMessage,
     #[serde(default)]
     done_reason: Option<String>,
+    /// Tokens in the prompt (Ollama omits this when the prompt was cached)
+    #[serde(default)]
+    prompt_eval_count: Option<u32>,
+    /// Tokens generated
+    #[serde(default)]
+    eval_count: Option<u32>,
+}
+
+impl OllamaChatResponse {
+    /// Token usage, if Ollama reported either count
+    fn usage(&self) -> Option<TokenUsage> {
+        match (self.prompt_eval_count, self.eval_count) {
+            (None, None) => None,
+            (prompt, eval) => Some(TokenUsage::new(prompt.unwrap_or(0), eval.unwrap_or(0))),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -129,6 +145,11 @@ impl LlamaClient {
     }
 
     pub async fn generate_text(&self, messages: Vec<Message>) -> Result<String, String> {
+        self.generate_text_response(messages).await.map(|response| response.content)
+    }
+
+    /// Plain text generation, keeping the provider's token usage alongside the text
+    pub async fn generate_text_response(&self, messages: Vec<Message>) -> Result<AiResponse, String> {
         let api_messages: Vec<OllamaMessage> = messages
             .into_iter()
             .map(|m| OllamaMessage {
@@ -248,7 +269,8 @@ impl LlamaClient {
             return Err("Ollama API returned no content".to_string());
         }
 
-        Ok(response_data.message.content)
+        let usage = response_data.usage();
+        Ok(AiResponse::text(response_data.message.content).with_usage(usage))
     }
 
     /// Generate a response with tool support (Llama 3.1+ with Ollama)
@@ -396,6 +418,7 @@ impl LlamaClient {
         let response_data = response_data_opt.ok_or_else(|| {
             last_error.unwrap_or_else(|| "Max retries exceeded".to_string())
         })?;
+        let usage = response_data.usage();
 
         // Parse tool calls from response
         let mut tool_calls = Vec::new();
@@ -412,6 +435,8 @@ impl LlamaClient {
         // Determine stop reason
         let stop_reason = if !tool_calls.is_empty() {
             Some("tool_use".to_string())
+        } else if response_data.done_reason.as_deref() == Some("length") {
+            Some("max_tokens".to_string())
         } else {
             response_data.done_reason
         };
@@ -421,6 +446,7 @@ impl LlamaClient {
             tool_calls,
             stop_reason,
             x402_payment: None, // Llama doesn't use x402 directly (handled by OpenAI-compatible wrapper)
+            usage,
         })
     }
 
@@ -1,8 +1,10 @@
 pub mod archetypes;
 pub mod claude;
 pub mod llama;
+pub mod model_selection;
 pub mod multi_agent;
 pub mod openai;
+pub mod request_sampler;
 pub mod streaming;
 pub mod types;
 
@@ -11,7 +13,7 @@ pub use llama::{LlamaClient, LlamaMessage};
 pub use openai::OpenAIClient;
 pub use archetypes::{ArchetypeId, ArchetypeRegistry, ModelArchetype};
 pub use types::{
-    AiError, AiResponse, ClaudeMessage as TypedClaudeMessage, ThinkingLevel, ToolCall,
+    AiError, AiResponse, ClaudeMessage as TypedClaudeMessage, ThinkingLevel, TokenUsage, ToolCall,
     ToolHistoryEntry, ToolResponse,
 };
 
@@ -48,6 +50,26 @@ pub struct Message {
     pub content: String,
 }
 
+/// Merge runs of consecutive messages with the same role into one message, joining their
+/// content with a blank line. Some providers reject requests whose turns don't alternate
+/// (e.g. two user turns batched from the web client). Empty messages are dropped.
+pub fn merge_consecutive_roles(messages: Vec<Message>) -> Vec<Message> {
+    let mut merged: Vec<Message> = Vec::with_capacity(messages.len());
+    for message in messages {
+        if message.content.trim().is_empty() {
+            continue;
+        }
+        match merged.last_mut() {
+            Some(last) if last.role == message.role => {
+                last.content.push_str("\n\n");
+                last.content.push_str(&message.content);
+            }
+            _ => merged.push(message),
+        }
+    }
+    merged
+}
+
 /// A single iteration's INPUT (what was sent to the AI) and OUTPUT (what came back).
 #[derive(Debug, Clone, Serialize)]
 pub struct TraceEntry {
@@ -128,6 +150,19 @@ pub enum AiClient {
     OpenAI(OpenAIClient),
     Llama(LlamaClient),
     Mock(MockAiClient),
+    /// Primary client followed by fallbacks, tried in order (see [`AiClient::with_fallbacks`])
+    Fallback(Vec<AiClient>),
+}
+
+/// Whether a failed request should be retried on the next provider in a fallback chain.
+/// A 400 means the request itself is malformed, so another provider won't do better.
+fn should_fall_back(error: &AiError) -> bool {
+    error.status_code != Some(400)
+}
+
+/// The settings' output token limit, or None (client default) if it isn't a positive value
+fn response_token_limit(settings: &AgentSettings) -> Option<u32> {
+    u32::try_from(settings.max_response_tokens).ok().filter(|v| *v > 0)
 }
 
 impl AiClient {
@@ -161,10 +196,11 @@ impl AiClient {
 
         // Use ClaudeClient for Claude archetype (native Anthropic API with x-api-key header)
         if archetype_id == ArchetypeId::Claude {
-            let client = ClaudeClient::new(
+            let client = ClaudeClient::new_with_max_tokens(
                 api_key,
                 Some(&settings.endpoint),
                 Some(model),
+                response_token_limit(settings),
             )?;
             return Ok(AiClient::Claude(client));
         }
@@ -175,7 +211,7 @@ impl AiClient {
             Some(&settings.endpoint),
             Some(model),
             burner_private_key,
-            Some(settings.max_response_tokens as u32),
+            response_token_limit(settings),
         )?;
         Ok(AiClient::OpenAI(client))
     }
@@ -203,10 +239,11 @@ impl AiClient {
 
         // Use ClaudeClient for Claude archetype (native Anthropic API with x-api-key header)
         if archetype_id == ArchetypeId::Claude {
-            let client = ClaudeClient::new(
+            let client = ClaudeClient::new_with_max_tokens(
                 api_key,
                 Some(&settings.endpoint),
                 Some(model),
+                response_token_limit(settings),
             )?;
             return Ok(AiClient::Claude(client));
         }
@@ -217,18 +254,69 @@ impl AiClient {
             Some(&settings.endpoint),
             Some(model),
             wallet_provider,
-            Some(settings.max_response_tokens as u32),
+            response_token_limit(settings),
         )?;
         Ok(AiClient::OpenAI(client))
     }
 
+    /// Chain fallback clients behind this one. Requests go to this client first; when it
+    /// fails with anything but a 400, the next client is tried. The dispatcher keeps the
+    /// primary's archetype for prompt formatting, so fallbacks should be compatible models.
+    pub fn with_fallbacks(self, fallbacks: Vec<AiClient>) -> Self {
+        if fallbacks.is_empty() {
+            return self;
+        }
+        let mut chain = Vec::with_capacity(fallbacks.len() + 1);
+        for client in std::iter::once(self).chain(fallbacks) {
+            match client {
+                AiClient::Fallback(inner) => chain.extend(inner),
+                other => chain.push(other),
+            }
+        }
+        AiClient::Fallback(chain)
+    }
+
+    /// Log and record that a request moved on to the next provider
+    fn note_fallback(position: usize, error: &str) {
+        log::warn!("[MODEL_FALLBACK] Provider {} failed ({}), trying the next one", position, error);
+        crate::telemetry::emit_annotation(
+            "model_fallback",
+            serde_json::json!({ "failed_position": position, "error": error }),
+        );
+    }
+
     /// Get the archetype ID from agent settings
     pub fn infer_archetype(settings: &AgentSettings) -> ArchetypeId {
         ArchetypeId::from_str(&settings.model_archetype).unwrap_or(ArchetypeId::Kimi)
     }
 
+    /// Provider label for the AI latency metric. Fallback chains aren't timed
+    /// themselves; each provider they try is.
+    fn metrics_label(&self) -> Option<&'static str> {
+        match self {
+            AiClient::Claude(_) => Some("claude"),
+            AiClient::OpenAI(_) => Some("openai"),
+            AiClient::Llama(_) => Some("llama"),
+            AiClient::Mock(_) => Some("mock"),
+            AiClient::Fallback(_) => None,
+        }
+    }
+
+    async fn timed<T, E>(&self, request: impl std::future::Future<Output = Result<T, E>>) -> Result<T, E> {
+        let started = std::time::Instant::now();
+        let result = request.await;
+        if let Some(provider) = self.metrics_label() {
+            crate::metrics::record_ai_request(provider, result.is_ok(), started.elapsed());
+        }
+        result
+    }
+
     /// Generate text using the configured provider
     pub async fn generate_text(&self, messages: Vec<Message>) -> Result<String, String> {
+        self.timed(self.generate_text_untimed(messages)).await
+    }
+
+    async fn generate_text_untimed(&self, messages: Vec<Message>) -> Result<String, String> {
         match self {
             AiClient::Claude(client) => client.generate_text(messages).await,
             AiClient::OpenAI(client) => client.generate_text(messages).await,
@@ -236,40 +324,69 @@ impl AiClient {
             AiClient::Mock(client) => client.next_response()
                 .map(|r| r.content)
                 .map_err(|e| e.message),
+            AiClient::Fallback(chain) => Self::fallback_text(chain, messages).await.map(|r| r.content),
         }
     }
 
     /// Generate text and emit x402 payment event if applicable
-    /// Returns (content, optional payment info) so caller can persist the payment
+    /// Returns the whole response so the caller can persist the payment and record token usage
     pub async fn generate_text_with_events(
         &self,
         messages: Vec<Message>,
         broadcaster: &Arc<EventBroadcaster>,
         channel_id: i64,
-    ) -> Result<(String, Option<X402PaymentInfo>), String> {
-        match self {
-            AiClient::OpenAI(client) => {
-                let (content, payment) = client.generate_text_with_payment_info(messages).await?;
-                // Emit x402 payment event if payment was made
-                if let Some(ref payment_info) = payment {
-                    broadcaster.broadcast(GatewayEvent::x402_payment(
-                        channel_id,
-                        &payment_info.amount,
-                        &payment_info.amount_formatted,
-                        &payment_info.asset,
-                        &payment_info.pay_to,
-                        payment_info.resource.as_deref(),
-                    ));
+    ) -> Result<AiResponse, String> {
+        self.timed(self.generate_text_with_events_untimed(messages, broadcaster, channel_id))
+            .await
+    }
+
+    async fn generate_text_with_events_untimed(
+        &self,
+        messages: Vec<Message>,
+        broadcaster: &Arc<EventBroadcaster>,
+        channel_id: i64,
+    ) -> Result<AiResponse, String> {
+        let response = match self {
+            AiClient::OpenAI(client) => client.generate_text_response(messages).await?,
+            // Other providers don't support x402
+            AiClient::Claude(client) => client.generate_text_response(messages).await?,
+            AiClient::Llama(client) => client.generate_text_response(messages).await?,
+            AiClient::Mock(client) => client.next_response().map_err(|e| e.message)?,
+            AiClient::Fallback(chain) => Self::fallback_text(chain, messages).await?,
+        };
+        // Emit x402 payment event if payment was made
+        if let Some(ref payment_info) = response.x402_payment {
+            Self::emit_x402_payment(broadcaster, channel_id, payment_info);
+        }
+        Ok(response)
+    }
+
+    fn emit_x402_payment(broadcaster: &Arc<EventBroadcaster>, channel_id: i64, payment_info: &X402PaymentInfo) {
+        broadcaster.broadcast(GatewayEvent::x402_payment(
+            channel_id,
+            &payment_info.amount,
+            &payment_info.amount_formatted,
+            &payment_info.asset,
+            &payment_info.pay_to,
+            payment_info.resource.as_deref(),
+        ));
+    }
+
+    /// Plain text generation across a fallback chain. Goes through `generate_with_tools`
+    /// (with no tools) so each failure keeps its HTTP status for `should_fall_back`.
+    async fn fallback_text(chain: &[AiClient], messages: Vec<Message>) -> Result<AiResponse, String> {
+        let mut last_error = "No AI providers configured".to_string();
+        for (position, client) in chain.iter().enumerate() {
+            match Box::pin(client.generate_with_tools(messages.clone(), Vec::new(), Vec::new())).await {
+                Ok(response) => return Ok(response),
+                Err(e) if !should_fall_back(&e) => return Err(e.to_string()),
+                Err(e) => {
+                    Self::note_fallback(position, &e.message);
+                    last_error = e.to_string();
                 }
-                Ok((content, payment))
             }
-            // Other providers don't support x402
-            AiClient::Claude(client) => Ok((client.generate_text(messages).await?, None)),
-            AiClient::Llama(client) => Ok((client.generate_text(messages).await?, None)),
-            AiClient::Mock(client) => client.next_response()
-                .map(|r| (r.content, None))
-                .map_err(|e| e.message),
         }
+        Err(last_error)
     }
 
     /// Generate response with tool support (Claude, OpenAI, and Llama 3.1+)
@@ -278,6 +395,16 @@ impl AiClient {
         messages: Vec<Message>,
         tool_history: Vec<ToolHistoryEntry>,
         tools: Vec<ToolDefinition>,
+    ) -> Result<AiResponse, AiError> {
+        self.timed(self.generate_with_tools_untimed(messages, tool_history, tools))
+            .await
+    }
+
+    async fn generate_with_tools_untimed(
+        &self,
+        messages: Vec<Message>,
+        tool_history: Vec<ToolHistoryEntry>,
+        tools: Vec<ToolDefinition>,
     ) -> Result<AiResponse, AiError> {
         match self {
             AiClient::Claude(client) => {
@@ -303,24 +430,53 @@ impl AiClient {
                     .map_err(AiError::from)
             }
             AiClient::Mock(client) => client.next_response_traced(messages, tool_history, tools),
+            AiClient::Fallback(chain) => {
+                let mut last_error = AiError::new("No AI providers configured");
+                for (position, client) in chain.iter().enumerate() {
+                    let result = Box::pin(client.generate_with_tools(
+                        messages.clone(),
+                        tool_history.clone(),
+                        tools.clone(),
+                    ))
+                    .await;
+                    match result {
+                        Ok(response) => return Ok(response),
+                        Err(e) if !should_fall_back(&e) => return Err(e),
+                        Err(e) => {
+                            Self::note_fallback(position, &e.message);
+                            last_error = e;
+                        }
+                    }
+                }
+                Err(last_error)
+            }
         }
     }
 
     /// Check if the current provider supports tools
     pub fn supports_tools(&self) -> bool {
         // All providers now support tools
-        matches!(self, AiClient::Claude(_) | AiClient::OpenAI(_) | AiClient::Llama(_) | AiClient::Mock(_))
+        match self {
+            AiClient::Fallback(chain) => chain.iter().all(|c| c.supports_tools()),
+            _ => true,
+        }
     }
 
     /// Check if the current provider supports extended thinking
     pub fn supports_thinking(&self) -> bool {
-        matches!(self, AiClient::Claude(_))
+        match self {
+            AiClient::Claude(_) => true,
+            AiClient::Fallback(chain) => chain.first().map(|c| c.supports_thinking()).unwrap_or(false),
+            _ => false,
+        }
     }
 
     /// Set the thinking level for Claude models
     pub fn set_thinking_level(&self, level: ThinkingLevel) {
-        if let AiClient::Claude(client) = self {
-            client.set_thinking_level(level);
+        match self {
+            AiClient::Claude(client) => client.set_thinking_level(level),
+            AiClient::Fallback(chain) => chain.iter().for_each(|c| c.set_thinking_level(level)),
+            _ => {}
         }
     }
 
@@ -337,6 +493,12 @@ impl AiClient {
                 AiClient::Llama(client.with_broadcaster(broadcaster, channel_id))
             }
             AiClient::Mock(_) => self, // Mock doesn't need broadcaster
+            AiClient::Fallback(chain) => AiClient::Fallback(
+                chain
+                    .into_iter()
+                    .map(|c| c.with_broadcaster(Arc::clone(&broadcaster), channel_id))
+                    .collect(),
+            ),
         }
     }
 
@@ -410,3 +572,68 @@ impl AiClient {
         messages
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(role: MessageRole, content: &str) -> Message {
+        Message { role, content: content.to_string() }
+    }
+
+    #[test]
+    fn test_merge_consecutive_user_messages() {
+        let merged = merge_consecutive_roles(vec![
+            msg(MessageRole::System, "You are StarkBot."),
+            msg(MessageRole::User, "what's the ETH price?"),
+            msg(MessageRole::User, "and BTC?"),
+            msg(MessageRole::Assistant, "Checking both."),
+            msg(MessageRole::User, ""),
+            msg(MessageRole::Assistant, "ETH is up."),
+        ]);
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged[1].role, MessageRole::User);
+        assert_eq!(merged[1].content, "what's the ETH price?\n\nand BTC?");
+        // The empty user turn in between doesn't break up the assistant run
+        assert_eq!(merged[2].content, "Checking both.\n\nETH is up.");
+    }
+
+    fn mock(responses: Vec<Result<AiResponse, AiError>>) -> AiClient {
+        AiClient::Mock(MockAiClient::new(responses))
+    }
+
+    #[tokio::test]
+    async fn test_fallback_used_when_primary_unavailable() {
+        let client = mock(vec![Err(AiError::with_status("overloaded", 503))])
+            .with_fallbacks(vec![mock(vec![Ok(AiResponse::text("from fallback".to_string()))])]);
+        let response = client
+            .generate_with_tools(vec![msg(MessageRole::User, "hi")], vec![], vec![])
+            .await
+            .unwrap();
+        assert_eq!(response.content, "from fallback");
+    }
+
+    #[tokio::test]
+    async fn test_bad_request_does_not_fall_back() {
+        let client = mock(vec![Err(AiError::with_status("invalid tool schema", 400))])
+            .with_fallbacks(vec![mock(vec![Ok(AiResponse::text("from fallback".to_string()))])]);
+        let err = client
+            .generate_with_tools(vec![msg(MessageRole::User, "hi")], vec![], vec![])
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code, Some(400));
+    }
+
+    #[tokio::test]
+    async fn test_text_generation_uses_same_fallback_rule() {
+        let client = mock(vec![Err(AiError::with_status("malformed", 400))])
+            .with_fallbacks(vec![mock(vec![Ok(AiResponse::text("from fallback".to_string()))])]);
+        let err = client.generate_text(vec![msg(MessageRole::User, "hi")]).await.unwrap_err();
+        assert!(err.contains("[HTTP 400]"));
+
+        let client = mock(vec![Err(AiError::with_status("overloaded", 503))])
+            .with_fallbacks(vec![mock(vec![Ok(AiResponse::text("from fallback".to_string()))])]);
+        let text = client.generate_text(vec![msg(MessageRole::User, "hi")]).await.unwrap();
+        assert_eq!(text, "from fallback");
+    }
+}
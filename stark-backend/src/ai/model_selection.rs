@@ -0,0 +1,101 @@
+//! Per-dispatch model selection
+//!
+//! Normally every dispatch uses the single enabled `agent_settings` row. For
+//! A/B testing, operators give two or more rows a positive `weight`; each
+//! dispatch then picks one of those rows at random in proportion to its
+//! weight. The choice is recorded as a telemetry span so outcomes can be
+//! compared per model.
+
+use crate::db::Database;
+use crate::models::AgentSettings;
+use rand::Rng;
+use rusqlite::Result as SqliteResult;
+
+/// Span name under which the chosen model is recorded
+pub const MODEL_SELECTION_SPAN: &str = "model_selection";
+
+/// The agent settings chosen for one dispatch
+#[derive(Debug, Clone)]
+pub struct ModelSelection {
+    pub settings: AgentSettings,
+    /// Chosen from the weighted pool rather than as the single enabled row
+    pub weighted: bool,
+    /// Sum of weights in the pool (0 for single-model operation)
+    pub total_weight: i64,
+}
+
+impl ModelSelection {
+    /// Telemetry attributes describing this choice
+    pub fn attributes(&self) -> serde_json::Value {
+        serde_json::json!({
+            "agent_settings_id": self.settings.id,
+            "endpoint": self.settings.endpoint,
+            "model_archetype": self.settings.model_archetype,
+            "mode": if self.weighted { "weighted" } else { "single" },
+            "weight": self.settings.weight,
+            "total_weight": self.total_weight,
+        })
+    }
+}
+
+/// Pick the candidate whose cumulative weight range contains `roll` (0 <= roll < total weight)
+pub fn pick_weighted(candidates: &[AgentSettings], roll: i64) -> Option<&AgentSettings> {
+    let mut upper = 0i64;
+    for candidate in candidates.iter().filter(|c| c.weight > 0) {
+        upper += candidate.weight as i64;
+        if roll < upper {
+            return Some(candidate);
+        }
+    }
+    None
+}
+
+/// Choose the agent settings for a dispatch: weighted random when two or more
+/// rows are weighted, otherwise the enabled row (`None` if nothing is configured)
+pub fn select_for_dispatch(db: &Database) -> SqliteResult<Option<ModelSelection>> {
+    let pool = db.list_weighted_agent_settings()?;
+    if pool.len() >= 2 {
+        let total_weight: i64 = pool.iter().map(|s| s.weight as i64).sum();
+        let roll = rand::thread_rng().gen_range(0..total_weight);
+        if let Some(settings) = pick_weighted(&pool, roll) {
+            return Ok(Some(ModelSelection {
+                settings: settings.clone(),
+                weighted: true,
+                total_weight,
+            }));
+        }
+    }
+
+    Ok(db.get_active_agent_settings()?.map(|settings| ModelSelection {
+        settings,
+        weighted: false,
+        total_weight: 0,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn weighted(id: i64, weight: i32) -> AgentSettings {
+        AgentSettings {
+            id,
+            weight,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_pick_weighted_ranges() {
+        let pool = vec![weighted(1, 3), weighted(2, 0), weighted(3, 1)];
+        let picks: Vec<i64> = (0..4).map(|roll| pick_weighted(&pool, roll).unwrap().id).collect();
+        assert_eq!(picks, vec![1, 1, 1, 3]);
+        assert!(pick_weighted(&pool, 4).is_none());
+    }
+
+    #[test]
+    fn test_pick_weighted_empty_pool() {
+        assert!(pick_weighted(&[], 0).is_none());
+        assert!(pick_weighted(&[weighted(1, 0)], 0).is_none());
+    }
+}
@@ -15,7 +15,7 @@ use crate::models::{AgentSettings, SessionScope};
 use crate::tools::{ToolContext, ToolDefinition, ToolRegistry};
 use dashmap::DashMap;
 use serde_json::json;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::sync::{oneshot, Semaphore};
 use tokio::time::{timeout, Duration};
@@ -27,6 +27,10 @@ static SUBAGENT_COUNTER: AtomicU64 = AtomicU64::new(1);
 pub struct SubAgentHandle {
     /// Cancel signal sender
     cancel_tx: Option<oneshot::Sender<()>>,
+    /// Channel that spawned the sub-agent
+    channel_id: i64,
+    /// Set once the sub-agent holds its concurrency permits (false while queued)
+    started: Arc<AtomicBool>,
 }
 
 impl SubAgentHandle {
@@ -38,6 +42,13 @@ impl SubAgentHandle {
     }
 }
 
+/// Running and queued sub-agents, server-wide or for one channel
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize)]
+pub struct SubAgentCounts {
+    pub running: usize,
+    pub queued: usize,
+}
+
 /// Manager for coordinating sub-agent execution
 pub struct SubAgentManager {
     db: Arc<Database>,
@@ -102,12 +113,45 @@ impl SubAgentManager {
             .clone()
     }
 
+    /// The concurrency caps this manager enforces
+    pub fn config(&self) -> &SubAgentConfig {
+        &self.config
+    }
+
+    /// Reject a spawn when its channel (or the server) is at its cap and the queue is full
+    fn check_capacity(&self, channel_id: i64) -> Result<(), String> {
+        let channel = self.counts(Some(channel_id));
+        let total = self.counts(None);
+        let channel_full = channel.running + channel.queued >= self.config.max_concurrent_per_channel;
+        let total_full = total.running + total.queued >= self.config.max_total_concurrent;
+        if !(channel_full || total_full) || total.queued < self.config.max_queued {
+            return Ok(());
+        }
+        let (scope, running, max) = if channel_full {
+            ("this channel", channel.running, self.config.max_concurrent_per_channel)
+        } else {
+            ("the server", total.running, self.config.max_total_concurrent)
+        };
+        Err(format!(
+            "Sub-agent limit reached: {} running in {} (max {}) and {} already queued (max {}). \
+             Wait for one to finish or cancel one before spawning more.",
+            running, scope, max, total.queued, self.config.max_queued
+        ))
+    }
+
     /// Spawn a new sub-agent
     ///
-    /// Returns the sub-agent ID immediately. The sub-agent will execute in the background.
+    /// Returns the sub-agent ID immediately. The sub-agent will execute in the background,
+    /// waiting for a slot first if its channel or the server is at the concurrency cap.
+    /// Fails when the cap is reached and the queue is full.
     pub async fn spawn(&self, mut context: SubAgentContext) -> Result<String, String> {
         let subagent_id = context.id.clone();
 
+        if let Err(e) = self.check_capacity(context.parent_channel_id) {
+            log::warn!("[SUBAGENT] Not spawning '{}': {}", subagent_id, e);
+            return Err(e);
+        }
+
         // Validate timeout
         if context.timeout_secs > self.config.max_timeout_secs {
             context.timeout_secs = self.config.max_timeout_secs;
@@ -136,12 +180,15 @@ impl SubAgentManager {
 
         // Create cancel channel
         let (cancel_tx, cancel_rx) = oneshot::channel();
+        let started = Arc::new(AtomicBool::new(false));
 
         // Store the handle
         self.active_agents.insert(
             subagent_id.clone(),
             SubAgentHandle {
                 cancel_tx: Some(cancel_tx),
+                channel_id: context.parent_channel_id,
+                started: started.clone(),
             },
         );
 
@@ -172,6 +219,7 @@ impl SubAgentManager {
                     return;
                 }
             };
+            started.store(true, Ordering::SeqCst);
 
             // Execute with timeout and cancel handling
             let execution = Self::execute_subagent(
@@ -362,8 +410,9 @@ impl SubAgentManager {
                 "[SUBAGENT] {} parent channel is safe mode — restricting to safe mode tools + sandboxed memory",
                 context.id
             );
-            tool_config = crate::tools::ToolConfig::safe_mode();
+            tool_config = tool_config.into_safe_mode();
         }
+        tool_context = tool_context.with_tool_registry(tool_registry.clone(), tool_config.clone());
 
         // Get available tools — filtered by safety level when in restricted mode
         let tools: Vec<ToolDefinition> = if parent_channel_safe_mode {
@@ -458,10 +507,24 @@ impl SubAgentManager {
                     tool_call.name
                 );
 
+                broadcaster.broadcast(GatewayEvent::subagent_progress(
+                    &context,
+                    "tool_call",
+                    &tool_call.name,
+                    None,
+                ));
+
                 let result = tool_registry
                     .execute(&tool_call.name, tool_call.arguments.clone(), &tool_context, Some(&tool_config))
                     .await;
 
+                broadcaster.broadcast(GatewayEvent::subagent_progress(
+                    &context,
+                    "tool_result",
+                    &tool_call.name,
+                    Some(result.success),
+                ));
+
                 // Check if task_fully_completed was called - stop the loop
                 if let Some(ref metadata) = result.metadata {
                     if metadata.get("task_fully_completed").and_then(|v| v.as_bool()).unwrap_or(false) {
@@ -747,14 +810,29 @@ impl SubAgentManager {
         self.active_agents.len()
     }
 
-    /// Get count of active sub-agents for a specific channel
-    /// Note: This returns total active count as we don't track channel per handle.
-    /// The per-channel semaphore enforces the actual limit.
-    pub fn active_count_for_channel(&self, _channel_id: i64) -> usize {
-        // We use the total count as an approximation since we don't
-        // store channel_id in the handle. The per-channel semaphore
-        // handles the actual concurrency limiting.
-        self.active_agents.len()
+    /// Get count of active sub-agents (running or queued) for a specific channel
+    pub fn active_count_for_channel(&self, channel_id: i64) -> usize {
+        self.active_agents
+            .iter()
+            .filter(|entry| entry.value().channel_id == channel_id)
+            .count()
+    }
+
+    /// Running and queued sub-agents for one channel, or server-wide with `None`
+    pub fn counts(&self, channel_id: Option<i64>) -> SubAgentCounts {
+        let mut counts = SubAgentCounts::default();
+        for entry in self.active_agents.iter() {
+            let handle = entry.value();
+            if channel_id.is_some_and(|id| id != handle.channel_id) {
+                continue;
+            }
+            if handle.started.load(Ordering::SeqCst) {
+                counts.running += 1;
+            } else {
+                counts.queued += 1;
+            }
+        }
+        counts
     }
 }
 
@@ -801,4 +879,84 @@ impl GatewayEvent {
             }),
         )
     }
+
+    /// Sub-agent progress (tool call started / finished), tagged with the sub-agent
+    /// and routed to the parent channel and session so consumers can show live activity
+    pub fn subagent_progress(
+        context: &SubAgentContext,
+        phase: &str,
+        tool_name: &str,
+        success: Option<bool>,
+    ) -> Self {
+        Self::new(
+            "subagent.progress",
+            json!({
+                "channel_id": context.parent_channel_id,
+                "session_id": context.parent_session_id,
+                "subagent_id": context.id,
+                "label": context.label,
+                "phase": phase,
+                "tool_name": tool_name,
+                "success": success,
+                "timestamp": chrono::Utc::now().to_rfc3339()
+            }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manager(config: SubAgentConfig) -> SubAgentManager {
+        let db = Arc::new(Database::new(":memory:").expect("in-memory db"));
+        SubAgentManager::new_with_config(
+            db,
+            Arc::new(EventBroadcaster::new()),
+            Arc::new(ToolRegistry::new()),
+            config,
+            None,
+        )
+    }
+
+    fn track(manager: &SubAgentManager, id: &str, channel_id: i64, started: bool) {
+        manager.active_agents.insert(
+            id.to_string(),
+            SubAgentHandle {
+                cancel_tx: None,
+                channel_id,
+                started: Arc::new(AtomicBool::new(started)),
+            },
+        );
+    }
+
+    #[tokio::test]
+    async fn test_spawns_queue_then_reject_at_cap() {
+        let manager = manager(SubAgentConfig {
+            max_concurrent_per_channel: 2,
+            max_total_concurrent: 3,
+            max_queued: 1,
+            ..SubAgentConfig::default()
+        });
+
+        track(&manager, "a", 1, true);
+        assert!(manager.check_capacity(1).is_ok());
+        track(&manager, "b", 1, true);
+        assert!(manager.check_capacity(2).is_ok());
+        track(&manager, "d", 2, true);
+
+        // Channel 1 (and the server) is full: the next one may wait in the queue
+        assert!(manager.check_capacity(1).is_ok());
+        track(&manager, "c", 1, false);
+        let err = manager.check_capacity(1).unwrap_err();
+        assert!(err.contains("this channel (max 2)"), "{}", err);
+
+        // Channel 2 is under its own cap but the server has no room left
+        let err = manager.check_capacity(2).unwrap_err();
+        assert!(err.contains("the server (max 3)"), "{}", err);
+
+        assert_eq!(manager.counts(Some(1)), SubAgentCounts { running: 2, queued: 1 });
+        assert_eq!(manager.counts(None), SubAgentCounts { running: 3, queued: 1 });
+        assert_eq!(manager.active_count_for_channel(2), 1);
+    }
 }
@@ -918,22 +918,38 @@ impl SubAgentContext {
 
 /// Configuration for the sub-agent system
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SubAgentConfig {
     /// Maximum concurrent sub-agents per channel
     pub max_concurrent_per_channel: usize,
     /// Maximum total concurrent sub-agents system-wide
     pub max_total_concurrent: usize,
+    /// Sub-agents that may wait for a free slot; spawns beyond this are rejected
+    pub max_queued: usize,
     /// Default timeout in seconds for sub-agents
     pub default_timeout_secs: u64,
     /// Maximum timeout allowed (cannot exceed this)
     pub max_timeout_secs: u64,
 }
 
+impl SubAgentConfig {
+    /// Defaults with the concurrency caps taken from the environment
+    pub fn from_env() -> Self {
+        Self {
+            max_concurrent_per_channel: crate::config::subagent_max_per_channel(),
+            max_total_concurrent: crate::config::subagent_max_concurrent(),
+            max_queued: crate::config::subagent_max_queued(),
+            ..Self::default()
+        }
+    }
+}
+
 impl Default for SubAgentConfig {
     fn default() -> Self {
         Self {
-            max_concurrent_per_channel: 3,
-            max_total_concurrent: 10,
+            max_concurrent_per_channel: crate::config::defaults::SUBAGENT_MAX_PER_CHANNEL,
+            max_total_concurrent: crate::config::defaults::SUBAGENT_MAX_CONCURRENT,
+            max_queued: crate::config::defaults::SUBAGENT_MAX_QUEUED,
             default_timeout_secs: 300,
             max_timeout_secs: 3600,
         }
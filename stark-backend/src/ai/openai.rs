@@ -1,6 +1,6 @@
-use crate::ai::streaming::{StreamEvent, StreamSender};
-use crate::ai::types::{AiError, AiResponse, ToolCall};
-use crate::ai::Message;
+use crate::ai::streaming::{create_default_stream_channel, StreamEvent, StreamReceiver, StreamSender};
+use crate::ai::types::{AiError, AiResponse, TokenUsage, ToolCall};
+use crate::ai::{merge_consecutive_roles, Message};
 use crate::gateway::events::EventBroadcaster;
 use crate::gateway::protocol::GatewayEvent;
 use crate::tools::ToolDefinition;
@@ -10,8 +10,11 @@ use futures_util::StreamExt;
 use reqwest::{header, Client};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
 
 #[derive(Clone)]
 pub struct OpenAIClient {
@@ -25,6 +28,69 @@ pub struct OpenAIClient {
     broadcaster: Option<Arc<EventBroadcaster>>,
     /// Channel ID for events (set when broadcasting)
     channel_id: Option<i64>,
+    /// Retry behaviour for rate limits and transient upstream errors
+    retry: RetryPolicy,
+}
+
+/// Longest `Retry-After` we'll honor; longer waits are clamped so a dispatch doesn't stall
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Retry settings for transient API failures (429, 5xx, network errors)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt (0 = fail on the first error)
+    pub max_retries: u32,
+    /// Backoff base; retry N waits base * 2^(N-1) plus up to 50% jitter
+    pub base_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts in total: the first try plus two retries
+    fn default() -> Self {
+        Self {
+            max_retries: 2,
+            base_delay: Duration::from_secs(2),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// No retries at all
+    pub fn none() -> Self {
+        Self { max_retries: 0, base_delay: Duration::ZERO }
+    }
+
+    /// Whether an HTTP status is worth retrying (rate limit or transient server error)
+    pub fn is_retryable_status(status_code: u16) -> bool {
+        matches!(status_code, 429 | 500 | 502 | 503 | 504)
+    }
+
+    /// Delay before retry `attempt` (1-based). A server-provided `Retry-After` wins
+    /// over exponential backoff.
+    fn delay_for(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        if let Some(wait) = retry_after {
+            return wait.min(MAX_RETRY_AFTER);
+        }
+        let backoff = self
+            .base_delay
+            .saturating_mul(1u32 << attempt.saturating_sub(1).min(16));
+        let jitter_ms = (backoff.as_millis() / 2) as u64;
+        if jitter_ms == 0 {
+            return backoff;
+        }
+        backoff + Duration::from_millis(rand::random::<u64>() % (jitter_ms + 1))
+    }
+}
+
+/// Parse a `Retry-After` header: delta-seconds or an HTTP date
+fn parse_retry_after(headers: &header::HeaderMap) -> Option<Duration> {
+    let value = headers.get(header::RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let secs = (date.with_timezone(&chrono::Utc) - chrono::Utc::now()).num_seconds();
+    Some(Duration::from_secs(secs.max(0) as u64))
 }
 
 #[derive(Debug, Serialize)]
@@ -51,12 +117,14 @@ struct OpenAIStreamChunk {
 
 #[derive(Debug, Deserialize)]
 struct OpenAIStreamChoice {
+    #[serde(default)]
     delta: OpenAIStreamDelta,
     finish_reason: Option<String>,
+    #[serde(default)]
     index: usize,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 struct OpenAIStreamDelta {
     content: Option<String>,
     tool_calls: Option<Vec<OpenAIStreamToolCall>>,
@@ -125,6 +193,28 @@ pub struct OpenAIFunctionCall {
 #[derive(Debug, Deserialize)]
 struct OpenAICompletionResponse {
     choices: Vec<OpenAIChoice>,
+    #[serde(default)]
+    usage: Option<OpenAIUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIUsage {
+    #[serde(default)]
+    prompt_tokens: u32,
+    #[serde(default)]
+    completion_tokens: u32,
+    #[serde(default)]
+    total_tokens: Option<u32>,
+}
+
+impl From<OpenAIUsage> for TokenUsage {
+    fn from(u: OpenAIUsage) -> Self {
+        let mut usage = TokenUsage::new(u.prompt_tokens, u.completion_tokens);
+        if let Some(total) = u.total_tokens {
+            usage.total_tokens = total;
+        }
+        usage
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -241,6 +331,7 @@ impl OpenAIClient {
             x402_client,
             broadcaster: None,
             channel_id: None,
+            retry: RetryPolicy::default(),
         })
     }
 
@@ -321,6 +412,7 @@ impl OpenAIClient {
             x402_client,
             broadcaster: None,
             channel_id: None,
+            retry: RetryPolicy::default(),
         })
     }
 
@@ -331,6 +423,12 @@ impl OpenAIClient {
         self
     }
 
+    /// Override the retry policy (e.g. `RetryPolicy::none()` or a zero delay in tests)
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
     /// Emit a retry event if broadcaster is configured
     fn emit_retry_event(&self, attempt: u32, max_attempts: u32, wait_seconds: u64, error: &str) {
         if let (Some(broadcaster), Some(channel_id)) = (&self.broadcaster, self.channel_id) {
@@ -351,11 +449,10 @@ impl OpenAIClient {
         Ok(response.content)
     }
 
-    /// Generate text and return payment info if x402 payment was made
-    pub async fn generate_text_with_payment_info(&self, messages: Vec<Message>) -> Result<(String, Option<X402PaymentInfo>), String> {
-        let response = self.generate_with_tools_internal(messages, vec![], vec![]).await
-            .map_err(|e| e.to_string())?;
-        Ok((response.content, response.x402_payment))
+    /// Generate text, keeping the x402 payment info (if a payment was made) and token usage
+    pub async fn generate_text_response(&self, messages: Vec<Message>) -> Result<AiResponse, String> {
+        self.generate_with_tools_internal(messages, vec![], vec![]).await
+            .map_err(|e| e.to_string())
     }
 
     pub async fn generate_with_tools(
@@ -373,8 +470,8 @@ impl OpenAIClient {
         tool_history: Vec<OpenAIMessage>,
         tools: Vec<ToolDefinition>,
     ) -> Result<AiResponse, AiError> {
-        // Convert messages to OpenAI format
-        let mut api_messages: Vec<OpenAIMessage> = messages
+        // Convert messages to OpenAI format, merging same-role runs so turns alternate
+        let mut api_messages: Vec<OpenAIMessage> = merge_consecutive_roles(messages)
             .into_iter()
             .map(|m| OpenAIMessage {
                 role: m.role.to_string(),
@@ -449,33 +546,29 @@ impl OpenAIClient {
             serde_json::to_string_pretty(&request).unwrap_or_default()
         );
 
-        // Retry configuration for transient errors
-        const MAX_RETRIES: u32 = 3;
-        const BASE_DELAY_MS: u64 = 2000; // 2 seconds base delay
-
+        let max_retries = self.retry.max_retries;
         let mut last_error: Option<(String, Option<u16>)> = None;
+        let mut retry_after: Option<Duration> = None;
         let mut x402_payment: Option<X402PaymentInfo> = None;
         let mut response_text: Option<String> = None;
 
-        for attempt in 0..=MAX_RETRIES {
+        for attempt in 0..=max_retries {
             if attempt > 0 {
-                // Exponential backoff: 2s, 4s, 8s
-                let delay_ms = BASE_DELAY_MS * (1 << (attempt - 1));
-                let wait_secs = delay_ms / 1000;
+                let delay = self.retry.delay_for(attempt, retry_after.take());
                 log::warn!(
                     "[OPENAI] Retry attempt {}/{} after {}ms delay",
                     attempt,
-                    MAX_RETRIES,
-                    delay_ms
+                    max_retries,
+                    delay.as_millis()
                 );
                 // Emit retry event to frontend
                 self.emit_retry_event(
                     attempt,
-                    MAX_RETRIES,
-                    wait_secs,
+                    max_retries,
+                    delay.as_secs(),
                     last_error.as_ref().map(|(m, _)| m.as_str()).unwrap_or("Unknown error"),
                 );
-                tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+                tokio::time::sleep(delay).await;
             }
 
             // Use x402 client if available, otherwise use regular client
@@ -502,7 +595,7 @@ impl OpenAIClient {
                 Err(e) => {
                     // Network errors are retryable
                     last_error = Some((e.clone(), None));
-                    if attempt < MAX_RETRIES {
+                    if attempt < max_retries {
                         log::warn!("[OPENAI] Request failed (attempt {}): {}, will retry", attempt + 1, e);
                         continue;
                     }
@@ -513,10 +606,11 @@ impl OpenAIClient {
             let status = response.status();
             let status_code = status.as_u16();
 
-            // Check for retryable status codes: 429 (rate limit), 502, 503, 504 (gateway errors)
-            let is_retryable = matches!(status_code, 429 | 502 | 503 | 504);
+            // Rate limits and transient server errors are retried; other 4xx fail fast
+            let is_retryable = RetryPolicy::is_retryable_status(status_code);
 
             if !status.is_success() {
+                let server_retry_after = parse_retry_after(response.headers());
                 let error_text = response.text().await.unwrap_or_default();
 
                 // Check if this is a transient 402 error (payment settlement network failure)
@@ -531,14 +625,15 @@ impl OpenAIClient {
                     error_text.contains("network error")
                 );
 
-                if (is_retryable || is_transient_402) && attempt < MAX_RETRIES {
+                if (is_retryable || is_transient_402) && attempt < max_retries {
                     log::warn!(
                         "[OPENAI] Received retryable status {} (attempt {}), will retry: {}",
                         status,
                         attempt + 1,
-                        if error_text.len() > 200 { &error_text[..200] } else { &error_text }
+                        error_text.chars().take(200).collect::<String>()
                     );
                     last_error = Some((format!("HTTP {}: {}", status, error_text), Some(status_code)));
+                    retry_after = server_retry_after;
                     continue;
                 }
 
@@ -554,6 +649,7 @@ impl OpenAIClient {
                     if is_html {
                         // Friendly messages for common gateway errors
                         match status_code {
+                            500 => "OpenAI API returned 500 Internal Server Error (provider temporarily unavailable)".to_string(),
                             502 => "OpenAI API returned 502 Bad Gateway (provider temporarily unavailable)".to_string(),
                             503 => "OpenAI API returned 503 Service Unavailable (provider temporarily unavailable)".to_string(),
                             504 => "OpenAI API returned 504 Gateway Timeout (provider did not respond in time)".to_string(),
@@ -640,10 +736,13 @@ impl OpenAIClient {
             tool_calls,
             stop_reason: if is_tool_use {
                 Some("tool_use".to_string())
+            } else if finish_reason.as_deref() == Some("length") {
+                Some("max_tokens".to_string())
             } else {
                 Some("end_turn".to_string())
             },
             x402_payment,
+            usage: response_data.usage.map(TokenUsage::from),
         })
     }
 
@@ -698,8 +797,8 @@ impl OpenAIClient {
         tools: Vec<ToolDefinition>,
         stream_sender: StreamSender,
     ) -> Result<AiResponse, String> {
-        // Convert messages to OpenAI format
-        let mut api_messages: Vec<OpenAIMessage> = messages
+        // Convert messages to OpenAI format, merging same-role runs so turns alternate
+        let mut api_messages: Vec<OpenAIMessage> = merge_consecutive_roles(messages)
             .into_iter()
             .map(|m| OpenAIMessage {
                 role: m.role.to_string(),
@@ -768,32 +867,29 @@ impl OpenAIClient {
             openai_tools.as_ref().map(|t| t.len()).unwrap_or(0),
         );
 
-        // Retry configuration for transient errors
-        const MAX_RETRIES: u32 = 3;
-        const BASE_DELAY_MS: u64 = 2000;
-
+        let max_retries = self.retry.max_retries;
         let mut last_error: Option<String> = None;
+        let mut retry_after: Option<Duration> = None;
         let mut response_opt: Option<reqwest::Response> = None;
 
         // Note: x402 streaming not yet supported, fall back to regular client
-        for attempt in 0..=MAX_RETRIES {
+        for attempt in 0..=max_retries {
             if attempt > 0 {
-                let delay_ms = BASE_DELAY_MS * (1 << (attempt - 1));
-                let wait_secs = delay_ms / 1000;
+                let delay = self.retry.delay_for(attempt, retry_after.take());
                 log::warn!(
                     "[OPENAI] Streaming retry attempt {}/{} after {}ms delay",
                     attempt,
-                    MAX_RETRIES,
-                    delay_ms
+                    max_retries,
+                    delay.as_millis()
                 );
                 // Emit retry event to frontend
                 self.emit_retry_event(
                     attempt,
-                    MAX_RETRIES,
-                    wait_secs,
+                    max_retries,
+                    delay.as_secs(),
                     last_error.as_deref().unwrap_or("Unknown error"),
                 );
-                tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+                tokio::time::sleep(delay).await;
             }
 
             let request_result = self.client
@@ -807,12 +903,12 @@ impl OpenAIClient {
                 Ok(r) => r,
                 Err(e) => {
                     last_error = Some(format!("OpenAI API streaming request failed: {}", e));
-                    if attempt < MAX_RETRIES {
+                    if attempt < max_retries {
                         log::warn!("[OPENAI] Streaming request failed (attempt {}): {}, will retry", attempt + 1, e);
                         continue;
                     }
                     let _ = stream_sender.send(StreamEvent::Error {
-                        message: format!("Request failed after {} retries: {}", MAX_RETRIES, e),
+                        message: format!("Request failed after {} retries: {}", max_retries, e),
                         code: None,
                     }).await;
                     return Err(last_error.unwrap());
@@ -821,9 +917,10 @@ impl OpenAIClient {
 
             let status = response.status();
             let status_code = status.as_u16();
-            let is_retryable = matches!(status_code, 429 | 502 | 503 | 504);
+            let is_retryable = RetryPolicy::is_retryable_status(status_code);
 
             if !status.is_success() {
+                let server_retry_after = parse_retry_after(response.headers());
                 let error_text = response.text().await.unwrap_or_default();
 
                 // Check if this is a transient 402 error (payment settlement network failure)
@@ -837,13 +934,14 @@ impl OpenAIClient {
                     error_text.contains("network error")
                 );
 
-                if (is_retryable || is_transient_402) && attempt < MAX_RETRIES {
+                if (is_retryable || is_transient_402) && attempt < max_retries {
                     log::warn!(
                         "[OPENAI] Streaming received retryable status {} (attempt {}), will retry",
                         status,
                         attempt + 1
                     );
                     last_error = Some(format!("HTTP {}: {}", status, error_text));
+                    retry_after = server_retry_after;
                     continue;
                 }
 
@@ -862,142 +960,452 @@ impl OpenAIClient {
             last_error.unwrap_or_else(|| "Max retries exceeded".to_string())
         })?;
 
-        // Process SSE stream
+        // Process SSE stream. Network chunks can split SSE lines (and multi-byte
+        // characters), so the accumulator buffers partial lines between chunks.
         let mut stream = response.bytes_stream();
-        let mut content = String::new();
-        let mut tool_calls: Vec<ToolCall> = Vec::new();
-        let mut partial_tool_calls: std::collections::HashMap<usize, (String, String, String)> =
-            std::collections::HashMap::new(); // index -> (id, name, arguments)
-        let mut finish_reason: Option<String> = None;
-        let mut usage: Option<(u32, u32)> = None;
+        let mut accumulator = StreamAccumulator::default();
 
         while let Some(chunk_result) = stream.next().await {
             let chunk = chunk_result
                 .map_err(|e| format!("Stream read error: {}", e))?;
 
-            let chunk_str = String::from_utf8_lossy(&chunk);
+            for event in accumulator.push_bytes(&chunk) {
+                let _ = stream_sender.send(event).await;
+            }
+        }
 
-            // Parse SSE format (data: {...}\n\n)
-            for line in chunk_str.lines() {
-                let line = line.trim();
-                if line.is_empty() || line == "data: [DONE]" {
-                    continue;
-                }
+        let (events, response) = accumulator.finish();
+        for event in events {
+            let _ = stream_sender.send(event).await;
+        }
 
-                if let Some(json_str) = line.strip_prefix("data: ") {
-                    if let Ok(chunk_data) = serde_json::from_str::<OpenAIStreamChunk>(json_str) {
-                        for choice in chunk_data.choices {
-                            // Handle content delta
-                            if let Some(delta_content) = choice.delta.content {
-                                content.push_str(&delta_content);
-                                let _ = stream_sender.send(StreamEvent::ContentDelta {
-                                    content: delta_content,
-                                    index: choice.index,
-                                }).await;
-                            }
-
-                            // Handle tool call deltas
-                            if let Some(tool_call_deltas) = choice.delta.tool_calls {
-                                for tc_delta in tool_call_deltas {
-                                    let idx = tc_delta.index;
-                                    let entry = partial_tool_calls.entry(idx).or_insert_with(|| {
-                                        (String::new(), String::new(), String::new())
-                                    });
-
-                                    // Update ID if present
-                                    if let Some(id) = tc_delta.id {
-                                        entry.0 = id.clone();
-                                        if let Some(ref func) = tc_delta.function {
-                                            if let Some(ref name) = func.name {
-                                                entry.1 = name.clone();
-                                                let _ = stream_sender.send(StreamEvent::ToolCallStart {
-                                                    id: entry.0.clone(),
-                                                    name: name.clone(),
-                                                    index: idx,
-                                                }).await;
-                                            }
-                                        }
-                                    }
+        Ok(response)
+    }
 
-                                    // Update function details
-                                    if let Some(ref func) = tc_delta.function {
-                                        if let Some(ref name) = func.name {
-                                            if entry.1.is_empty() {
-                                                entry.1 = name.clone();
-                                            }
-                                        }
-                                        if let Some(ref args) = func.arguments {
-                                            entry.2.push_str(args);
-                                            let _ = stream_sender.send(StreamEvent::ToolCallDelta {
-                                                id: entry.0.clone(),
-                                                arguments_delta: args.clone(),
-                                                index: idx,
-                                            }).await;
-                                        }
-                                    }
-                                }
-                            }
+    /// Generate a response as a stream of content deltas
+    ///
+    /// Returns a receiver yielding text as it is generated, plus a handle that
+    /// resolves to the final `AiResponse` (with fully assembled tool calls) once
+    /// the stream terminates. Dropping the receiver doesn't cancel the request, and
+    /// the request never waits on it: a caller may await the handle first.
+    pub fn generate_with_tools_stream(
+        &self,
+        messages: Vec<Message>,
+        tool_history: Vec<OpenAIMessage>,
+        tools: Vec<ToolDefinition>,
+    ) -> (mpsc::Receiver<String>, JoinHandle<Result<AiResponse, String>>) {
+        let (content_tx, content_rx) = mpsc::channel(CONTENT_STREAM_BUFFER);
+        let (event_tx, event_rx) = create_default_stream_channel();
+        let client = self.clone();
+
+        let handle = tokio::spawn(async move {
+            let (result, _) = tokio::join!(
+                client.generate_with_tools_streaming(messages, tool_history, tools, event_tx),
+                forward_content_deltas(event_rx, content_tx)
+            );
+            result
+        });
 
-                            // Handle finish reason
-                            if let Some(reason) = choice.finish_reason {
-                                finish_reason = Some(reason);
-                            }
-                        }
+        (content_rx, handle)
+    }
+}
+
+/// Buffer size for `generate_with_tools_stream` content deltas
+const CONTENT_STREAM_BUFFER: usize = 64;
+
+/// Forward content deltas without ever waiting on the receiver. While it's full,
+/// deltas are merged and sent as one once there's room; whatever is still unsent
+/// at the end is dropped (the final `AiResponse` always has the whole text).
+async fn forward_content_deltas(mut event_rx: StreamReceiver, content_tx: mpsc::Sender<String>) {
+    let mut pending = String::new();
+    while let Some(event) = event_rx.recv().await {
+        if let StreamEvent::ContentDelta { content, .. } = event {
+            pending.push_str(&content);
+            if let Err(mpsc::error::TrySendError::Full(unsent)) = content_tx.try_send(std::mem::take(&mut pending)) {
+                pending = unsent;
+            }
+        }
+    }
+    if !pending.is_empty() {
+        let _ = content_tx.try_send(pending);
+    }
+}
+
+/// A tool call being assembled from streamed deltas
+#[derive(Debug, Default)]
+struct PartialToolCall {
+    id: String,
+    name: String,
+    arguments: String,
+    /// ToolCallStart has been emitted
+    started: bool,
+}
+
+/// Incremental parser for OpenAI-style SSE completion streams
+#[derive(Debug, Default)]
+struct StreamAccumulator {
+    /// Bytes after the last complete line
+    buffer: Vec<u8>,
+    content: String,
+    /// Keyed by the provider's tool call index, so calls come out in order
+    tool_calls: BTreeMap<usize, PartialToolCall>,
+    finish_reason: Option<String>,
+    usage: Option<(u32, u32)>,
+}
+
+impl StreamAccumulator {
+    /// Feed raw bytes from the response; returns events for every complete line
+    fn push_bytes(&mut self, bytes: &[u8]) -> Vec<StreamEvent> {
+        self.buffer.extend_from_slice(bytes);
+        let mut events = Vec::new();
+        while let Some(pos) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=pos).collect();
+            self.process_line(&String::from_utf8_lossy(&line), &mut events);
+        }
+        events
+    }
+
+    fn process_line(&mut self, line: &str, events: &mut Vec<StreamEvent>) {
+        // Only `data:` lines carry payloads; comments and `event:` lines are ignored
+        let Some(data) = line.trim().strip_prefix("data:") else {
+            return;
+        };
+        let data = data.trim_start();
+        if data.is_empty() || data == "[DONE]" {
+            return;
+        }
+
+        let chunk: OpenAIStreamChunk = match serde_json::from_str(data) {
+            Ok(c) => c,
+            Err(e) => {
+                log::warn!("[OPENAI] Skipping unparseable stream chunk: {}", e);
+                return;
+            }
+        };
 
-                        // Capture usage if present
-                        if let Some(u) = chunk_data.usage {
-                            usage = Some((
-                                u.prompt_tokens.unwrap_or(0),
-                                u.completion_tokens.unwrap_or(0),
-                            ));
+        for choice in chunk.choices {
+            if let Some(delta_content) = choice.delta.content {
+                if !delta_content.is_empty() {
+                    self.content.push_str(&delta_content);
+                    events.push(StreamEvent::ContentDelta {
+                        content: delta_content,
+                        index: choice.index,
+                    });
+                }
+            }
+
+            for tc_delta in choice.delta.tool_calls.unwrap_or_default() {
+                let idx = tc_delta.index;
+                let entry = self.tool_calls.entry(idx).or_default();
+
+                if let Some(id) = tc_delta.id.filter(|id| !id.is_empty()) {
+                    entry.id = id;
+                }
+                if let Some(func) = tc_delta.function {
+                    if let Some(name) = func.name.filter(|n| !n.is_empty()) {
+                        if entry.name.is_empty() {
+                            entry.name = name;
                         }
                     }
+                    if !entry.started && !entry.id.is_empty() && !entry.name.is_empty() {
+                        entry.started = true;
+                        events.push(StreamEvent::ToolCallStart {
+                            id: entry.id.clone(),
+                            name: entry.name.clone(),
+                            index: idx,
+                        });
+                    }
+                    if let Some(args) = func.arguments.filter(|a| !a.is_empty()) {
+                        entry.arguments.push_str(&args);
+                        events.push(StreamEvent::ToolCallDelta {
+                            id: entry.id.clone(),
+                            arguments_delta: args,
+                            index: idx,
+                        });
+                    }
                 }
             }
+
+            if let Some(reason) = choice.finish_reason {
+                self.finish_reason = Some(reason);
+            }
         }
 
-        // Convert partial tool calls to complete ones
-        for (idx, (id, name, args)) in partial_tool_calls {
-            if !id.is_empty() && !name.is_empty() {
-                let arguments: Value = serde_json::from_str(&args).unwrap_or(json!({}));
+        if let Some(u) = chunk.usage {
+            self.usage = Some((u.prompt_tokens.unwrap_or(0), u.completion_tokens.unwrap_or(0)));
+        }
+    }
 
-                let _ = stream_sender.send(StreamEvent::ToolCallComplete {
-                    id: id.clone(),
-                    name: name.clone(),
-                    arguments: arguments.clone(),
-                    index: idx,
-                }).await;
+    /// Flush any trailing line and assemble the final response.
+    /// Tool call arguments are only parsed here, once every fragment has arrived.
+    fn finish(mut self) -> (Vec<StreamEvent>, AiResponse) {
+        let mut events = Vec::new();
+        if !self.buffer.is_empty() {
+            let line = String::from_utf8_lossy(&std::mem::take(&mut self.buffer)).into_owned();
+            self.process_line(&line, &mut events);
+        }
 
-                tool_calls.push(ToolCall {
-                    id,
-                    name,
-                    arguments,
-                });
+        let mut tool_calls = Vec::new();
+        for (idx, call) in std::mem::take(&mut self.tool_calls) {
+            if call.id.is_empty() || call.name.is_empty() {
+                log::warn!("[OPENAI] Dropping streamed tool call {} without an id or name", idx);
+                continue;
             }
+
+            let arguments: Value = if call.arguments.trim().is_empty() {
+                json!({})
+            } else {
+                serde_json::from_str(&call.arguments).unwrap_or_else(|e| {
+                    log::warn!(
+                        "[OPENAI] Streamed arguments for tool '{}' are not valid JSON ({}), using {{}}",
+                        call.name, e
+                    );
+                    json!({})
+                })
+            };
+
+            events.push(StreamEvent::ToolCallComplete {
+                id: call.id.clone(),
+                name: call.name.clone(),
+                arguments: arguments.clone(),
+                index: idx,
+            });
+            tool_calls.push(ToolCall {
+                id: call.id,
+                name: call.name,
+                arguments,
+            });
         }
 
-        // Send done event
-        let _ = stream_sender.send(StreamEvent::Done {
-            stop_reason: finish_reason.clone(),
-            usage: usage.map(|(input, output)| crate::ai::streaming::StreamUsage {
+        events.push(StreamEvent::Done {
+            stop_reason: self.finish_reason.clone(),
+            usage: self.usage.map(|(input, output)| crate::ai::streaming::StreamUsage {
                 input_tokens: input,
                 output_tokens: output,
                 cache_creation_input_tokens: None,
                 cache_read_input_tokens: None,
             }),
-        }).await;
+        });
 
-        let is_tool_use = finish_reason.as_deref() == Some("tool_calls") || !tool_calls.is_empty();
+        let is_tool_use = self.finish_reason.as_deref() == Some("tool_calls") || !tool_calls.is_empty();
 
-        Ok(AiResponse {
-            content,
+        let response = AiResponse {
+            content: self.content,
             tool_calls,
             stop_reason: if is_tool_use {
                 Some("tool_use".to_string())
+            } else if self.finish_reason.as_deref() == Some("length") {
+                Some("max_tokens".to_string())
             } else {
                 Some("end_turn".to_string())
             },
             x402_payment: None, // Streaming doesn't support x402 yet
-        })
+            usage: self.usage.map(|(input, output)| TokenUsage::new(input, output)),
+        };
+
+        (events, response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_content_deltas_never_wait_on_the_receiver() {
+        let (event_tx, event_rx) = create_default_stream_channel();
+        let (content_tx, mut content_rx) = mpsc::channel(2);
+        let forward = tokio::spawn(forward_content_deltas(event_rx, content_tx));
+
+        // Nobody reads while 100 deltas go through a 2-slot channel
+        for i in 0..100 {
+            let delta = StreamEvent::ContentDelta { content: format!("{} ", i), index: 0 };
+            event_tx.send(delta).await.unwrap();
+        }
+        drop(event_tx);
+        tokio::time::timeout(Duration::from_secs(5), forward).await.expect("forwarding blocked").unwrap();
+
+        // The first deltas got through; the rest waited for room that never came
+        assert_eq!(content_rx.recv().await.as_deref(), Some("0 "));
+        assert_eq!(content_rx.recv().await.as_deref(), Some("1 "));
+        assert_eq!(content_rx.recv().await, None);
+    }
+
+    #[test]
+    fn test_retryable_statuses() {
+        for code in [429, 500, 502, 503, 504] {
+            assert!(RetryPolicy::is_retryable_status(code), "{} should retry", code);
+        }
+        for code in [400, 401, 403, 404, 422] {
+            assert!(!RetryPolicy::is_retryable_status(code), "{} should fail fast", code);
+        }
+    }
+
+    #[test]
+    fn test_retry_delay_backoff_and_retry_after() {
+        let policy = RetryPolicy { max_retries: 3, base_delay: Duration::from_millis(1000) };
+        for attempt in 1..=3u32 {
+            let base = Duration::from_millis(1000 * (1 << (attempt - 1)));
+            let delay = policy.delay_for(attempt, None);
+            assert!(delay >= base && delay <= base + base / 2, "attempt {}: {:?}", attempt, delay);
+        }
+
+        // Retry-After overrides backoff but is clamped
+        assert_eq!(policy.delay_for(1, Some(Duration::from_secs(7))), Duration::from_secs(7));
+        assert_eq!(policy.delay_for(1, Some(Duration::from_secs(3600))), MAX_RETRY_AFTER);
+
+        // Zero base delay means no waiting at all (for tests)
+        let instant = RetryPolicy { max_retries: 3, base_delay: Duration::ZERO };
+        assert_eq!(instant.delay_for(3, None), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let mut headers = header::HeaderMap::new();
+        assert_eq!(parse_retry_after(&headers), None);
+
+        headers.insert(header::RETRY_AFTER, header::HeaderValue::from_static("12"));
+        assert_eq!(parse_retry_after(&headers), Some(Duration::from_secs(12)));
+
+        // A date in the past means "retry now"
+        headers.insert(
+            header::RETRY_AFTER,
+            header::HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(parse_retry_after(&headers), Some(Duration::ZERO));
+
+        headers.insert(header::RETRY_AFTER, header::HeaderValue::from_static("soon"));
+        assert_eq!(parse_retry_after(&headers), None);
+    }
+
+    #[tokio::test]
+    async fn test_non_retryable_status_fails_fast() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let hits = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let server_hits = hits.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                server_hits.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                let mut buf = [0u8; 8192];
+                let _ = socket.read(&mut buf).await;
+                let body = r#"{"error":{"message":"bad key"}}"#;
+                let response = format!(
+                    "HTTP/1.1 401 Unauthorized\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        let client = OpenAIClient::new("key", Some(&format!("http://{}/v1/chat/completions", addr)), Some("m"))
+            .unwrap()
+            .with_retry_policy(RetryPolicy { max_retries: 3, base_delay: Duration::ZERO });
+        let err = client.generate_text(vec![]).await.unwrap_err();
+
+        assert!(err.contains("bad key"), "{}", err);
+        assert_eq!(hits.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    fn sse(payload: Value) -> String {
+        format!("data: {}\n\n", payload)
+    }
+
+    #[test]
+    fn test_tool_call_arguments_split_across_chunks() {
+        let stream = [
+            sse(json!({"choices": [{"index": 0, "delta": {"tool_calls": [
+                {"index": 1, "id": "call_b", "type": "function", "function": {"name": "second", "arguments": ""}}
+            ]}}]})),
+            sse(json!({"choices": [{"index": 0, "delta": {"tool_calls": [
+                {"index": 0, "id": "call_a", "type": "function", "function": {"name": "first", "arguments": "{\"pa"}}
+            ]}}]})),
+            sse(json!({"choices": [{"index": 0, "delta": {"tool_calls": [
+                {"index": 0, "function": {"arguments": "th\": \"a.txt\"}"}}
+            ]}}]})),
+            sse(json!({"choices": [{"index": 0, "delta": {}, "finish_reason": "tool_calls"}]})),
+            "data: [DONE]\n\n".to_string(),
+        ]
+        .concat();
+
+        // Feed in tiny slices so SSE lines and JSON strings are split mid-way
+        let mut acc = StreamAccumulator::default();
+        let mut events = Vec::new();
+        for piece in stream.as_bytes().chunks(7) {
+            events.extend(acc.push_bytes(piece));
+        }
+        let (final_events, response) = acc.finish();
+        events.extend(final_events);
+
+        assert_eq!(response.stop_reason.as_deref(), Some("tool_use"));
+        assert_eq!(response.tool_calls.len(), 2);
+        assert_eq!(response.tool_calls[0].name, "first");
+        assert_eq!(response.tool_calls[0].arguments, json!({"path": "a.txt"}));
+        assert_eq!(response.tool_calls[1].arguments, json!({}));
+        assert_eq!(
+            events.iter().filter(|e| matches!(e, StreamEvent::ToolCallStart { .. })).count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_length_finish_reason_marks_response_truncated() {
+        let stream = sse(json!({"choices": [{"index": 0, "delta": {"content": "Part one of"}, "finish_reason": "length"}]}));
+        let mut acc = StreamAccumulator::default();
+        acc.push_bytes(stream.as_bytes());
+        let (_, response) = acc.finish();
+
+        assert_eq!(response.stop_reason.as_deref(), Some("max_tokens"));
+        assert!(response.is_truncated());
+    }
+
+    #[test]
+    fn test_content_deltas_survive_split_utf8_and_missing_trailing_newline() {
+        let stream = format!(
+            "{}{}data:{}",
+            sse(json!({"choices": [{"index": 0, "delta": {"content": "héllo "}}]})),
+            ": keep-alive\n\n",
+            json!({"choices": [{"index": 0, "delta": {"content": "wörld"}, "finish_reason": "stop"}],
+                   "usage": {"prompt_tokens": 5, "completion_tokens": 2}})
+        );
+
+        let mut acc = StreamAccumulator::default();
+        let mut deltas = Vec::new();
+        for piece in stream.as_bytes().chunks(3) {
+            for event in acc.push_bytes(piece) {
+                if let StreamEvent::ContentDelta { content, .. } = event {
+                    deltas.push(content);
+                }
+            }
+        }
+        let (events, response) = acc.finish();
+
+        assert_eq!(deltas, vec!["héllo ".to_string()]);
+        assert_eq!(response.content, "héllo wörld");
+        assert_eq!(response.stop_reason.as_deref(), Some("end_turn"));
+        assert_eq!(response.usage, Some(TokenUsage::new(5, 2)));
+        assert!(matches!(
+            events.last(),
+            Some(StreamEvent::Done { usage: Some(u), .. }) if u.output_tokens == 2
+        ));
+    }
+
+    #[test]
+    fn test_completion_usage_is_optional() {
+        let with_usage: OpenAICompletionResponse = serde_json::from_value(json!({
+            "choices": [],
+            "usage": {"prompt_tokens": 120, "completion_tokens": 30, "total_tokens": 150}
+        }))
+        .unwrap();
+        assert_eq!(
+            with_usage.usage.map(TokenUsage::from),
+            Some(TokenUsage { prompt_tokens: 120, completion_tokens: 30, total_tokens: 150 })
+        );
+
+        let without: OpenAICompletionResponse = serde_json::from_value(json!({"choices": []})).unwrap();
+        assert!(without.usage.is_none());
     }
 }
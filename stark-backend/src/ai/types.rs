@@ -270,6 +270,31 @@ pub fn create_error_feedback(
     )
 }
 
+/// Token counts reported by the provider (summed when covering several requests)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
+    pub total_tokens: u32,
+}
+
+impl TokenUsage {
+    pub fn new(prompt_tokens: u32, completion_tokens: u32) -> Self {
+        TokenUsage {
+            prompt_tokens,
+            completion_tokens,
+            total_tokens: prompt_tokens.saturating_add(completion_tokens),
+        }
+    }
+
+    /// Accumulate another request's usage into this one
+    pub fn add(&mut self, other: &TokenUsage) {
+        self.prompt_tokens = self.prompt_tokens.saturating_add(other.prompt_tokens);
+        self.completion_tokens = self.completion_tokens.saturating_add(other.completion_tokens);
+        self.total_tokens = self.total_tokens.saturating_add(other.total_tokens);
+    }
+}
+
 /// Unified AI response that can contain both text and tool calls
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AiResponse {
@@ -282,6 +307,9 @@ pub struct AiResponse {
     /// x402 payment info if a payment was made for this request
     #[serde(skip_serializing_if = "Option::is_none")]
     pub x402_payment: Option<X402PaymentInfo>,
+    /// Token usage reported by the provider, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<TokenUsage>,
 }
 
 impl AiResponse {
@@ -291,6 +319,7 @@ impl AiResponse {
             tool_calls: vec![],
             stop_reason: Some("end_turn".to_string()),
             x402_payment: None,
+            usage: None,
         }
     }
 
@@ -300,6 +329,7 @@ impl AiResponse {
             tool_calls,
             stop_reason: Some("tool_use".to_string()),
             x402_payment: None,
+            usage: None,
         }
     }
 
@@ -309,6 +339,12 @@ impl AiResponse {
         self
     }
 
+    /// Attach provider-reported token usage
+    pub fn with_usage(mut self, usage: Option<TokenUsage>) -> Self {
+        self.usage = usage;
+        self
+    }
+
     /// Check if the response contains tool calls
     pub fn has_tool_calls(&self) -> bool {
         !self.tool_calls.is_empty()
@@ -318,6 +354,11 @@ impl AiResponse {
     pub fn is_tool_use(&self) -> bool {
         self.stop_reason.as_deref() == Some("tool_use") || !self.tool_calls.is_empty()
     }
+
+    /// Check if the text was cut off at the output token limit
+    pub fn is_truncated(&self) -> bool {
+        self.stop_reason.as_deref() == Some("max_tokens") && self.tool_calls.is_empty()
+    }
 }
 
 /// Tool definition in Claude API format
@@ -14,6 +14,8 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+pub mod snapshot;
+
 /// Current backup format version
 pub const BACKUP_VERSION: u32 = 1;
 
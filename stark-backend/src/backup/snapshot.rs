@@ -0,0 +1,589 @@
+//! Portable configuration snapshots
+//!
+//! A snapshot holds the deployment's channels (with their per-channel settings)
+//! and agent settings as plain JSON, for moving a bot between environments.
+//! Unlike the cloud backup it is not encrypted as a whole; credentials are
+//! handled per field according to [`SecretMode`].
+//!
+//! Importing is split into two steps: [`plan_import`] validates the snapshot
+//! against the current state and resolves conflicts and secrets without
+//! touching the database, then `Database::apply_config_import` applies the
+//! plan in a single transaction. Channel IDs in the snapshot are the source
+//! deployment's; the import report maps them to the IDs in this deployment.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+use super::{decrypt_with_private_key, encrypt_with_private_key, AgentSettingsEntry};
+use crate::models::{
+    get_settings_for_channel_type, AgentSettings, Channel, ChannelSetting, ChannelSettingKey,
+    ChannelType,
+};
+
+/// Current snapshot format version
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// Placeholder written in place of a redacted credential
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Prefix marking an ECIES-encrypted credential (hex ciphertext follows)
+pub const ENCRYPTED_PREFIX: &str = "enc:";
+
+/// How credentials (bot/app tokens, secret settings, agent secret keys) are exported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SecretMode {
+    /// Replace credentials with a placeholder; importing keeps the target's existing values
+    #[default]
+    Redact,
+    /// Encrypt each credential with the wallet's ECIES key (importable only with the same wallet)
+    Encrypt,
+    /// Export credentials in plaintext
+    Include,
+}
+
+impl SecretMode {
+    pub fn from_str_or_default(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "encrypt" => SecretMode::Encrypt,
+            "include" => SecretMode::Include,
+            _ => SecretMode::Redact,
+        }
+    }
+}
+
+/// What to do when an imported channel already exists (same type and name)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictStrategy {
+    /// Reject the whole import
+    #[default]
+    Fail,
+    /// Leave the existing channel untouched
+    Skip,
+    /// Overwrite the existing channel and its settings
+    Replace,
+}
+
+/// Portable configuration snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConfigSnapshot {
+    pub version: u32,
+    pub created_at: DateTime<Utc>,
+    /// How credentials in this snapshot were exported
+    pub secrets: SecretMode,
+    pub channels: Vec<SnapshotChannel>,
+    pub agent_settings: Vec<AgentSettingsEntry>,
+}
+
+impl Default for ConfigSnapshot {
+    fn default() -> Self {
+        Self {
+            version: SNAPSHOT_VERSION,
+            created_at: Utc::now(),
+            secrets: SecretMode::default(),
+            channels: Vec::new(),
+            agent_settings: Vec::new(),
+        }
+    }
+}
+
+/// A channel with its settings nested, keyed by setting key
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SnapshotChannel {
+    /// Channel ID in the source deployment (used only for remapping)
+    pub id: i64,
+    pub channel_type: String,
+    pub name: String,
+    pub enabled: bool,
+    pub bot_token: String,
+    pub app_token: Option<String>,
+    pub settings: BTreeMap<String, String>,
+}
+
+/// Import options
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImportOptions {
+    pub conflict: ConflictStrategy,
+    /// Also import agent settings (AI endpoints)
+    pub agent_settings: bool,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            conflict: ConflictStrategy::default(),
+            agent_settings: true,
+        }
+    }
+}
+
+/// What the import does with one snapshot channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum ChannelAction {
+    Create,
+    Replace { existing_id: i64 },
+    Skip { existing_id: i64 },
+}
+
+/// A validated channel ready to apply. Credentials are decrypted; `None`
+/// means "keep the existing value" (or empty for a new channel).
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedChannel {
+    pub source_id: i64,
+    pub channel_type: String,
+    pub name: String,
+    pub enabled: bool,
+    #[serde(flatten)]
+    pub action: ChannelAction,
+    #[serde(skip)]
+    pub bot_token: Option<String>,
+    #[serde(skip)]
+    pub app_token: Option<Option<String>>,
+    #[serde(skip)]
+    pub settings: Vec<(String, String)>,
+    /// Setting keys whose existing values are kept (redacted in the snapshot)
+    pub preserved_settings: Vec<String>,
+}
+
+/// A validated agent settings entry ready to apply
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedAgentSettings {
+    pub endpoint: String,
+    pub model_archetype: String,
+    pub max_response_tokens: i32,
+    pub max_context_tokens: i32,
+    /// `None` keeps the existing secret key for this endpoint
+    #[serde(skip)]
+    pub secret_key: Option<Option<String>>,
+    pub existing: bool,
+}
+
+/// Result of validating a snapshot against the current configuration
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ImportPlan {
+    pub channels: Vec<PlannedChannel>,
+    pub agent_settings: Vec<PlannedAgentSettings>,
+    /// Endpoint to make the active agent settings after import
+    pub activate_endpoint: Option<String>,
+    pub warnings: Vec<String>,
+}
+
+/// Outcome of an applied import
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ImportReport {
+    pub channels_created: usize,
+    pub channels_replaced: usize,
+    pub channels_skipped: usize,
+    pub agent_settings_imported: usize,
+    /// Source channel ID -> channel ID in this deployment
+    pub channel_id_map: BTreeMap<i64, i64>,
+    pub warnings: Vec<String>,
+}
+
+fn export_secret(value: &str, mode: SecretMode, key: Option<&str>) -> Result<String, String> {
+    if value.is_empty() {
+        return Ok(String::new());
+    }
+    match mode {
+        SecretMode::Include => Ok(value.to_string()),
+        SecretMode::Redact => Ok(REDACTED.to_string()),
+        SecretMode::Encrypt => {
+            let key = key.ok_or("Encrypted export requires a wallet encryption key")?;
+            Ok(format!("{}{}", ENCRYPTED_PREFIX, encrypt_with_private_key(key, value)?))
+        }
+    }
+}
+
+/// Resolve an exported credential: `Ok(None)` if it was redacted
+fn import_secret(value: &str, key: Option<&str>) -> Result<Option<String>, String> {
+    if value == REDACTED {
+        return Ok(None);
+    }
+    match value.strip_prefix(ENCRYPTED_PREFIX) {
+        Some(ciphertext) => {
+            let key = key.ok_or("value is encrypted but no wallet encryption key is available")?;
+            decrypt_with_private_key(key, ciphertext)
+                .map(Some)
+                .map_err(|e| format!("cannot decrypt value ({}); was it exported by a different wallet?", e))
+        }
+        None => Ok(Some(value.to_string())),
+    }
+}
+
+fn is_secret_setting(key: &str) -> bool {
+    key.parse::<ChannelSettingKey>().map(|k| k.is_secret()).unwrap_or(false)
+}
+
+/// Build a snapshot from current configuration rows
+pub fn build_snapshot(
+    channels: Vec<Channel>,
+    settings: Vec<ChannelSetting>,
+    agent_settings: Vec<AgentSettings>,
+    mode: SecretMode,
+    encryption_key: Option<&str>,
+) -> Result<ConfigSnapshot, String> {
+    let mut snapshot_channels = Vec::with_capacity(channels.len());
+    for channel in channels {
+        let mut channel_settings = BTreeMap::new();
+        for s in settings.iter().filter(|s| s.channel_id == channel.id) {
+            let value = if is_secret_setting(&s.setting_key) {
+                export_secret(&s.setting_value, mode, encryption_key)?
+            } else {
+                s.setting_value.clone()
+            };
+            channel_settings.insert(s.setting_key.clone(), value);
+        }
+        snapshot_channels.push(SnapshotChannel {
+            id: channel.id,
+            bot_token: export_secret(&channel.bot_token, mode, encryption_key)?,
+            app_token: match channel.app_token.as_deref() {
+                Some(token) => Some(export_secret(token, mode, encryption_key)?),
+                None => None,
+            },
+            channel_type: channel.channel_type,
+            name: channel.name,
+            enabled: channel.enabled,
+            settings: channel_settings,
+        });
+    }
+
+    let mut agents = Vec::with_capacity(agent_settings.len());
+    for a in agent_settings {
+        agents.push(AgentSettingsEntry {
+            secret_key: match a.secret_key.as_deref() {
+                Some(key) => Some(export_secret(key, mode, encryption_key)?),
+                None => None,
+            },
+            endpoint: a.endpoint,
+            model_archetype: a.model_archetype,
+            max_response_tokens: a.max_response_tokens,
+            max_context_tokens: a.max_context_tokens,
+            enabled: a.enabled,
+        });
+    }
+
+    Ok(ConfigSnapshot {
+        version: SNAPSHOT_VERSION,
+        created_at: Utc::now(),
+        secrets: mode,
+        channels: snapshot_channels,
+        agent_settings: agents,
+    })
+}
+
+/// Export the current configuration (safe mode channels are temporary and excluded)
+pub fn export_snapshot(
+    db: &crate::db::Database,
+    mode: SecretMode,
+    encryption_key: Option<&str>,
+) -> Result<ConfigSnapshot, String> {
+    let channels = db
+        .list_channels_for_backup()
+        .map_err(|e| format!("Failed to list channels: {}", e))?;
+    let settings = db
+        .get_all_channel_settings()
+        .map_err(|e| format!("Failed to list channel settings: {}", e))?;
+    let agent_settings = db
+        .list_agent_settings()
+        .map_err(|e| format!("Failed to list agent settings: {}", e))?;
+    build_snapshot(channels, settings, agent_settings, mode, encryption_key)
+}
+
+/// Validate a snapshot against the existing configuration and work out what
+/// importing it would do. Returns every validation error at once.
+pub fn plan_import(
+    snapshot: &ConfigSnapshot,
+    existing_channels: &[Channel],
+    existing_agent_settings: &[AgentSettings],
+    options: ImportOptions,
+    decryption_key: Option<&str>,
+) -> Result<ImportPlan, Vec<String>> {
+    let mut errors = Vec::new();
+    let mut plan = ImportPlan::default();
+
+    if snapshot.version > SNAPSHOT_VERSION {
+        errors.push(format!(
+            "Snapshot version {} is newer than supported version {}",
+            snapshot.version, SNAPSHOT_VERSION
+        ));
+    }
+
+    let mut seen = HashSet::new();
+    for (i, ch) in snapshot.channels.iter().enumerate() {
+        let label = format!("channels[{}] ({} '{}')", i, ch.channel_type, ch.name);
+        let name = ch.name.trim();
+        if name.is_empty() {
+            errors.push(format!("channels[{}]: name is required", i));
+            continue;
+        }
+        let channel_type = match ChannelType::from_str(&ch.channel_type) {
+            Some(t) => t,
+            None => {
+                errors.push(format!("{}: unknown channel type", label));
+                continue;
+            }
+        };
+        if !seen.insert((channel_type.as_str(), name.to_string())) {
+            errors.push(format!("{}: duplicate channel in snapshot", label));
+            continue;
+        }
+
+        let existing = existing_channels
+            .iter()
+            .find(|c| c.channel_type == channel_type.as_str() && c.name == name);
+        let action = match (existing, options.conflict) {
+            (None, _) => ChannelAction::Create,
+            (Some(c), ConflictStrategy::Skip) => ChannelAction::Skip { existing_id: c.id },
+            (Some(c), ConflictStrategy::Replace) => ChannelAction::Replace { existing_id: c.id },
+            (Some(c), ConflictStrategy::Fail) => {
+                errors.push(format!(
+                    "{}: conflicts with existing channel {} (use conflict 'skip' or 'replace')",
+                    label, c.id
+                ));
+                continue;
+            }
+        };
+
+        let allowed: HashSet<String> = get_settings_for_channel_type(channel_type)
+            .into_iter()
+            .map(|d| d.key)
+            .collect();
+        let mut settings = Vec::new();
+        let mut preserved_settings = Vec::new();
+        for (key, value) in &ch.settings {
+            if !allowed.contains(key) {
+                errors.push(format!("{}: setting '{}' does not apply to {} channels", label, key, channel_type));
+                continue;
+            }
+            if !is_secret_setting(key) {
+                settings.push((key.clone(), value.clone()));
+                continue;
+            }
+            match import_secret(value, decryption_key) {
+                Ok(Some(v)) => settings.push((key.clone(), v)),
+                Ok(None) => preserved_settings.push(key.clone()),
+                Err(e) => errors.push(format!("{}: setting '{}': {}", label, key, e)),
+            }
+        }
+
+        let bot_token = match import_secret(&ch.bot_token, decryption_key) {
+            Ok(t) => t,
+            Err(e) => {
+                errors.push(format!("{}: bot_token: {}", label, e));
+                continue;
+            }
+        };
+        let app_token = match ch.app_token.as_deref() {
+            Some(t) => match import_secret(t, decryption_key) {
+                Ok(t) => t.map(Some),
+                Err(e) => {
+                    errors.push(format!("{}: app_token: {}", label, e));
+                    continue;
+                }
+            },
+            None => Some(None),
+        };
+
+        let mut enabled = ch.enabled;
+        if action == ChannelAction::Create {
+            if bot_token.is_none() || !preserved_settings.is_empty() {
+                plan.warnings.push(format!(
+                    "{}: credentials were redacted; the channel is created disabled until they are entered",
+                    label
+                ));
+                enabled = false;
+            }
+            preserved_settings.clear();
+        }
+
+        plan.channels.push(PlannedChannel {
+            source_id: ch.id,
+            channel_type: channel_type.as_str().to_string(),
+            name: name.to_string(),
+            enabled,
+            action,
+            bot_token,
+            app_token,
+            settings,
+            preserved_settings,
+        });
+    }
+
+    if options.agent_settings {
+        let mut endpoints = HashSet::new();
+        for (i, a) in snapshot.agent_settings.iter().enumerate() {
+            let endpoint = a.endpoint.trim();
+            let label = format!("agent_settings[{}] ({})", i, endpoint);
+            if endpoint.is_empty() {
+                errors.push(format!("agent_settings[{}]: endpoint is required", i));
+                continue;
+            }
+            if !endpoints.insert(endpoint.to_string()) {
+                errors.push(format!("{}: duplicate endpoint in snapshot", label));
+                continue;
+            }
+            if a.max_response_tokens <= 0 || a.max_context_tokens <= 0 {
+                errors.push(format!("{}: token limits must be positive", label));
+                continue;
+            }
+            let existing = existing_agent_settings.iter().any(|e| e.endpoint == endpoint);
+            let secret_key = match a.secret_key.as_deref() {
+                Some(key) => match import_secret(key, decryption_key) {
+                    Ok(k) => k.map(Some),
+                    Err(e) => {
+                        errors.push(format!("{}: secret_key: {}", label, e));
+                        continue;
+                    }
+                },
+                None => Some(None),
+            };
+            if secret_key.is_none() && !existing {
+                plan.warnings.push(format!("{}: secret key was redacted and must be re-entered", label));
+            }
+            if a.enabled {
+                match &plan.activate_endpoint {
+                    None => plan.activate_endpoint = Some(endpoint.to_string()),
+                    Some(first) => plan.warnings.push(format!(
+                        "{}: multiple enabled entries; '{}' stays the active one",
+                        label, first
+                    )),
+                }
+            }
+            plan.agent_settings.push(PlannedAgentSettings {
+                endpoint: endpoint.to_string(),
+                model_archetype: a.model_archetype.clone(),
+                max_response_tokens: a.max_response_tokens,
+                max_context_tokens: a.max_context_tokens,
+                secret_key,
+                existing,
+            });
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(plan)
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_KEY: &str = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+    fn channel(id: i64, channel_type: &str, name: &str, token: &str) -> Channel {
+        Channel {
+            id,
+            channel_type: channel_type.to_string(),
+            name: name.to_string(),
+            enabled: true,
+            bot_token: token.to_string(),
+            app_token: None,
+            safe_mode: false,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    fn setting(channel_id: i64, key: &str, value: &str) -> ChannelSetting {
+        ChannelSetting {
+            channel_id,
+            setting_key: key.to_string(),
+            setting_value: value.to_string(),
+        }
+    }
+
+    fn sample_snapshot(mode: SecretMode, key: Option<&str>) -> ConfigSnapshot {
+        build_snapshot(
+            vec![channel(7, "discord", "main", "discord-token")],
+            vec![
+                setting(7, "discord_bot_token", "discord-token"),
+                setting(7, "discord_auto_pin", "true"),
+            ],
+            vec![],
+            mode,
+            key,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_redacted_export_hides_credentials() {
+        let snapshot = sample_snapshot(SecretMode::Redact, None);
+        let ch = &snapshot.channels[0];
+        assert_eq!(ch.bot_token, REDACTED);
+        assert_eq!(ch.settings["discord_bot_token"], REDACTED);
+        assert_eq!(ch.settings["discord_auto_pin"], "true");
+    }
+
+    #[test]
+    fn test_encrypted_roundtrip() {
+        let snapshot = sample_snapshot(SecretMode::Encrypt, Some(TEST_KEY));
+        assert!(snapshot.channels[0].bot_token.starts_with(ENCRYPTED_PREFIX));
+
+        let plan = plan_import(&snapshot, &[], &[], ImportOptions::default(), Some(TEST_KEY)).unwrap();
+        assert_eq!(plan.channels[0].bot_token.as_deref(), Some("discord-token"));
+        assert!(plan.channels[0].settings.contains(&("discord_bot_token".to_string(), "discord-token".to_string())));
+
+        let errors = plan_import(&snapshot, &[], &[], ImportOptions::default(), None).unwrap_err();
+        assert!(errors[0].contains("encrypted"));
+    }
+
+    #[test]
+    fn test_redacted_new_channel_is_disabled() {
+        let snapshot = sample_snapshot(SecretMode::Redact, None);
+        let plan = plan_import(&snapshot, &[], &[], ImportOptions::default(), None).unwrap();
+        assert_eq!(plan.channels[0].action, ChannelAction::Create);
+        assert!(!plan.channels[0].enabled);
+        assert_eq!(plan.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_conflict_strategies() {
+        let snapshot = sample_snapshot(SecretMode::Redact, None);
+        let existing = vec![channel(42, "discord", "main", "old-token")];
+
+        let errors = plan_import(&snapshot, &existing, &[], ImportOptions::default(), None).unwrap_err();
+        assert!(errors[0].contains("conflicts with existing channel 42"));
+
+        let options = ImportOptions { conflict: ConflictStrategy::Replace, ..Default::default() };
+        let plan = plan_import(&snapshot, &existing, &[], options, None).unwrap();
+        let ch = &plan.channels[0];
+        assert_eq!(ch.action, ChannelAction::Replace { existing_id: 42 });
+        assert!(ch.enabled);
+        assert_eq!(ch.bot_token, None);
+        assert_eq!(ch.preserved_settings, vec!["discord_bot_token".to_string()]);
+
+        let options = ImportOptions { conflict: ConflictStrategy::Skip, ..Default::default() };
+        let plan = plan_import(&snapshot, &existing, &[], options, None).unwrap();
+        assert_eq!(plan.channels[0].action, ChannelAction::Skip { existing_id: 42 });
+    }
+
+    #[test]
+    fn test_validation_collects_errors() {
+        let mut snapshot = sample_snapshot(SecretMode::Include, None);
+        snapshot.channels[0].settings.insert("slack_app_token".to_string(), "x".to_string());
+        snapshot.channels.push(snapshot.channels[0].clone());
+        snapshot.channels.push(SnapshotChannel {
+            channel_type: "irc".to_string(),
+            name: "old".to_string(),
+            ..Default::default()
+        });
+        snapshot.agent_settings.push(AgentSettingsEntry::default());
+
+        let errors = plan_import(&snapshot, &[], &[], ImportOptions::default(), None).unwrap_err();
+        assert!(errors.iter().any(|e| e.contains("'slack_app_token' does not apply")));
+        assert!(errors.iter().any(|e| e.contains("duplicate channel")));
+        assert!(errors.iter().any(|e| e.contains("unknown channel type")));
+        assert!(errors.iter().any(|e| e.contains("endpoint is required")));
+    }
+}
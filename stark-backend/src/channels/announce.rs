@@ -0,0 +1,267 @@
+//! Operator announcements fanned out to every enabled channel.
+//!
+//! Each Discord/Telegram/Slack channel opts in by setting `announcement_chat_id`;
+//! the message is split to the platform's length limit and sent through that
+//! channel's own bot token. Used by both the `broadcast_announcement` tool and
+//! the `POST /api/channels/announce` endpoint. The per-platform send helpers are
+//! also used for reminder delivery (`scheduler::reminders`).
+
+use crate::channels::util;
+use crate::db::Database;
+use crate::models::{Channel, ChannelSettingKey, ChannelType};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// Longest announcement accepted (announcements are meant to be short notices)
+pub const MAX_ANNOUNCEMENT_CHARS: usize = 4000;
+
+/// Per-channel outcome of an announcement
+#[derive(Debug, Clone, Serialize)]
+pub struct AnnouncementDelivery {
+    pub channel_id: i64,
+    pub channel_name: String,
+    pub channel_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chat_id: Option<String>,
+    pub success: bool,
+    /// Number of message parts delivered (long text is split per platform limits)
+    pub parts_sent: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Check an announcement before fanning it out
+pub fn validate_announcement(message: &str) -> Result<(), String> {
+    if message.trim().is_empty() {
+        return Err("Announcement text is empty".to_string());
+    }
+    let len = message.chars().count();
+    if len > MAX_ANNOUNCEMENT_CHARS {
+        return Err(format!(
+            "Announcement is {} characters; the maximum is {}",
+            len, MAX_ANNOUNCEMENT_CHARS
+        ));
+    }
+    Ok(())
+}
+
+/// Platform message length limit, or None if the channel type can't receive announcements
+pub(crate) fn message_limit(channel_type: ChannelType) -> Option<usize> {
+    match channel_type {
+        ChannelType::Discord => Some(2000),
+        ChannelType::Telegram => Some(4096),
+        ChannelType::Slack => Some(4000),
+        ChannelType::Twitter | ChannelType::ExternalChannel | ChannelType::Webhook => None,
+    }
+}
+
+fn bot_token_key(channel_type: ChannelType) -> Option<ChannelSettingKey> {
+    match channel_type {
+        ChannelType::Discord => Some(ChannelSettingKey::DiscordBotToken),
+        ChannelType::Telegram => Some(ChannelSettingKey::TelegramBotToken),
+        ChannelType::Slack => Some(ChannelSettingKey::SlackBotToken),
+        ChannelType::Twitter | ChannelType::ExternalChannel | ChannelType::Webhook => None,
+    }
+}
+
+/// Bot token for a Discord/Telegram/Slack channel: the `<type>_bot_token` setting,
+/// falling back to the token stored on the channel row
+pub(crate) fn resolve_bot_token(db: &Database, channel: &Channel, channel_type: ChannelType) -> Option<String> {
+    let key = bot_token_key(channel_type)?;
+    let token = db
+        .get_channel_setting(channel.id, key.as_ref())
+        .ok()
+        .flatten()
+        .filter(|t| !t.is_empty())
+        .unwrap_or_else(|| channel.bot_token.clone());
+    if token.is_empty() {
+        None
+    } else {
+        Some(token)
+    }
+}
+
+/// Send `message` to the announcement chat of every enabled channel.
+/// Channels without an announcement chat are reported as skipped, not silently dropped.
+pub async fn broadcast_announcement(
+    db: &Database,
+    client: &reqwest::Client,
+    message: &str,
+) -> Result<Vec<AnnouncementDelivery>, String> {
+    validate_announcement(message)?;
+
+    let channels = db
+        .list_enabled_channels()
+        .map_err(|e| format!("Failed to list channels: {}", e))?;
+
+    let mut results = Vec::with_capacity(channels.len());
+    for channel in channels {
+        let delivery = deliver_to_channel(db, client, &channel, message).await;
+        if delivery.success {
+            log::info!(
+                "[ANNOUNCE] Delivered to {} channel '{}' ({} parts)",
+                delivery.channel_type, delivery.channel_name, delivery.parts_sent
+            );
+        } else {
+            log::warn!(
+                "[ANNOUNCE] Not delivered to {} channel '{}': {}",
+                delivery.channel_type,
+                delivery.channel_name,
+                delivery.error.as_deref().unwrap_or("unknown error")
+            );
+        }
+        results.push(delivery);
+    }
+    Ok(results)
+}
+
+async fn deliver_to_channel(
+    db: &Database,
+    client: &reqwest::Client,
+    channel: &Channel,
+    message: &str,
+) -> AnnouncementDelivery {
+    let mut delivery = AnnouncementDelivery {
+        channel_id: channel.id,
+        channel_name: channel.name.clone(),
+        channel_type: channel.channel_type.clone(),
+        chat_id: None,
+        success: false,
+        parts_sent: 0,
+        error: None,
+    };
+
+    let channel_type = match channel.channel_type_enum() {
+        Some(ct) => ct,
+        None => {
+            delivery.error = Some(format!("Unknown channel type '{}'", channel.channel_type));
+            return delivery;
+        }
+    };
+    let limit = match message_limit(channel_type) {
+        Some(limit) => limit,
+        None => {
+            delivery.error = Some("Announcements are not supported for this channel type".to_string());
+            return delivery;
+        }
+    };
+
+    let chat_id = db
+        .get_channel_setting(channel.id, ChannelSettingKey::AnnouncementChatId.as_ref())
+        .ok()
+        .flatten()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+    let chat_id = match chat_id {
+        Some(id) => id,
+        None => {
+            delivery.error = Some("No announcement_chat_id configured (skipped)".to_string());
+            return delivery;
+        }
+    };
+    delivery.chat_id = Some(chat_id.clone());
+
+    let token = match resolve_bot_token(db, channel, channel_type) {
+        Some(token) => token,
+        None => {
+            delivery.error = Some("Bot token not configured".to_string());
+            return delivery;
+        }
+    };
+
+    for part in util::split_message(message, limit) {
+        let sent = match channel_type {
+            ChannelType::Discord => send_discord(client, &token, &chat_id, &part).await,
+            ChannelType::Telegram => send_telegram(client, &token, &chat_id, &part).await,
+            ChannelType::Slack => send_slack(client, &token, &chat_id, &part).await,
+            ChannelType::Twitter | ChannelType::ExternalChannel | ChannelType::Webhook => unreachable!("filtered above"),
+        };
+        if let Err(e) = sent {
+            delivery.error = Some(e);
+            return delivery;
+        }
+        delivery.parts_sent += 1;
+    }
+
+    delivery.success = true;
+    delivery
+}
+
+pub(crate) async fn send_discord(client: &reqwest::Client, token: &str, chat_id: &str, text: &str) -> Result<(), String> {
+    let url = format!("https://discord.com/api/v10/channels/{}/messages", chat_id);
+    let response = client
+        .post(&url)
+        .header("Authorization", format!("Bot {}", token))
+        .json(&json!({ "content": text }))
+        .send()
+        .await
+        .map_err(|e| format!("Discord request failed: {}", e))?;
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        Err(format!("Discord API error ({}): {}", status, body))
+    }
+}
+
+pub(crate) async fn send_telegram(client: &reqwest::Client, token: &str, chat_id: &str, text: &str) -> Result<(), String> {
+    // Plain text: announcements are operator-written and may contain unbalanced Markdown
+    let url = format!("https://api.telegram.org/bot{}/sendMessage", token);
+    let response = client
+        .post(&url)
+        .json(&json!({ "chat_id": chat_id, "text": text }))
+        .send()
+        .await
+        .map_err(|e| format!("Telegram request failed: {}", e))?;
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        Err(format!("Telegram API error ({}): {}", status, body))
+    }
+}
+
+pub(crate) async fn send_slack(client: &reqwest::Client, token: &str, chat_id: &str, text: &str) -> Result<(), String> {
+    let response = client
+        .post("https://slack.com/api/chat.postMessage")
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&json!({ "channel": chat_id, "text": text }))
+        .send()
+        .await
+        .map_err(|e| format!("Slack request failed: {}", e))?;
+    // Slack returns 200 even on errors, check the response body
+    let body: Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Invalid Slack response: {}", e))?;
+    if body.get("ok").and_then(|v| v.as_bool()).unwrap_or(false) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Slack API error: {}",
+            body.get("error").and_then(|v| v.as_str()).unwrap_or("unknown error")
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_announcement() {
+        assert!(validate_announcement("Maintenance at 10:00 UTC").is_ok());
+        assert!(validate_announcement("   ").is_err());
+        assert!(validate_announcement(&"x".repeat(MAX_ANNOUNCEMENT_CHARS + 1)).is_err());
+    }
+
+    #[test]
+    fn test_message_limits() {
+        assert_eq!(message_limit(ChannelType::Discord), Some(2000));
+        assert_eq!(message_limit(ChannelType::Telegram), Some(4096));
+        assert_eq!(message_limit(ChannelType::Slack), Some(4000));
+        assert_eq!(message_limit(ChannelType::Twitter), None);
+    }
+}
@@ -0,0 +1,111 @@
+//! Connection health of a channel listener
+//!
+//! Listeners report when their connection drops and comes back; this turns
+//! those reports into `channel.reconnecting` / `channel.reconnected` gateway
+//! events, so a dashboard can show a degraded channel instead of only
+//! started/stopped. Nothing is reported before the first successful
+//! connection (that's startup, not a reconnect).
+
+use crate::gateway::events::EventBroadcaster;
+use crate::gateway::protocol::GatewayEvent;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
+
+pub struct ConnectionHealth {
+    channel_id: i64,
+    channel_type: &'static str,
+    broadcaster: Arc<EventBroadcaster>,
+    /// Set once the listener has connected at least once
+    established: AtomicBool,
+    /// Reconnect attempts since the connection was last healthy
+    attempts: AtomicU32,
+}
+
+impl ConnectionHealth {
+    pub fn new(channel_id: i64, channel_type: &'static str, broadcaster: Arc<EventBroadcaster>) -> Self {
+        Self {
+            channel_id,
+            channel_type,
+            broadcaster,
+            established: AtomicBool::new(false),
+            attempts: AtomicU32::new(0),
+        }
+    }
+
+    /// The connection is up. Emits `channel.reconnected` if it had dropped.
+    pub fn connected(&self) {
+        self.established.store(true, Ordering::SeqCst);
+        let attempts = self.attempts.swap(0, Ordering::SeqCst);
+        if attempts > 0 {
+            log::info!(
+                "[CHANNEL_HEALTH] {} channel {} reconnected after {} attempt(s)",
+                self.channel_type,
+                self.channel_id,
+                attempts
+            );
+            self.broadcaster.broadcast(GatewayEvent::channel_reconnected(
+                self.channel_id,
+                self.channel_type,
+                attempts,
+            ));
+        }
+    }
+
+    /// The connection dropped or a reconnect attempt failed. Emits `channel.reconnecting`.
+    pub fn reconnecting(&self, reason: &str) {
+        if !self.established.load(Ordering::SeqCst) {
+            return;
+        }
+        let attempt = self.attempts.fetch_add(1, Ordering::SeqCst) + 1;
+        log::warn!(
+            "[CHANNEL_HEALTH] {} channel {} reconnecting (attempt {}): {}",
+            self.channel_type,
+            self.channel_id,
+            attempt,
+            reason
+        );
+        self.broadcaster.broadcast(GatewayEvent::channel_reconnecting(
+            self.channel_id,
+            self.channel_type,
+            attempt,
+            reason,
+        ));
+    }
+
+    /// Whether the connection is currently down
+    pub fn is_degraded(&self) -> bool {
+        self.attempts.load(Ordering::SeqCst) > 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_reconnect_events() {
+        let broadcaster = Arc::new(EventBroadcaster::new());
+        let (_client_id, mut rx) = broadcaster.subscribe();
+        let health = ConnectionHealth::new(7, "discord", broadcaster.clone());
+
+        // Failures before the first connection are startup, not reconnects
+        health.reconnecting("connecting");
+        health.connected();
+        health.reconnecting("shard resuming");
+        health.reconnecting("shard connecting");
+        assert!(health.is_degraded());
+        health.connected();
+        assert!(!health.is_degraded());
+
+        let mut events = Vec::new();
+        while let Ok(Some(event)) = tokio::time::timeout(Duration::from_millis(100), rx.recv()).await {
+            events.push(event);
+        }
+        let names: Vec<&str> = events.iter().map(|e| e.event.as_str()).collect();
+        assert_eq!(names, ["channel.reconnecting", "channel.reconnecting", "channel.reconnected"]);
+        assert_eq!(events[1].data["attempt"], 2);
+        assert_eq!(events[2].data["attempts"], 2);
+        assert_eq!(events[2].data["channel_id"], 7);
+    }
+}
@@ -1,15 +1,23 @@
+use crate::channels::discord_attachments;
+use crate::channels::discord_debounce::{self, MessageDebouncer};
+use crate::channels::discord_permissions;
+use crate::channels::discord_pins::{self, PinOutcome};
+use crate::channels::discord_presence::{self, BusyPresence};
+use crate::channels::discord_reply_context;
 use crate::channels::dispatcher::MessageDispatcher;
-use crate::channels::safe_mode_rate_limiter::SafeModeChannelRateLimiter;
+use crate::channels::preferences;
+use crate::channels::safe_mode_rate_limiter::{self, SafeModeChannelRateLimiter};
 use crate::channels::types::{ChannelType, NormalizedMessage};
 use crate::channels::util;
+use crate::channels::connection_health::ConnectionHealth;
 use crate::db::Database;
 use crate::discord_hooks;
 use crate::gateway::events::EventBroadcaster;
 use crate::gateway::protocol::GatewayEvent;
-use crate::models::{Channel, ToolOutputVerbosity};
+use crate::models::{Channel, ChannelSettingKey, ForwardedEvents, ToolOutputVerbosity};
 use serenity::all::{
-    Client, Context, EditMessage, EventHandler, GatewayIntents, GetMessages, Message, MessageId,
-    Ready,
+    Client, ConnectionStage, Context, EditMessage, EventHandler, GatewayIntents, GetMessages, Interaction,
+    Message, MessageId, Reaction, Ready, ShardStageUpdateEvent,
 };
 use std::sync::Arc;
 use tokio::sync::oneshot;
@@ -27,11 +35,7 @@ fn format_tool_call_for_discord(
             let params_str = serde_json::to_string_pretty(parameters)
                 .unwrap_or_else(|_| parameters.to_string());
             // Truncate params if too long for Discord
-            let params_display = if params_str.len() > 800 {
-                format!("{}...", &params_str[..800])
-            } else {
-                params_str
-            };
+            let params_display = util::truncate_str(&params_str, 800);
             Some(format!("🔧 **Tool Call:** `{}`\n```json\n{}\n```", tool_name, params_display))
         }
     }
@@ -56,11 +60,7 @@ fn format_tool_result_for_discord(
         }
         ToolOutputVerbosity::Full => {
             // Truncate content if too long
-            let content_display = if content.len() > 1200 {
-                format!("{}...", &content[..1200])
-            } else {
-                content.to_string()
-            };
+            let content_display = util::truncate_str(&content, 1200);
             Some(format!(
                 "{} **Tool Result:** `{}` ({} ms)\n```\n{}\n```",
                 status, tool_name, duration_ms, content_display
@@ -69,6 +69,64 @@ fn format_tool_result_for_discord(
     }
 }
 
+/// Per-dispatch cap on forwarded tool-event messages (0 = unlimited)
+fn max_tool_events(db: &Database, channel_id: i64) -> usize {
+    db.get_channel_setting(channel_id, ChannelSettingKey::DiscordMaxToolEvents.as_ref())
+        .ok()
+        .flatten()
+        .and_then(|s| s.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+/// Whether tool updates are appended to one edited status message instead of replacing it
+fn edit_mode_enabled(db: &Database, channel_id: i64) -> bool {
+    db.get_channel_setting(channel_id, ChannelSettingKey::DiscordEditMode.as_ref())
+        .ok()
+        .flatten()
+        .map(|v| v.trim().eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+/// Which event types are posted to the channel while a dispatch runs
+fn forwarded_events(db: &Database, channel_id: i64) -> ForwardedEvents {
+    db.get_channel_setting(channel_id, ChannelSettingKey::DiscordForwardedEvents.as_ref())
+        .ok()
+        .flatten()
+        .map(|v| ForwardedEvents::parse(&v))
+        .unwrap_or_default()
+}
+
+/// Append a line to the running status log. Returns false when the line doesn't fit in
+/// the current message; the log then holds only the new line, for a fresh message.
+fn append_to_status_log(log: &mut String, line: &str) -> bool {
+    if log.is_empty() {
+        log.push_str(line);
+        return true;
+    }
+    if log.len() + 1 + line.len() > 2000 {
+        *log = line.to_string();
+        return false;
+    }
+    log.push('\n');
+    log.push_str(line);
+    true
+}
+
+/// Split a reply for Discord's 2000-char limit, numbering the parts if the channel opted in
+fn split_reply(db: &Database, channel_id: i64, text: &str) -> Vec<String> {
+    let numbered = db
+        .get_channel_setting(channel_id, ChannelSettingKey::DiscordNumberChunks.as_ref())
+        .ok()
+        .flatten()
+        .map(|v| v.trim().eq_ignore_ascii_case("true"))
+        .unwrap_or(false);
+    if numbered {
+        util::split_message_numbered(text, 2000)
+    } else {
+        util::split_message(text, 2000)
+    }
+}
+
 /// Format an agent mode change for Discord display
 fn format_mode_change_for_discord(mode: &str, label: &str, reason: Option<&str>) -> String {
     let emoji = match mode {
@@ -91,6 +149,9 @@ struct DiscordHandler {
     broadcaster: Arc<EventBroadcaster>,
     db: Arc<Database>,
     safe_mode_rate_limiter: SafeModeChannelRateLimiter,
+    busy_presence: BusyPresence,
+    debouncer: MessageDebouncer,
+    health: ConnectionHealth,
 }
 
 #[serenity::async_trait]
@@ -116,7 +177,7 @@ impl EventHandler for DiscordHandler {
         }
 
         let text = msg.content.clone();
-        if text.is_empty() {
+        if text.is_empty() && msg.attachments.is_empty() {
             return;
         }
 
@@ -124,22 +185,94 @@ impl EventHandler for DiscordHandler {
         // Process through discord_hooks module first (config reloaded from DB each time)
         match discord_hooks::process(&msg, &ctx, &self.db, self.channel_id).await {
             Ok(result) => {
+                // Admin `configure`: post the settings prompt with its button
+                if result.agent_config_prompt {
+                    let prompt = discord_hooks::agent_config::prompt_message(&self.db);
+                    if let Err(e) = msg.channel_id.send_message(&ctx.http, prompt).await {
+                        log::error!("Discord: Failed to send configuration prompt: {}", e);
+                    }
+                    return;
+                }
+
                 // If module handled it with a direct response, send it and return
                 if let Some(response) = result.response {
-                    let chunks = util::split_message(&response, 2000);
+                    let chunks = split_reply(&self.db, self.channel_id, &response);
                     for chunk in chunks {
                         if let Err(e) = msg.channel_id.say(&ctx.http, &chunk).await {
-                            log::error!("Discord: Failed to send hooks response: {}", e);
+                            if discord_permissions::report_send_failure(&ctx, &msg, "hooks response", &e).await {
+                                break;
+                            }
                         }
                     }
                     return;
                 }
 
                 // If module says forward to agent, use the forwarded text
-                if let Some(forward) = result.forward_to_agent {
+                if let Some(mut forward) = result.forward_to_agent {
                     let user_name = forward.user_name;
                     let user_id = forward.user_id.clone();
 
+                    // `limits` command: report the user's own query budget without spending a query
+                    if safe_mode_rate_limiter::is_limits_command(&forward.text) {
+                        let reply = if forward.force_safe_mode {
+                            self.safe_mode_rate_limiter.get_user_limit_status(&user_id, "discord").to_message()
+                        } else {
+                            safe_mode_rate_limiter::NO_LIMITS_MESSAGE.to_string()
+                        };
+                        if let Err(e) = msg.channel_id.say(&ctx.http, reply).await {
+                            log::error!("Discord: Failed to send limits status to user {}: {}", user_id, e);
+                        }
+                        return;
+                    }
+
+                    // `pref` commands skip debouncing and chat context and reach the dispatcher
+                    // bare, which handles them after the rate limits
+                    let is_pref_command = preferences::parse_command(&forward.text).is_some();
+
+                    // Admins' file attachments go to the workspace for the file/exec tools;
+                    // safe mode can't use those tools, so nothing is saved for other users.
+                    // Saved before debouncing so a follow-up's files join the burst.
+                    let mut attachments = Vec::new();
+                    if !forward.force_safe_mode && !msg.attachments.is_empty() {
+                        let disk_quota = self.dispatcher.disk_quota();
+                        let saved = discord_attachments::save_attachments(
+                            &msg.attachments,
+                            &msg.id.to_string(),
+                            self.channel_id,
+                            disk_quota.as_deref(),
+                        )
+                        .await;
+                        if !saved.skipped.is_empty() {
+                            forward.text = format!(
+                                "{}\n\n[ATTACHMENTS NOT SAVED:]\n{}",
+                                forward.text,
+                                saved.skipped.join("\n")
+                            );
+                        }
+                        attachments = saved.paths;
+                    }
+
+                    // Rapid follow-ups are folded into the first message's dispatch
+                    if let Some(window) = discord_debounce::debounce_window(&self.db, self.channel_id).filter(|_| !is_pref_command) {
+                        match self
+                            .debouncer
+                            .collect(
+                                msg.channel_id.get(),
+                                &user_id,
+                                std::mem::take(&mut forward.text),
+                                std::mem::take(&mut attachments),
+                                window,
+                            )
+                            .await
+                        {
+                            Some(burst) => {
+                                forward.text = burst.text;
+                                attachments = burst.attachments;
+                            }
+                            None => return,
+                        }
+                    }
+
                     // Check safe mode rate limit for non-admin queries
                     if forward.force_safe_mode {
                         if let Err(rate_limit_msg) = self.safe_mode_rate_limiter.check_and_record_query(&user_id, "discord") {
@@ -160,21 +293,14 @@ impl EventHandler for DiscordHandler {
                     );
 
                     // Fetch recent channel context (last 6 messages before this one)
-                    let recent_context = {
+                    let recent_context = if is_pref_command {
+                        String::new()
+                    } else {
                         let mut ctx_str = String::new();
 
                         // If this is a reply, include what it's replying to
-                        if let Some(ref replied) = msg.referenced_message {
-                            let reply_author = &replied.author.name;
-                            let reply_content = if replied.content.len() > 300 {
-                                format!("{}...", &replied.content[..300])
-                            } else {
-                                replied.content.clone()
-                            };
-                            ctx_str.push_str(&format!(
-                                "[REPLYING TO @{}:]\n{}\n\n",
-                                reply_author, reply_content
-                            ));
+                        if let Some(quoted) = discord_reply_context::reply_context(&ctx, &msg).await {
+                            ctx_str.push_str(&quoted);
                         }
 
                         // Fetch last 6 messages from channel
@@ -191,11 +317,7 @@ impl EventHandler for DiscordHandler {
                                 for m in msgs {
                                     let who = &m.author.name;
                                     let tag = if m.author.bot { " [you]" } else { "" };
-                                    let preview = if m.content.len() > 300 {
-                                        format!("{}...", &m.content[..300])
-                                    } else {
-                                        m.content.clone()
-                                    };
+                                    let preview = util::truncate_str(&m.content, 300);
                                     ctx_str.push_str(&format!("@{}{}: {}\n", who, tag, preview));
                                 }
                                 ctx_str.push('\n');
@@ -209,7 +331,9 @@ impl EventHandler for DiscordHandler {
                         ctx_str
                     };
 
-                    let text_with_hint = if recent_context.is_empty() {
+                    let text_with_hint = if is_pref_command {
+                        forward.text.clone()
+                    } else if recent_context.is_empty() {
                         format!(
                             "[DISCORD MESSAGE - Use discord_tipping skill for tips.]\n\n{}",
                             forward.text
@@ -238,6 +362,8 @@ impl EventHandler for DiscordHandler {
                         session_mode: None,
                         selected_network: None,
                         force_safe_mode: forward.force_safe_mode,
+                        from_admin: forward.is_admin,
+                        attachments,
                     };
 
                     self.dispatch_and_respond(&ctx, &msg, normalized, &user_name).await;
@@ -267,12 +393,96 @@ impl EventHandler for DiscordHandler {
         // ===== End Discord Hooks Integration =====
     }
 
-    async fn ready(&self, _ctx: Context, ready: Ready) {
+    async fn ready(&self, ctx: Context, ready: Ready) {
         log::info!("Discord: Bot connected as {}", ready.user.name);
+        discord_presence::apply_configured(&ctx, &self.db, self.channel_id);
+        self.health.connected();
+    }
+
+    /// Gateway connection changes: report drops and recoveries as channel health events
+    async fn shard_stage_update(&self, _ctx: Context, event: ShardStageUpdateEvent) {
+        match event.new {
+            ConnectionStage::Connected => self.health.connected(),
+            ConnectionStage::Connecting | ConnectionStage::Resuming => {
+                self.health
+                    .reconnecting(&format!("shard {} {:?} (was {:?})", event.shard_id, event.new, event.old));
+            }
+            _ => {}
+        }
+    }
+
+    /// Buttons and modals of the guided `configure` flow
+    async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
+        let result = match &interaction {
+            Interaction::Component(component)
+                if component.data.custom_id == discord_hooks::agent_config::OPEN_BUTTON_ID =>
+            {
+                discord_hooks::agent_config::handle_button(&ctx, component, &self.db, self.channel_id).await
+            }
+            Interaction::Modal(modal) if modal.data.custom_id == discord_hooks::agent_config::MODAL_ID => {
+                discord_hooks::agent_config::handle_modal(&ctx, modal, &self.db, self.channel_id).await
+            }
+            _ => Ok(()),
+        };
+        if let Err(e) = result {
+            log::error!("Discord: {}", e);
+        }
+    }
+
+    /// 📌 on one of the bot's messages pins it (when pinning is enabled for this channel)
+    async fn reaction_add(&self, ctx: Context, reaction: Reaction) {
+        if !discord_pins::is_pin_reaction(&reaction.emoji)
+            || !discord_pins::auto_pin_enabled(&self.db, self.channel_id)
+        {
+            return;
+        }
+
+        let message = match reaction.message(&ctx.http).await {
+            Ok(m) => m,
+            Err(e) => {
+                log::warn!("Discord: Failed to fetch reacted message {}: {}", reaction.message_id, e);
+                return;
+            }
+        };
+        if message.pinned {
+            return;
+        }
+        let bot_id = match ctx.http.get_current_user().await {
+            Ok(user) => user.id,
+            Err(e) => {
+                log::warn!("Discord: Failed to get bot user for pin reaction: {}", e);
+                return;
+            }
+        };
+        // Only the bot's own messages can be pinned this way
+        if message.author.id != bot_id {
+            return;
+        }
+
+        self.pin_and_report(&ctx, reaction.channel_id, message.id).await;
     }
 }
 
 impl DiscordHandler {
+    /// Pin a bot message, telling the channel if the pin limit prevents it
+    async fn pin_and_report(&self, ctx: &Context, channel: serenity::all::ChannelId, message_id: MessageId) {
+        match discord_pins::pin_message(&ctx.http, channel, message_id).await {
+            Ok(PinOutcome::Pinned) => log::info!("Discord: Pinned message {}", message_id),
+            Ok(PinOutcome::PinnedAfterUnpin(old)) => {
+                log::info!("Discord: Pinned message {} (unpinned older bot pin {})", message_id, old)
+            }
+            Ok(PinOutcome::LimitReached) => {
+                let _ = channel
+                    .say(
+                        &ctx.http,
+                        "📌 Couldn't pin that: this channel has reached Discord's pin limit. Unpin something first.",
+                    )
+                    .await;
+            }
+            Err(e) => log::warn!("Discord: Failed to pin message {}: {}", message_id, e),
+        }
+    }
+
     /// Dispatch a message to the AI and send the response
     async fn dispatch_and_respond(
         &self,
@@ -282,6 +492,9 @@ impl DiscordHandler {
         user_name: &str,
     ) {
         let verbosity = ToolOutputVerbosity::Minimal;
+        let tool_event_cap = max_tool_events(&self.db, self.channel_id);
+        let edit_mode = edit_mode_enabled(&self.db, self.channel_id);
+        let event_filter = forwarded_events(&self.db, self.channel_id);
 
         // Subscribe to events for real-time tool call forwarding
         let (client_id, mut event_rx) = self.broadcaster.subscribe();
@@ -299,6 +512,13 @@ impl DiscordHandler {
         let event_task = tokio::spawn(async move {
             // Track the status message ID - we'll edit this instead of sending new messages
             let mut status_message_id: Option<MessageId> = None;
+            // Set when the agent successfully calls pin_response this turn
+            let mut pin_requested = false;
+            // Tool events forwarded so far, and tool calls dropped once the cap is hit
+            let mut tool_events_forwarded = 0usize;
+            let mut tool_calls_suppressed = 0usize;
+            // Edit mode: everything shown so far in the current status message
+            let mut status_log = String::new();
 
             while let Some(event) = event_rx.recv().await {
                 if !util::event_matches_session(
@@ -309,6 +529,28 @@ impl DiscordHandler {
                     continue;
                 }
 
+                // Liveness pings are for the web UI's spinner, not for the chat
+                if event.event == "agent.heartbeat" {
+                    continue;
+                }
+                let forwarded = event_filter.allows(&event.event);
+
+                // Narration between tool rounds is part of the answer, so it gets its own
+                // messages rather than the (deleted) status message
+                if event.event == "agent.partial_response" {
+                    if !forwarded {
+                        continue;
+                    }
+                    let text = event.data.get("text").and_then(|v| v.as_str()).unwrap_or("");
+                    for chunk in util::split_message(text, 2000) {
+                        if let Err(e) = discord_channel_id.say(&http, &chunk).await {
+                            log::warn!("Discord: Failed to send partial response: {}", e);
+                            break;
+                        }
+                    }
+                    continue;
+                }
+
                 let message_text = match event.event.as_str() {
                     "agent.tool_call" => {
                         let tool_name = event.data.get("tool_name")
@@ -335,10 +577,29 @@ impl DiscordHandler {
                         // Skip say_to_user in event stream — content comes through result.response
                         if tool_name == "say_to_user" {
                             None
+                        } else if tool_name == discord_pins::PIN_RESPONSE_TOOL {
+                            pin_requested |= success;
+                            None
                         } else {
                             format_tool_result_for_discord(tool_name, success, duration_ms, content, verbosity)
                         }
                     }
+                    "subagent.progress" => {
+                        // Only announce tool starts; results arrive with subagent.completed
+                        if event.data.get("phase").and_then(|v| v.as_str()) != Some("tool_call")
+                            || matches!(verbosity, ToolOutputVerbosity::None)
+                        {
+                            None
+                        } else {
+                            let label = event.data.get("label")
+                                .and_then(|v| v.as_str())
+                                .unwrap_or("subagent");
+                            let tool_name = event.data.get("tool_name")
+                                .and_then(|v| v.as_str())
+                                .unwrap_or("unknown");
+                            Some(format!("🤖 Subagent '{}' is calling `{}`", label, tool_name))
+                        }
+                    }
                     "agent.mode_change" => {
                         // Skip mode changes in minimal/none verbosity
                         if matches!(verbosity, ToolOutputVerbosity::Minimal | ToolOutputVerbosity::None) {
@@ -383,15 +644,46 @@ impl DiscordHandler {
                     }
                     _ => None,
                 };
+                // Filtered after rendering so pin_response is still noticed
+                let message_text = message_text.filter(|_| forwarded);
+
+                let is_tool_event = matches!(
+                    event.event.as_str(),
+                    "agent.tool_call" | "tool.result" | "subagent.progress"
+                );
+                let message_text = match message_text {
+                    Some(_) if is_tool_event && tool_event_cap > 0 && tool_events_forwarded >= tool_event_cap => {
+                        // Results belong to calls already counted; only count the calls
+                        if event.event != "tool.result" {
+                            tool_calls_suppressed += 1;
+                        }
+                        None
+                    }
+                    Some(text) => {
+                        if is_tool_event {
+                            tool_events_forwarded += 1;
+                        }
+                        Some(text)
+                    }
+                    None => None,
+                };
 
                 if let Some(text) = message_text {
                     // Only use the first chunk if message is too long (status updates should be brief)
-                    let display_text = if text.len() > 2000 {
-                        format!("{}...", &text[..1997])
+                    let mut display_text = if text.len() > 2000 {
+                        util::truncate_str(&text, 1997)
                     } else {
                         text
                     };
 
+                    if edit_mode {
+                        // Keep the full message once it's full and start the next one
+                        if !append_to_status_log(&mut status_log, &display_text) {
+                            status_message_id = None;
+                        }
+                        display_text = status_log.clone();
+                    }
+
                     match status_message_id {
                         Some(msg_id) => {
                             // Try to edit the existing status message
@@ -432,38 +724,41 @@ impl DiscordHandler {
             }
 
             // Return the status message ID so we can clean it up after the response
-            status_message_id
+            (status_message_id, pin_requested, tool_calls_suppressed)
         });
 
         // Dispatch to AI
         log::info!("Discord: Dispatching message to AI for user {}", user_name);
+        let busy = self.busy_presence.begin(ctx, &self.db, self.channel_id);
         let result = self.dispatcher.dispatch(normalized).await;
+        drop(busy);
         log::info!("Discord: Dispatch complete, error={:?}", result.error);
 
         // Unsubscribe from events
         self.broadcaster.unsubscribe(&client_id);
 
         // Wait for the event task to finish processing, then get the status message ID
-        let status_message_id = match tokio::time::timeout(
+        let (status_message_id, pin_requested, tool_calls_suppressed) = match tokio::time::timeout(
             std::time::Duration::from_millis(2000),
             event_task,
         )
         .await
         {
-            Ok(Ok(id)) => id,
+            Ok(Ok(outcome)) => outcome,
             Ok(Err(e)) => {
                 log::warn!("Discord: Event task panicked: {}", e);
-                None
+                (None, false, 0)
             }
             Err(_) => {
                 log::warn!("Discord: Event task timed out — status message may not be deleted");
-                None
+                (None, false, 0)
             }
         };
 
         // Delete the status message now that we have the final response
-        // This keeps the chat clean - users see only their message and the final answer
-        if let Some(msg_id) = status_message_id {
+        // This keeps the chat clean - users see only their message and the final answer.
+        // In edit mode the status message is the tool log, so it stays.
+        if let Some(msg_id) = status_message_id.filter(|_| !edit_mode) {
             if let Err(e) = msg.channel_id.delete_message(&ctx.http, msg_id).await {
                 log::warn!("Discord: Failed to delete status message: {}", e);
             } else {
@@ -473,20 +768,49 @@ impl DiscordHandler {
 
         log::info!("Discord: Unsubscribed from events, client {}", client_id);
 
+        // One summary line in place of the tool events held back by the cap
+        if tool_calls_suppressed > 0 {
+            let summary = format!(
+                "🔧 {} more tool call{}…",
+                tool_calls_suppressed,
+                if tool_calls_suppressed == 1 { "" } else { "s" }
+            );
+            if let Err(e) = msg.channel_id.say(&ctx.http, &summary).await {
+                log::warn!("Discord: Failed to send tool event summary: {}", e);
+            }
+        }
+
         // Send final response
         if result.error.is_none() && !result.response.is_empty() {
             // Discord has a 2000 character limit per message
-            let response = &result.response;
-            let chunks = util::split_message(response, 2000);
+            let chunks = split_reply(&self.db, self.channel_id, &result.response);
 
+            // Pin the first part of the response so the pin jumps to its start
+            let mut first_sent: Option<MessageId> = None;
             for chunk in chunks {
-                if let Err(e) = msg.channel_id.say(&ctx.http, &chunk).await {
-                    log::error!("Failed to send Discord message: {}", e);
+                match msg.channel_id.say(&ctx.http, &chunk).await {
+                    Ok(sent) => {
+                        first_sent.get_or_insert(sent.id);
+                    }
+                    Err(e) => {
+                        // No point sending the remaining parts into a channel we can't post in
+                        if discord_permissions::report_send_failure(ctx, msg, "response", &e).await {
+                            break;
+                        }
+                    }
+                }
+            }
+
+            if let Some(sent_id) = first_sent.filter(|_| pin_requested) {
+                if discord_pins::auto_pin_enabled(&self.db, self.channel_id) {
+                    self.pin_and_report(ctx, msg.channel_id, sent_id).await;
                 }
             }
         } else if let Some(error) = result.error {
             let error_msg = format!("Sorry, I encountered an error: {}", error);
-            let _ = msg.channel_id.say(&ctx.http, &error_msg).await;
+            if let Err(e) = msg.channel_id.say(&ctx.http, &error_msg).await {
+                discord_permissions::report_send_failure(ctx, msg, "error message", &e).await;
+            }
         } else if result.response.is_empty() {
             log::debug!("Discord: Empty final response for user {}", user_name);
         }
@@ -512,7 +836,9 @@ pub async fn start_discord_listener(
     // Set up intents - we need message content to read messages
     let intents = GatewayIntents::GUILD_MESSAGES
         | GatewayIntents::DIRECT_MESSAGES
-        | GatewayIntents::MESSAGE_CONTENT;
+        | GatewayIntents::MESSAGE_CONTENT
+        | GatewayIntents::GUILD_MESSAGE_REACTIONS
+        | GatewayIntents::DIRECT_MESSAGE_REACTIONS;
 
     let handler = DiscordHandler {
         channel_id,
@@ -520,6 +846,9 @@ pub async fn start_discord_listener(
         broadcaster: broadcaster.clone(),
         db,
         safe_mode_rate_limiter,
+        busy_presence: BusyPresence::default(),
+        debouncer: MessageDebouncer::default(),
+        health: ConnectionHealth::new(channel_id, ChannelType::Discord.as_str(), broadcaster.clone()),
     };
 
     // Create client
@@ -0,0 +1,194 @@
+//! Saving Discord message attachments into the workspace
+//!
+//! When an admin attaches a text file (a script, config, CSV, ...) and asks the
+//! agent to work on it, the file is downloaded to
+//! `<workspace>/attachments/discord/<message_id>/<filename>` so the file and
+//! exec tools can open it. Only allowlisted text/code types under the size cap
+//! are saved; images are left to Discord. Saved files are swept once they're
+//! older than the TTL.
+
+use crate::disk_quota::DiskQuotaManager;
+use serenity::all::Attachment;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Directory under the workspace that holds saved attachments
+const ATTACHMENTS_DIR: &str = "attachments/discord";
+
+/// File extensions that are saved (lowercase, without the dot)
+const ALLOWED_EXTENSIONS: &[&str] = &[
+    "txt", "md", "log", "csv", "tsv", "json", "jsonl", "yaml", "yml", "toml", "ini", "cfg", "conf", "xml",
+    "html", "css", "sql", "sh", "bash", "py", "js", "mjs", "ts", "tsx", "jsx", "rs", "go", "java", "kt",
+    "c", "h", "cpp", "hpp", "rb", "php", "sol", "cairo", "lua", "diff", "patch",
+];
+
+/// Outcome of saving one message's attachments
+#[derive(Debug, Default)]
+pub struct SavedAttachments {
+    /// Workspace-relative paths of the saved files
+    pub paths: Vec<String>,
+    /// One line per attachment that wasn't saved, with the reason
+    pub skipped: Vec<String>,
+}
+
+/// Whether a file with this name and content type may be saved
+fn is_allowed(filename: &str, content_type: Option<&str>) -> bool {
+    if content_type.is_some_and(|ct| ct.starts_with("image/") || ct.starts_with("video/") || ct.starts_with("audio/")) {
+        return false;
+    }
+    Path::new(filename)
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| ALLOWED_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+/// Reduce an uploaded filename to a safe single path component
+fn sanitize_filename(filename: &str) -> String {
+    let base = filename.rsplit(['/', '\\']).next().unwrap_or(filename);
+    let cleaned: String = base
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') { c } else { '_' })
+        .collect();
+    let cleaned = cleaned.trim_start_matches('.');
+    if cleaned.is_empty() {
+        "attachment.txt".to_string()
+    } else {
+        cleaned.to_string()
+    }
+}
+
+/// Download the message's allowlisted attachments into the workspace, counting them
+/// against the channel's disk quota when one is configured
+pub async fn save_attachments(
+    attachments: &[Attachment],
+    message_id: &str,
+    channel_id: i64,
+    disk_quota: Option<&DiskQuotaManager>,
+) -> SavedAttachments {
+    let mut saved = SavedAttachments::default();
+    if attachments.is_empty() {
+        return saved;
+    }
+
+    let workspace = PathBuf::from(crate::config::workspace_dir());
+    let root = workspace.join(ATTACHMENTS_DIR);
+    let ttl = Duration::from_secs(crate::config::attachment_ttl_hours() * 3600);
+    remove_expired(&root, ttl, SystemTime::now());
+
+    let max_bytes = crate::config::attachment_max_bytes();
+    let dir = root.join(sanitize_filename(message_id));
+
+    for attachment in attachments {
+        if !is_allowed(&attachment.filename, attachment.content_type.as_deref()) {
+            saved.skipped.push(format!("{}: file type not accepted", attachment.filename));
+            continue;
+        }
+        if attachment.size as u64 > max_bytes {
+            saved.skipped.push(format!(
+                "{}: {} bytes is over the {} byte limit",
+                attachment.filename, attachment.size, max_bytes
+            ));
+            continue;
+        }
+
+        let bytes = match attachment.download().await {
+            Ok(b) if b.len() as u64 <= max_bytes => b,
+            Ok(_) => {
+                saved.skipped.push(format!("{}: over the {} byte limit", attachment.filename, max_bytes));
+                continue;
+            }
+            Err(e) => {
+                log::warn!("[DISCORD_ATTACHMENTS] Failed to download {}: {}", attachment.filename, e);
+                saved.skipped.push(format!("{}: download failed", attachment.filename));
+                continue;
+            }
+        };
+
+        if let Some(Err(e)) = disk_quota.map(|dq| dq.check_channel_quota(channel_id, bytes.len() as u64)) {
+            saved.skipped.push(format!("{}: {}", attachment.filename, e));
+            continue;
+        }
+
+        let filename = sanitize_filename(&attachment.filename);
+        let path = dir.join(&filename);
+        if let Err(e) = tokio::fs::create_dir_all(&dir).await {
+            log::error!("[DISCORD_ATTACHMENTS] Failed to create {}: {}", dir.display(), e);
+            saved.skipped.push(format!("{}: could not be saved", attachment.filename));
+            continue;
+        }
+        if let Err(e) = tokio::fs::write(&path, &bytes).await {
+            log::error!("[DISCORD_ATTACHMENTS] Failed to write {}: {}", path.display(), e);
+            saved.skipped.push(format!("{}: could not be saved", attachment.filename));
+            continue;
+        }
+
+        if let Some(dq) = disk_quota {
+            dq.record_channel_write(channel_id, &path, bytes.len() as u64);
+        }
+
+        let relative = format!("{}/{}/{}", ATTACHMENTS_DIR, sanitize_filename(message_id), filename);
+        log::info!("[DISCORD_ATTACHMENTS] Saved {} ({} bytes)", relative, bytes.len());
+        saved.paths.push(relative);
+    }
+    saved
+}
+
+/// Delete per-message attachment directories last modified more than `ttl` before `now`
+fn remove_expired(root: &Path, ttl: Duration, now: SystemTime) {
+    let Ok(entries) = std::fs::read_dir(root) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let expired = entry
+            .metadata()
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok())
+            .is_some_and(|age| age > ttl);
+        if expired {
+            match std::fs::remove_dir_all(entry.path()) {
+                Ok(()) => log::debug!("[DISCORD_ATTACHMENTS] Removed expired {}", entry.path().display()),
+                Err(e) => log::warn!("[DISCORD_ATTACHMENTS] Failed to remove {}: {}", entry.path().display(), e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allowlist() {
+        assert!(is_allowed("fix_me.py", Some("text/x-python")));
+        assert!(is_allowed("DATA.CSV", None));
+        assert!(!is_allowed("photo.png", Some("image/png")));
+        assert!(!is_allowed("notes.txt", Some("image/png")));
+        assert!(!is_allowed("tool.exe", Some("application/octet-stream")));
+        assert!(!is_allowed("Makefile", None));
+    }
+
+    #[test]
+    fn test_sanitize_filename() {
+        assert_eq!(sanitize_filename("../../etc/passwd.txt"), "passwd.txt");
+        assert_eq!(sanitize_filename("my script (1).sh"), "my_script__1_.sh");
+        assert_eq!(sanitize_filename(".env"), "env");
+        assert_eq!(sanitize_filename(".."), "attachment.txt");
+    }
+
+    #[test]
+    fn test_remove_expired() {
+        let root = std::env::temp_dir().join(format!("stark-attachments-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(root.join("111")).unwrap();
+        std::fs::write(root.join("111/a.txt"), "x").unwrap();
+
+        remove_expired(&root, Duration::from_secs(3600), SystemTime::now());
+        assert!(root.join("111").exists());
+
+        let later = SystemTime::now() + Duration::from_secs(7200);
+        remove_expired(&root, Duration::from_secs(3600), later);
+        assert!(!root.join("111").exists());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
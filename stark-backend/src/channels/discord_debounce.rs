@@ -0,0 +1,221 @@
+//! Coalesce rapid-fire Discord messages into a single dispatch.
+//!
+//! With `discord_debounce_ms` set, the first message from a user in a Discord
+//! channel waits for the window to pass without a follow-up; messages arriving
+//! meanwhile are appended to it (exact repeats are dropped) and their handlers
+//! return early. Attachments saved for any message in the burst travel with it.
+//! Each follow-up restarts the window, but a buffer is always flushed after
+//! `MAX_HOLD` or `MAX_BUFFERED_MESSAGES`, so a chatty user can't hold their
+//! messages back indefinitely.
+
+use crate::db::Database;
+use crate::models::channel_settings::MAX_DISCORD_DEBOUNCE_MS;
+use crate::models::ChannelSettingKey;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Longest a buffer is held after its first message, however often it's extended
+const MAX_HOLD: Duration = Duration::from_secs(10);
+
+/// A buffer holding this many messages is flushed without waiting out the window
+const MAX_BUFFERED_MESSAGES: usize = 20;
+
+/// Debounce window for a channel; `None` when debouncing is off
+pub fn debounce_window(db: &Database, channel_id: i64) -> Option<Duration> {
+    db.get_channel_setting(channel_id, ChannelSettingKey::DiscordDebounceMs.as_ref())
+        .ok()
+        .flatten()
+        .and_then(|s| parse_window(&s))
+}
+
+fn parse_window(value: &str) -> Option<Duration> {
+    match value.trim().parse::<u64>() {
+        Ok(0) | Err(_) => None,
+        Ok(ms) => Some(Duration::from_millis(ms.min(MAX_DISCORD_DEBOUNCE_MS as u64))),
+    }
+}
+
+/// Buffer key: (Discord channel ID, user ID)
+type BufferKey = (u64, String);
+
+/// Messages flushed together for a single dispatch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Burst {
+    /// Every buffered message, newline-separated
+    pub text: String,
+    /// Workspace paths of attachments saved for any message in the burst
+    pub attachments: Vec<String>,
+}
+
+struct PendingBuffer {
+    texts: Vec<String>,
+    attachments: Vec<String>,
+    started_at: Instant,
+    last_message_at: Instant,
+}
+
+impl PendingBuffer {
+    fn new(text: String, attachments: Vec<String>, now: Instant) -> Self {
+        Self {
+            texts: vec![text],
+            attachments,
+            started_at: now,
+            last_message_at: now,
+        }
+    }
+
+    /// Add a follow-up message, skipping an exact repeat of the previous one's text.
+    /// Its attachments are always kept.
+    fn push(&mut self, text: String, attachments: Vec<String>, now: Instant) {
+        self.last_message_at = now;
+        self.attachments.extend(attachments);
+        if self.texts.last().map(|t| t.trim()) != Some(text.trim()) {
+            self.texts.push(text);
+        }
+    }
+
+    /// When the buffer should be flushed: a quiet window after the latest message,
+    /// capped by the hold limit. Immediately once it is full.
+    fn flush_at(&self, window: Duration) -> Instant {
+        if self.texts.len() >= MAX_BUFFERED_MESSAGES {
+            return self.last_message_at;
+        }
+        (self.last_message_at + window).min(self.started_at + MAX_HOLD)
+    }
+}
+
+/// Per-listener buffers of messages waiting to be dispatched
+#[derive(Default)]
+pub struct MessageDebouncer {
+    pending: Mutex<HashMap<BufferKey, PendingBuffer>>,
+}
+
+impl MessageDebouncer {
+    /// Buffer `text` and the message's saved `attachments` for `(chat_id, user_id)`.
+    ///
+    /// Returns `None` when the message joined a buffer that an earlier call is
+    /// already waiting on. Otherwise waits until the user goes quiet for
+    /// `window` (or a flush limit is hit) and returns the whole burst for a
+    /// single dispatch.
+    pub async fn collect(
+        &self,
+        chat_id: u64,
+        user_id: &str,
+        text: String,
+        attachments: Vec<String>,
+        window: Duration,
+    ) -> Option<Burst> {
+        let key = (chat_id, user_id.to_string());
+        {
+            let mut pending = self.pending.lock();
+            if let Some(buffer) = pending.get_mut(&key) {
+                buffer.push(text, attachments, Instant::now());
+                return None;
+            }
+            pending.insert(key.clone(), PendingBuffer::new(text, attachments, Instant::now()));
+        }
+
+        loop {
+            let flush_at = match self.pending.lock().get(&key) {
+                Some(buffer) => buffer.flush_at(window),
+                None => return None,
+            };
+            if Instant::now() >= flush_at {
+                let buffer = self.pending.lock().remove(&key)?;
+                if buffer.texts.len() > 1 {
+                    log::info!(
+                        "Discord: Coalesced {} messages from user {} into one dispatch",
+                        buffer.texts.len(),
+                        user_id
+                    );
+                }
+                return Some(Burst {
+                    text: buffer.texts.join("\n"),
+                    attachments: buffer.attachments,
+                });
+            }
+            tokio::time::sleep_until(flush_at).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_parse_window() {
+        assert_eq!(parse_window("0"), None);
+        assert_eq!(parse_window(""), None);
+        assert_eq!(parse_window("abc"), None);
+        assert_eq!(parse_window(" 1500 "), Some(Duration::from_millis(1500)));
+        assert_eq!(parse_window("60000"), Some(Duration::from_millis(MAX_DISCORD_DEBOUNCE_MS as u64)));
+    }
+
+    #[test]
+    fn test_flush_at_is_capped() {
+        let start = Instant::now();
+        let window = Duration::from_millis(1500);
+        let mut buffer = PendingBuffer::new("hi".into(), Vec::new(), start);
+        assert_eq!(buffer.flush_at(window), start + window);
+
+        // Follow-ups extend the window, but never past the hold limit
+        buffer.push("again".into(), Vec::new(), start + Duration::from_secs(9));
+        assert_eq!(buffer.flush_at(window), start + MAX_HOLD);
+
+        // Repeats are dropped; a full buffer flushes right away
+        buffer.push("again".into(), Vec::new(), start + Duration::from_secs(9));
+        assert_eq!(buffer.texts, vec!["hi", "again"]);
+        for i in 0..MAX_BUFFERED_MESSAGES {
+            buffer.push(format!("msg {}", i), Vec::new(), start + Duration::from_secs(1));
+        }
+        assert_eq!(buffer.flush_at(window), start + Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_collect_coalesces_messages() {
+        let debouncer = Arc::new(MessageDebouncer::default());
+        let window = Duration::from_millis(100);
+
+        let first = {
+            let debouncer = debouncer.clone();
+            tokio::spawn(async move { debouncer.collect(1, "u1", "hello".into(), Vec::new(), window).await })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(debouncer.collect(1, "u1", "are you there?".into(), Vec::new(), window).await, None);
+        // A different user in the same channel gets their own buffer
+        assert_eq!(
+            debouncer.collect(1, "u2", "hi".into(), Vec::new(), window).await.map(|b| b.text).as_deref(),
+            Some("hi")
+        );
+
+        let burst = first.await.unwrap().unwrap();
+        assert_eq!(burst.text, "hello\nare you there?");
+        assert!(debouncer.pending.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_attachments_on_earlier_messages_survive_the_burst() {
+        let debouncer = Arc::new(MessageDebouncer::default());
+        let window = Duration::from_millis(100);
+        let path = "attachments/discord/1/script.py".to_string();
+
+        let first = {
+            let debouncer = debouncer.clone();
+            let attachments = vec![path.clone()];
+            tokio::spawn(async move { debouncer.collect(1, "u1", "check this".into(), attachments, window).await })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        // A repeated text is dropped, but its attachment still joins the burst
+        let second = vec!["attachments/discord/2/data.csv".to_string()];
+        assert_eq!(debouncer.collect(1, "u1", "check this".into(), second.clone(), window).await, None);
+        assert_eq!(debouncer.collect(1, "u1", "what's wrong?".into(), Vec::new(), window).await, None);
+
+        let burst = first.await.unwrap().unwrap();
+        assert_eq!(burst.text, "check this\nwhat's wrong?");
+        assert_eq!(burst.attachments, vec![path, second[0].clone()]);
+    }
+}
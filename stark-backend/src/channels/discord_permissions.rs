@@ -0,0 +1,221 @@
+//! Diagnosing Discord permission failures when the bot replies.
+//!
+//! A reply that fails with "Missing Access" / "Missing Permissions" (usually the bot
+//! was added to a server without Send Messages in that channel) is otherwise only a
+//! log line nobody reads. Instead we work out which permission is missing, log it
+//! clearly, and DM the user who was waiting for the reply so they can tell an admin.
+
+use once_cell::sync::Lazy;
+use serenity::all::{
+    ChannelId, Context, CreateMessage, GuildChannel, HttpError, Message, PermissionOverwrite,
+    PermissionOverwriteType, Permissions, RoleId, UserId,
+};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Discord JSON error code: "Missing Access" (can't see the channel)
+const MISSING_ACCESS_CODE: isize = 50001;
+/// Discord JSON error code: "Missing Permissions"
+const MISSING_PERMISSIONS_CODE: isize = 50013;
+
+/// Don't DM the same user about the same channel more often than this
+const DM_COOLDOWN: Duration = Duration::from_secs(600);
+
+/// Last DM per (discord channel, user), for the cooldown
+static LAST_NOTICE: Lazy<Mutex<HashMap<(u64, u64), Instant>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Whether a send failed because the bot lacks access or permissions in the channel
+pub fn is_permission_error(e: &serenity::Error) -> bool {
+    match e {
+        serenity::Error::Http(HttpError::UnsuccessfulRequest(resp)) => {
+            resp.status_code.as_u16() == 403
+                || matches!(resp.error.code, MISSING_ACCESS_CODE | MISSING_PERMISSIONS_CODE)
+        }
+        _ => false,
+    }
+}
+
+/// Permissions needed to post in a channel (threads use their own send permission)
+fn required_send_permissions(channel: &GuildChannel) -> Permissions {
+    if channel.thread_metadata.is_some() {
+        Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES_IN_THREADS
+    } else {
+        Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES
+    }
+}
+
+/// Effective permissions of a member in a channel: guild-level role permissions, then
+/// channel overwrites (@everyone, then roles, then the member), per Discord's rules
+fn channel_permissions(
+    is_owner: bool,
+    everyone_role: RoleId,
+    role_permissions: &[(RoleId, Permissions)],
+    member_id: UserId,
+    member_roles: &[RoleId],
+    overwrites: &[PermissionOverwrite],
+) -> Permissions {
+    if is_owner {
+        return Permissions::all();
+    }
+    let mut perms = role_permissions
+        .iter()
+        .filter(|(id, _)| *id == everyone_role || member_roles.contains(id))
+        .fold(Permissions::empty(), |acc, (_, p)| acc | *p);
+    if perms.contains(Permissions::ADMINISTRATOR) {
+        return Permissions::all();
+    }
+
+    let overwrite_for = |kind: PermissionOverwriteType| overwrites.iter().find(|o| o.kind == kind);
+    if let Some(o) = overwrite_for(PermissionOverwriteType::Role(everyone_role)) {
+        perms = (perms - o.deny) | o.allow;
+    }
+    let (mut role_allow, mut role_deny) = (Permissions::empty(), Permissions::empty());
+    for role in member_roles {
+        if let Some(o) = overwrite_for(PermissionOverwriteType::Role(*role)) {
+            role_allow |= o.allow;
+            role_deny |= o.deny;
+        }
+    }
+    perms = (perms - role_deny) | role_allow;
+    if let Some(o) = overwrite_for(PermissionOverwriteType::Member(member_id)) {
+        perms = (perms - o.deny) | o.allow;
+    }
+    perms
+}
+
+/// The send permissions the bot is missing in this channel, if they can be worked out
+/// (needs the channel, guild and bot member to be readable; `None` otherwise)
+async fn missing_send_permissions(ctx: &Context, channel_id: ChannelId) -> Option<Permissions> {
+    let channel = channel_id.to_channel(&ctx.http).await.ok()?.guild()?;
+    let bot_id = ctx.http.get_current_user().await.ok()?.id;
+    let member = channel.guild_id.member(&ctx.http, bot_id).await.ok()?;
+    let guild = ctx.http.get_guild(channel.guild_id).await.ok()?;
+
+    let role_permissions: Vec<(RoleId, Permissions)> =
+        guild.roles.iter().map(|(id, role)| (*id, role.permissions)).collect();
+    let present = channel_permissions(
+        guild.owner_id == bot_id,
+        RoleId::new(guild.id.get()),
+        &role_permissions,
+        bot_id,
+        &member.roles,
+        &channel.permission_overwrites,
+    );
+    let missing = required_send_permissions(&channel) - present;
+    (!missing.is_empty()).then_some(missing)
+}
+
+/// Human-readable permission list, e.g. "View Channel, Send Messages"
+fn describe(permissions: Permissions) -> String {
+    permissions.get_permission_names().join(", ")
+}
+
+/// Log a failed send and, for permission failures, tell the user by DM which
+/// permission the bot is missing. Returns true for permission failures, so callers
+/// can stop trying to post more to the same channel.
+pub async fn report_send_failure(ctx: &Context, msg: &Message, what: &str, error: &serenity::Error) -> bool {
+    if !is_permission_error(error) {
+        log::error!("Discord: Failed to send {}: {}", what, error);
+        return false;
+    }
+
+    let channel_id = msg.channel_id;
+    let missing = missing_send_permissions(ctx, channel_id).await;
+    let missing_text = match missing {
+        Some(p) => describe(p),
+        None => "Send Messages (or View Channel)".to_string(),
+    };
+    log::error!(
+        "Discord: No permission to send {} in channel {} (guild {:?}): missing {} — {}",
+        what,
+        channel_id,
+        msg.guild_id,
+        missing_text,
+        error
+    );
+
+    let key = (channel_id.get(), msg.author.id.get());
+    let notify = {
+        let mut last = LAST_NOTICE.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        let due = last.get(&key).is_none_or(|t| now.duration_since(*t) >= DM_COOLDOWN);
+        if due {
+            last.insert(key, now);
+        }
+        due
+    };
+    if notify {
+        let notice = format!(
+            "I couldn't reply to you in <#{}> because I'm missing the **{}** permission there. \
+             Please ask a server admin to grant it to my role in that channel.",
+            channel_id, missing_text
+        );
+        if let Err(e) = msg.author.direct_message(ctx, CreateMessage::new().content(notice)).await {
+            log::warn!("Discord: Could not DM {} about missing permissions: {}", msg.author.name, e);
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GUILD: u64 = 100;
+
+    fn overwrite(kind: PermissionOverwriteType, allow: Permissions, deny: Permissions) -> PermissionOverwrite {
+        PermissionOverwrite { allow, deny, kind }
+    }
+
+    #[test]
+    fn test_channel_overwrites_can_remove_send_messages() {
+        let everyone = RoleId::new(GUILD);
+        let bot_role = RoleId::new(7);
+        let bot = UserId::new(42);
+        let roles = [
+            (everyone, Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES),
+            (bot_role, Permissions::EMBED_LINKS),
+        ];
+
+        // No overwrites: inherits @everyone
+        let perms = channel_permissions(false, everyone, &roles, bot, &[bot_role], &[]);
+        assert!(perms.contains(Permissions::SEND_MESSAGES | Permissions::EMBED_LINKS));
+
+        // Read-only channel: @everyone denied Send Messages
+        let read_only = [overwrite(
+            PermissionOverwriteType::Role(everyone),
+            Permissions::empty(),
+            Permissions::SEND_MESSAGES,
+        )];
+        let perms = channel_permissions(false, everyone, &roles, bot, &[bot_role], &read_only);
+        assert_eq!(
+            (Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES) - perms,
+            Permissions::SEND_MESSAGES
+        );
+
+        // ...unless the bot's role is allowed back in
+        let mut with_role_allow = read_only.to_vec();
+        with_role_allow.push(overwrite(
+            PermissionOverwriteType::Role(bot_role),
+            Permissions::SEND_MESSAGES,
+            Permissions::empty(),
+        ));
+        let perms = channel_permissions(false, everyone, &roles, bot, &[bot_role], &with_role_allow);
+        assert!(perms.contains(Permissions::SEND_MESSAGES));
+
+        // A member overwrite wins over role overwrites
+        with_role_allow.push(overwrite(
+            PermissionOverwriteType::Member(bot),
+            Permissions::empty(),
+            Permissions::SEND_MESSAGES,
+        ));
+        let perms = channel_permissions(false, everyone, &roles, bot, &[bot_role], &with_role_allow);
+        assert!(!perms.contains(Permissions::SEND_MESSAGES));
+
+        // Administrators and owners bypass overwrites
+        let admin_roles = [(everyone, Permissions::ADMINISTRATOR)];
+        assert!(channel_permissions(false, everyone, &admin_roles, bot, &[], &read_only).contains(Permissions::SEND_MESSAGES));
+        assert!(channel_permissions(true, everyone, &[], bot, &[], &read_only).contains(Permissions::SEND_MESSAGES));
+    }
+}
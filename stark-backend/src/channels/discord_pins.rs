@@ -0,0 +1,101 @@
+//! Pinning important bot responses in Discord.
+//!
+//! Opt-in per channel via the `discord_auto_pin` setting. A response is pinned
+//! when the agent calls the `pin_response` tool during the turn, or when a user
+//! reacts to one of the bot's messages with 📌. Discord caps pins per channel;
+//! when the cap is hit, the oldest pin made by the bot is unpinned to make room.
+
+use crate::db::Database;
+use crate::models::ChannelSettingKey;
+use serenity::all::{ChannelId, Http, HttpError, MessageId, ReactionType};
+
+/// Name of the tool the agent calls to request pinning its final response
+pub const PIN_RESPONSE_TOOL: &str = "pin_response";
+
+/// Reaction users add to a bot message to pin it
+pub const PIN_EMOJI: &str = "📌";
+
+/// Discord JSON error code: "Maximum number of pins reached"
+const MAX_PINS_ERROR_CODE: isize = 30003;
+
+/// Result of a pin attempt
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PinOutcome {
+    Pinned,
+    /// Pinned after unpinning this older bot message to stay under the limit
+    PinnedAfterUnpin(MessageId),
+    /// The channel is at the pin limit and none of the pins are the bot's own
+    LimitReached,
+}
+
+/// Whether pinning is enabled for this channel (off unless the setting is "true")
+pub fn auto_pin_enabled(db: &Database, channel_id: i64) -> bool {
+    db.get_channel_setting(channel_id, ChannelSettingKey::DiscordAutoPin.as_ref())
+        .ok()
+        .flatten()
+        .map(|v| v.trim().eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+/// Whether a reaction is the pin emoji (with or without the emoji variation selector)
+pub fn is_pin_reaction(emoji: &ReactionType) -> bool {
+    match emoji {
+        ReactionType::Unicode(s) => s.trim_end_matches('\u{FE0F}') == PIN_EMOJI,
+        _ => false,
+    }
+}
+
+fn is_max_pins_error(e: &serenity::Error) -> bool {
+    match e {
+        serenity::Error::Http(HttpError::UnsuccessfulRequest(resp)) => resp.error.code == MAX_PINS_ERROR_CODE,
+        _ => false,
+    }
+}
+
+/// Pin a message, freeing a slot by unpinning the bot's oldest pin if the channel is full
+pub async fn pin_message(http: &Http, channel: ChannelId, message: MessageId) -> Result<PinOutcome, String> {
+    match channel.pin(http, message).await {
+        Ok(()) => return Ok(PinOutcome::Pinned),
+        Err(e) if is_max_pins_error(&e) => {
+            log::info!("Discord: Pin limit reached in channel {}, looking for an old bot pin to replace", channel);
+        }
+        Err(e) => return Err(format!("Failed to pin message: {}", e)),
+    }
+
+    let bot_id = http
+        .get_current_user()
+        .await
+        .map_err(|e| format!("Failed to get bot user: {}", e))?
+        .id;
+    let pins = channel
+        .pins(http)
+        .await
+        .map_err(|e| format!("Failed to list pins: {}", e))?;
+
+    // Pins are returned newest first; only ever unpin the bot's own messages
+    let oldest_bot_pin = match pins.iter().rev().find(|m| m.author.id == bot_id) {
+        Some(m) => m.id,
+        None => return Ok(PinOutcome::LimitReached),
+    };
+    channel
+        .unpin(http, oldest_bot_pin)
+        .await
+        .map_err(|e| format!("Failed to unpin old message: {}", e))?;
+    channel
+        .pin(http, message)
+        .await
+        .map_err(|e| format!("Failed to pin message: {}", e))?;
+    Ok(PinOutcome::PinnedAfterUnpin(oldest_bot_pin))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_pin_reaction() {
+        assert!(is_pin_reaction(&ReactionType::Unicode("📌".to_string())));
+        assert!(is_pin_reaction(&ReactionType::Unicode("📌\u{FE0F}".to_string())));
+        assert!(!is_pin_reaction(&ReactionType::Unicode("👍".to_string())));
+    }
+}
@@ -0,0 +1,127 @@
+//! Discord bot presence (the activity line shown in the member list).
+//!
+//! `discord_presence` sets the activity shown while the bot is online, e.g.
+//! "Watching for @mentions". A leading "Playing", "Watching", "Listening to" or
+//! "Competing in" picks that activity type; anything else becomes a custom status.
+//! With `discord_busy_presence` on, the bot switches to an idle "Busy processing..."
+//! status while any dispatch is running and restores the normal presence afterwards.
+
+use crate::db::Database;
+use crate::models::ChannelSettingKey;
+use serenity::all::{ActivityData, Context, OnlineStatus};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Activity text shown while a dispatch is running
+pub const BUSY_ACTIVITY: &str = "Busy processing...";
+
+/// Discord's limit on activity text
+const MAX_ACTIVITY_CHARS: usize = 128;
+
+/// Parse presence text into an activity; `None` for blank text
+pub fn parse_activity(text: &str) -> Option<ActivityData> {
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    let text: String = text.chars().take(MAX_ACTIVITY_CHARS).collect();
+    let lower = text.to_lowercase();
+
+    let prefixes: [(&str, fn(String) -> ActivityData); 4] = [
+        ("playing ", ActivityData::playing),
+        ("watching ", ActivityData::watching),
+        ("listening to ", ActivityData::listening),
+        ("competing in ", ActivityData::competing),
+    ];
+    for (prefix, build) in prefixes {
+        // Prefixes are ASCII, so the byte offset is valid in the original text too
+        if lower.starts_with(prefix) && text.len() > prefix.len() {
+            return Some(build(text[prefix.len()..].trim().to_string()));
+        }
+    }
+    Some(ActivityData::custom(text))
+}
+
+/// The configured presence activity for this channel, if any
+pub fn configured_activity(db: &Database, channel_id: i64) -> Option<ActivityData> {
+    db.get_channel_setting(channel_id, ChannelSettingKey::DiscordPresence.as_ref())
+        .ok()
+        .flatten()
+        .and_then(|text| parse_activity(&text))
+}
+
+/// Whether the busy status is enabled for this channel (off unless the setting is "true")
+pub fn busy_presence_enabled(db: &Database, channel_id: i64) -> bool {
+    db.get_channel_setting(channel_id, ChannelSettingKey::DiscordBusyPresence.as_ref())
+        .ok()
+        .flatten()
+        .map(|v| v.trim().eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+/// Apply the configured presence (called on ready and when the last dispatch finishes)
+pub fn apply_configured(ctx: &Context, db: &Database, channel_id: i64) {
+    ctx.set_presence(configured_activity(db, channel_id), OnlineStatus::Online);
+}
+
+/// Counts in-flight dispatches so overlapping messages share one busy period
+#[derive(Default)]
+pub struct BusyPresence {
+    in_flight: AtomicUsize,
+}
+
+impl BusyPresence {
+    /// Mark a dispatch as started; the returned guard restores the normal
+    /// presence when the last overlapping dispatch ends. `None` if disabled.
+    pub fn begin(&self, ctx: &Context, db: &Database, channel_id: i64) -> Option<BusyGuard<'_>> {
+        if !busy_presence_enabled(db, channel_id) {
+            return None;
+        }
+        if self.in_flight.fetch_add(1, Ordering::SeqCst) == 0 {
+            ctx.set_presence(Some(ActivityData::custom(BUSY_ACTIVITY)), OnlineStatus::Idle);
+        }
+        Some(BusyGuard {
+            state: self,
+            ctx: ctx.clone(),
+            idle_activity: configured_activity(db, channel_id),
+        })
+    }
+}
+
+/// Held for the duration of one dispatch
+pub struct BusyGuard<'a> {
+    state: &'a BusyPresence,
+    ctx: Context,
+    idle_activity: Option<ActivityData>,
+}
+
+impl Drop for BusyGuard<'_> {
+    fn drop(&mut self) {
+        if self.state.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.ctx.set_presence(self.idle_activity.take(), OnlineStatus::Online);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serenity::all::ActivityType;
+
+    #[test]
+    fn test_parse_activity() {
+        let watching = parse_activity("Watching for @mentions").unwrap();
+        assert_eq!(watching.kind, ActivityType::Watching);
+        assert_eq!(watching.name, "for @mentions");
+
+        let listening = parse_activity("listening to the market").unwrap();
+        assert_eq!(listening.kind, ActivityType::Listening);
+        assert_eq!(listening.name, "the market");
+
+        let custom = parse_activity("Ready to help").unwrap();
+        assert_eq!(custom.kind, ActivityType::Custom);
+
+        // A bare verb is treated as custom text rather than an empty activity
+        assert_eq!(parse_activity("Playing").unwrap().kind, ActivityType::Custom);
+        assert!(parse_activity("   ").is_none());
+    }
+}
@@ -0,0 +1,128 @@
+//! Context for Discord replies.
+//!
+//! When a user mentions the bot in a reply, the agent needs to know what they
+//! are replying to. Discord usually includes the replied-to message in the event
+//! (`referenced_message`); when it doesn't, it is fetched through the message
+//! reference. The result is a quoted, truncated block that is clearly labelled
+//! as context so the model doesn't mistake it for the user's request.
+
+use crate::channels::util;
+use serenity::all::{Context, Message, MessageType};
+
+/// Longest excerpt of the replied-to message that is quoted, in bytes
+const MAX_QUOTE_BYTES: usize = 600;
+
+/// Most quoted lines; long multi-line messages are cut after this many
+const MAX_QUOTE_LINES: usize = 12;
+
+/// The message `msg` replies to, if it is a reply and the original still exists
+async fn referenced_message(ctx: &Context, msg: &Message) -> Option<Message> {
+    if msg.kind != MessageType::InlineReply {
+        return None;
+    }
+    if let Some(replied) = &msg.referenced_message {
+        return Some((**replied).clone());
+    }
+
+    // Discord leaves `referenced_message` out when it didn't resolve the reply;
+    // fall back to fetching it (it may have been deleted)
+    let reference = msg.message_reference.as_ref()?;
+    let message_id = reference.message_id?;
+    match reference.channel_id.message(&ctx.http, message_id).await {
+        Ok(replied) => Some(replied),
+        Err(e) => {
+            log::debug!("Discord: Could not fetch replied-to message {}: {}", message_id, e);
+            None
+        }
+    }
+}
+
+/// Quoted context block for a reply, or None when `msg` isn't a reply
+pub async fn reply_context(ctx: &Context, msg: &Message) -> Option<String> {
+    let replied = referenced_message(ctx, msg).await?;
+
+    // Bot answers are sometimes embed-only; quote the embed text instead
+    let content = if replied.content.trim().is_empty() {
+        replied
+            .embeds
+            .iter()
+            .filter_map(|e| e.description.clone().or_else(|| e.title.clone()))
+            .collect::<Vec<_>>()
+            .join("\n")
+    } else {
+        replied.content.clone()
+    };
+
+    Some(format_reply_context(
+        &replied.author.name,
+        replied.author.bot,
+        &content,
+        replied.attachments.len(),
+    ))
+}
+
+/// Format the replied-to message as a labelled, quoted block ending in a blank line
+pub fn format_reply_context(author: &str, from_bot: bool, content: &str, attachments: usize) -> String {
+    let excerpt = util::truncate_str(content.trim(), MAX_QUOTE_BYTES);
+    let mut lines: Vec<&str> = excerpt.lines().collect();
+    let cut_lines = lines.len() > MAX_QUOTE_LINES;
+    lines.truncate(MAX_QUOTE_LINES);
+
+    let mut block = format!(
+        "[REPLYING TO @{}{} - quoted for context only, not part of the user's request:]\n",
+        author,
+        if from_bot { " [you]" } else { "" }
+    );
+    for line in &lines {
+        block.push_str("> ");
+        block.push_str(line);
+        block.push('\n');
+    }
+    if cut_lines {
+        block.push_str("> ...\n");
+    }
+    if lines.is_empty() && attachments == 0 {
+        block.push_str("> (no text)\n");
+    }
+    if attachments > 0 {
+        block.push_str(&format!(
+            "> [{} attachment{}]\n",
+            attachments,
+            if attachments == 1 { "" } else { "s" }
+        ));
+    }
+    block.push('\n');
+    block
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_reply_context_quotes_and_labels() {
+        let block = format_reply_context("alice", false, "gm\nwhat's the ETH price?", 0);
+        assert_eq!(
+            block,
+            "[REPLYING TO @alice - quoted for context only, not part of the user's request:]\n\
+             > gm\n> what's the ETH price?\n\n"
+        );
+
+        let block = format_reply_context("starkbot", true, "", 2);
+        assert!(block.starts_with("[REPLYING TO @starkbot [you]"));
+        assert!(block.contains("> [2 attachments]\n"));
+    }
+
+    #[test]
+    fn test_format_reply_context_truncates() {
+        let long = "x".repeat(5000);
+        let block = format_reply_context("bob", false, &long, 0);
+        assert!(block.len() < MAX_QUOTE_BYTES + 200);
+        assert!(block.contains("..."));
+
+        let many_lines = (0..50).map(|i| i.to_string()).collect::<Vec<_>>().join("\n");
+        let block = format_reply_context("bob", false, &many_lines, 0);
+        assert_eq!(block.matches("\n> ").count(), MAX_QUOTE_LINES + 1);
+        assert!(block.ends_with("> ...\n\n"));
+    }
+}
@@ -0,0 +1,164 @@
+//! Per-user message rate limiting in the dispatcher
+//!
+//! Every dispatched message can cost an AI call, so a single noisy server can
+//! run up a bill quickly. Each (channel, user) pair gets a token bucket holding
+//! `rate_limit_per_minute` tokens that refills continuously; a message that
+//! finds the bucket empty is rejected before the AI is called. The bucket holds
+//! `burst` tokens instead when a burst allowance is set. Limits come from the
+//! channel settings, falling back to the server-wide default (0 = unlimited).
+
+use dashmap::DashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How often idle buckets are swept
+const PRUNE_INTERVAL: Duration = Duration::from_secs(300);
+
+/// A bucket untouched this long has refilled completely, so it's equivalent to no bucket
+const BUCKET_IDLE: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Token buckets keyed by (channel_id, user_id)
+pub struct DispatchRateLimiter {
+    buckets: DashMap<(i64, String), Bucket>,
+    last_prune: Mutex<Instant>,
+}
+
+impl DispatchRateLimiter {
+    pub fn new() -> Self {
+        Self {
+            buckets: DashMap::new(),
+            last_prune: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Take one token for this user. Returns how long to wait if the bucket is empty.
+    /// A `burst` of 0 lets the bucket hold a full minute's worth.
+    pub fn check(&self, channel_id: i64, user_id: &str, per_minute: u32, burst: u32) -> Result<(), Duration> {
+        self.check_at(channel_id, user_id, per_minute, burst, Instant::now())
+    }
+
+    fn check_at(&self, channel_id: i64, user_id: &str, per_minute: u32, burst: u32, now: Instant) -> Result<(), Duration> {
+        if per_minute == 0 {
+            return Ok(());
+        }
+        self.prune_if_due(now);
+
+        let capacity = (if burst == 0 { per_minute } else { burst }) as f64;
+        let refill_per_sec = capacity / 60.0;
+        let mut bucket = self
+            .buckets
+            .entry((channel_id, user_id.to_string()))
+            .or_insert(Bucket { tokens: capacity, updated: now });
+
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * refill_per_sec).min(capacity);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / refill_per_sec))
+        }
+    }
+
+    /// Drop buckets that have been idle long enough to be full again
+    fn prune_if_due(&self, now: Instant) {
+        {
+            let mut last = self.last_prune.lock().unwrap_or_else(|e| e.into_inner());
+            if now.saturating_duration_since(*last) < PRUNE_INTERVAL {
+                return;
+            }
+            *last = now;
+        }
+        let before = self.buckets.len();
+        self.buckets
+            .retain(|_, b| now.saturating_duration_since(b.updated) < BUCKET_IDLE);
+        let pruned = before.saturating_sub(self.buckets.len());
+        if pruned > 0 {
+            log::debug!("[RATE_LIMIT] Pruned {} idle rate limit buckets", pruned);
+        }
+    }
+
+    /// Friendly message for a rejected dispatch
+    pub fn slow_down_message(retry_after: Duration) -> String {
+        format!(
+            "You're sending messages too quickly. Please wait {}s and try again.",
+            retry_after.as_secs().max(1)
+        )
+    }
+}
+
+impl Default for DispatchRateLimiter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_limits_and_refills() {
+        let limiter = DispatchRateLimiter::new();
+        let start = Instant::now();
+
+        // A burst up to the limit is allowed, then the user has to wait
+        for _ in 0..3 {
+            assert!(limiter.check_at(1, "alice", 3, 0, start).is_ok());
+        }
+        let wait = limiter.check_at(1, "alice", 3, 0, start).unwrap_err();
+        assert!((19.9..=20.1).contains(&wait.as_secs_f64()));
+
+        // Other users and channels have their own buckets
+        assert!(limiter.check_at(1, "bob", 3, 0, start).is_ok());
+        assert!(limiter.check_at(2, "alice", 3, 0, start).is_ok());
+
+        // One token refills every 20s at 3/min
+        assert!(limiter.check_at(1, "alice", 3, 0, start + Duration::from_secs(21)).is_ok());
+        assert!(limiter.check_at(1, "alice", 3, 0, start + Duration::from_secs(22)).is_err());
+
+        // 0 means unlimited
+        for _ in 0..100 {
+            assert!(limiter.check_at(3, "carol", 0, 0, start).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_burst_caps_the_bucket() {
+        let limiter = DispatchRateLimiter::new();
+        let start = Instant::now();
+
+        // 60/min with a burst of 2: two back-to-back, then one per second
+        assert!(limiter.check_at(1, "alice", 60, 2, start).is_ok());
+        assert!(limiter.check_at(1, "alice", 60, 2, start).is_ok());
+        assert!(limiter.check_at(1, "alice", 60, 2, start).is_err());
+        assert!(limiter.check_at(1, "alice", 60, 2, start + Duration::from_millis(1100)).is_ok());
+
+        // A long idle period refills only up to the burst
+        let later = start + Duration::from_secs(50);
+        assert!(limiter.check_at(1, "alice", 60, 2, later).is_ok());
+        assert!(limiter.check_at(1, "alice", 60, 2, later).is_ok());
+        assert!(limiter.check_at(1, "alice", 60, 2, later).is_err());
+    }
+
+    #[test]
+    fn test_idle_buckets_are_pruned() {
+        let limiter = DispatchRateLimiter::new();
+        let start = Instant::now();
+        limiter.check_at(1, "alice", 5, 0, start).unwrap();
+        limiter.check_at(1, "bob", 5, 0, start).unwrap();
+        assert_eq!(limiter.buckets.len(), 2);
+
+        let later = start + PRUNE_INTERVAL + Duration::from_secs(1);
+        limiter.check_at(1, "carol", 5, 0, later).unwrap();
+        assert_eq!(limiter.buckets.len(), 1);
+    }
+}
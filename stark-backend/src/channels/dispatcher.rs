@@ -1,15 +1,22 @@
 use crate::ai::{
-    multi_agent::{types::{AgentSubtype, AgentMode}, Orchestrator, ProcessResult as OrchestratorResult, SubAgentManager},
-    AiClient, ArchetypeId, ArchetypeRegistry, AiResponse, Message, MessageRole, ModelArchetype,
-    ThinkingLevel, ToolHistoryEntry, ToolResponse,
+    multi_agent::{types::{AgentSubtype, AgentMode, SubAgentConfig}, Orchestrator, ProcessResult as OrchestratorResult, SubAgentManager},
+    request_sampler, AiClient, ArchetypeId, ArchetypeRegistry, AiResponse, Message, MessageRole, ModelArchetype,
+    ThinkingLevel, TokenUsage, ToolHistoryEntry, ToolResponse,
 };
+use crate::channels::dispatch_rate_limiter::DispatchRateLimiter;
+use crate::channels::post_process;
+use crate::channels::preferences;
+use crate::channels::prompt_template::{self, PromptVariables};
 use crate::channels::types::{DispatchResult, NormalizedMessage};
+use crate::channels::util;
 use crate::config::MemoryConfig;
 use crate::context::{self, estimate_tokens, ContextManager};
 use crate::db::Database;
 use crate::execution::ExecutionTracker;
 use crate::gateway::events::EventBroadcaster;
 use crate::gateway::protocol::GatewayEvent;
+use crate::logging;
+use crate::models::channel_settings::ChannelSettingKey;
 use crate::models::session_message::MessageRole as DbMessageRole;
 use crate::models::{AgentSettings, CompletionStatus, SessionScope, DEFAULT_MAX_TOOL_ITERATIONS};
 use crate::qmd_memory::MemoryStore;
@@ -19,12 +26,14 @@ use crate::telemetry::{
 };
 use crate::tools::{ToolConfig, ToolContext, ToolDefinition, ToolExecution, ToolRegistry};
 use chrono::Utc;
+use dashmap::DashSet;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use serde_json::Value;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::time::interval;
+use tokio_util::sync::CancellationToken;
 
 /// Compiled regex patterns - avoid recompiling on every call
 static INLINE_THINKING_PATTERN: Lazy<Regex> = Lazy::new(|| {
@@ -41,6 +50,55 @@ const FALLBACK_MAX_TOOL_ITERATIONS: usize = DEFAULT_MAX_TOOL_ITERATIONS as usize
 /// How often to broadcast "still waiting" events during long AI calls
 const AI_PROGRESS_INTERVAL_SECS: u64 = 30;
 
+/// Annotation span recorded when an empty provider response triggers a retry
+const EMPTY_RESPONSE_RETRY_SPAN: &str = "empty_response_retry";
+
+/// Appended to replies that were cut off at the model's output token limit
+const TRUNCATION_NOTICE: &str =
+    "\n\n_(This reply was cut off at the output length limit. Send `continue` for the rest.)_";
+
+/// Sent to the model in place of the user's `continue` command
+const CONTINUE_PROMPT: &str = "[CONTINUE] Your previous reply was cut off at the output length limit. \
+    Continue exactly where it stopped: no preamble, no recap and no repetition of what you already wrote.";
+
+/// Identifies one user's conversation for tracking their last truncated reply
+fn truncation_key(message: &NormalizedMessage) -> (i64, String, String) {
+    (message.channel_id, message.chat_id.clone(), message.user_id.clone())
+}
+
+/// Aborts the watchdog heartbeat monitor when dropped, so a dispatch future that
+/// is dropped partway through doesn't leave the monitor running forever
+struct HeartbeatGuard(tokio::task::JoinHandle<()>);
+
+impl HeartbeatGuard {
+    fn abort(&self) {
+        self.0.abort();
+    }
+}
+
+impl Drop for HeartbeatGuard {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Provider token usage summed over every AI request one dispatch makes
+/// (all tool loop iterations and retries, whichever generation path is taken)
+#[derive(Default)]
+struct DispatchUsage(parking_lot::Mutex<Option<TokenUsage>>);
+
+impl DispatchUsage {
+    fn record(&self, usage: Option<&TokenUsage>) {
+        if let Some(usage) = usage {
+            self.0.lock().get_or_insert_default().add(usage);
+        }
+    }
+
+    fn total(&self) -> Option<TokenUsage> {
+        *self.0.lock()
+    }
+}
+
 /// Result of attempting to advance to the next task in the queue
 enum TaskAdvanceResult {
     /// Started working on the next task
@@ -127,6 +185,10 @@ pub struct MessageDispatcher {
     resource_manager: Arc<ResourceManager>,
     /// Watchdog configuration for timeout enforcement
     watchdog_config: WatchdogConfig,
+    /// (channel_id, chat_id, user_id) whose last reply was cut off at the output limit
+    truncated_replies: DashSet<(i64, String, String)>,
+    /// Per-user message rate limits (the `rate_limit_*` channel settings or the server default)
+    rate_limiter: DispatchRateLimiter,
     /// Mock AI client for integration tests (bypasses real AI API)
     #[cfg(test)]
     mock_ai_client: Option<crate::ai::MockAiClient>,
@@ -177,7 +239,7 @@ impl MessageDispatcher {
             db.clone(),
             broadcaster.clone(),
             tool_registry.clone(),
-            Default::default(),
+            SubAgentConfig::from_env(),
             wallet_provider.clone(),
         ));
         log::info!("[DISPATCHER] SubAgentManager initialized");
@@ -232,6 +294,8 @@ impl MessageDispatcher {
             rollout_manager,
             resource_manager,
             watchdog_config: WatchdogConfig::default(),
+            truncated_replies: DashSet::new(),
+            rate_limiter: DispatchRateLimiter::new(),
             #[cfg(test)]
             mock_ai_client: None,
         }
@@ -319,6 +383,8 @@ impl MessageDispatcher {
             rollout_manager,
             resource_manager,
             watchdog_config: WatchdogConfig::default(),
+            truncated_replies: DashSet::new(),
+            rate_limiter: DispatchRateLimiter::new(),
             #[cfg(test)]
             mock_ai_client: None,
         }
@@ -329,6 +395,11 @@ impl MessageDispatcher {
         self.memory_store.clone()
     }
 
+    /// Get the DiskQuotaManager (if available)
+    pub fn disk_quota(&self) -> Option<Arc<crate::disk_quota::DiskQuotaManager>> {
+        self.disk_quota.clone()
+    }
+
     /// Get the SubAgentManager (if available)
     pub fn subagent_manager(&self) -> Option<Arc<SubAgentManager>> {
         self.subagent_manager.clone()
@@ -346,6 +417,22 @@ impl MessageDispatcher {
 
     /// Dispatch a normalized message to the AI and return the response
     pub async fn dispatch(&self, message: NormalizedMessage) -> DispatchResult {
+        self.dispatch_with_cancellation(message, CancellationToken::new()).await
+    }
+
+    /// Dispatch like `dispatch`, but cancelling `cancel` stops only this dispatch.
+    /// Other executions on the same channel (e.g. main-mode cron jobs) keep running.
+    pub async fn dispatch_with_cancellation(&self, message: NormalizedMessage, cancel: CancellationToken) -> DispatchResult {
+        let channel_type = message.channel_type.clone();
+        let started = std::time::Instant::now();
+        let (channel_id, user_id) = (message.channel_id, message.user_id.clone());
+        let dispatch = self.dispatch_unrecorded(message, cancel);
+        let result = logging::with_log_context(channel_id, &user_id, dispatch).await;
+        crate::metrics::record_dispatch(&channel_type, result.error.is_none(), started.elapsed());
+        result
+    }
+
+    async fn dispatch_unrecorded(&self, mut message: NormalizedMessage, cancel: CancellationToken) -> DispatchResult {
         // Emit message received event
         self.broadcaster.broadcast(GatewayEvent::channel_message(
             message.channel_id,
@@ -360,6 +447,53 @@ impl MessageDispatcher {
             return self.handle_reset_command(&message).await;
         }
 
+        // Per-user rate limit, checked before anything that can reach the AI or write to the db
+        // (or drop a cut-off reply, so a rate-limited `continue` can be retried)
+        if let Some(response) = self.check_rate_limit(&message) {
+            return response;
+        }
+
+        // `continue` resumes the user's own last reply if it was cut off; any other
+        // message from them means that reply is no longer the one to continue
+        let was_truncated = self.truncated_replies.remove(&truncation_key(&message)).is_some();
+        if text_lower == "/continue" || text_lower == "continue" {
+            if was_truncated {
+                message.text = CONTINUE_PROMPT.to_string();
+            } else if text_lower == "/continue" {
+                let response = "There's no cut-off reply of yours to continue.".to_string();
+                self.broadcaster.broadcast(GatewayEvent::agent_response(
+                    message.channel_id,
+                    Some(&message.chat_id),
+                    &message.user_name,
+                    &response,
+                ));
+                return DispatchResult::success(response);
+            }
+        }
+
+        // Per-user preference commands (pref set/unset/list)
+        if let Some(command) = preferences::parse_command(&message.text) {
+            let response = preferences::execute_command(
+                &self.db,
+                &message.channel_type,
+                &message.user_id,
+                &message.user_name,
+                command,
+            );
+            self.broadcaster.broadcast(GatewayEvent::agent_response(
+                message.channel_id,
+                Some(&message.chat_id),
+                &message.user_name,
+                &response,
+            ));
+            return DispatchResult::success(response);
+        }
+
+        // Oversized input is cut down (or refused) before it can overflow the model's context
+        if let Some(response) = self.enforce_message_length(&mut message) {
+            return response;
+        }
+
         // Check for thinking directives (session-level setting)
         if let Some(thinking_response) = self.handle_thinking_directive(&message).await {
             return thinking_response;
@@ -377,6 +511,22 @@ impl MessageDispatcher {
             Some(user_msg),
         );
 
+        // Stopping the channel cancels this dispatch's token; `cancel` cancels only this dispatch.
+        // The guard cancels the token once the dispatch returns, which ends the forwarding task.
+        let cancel_token = self.execution_tracker.get_cancellation_token(message.channel_id).child_token();
+        let _cancel_guard = cancel_token.clone().drop_guard();
+        if cancel.is_cancelled() {
+            cancel_token.cancel();
+        } else {
+            let cancel_token = cancel_token.clone();
+            tokio::spawn(async move {
+                tokio::select! {
+                    _ = cancel.cancelled() => cancel_token.cancel(),
+                    _ = cancel_token.cancelled() => {}
+                }
+            });
+        }
+
         // Initialize telemetry rollout for this dispatch
         // We use session_id=0 initially; it will be updated once the session is resolved
         let rollout_config = RolloutConfig::default();
@@ -397,10 +547,11 @@ impl MessageDispatcher {
 
         // Start heartbeat monitor for long-running executions
         let watchdog = Arc::new(watchdog);
-        let heartbeat_handle = watchdog.start_heartbeat_monitor(
+        let heartbeat_handle = HeartbeatGuard(watchdog.start_heartbeat_monitor(
             message.channel_id,
+            &message.chat_id,
             Arc::clone(&self.broadcaster),
-        );
+        ));
 
         // Install thread-local span collector for emit_* functions
         telemetry::set_active_collector(Arc::clone(&span_collector));
@@ -471,6 +622,7 @@ impl MessageDispatcher {
         // preserved by including the last 10 messages in the system prompt.
         let channel_type_lower = message.channel_type.to_lowercase();
         let is_gateway_channel = channel_type_lower == "discord" || channel_type_lower == "telegram";
+        let session_ttl_minutes = self.session_ttl_minutes(message.channel_id);
 
         // Collect previous session messages for gateway channels (max 10)
         let previous_gateway_messages: Vec<crate::models::SessionMessage> = if is_gateway_channel {
@@ -481,8 +633,19 @@ impl MessageDispatcher {
                 &message.channel_type,
                 message.channel_id,
             ) {
-                let messages = self.db.get_recent_session_messages(prev_session.id, MAX_PREVIOUS_MESSAGES)
-                    .unwrap_or_default();
+                // Past the channel's session TTL the old conversation is left out of the new context
+                let expired = session_ttl_minutes
+                    .is_some_and(|ttl| prev_session.is_idle_for(ttl, Utc::now()));
+                let messages = if expired {
+                    log::info!(
+                        "[DISPATCH] Previous {} session {} idle past session TTL, starting without its context",
+                        message.channel_type, prev_session.id
+                    );
+                    vec![]
+                } else {
+                    self.db.get_recent_session_messages(prev_session.id, MAX_PREVIOUS_MESSAGES)
+                        .unwrap_or_default()
+                };
 
                 // Deactivate the old session
                 if let Err(e) = self.db.deactivate_session(prev_session.id) {
@@ -529,11 +692,28 @@ impl MessageDispatcher {
                     self.rollout_manager.fail_attempt(&mut rollout, &error_msg, &span_collector);
                     self.telemetry_store.persist_spans(&span_collector);
                     heartbeat_handle.abort();
-                telemetry::clear_active_collector();
+                    telemetry::clear_active_collector();
                     return DispatchResult::error(error_msg);
                 }
             }
         } else {
+            // Archive a session idle past the channel's TTL so this message starts a fresh context
+            if let Some(ttl) = session_ttl_minutes {
+                match self.db.expire_idle_chat_session(
+                    &message.channel_type,
+                    message.channel_id,
+                    &message.chat_id,
+                    ttl,
+                ) {
+                    Ok(Some(old_id)) => log::info!(
+                        "[DISPATCH] Session {} idle for {}+ minutes, starting a fresh session",
+                        old_id, ttl
+                    ),
+                    Ok(None) => {}
+                    Err(e) => log::warn!("[DISPATCH] Failed to expire idle session: {}", e),
+                }
+            }
+
             // Standard session handling for other channels
             match self.db.get_or_create_chat_session(
                 &message.channel_type,
@@ -554,7 +734,7 @@ impl MessageDispatcher {
                     self.rollout_manager.fail_attempt(&mut rollout, &error_msg, &span_collector);
                     self.telemetry_store.persist_spans(&span_collector);
                     heartbeat_handle.abort();
-                telemetry::clear_active_collector();
+                    telemetry::clear_active_collector();
                     return DispatchResult::error(error_msg);
                 }
             }
@@ -586,8 +766,20 @@ impl MessageDispatcher {
             }
         }
 
-        // Use clean text (with inline thinking directive removed) for storage
+        // Use clean text (with inline thinking directive removed) for storage, plus where any
+        // attached files were saved so later turns can still find them
         let message_text = clean_text.as_deref().unwrap_or(&message.text);
+        let message_text_with_attachments;
+        let message_text = if message.attachments.is_empty() {
+            message_text
+        } else {
+            message_text_with_attachments = format!(
+                "{}\n\n[ATTACHED FILES saved in the workspace:]\n{}",
+                message_text,
+                message.attachments.join("\n")
+            );
+            &message_text_with_attachments
+        };
 
         // Estimate tokens for the user message
         let user_tokens = estimate_tokens(message_text);
@@ -608,9 +800,27 @@ impl MessageDispatcher {
             self.context_manager.update_context_tokens(session.id, user_tokens);
         }
 
-        // Get active agent settings from database, falling back to kimi defaults
-        let settings = match self.db.get_active_agent_settings() {
-            Ok(Some(settings)) => settings,
+        // Get agent settings from database (weighted A/B pick if configured), falling back to kimi defaults
+        let settings = match crate::ai::model_selection::select_for_dispatch(&self.db) {
+            Ok(Some(selection)) => {
+                let mut selection_span = span_collector.start_span(
+                    SpanType::Annotation,
+                    crate::ai::model_selection::MODEL_SELECTION_SPAN,
+                );
+                selection_span.attributes = selection.attributes();
+                selection_span.succeed();
+                span_collector.record(selection_span);
+                if selection.weighted {
+                    log::info!(
+                        "[MODEL_SELECTION] Weighted pick: agent_settings {} ({}), weight {}/{}",
+                        selection.settings.id,
+                        selection.settings.endpoint,
+                        selection.settings.weight,
+                        selection.total_weight
+                    );
+                }
+                selection.settings
+            }
             Ok(None) => {
                 log::info!("No agent configured, using default kimi settings");
                 AgentSettings::default()
@@ -654,7 +864,9 @@ impl MessageDispatcher {
             AiClient::Mock(mock.clone())
         } else {
             match AiClient::from_settings_with_wallet_provider(&settings, self.wallet_provider.clone()) {
-                Ok(c) => c.with_broadcaster(Arc::clone(&self.broadcaster), message.channel_id),
+                Ok(c) => c
+                .with_fallbacks(self.fallback_clients(settings.id))
+                .with_broadcaster(Arc::clone(&self.broadcaster), message.channel_id),
                 Err(e) => {
                     let error = format!("Failed to create AI client: {}", e);
                     log::error!("{}", error);
@@ -678,7 +890,9 @@ impl MessageDispatcher {
         };
         #[cfg(not(test))]
         let client = match AiClient::from_settings_with_wallet_provider(&settings, self.wallet_provider.clone()) {
-            Ok(c) => c.with_broadcaster(Arc::clone(&self.broadcaster), message.channel_id),
+            Ok(c) => c
+                .with_fallbacks(self.fallback_clients(settings.id))
+                .with_broadcaster(Arc::clone(&self.broadcaster), message.channel_id),
             Err(e) => {
                 let error = format!("Failed to create AI client: {}", e);
                 log::error!("{}", error);
@@ -729,10 +943,10 @@ impl MessageDispatcher {
             if let Err(e) = self.db.set_session_safe_mode(session.id) {
                 log::warn!("[DISPATCH] Failed to set session safe_mode: {}", e);
             }
-            // Replace the entire tool config with the canonical safe mode config.
+            // Replace the tool config with the canonical safe mode config.
             // ToolConfig::safe_mode() is the single source of truth for safe mode permissions.
-            // This discards any channel-level overrides — safe mode is absolute.
-            tool_config = crate::tools::ToolConfig::safe_mode();
+            // Channel overrides are discarded, except tools the channel explicitly disabled.
+            tool_config = tool_config.into_safe_mode();
         }
 
         // Twitter has no interactive session — ask_user can never work, so block it.
@@ -789,11 +1003,7 @@ impl MessageDispatcher {
                     DbMessageRole::ToolResult => "Tool Result",
                 };
                 // Truncate very long messages to keep context manageable
-                let content = if msg.content.len() > 500 {
-                    format!("{}...", &msg.content[..500])
-                } else {
-                    msg.content.clone()
-                };
+                let content = util::truncate_str(&msg.content, 500);
                 context_text.push_str(&format!("**{}**: {}\n\n", role_label, content));
             }
             messages.push(Message {
@@ -883,7 +1093,9 @@ impl MessageDispatcher {
             .with_workspace(workspace_dir.clone())
             .with_broadcaster(self.broadcaster.clone())
             .with_database(self.db.clone())
-            .with_selected_network(message.selected_network.clone());
+            .with_selected_network(message.selected_network.clone())
+            .with_cancellation_token(cancel_token.clone())
+            .with_tool_registry(self.tool_registry.clone(), tool_config.clone());
 
         // Log selected network if present
         if let Some(ref network) = message.selected_network {
@@ -934,6 +1146,14 @@ impl MessageDispatcher {
             );
         }
 
+        // Admin-only tools check this; a safe mode channel never counts as admin
+        if message.from_admin && !is_safe_mode {
+            tool_context.extra.insert(
+                "admin".to_string(),
+                serde_json::json!(true),
+            );
+        }
+
         // Populate tool context with the context bank items scanned earlier
         if !context_bank_items.is_empty() {
             tool_context.context_bank.add_all(context_bank_items.clone());
@@ -1014,6 +1234,8 @@ impl MessageDispatcher {
         // Generate response with retry-aware loop.
         // On retryable failures (timeout, LLM error, context overflow), the rollout
         // manager creates a new attempt and we retry the entire generation.
+        let mut empty_response_retried = false;
+        let dispatch_usage = DispatchUsage::default();
         let final_response = loop {
             let attempt_result = if use_tools {
                 self.generate_with_tool_loop(
@@ -1027,13 +1249,15 @@ impl MessageDispatcher {
                     archetype_id,
                     is_safe_mode,
                     &watchdog,
+                    &dispatch_usage,
                 ).await
             } else {
                 // Simple generation without tools - with x402 event emission
                 match client.generate_text_with_events(messages.clone(), &self.broadcaster, message.channel_id).await {
-                    Ok((content, payment)) => {
+                    Ok(response) => {
+                        dispatch_usage.record(response.usage.as_ref());
                         // Save x402 payment if one was made
-                        if let Some(ref payment_info) = payment {
+                        if let Some(ref payment_info) = response.x402_payment {
                             if let Err(e) = self.db.record_x402_payment(
                                 Some(message.channel_id),
                                 None,
@@ -1048,7 +1272,7 @@ impl MessageDispatcher {
                                 log::error!("[DISPATCH] Failed to record x402 payment: {}", e);
                             }
                         }
-                        Ok((content, false))
+                        Ok((response.content, false))
                     }
                     Err(e) => Err(e),
                 }
@@ -1056,6 +1280,28 @@ impl MessageDispatcher {
 
             // On success, break out of the retry loop
             match attempt_result {
+                // Provider glitch: nothing came back at all (no text, no tools, no error).
+                // Retry the whole generation once; tool calls rule it out since re-running them has side effects.
+                Ok((ref response, false))
+                    if response.trim().is_empty()
+                        && !empty_response_retried
+                        && crate::config::empty_response_retry_enabled()
+                        && !span_collector.snapshot().iter().any(|s| s.span_type == SpanType::ToolCall) =>
+                {
+                    empty_response_retried = true;
+                    log::warn!(
+                        "[DISPATCH] Empty final response from provider (no content, no tool calls), retrying once"
+                    );
+                    let mut retry_span = span_collector.start_span(SpanType::Annotation, EMPTY_RESPONSE_RETRY_SPAN);
+                    retry_span.attributes = serde_json::json!({
+                        "channel_id": message.channel_id,
+                        "session_id": session.id,
+                        "attempt": rollout.attempt_count(),
+                    });
+                    retry_span.succeed();
+                    span_collector.record(retry_span);
+                    continue;
+                }
                 Ok(response) => {
                     // Emit retry_succeeded reward if this wasn't the first attempt
                     if rollout.attempt_count() > 1 {
@@ -1112,8 +1358,19 @@ impl MessageDispatcher {
             }
         };
 
+        let usage = dispatch_usage.total();
+        if let Some(ref u) = usage {
+            log::info!(
+                "[DISPATCH] Token usage for session {}: {} prompt + {} completion = {} total",
+                session.id, u.prompt_tokens, u.completion_tokens, u.total_tokens
+            );
+        }
+
         match final_response {
             Ok((response, delivered_via_say_to_user)) => {
+                // Operator-configured transforms (secret redaction always included)
+                let response = post_process::global().apply(&response);
+
                 // Estimate tokens for the response
                 let response_tokens = estimate_tokens(&response);
 
@@ -1190,10 +1447,18 @@ impl MessageDispatcher {
                     }
                 }
 
+                // Replies cut off at the output limit say how to get the rest
+                let response = if self.truncated_replies.contains(&truncation_key(&message)) {
+                    format!("{}{}", response, TRUNCATION_NOTICE)
+                } else {
+                    response
+                };
+
                 // Emit response event — skip if empty or if say_to_user already broadcast it
                 if !response.trim().is_empty() && !delivered_via_say_to_user {
                     self.broadcaster.broadcast(GatewayEvent::agent_response(
                         message.channel_id,
+                        Some(&message.chat_id),
                         &message.user_name,
                         &response,
                     ));
@@ -1227,6 +1492,8 @@ impl MessageDispatcher {
                 telemetry::clear_active_collector();
 
                 DispatchResult::success(response)
+                    .with_usage(usage)
+                    .with_session_id(session.id)
             }
             Err(e) => {
                 let mut error = format!("AI generation error ({}): {}", archetype_id, e);
@@ -1294,7 +1561,7 @@ impl MessageDispatcher {
                 heartbeat_handle.abort();
                 telemetry::clear_active_collector();
 
-                DispatchResult::error(error)
+                DispatchResult::error(error).with_session_id(session.id)
             }
         }
     }
@@ -1313,6 +1580,7 @@ impl MessageDispatcher {
         archetype_id: ArchetypeId,
         is_safe_mode: bool,
         watchdog: &Arc<Watchdog>,
+        dispatch_usage: &DispatchUsage,
     ) -> Result<(String, bool), String> {
         // Load existing agent context or create new one
         let mut orchestrator = match self.db.get_agent_context(session_id) {
@@ -1401,9 +1669,10 @@ impl MessageDispatcher {
 
         if tools.is_empty() {
             log::warn!("[TOOL_LOOP] No tools available, falling back to text-only generation");
-            let (content, payment) = client.generate_text_with_events(messages, &self.broadcaster, original_message.channel_id).await?;
+            let response = client.generate_text_with_events(messages, &self.broadcaster, original_message.channel_id).await?;
+            dispatch_usage.record(response.usage.as_ref());
             // Save x402 payment if one was made
-            if let Some(ref payment_info) = payment {
+            if let Some(ref payment_info) = response.x402_payment {
                 if let Err(e) = self.db.record_x402_payment(
                     Some(original_message.channel_id),
                     None,
@@ -1418,7 +1687,7 @@ impl MessageDispatcher {
                     log::error!("[TOOL_LOOP] Failed to record x402 payment: {}", e);
                 }
             }
-            return Ok((content, false));
+            return Ok((response.content, false));
         }
 
         // Get the archetype for this request
@@ -1435,12 +1704,14 @@ impl MessageDispatcher {
         if archetype.uses_native_tool_calling() {
             self.generate_with_native_tools_orchestrated(
                 client, messages, tools, tool_config, tool_context,
-                original_message, archetype, &mut orchestrator, session_id, is_safe_mode, watchdog
+                original_message, archetype, &mut orchestrator, session_id, is_safe_mode, watchdog,
+                dispatch_usage,
             ).await
         } else {
             self.generate_with_text_tools_orchestrated(
                 client, messages, tools, tool_config, tool_context,
-                original_message, archetype, &mut orchestrator, session_id, is_safe_mode, watchdog
+                original_message, archetype, &mut orchestrator, session_id, is_safe_mode, watchdog,
+                dispatch_usage,
             ).await
         }
     }
@@ -1925,7 +2196,15 @@ impl MessageDispatcher {
             tool_arguments,
         ));
 
-        let result = if tool_name == "use_skill" {
+        let result = if tool_name != "use_skill" && !self.tool_registry.has_tool(tool_name) {
+            // Hallucinated tool name: tell the model what it can call so it can self-correct
+            log::warn!("[TOOL_CALL] Model called unknown tool '{}'", tool_name);
+            telemetry::emit_annotation("unknown_tool_called", serde_json::json!({
+                "tool_name": tool_name,
+                "available_tools": current_tools.len(),
+            }));
+            crate::tools::ToolResult::error(crate::tools::registry::unknown_tool_message(tool_name, current_tools))
+        } else if tool_name == "use_skill" {
             // Check if the same skill is already active — avoid redundant reloads
             let requested_skill = tool_arguments.get("skill_name").and_then(|v| v.as_str()).unwrap_or("");
             let already_active = orchestrator.context().active_skill
@@ -2042,6 +2321,22 @@ impl MessageDispatcher {
                     tool_config
                 };
 
+                // list_capabilities answers from the toolset the model can see this iteration
+                let capabilities_context;
+                let tool_context = if tool_name == "list_capabilities" {
+                    capabilities_context = {
+                        let mut ctx = tool_context.clone();
+                        ctx.extra.insert(
+                            crate::tools::builtin::core::AVAILABLE_TOOLS_EXTRA_KEY.to_string(),
+                            crate::tools::builtin::ListCapabilitiesTool::available_tools_value(current_tools),
+                        );
+                        ctx
+                    };
+                    &capabilities_context
+                } else {
+                    tool_context
+                };
+
                 // Run tool validators before execution
                 if let Some(ref validator_registry) = self.validator_registry {
                     let validation_ctx = crate::tool_validators::ValidationContext::new(
@@ -2443,7 +2738,12 @@ impl MessageDispatcher {
         // channels that capture them (e.g. Twitter). Minimal-style channels (Discord,
         // Telegram, AgentChat) skip say_to_user in their event handlers and instead
         // receive the content via the final result.response.
-        if !is_duplicate_say_to_user {
+        // The registry has already broadcast the result of a call it timed out
+        let registry_timed_out = result.metadata.as_ref()
+            .and_then(|m| m.get(crate::tools::registry::REGISTRY_TIMEOUT_KEY))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        if !is_duplicate_say_to_user && !registry_timed_out {
             self.broadcaster.broadcast(GatewayEvent::tool_result(
                 original_message.channel_id,
                 Some(&original_message.chat_id),
@@ -2490,7 +2790,12 @@ impl MessageDispatcher {
         // Broadcast task list update after any orchestrator tool processing
         self.broadcast_tasks_update(original_message.channel_id, session_id, orchestrator);
 
-        processed.result_content = result.content;
+        // Delimit untrusted output (web pages, API responses, ...) before it reaches the model
+        let guard = crate::tools::injection_guard::global();
+        processed.result_content = match self.tool_registry.get(tool_name) {
+            Some(tool) if guard.is_enabled() => guard.apply(tool_name, tool.group(), result.content),
+            _ => result.content,
+        };
         processed.success = result.success;
         processed
     }
@@ -2661,6 +2966,7 @@ impl MessageDispatcher {
         session_id: i64,
         is_safe_mode: bool,
         watchdog: &Arc<Watchdog>,
+        dispatch_usage: &DispatchUsage,
     ) -> Result<(String, bool), String> {
         // Get max tool iterations from bot settings
         let max_tool_iterations = self.db.get_bot_settings()
@@ -2727,6 +3033,8 @@ impl MessageDispatcher {
         // say_to_user loop prevention: don't allow say_to_user to be called twice in a row
         let mut previous_iteration_had_say_to_user = false;
 
+        let send_partial_responses = self.partial_responses_enabled(original_message.channel_id);
+
         loop {
             iterations += 1;
             log::info!(
@@ -2798,7 +3106,7 @@ impl MessageDispatcher {
             }
 
             // Check if execution was cancelled (e.g., user sent /new or stop button)
-            if self.execution_tracker.is_cancelled(original_message.channel_id) {
+            if self.execution_tracker.is_cancelled(original_message.channel_id) || tool_context.is_cancelled() {
                 log::info!("[ORCHESTRATED_LOOP] Execution cancelled by user, stopping loop");
                 was_cancelled = true;
                 break;
@@ -2983,7 +3291,10 @@ impl MessageDispatcher {
                 tool_history.clone(),
                 current_tools.clone(),
                 original_message.channel_id,
+                &original_message.user_id,
                 session_id,
+                dispatch_usage,
+                tool_context.cancellation_token.clone().unwrap_or_default(),
             ).await {
                 Ok(response) => response,
                 Err(e) => {
@@ -3131,6 +3442,11 @@ impl MessageDispatcher {
                     return Ok((last_say_to_user_content.clone(), true));
                 }
 
+                if ai_response.is_truncated() {
+                    log::info!("[ORCHESTRATED_LOOP] Response hit the output token limit");
+                    self.truncated_replies.insert(truncation_key(original_message));
+                }
+
                 if orchestrator_complete {
                     // Build response from non-empty parts
                     let mut parts: Vec<&str> = Vec::new();
@@ -3151,6 +3467,16 @@ impl MessageDispatcher {
                 }
             }
 
+            // Text written alongside tool calls would otherwise only surface in the final reply
+            if send_partial_responses && !ai_response.content.trim().is_empty() {
+                self.broadcaster.broadcast(GatewayEvent::agent_partial_response(
+                    original_message.channel_id,
+                    Some(&original_message.chat_id),
+                    ai_response.content.trim(),
+                    iterations,
+                ));
+            }
+
             // Process tool calls
             let mut tool_responses = Vec::new();
 
@@ -3232,7 +3558,8 @@ impl MessageDispatcher {
 
             let mut batch_state = BatchState::new();
 
-            for call in &ai_response.tool_calls {
+            // Run dependent tools in a sensible order regardless of how the model listed them
+            for call in crate::tools::execution_order::global().order(&ai_response.tool_calls) {
                 let processed = self.process_tool_call_result(
                     &call.name,
                     &call.arguments,
@@ -3342,6 +3669,7 @@ impl MessageDispatcher {
         session_id: i64,
         is_safe_mode: bool,
         watchdog: &Arc<Watchdog>,
+        dispatch_usage: &DispatchUsage,
     ) -> Result<(String, bool), String> {
         // Get max tool iterations from bot settings
         let max_tool_iterations = self.db.get_bot_settings()
@@ -3417,7 +3745,7 @@ impl MessageDispatcher {
             );
 
             // Check if execution was cancelled (e.g., user sent /new or stop button)
-            if self.execution_tracker.is_cancelled(original_message.channel_id) {
+            if self.execution_tracker.is_cancelled(original_message.channel_id) || tool_context.is_cancelled() {
                 log::info!("[TEXT_ORCHESTRATED] Execution cancelled by user, stopping loop");
                 was_cancelled = true;
                 break;
@@ -3488,7 +3816,10 @@ impl MessageDispatcher {
                 &self.broadcaster,
                 original_message.channel_id,
             ).await {
-                Ok(result) => result,
+                Ok(response) => {
+                    dispatch_usage.record(response.usage.as_ref());
+                    (response.content, response.x402_payment)
+                }
                 Err(e) => {
                     // AI generation failed - save summary of work done so far
                     if !tool_call_log.is_empty() {
@@ -3880,10 +4211,22 @@ impl MessageDispatcher {
         &self,
         message: &NormalizedMessage,
         identity_id: &str,
-        _tool_config: &ToolConfig,
+        tool_config: &ToolConfig,
         is_safe_mode: bool,
     ) -> String {
         let mut prompt = String::new();
+        let prefs = self.db.get_user_preferences(identity_id).ok();
+
+        // {{variable}} placeholders in SOUL.md / GUIDELINES.md
+        let template_vars = PromptVariables::new(
+            message,
+            &prefs.clone().unwrap_or_default(),
+            self.tool_registry
+                .get_tool_definitions(tool_config)
+                .into_iter()
+                .map(|d| d.name)
+                .collect(),
+        );
 
         // SECURITY: Add safe mode warning at the very beginning
         if is_safe_mode {
@@ -3907,17 +4250,39 @@ impl MessageDispatcher {
             prompt.push_str("5. Do NOT say you lack access or cannot respond. You CAN respond — just use say_to_user.\n\n");
         }
 
-        // Load SOUL.md if available, otherwise use default intro
-        if let Some(soul) = Self::load_soul() {
-            prompt.push_str(&soul);
-            prompt.push_str("\n\n");
-        } else {
-            prompt.push_str("You are StarkBot, an AI agent who can respond to users and operate tools.\n\n");
+        // Operator started the whole deployment in safe mode (--safe-mode / STARK_SAFE_MODE)
+        if self.tool_registry.global_safe_mode() {
+            prompt.push_str("## DEPLOYMENT SAFE MODE\n");
+            prompt.push_str("This bot was started in safe mode by its operator. Only read-only tools are available: you cannot run commands, post, send messages to other chats, or move funds. If asked to take such an action, explain that the bot is in safe mode and describe what you would do instead.\n\n");
+        }
+
+        // Persona. Precedence: the channel's `system_prompt` setting (prepended, or replacing
+        // SOUL.md in `replace` mode) > SOUL.md > the built-in intro. Blank settings are ignored.
+        let channel_prompt = self.channel_system_prompt(message.channel_id);
+        let replace_soul = match &channel_prompt {
+            Some((text, replace)) => {
+                if !*replace {
+                    prompt.push_str("## Channel Instructions\n");
+                }
+                prompt.push_str(&prompt_template::render(text, &template_vars));
+                prompt.push_str("\n\n");
+                *replace
+            }
+            None => false,
+        };
+        if !replace_soul {
+            // Load SOUL.md if available, otherwise use default intro
+            if let Some(soul) = Self::load_soul() {
+                prompt.push_str(&prompt_template::render(&soul, &template_vars));
+                prompt.push_str("\n\n");
+            } else {
+                prompt.push_str("You are StarkBot, an AI agent who can respond to users and operate tools.\n\n");
+            }
         }
 
         // Load GUIDELINES.md if available (operational guidelines)
         if let Some(guidelines) = Self::load_guidelines() {
-            prompt.push_str(&guidelines);
+            prompt.push_str(&prompt_template::render(&guidelines, &template_vars));
             prompt.push_str("\n\n");
         }
 
@@ -4014,6 +4379,11 @@ impl MessageDispatcher {
             }
         }
 
+        // User preferences (name, timezone, language, notifications)
+        if let Some(ref prefs) = prefs {
+            prompt.push_str(&preferences::prompt_section(prefs));
+        }
+
         // Memory tool instructions
         prompt.push_str("## Memory\nUse `memory_search` to find relevant memories. Use `memory_read` to read specific memory files.\n\n");
 
@@ -4056,6 +4426,7 @@ impl MessageDispatcher {
 
                 self.broadcaster.broadcast(GatewayEvent::agent_response(
                     message.channel_id,
+                    Some(&message.chat_id),
                     &message.user_name,
                     &response,
                 ));
@@ -4076,6 +4447,7 @@ impl MessageDispatcher {
                 );
                 self.broadcaster.broadcast(GatewayEvent::agent_response(
                     message.channel_id,
+                    Some(&message.chat_id),
                     &message.user_name,
                     &response,
                 ));
@@ -4115,7 +4487,10 @@ impl MessageDispatcher {
         tool_history: Vec<ToolHistoryEntry>,
         tools: Vec<ToolDefinition>,
         channel_id: i64,
+        user_id: &str,
         session_id: i64,
+        dispatch_usage: &DispatchUsage,
+        cancel_token: CancellationToken,
     ) -> Result<AiResponse, crate::ai::AiError> {
         let broadcaster = self.broadcaster.clone();
         let mut elapsed_secs = 0u64;
@@ -4147,9 +4522,6 @@ impl MessageDispatcher {
             None
         };
 
-        // Get cancellation token for immediate interruption
-        let cancel_token = self.execution_tracker.get_cancellation_token(channel_id);
-
         // Broadcast the full context being sent to the AI (for debug panel)
         broadcaster.broadcast(GatewayEvent::agent_context_update(
             channel_id,
@@ -4159,6 +4531,12 @@ impl MessageDispatcher {
             &tool_history,
         ));
 
+        // Sampled payload logging for debugging intermittent provider issues
+        let sample = request_sampler::global().sample(channel_id, user_id);
+        if let Some(ref sample) = sample {
+            request_sampler::log_request(sample, channel_id, user_id, &conversation, &tool_history, &tools);
+        }
+
         // Spawn the actual AI request
         let ai_future = client.generate_with_tools(conversation, tool_history, tools.clone());
         tokio::pin!(ai_future);
@@ -4238,9 +4616,14 @@ impl MessageDispatcher {
                     if let Some(ref task_id) = thinking_task_id {
                         self.execution_tracker.complete_task(task_id);
                     }
+                    if let Some(ref sample) = sample {
+                        request_sampler::log_response(sample, &result);
+                    }
 
                     match result {
                         Ok(response) => {
+                            dispatch_usage.record(response.usage.as_ref());
+
                             // If there are tool calls, emit a planning task
                             if !response.tool_calls.is_empty() {
                                 if let Some(ref exec_id) = execution_id {
@@ -4296,6 +4679,165 @@ impl MessageDispatcher {
         }
     }
 
+    /// Clients for the configured fallback chain, skipping the row already used as primary.
+    /// Rows whose client can't be built are left out of the chain.
+    fn fallback_clients(&self, primary_id: i64) -> Vec<AiClient> {
+        let fallbacks = match self.db.list_fallback_agent_settings() {
+            Ok(f) => f,
+            Err(e) => {
+                log::warn!("[MODEL_FALLBACK] Failed to load fallback chain: {}", e);
+                return Vec::new();
+            }
+        };
+        fallbacks
+            .iter()
+            .filter(|s| s.id != primary_id)
+            .filter_map(|s| {
+                AiClient::from_settings_with_wallet_provider(s, self.wallet_provider.clone())
+                    .map_err(|e| log::warn!("[MODEL_FALLBACK] Skipping fallback {} ({}): {}", s.id, s.endpoint, e))
+                    .ok()
+            })
+            .collect()
+    }
+
+    /// The channel's `session_ttl_minutes`, if set (0 or unset = sessions don't expire)
+    fn session_ttl_minutes(&self, channel_id: i64) -> Option<i64> {
+        self.db
+            .get_channel_setting(channel_id, ChannelSettingKey::SessionTtlMinutes.as_ref())
+            .ok()
+            .flatten()
+            .and_then(|v| v.trim().parse::<i64>().ok())
+            .filter(|ttl| *ttl > 0)
+    }
+
+    /// The channel's `system_prompt` override and whether it replaces SOUL.md
+    /// (`system_prompt_mode = replace`) rather than being prepended to it
+    fn channel_system_prompt(&self, channel_id: i64) -> Option<(String, bool)> {
+        let setting = |key: ChannelSettingKey| self.db.get_channel_setting(channel_id, key.as_ref()).ok().flatten();
+        let text = setting(ChannelSettingKey::SystemPrompt)
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())?;
+        let replace = setting(ChannelSettingKey::SystemPromptMode)
+            .is_some_and(|m| m.trim().eq_ignore_ascii_case("replace"));
+        Some((text, replace))
+    }
+
+    /// Whether the channel's `discord_partial_responses` setting is on (only Discord renders them)
+    fn partial_responses_enabled(&self, channel_id: i64) -> bool {
+        self.db
+            .get_channel_setting(channel_id, ChannelSettingKey::DiscordPartialResponses.as_ref())
+            .ok()
+            .flatten()
+            .map(|v| v.trim().eq_ignore_ascii_case("true"))
+            .unwrap_or(false)
+    }
+
+    /// Apply the effective rate limit to the sender. Returns a "slow down" result instead of
+    /// dispatching when their bucket is empty. Admins are never limited.
+    fn check_rate_limit(&self, message: &NormalizedMessage) -> Option<DispatchResult> {
+        let (per_minute, burst) = self.effective_rate_limit(message.channel_id);
+        if per_minute == 0 || self.is_channel_admin(message) {
+            return None;
+        }
+
+        let retry_after = self
+            .rate_limiter
+            .check(message.channel_id, &message.user_id, per_minute, burst)
+            .err()?;
+        log::info!(
+            "[RATE_LIMIT] {} ({}) exceeded {}/min on channel {}, retry in {:?}",
+            message.user_name,
+            message.user_id,
+            per_minute,
+            message.channel_id,
+            retry_after
+        );
+        Some(DispatchResult::error(DispatchRateLimiter::slow_down_message(retry_after)))
+    }
+
+    /// Apply the channel's `max_message_chars` limit: truncate the text in place with a notice
+    /// appended, or, with `oversized_message_action = reject`, return an error asking the user
+    /// to shorten it
+    fn enforce_message_length(&self, message: &mut NormalizedMessage) -> Option<DispatchResult> {
+        let setting = |key: ChannelSettingKey| {
+            self.db
+                .get_channel_setting(message.channel_id, key.as_ref())
+                .ok()
+                .flatten()
+        };
+        let limit = setting(ChannelSettingKey::MaxMessageChars)
+            .and_then(|v| v.trim().parse::<u32>().ok())
+            .unwrap_or_else(crate::config::max_message_chars) as usize;
+        let len = message.text.chars().count();
+        if limit == 0 || len <= limit {
+            return None;
+        }
+
+        let reject = setting(ChannelSettingKey::OversizedMessageAction)
+            .is_some_and(|v| v.trim().eq_ignore_ascii_case("reject"));
+        log::info!(
+            "[DISPATCH] {} message from {} on channel {} is {} chars (limit {})",
+            if reject { "Rejecting" } else { "Truncating" },
+            message.user_name,
+            message.channel_id,
+            len,
+            limit
+        );
+        if reject {
+            return Some(DispatchResult::error(format!(
+                "Your message is too long ({} characters; the limit here is {}). \
+                 Please shorten it or split it into smaller parts.",
+                len, limit
+            )));
+        }
+
+        let mut text: String = message.text.chars().take(limit).collect();
+        text.push_str(&format!(
+            "\n\n[Message truncated: only the first {} of {} characters were kept.]",
+            limit, len
+        ));
+        message.text = text;
+        None
+    }
+
+    /// `(per_minute, burst)` for a channel: its own settings when set, else the server default
+    fn effective_rate_limit(&self, channel_id: i64) -> (u32, u32) {
+        let setting = |key: ChannelSettingKey| {
+            self.db
+                .get_channel_setting(channel_id, key.as_ref())
+                .ok()
+                .flatten()
+                .and_then(|v| v.trim().parse::<u32>().ok())
+        };
+        let per_minute = setting(ChannelSettingKey::RateLimitPerMinute)
+            .unwrap_or_else(crate::config::rate_limit_per_minute);
+        let burst = setting(ChannelSettingKey::RateLimitBurst)
+            .unwrap_or_else(crate::config::rate_limit_burst);
+        (per_minute, burst)
+    }
+
+    /// Whether the sender is one of the channel's admins. Discord and Twitter put every
+    /// non-admin in safe mode; Telegram and Slack only do so once admins are configured.
+    /// Web chat is the authenticated dashboard operator.
+    fn is_channel_admin(&self, message: &NormalizedMessage) -> bool {
+        if message.force_safe_mode {
+            return false;
+        }
+        let admins_configured = |key: ChannelSettingKey| {
+            self.db
+                .get_channel_setting(message.channel_id, key.as_ref())
+                .ok()
+                .flatten()
+                .is_some_and(|v| !v.trim().is_empty())
+        };
+        match message.channel_type.as_str() {
+            "discord" | "twitter" | "web" => true,
+            "telegram" => admins_configured(ChannelSettingKey::TelegramAdminUserId),
+            "slack" => admins_configured(ChannelSettingKey::SlackAdminUserIds),
+            _ => false,
+        }
+    }
+
     /// Handle /new or /reset commands
     async fn handle_reset_command(&self, message: &NormalizedMessage) -> DispatchResult {
         // Cancel any ongoing execution for this channel
@@ -4381,6 +4923,7 @@ impl MessageDispatcher {
                         let response = "Session reset. Let's start fresh!".to_string();
                         self.broadcaster.broadcast(GatewayEvent::agent_response(
                             message.channel_id,
+                            Some(&message.chat_id),
                             &message.user_name,
                             &response,
                         ));
@@ -17,6 +17,7 @@ use serde_json::json;
 use std::sync::Arc;
 use tokio::sync::mpsc;
 use tokio::time::{timeout, Duration};
+use tokio_util::sync::CancellationToken;
 
 /// Test harness that wires up an in-memory database, event subscriber,
 /// tool registry with real say_to_user / task_fully_completed tools,
@@ -26,12 +27,13 @@ struct TestHarness {
     _client_id: String,
     event_rx: mpsc::Receiver<GatewayEvent>,
     channel_id: i64,
+    execution_tracker: Arc<ExecutionTracker>,
 }
 
 impl TestHarness {
     /// Build a test harness.
     ///
-    /// * `channel_type` — "web", "discord", etc.
+    /// * `channel_type` — channel row type: "external_channel", "discord", etc.
     /// * `safe_mode` — whether the channel has safe_mode enabled
     /// * `force_safe_mode` — whether the message forces safe mode (e.g. non-admin Discord)
     /// * `mock_responses` — pre-configured AI responses
@@ -98,7 +100,7 @@ impl TestHarness {
             db.clone(),
             broadcaster.clone(),
             tool_registry,
-            execution_tracker,
+            execution_tracker.clone(),
             None,
             Some(skill_registry),
         )
@@ -109,12 +111,13 @@ impl TestHarness {
             _client_id: client_id,
             event_rx,
             channel_id,
+            execution_tracker,
         }
     }
 
     /// Build a test harness with skills loaded from the skills/ directory.
     ///
-    /// * `channel_type` — "web", "discord", etc.
+    /// * `channel_type` — channel row type: "external_channel", "discord", etc.
     /// * `safe_mode` — whether the channel has safe_mode enabled
     /// * `skill_names` — list of skill markdown filenames to load (e.g. ["swap", "local_wallet"])
     /// * `mock_responses` — pre-configured AI responses
@@ -165,7 +168,7 @@ impl TestHarness {
             db.clone(),
             broadcaster.clone(),
             tool_registry,
-            execution_tracker,
+            execution_tracker.clone(),
             None, // no wallet provider
             Some(skill_registry),
         )
@@ -176,6 +179,7 @@ impl TestHarness {
             _client_id: client_id,
             event_rx,
             channel_id,
+            execution_tracker,
         }
     }
 
@@ -193,6 +197,8 @@ impl TestHarness {
             session_mode: None,
             selected_network: None,
             force_safe_mode,
+            from_admin: false,
+            attachments: Vec::new(),
         }
     }
 
@@ -379,7 +385,7 @@ async fn pattern_a_say_to_user_finished_web_normal() {
         )],
     )];
 
-    let mut harness = TestHarness::new("web", false, false, responses);
+    let mut harness = TestHarness::new("external_channel", false, false, responses);
     let (result, events) = harness.dispatch("hello", false).await;
 
     assert!(result.error.is_none(), "dispatch should succeed: {:?}", result.error);
@@ -397,7 +403,7 @@ async fn pattern_a_say_to_user_finished_safe_mode() {
         )],
     )];
 
-    let mut harness = TestHarness::new("web", true, false, responses);
+    let mut harness = TestHarness::new("external_channel", true, false, responses);
     let (result, events) = harness.dispatch("hello", false).await;
 
     assert!(result.error.is_none(), "dispatch should succeed: {:?}", result.error);
@@ -440,7 +446,7 @@ async fn pattern_c_task_fully_completed_web_normal() {
         )],
     )];
 
-    let mut harness = TestHarness::new("web", false, false, responses);
+    let mut harness = TestHarness::new("external_channel", false, false, responses);
     let (result, events) = harness.dispatch("do something", false).await;
 
     assert!(result.error.is_none(), "dispatch should succeed: {:?}", result.error);
@@ -458,7 +464,7 @@ async fn pattern_c_task_fully_completed_safe_mode() {
         )],
     )];
 
-    let mut harness = TestHarness::new("web", true, false, responses);
+    let mut harness = TestHarness::new("external_channel", true, false, responses);
     let (result, events) = harness.dispatch("do something", false).await;
 
     assert!(result.error.is_none(), "dispatch should succeed: {:?}", result.error);
@@ -510,7 +516,7 @@ async fn pattern_d_say_then_complete_web_normal() {
         ),
     ];
 
-    let mut harness = TestHarness::new("web", false, false, responses);
+    let mut harness = TestHarness::new("external_channel", false, false, responses);
     let (result, events) = harness.dispatch("do something", false).await;
 
     assert!(result.error.is_none(), "dispatch should succeed: {:?}", result.error);
@@ -546,7 +552,7 @@ async fn pattern_d_say_then_complete_safe_mode() {
         ),
     ];
 
-    let mut harness = TestHarness::new("web", true, false, responses);
+    let mut harness = TestHarness::new("external_channel", true, false, responses);
     let (result, events) = harness.dispatch("do something", false).await;
 
     assert!(result.error.is_none(), "dispatch should succeed: {:?}", result.error);
@@ -672,7 +678,7 @@ async fn swap_flow_with_trace() {
     ];
 
     // Use skill-aware harness so `use_skill` appears in the tools list
-    let mut harness = TestHarness::new_with_skills("web", false, &["swap"], responses);
+    let mut harness = TestHarness::new_with_skills("external_channel", false, &["swap"], responses);
     let (result, events) = harness.dispatch("swap 0.02 eth to starkbot", false).await;
 
     // Write trace for auditing
@@ -838,9 +844,9 @@ async fn swap_flow_realistic() {
     )
     .expect("save agent settings");
 
-    // Create a web channel (safe_mode off so AI has full tool access)
+    // Create an external channel (safe_mode off so AI has full tool access)
     let channel = db
-        .create_channel_with_safe_mode("web", "test-channel", "fake-token", None, false)
+        .create_channel_with_safe_mode("external_channel", "test-channel", "fake-token", None, false)
         .expect("create channel");
     let channel_id = channel.id;
 
@@ -885,6 +891,8 @@ async fn swap_flow_realistic() {
         session_mode: None,
         selected_network: None,
         force_safe_mode: false,
+        from_admin: false,
+        attachments: Vec::new(),
     };
 
     eprintln!("  Dispatching: \"{}\"", msg.text);
@@ -1163,7 +1171,7 @@ async fn lp_deposit_flow_with_trace() {
     ];
 
     // Use skill-aware harness so `use_skill` appears in the tools list
-    let mut harness = TestHarness::new_with_skills("web", false, &["uniswap_lp"], responses);
+    let mut harness = TestHarness::new_with_skills("external_channel", false, &["uniswap_lp"], responses);
     let (result, events) = harness.dispatch("deposit 1000 starkbot into the uniswap LP pool", false).await;
 
     // Write trace for auditing
@@ -1337,7 +1345,7 @@ async fn safe_mode_finished_task_advances_task_queue() {
         ),
     ];
 
-    let mut harness = TestHarness::new("web", true, false, responses);
+    let mut harness = TestHarness::new("external_channel", true, false, responses);
     let (result, _events) = harness.dispatch("multi-part question", false).await;
 
     assert!(result.error.is_none(), "dispatch should succeed: {:?}", result.error);
@@ -1434,7 +1442,7 @@ async fn consecutive_say_to_user_with_pending_tasks_does_not_terminate() {
         ),
     ];
 
-    let mut harness = TestHarness::new("web", false, false, responses);
+    let mut harness = TestHarness::new("external_channel", false, false, responses);
     let (result, _events) = harness.dispatch("do two things", false).await;
 
     assert!(result.error.is_none(), "dispatch should succeed: {:?}", result.error);
@@ -1524,7 +1532,7 @@ async fn say_to_user_mixed_with_other_tools_does_not_trigger_loop_break() {
         ),
     ];
 
-    let mut harness = TestHarness::new("web", false, false, responses);
+    let mut harness = TestHarness::new("external_channel", false, false, responses);
     let (result, _events) = harness.dispatch("do something complex", false).await;
 
     assert!(result.error.is_none(), "dispatch should succeed: {:?}", result.error);
@@ -1553,6 +1561,286 @@ async fn say_to_user_mixed_with_other_tools_does_not_trigger_loop_break() {
     );
 }
 
+#[tokio::test]
+async fn unknown_tool_call_returns_available_tools_to_model() {
+    let responses = vec![
+        // Iteration 1: the model hallucinates a tool name
+        AiResponse::with_tools(
+            String::new(),
+            vec![tool_call("fetch_weather_now", json!({"city": "Boston"}))],
+        ),
+        // Iteration 2: it recovers and finishes
+        AiResponse::with_tools(
+            String::new(),
+            vec![tool_call("task_fully_completed", json!({"summary": "Done."}))],
+        ),
+    ];
+
+    let mut harness = TestHarness::new("external_channel", false, false, responses);
+    let (result, _events) = harness.dispatch("what's the weather?", false).await;
+    assert!(result.error.is_none(), "dispatch should succeed: {:?}", result.error);
+
+    // The second iteration sees an error response naming real tools
+    let trace = harness.get_trace();
+    assert!(trace.len() >= 2, "expected a second iteration, got {}", trace.len());
+    let response = trace[1]
+        .input_tool_history
+        .iter()
+        .flat_map(|h| h.tool_responses.iter())
+        .find(|r| r.content.contains("fetch_weather_now"))
+        .expect("tool response for the unknown tool");
+    assert!(response.is_error);
+    assert!(response.content.starts_with("Unknown tool 'fetch_weather_now'"));
+    assert!(response.content.contains("task_fully_completed"));
+}
+
+#[tokio::test]
+async fn partial_responses_are_broadcast_only_when_enabled() {
+    let responses = || {
+        vec![
+            AiResponse::with_tools(
+                "Let me check the docs first.".to_string(),
+                vec![tool_call("say_to_user", json!({"message": "Checking..."}))],
+            ),
+            AiResponse::with_tools(
+                String::new(),
+                vec![tool_call("task_fully_completed", json!({"summary": "Done."}))],
+            ),
+        ]
+    };
+    let partials = |events: &[GatewayEvent]| -> Vec<String> {
+        events
+            .iter()
+            .filter(|e| e.event == "agent.partial_response")
+            .filter_map(|e| e.data.get("text").and_then(|t| t.as_str()).map(String::from))
+            .collect()
+    };
+
+    // Off by default
+    let mut harness = TestHarness::new("discord", false, false, responses());
+    let (_, events) = harness.dispatch("read the docs", false).await;
+    assert!(partials(&events).is_empty());
+
+    let mut harness = TestHarness::new("discord", false, false, responses());
+    harness
+        .dispatcher
+        .db
+        .set_channel_setting(harness.channel_id, "discord_partial_responses", "true")
+        .expect("set discord_partial_responses");
+    let (result, events) = harness.dispatch("read the docs", false).await;
+    assert!(result.error.is_none(), "dispatch should succeed: {:?}", result.error);
+    assert_eq!(partials(&events), vec!["Let me check the docs first.".to_string()]);
+}
+
+#[tokio::test]
+async fn channel_system_prompt_reaches_the_model() {
+    let done = || {
+        vec![AiResponse::with_tools(
+            String::new(),
+            vec![tool_call("task_fully_completed", json!({"summary": "Done."}))],
+        )]
+    };
+    let system_prompt = |harness: &TestHarness| harness.get_trace()[0].input_messages[0].content.clone();
+
+    let mut harness = TestHarness::new("external_channel", false, false, done());
+    harness
+        .dispatcher
+        .db
+        .set_channel_setting(harness.channel_id, "system_prompt", "Always answer like a pirate.")
+        .expect("set system_prompt");
+    let (result, _) = harness.dispatch("hello", false).await;
+    assert!(result.error.is_none(), "dispatch should succeed: {:?}", result.error);
+    let prompt = system_prompt(&harness);
+    assert!(prompt.contains("## Channel Instructions\nAlways answer like a pirate."), "{}", prompt);
+
+    // A blank override falls back to the default prompt
+    let mut harness = TestHarness::new("external_channel", false, false, done());
+    harness
+        .dispatcher
+        .db
+        .set_channel_setting(harness.channel_id, "system_prompt", "   ")
+        .expect("set system_prompt");
+    harness.dispatch("hello", false).await;
+    assert!(!system_prompt(&harness).contains("## Channel Instructions"));
+}
+
+#[tokio::test]
+async fn oversized_messages_are_truncated_or_rejected() {
+    let done = || {
+        vec![AiResponse::with_tools(
+            String::new(),
+            vec![tool_call("task_fully_completed", json!({"summary": "Done."}))],
+        )]
+    };
+    let long_text = format!("{}{}", "a".repeat(50), "b".repeat(50));
+
+    let mut harness = TestHarness::new("external_channel", false, false, done());
+    harness
+        .dispatcher
+        .db
+        .set_channel_setting(harness.channel_id, "max_message_chars", "50")
+        .expect("set max_message_chars");
+    let (result, _) = harness.dispatch(&long_text, false).await;
+    assert!(result.error.is_none(), "dispatch should succeed: {:?}", result.error);
+    let trace = harness.get_trace();
+    let user_turn = trace[0]
+        .input_messages
+        .iter()
+        .rev()
+        .find(|m| m.role == crate::ai::MessageRole::User)
+        .expect("user message");
+    assert!(user_turn.content.contains(&"a".repeat(50)));
+    assert!(!user_turn.content.contains('b'), "{}", user_turn.content);
+    assert!(user_turn.content.contains("first 50 of 100 characters"), "{}", user_turn.content);
+
+    let mut harness = TestHarness::new("external_channel", false, false, done());
+    let db = &harness.dispatcher.db;
+    db.set_channel_setting(harness.channel_id, "max_message_chars", "50")
+        .expect("set max_message_chars");
+    db.set_channel_setting(harness.channel_id, "oversized_message_action", "reject")
+        .expect("set oversized_message_action");
+    let (result, _) = harness.dispatch(&long_text, false).await;
+    assert!(result.error.as_deref().is_some_and(|e| e.contains("too long")), "{:?}", result.error);
+    assert!(harness.get_trace().is_empty(), "rejected messages must not reach the AI");
+}
+
+#[tokio::test]
+async fn attachment_paths_reach_the_model() {
+    let harness = TestHarness::new(
+        "external_channel",
+        false,
+        false,
+        vec![AiResponse::with_tools(
+            String::new(),
+            vec![tool_call("task_fully_completed", json!({"summary": "Done."}))],
+        )],
+    );
+    let mut msg = harness.make_message("fix this script", false);
+    msg.attachments = vec!["attachments/discord/123/fix_me.py".to_string()];
+    let result = harness.dispatcher.dispatch(msg).await;
+    assert!(result.error.is_none(), "dispatch should succeed: {:?}", result.error);
+
+    let trace = harness.get_trace();
+    let user_turn = trace[0]
+        .input_messages
+        .iter()
+        .rev()
+        .find(|m| m.role == crate::ai::MessageRole::User)
+        .expect("user message");
+    assert!(user_turn.content.contains("fix this script"));
+    assert!(user_turn.content.contains("attachments/discord/123/fix_me.py"));
+}
+
+#[tokio::test]
+async fn truncated_reply_can_be_continued() {
+    let mut cut_off = AiResponse::text("Here is part one of the essay".to_string());
+    cut_off.stop_reason = Some("max_tokens".to_string());
+    let mut harness = TestHarness::new(
+        "external_channel",
+        false,
+        false,
+        vec![cut_off, AiResponse::text("and here is the rest.".to_string())],
+    );
+
+    let (result, _) = harness.dispatch("write me a long essay", false).await;
+    assert!(result.error.is_none(), "dispatch should succeed: {:?}", result.error);
+    assert!(result.response.contains("Send `continue` for the rest"), "{}", result.response);
+
+    let (result, _) = harness.dispatch("continue", false).await;
+    assert_eq!(result.response, "and here is the rest.");
+    let trace = harness.get_trace();
+    let user_turn = trace
+        .last()
+        .expect("second AI call")
+        .input_messages
+        .iter()
+        .rev()
+        .find(|m| m.role == crate::ai::MessageRole::User)
+        .expect("user message");
+    assert!(user_turn.content.contains("[CONTINUE]"), "{}", user_turn.content);
+
+    // Nothing left to continue
+    let (result, _) = harness.dispatch("/continue", false).await;
+    assert!(result.response.contains("no cut-off reply"), "{}", result.response);
+    assert_eq!(harness.get_trace().len(), 2);
+}
+
+#[tokio::test]
+async fn rate_limited_continue_keeps_the_cut_off_reply() {
+    let mut cut_off = AiResponse::text("Here is part one of the essay".to_string());
+    cut_off.stop_reason = Some("max_tokens".to_string());
+    let harness = TestHarness::new(
+        "external_channel",
+        false,
+        false,
+        vec![cut_off, AiResponse::text("and here is the rest.".to_string())],
+    );
+    let db = &harness.dispatcher.db;
+    db.set_channel_setting(harness.channel_id, "rate_limit_per_minute", "1")
+        .expect("set rate_limit_per_minute");
+    // Web users are exempt from the limit
+    let message = |text: &str| NormalizedMessage {
+        channel_type: "external_channel".to_string(),
+        ..harness.make_message(text, false)
+    };
+
+    let result = harness.dispatcher.dispatch(message("write me a long essay")).await;
+    assert!(result.response.contains("Send `continue` for the rest"), "{}", result.response);
+
+    let result = harness.dispatcher.dispatch(message("continue")).await;
+    assert!(result.error.is_some(), "second message within the minute should be rate limited");
+
+    db.set_channel_setting(harness.channel_id, "rate_limit_per_minute", "0")
+        .expect("clear rate_limit_per_minute");
+    let result = harness.dispatcher.dispatch(message("continue")).await;
+    assert_eq!(result.response, "and here is the rest.");
+}
+
+#[tokio::test]
+async fn token_usage_is_summed_per_dispatch() {
+    use crate::ai::TokenUsage;
+    let responses = vec![
+        AiResponse::with_tools(String::new(), vec![tool_call("say_to_user", json!({"message": "On it"}))])
+            .with_usage(Some(TokenUsage::new(100, 10))),
+        AiResponse::with_tools(String::new(), vec![tool_call("task_fully_completed", json!({"summary": ""}))])
+            .with_usage(Some(TokenUsage::new(120, 5))),
+        AiResponse::with_tools(
+            String::new(),
+            vec![tool_call("say_to_user", json!({"message": "Done", "finished_task": true}))],
+        )
+        .with_usage(Some(TokenUsage::new(50, 3))),
+    ];
+    let mut harness = TestHarness::new("external_channel", false, false, responses);
+
+    let (result, _) = harness.dispatch("do something", false).await;
+    assert!(result.error.is_none(), "dispatch should succeed: {:?}", result.error);
+    assert_eq!(result.usage, Some(TokenUsage::new(220, 15)));
+
+    // The next dispatch starts from zero
+    let (result, _) = harness.dispatch("and again", false).await;
+    assert_eq!(result.usage, Some(TokenUsage::new(50, 3)));
+}
+
+#[tokio::test]
+async fn cancelling_a_dispatch_leaves_the_channel_running() {
+    let harness = TestHarness::new(
+        "external_channel",
+        false,
+        false,
+        vec![AiResponse::text("never sent".to_string())],
+    );
+    let cancel = CancellationToken::new();
+    cancel.cancel();
+
+    let msg = harness.make_message("do something slow", false);
+    harness.dispatcher.dispatch_with_cancellation(msg, cancel).await;
+    assert!(harness.get_trace().is_empty(), "a cancelled dispatch should not call the AI");
+
+    // Other executions on the channel (e.g. main-mode cron jobs) are not stopped
+    assert!(!harness.execution_tracker.is_cancelled(harness.channel_id));
+    assert!(!harness.execution_tracker.get_cancellation_token(harness.channel_id).is_cancelled());
+}
+
 // ============================================================================
 // build_tool_list() unit tests
 // ============================================================================
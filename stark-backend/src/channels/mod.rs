@@ -1,5 +1,17 @@
+pub mod announce;
+pub mod connection_health;
 pub mod discord;
+pub mod discord_attachments;
+pub mod discord_debounce;
+pub mod discord_permissions;
+pub mod discord_pins;
+pub mod discord_presence;
+pub mod discord_reply_context;
+pub mod dispatch_rate_limiter;
 pub mod dispatcher;
+pub mod post_process;
+pub mod preferences;
+pub mod prompt_template;
 pub mod safe_mode_rate_limiter;
 pub mod session_writer;
 pub mod slack;
@@ -113,7 +125,7 @@ impl ChannelManager {
                 "discord" => "discord_bot_token",
                 "telegram" => "telegram_bot_token",
                 "slack" => "slack_bot_token",
-                _ => "", // Twitter and ExternalChannel don't use bot_token
+                _ => "", // Twitter, ExternalChannel and Webhook don't use bot_token
             };
             if !setting_key.is_empty() {
                 if let Ok(Some(token)) = self.db.get_channel_setting(channel_id, setting_key) {
@@ -172,10 +184,10 @@ impl ChannelManager {
 
         // Parse channel type
         let channel_type_enum = match types::ChannelType::from_str(&channel_type) {
-            Some(ct) => ct,
-            None => {
+            Ok(ct) => ct,
+            Err(e) => {
                 self.running_channels.remove(&channel_id);
-                return Err(format!("Unknown channel type: {}", channel_type));
+                return Err(e);
             }
         };
 
@@ -273,6 +285,10 @@ impl ChannelManager {
                 // Channel being in running_channels is sufficient.
                 log::info!("External channel '{}' started (no listener)", channel_name);
             }
+            types::ChannelType::Webhook => {
+                // Same as ExternalChannel: requests arrive on POST /api/channels/{id}/webhook
+                log::info!("Webhook channel '{}' started (no listener)", channel_name);
+            }
         }
 
         log::info!(
@@ -32,6 +32,45 @@ const MAX_QUEUE_SIZE: usize = 50;
 /// Time window for per-user rate limiting (10 minutes)
 const USER_RATE_LIMIT_WINDOW_MINS: i64 = 10;
 
+/// Snapshot of a user's query budget, for the `limits` command
+#[derive(Debug, Clone)]
+pub struct UserLimitStatus {
+    pub queries_used: usize,
+    pub queries_remaining: usize,
+    pub limit: usize,
+    /// Seconds until the oldest query in the window expires (None if no queries in the window)
+    pub reset_in_secs: Option<i64>,
+}
+
+impl UserLimitStatus {
+    /// User-facing summary of the current limits
+    pub fn to_message(&self) -> String {
+        let mut msg = format!(
+            "Your limits\n\nQueries: {}/{} used in the last {} minutes ({} remaining)",
+            self.queries_used, self.limit, USER_RATE_LIMIT_WINDOW_MINS, self.queries_remaining
+        );
+        match self.reset_in_secs {
+            Some(secs) if self.queries_remaining == 0 => {
+                msg.push_str(&format!("\nYou can ask again in {}s.", secs))
+            }
+            Some(secs) => msg.push_str(&format!("\nNext query slot frees up in {}s.", secs)),
+            None => {}
+        }
+        msg
+    }
+}
+
+/// Reply to the `limits` command for users who aren't rate limited
+pub const NO_LIMITS_MESSAGE: &str = "Your limits\n\nYou have full access — query limits don't apply to you.";
+
+/// Whether a message is the `limits` command (checked before the rate limit so it never costs a query)
+pub fn is_limits_command(text: &str) -> bool {
+    matches!(
+        text.trim().to_lowercase().as_str(),
+        "limits" | "limit" | "quota" | "ratelimit" | "rate limit"
+    )
+}
+
 /// Request for creating a safe mode channel
 #[derive(Debug)]
 struct ChannelCreationRequest {
@@ -384,6 +423,29 @@ impl SafeModeChannelRateLimiter {
         (remaining, limit)
     }
 
+    /// Get the user's current query budget and reset time without recording a query
+    pub fn get_user_limit_status(&self, user_id: &str, platform: &str) -> UserLimitStatus {
+        let user_key = format!("{}:{}", platform, user_id);
+        let limit = self.get_user_query_limit() as usize;
+
+        let mut state = self.state.lock().unwrap();
+        let history = state.user_histories
+            .entry(user_key)
+            .or_insert_with(|| UserQueryHistory::new(platform));
+
+        let used = history.count_recent_queries();
+        let reset_in_secs = history.query_times.first().map(|t| {
+            (*t + Duration::minutes(USER_RATE_LIMIT_WINDOW_MINS) - Utc::now()).num_seconds().max(0)
+        });
+
+        UserLimitStatus {
+            queries_used: used,
+            queries_remaining: limit.saturating_sub(used),
+            limit,
+            reset_in_secs,
+        }
+    }
+
     /// Check if a user can make a safe mode query and record it if allowed
     ///
     /// Returns Ok(remaining_queries) if allowed, Err(message) if rate limited.
@@ -460,6 +522,26 @@ mod tests {
         assert!(state.user_histories.is_empty());
     }
 
+    #[test]
+    fn test_is_limits_command() {
+        assert!(is_limits_command("limits"));
+        assert!(is_limits_command("  Quota "));
+        assert!(!is_limits_command("what are your limits?"));
+    }
+
+    #[test]
+    fn test_limit_status_message() {
+        let status = UserLimitStatus {
+            queries_used: 5,
+            queries_remaining: 0,
+            limit: 5,
+            reset_in_secs: Some(42),
+        };
+        let msg = status.to_message();
+        assert!(msg.contains("5/5 used"));
+        assert!(msg.contains("ask again in 42s"));
+    }
+
     #[test]
     fn test_user_query_history_basic() {
         let mut history = UserQueryHistory::new("discord");
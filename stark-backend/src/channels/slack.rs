@@ -1,5 +1,6 @@
 use crate::channels::dispatcher::MessageDispatcher;
-use crate::channels::safe_mode_rate_limiter::SafeModeChannelRateLimiter;
+use crate::channels::preferences;
+use crate::channels::safe_mode_rate_limiter::{self, SafeModeChannelRateLimiter};
 use crate::channels::types::{ChannelType, NormalizedMessage};
 use crate::channels::util;
 use crate::db::Database;
@@ -137,11 +138,7 @@ async fn fetch_chat_context(
             .text
             .as_deref()
             .unwrap_or("");
-        let preview = if text.len() > 300 {
-            format!("{}...", &text[..300])
-        } else {
-            text.to_string()
-        };
+        let preview = util::truncate_str(&text, 300);
         ctx.push_str(&format!("@{}{}: {}\n", who, tag, preview));
     }
 
@@ -346,6 +343,7 @@ async fn handle_shortcircuit_command(
             "StarkBot Commands\n\n\
             register <address> - Register your public address for tipping\n\
             status - Check your registration status\n\
+            limits - Show your remaining queries and when they reset\n\
             unregister - Remove your registered address\n\
             help - Show this message\n\n\
             For anything else, just @ me with your question!"
@@ -435,12 +433,12 @@ async fn process_slack_message(
     }
 
     // Determine safe mode from admin setting
+    let is_admin = state
+        .admin_user_ids
+        .as_deref()
+        .is_some_and(|admin_ids| admin_ids.split(',').map(|s| s.trim()).any(|id| id == user_id));
     let force_safe_mode = match &state.admin_user_ids {
-        Some(admin_ids) => {
-            let is_admin = admin_ids
-                .split(',')
-                .map(|s| s.trim())
-                .any(|id| id == user_id);
+        Some(_) => {
             if is_admin {
                 log::info!("Slack: User {} ({}) is admin — full access", user_name, user_id);
                 false
@@ -456,6 +454,27 @@ async fn process_slack_message(
         None => false,
     };
 
+    // `limits` command: report the user's own query budget without spending a query
+    if safe_mode_rate_limiter::is_limits_command(&clean_text) {
+        let reply = if force_safe_mode {
+            state.safe_mode_rate_limiter.get_user_limit_status(&user_id, "slack").to_message()
+        } else {
+            safe_mode_rate_limiter::NO_LIMITS_MESSAGE.to_string()
+        };
+        if let Err(e) = send_slack_message(
+            &client,
+            &state.bot_token,
+            &slack_channel,
+            &reply,
+            Some(&reply_thread_ts),
+        )
+        .await
+        {
+            log::error!("Slack: Failed to send limits status: {}", e);
+        }
+        return;
+    }
+
     // Check safe mode rate limit for non-admin queries
     if force_safe_mode {
         if let Err(rate_limit_msg) = state.safe_mode_rate_limiter.check_and_record_query(&user_id, "slack") {
@@ -472,21 +491,26 @@ async fn process_slack_message(
         }
     }
 
-    // Fetch chat context via conversations.history
-    let message_text = match fetch_chat_context(
-        &client,
-        &state.bot_token,
-        &slack_channel,
-        &message_ts,
-        &bot_user_id,
-    )
-    .await
-    {
-        Some(mut ctx) => {
-            ctx.push_str(&format!("\n[MESSAGE DIRECTED TO YOU:]\n{}", clean_text));
-            format!("[SLACK MESSAGE]\n\n{}", ctx)
+    // Fetch chat context via conversations.history. `pref` commands go through bare so the
+    // dispatcher recognizes them, after its own rate limit.
+    let message_text = if preferences::parse_command(&clean_text).is_some() {
+        clean_text.clone()
+    } else {
+        match fetch_chat_context(
+            &client,
+            &state.bot_token,
+            &slack_channel,
+            &message_ts,
+            &bot_user_id,
+        )
+        .await
+        {
+            Some(mut ctx) => {
+                ctx.push_str(&format!("\n[MESSAGE DIRECTED TO YOU:]\n{}", clean_text));
+                format!("[SLACK MESSAGE]\n\n{}", ctx)
+            }
+            None => format!("[SLACK MESSAGE]\n\n{}", clean_text),
         }
-        None => format!("[SLACK MESSAGE]\n\n{}", clean_text),
     };
 
     let normalized = NormalizedMessage {
@@ -501,6 +525,8 @@ async fn process_slack_message(
         session_mode: None,
         selected_network: None,
         force_safe_mode,
+        from_admin: is_admin,
+        attachments: Vec::new(),
     };
 
     // Subscribe to events for real-time tool call forwarding
@@ -590,7 +616,7 @@ async fn process_slack_message(
                 }
 
                 let display_text = if text.len() > 4000 {
-                    format!("{}...", &text[..3997])
+                    util::truncate_str(&text, 3997)
                 } else {
                     text
                 };
@@ -805,11 +831,7 @@ fn handle_push_event(
                     "Slack: AppMention from {} in {}: {}",
                     user_id,
                     slack_channel,
-                    if text.len() > 50 {
-                        format!("{}...", &text[..50])
-                    } else {
-                        text.clone()
-                    }
+                    util::log_preview(&text)
                 );
 
                 tokio::spawn(process_slack_message(
@@ -876,11 +898,7 @@ fn handle_push_event(
                     "Slack: DM from {} in {}: {}",
                     sender_id,
                     slack_channel,
-                    if text.len() > 50 {
-                        format!("{}...", &text[..50])
-                    } else {
-                        text.clone()
-                    }
+                    util::log_preview(&text)
                 );
 
                 tokio::spawn(process_slack_message(
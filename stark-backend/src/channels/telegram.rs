@@ -1,4 +1,12 @@
+//! Telegram bot listener using long polling
+//!
+//! teloxide's dispatcher long-polls `getUpdates` with the channel's bot token; messages are
+//! normalized and dispatched, tool events are streamed back to the chat, and replies are
+//! split to Telegram's 4096-character limit.
+
+use crate::channels::connection_health::ConnectionHealth;
 use crate::channels::dispatcher::MessageDispatcher;
+use crate::channels::preferences;
 use crate::channels::types::{ChannelType, NormalizedMessage};
 use crate::channels::util;
 use crate::db::Database;
@@ -7,12 +15,16 @@ use crate::gateway::events::EventBroadcaster;
 use crate::gateway::protocol::GatewayEvent;
 use crate::models::channel_settings::ChannelSettingKey;
 use crate::models::{Channel, ToolOutputVerbosity};
+use futures_util::future::BoxFuture;
 use rand::seq::SliceRandom;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use teloxide::error_handlers::ErrorHandler;
 use teloxide::prelude::*;
 use teloxide::requests::Requester;
 use teloxide::types::MessageId;
 use tokio::sync::oneshot;
+use tokio_util::sync::CancellationToken;
 
 /// Format a tool call event for Telegram display based on verbosity
 fn format_tool_call_for_telegram(
@@ -26,11 +38,7 @@ fn format_tool_call_for_telegram(
         ToolOutputVerbosity::Full => {
             let params_str = serde_json::to_string_pretty(parameters)
                 .unwrap_or_else(|_| parameters.to_string());
-            let params_display = if params_str.len() > 500 {
-                format!("{}...", &params_str[..500])
-            } else {
-                params_str
-            };
+            let params_display = util::truncate_str(&params_str, 500);
             Some(format!("🔧 Tool Call: {}\n{}", tool_name, params_display))
         }
     }
@@ -54,11 +62,7 @@ fn format_tool_result_for_telegram(
             ))
         }
         ToolOutputVerbosity::Full => {
-            let content_display = if content.len() > 1000 {
-                format!("{}...", &content[..1000])
-            } else {
-                content.to_string()
-            };
+            let content_display = util::truncate_str(&content, 1000);
             Some(format!(
                 "{} Tool Result: {} ({} ms)\n{}",
                 status, tool_name, duration_ms, content_display
@@ -230,6 +234,51 @@ async fn handle_shortcircuit_command(
     }
 }
 
+/// How often a dropped polling connection is probed for recovery
+const RECOVERY_PROBE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Handles long-polling failures: reports the channel as reconnecting and
+/// probes `getMe` until the API answers again or the listener shuts down.
+struct PollingErrorHandler {
+    bot: Bot,
+    health: Arc<ConnectionHealth>,
+    probing: AtomicBool,
+    /// Cancelled when the listener stops, ending any recovery probe
+    shutdown: CancellationToken,
+}
+
+impl ErrorHandler<teloxide::RequestError> for PollingErrorHandler {
+    fn handle_error(self: Arc<Self>, error: teloxide::RequestError) -> BoxFuture<'static, ()> {
+        Box::pin(async move {
+            log::warn!("Telegram: Polling error: {}", error);
+            self.health.reconnecting(&error.to_string());
+
+            // One probe at a time; the polling loop keeps retrying on its own
+            if self.probing.swap(true, Ordering::SeqCst) {
+                return;
+            }
+            let handler = self.clone();
+            tokio::spawn(async move {
+                loop {
+                    tokio::select! {
+                        _ = handler.shutdown.cancelled() => break,
+                        _ = tokio::time::sleep(RECOVERY_PROBE_INTERVAL) => {}
+                    }
+                    let probe = tokio::select! {
+                        _ = handler.shutdown.cancelled() => break,
+                        probe = handler.bot.get_me() => probe,
+                    };
+                    if probe.is_ok() {
+                        handler.health.connected();
+                        break;
+                    }
+                }
+                handler.probing.store(false, Ordering::SeqCst);
+            });
+        })
+    }
+}
+
 /// Start a Telegram bot listener
 pub async fn start_telegram_listener(
     channel: Channel,
@@ -269,6 +318,13 @@ pub async fn start_telegram_listener(
     let bot_username = me.username().to_string();
     let bot_user_id = me.id;
 
+    let health = Arc::new(ConnectionHealth::new(
+        channel_id,
+        ChannelType::Telegram.as_str(),
+        broadcaster.clone(),
+    ));
+    health.connected();
+
     // Load admin user ID setting
     let admin_user_id: Option<String> = db
         .get_channel_setting(channel_id, ChannelSettingKey::TelegramAdminUserId.as_ref())
@@ -300,6 +356,7 @@ pub async fn start_telegram_listener(
     let broadcaster_for_handler = broadcaster.clone();
     let bot_username_for_handler = bot_username.clone();
     let db_for_handler = db.clone();
+    let health_for_handler = health.clone();
 
     // Create message handler
     let handler = Update::filter_message().endpoint(
@@ -309,8 +366,11 @@ pub async fn start_telegram_listener(
             let admin_user_id = admin_user_id.clone();
             let bot_username = bot_username_for_handler.clone();
             let bot_user_id = bot_user_id;
+            let health = health_for_handler.clone();
             async move {
                 log::info!("Telegram: Received update from chat {}", msg.chat.id);
+                // An update arrived, so polling works again
+                health.connected();
 
                 // Only handle text messages
                 if let Some(text) = msg.text() {
@@ -414,8 +474,11 @@ pub async fn start_telegram_listener(
                         );
                     }
 
-                    // Prepend recent chat context (like Twitter thread context)
-                    let message_text = {
+                    // Prepend recent chat context (like Twitter thread context). `pref` commands
+                    // go through bare so the dispatcher recognizes them, after its own rate limit.
+                    let message_text = if preferences::parse_command(&clean_text).is_some() {
+                        clean_text.clone()
+                    } else {
                         let chat_id_str = msg.chat.id.to_string();
                         let recent = db.get_recent_telegram_chat_messages(channel_id, &chat_id_str, 6);
                         match recent {
@@ -430,11 +493,7 @@ pub async fn start_telegram_listener(
                                         let who = m.user_name.as_deref()
                                             .unwrap_or(m.user_id.as_deref().unwrap_or("unknown"));
                                         let tag = if m.is_bot_response { " [you]" } else { "" };
-                                        let preview = if m.content.len() > 300 {
-                                            format!("{}...", &m.content[..300])
-                                        } else {
-                                            m.content.clone()
-                                        };
+                                        let preview = util::truncate_str(&m.content, 300);
                                         ctx.push_str(&format!("@{}{}: {}\n", who, tag, preview));
                                     }
                                     ctx.push_str(&format!("\n[MESSAGE DIRECTED TO YOU:]\n{}", clean_text));
@@ -447,6 +506,7 @@ pub async fn start_telegram_listener(
                         }
                     };
 
+                    let is_admin = admin_user_id.as_ref() == Some(&user_id);
                     let normalized = NormalizedMessage {
                         channel_id,
                         channel_type: ChannelType::Telegram.to_string(),
@@ -459,6 +519,8 @@ pub async fn start_telegram_listener(
                         session_mode: None,
                         selected_network: None,
                         force_safe_mode,
+                        from_admin: is_admin,
+                        attachments: Vec::new(),
                     };
 
                     // Subscribe to events for real-time tool call forwarding
@@ -555,7 +617,7 @@ pub async fn start_telegram_listener(
                                 }
 
                                 let display_text = if text.len() > 4096 {
-                                    format!("{}...", &text[..4093])
+                                    util::truncate_str(&text, 4093)
                                 } else {
                                     text
                                 };
@@ -721,20 +783,30 @@ pub async fn start_telegram_listener(
     );
 
     // Create dispatcher
-    let mut tg_dispatcher = Dispatcher::builder(bot, handler)
+    let mut tg_dispatcher = Dispatcher::builder(bot.clone(), handler)
         .dependencies(dptree::deps![dispatcher, db_for_handler])
         .enable_ctrlc_handler()
         .build();
 
+    let listener = teloxide::update_listeners::polling_default(bot.clone()).await;
+    let probe_shutdown = CancellationToken::new();
+    let polling_errors = Arc::new(PollingErrorHandler {
+        bot,
+        health,
+        probing: AtomicBool::new(false),
+        shutdown: probe_shutdown.clone(),
+    });
+
     // Run with shutdown signal
     tokio::select! {
         _ = shutdown_rx => {
             log::info!("Telegram listener {} received shutdown signal", channel_name);
         }
-        _ = tg_dispatcher.dispatch() => {
+        _ = tg_dispatcher.dispatch_with_listener(listener, polling_errors) => {
             log::info!("Telegram listener {} stopped", channel_name);
         }
     }
+    probe_shutdown.cancel();
 
     // Emit stopped event
     broadcaster.broadcast(GatewayEvent::channel_stopped(
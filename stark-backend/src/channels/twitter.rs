@@ -5,13 +5,14 @@
 
 use crate::channels::dispatcher::MessageDispatcher;
 use crate::channels::types::{ChannelType, NormalizedMessage};
+use crate::channels::util;
 use crate::controllers::api_keys::ApiKeyId;
 use crate::db::Database;
 use crate::gateway::events::EventBroadcaster;
 use crate::gateway::protocol::GatewayEvent;
 use crate::models::{Channel, ChannelSettingKey};
 use crate::tools::builtin::social_media::{
-    check_subscription_tier, generate_oauth_header, percent_encode, TwitterCredentials,
+    check_subscription_tier, generate_oauth_header, percent_encode, TwitterAuth, TwitterCredentials,
     XSubscriptionTier, TWITTER_MAX_CHARS, TWITTER_PREMIUM_MAX_CHARS,
 };
 use once_cell::sync::Lazy;
@@ -365,7 +366,8 @@ pub async fn start_twitter_listener(
     }
 
     // Auto-detect subscription tier (Premium allows long tweets)
-    config.subscription_tier = check_subscription_tier(&client, &config.credentials).await;
+    config.subscription_tier =
+        check_subscription_tier(&client, &TwitterAuth::OAuth1(config.credentials.clone())).await;
     log::info!(
         "Twitter: Subscription tier={:?}, max_chars={}",
         config.subscription_tier,
@@ -1025,6 +1027,8 @@ async fn process_mention(
         session_mode: None,
         selected_network: None,
         force_safe_mode,
+        from_admin: !force_safe_mode,
+        attachments: Vec::new(),
     };
 
     // Subscribe to events to capture say_to_user messages.
@@ -1243,11 +1247,7 @@ async fn post_single_tweet(
             log::info!(
                 "Twitter: Posted tweet {} - {}",
                 tweet.id,
-                if tweet.text.len() > 50 {
-                    format!("{}...", &tweet.text[..50])
-                } else {
-                    tweet.text.clone()
-                }
+                util::log_preview(&tweet.text)
             );
             tweet.id
         })
@@ -1,3 +1,4 @@
+use crate::ai::TokenUsage;
 use serde::{Deserialize, Serialize};
 
 /// Supported channel types
@@ -9,6 +10,7 @@ pub enum ChannelType {
     Discord,
     Twitter,
     ExternalChannel,
+    Webhook,
 }
 
 impl ChannelType {
@@ -20,24 +22,37 @@ impl ChannelType {
             Self::Discord => "discord",
             Self::Twitter => "twitter",
             Self::ExternalChannel => "external_channel",
+            Self::Webhook => "webhook",
         }
     }
 
-    /// Parse from string
-    pub fn from_str(s: &str) -> Option<Self> {
-        match s.to_lowercase().as_str() {
-            "telegram" => Some(Self::Telegram),
-            "slack" => Some(Self::Slack),
-            "discord" => Some(Self::Discord),
-            "twitter" => Some(Self::Twitter),
-            "external_channel" => Some(Self::ExternalChannel),
-            _ => None,
+    /// Parse from string (case-insensitive); the error lists the valid types
+    pub fn from_str(s: &str) -> Result<Self, String> {
+        match s.trim().to_lowercase().as_str() {
+            "telegram" => Ok(Self::Telegram),
+            "slack" => Ok(Self::Slack),
+            "discord" => Ok(Self::Discord),
+            "twitter" => Ok(Self::Twitter),
+            "external_channel" => Ok(Self::ExternalChannel),
+            "webhook" => Ok(Self::Webhook),
+            _ => Err(format!(
+                "Unknown channel type '{}'. Valid types: {}",
+                s,
+                Self::all().iter().map(|t| t.as_str()).collect::<Vec<_>>().join(", ")
+            )),
         }
     }
 
     /// All supported channel types
     pub fn all() -> &'static [ChannelType] {
-        &[Self::Telegram, Self::Slack, Self::Discord, Self::Twitter, Self::ExternalChannel]
+        &[
+            Self::Telegram,
+            Self::Slack,
+            Self::Discord,
+            Self::Twitter,
+            Self::ExternalChannel,
+            Self::Webhook,
+        ]
     }
 
     /// Display name for UI
@@ -48,6 +63,7 @@ impl ChannelType {
             Self::Discord => "Discord",
             Self::Twitter => "Twitter",
             Self::ExternalChannel => "External Channel",
+            Self::Webhook => "Webhook",
         }
     }
 }
@@ -88,6 +104,13 @@ pub struct NormalizedMessage {
     /// Force safe mode for this message (e.g., non-admin Discord queries)
     #[serde(default)]
     pub force_safe_mode: bool,
+    /// The channel verified the sender as an admin (logged-in web user, configured
+    /// Discord/Slack/Telegram/Twitter admin). Admin-only tools require it.
+    #[serde(default)]
+    pub from_admin: bool,
+    /// Workspace-relative paths of files the user attached, already saved to disk
+    #[serde(default)]
+    pub attachments: Vec<String>,
 }
 
 /// Handle to a running channel listener
@@ -119,6 +142,10 @@ impl ChannelHandle {
 pub struct DispatchResult {
     pub response: String,
     pub error: Option<String>,
+    /// Provider token usage summed over every AI call made for this dispatch
+    pub usage: Option<TokenUsage>,
+    /// The chat session the message was handled in, once one was resolved
+    pub session_id: Option<i64>,
 }
 
 impl DispatchResult {
@@ -126,6 +153,8 @@ impl DispatchResult {
         Self {
             response,
             error: None,
+            usage: None,
+            session_id: None,
         }
     }
 
@@ -133,6 +162,36 @@ impl DispatchResult {
         Self {
             response: String::new(),
             error: Some(error),
+            usage: None,
+            session_id: None,
+        }
+    }
+
+    pub fn with_usage(mut self, usage: Option<TokenUsage>) -> Self {
+        self.usage = usage;
+        self
+    }
+
+    pub fn with_session_id(mut self, session_id: i64) -> Self {
+        self.session_id = Some(session_id);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_channel_type_from_str() {
+        assert_eq!(ChannelType::from_str("discord"), Ok(ChannelType::Discord));
+        assert_eq!(ChannelType::from_str(" Webhook "), Ok(ChannelType::Webhook));
+        for t in ChannelType::all() {
+            assert_eq!(ChannelType::from_str(t.as_str()), Ok(*t));
         }
+
+        let err = ChannelType::from_str("discrod").unwrap_err();
+        assert!(err.contains("'discrod'"));
+        assert!(err.contains("telegram, slack, discord, twitter, external_channel, webhook"));
     }
 }
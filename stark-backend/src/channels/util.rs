@@ -19,8 +19,12 @@ pub fn split_message(text: &str, max_len: usize) -> Vec<String> {
             if line.len() > max_len {
                 let mut remaining = line;
                 while remaining.len() > max_len {
-                    chunks.push(remaining[..max_len].to_string());
-                    remaining = &remaining[max_len..];
+                    let mut end = max_len;
+                    while !remaining.is_char_boundary(end) {
+                        end -= 1;
+                    }
+                    chunks.push(remaining[..end].to_string());
+                    remaining = &remaining[end..];
                 }
                 if !remaining.is_empty() {
                     current = remaining.to_string();
@@ -43,6 +47,58 @@ pub fn split_message(text: &str, max_len: usize) -> Vec<String> {
     chunks
 }
 
+/// Like `split_message`, but when the text needs more than one chunk each chunk is
+/// prefixed with "(part i/n) ". Room for the prefix is reserved so every chunk,
+/// prefix included, still fits in `max_len`. A single chunk is returned unprefixed.
+pub fn split_message_numbered(text: &str, max_len: usize) -> Vec<String> {
+    let chunks = split_message(text, max_len);
+    if chunks.len() <= 1 {
+        return chunks;
+    }
+
+    // Reserving room can push the count to more digits, so repeat until it settles
+    let mut total = chunks.len();
+    loop {
+        let reserve = part_prefix(total, total).len();
+        let chunks = split_message(text, max_len.saturating_sub(reserve).max(1));
+        if chunks.len() == total {
+            return chunks
+                .into_iter()
+                .enumerate()
+                .map(|(i, chunk)| format!("{}{}", part_prefix(i + 1, total), chunk))
+                .collect();
+        }
+        total = chunks.len();
+    }
+}
+
+fn part_prefix(part: usize, total: usize) -> String {
+    format!("(part {}/{}) ", part, total)
+}
+
+/// Truncate `text` to at most `max_bytes` bytes, appending "..." if anything was cut.
+/// Backs off to the previous char boundary so multi-byte characters (emoji, CJK)
+/// near the cutoff can't cause a slicing panic.
+pub fn truncate_str(text: &str, max_bytes: usize) -> String {
+    if text.len() <= max_bytes {
+        return text.to_string();
+    }
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}...", &text[..end])
+}
+
+/// Truncated preview of a user message for log lines: at most `STARK_LOG_PREVIEW_CHARS`
+/// characters, plus "..." if anything was cut.
+pub fn log_preview(text: &str) -> String {
+    match text.char_indices().nth(crate::config::log_preview_chars()) {
+        Some((end, _)) => format!("{}...", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
 /// Parse "Retry after Xs" from a platform API error string.
 /// Returns the number of seconds to wait, or None if not a rate-limit error.
 pub fn parse_retry_after(err: &str) -> Option<u64> {
@@ -155,3 +211,72 @@ pub fn event_matches_session(
         _ => false,
     }
 }
+
+/// Stricter `event_matches_session` for streams that must only ever see their own
+/// conversation: the event has to carry both this `channel_id` and this `chat_id`.
+/// Channel-wide events without a `chat_id` are not forwarded.
+pub fn event_matches_chat(data: &serde_json::Value, channel_id: i64, chat_id: &str) -> bool {
+    data.get("channel_id").and_then(|v| v.as_i64()) == Some(channel_id)
+        && data.get("chat_id").and_then(|v| v.as_str()) == Some(chat_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_str_short_text_unchanged() {
+        assert_eq!(truncate_str("hello", 50), "hello");
+    }
+
+    #[test]
+    fn test_truncate_str_emoji_at_boundary() {
+        // 48 ASCII bytes + a 4-byte emoji spanning bytes 48..52, so byte 50 is mid-char
+        let text = format!("{}🚀 and more text after", "a".repeat(48));
+        assert!(!text.is_char_boundary(50));
+        let truncated = truncate_str(&text, 50);
+        assert_eq!(truncated, format!("{}...", "a".repeat(48)));
+    }
+
+    #[test]
+    fn test_log_preview_counts_chars() {
+        let limit = crate::config::log_preview_chars();
+        let text = "é".repeat(limit + 1);
+        assert_eq!(log_preview(&text), format!("{}...", "é".repeat(limit)));
+        assert_eq!(log_preview(&"é".repeat(limit)), "é".repeat(limit));
+    }
+
+    #[test]
+    fn test_split_message_multibyte_hard_split() {
+        let line = "é".repeat(10); // 20 bytes, boundaries every 2 bytes
+        let chunks = split_message(&line, 5);
+        assert!(chunks.iter().all(|c| c.len() <= 5));
+        assert_eq!(chunks.concat(), line);
+    }
+
+    #[test]
+    fn test_split_message_numbered() {
+        assert_eq!(split_message_numbered("short reply", 2000), vec!["short reply".to_string()]);
+
+        let text = (0..30).map(|i| format!("line number {:02}", i)).collect::<Vec<_>>().join("\n");
+        let chunks = split_message_numbered(&text, 100);
+        let total = chunks.len();
+        assert!(total > 1);
+        for (i, chunk) in chunks.iter().enumerate() {
+            assert!(chunk.len() <= 100, "chunk {} is {} bytes", i, chunk.len());
+            assert!(chunk.starts_with(&format!("(part {}/{}) ", i + 1, total)));
+        }
+    }
+
+    #[test]
+    fn test_event_matches_chat_requires_chat_id() {
+        let own = serde_json::json!({"channel_id": 0, "chat_id": "web-abc"});
+        let other = serde_json::json!({"channel_id": 0, "chat_id": "web-xyz"});
+        let channel_wide = serde_json::json!({"channel_id": 0});
+        assert!(event_matches_chat(&own, 0, "web-abc"));
+        assert!(!event_matches_chat(&other, 0, "web-abc"));
+        assert!(!event_matches_chat(&channel_wide, 0, "web-abc"));
+        // The lenient check still lets channel-wide events through
+        assert!(event_matches_session(&channel_wide, 0, "web-abc"));
+    }
+}
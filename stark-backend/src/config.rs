@@ -8,6 +8,8 @@ pub mod env_vars {
     pub const LOGIN_ADMIN_PUBLIC_ADDRESS: &str = "LOGIN_ADMIN_PUBLIC_ADDRESS";
     pub const BURNER_WALLET_PRIVATE_KEY: &str = "BURNER_WALLET_BOT_PRIVATE_KEY";
     pub const PORT: &str = "PORT";
+    // "json" emits one JSON object per log line; anything else keeps env_logger's text format
+    pub const LOG_FORMAT: &str = "LOG_FORMAT";
     pub const DATABASE_URL: &str = "DATABASE_URL";
     pub const WORKSPACE_DIR: &str = "STARK_WORKSPACE_DIR";
     pub const SKILLS_DIR: &str = "STARK_SKILLS_DIR";
@@ -15,6 +17,62 @@ pub mod env_vars {
     pub const SOUL_DIR: &str = "STARK_SOUL_DIR";
     // Disk quota (0 = disabled)
     pub const DISK_QUOTA_MB: &str = "STARK_DISK_QUOTA_MB";
+    // Per-channel share of the disk quota (0 = no per-channel cap)
+    pub const CHANNEL_DISK_QUOTA_MB: &str = "STARK_CHANNEL_DISK_QUOTA_MB";
+    // Max characters of user message text included in log lines
+    pub const LOG_PREVIEW_CHARS: &str = "STARK_LOG_PREVIEW_CHARS";
+    // Chaos testing: JSON fault map (or path to a JSON file) forcing tools to fail/time out
+    pub const TOOL_FAULT_INJECTION: &str = "STARK_TOOL_FAULT_INJECTION";
+    // Comma-separated tool groups whose output is wrapped as untrusted data (e.g. "web,social")
+    pub const INJECTION_GUARD_GROUPS: &str = "STARK_INJECTION_GUARD_GROUPS";
+    // Response post-processors: JSON array (or path to a JSON file) of transforms applied to final responses
+    pub const RESPONSE_POST_PROCESSORS: &str = "STARK_RESPONSE_POST_PROCESSORS";
+    // Retry a dispatch once when the provider returns a completely empty response (default: true)
+    pub const EMPTY_RESPONSE_RETRY: &str = "STARK_EMPTY_RESPONSE_RETRY";
+    // Fiat price source for fee estimates ("coingecko"; unset = no fiat conversion)
+    pub const FIAT_PRICE_SOURCE: &str = "STARK_FIAT_PRICE_SOURCE";
+    // Base URL of the CoinGecko-compatible API used by price_lookup
+    pub const PRICE_API_URL: &str = "STARK_PRICE_API_URL";
+    // CORS for the HTTP API: comma-separated origins ("*" = any; unset = same-origin only)
+    pub const CORS_ALLOWED_ORIGINS: &str = "STARK_CORS_ALLOWED_ORIGINS";
+    pub const CORS_ALLOWED_METHODS: &str = "STARK_CORS_ALLOWED_METHODS";
+    pub const CORS_ALLOWED_HEADERS: &str = "STARK_CORS_ALLOWED_HEADERS";
+    pub const CORS_ALLOW_CREDENTIALS: &str = "STARK_CORS_ALLOW_CREDENTIALS";
+    pub const CORS_MAX_AGE_SECS: &str = "STARK_CORS_MAX_AGE_SECS";
+    // Provider request log sampling: log 1 in N requests, plus all requests for listed users/channels
+    pub const PROVIDER_LOG_SAMPLE_RATE: &str = "STARK_PROVIDER_LOG_SAMPLE_RATE";
+    pub const PROVIDER_LOG_USERS: &str = "STARK_PROVIDER_LOG_USERS";
+    pub const PROVIDER_LOG_CHANNELS: &str = "STARK_PROVIDER_LOG_CHANNELS";
+    // Comma-separated programs exec may run; when set, exec runs in "allowlist" mode (e.g. "git,cargo,ls")
+    pub const EXEC_ALLOWLIST: &str = "STARK_EXEC_ALLOWLIST";
+    // Most output bytes an exec call may ask for with `max_output_bytes`
+    pub const EXEC_MAX_OUTPUT_BYTES: &str = "STARK_EXEC_MAX_OUTPUT_BYTES";
+    // Treat every exec call as a dry run: checks run and the plan is reported, nothing is spawned
+    pub const EXEC_DRY_RUN: &str = "STARK_EXEC_DRY_RUN";
+    // Files attached to Discord messages: largest saved to the workspace, and how long they're kept
+    pub const ATTACHMENT_MAX_BYTES: &str = "STARK_ATTACHMENT_MAX_BYTES";
+    pub const ATTACHMENT_TTL_HOURS: &str = "STARK_ATTACHMENT_TTL_HOURS";
+    // Tool execution priorities within a batch, lower runs first (e.g. "token_lookup=5,exec=60")
+    pub const TOOL_PRIORITIES: &str = "STARK_TOOL_PRIORITIES";
+    // Per-group tool execution time limits in seconds, e.g. "web=60,exec=1800" (0 = no limit)
+    pub const TOOL_GROUP_TIMEOUTS: &str = "STARK_TOOL_GROUP_TIMEOUTS";
+    // How long EIP-8004 discovery pages and searches are cached, in seconds (0 = no caching)
+    pub const EIP8004_DISCOVERY_CACHE_TTL_SECS: &str = "STARK_EIP8004_DISCOVERY_CACHE_TTL_SECS";
+    // Overall time limit for one /api/chat dispatch, in seconds (0 = no limit)
+    pub const CHAT_DISPATCH_TIMEOUT_SECS: &str = "STARK_CHAT_DISPATCH_TIMEOUT_SECS";
+    // Server-wide per-user message rate limit; channels can override it in their settings
+    pub const RATE_LIMIT_PER_MINUTE: &str = "STARK_RATE_LIMIT_PER_MINUTE";
+    pub const RATE_LIMIT_BURST: &str = "STARK_RATE_LIMIT_BURST";
+    // Bearer token required by GET /metrics; unset disables the endpoint
+    pub const METRICS_TOKEN: &str = "STARK_METRICS_TOKEN";
+    // Longest incoming message, in characters, before it's truncated or rejected (0 = no limit)
+    pub const MAX_MESSAGE_CHARS: &str = "STARK_MAX_MESSAGE_CHARS";
+    // Sub-agents running at once (server-wide and per channel), and how many more may wait for a slot
+    pub const SUBAGENT_MAX_CONCURRENT: &str = "STARK_SUBAGENT_MAX_CONCURRENT";
+    pub const SUBAGENT_MAX_PER_CHANNEL: &str = "STARK_SUBAGENT_MAX_PER_CHANNEL";
+    pub const SUBAGENT_MAX_QUEUED: &str = "STARK_SUBAGENT_MAX_QUEUED";
+    // Start in safe mode: only read-only tools are available on every channel (also `--safe-mode`)
+    pub const SAFE_MODE: &str = "STARK_SAFE_MODE";
     // QMD Memory configuration (simplified file-based memory system)
     pub const MEMORY_DIR: &str = "STARK_MEMORY_DIR";
     pub const MEMORY_REINDEX_INTERVAL_SECS: &str = "STARK_MEMORY_REINDEX_INTERVAL_SECS";
@@ -34,6 +92,21 @@ pub mod defaults {
     pub const SOUL_DIR: &str = "soul";
     pub const MEMORY_DIR: &str = "memory";
     pub const DISK_QUOTA_MB: u64 = 1024;
+    pub const CHANNEL_DISK_QUOTA_MB: u64 = 0;
+    pub const LOG_PREVIEW_CHARS: usize = 100;
+    pub const CORS_ALLOWED_METHODS: &str = "GET,POST,PUT,PATCH,DELETE,OPTIONS";
+    pub const CORS_ALLOWED_HEADERS: &str = "Authorization,Content-Type,Accept";
+    pub const CORS_MAX_AGE_SECS: usize = 3600;
+    pub const PRICE_API_URL: &str = "https://api.coingecko.com/api/v3";
+    pub const EIP8004_DISCOVERY_CACHE_TTL_SECS: u64 = 60;
+    pub const CHAT_DISPATCH_TIMEOUT_SECS: u64 = 600;
+    pub const EXEC_MAX_OUTPUT_BYTES: usize = 200_000;
+    pub const ATTACHMENT_MAX_BYTES: u64 = 1024 * 1024;
+    pub const ATTACHMENT_TTL_HOURS: u64 = 24;
+    pub const SUBAGENT_MAX_CONCURRENT: usize = 10;
+    pub const SUBAGENT_MAX_PER_CHANNEL: usize = 3;
+    pub const SUBAGENT_MAX_QUEUED: usize = 5;
+    pub const MAX_MESSAGE_CHARS: u32 = 0;
 }
 
 /// Returns the absolute path to the stark-backend directory.
@@ -92,6 +165,309 @@ pub fn disk_quota_mb() -> u64 {
         .unwrap_or(defaults::DISK_QUOTA_MB)
 }
 
+/// Get the per-channel disk quota in megabytes (0 = only the global quota applies)
+pub fn channel_disk_quota_mb() -> u64 {
+    env::var(env_vars::CHANNEL_DISK_QUOTA_MB)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(defaults::CHANNEL_DISK_QUOTA_MB)
+}
+
+/// Get the maximum number of characters of user message previews written to logs
+pub fn log_preview_chars() -> usize {
+    env::var(env_vars::LOG_PREVIEW_CHARS)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(defaults::LOG_PREVIEW_CHARS)
+}
+
+/// Get the tool fault injection spec (inline JSON or a path to a JSON file), if set.
+/// Test-only: never set this in production.
+pub fn tool_fault_injection() -> Option<String> {
+    env::var(env_vars::TOOL_FAULT_INJECTION)
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+}
+
+/// Get the tool groups whose output goes through the prompt-injection guard.
+/// Unset or empty disables the guard.
+pub fn injection_guard_groups() -> Vec<String> {
+    env::var(env_vars::INJECTION_GUARD_GROUPS)
+        .map(|v| {
+            v.split(',')
+                .map(|g| g.trim().to_lowercase())
+                .filter(|g| !g.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Get the response post-processor spec (inline JSON array or a path to a JSON file), if set
+pub fn response_post_processors() -> Option<String> {
+    env::var(env_vars::RESPONSE_POST_PROCESSORS)
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+}
+
+/// Whether an empty final response (no content, no tool calls, no error) is retried once
+pub fn empty_response_retry_enabled() -> bool {
+    env::var(env_vars::EMPTY_RESPONSE_RETRY)
+        .map(|v| !matches!(v.trim().to_lowercase().as_str(), "false" | "0" | "no" | "off"))
+        .unwrap_or(true)
+}
+
+/// Whether logs should be emitted as single-line JSON (`LOG_FORMAT=json`)
+pub fn log_format_json() -> bool {
+    env::var(env_vars::LOG_FORMAT)
+        .map(|v| v.trim().eq_ignore_ascii_case("json"))
+        .unwrap_or(false)
+}
+
+/// Fiat price source used to convert fee estimates to USD, if configured
+pub fn fiat_price_source() -> Option<String> {
+    env::var(env_vars::FIAT_PRICE_SOURCE)
+        .ok()
+        .map(|v| v.trim().to_lowercase())
+        .filter(|v| !v.is_empty() && v != "off")
+}
+
+/// Base URL of the price API (CoinGecko-compatible)
+pub fn price_api_url() -> String {
+    non_empty_env(env_vars::PRICE_API_URL)
+        .map(|v| v.trim().to_string())
+        .unwrap_or_else(|| defaults::PRICE_API_URL.to_string())
+}
+
+fn non_empty_env(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|v| !v.trim().is_empty())
+}
+
+/// Origins allowed to call the API cross-origin (raw comma-separated list), if set
+pub fn cors_allowed_origins() -> Option<String> {
+    non_empty_env(env_vars::CORS_ALLOWED_ORIGINS)
+}
+
+/// CORS allowed methods (raw comma-separated list); falls back to defaults when unset
+pub fn cors_allowed_methods() -> Option<String> {
+    non_empty_env(env_vars::CORS_ALLOWED_METHODS)
+}
+
+/// CORS allowed request headers (raw comma-separated list); falls back to defaults when unset
+pub fn cors_allowed_headers() -> Option<String> {
+    non_empty_env(env_vars::CORS_ALLOWED_HEADERS)
+}
+
+/// Whether cross-origin requests may carry credentials (default: false)
+pub fn cors_allow_credentials() -> bool {
+    env::var(env_vars::CORS_ALLOW_CREDENTIALS)
+        .map(|v| matches!(v.trim().to_lowercase().as_str(), "true" | "1" | "yes" | "on"))
+        .unwrap_or(false)
+}
+
+/// How long browsers may cache a preflight response, in seconds
+pub fn cors_max_age_secs() -> usize {
+    env::var(env_vars::CORS_MAX_AGE_SECS)
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(defaults::CORS_MAX_AGE_SECS)
+}
+
+/// How long EIP-8004 discovery results are served from cache, in seconds
+pub fn eip8004_discovery_cache_ttl_secs() -> u64 {
+    env::var(env_vars::EIP8004_DISCOVERY_CACHE_TTL_SECS)
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(defaults::EIP8004_DISCOVERY_CACHE_TTL_SECS)
+}
+
+/// How long an /api/chat request may run before it's cancelled, in seconds (0 = no limit)
+pub fn chat_dispatch_timeout_secs() -> u64 {
+    env::var(env_vars::CHAT_DISPATCH_TIMEOUT_SECS)
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(defaults::CHAT_DISPATCH_TIMEOUT_SECS)
+}
+
+/// Default messages per user per minute for channels without an override (0 = unlimited, the default)
+pub fn rate_limit_per_minute() -> u32 {
+    env::var(env_vars::RATE_LIMIT_PER_MINUTE)
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+/// Default burst allowance for channels without an override (0 = same as the per-minute limit)
+pub fn rate_limit_burst() -> u32 {
+    env::var(env_vars::RATE_LIMIT_BURST)
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+/// Bearer token for GET /metrics, if one is configured
+pub fn metrics_token() -> Option<String> {
+    env::var(env_vars::METRICS_TOKEN).ok().filter(|t| !t.trim().is_empty())
+}
+
+/// Default longest incoming message, in characters, for channels without an override (0 = no limit)
+pub fn max_message_chars() -> u32 {
+    env::var(env_vars::MAX_MESSAGE_CHARS)
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(defaults::MAX_MESSAGE_CHARS)
+}
+
+/// Sub-agents that may run at once across all channels
+pub fn subagent_max_concurrent() -> usize {
+    env::var(env_vars::SUBAGENT_MAX_CONCURRENT)
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .filter(|&n| n > 0)
+        .unwrap_or(defaults::SUBAGENT_MAX_CONCURRENT)
+}
+
+/// Sub-agents that may run at once in one channel
+pub fn subagent_max_per_channel() -> usize {
+    env::var(env_vars::SUBAGENT_MAX_PER_CHANNEL)
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .filter(|&n| n > 0)
+        .unwrap_or(defaults::SUBAGENT_MAX_PER_CHANNEL)
+}
+
+/// Sub-agents that may wait for a free slot before new spawns are rejected (0 = never queue)
+pub fn subagent_max_queued() -> usize {
+    env::var(env_vars::SUBAGENT_MAX_QUEUED)
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(defaults::SUBAGENT_MAX_QUEUED)
+}
+
+/// Log 1 in every N provider requests (0 = off, the default)
+pub fn provider_log_sample_rate() -> u64 {
+    env::var(env_vars::PROVIDER_LOG_SAMPLE_RATE)
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+/// User IDs whose provider requests are always logged
+pub fn provider_log_users() -> Vec<String> {
+    env::var(env_vars::PROVIDER_LOG_USERS)
+        .map(|v| {
+            v.split(',')
+                .map(|u| u.trim().to_string())
+                .filter(|u| !u.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Channel IDs whose provider requests are always logged (invalid entries are ignored)
+pub fn provider_log_channels() -> Vec<i64> {
+    env::var(env_vars::PROVIDER_LOG_CHANNELS)
+        .map(|v| v.split(',').filter_map(|c| c.trim().parse().ok()).collect())
+        .unwrap_or_default()
+}
+
+/// Programs the exec tool is restricted to, if an allowlist is configured
+pub fn exec_allowlist() -> Option<Vec<String>> {
+    let list: Vec<String> = non_empty_env(env_vars::EXEC_ALLOWLIST)?
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+    Some(list).filter(|l| !l.is_empty())
+}
+
+/// Whether every exec call is forced into dry-run mode
+pub fn exec_dry_run() -> bool {
+    env::var(env_vars::EXEC_DRY_RUN)
+        .map(|v| matches!(v.trim().to_lowercase().as_str(), "true" | "1" | "yes" | "on"))
+        .unwrap_or(false)
+}
+
+/// Upper bound on the exec tool's `max_output_bytes` parameter
+pub fn exec_max_output_bytes() -> usize {
+    env::var(env_vars::EXEC_MAX_OUTPUT_BYTES)
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .filter(|&n| n > 0)
+        .unwrap_or(defaults::EXEC_MAX_OUTPUT_BYTES)
+}
+
+/// Largest Discord attachment saved to the workspace, in bytes
+pub fn attachment_max_bytes() -> u64 {
+    env::var(env_vars::ATTACHMENT_MAX_BYTES)
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(defaults::ATTACHMENT_MAX_BYTES)
+}
+
+/// Hours a saved Discord attachment is kept before it's deleted
+pub fn attachment_ttl_hours() -> u64 {
+    env::var(env_vars::ATTACHMENT_TTL_HOURS)
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(defaults::ATTACHMENT_TTL_HOURS)
+}
+
+/// Per-tool execution priority overrides as (tool name, priority).
+/// Malformed entries are logged and skipped.
+pub fn tool_priorities() -> Vec<(String, i32)> {
+    let Some(spec) = non_empty_env(env_vars::TOOL_PRIORITIES) else {
+        return Vec::new();
+    };
+    spec.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .filter_map(|entry| {
+            let parsed = entry
+                .split_once('=')
+                .and_then(|(name, p)| Some((name.trim().to_string(), p.trim().parse::<i32>().ok()?)))
+                .filter(|(name, _)| !name.is_empty());
+            if parsed.is_none() {
+                log::warn!("Ignoring malformed {} entry '{}' (expected tool=priority)", env_vars::TOOL_PRIORITIES, entry);
+            }
+            parsed
+        })
+        .collect()
+}
+
+/// Per-group tool timeout overrides as (group name, seconds).
+/// Malformed entries are logged and skipped.
+pub fn tool_group_timeouts() -> Vec<(String, u64)> {
+    let Some(spec) = non_empty_env(env_vars::TOOL_GROUP_TIMEOUTS) else {
+        return Vec::new();
+    };
+    spec.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .filter_map(|entry| {
+            let parsed = entry
+                .split_once('=')
+                .and_then(|(group, secs)| Some((group.trim().to_string(), secs.trim().parse::<u64>().ok()?)))
+                .filter(|(group, _)| !group.is_empty());
+            if parsed.is_none() {
+                log::warn!("Ignoring malformed {} entry '{}' (expected group=seconds)", env_vars::TOOL_GROUP_TIMEOUTS, entry);
+            }
+            parsed
+        })
+        .collect()
+}
+
+/// Command-line flag equivalent to STARK_SAFE_MODE=true
+pub const SAFE_MODE_FLAG: &str = "--safe-mode";
+
+/// Whether the bot should start in safe mode, from the env var or the `--safe-mode` flag.
+/// There is deliberately no runtime command to leave safe mode; restart without the flag instead.
+pub fn safe_mode_requested() -> bool {
+    let from_env = env::var(env_vars::SAFE_MODE)
+        .map(|v| matches!(v.trim().to_lowercase().as_str(), "true" | "1" | "yes" | "on"))
+        .unwrap_or(false);
+    from_env || env::args().skip(1).any(|arg| arg == SAFE_MODE_FLAG)
+}
+
 /// Get the burner wallet private key from environment (for tools)
 pub fn burner_wallet_private_key() -> Option<String> {
     env::var(env_vars::BURNER_WALLET_PRIVATE_KEY).ok()
@@ -116,6 +492,8 @@ pub struct Config {
     pub burner_wallet_private_key: Option<String>,
     pub port: u16,
     pub database_url: String,
+    /// Started with --safe-mode / STARK_SAFE_MODE: side-effecting tools are disabled globally
+    pub safe_mode: bool,
 }
 
 impl Config {
@@ -142,6 +520,7 @@ impl Config {
                 .expect("PORT must be a valid number"),
             database_url: env::var(env_vars::DATABASE_URL)
                 .unwrap_or_else(|_| defaults::DATABASE_URL.to_string()),
+            safe_mode: safe_mode_requested(),
         }
     }
 }
@@ -1,7 +1,7 @@
 use actix_web::{web, HttpRequest, HttpResponse, Responder};
 use crate::ai::ArchetypeId;
 use crate::keystore_client::{KEYSTORE_CLIENT, DEFAULT_KEYSTORE_URL};
-use crate::models::{AgentSettings, AgentSettingsResponse, UpdateAgentSettingsRequest, UpdateBotSettingsRequest};
+use crate::models::{AgentSettings, AgentSettingsResponse, UpdateAgentFallbacksRequest, UpdateAgentSettingsRequest, UpdateAgentWeightsRequest, UpdateBotSettingsRequest};
 use crate::ai_endpoint_config;
 use crate::tools::rpc_config;
 use crate::AppState;
@@ -155,6 +155,12 @@ pub async fn update_agent_settings(
         }));
     }
 
+    if request.max_response_tokens <= 0 {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "max_response_tokens must be greater than 0"
+        }));
+    }
+
     // Save settings
     log::info!(
         "Saving agent settings: endpoint={}, archetype={}, max_response_tokens={}, max_context_tokens={}, has_secret_key={}",
@@ -205,6 +211,59 @@ pub async fn disable_agent(
     }
 }
 
+/// Set A/B traffic weights. Rows not listed keep their current weight; with
+/// fewer than two weighted rows, dispatch uses the single enabled endpoint.
+pub async fn update_agent_weights(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    body: web::Json<UpdateAgentWeightsRequest>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&state, &req) {
+        return resp;
+    }
+    let request = body.into_inner();
+
+    if let Some(w) = request.weights.iter().find(|w| w.weight < 0) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("Weight for agent settings {} must not be negative", w.id)
+        }));
+    }
+
+    let weights: Vec<(i64, i32)> = request.weights.iter().map(|w| (w.id, w.weight)).collect();
+    match state.db.set_agent_settings_weights(&weights) {
+        Ok(true) => {}
+        Ok(false) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "One or more agent settings ids were not found"
+            }));
+        }
+        Err(e) => {
+            log::error!("Failed to set agent settings weights: {}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Database error: {}", e)
+            }));
+        }
+    }
+
+    match state.db.list_agent_settings() {
+        Ok(settings) => {
+            let weighted = settings.iter().filter(|s| s.weight > 0).count();
+            log::info!("Updated agent settings weights ({} weighted endpoints)", weighted);
+            let responses: Vec<AgentSettingsResponse> = settings.into_iter().map(|s| s.into()).collect();
+            HttpResponse::Ok().json(serde_json::json!({
+                "ab_testing": weighted >= 2,
+                "settings": responses
+            }))
+        }
+        Err(e) => {
+            log::error!("Failed to list agent settings: {}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Database error: {}", e)
+            }))
+        }
+    }
+}
+
 /// Get bot settings
 pub async fn get_bot_settings(
     state: web::Data<AppState>,
@@ -391,6 +450,58 @@ pub async fn get_ai_endpoint_presets(
     HttpResponse::Ok().json(presets)
 }
 
+/// Set the provider fallback chain: when a dispatch's model fails (anything but a 400),
+/// these agent settings rows are tried in the given order
+pub async fn update_agent_fallbacks(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    body: web::Json<UpdateAgentFallbacksRequest>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&state, &req) {
+        return resp;
+    }
+    let request = body.into_inner();
+
+    let mut seen = std::collections::HashSet::new();
+    if let Some(id) = request.fallbacks.iter().find(|id| !seen.insert(**id)) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("Agent settings {} is listed more than once", id)
+        }));
+    }
+
+    match state.db.set_agent_settings_fallbacks(&request.fallbacks) {
+        Ok(true) => {}
+        Ok(false) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "One or more agent settings ids were not found"
+            }));
+        }
+        Err(e) => {
+            log::error!("Failed to set agent settings fallbacks: {}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Database error: {}", e)
+            }));
+        }
+    }
+
+    match state.db.list_agent_settings() {
+        Ok(settings) => {
+            log::info!("Updated agent settings fallback chain ({} fallbacks)", request.fallbacks.len());
+            let responses: Vec<AgentSettingsResponse> = settings.into_iter().map(|s| s.into()).collect();
+            HttpResponse::Ok().json(serde_json::json!({
+                "fallbacks": request.fallbacks,
+                "settings": responses
+            }))
+        }
+        Err(e) => {
+            log::error!("Failed to list agent settings: {}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Database error: {}", e)
+            }))
+        }
+    }
+}
+
 /// Configure routes
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(
@@ -401,6 +512,8 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
             .route("/archetypes", web::get().to(get_available_archetypes))
             .route("/endpoints", web::get().to(get_ai_endpoint_presets))
             .route("/disable", web::post().to(disable_agent))
+            .route("/weights", web::put().to(update_agent_weights))
+            .route("/fallbacks", web::put().to(update_agent_fallbacks))
     );
     cfg.service(
         web::scope("/api/bot-settings")
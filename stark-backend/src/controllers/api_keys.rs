@@ -43,10 +43,14 @@ pub enum ApiKeyId {
     TwitterAccessToken,
     #[strum(serialize = "TWITTER_ACCESS_TOKEN_SECRET")]
     TwitterAccessTokenSecret,
+    #[strum(serialize = "TWITTER_OAUTH2_ACCESS_TOKEN")]
+    TwitterOauth2AccessToken,
     #[strum(serialize = "SUPABASE_ACCESS_TOKEN")]
     SupabaseAccessToken,
     #[strum(serialize = "ALCHEMY_API_KEY")]
     AlchemyApiKey,
+    #[strum(serialize = "COINGECKO_API_KEY")]
+    CoingeckoApiKey,
     #[strum(serialize = "XAI_API_KEY")]
     XaiApiKey,
 }
@@ -61,8 +65,10 @@ impl ApiKeyId {
             Self::TwitterConsumerSecret => "TWITTER_CONSUMER_SECRET",
             Self::TwitterAccessToken => "TWITTER_ACCESS_TOKEN",
             Self::TwitterAccessTokenSecret => "TWITTER_ACCESS_TOKEN_SECRET",
+            Self::TwitterOauth2AccessToken => "TWITTER_OAUTH2_ACCESS_TOKEN",
             Self::SupabaseAccessToken => "SUPABASE_ACCESS_TOKEN",
             Self::AlchemyApiKey => "ALCHEMY_API_KEY",
+            Self::CoingeckoApiKey => "COINGECKO_API_KEY",
             Self::XaiApiKey => "XAI_API_KEY",
         }
     }
@@ -75,8 +81,10 @@ impl ApiKeyId {
             Self::TwitterConsumerSecret => Some(&["TWITTER_CONSUMER_SECRET", "TWITTER_API_SECRET"]),
             Self::TwitterAccessToken => Some(&["TWITTER_ACCESS_TOKEN"]),
             Self::TwitterAccessTokenSecret => Some(&["TWITTER_ACCESS_TOKEN_SECRET"]),
+            Self::TwitterOauth2AccessToken => Some(&["TWITTER_OAUTH2_ACCESS_TOKEN"]),
             Self::SupabaseAccessToken => Some(&["SUPABASE_ACCESS_TOKEN"]),
             Self::AlchemyApiKey => Some(&["ALCHEMY_API_KEY"]),
+            Self::CoingeckoApiKey => Some(&["COINGECKO_API_KEY"]),
             Self::XaiApiKey => Some(&["XAI_API_KEY"]),
         }
     }
@@ -141,6 +149,17 @@ pub fn get_service_configs() -> Vec<ServiceConfig> {
                 secret: true,
             }],
         },
+        ServiceConfig {
+            group: "coingecko".into(),
+            label: "CoinGecko".into(),
+            description: "Optional API key for token price lookups. Raises the free rate limit; a demo key is enough.".into(),
+            url: "https://www.coingecko.com/en/developers/dashboard".into(),
+            keys: vec![KeyConfig {
+                name: "COINGECKO_API_KEY".into(),
+                label: "API Key".into(),
+                secret: true,
+            }],
+        },
         ServiceConfig {
             group: "github".into(),
             label: "GitHub".into(),
@@ -166,7 +185,7 @@ pub fn get_service_configs() -> Vec<ServiceConfig> {
         ServiceConfig {
             group: "twitter".into(),
             label: "Twitter/X".into(),
-            description: "OAuth 1.0a credentials for posting tweets. Get all 4 keys from your Twitter Developer App's 'Keys and Tokens' tab.".into(),
+            description: "Credentials for posting tweets. Either an OAuth 2.0 user access token (tweet.read, tweet.write and users.read scopes), which is used for posting when set, or all 4 OAuth 1.0a keys from your Twitter Developer App's 'Keys and Tokens' tab. Image attachments and the Twitter channel still need the OAuth 1.0a keys.".into(),
             url: "https://developer.twitter.com/en/portal/projects-and-apps".into(),
             keys: vec![
                 KeyConfig {
@@ -189,6 +208,11 @@ pub fn get_service_configs() -> Vec<ServiceConfig> {
                     label: "Access Token Secret".into(),
                     secret: true,
                 },
+                KeyConfig {
+                    name: "TWITTER_OAUTH2_ACCESS_TOKEN".into(),
+                    label: "OAuth 2.0 User Access Token (optional)".into(),
+                    secret: true,
+                },
             ],
         },
         ServiceConfig {
@@ -409,6 +433,7 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             .route("", web::delete().to(delete_api_key))
             .route("/config", web::get().to(get_configs))
             .route("/value", web::get().to(get_api_key_value))
+            .route("/audit", web::get().to(list_api_key_audit))
             .route("/cloud_backup", web::post().to(backup_to_cloud))
             .route("/cloud_restore", web::post().to(restore_from_cloud))
             .route("/cloud_preview", web::get().to(preview_cloud_keys)),
@@ -542,6 +567,27 @@ async fn get_api_key_value(
     }
 }
 
+/// Recent API key changes (who changed which key, never the values)
+async fn list_api_key_audit(state: web::Data<AppState>, req: HttpRequest) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&state, &req) {
+        return resp;
+    }
+
+    match state.db.list_api_key_changes(100) {
+        Ok(changes) => HttpResponse::Ok().json(serde_json::json!({
+            "success": true,
+            "changes": changes,
+        })),
+        Err(e) => {
+            log::error!("Failed to list API key changes: {}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "success": false,
+                "error": "Failed to retrieve API key audit log",
+            }))
+        }
+    }
+}
+
 async fn list_api_keys(state: web::Data<AppState>, req: HttpRequest) -> impl Responder {
     if let Err(resp) = validate_session_from_request(&state, &req) {
         return resp;
@@ -613,12 +659,20 @@ async fn upsert_api_key(
     }
 
     // Store the key (key_name is the service_name in the database)
+    let existed = matches!(state.db.get_api_key(&body.key_name), Ok(Some(_)));
     match state.db.upsert_api_key(&body.key_name, &body.api_key) {
-        Ok(key) => HttpResponse::Ok().json(ApiKeyOperationResponse {
-            success: true,
-            key: Some(key.to_response()),
-            error: None,
-        }),
+        Ok(key) => {
+            let action = if existed { "rotate" } else { "set" };
+            let hint = crate::models::key_last_four(&body.api_key);
+            if let Err(e) = state.db.log_api_key_change(&body.key_name, action, "api", Some(&hint)) {
+                log::error!("Failed to audit API key change: {}", e);
+            }
+            HttpResponse::Ok().json(ApiKeyOperationResponse {
+                success: true,
+                key: Some(key.to_response()),
+                error: None,
+            })
+        }
         Err(e) => {
             log::error!("Failed to save API key: {}", e);
             HttpResponse::InternalServerError().json(ApiKeyOperationResponse {
@@ -642,6 +696,9 @@ async fn delete_api_key(
     match state.db.delete_api_key(&body.key_name) {
         Ok(deleted) => {
             if deleted {
+                if let Err(e) = state.db.log_api_key_change(&body.key_name, "delete", "api", None) {
+                    log::error!("Failed to audit API key change: {}", e);
+                }
                 HttpResponse::Ok().json(ApiKeyOperationResponse {
                     success: true,
                     key: None,
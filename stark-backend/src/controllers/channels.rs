@@ -1,8 +1,9 @@
 use actix_web::{web, HttpRequest, HttpResponse, Responder};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
+use crate::channels::announce::{self, AnnouncementDelivery};
 use crate::models::{
-    get_settings_for_channel_type, ChannelResponse, ChannelSettingsResponse,
+    get_settings_for_channel_type, ChannelResponse, ChannelSettingKey, ChannelSettingsResponse,
     ChannelSettingsSchemaResponse, ChannelType, CreateChannelRequest, CreateSafeModeChannelRequest,
     UpdateChannelRequest, UpdateChannelSettingsRequest,
 };
@@ -40,21 +41,41 @@ pub struct SafeModeChannelResponse {
     pub next_slot_ms: u64,
 }
 
+/// Request body for broadcasting an announcement to all enabled channels
+#[derive(Deserialize)]
+pub struct AnnounceRequest {
+    pub message: String,
+}
+
+#[derive(Serialize)]
+pub struct AnnounceResponse {
+    pub success: bool,
+    pub delivered: usize,
+    pub results: Vec<AnnouncementDelivery>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
 pub fn config(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/api/channels")
             .route("", web::get().to(list_channels))
             .route("", web::post().to(create_channel))
+            .route("/deleted", web::get().to(list_deleted_channels))
             .route("/safe-mode", web::post().to(create_safe_mode_channel))
             .route("/safe-mode/status", web::get().to(safe_mode_rate_limit_status))
+            .route("/announce", web::post().to(announce_to_all_channels))
             .route("/settings/schema/{channel_type}", web::get().to(get_settings_schema))
             .route("/{id}", web::get().to(get_channel))
             .route("/{id}", web::put().to(update_channel))
             .route("/{id}", web::delete().to(delete_channel))
+            .route("/{id}/restore", web::post().to(restore_channel))
+            .route("/{id}/purge", web::delete().to(purge_channel))
             .route("/{id}/start", web::post().to(start_channel))
             .route("/{id}/stop", web::post().to(stop_channel))
             .route("/{id}/settings", web::get().to(get_channel_settings))
-            .route("/{id}/settings", web::put().to(update_channel_settings)),
+            .route("/{id}/settings", web::put().to(update_channel_settings))
+            .route("/{id}/webhook", web::post().to(super::webhook_channel::receive_webhook)),
     );
 }
 
@@ -130,6 +151,29 @@ async fn list_channels(state: web::Data<AppState>, req: HttpRequest) -> impl Res
     }
 }
 
+/// List soft-deleted channels that can still be restored
+async fn list_deleted_channels(state: web::Data<AppState>, req: HttpRequest) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&state, &req) {
+        return resp;
+    }
+
+    match state.db.list_deleted_channels() {
+        Ok(channels) => HttpResponse::Ok().json(ChannelsListResponse {
+            success: true,
+            channels: Some(channels.into_iter().map(ChannelResponse::from).collect()),
+            error: None,
+        }),
+        Err(e) => {
+            log::error!("Failed to list deleted channels: {}", e);
+            HttpResponse::InternalServerError().json(ChannelsListResponse {
+                success: false,
+                channels: None,
+                error: Some("Failed to retrieve deleted channels".to_string()),
+            })
+        }
+    }
+}
+
 async fn get_channel(
     state: web::Data<AppState>,
     req: HttpRequest,
@@ -183,7 +227,7 @@ async fn create_channel(
         return HttpResponse::BadRequest().json(ChannelOperationResponse {
             success: false,
             channel: None,
-            error: Some("Invalid channel type. Valid options: telegram, slack, discord, twitter, external_channel".to_string()),
+            error: Some("Invalid channel type. Valid options: telegram, slack, discord, twitter, external_channel, webhook".to_string()),
         });
     }
 
@@ -218,7 +262,7 @@ async fn create_channel(
 
             // Check for unique constraint violation
             let error_msg = if e.to_string().contains("UNIQUE constraint failed") {
-                "A channel with this type and name already exists".to_string()
+                "A channel with this type and name already exists (if it was deleted, restore or purge it first)".to_string()
             } else {
                 "Failed to create channel".to_string()
             };
@@ -472,6 +516,80 @@ async fn delete_channel(
     }
 }
 
+/// Bring back a soft-deleted channel (disabled; start it again to reconnect)
+async fn restore_channel(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<i64>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&state, &req) {
+        return resp;
+    }
+
+    let id = path.into_inner();
+
+    match state.db.restore_channel(id) {
+        Ok(true) => {
+            let channel = state.db.get_channel(id).ok().flatten().map(|ch| {
+                let running = state.gateway.channel_manager().is_running(ch.id);
+                ChannelResponse::from(ch).with_running(running)
+            });
+            HttpResponse::Ok().json(ChannelOperationResponse {
+                success: true,
+                channel,
+                error: None,
+            })
+        }
+        Ok(false) => HttpResponse::NotFound().json(ChannelOperationResponse {
+            success: false,
+            channel: None,
+            error: Some("Deleted channel not found".to_string()),
+        }),
+        Err(e) => {
+            log::error!("Failed to restore channel: {}", e);
+            HttpResponse::InternalServerError().json(ChannelOperationResponse {
+                success: false,
+                channel: None,
+                error: Some("Failed to restore channel".to_string()),
+            })
+        }
+    }
+}
+
+/// Permanently remove a soft-deleted channel and its settings
+async fn purge_channel(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<i64>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&state, &req) {
+        return resp;
+    }
+
+    let id = path.into_inner();
+
+    match state.db.purge_channel(id) {
+        Ok(true) => HttpResponse::Ok().json(ChannelOperationResponse {
+            success: true,
+            channel: None,
+            error: None,
+        }),
+        Ok(false) => HttpResponse::NotFound().json(ChannelOperationResponse {
+            success: false,
+            channel: None,
+            error: Some("Deleted channel not found (delete it before purging)".to_string()),
+        }),
+        Err(e) => {
+            log::error!("Failed to purge channel: {}", e);
+            HttpResponse::InternalServerError().json(ChannelOperationResponse {
+                success: false,
+                channel: None,
+                error: Some("Failed to purge channel".to_string()),
+            })
+        }
+    }
+}
+
 async fn start_channel(
     state: web::Data<AppState>,
     req: HttpRequest,
@@ -691,6 +809,19 @@ async fn update_channel_settings(
         }
     }
 
+    // Reject out-of-range values before anything is written
+    for update in &body.settings {
+        if let Ok(key) = update.key.parse::<ChannelSettingKey>() {
+            if let Err(error) = key.validate_value(&update.value) {
+                return HttpResponse::BadRequest().json(serde_json::json!({
+                    "success": false,
+                    "settings": [],
+                    "error": error,
+                }));
+            }
+        }
+    }
+
     // Convert to tuple format for bulk update
     let settings_tuples: Vec<(String, String)> = body
         .settings
@@ -724,3 +855,45 @@ async fn update_channel_settings(
         }
     }
 }
+
+/// Post an announcement to every enabled channel's announcement chat
+async fn announce_to_all_channels(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    body: web::Json<AnnounceRequest>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&state, &req) {
+        return resp;
+    }
+
+    if let Err(e) = announce::validate_announcement(&body.message) {
+        return HttpResponse::BadRequest().json(AnnounceResponse {
+            success: false,
+            delivered: 0,
+            results: vec![],
+            error: Some(e),
+        });
+    }
+
+    let client = crate::http::shared_client();
+    match announce::broadcast_announcement(&state.db, client, &body.message).await {
+        Ok(results) => {
+            let delivered = results.iter().filter(|r| r.success).count();
+            HttpResponse::Ok().json(AnnounceResponse {
+                success: delivered > 0,
+                delivered,
+                results,
+                error: None,
+            })
+        }
+        Err(e) => {
+            log::error!("Failed to broadcast announcement: {}", e);
+            HttpResponse::InternalServerError().json(AnnounceResponse {
+                success: false,
+                delivered: 0,
+                results: vec![],
+                error: Some(e),
+            })
+        }
+    }
+}
@@ -1,8 +1,11 @@
 use actix_web::{web, HttpRequest, HttpResponse, Responder};
 use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
 
+use crate::ai::TokenUsage;
+use crate::channels::types::DispatchResult;
 use crate::channels::NormalizedMessage;
-use crate::models::SessionScope;
+use crate::models::{ChannelSettingKey, SessionScope};
 use crate::AppState;
 
 /// Web channel ID - a reserved ID for web-based chat
@@ -10,6 +13,17 @@ use crate::AppState;
 const WEB_CHANNEL_ID: i64 = 0;
 const WEB_CHANNEL_TYPE: &str = "web";
 
+/// Events buffered for a `/api/chat/stream` client; more are dropped while it's full
+const SSE_BUFFER: usize = 64;
+
+/// How long a timed-out dispatch gets to wind down after its execution is cancelled
+const DISPATCH_CANCEL_GRACE: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Web chat sessions are tied to the auth token through their platform_chat_id
+fn web_chat_id(token: &str) -> String {
+    format!("web-{}", &token[..8.min(token.len())])
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ChatRequest {
     pub messages: Vec<ChatMessage>,
@@ -19,6 +33,10 @@ pub struct ChatRequest {
     /// Currently selected network from the UI (e.g., "base", "polygon", "mainnet")
     #[serde(default)]
     pub network: Option<String>,
+    /// Continue this chat session (from a previous response's `session_id`).
+    /// Must belong to the caller's auth token.
+    #[serde(default)]
+    pub session_id: Option<i64>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -37,6 +55,9 @@ pub struct ChatResponse {
     /// Session ID for persistent conversations
     #[serde(skip_serializing_if = "Option::is_none")]
     pub session_id: Option<i64>,
+    /// Provider token usage for this turn, when the model reported it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<TokenUsage>,
 }
 
 #[derive(Serialize)]
@@ -82,10 +103,69 @@ pub struct SubagentInfo {
 }
 
 /// Response listing subagents
-#[derive(Serialize)]
+#[derive(Serialize, Default)]
 pub struct SubagentListResponse {
     pub success: bool,
     pub subagents: Vec<SubagentInfo>,
+    /// Sub-agents of this channel currently running
+    pub running: usize,
+    /// Sub-agents of this channel waiting for a free slot
+    pub queued: usize,
+    /// Running sub-agents across all channels
+    pub total_running: usize,
+    pub max_concurrent_per_channel: usize,
+    pub max_total_concurrent: usize,
+}
+
+/// A single subagent's outcome, for polling until it finishes
+#[derive(Serialize, Default)]
+pub struct SubagentResultResponse {
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    /// True while the subagent is queued or running
+    pub pending: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl SubagentResultResponse {
+    fn error(message: impl Into<String>) -> Self {
+        Self {
+            error: Some(message.into()),
+            ..Default::default()
+        }
+    }
+}
+
+/// Web channel system prompt override (see the `system_prompt` channel setting)
+#[derive(Serialize, Deserialize, Default)]
+pub struct WebSystemPrompt {
+    /// Blank means no override: the default SOUL.md prompt is used as-is
+    #[serde(default)]
+    pub system_prompt: String,
+    /// `prepend` (default) or `replace`
+    #[serde(default)]
+    pub mode: Option<String>,
+}
+
+#[derive(Serialize, Default)]
+pub struct WebSystemPromptResponse {
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<WebSystemPrompt>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 /// Response for task deletion
@@ -118,23 +198,32 @@ pub struct GetPlannerTasksResponse {
 
 pub fn config(cfg: &mut web::ServiceConfig) {
     cfg.service(web::resource("/api/chat").route(web::post().to(chat)))
+        .service(web::resource("/api/chat/stream").route(web::post().to(chat_stream)))
         .service(web::resource("/api/chat/stop").route(web::post().to(stop_execution)))
         .service(web::resource("/api/chat/execution-status").route(web::get().to(get_execution_status)))
         .service(web::resource("/api/chat/subagents").route(web::get().to(list_subagents)))
         .service(web::resource("/api/chat/subagents/cancel").route(web::post().to(cancel_subagent)))
+        .service(web::resource("/api/chat/subagents/{id}").route(web::get().to(get_subagent)))
         // Task management for planner tasks
         .service(web::resource("/api/chat/tasks").route(web::get().to(get_planner_tasks)))
         .service(web::resource("/api/chat/tasks/{task_id}").route(web::delete().to(delete_task)))
         // Session management for web channel
         .service(web::resource("/api/chat/session").route(web::get().to(get_web_session)))
+        .service(
+            web::resource("/api/chat/system-prompt")
+                .route(web::get().to(get_web_system_prompt))
+                .route(web::put().to(update_web_system_prompt)),
+        )
         .service(web::resource("/api/chat/session/new").route(web::post().to(new_web_session)));
 }
 
-async fn chat(
-    state: web::Data<AppState>,
-    req: HttpRequest,
-    body: web::Json<ChatRequest>,
-) -> impl Responder {
+/// Authenticate a chat request and build the web channel message for it.
+/// Shared by `/api/chat` and `/api/chat/stream`; the error is the response to send.
+fn prepare_web_message(
+    state: &AppState,
+    req: &HttpRequest,
+    body: &ChatRequest,
+) -> Result<NormalizedMessage, HttpResponse> {
     // Validate session token
     let token = req
         .headers()
@@ -145,12 +234,13 @@ async fn chat(
     let token = match token {
         Some(t) => t,
         None => {
-            return HttpResponse::Unauthorized().json(ChatResponse {
+            return Err(HttpResponse::Unauthorized().json(ChatResponse {
                 success: false,
                 message: None,
                 error: Some("No authorization token provided".to_string()),
                 session_id: None,
-            });
+                usage: None,
+            }));
         }
     };
 
@@ -158,21 +248,23 @@ async fn chat(
     match state.db.validate_session(&token) {
         Ok(Some(_)) => {} // Session is valid
         Ok(None) => {
-            return HttpResponse::Unauthorized().json(ChatResponse {
+            return Err(HttpResponse::Unauthorized().json(ChatResponse {
                 success: false,
                 message: None,
                 error: Some("Invalid or expired session".to_string()),
                 session_id: None,
-            });
+                usage: None,
+            }));
         }
         Err(e) => {
             log::error!("Failed to validate session: {}", e);
-            return HttpResponse::InternalServerError().json(ChatResponse {
+            return Err(HttpResponse::InternalServerError().json(ChatResponse {
                 success: false,
                 message: None,
                 error: Some("Internal server error".to_string()),
                 session_id: None,
-            });
+                usage: None,
+            }));
         }
     };
 
@@ -180,26 +272,74 @@ async fn chat(
     let user_message = match body.messages.iter().rev().find(|m| m.role == "user") {
         Some(msg) => msg.content.clone(),
         None => {
-            return HttpResponse::BadRequest().json(ChatResponse {
+            return Err(HttpResponse::BadRequest().json(ChatResponse {
                 success: false,
                 message: None,
                 error: Some("No user message provided".to_string()),
                 session_id: None,
-            });
+                usage: None,
+            }));
         }
     };
 
     // Generate a user ID for the web session
     // Use the provided user_id, or derive from the session token
     let user_id = body.user_id.clone()
-        .unwrap_or_else(|| format!("web-{}", &token[..8.min(token.len())]));
+        .unwrap_or_else(|| web_chat_id(&token));
+    // The session is always keyed by the auth token, so the session ids it hands out are
+    // the ones the ownership check below accepts
+    let chat_id = web_chat_id(&token);
+
+    // Resuming a session: it must be a web session of this auth token. Unknown ids get the
+    // same 403 as foreign ones so session ids can't be probed.
+    if let Some(session_id) = body.session_id {
+        let owned = match state.db.get_chat_session(session_id) {
+            Ok(Some(s)) => {
+                s.channel_type == WEB_CHANNEL_TYPE
+                    && s.channel_id == WEB_CHANNEL_ID
+                    && s.platform_chat_id == chat_id
+            }
+            Ok(None) => false,
+            Err(e) => {
+                log::error!("Failed to load chat session {}: {}", session_id, e);
+                return Err(HttpResponse::InternalServerError().json(ChatResponse {
+                    success: false,
+                    message: None,
+                    error: Some("Internal server error".to_string()),
+                    session_id: None,
+                    usage: None,
+                }));
+            }
+        };
+        if !owned {
+            log::warn!("[CHAT] Rejected session_id {} not owned by this login", session_id);
+            return Err(HttpResponse::Forbidden().json(ChatResponse {
+                success: false,
+                message: None,
+                error: Some("Session not found for this login".to_string()),
+                session_id: None,
+                usage: None,
+            }));
+        }
+        // Make it the active session so the dispatcher appends to it with its history
+        if let Err(e) = state.db.resume_chat_session(session_id) {
+            log::error!("Failed to resume chat session {}: {}", session_id, e);
+            return Err(HttpResponse::InternalServerError().json(ChatResponse {
+                success: false,
+                message: None,
+                error: Some("Failed to resume session".to_string()),
+                session_id: None,
+                usage: None,
+            }));
+        }
+    }
 
     // Create a normalized message for the dispatcher
     // This makes web chat go through the same pipeline as Telegram/Slack
-    let normalized = NormalizedMessage {
+    Ok(NormalizedMessage {
         channel_id: WEB_CHANNEL_ID,
         channel_type: WEB_CHANNEL_TYPE.to_string(),
-        chat_id: user_id.clone(),  // For web, chat_id == user_id (always DM-like)
+        chat_id,
         chat_name: None,
         user_id: user_id.clone(),
         user_name: format!("web-user-{}", &user_id[..8.min(user_id.len())]),
@@ -208,31 +348,198 @@ async fn chat(
         session_mode: None,
         selected_network: body.network.clone(),
         force_safe_mode: false,
+        from_admin: true,
+        attachments: Vec::new(),
+    })
+}
+
+/// Run a web dispatch under the configured chat timeout.
+/// On timeout this dispatch is stopped and `Err(timeout_secs)` is returned.
+async fn run_web_dispatch(state: &AppState, normalized: NormalizedMessage) -> Result<DispatchResult, u64> {
+    let timeout_secs = crate::config::chat_dispatch_timeout_secs();
+    let cancel = CancellationToken::new();
+    let dispatch = state.dispatcher.dispatch_with_cancellation(normalized, cancel.clone());
+    if timeout_secs == 0 {
+        return Ok(dispatch.await);
+    }
+    tokio::pin!(dispatch);
+    match tokio::time::timeout(std::time::Duration::from_secs(timeout_secs), &mut dispatch).await {
+        Ok(result) => Ok(result),
+        Err(_) => {
+            // Stop only this dispatch, so nothing keeps running for a client that got its answer.
+            // Other executions on the web channel (e.g. main-mode cron jobs) are left alone.
+            log::warn!("[CHAT] Dispatch exceeded {}s, stopping it", timeout_secs);
+            cancel.cancel();
+            // Let the cancelled dispatch run its own cleanup (heartbeat, telemetry,
+            // execution tracking) rather than dropping it mid-flight
+            if tokio::time::timeout(DISPATCH_CANCEL_GRACE, &mut dispatch).await.is_err() {
+                log::warn!(
+                    "[CHAT] Dispatch did not stop within {}s of cancellation, dropping it",
+                    DISPATCH_CANCEL_GRACE.as_secs()
+                );
+            }
+            Err(timeout_secs)
+        }
+    }
+}
+
+fn dispatch_timeout_body(timeout_secs: u64) -> serde_json::Value {
+    serde_json::json!({
+        "success": false,
+        "error": format!("The request timed out after {} seconds and was stopped.", timeout_secs),
+        "error_code": "dispatch_timeout",
+        "timeout_secs": timeout_secs,
+        "hint": "Try a narrower request, or split it into smaller steps.",
+    })
+}
+
+fn chat_response(result: DispatchResult) -> ChatResponse {
+    match result.error {
+        Some(error) => ChatResponse {
+            success: false,
+            message: None,
+            error: Some(error),
+            session_id: result.session_id,
+            usage: None,
+        },
+        None => ChatResponse {
+            success: true,
+            message: Some(ChatMessage {
+                role: "assistant".to_string(),
+                content: result.response,
+            }),
+            error: None,
+            session_id: result.session_id,
+            usage: result.usage,
+        },
+    }
+}
+
+async fn chat(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    body: web::Json<ChatRequest>,
+) -> impl Responder {
+    let normalized = match prepare_web_message(&state, &req, &body) {
+        Ok(m) => m,
+        Err(resp) => return resp,
     };
 
     // Dispatch through the unified pipeline
     // This gives us: sessions, identities, memories, tool execution, gateway events
-    let result = state.dispatcher.dispatch(normalized).await;
+    let result = match run_web_dispatch(&state, normalized).await {
+        Ok(result) => result,
+        Err(timeout_secs) => return HttpResponse::GatewayTimeout().json(dispatch_timeout_body(timeout_secs)),
+    };
 
-    if let Some(error) = result.error {
+    if let Some(error) = &result.error {
         log::error!("Chat dispatch error: {}", error);
-        return HttpResponse::InternalServerError().json(ChatResponse {
-            success: false,
-            message: None,
-            error: Some(error),
-            session_id: None,
-        });
+        return HttpResponse::InternalServerError().json(chat_response(result));
     }
+    HttpResponse::Ok().json(chat_response(result))
+}
 
-    HttpResponse::Ok().json(ChatResponse {
-        success: true,
-        message: Some(ChatMessage {
-            role: "assistant".to_string(),
-            content: result.response,
-        }),
-        error: None,
-        session_id: None, // Could return session ID if needed
-    })
+/// Streaming variant of `/api/chat`: same request body and auth, but the response is a
+/// `text/event-stream` of the web channel's gateway events for this dispatch, ending with
+/// a `message` event carrying the usual chat response. Each SSE `event:` is the gateway
+/// event name and `data:` is its payload.
+async fn chat_stream(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    body: web::Json<ChatRequest>,
+) -> impl Responder {
+    let normalized = match prepare_web_message(&state, &req, &body) {
+        Ok(m) => m,
+        Err(resp) => return resp,
+    };
+    let chat_id = normalized.chat_id.clone();
+
+    // Subscribe before dispatching so no early events are missed
+    let (client_id, mut events) = state.broadcaster.subscribe();
+    let (tx, sse_rx) = tokio::sync::mpsc::channel::<web::Bytes>(SSE_BUFFER);
+
+    let state_bg = state.clone();
+    tokio::spawn(async move {
+        let dispatch = run_web_dispatch(&state_bg, normalized);
+        tokio::pin!(dispatch);
+        let mut dropped = 0usize;
+
+        let final_frame = loop {
+            tokio::select! {
+                result = &mut dispatch => {
+                    break match result {
+                        Ok(result) => sse_frame("message", &serde_json::to_value(chat_response(result)).unwrap_or_default()),
+                        Err(timeout_secs) => sse_frame("error", &dispatch_timeout_body(timeout_secs)),
+                    };
+                }
+                Some(event) = events.recv() => {
+                    if !crate::channels::util::event_matches_chat(&event.data, WEB_CHANNEL_ID, &chat_id) {
+                        continue;
+                    }
+                    // Never wait on the client: a slow reader loses progress events instead of
+                    // stalling the dispatch, and a closed stream means it went away
+                    if let Err(tokio::sync::mpsc::error::TrySendError::Full(_)) =
+                        tx.try_send(sse_frame(&event.event, &event.data))
+                    {
+                        dropped += 1;
+                    }
+                }
+            }
+        };
+
+        // Flush events broadcast right before the dispatch returned
+        while let Ok(event) = events.try_recv() {
+            if crate::channels::util::event_matches_chat(&event.data, WEB_CHANNEL_ID, &chat_id) {
+                let _ = tx.send(sse_frame(&event.event, &event.data)).await;
+            }
+        }
+        state_bg.broadcaster.unsubscribe(&client_id);
+        if dropped > 0 {
+            log::warn!("[CHAT_STREAM] Dropped {} events the client was too slow to read", dropped);
+        }
+        let _ = tx.send(final_frame).await;
+    });
+
+    let stream = futures_util::stream::unfold(sse_rx, |mut rx| async move {
+        rx.recv().await.map(|bytes| (Ok::<_, actix_web::Error>(bytes), rx))
+    });
+
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(("Cache-Control", "no-cache"))
+        .insert_header(("X-Accel-Buffering", "no"))
+        .streaming(stream)
+}
+
+fn sse_frame(event: &str, data: &serde_json::Value) -> web::Bytes {
+    web::Bytes::from(format!("event: {}\ndata: {}\n\n", event, data))
+}
+
+/// Cancel everything running on the web channel. Returns the number of subagents cancelled.
+async fn cancel_web_execution(state: &AppState) -> usize {
+    use std::time::Duration;
+
+    // Cancel the execution for the web channel
+    // This will:
+    // 1. Cancel via CancellationToken (immediate interruption of async ops)
+    // 2. Set the cancelled flag (for checkpoint compatibility)
+    // 3. Emit execution.stopped event for frontend confirmation
+    // 4. Complete/abort the current execution
+    state.execution_tracker.cancel_execution(WEB_CHANNEL_ID);
+
+    // Also cancel any session-based executions running on this channel
+    // This ensures cron jobs running in "main" mode on channel 0 are also stopped
+    state.execution_tracker.cancel_all_sessions_for_channel(WEB_CHANNEL_ID);
+
+    // Also cancel any running subagents for this channel and wait for acknowledgment
+    let mut subagents_cancelled = 0;
+    if let Some(subagent_manager) = state.dispatcher.subagent_manager() {
+        subagents_cancelled = subagent_manager
+            .cancel_all_for_channel_and_wait(WEB_CHANNEL_ID, Duration::from_millis(100))
+            .await;
+        log::info!("[CHAT_STOP] Cancelled {} subagents for web channel", subagents_cancelled);
+    }
+    subagents_cancelled
 }
 
 /// Stop the current agent execution for the web channel
@@ -240,8 +547,6 @@ async fn stop_execution(
     state: web::Data<AppState>,
     req: HttpRequest,
 ) -> impl Responder {
-    use std::time::Duration;
-
     // Validate session token
     let token = req
         .headers()
@@ -280,27 +585,8 @@ async fn stop_execution(
         }
     };
 
-    // Cancel the execution for the web channel
-    // This will:
-    // 1. Cancel via CancellationToken (immediate interruption of async ops)
-    // 2. Set the cancelled flag (for checkpoint compatibility)
-    // 3. Emit execution.stopped event for frontend confirmation
-    // 4. Complete/abort the current execution
     log::info!("[CHAT_STOP] Stopping execution for web channel {}", WEB_CHANNEL_ID);
-    state.execution_tracker.cancel_execution(WEB_CHANNEL_ID);
-
-    // Also cancel any session-based executions running on this channel
-    // This ensures cron jobs running in "main" mode on channel 0 are also stopped
-    state.execution_tracker.cancel_all_sessions_for_channel(WEB_CHANNEL_ID);
-
-    // Also cancel any running subagents for this channel and wait for acknowledgment
-    let mut subagents_cancelled = 0;
-    if let Some(subagent_manager) = state.dispatcher.subagent_manager() {
-        subagents_cancelled = subagent_manager
-            .cancel_all_for_channel_and_wait(WEB_CHANNEL_ID, Duration::from_millis(100))
-            .await;
-        log::info!("[CHAT_STOP] Cancelled {} subagents for web channel", subagents_cancelled);
-    }
+    let subagents_cancelled = cancel_web_execution(&state).await;
 
     let message = if subagents_cancelled > 0 {
         format!("Execution stopped. {} subagent(s) cancelled.", subagents_cancelled)
@@ -369,47 +655,109 @@ async fn list_subagents(
     let token = match token {
         Some(t) => t,
         None => {
-            return HttpResponse::Unauthorized().json(SubagentListResponse {
-                success: false,
-                subagents: vec![],
-            });
+            return HttpResponse::Unauthorized().json(SubagentListResponse::default());
         }
     };
 
     // Validate the session
     if state.db.validate_session(&token).ok().flatten().is_none() {
-        return HttpResponse::Unauthorized().json(SubagentListResponse {
-            success: false,
-            subagents: vec![],
-        });
+        return HttpResponse::Unauthorized().json(SubagentListResponse::default());
     }
 
+    let Some(subagent_manager) = state.dispatcher.subagent_manager() else {
+        return HttpResponse::Ok().json(SubagentListResponse {
+            success: true,
+            ..Default::default()
+        });
+    };
+
     // Get subagents for the web channel
-    let subagents = if let Some(subagent_manager) = state.dispatcher.subagent_manager() {
-        match subagent_manager.list_by_channel(WEB_CHANNEL_ID) {
-            Ok(agents) => agents
-                .into_iter()
-                .map(|ctx| SubagentInfo {
-                    id: ctx.id,
-                    label: ctx.label,
-                    task: if ctx.task.len() > 100 {
-                        format!("{}...", &ctx.task[..97])
-                    } else {
-                        ctx.task
-                    },
-                    status: format!("{:?}", ctx.status),
-                    started_at: ctx.started_at.to_rfc3339(),
-                })
-                .collect(),
-            Err(_) => vec![],
-        }
-    } else {
-        vec![]
+    let subagents = match subagent_manager.list_by_channel(WEB_CHANNEL_ID) {
+        Ok(agents) => agents
+            .into_iter()
+            .map(|ctx| SubagentInfo {
+                id: ctx.id,
+                label: ctx.label,
+                task: if ctx.task.len() > 100 {
+                    format!("{}...", &ctx.task[..97])
+                } else {
+                    ctx.task
+                },
+                status: format!("{:?}", ctx.status),
+                started_at: ctx.started_at.to_rfc3339(),
+            })
+            .collect(),
+        Err(_) => vec![],
     };
+    let channel = subagent_manager.counts(Some(WEB_CHANNEL_ID));
+    let config = subagent_manager.config();
 
     HttpResponse::Ok().json(SubagentListResponse {
         success: true,
         subagents,
+        running: channel.running,
+        queued: channel.queued,
+        total_running: subagent_manager.counts(None).running,
+        max_concurrent_per_channel: config.max_concurrent_per_channel,
+        max_total_concurrent: config.max_total_concurrent,
+    })
+}
+
+/// Get a web channel subagent's status and, once it has finished, its result or error
+async fn get_subagent(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    // Validate session token
+    let token = req
+        .headers()
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.trim_start_matches("Bearer ").to_string());
+
+    let token = match token {
+        Some(t) => t,
+        None => {
+            return HttpResponse::Unauthorized()
+                .json(SubagentResultResponse::error("No authorization token provided"));
+        }
+    };
+
+    // Validate the session
+    if state.db.validate_session(&token).ok().flatten().is_none() {
+        return HttpResponse::Unauthorized().json(SubagentResultResponse::error("Invalid or expired session"));
+    }
+
+    let Some(subagent_manager) = state.dispatcher.subagent_manager() else {
+        return HttpResponse::ServiceUnavailable()
+            .json(SubagentResultResponse::error("Subagent manager not available"));
+    };
+
+    let subagent_id = path.into_inner();
+    let ctx = match subagent_manager.get_status(&subagent_id) {
+        Ok(Some(ctx)) if ctx.parent_channel_id == WEB_CHANNEL_ID => ctx,
+        Ok(_) => {
+            return HttpResponse::NotFound()
+                .json(SubagentResultResponse::error(format!("Subagent {} not found", subagent_id)));
+        }
+        Err(e) => {
+            log::error!("[CHAT] Failed to load subagent {}: {}", subagent_id, e);
+            return HttpResponse::InternalServerError()
+                .json(SubagentResultResponse::error("Failed to load subagent"));
+        }
+    };
+
+    HttpResponse::Ok().json(SubagentResultResponse {
+        success: true,
+        id: Some(ctx.id),
+        label: Some(ctx.label),
+        status: Some(ctx.status.to_string()),
+        pending: !ctx.status.is_terminal(),
+        result: ctx.result,
+        started_at: Some(ctx.started_at.to_rfc3339()),
+        completed_at: ctx.completed_at.map(|t| t.to_rfc3339()),
+        error: ctx.error,
     })
 }
 
@@ -598,6 +946,116 @@ pub struct WebSessionResponse {
     pub error: Option<String>,
 }
 
+/// Validate the Bearer session for the system prompt endpoints
+fn require_session(state: &web::Data<AppState>, req: &HttpRequest) -> Result<(), HttpResponse> {
+    let token = req
+        .headers()
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.trim_start_matches("Bearer ").to_string());
+
+    let error = match token {
+        None => "No authorization token provided",
+        Some(t) if state.db.validate_session(&t).ok().flatten().is_none() => "Invalid or expired session",
+        Some(_) => return Ok(()),
+    };
+    Err(HttpResponse::Unauthorized().json(WebSystemPromptResponse {
+        error: Some(error.to_string()),
+        ..Default::default()
+    }))
+}
+
+fn load_web_system_prompt(state: &AppState) -> WebSystemPrompt {
+    let setting = |key: ChannelSettingKey| {
+        state
+            .db
+            .get_channel_setting(WEB_CHANNEL_ID, key.as_ref())
+            .ok()
+            .flatten()
+    };
+    WebSystemPrompt {
+        system_prompt: setting(ChannelSettingKey::SystemPrompt).unwrap_or_default(),
+        mode: Some(
+            setting(ChannelSettingKey::SystemPromptMode)
+                .unwrap_or_else(|| ChannelSettingKey::SystemPromptMode.default_value().to_string()),
+        ),
+    }
+}
+
+/// Get the web channel's system prompt override
+async fn get_web_system_prompt(state: web::Data<AppState>, req: HttpRequest) -> impl Responder {
+    if let Err(resp) = require_session(&state, &req) {
+        return resp;
+    }
+
+    HttpResponse::Ok().json(WebSystemPromptResponse {
+        success: true,
+        prompt: Some(load_web_system_prompt(&state)),
+        error: None,
+    })
+}
+
+/// Set (or, with a blank prompt, clear) the web channel's system prompt override
+async fn update_web_system_prompt(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    body: web::Json<WebSystemPrompt>,
+) -> impl Responder {
+    if let Err(resp) = require_session(&state, &req) {
+        return resp;
+    }
+
+    let body = body.into_inner();
+    let bad_request = |error: String| {
+        HttpResponse::BadRequest().json(WebSystemPromptResponse {
+            error: Some(error),
+            ..Default::default()
+        })
+    };
+    if let Err(e) = ChannelSettingKey::SystemPrompt.validate_value(&body.system_prompt) {
+        return bad_request(e);
+    }
+    let mode = body.mode.as_deref().map(|m| m.trim().to_lowercase());
+    if let Some(ref m) = mode {
+        if m != "prepend" && m != "replace" {
+            return bad_request(format!("Invalid mode '{}'. Valid options: prepend, replace", m));
+        }
+    }
+
+    let prompt = body.system_prompt.trim();
+    let result = if prompt.is_empty() {
+        state
+            .db
+            .delete_channel_setting(WEB_CHANNEL_ID, ChannelSettingKey::SystemPrompt.as_ref())
+            .map(|_| ())
+    } else {
+        state
+            .db
+            .set_channel_setting(WEB_CHANNEL_ID, ChannelSettingKey::SystemPrompt.as_ref(), prompt)
+    };
+    let result = result.and_then(|_| match mode {
+        Some(m) => state
+            .db
+            .set_channel_setting(WEB_CHANNEL_ID, ChannelSettingKey::SystemPromptMode.as_ref(), &m),
+        None => Ok(()),
+    });
+
+    match result {
+        Ok(()) => HttpResponse::Ok().json(WebSystemPromptResponse {
+            success: true,
+            prompt: Some(load_web_system_prompt(&state)),
+            error: None,
+        }),
+        Err(e) => {
+            log::error!("[CHAT] Failed to save web system prompt: {}", e);
+            HttpResponse::InternalServerError().json(WebSystemPromptResponse {
+                error: Some("Failed to save system prompt".to_string()),
+                ..Default::default()
+            })
+        }
+    }
+}
+
 /// Get the current active web session (or create one if none exists)
 async fn get_web_session(
     state: web::Data<AppState>,
@@ -638,7 +1096,7 @@ async fn get_web_session(
 
     // Get or create the web session
     // Use token prefix as the platform_chat_id to tie session to the auth token
-    let chat_id = format!("web-{}", &token[..8.min(token.len())]);
+    let chat_id = web_chat_id(&token);
 
     match state.db.get_or_create_chat_session(
         WEB_CHANNEL_TYPE,
@@ -713,7 +1171,7 @@ async fn new_web_session(
     }
 
     // First get the current session
-    let chat_id = format!("web-{}", &token[..8.min(token.len())]);
+    let chat_id = web_chat_id(&token);
 
     let current_session = state.db.get_or_create_chat_session(
         WEB_CHANNEL_TYPE,
@@ -0,0 +1,235 @@
+//! Config snapshot controller — export/import of channels, channel settings, and agent settings.
+
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use serde::Deserialize;
+
+use crate::backup::snapshot::{
+    self, ChannelAction, ConfigSnapshot, ConflictStrategy, ImportOptions, SecretMode,
+};
+use crate::AppState;
+
+/// Validate session token from request (same pattern as memory controller)
+fn validate_session_from_request(
+    state: &web::Data<AppState>,
+    req: &HttpRequest,
+) -> Result<(), HttpResponse> {
+    let token = req
+        .headers()
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.trim_start_matches("Bearer ").to_string());
+
+    let token = match token {
+        Some(t) => t,
+        None => {
+            return Err(HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": "No authorization token provided"
+            })));
+        }
+    };
+
+    match state.db.validate_session(&token) {
+        Ok(Some(_)) => Ok(()),
+        Ok(None) => Err(HttpResponse::Unauthorized().json(serde_json::json!({
+            "error": "Invalid or expired session"
+        }))),
+        Err(e) => {
+            log::error!("Session validation error: {}", e);
+            Err(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Internal server error"
+            })))
+        }
+    }
+}
+
+/// ECIES key from the wallet provider (same key the cloud backup uses)
+async fn encryption_key(state: &web::Data<AppState>) -> Result<String, String> {
+    match &state.wallet_provider {
+        Some(wp) => wp
+            .get_encryption_key()
+            .await
+            .map_err(|e| format!("Failed to get encryption key: {}", e)),
+        None => Err("No wallet configured; encrypted snapshots are not available".to_string()),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ExportQuery {
+    /// redact (default), encrypt, or include
+    #[serde(default)]
+    secrets: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ImportBody {
+    snapshot: ConfigSnapshot,
+    #[serde(default)]
+    conflict: ConflictStrategy,
+    #[serde(default = "default_true")]
+    agent_settings: bool,
+    /// Validate and return the plan without applying it
+    #[serde(default)]
+    dry_run: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// GET /api/config/export
+///
+/// Portable JSON snapshot of channels, channel settings, and agent settings.
+async fn export_config(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<ExportQuery>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&state, &req) {
+        return resp;
+    }
+
+    let mode = query
+        .secrets
+        .as_deref()
+        .map(SecretMode::from_str_or_default)
+        .unwrap_or_default();
+    let key = if mode == SecretMode::Encrypt {
+        match encryption_key(&state).await {
+            Ok(k) => Some(k),
+            Err(e) => {
+                return HttpResponse::BadRequest().json(serde_json::json!({
+                    "success": false,
+                    "error": e
+                }));
+            }
+        }
+    } else {
+        None
+    };
+
+    match snapshot::export_snapshot(&state.db, mode, key.as_deref()) {
+        Ok(snapshot) => HttpResponse::Ok().json(snapshot),
+        Err(e) => {
+            log::error!("Config export failed: {}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "success": false,
+                "error": e
+            }))
+        }
+    }
+}
+
+/// POST /api/config/import
+///
+/// Validate a snapshot and restore it in one transaction. With `dry_run` the
+/// plan is returned without changing anything.
+async fn import_config(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    body: web::Json<ImportBody>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&state, &req) {
+        return resp;
+    }
+
+    let body = body.into_inner();
+    let key = if body.snapshot.secrets == SecretMode::Encrypt {
+        encryption_key(&state).await.ok()
+    } else {
+        None
+    };
+
+    let existing_channels = match state.db.list_channels() {
+        Ok(c) => c,
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "success": false,
+                "error": format!("Failed to list channels: {}", e)
+            }));
+        }
+    };
+    let existing_agent_settings = match state.db.list_agent_settings() {
+        Ok(a) => a,
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "success": false,
+                "error": format!("Failed to list agent settings: {}", e)
+            }));
+        }
+    };
+
+    let options = ImportOptions {
+        conflict: body.conflict,
+        agent_settings: body.agent_settings,
+    };
+    let plan = match snapshot::plan_import(
+        &body.snapshot,
+        &existing_channels,
+        &existing_agent_settings,
+        options,
+        key.as_deref(),
+    ) {
+        Ok(plan) => plan,
+        Err(errors) => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "success": false,
+                "error": "Snapshot failed validation; nothing was imported",
+                "errors": errors
+            }));
+        }
+    };
+
+    if body.dry_run {
+        return HttpResponse::Ok().json(serde_json::json!({
+            "success": true,
+            "dry_run": true,
+            "plan": plan
+        }));
+    }
+
+    let report = match state.db.apply_config_import(&plan) {
+        Ok(r) => r,
+        Err(e) => {
+            log::error!("Config import failed: {}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "success": false,
+                "error": format!("Import failed and was rolled back: {}", e)
+            }));
+        }
+    };
+
+    // Running channels keep their old connection until restarted
+    let channel_manager = state.gateway.channel_manager();
+    let restart_required: Vec<i64> = plan
+        .channels
+        .iter()
+        .filter_map(|c| match c.action {
+            ChannelAction::Replace { existing_id } if channel_manager.is_running(existing_id) => Some(existing_id),
+            _ => None,
+        })
+        .collect();
+
+    log::info!(
+        "Config import applied: {} created, {} replaced, {} skipped, {} agent settings",
+        report.channels_created,
+        report.channels_replaced,
+        report.channels_skipped,
+        report.agent_settings_imported
+    );
+
+    HttpResponse::Ok().json(serde_json::json!({
+        "success": true,
+        "dry_run": false,
+        "report": report,
+        "restart_required": restart_required
+    }))
+}
+
+/// Configure config snapshot routes
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/api/config")
+            .route("/export", web::get().to(export_config))
+            .route("/import", web::post().to(import_config)),
+    );
+}
@@ -73,6 +73,8 @@ async fn dev_chat(
         session_mode: None,
         selected_network: None,
         force_safe_mode: false,
+        from_admin: false,
+        attachments: Vec::new(),
     };
 
     let result = state.dispatcher.dispatch(normalized).await;
@@ -107,6 +109,7 @@ async fn dev_chat(
             message: None,
             error: Some(error),
             session_id,
+            usage: None,
         });
     }
 
@@ -118,5 +121,6 @@ async fn dev_chat(
         }),
         error: None,
         session_id,
+        usage: result.usage,
     })
 }
@@ -1,16 +1,18 @@
 //! EIP-8004 Trustless Agents API endpoints
 //!
-//! Endpoints for identity, reputation, and discovery.
+//! Endpoints for identity, reputation, validation, and discovery.
 
 use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use serde::{Deserialize, Serialize};
 
 use crate::eip8004::{
     config::Eip8004Config,
-    discovery::{AgentDiscovery, SearchCriteria},
+    discovery::{discovery_cache, next_offset, AgentDiscovery, CachedDiscovery, DiscoveryCache, SearchCriteria},
     identity::{IdentityRegistry, RegistrationBuilder},
-    reputation::ReputationRegistry,
+    reputation::{FeedbackBuilder, ReputationRegistry},
     types::TrustLevel,
+    validation::{ValidationRegistry, DEFAULT_MIN_RESPONSE},
 };
 use crate::AppState;
 
@@ -56,6 +58,15 @@ pub struct DiscoverQuery {
     x402_only: Option<bool>,
     service: Option<String>,
     min_reputation: Option<u64>,
+    /// Bypass the discovery cache and read from the chain
+    nocache: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ValidationQuery {
+    /// Validation type (registry tag); omit to accept any
+    validation_type: Option<String>,
+    min_response: Option<u8>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -66,6 +77,17 @@ pub struct CreateRegistrationRequest {
     services: Option<Vec<ServiceInput>>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct SubmitFeedbackRequest {
+    /// -100 (bad) to 100 (good)
+    score: i64,
+    comment: Option<String>,
+    tag1: Option<String>,
+    tag2: Option<String>,
+    /// The agent's endpoint the interaction was with
+    endpoint: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ServiceInput {
     name: String,
@@ -86,9 +108,13 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             .route("/identity", web::get().to(get_our_identity))
             .route("/identity/registration", web::post().to(create_registration_json))
             .route("/identity/{agent_id}", web::get().to(get_agent_identity))
+            .route("/identity/{agent_id}", web::delete().to(delete_agent_identity))
             // Reputation
             .route("/reputation/{agent_id}", web::get().to(get_agent_reputation))
             .route("/reputation/{agent_id}/trust", web::get().to(check_trust))
+            .route("/reputation/{agent_id}/feedback", web::post().to(submit_feedback))
+            // Validation
+            .route("/validation/{agent_id}", web::get().to(check_validation))
             // Discovery
             .route("/agents", web::get().to(discover_agents))
             .route("/agents/search", web::get().to(search_agents))
@@ -118,6 +144,7 @@ async fn get_config(state: web::Data<AppState>, req: HttpRequest) -> impl Respon
             "validation_registry": config.validation_registry,
             "identity_deployed": config.is_identity_deployed(),
             "reputation_deployed": config.is_reputation_deployed(),
+            "validation_deployed": config.is_validation_deployed(),
             "explorer_url": config.explorer_url
         }
     }))
@@ -265,6 +292,52 @@ async fn get_agent_identity(
     }
 }
 
+/// Deactivate a stored identity (soft delete: the row is kept with active = 0).
+///
+/// The Identity Registry is an ERC-721 with no deregister/burn function, so nothing is
+/// sent on-chain; the agent NFT stays registered. To advertise the agent as inactive,
+/// publish a registration file with `active: false` and point the agent URI at it.
+async fn delete_agent_identity(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<u64>,
+) -> impl Responder {
+    if let Err(resp) = validate_auth(&state, &req) {
+        return resp;
+    }
+
+    let agent_id = path.into_inner();
+    match state.db.deactivate_agent_identity(agent_id as i64) {
+        Ok(true) => {}
+        Ok(false) => {
+            return HttpResponse::NotFound().json(ApiResponse::<()>::error(&format!(
+                "No stored identity for agent {}",
+                agent_id
+            )));
+        }
+        Err(e) => {
+            log::error!("[eip8004/identity] Failed to deactivate agent {}: {}", agent_id, e);
+            return HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to deactivate identity"));
+        }
+    }
+    log::info!("[eip8004/identity] Deactivated stored identity for agent {}", agent_id);
+
+    let config = Eip8004Config::from_env();
+    let note = if config.is_identity_deployed() {
+        "Identity deactivated locally. The registry has no deregister function, so the agent NFT remains on-chain; \
+         publish a registration file with active=false to advertise it as inactive."
+    } else {
+        "Identity deactivated locally."
+    };
+
+    HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "agent_id": agent_id,
+        "active": false,
+        "on_chain_deregistered": false,
+        "note": note,
+    })))
+}
+
 /// Create registration JSON file
 async fn create_registration_json(
     state: web::Data<AppState>,
@@ -287,7 +360,16 @@ async fn create_registration_json(
         }
     }
 
-    let registration = builder.build();
+    let registration = match builder.build_validated() {
+        Ok(r) => r,
+        Err(errors) => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "success": false,
+                "error": format!("Registration does not conform to EIP-8004: {}", errors.join("; ")),
+                "validation_errors": errors
+            }));
+        }
+    };
 
     match serde_json::to_string_pretty(&registration) {
         Ok(json) => HttpResponse::Ok().json(serde_json::json!({
@@ -385,6 +467,117 @@ async fn check_trust(
     }
 }
 
+/// Submit feedback about an agent on-chain
+async fn submit_feedback(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<u64>,
+    body: web::Json<SubmitFeedbackRequest>,
+) -> impl Responder {
+    if let Err(resp) = validate_auth(&state, &req) {
+        return resp;
+    }
+
+    let agent_id = path.into_inner();
+    let body = body.into_inner();
+    let config = Eip8004Config::from_env();
+
+    if !(-100..=100).contains(&body.score) {
+        return HttpResponse::BadRequest().json(ApiResponse::<()>::error("score must be between -100 and 100"));
+    }
+    if !config.is_reputation_deployed() {
+        return HttpResponse::BadRequest().json(ApiResponse::<()>::error("Reputation Registry not deployed"));
+    }
+    let Some(ref wp) = state.wallet_provider else {
+        return HttpResponse::BadRequest().json(ApiResponse::<()>::error("Wallet not configured"));
+    };
+
+    let registry = ReputationRegistry::new_with_wallet_provider(config, wp.clone());
+    let tag1 = body.tag1.unwrap_or_default();
+    let tag2 = body.tag2.unwrap_or_default();
+    let endpoint = body.endpoint.unwrap_or_default();
+
+    // The feedback file is embedded as a data URI so the comment travels with the on-chain record
+    let mut file = registry.create_feedback_file(
+        agent_id,
+        &wp.get_address(),
+        body.score,
+        (!tag1.is_empty()).then_some(tag1.as_str()),
+        (!tag2.is_empty()).then_some(tag2.as_str()),
+        (!endpoint.is_empty()).then_some(endpoint.as_str()),
+        None,
+    );
+    file.comment = body.comment.filter(|c| !c.trim().is_empty());
+    let content = match serde_json::to_string(&file) {
+        Ok(c) => c,
+        Err(e) => return HttpResponse::InternalServerError().json(ApiResponse::<()>::error(&e.to_string())),
+    };
+    let feedback_uri = format!("data:application/json;base64,{}", BASE64.encode(&content));
+
+    let params = FeedbackBuilder::new(agent_id)
+        .value(body.score)
+        .tags(&tag1, &tag2)
+        .endpoint(&endpoint)
+        .feedback_uri(&feedback_uri)
+        .feedback_content(&content)
+        .build();
+
+    match registry.submit_feedback(&params).await {
+        Ok(tx_hash) => HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+            "agent_id": agent_id,
+            "score": body.score,
+            "tx_hash": tx_hash,
+        }))),
+        Err(e) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error(&e)),
+    }
+}
+
+// =====================================================
+// Validation Endpoints
+// =====================================================
+
+/// Check whether an agent holds a passing validation of a given type
+async fn check_validation(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<u64>,
+    query: web::Query<ValidationQuery>,
+) -> impl Responder {
+    if let Err(resp) = validate_auth(&state, &req) {
+        return resp;
+    }
+
+    let agent_id = path.into_inner();
+    let min_response = query.min_response.unwrap_or(DEFAULT_MIN_RESPONSE);
+    if min_response > 100 {
+        return HttpResponse::BadRequest().json(ApiResponse::<()>::error("min_response must be between 0 and 100"));
+    }
+
+    let config = Eip8004Config::from_env();
+
+    if !config.is_validation_deployed() {
+        return HttpResponse::Ok().json(serde_json::json!({
+            "success": true,
+            "agent_id": agent_id,
+            "deployed": false,
+            "valid": false,
+            "reason": "Validation Registry not deployed"
+        }));
+    }
+
+    let registry = if let Some(ref wp) = state.wallet_provider {
+        ValidationRegistry::new_with_wallet_provider(config, wp.clone())
+    } else {
+        ValidationRegistry::new(config)
+    };
+
+    let validation_type = query.validation_type.clone().unwrap_or_default();
+    match registry.check(agent_id, &validation_type, min_response).await {
+        Ok(check) => HttpResponse::Ok().json(ApiResponse::success(check)),
+        Err(e) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error(&e)),
+    }
+}
+
 // =====================================================
 // Discovery Endpoints
 // =====================================================
@@ -405,27 +598,45 @@ async fn discover_agents(
         return HttpResponse::BadRequest().json(ApiResponse::<()>::error("Identity Registry not deployed"));
     }
 
-    let mut discovery = if let Some(ref wp) = state.wallet_provider {
-        AgentDiscovery::new_with_wallet_provider(config, wp.clone())
-    } else {
-        AgentDiscovery::new(config)
-    };
     let offset = query.offset.unwrap_or(0);
     let limit = query.limit.unwrap_or(20).min(100);
+    let cache_key = DiscoveryCache::page_key(&config.agent_registry_string(), offset, limit);
+    let nocache = query.nocache.unwrap_or(false);
 
-    match discovery.discover_all(offset, limit).await {
-        Ok(agents) => {
+    let (result, cached) = match discovery_cache().get(&cache_key).filter(|_| !nocache) {
+        Some(hit) => (hit, true),
+        None => {
+            let mut discovery = if let Some(ref wp) = state.wallet_provider {
+                AgentDiscovery::new_with_wallet_provider(config, wp.clone())
+            } else {
+                AgentDiscovery::new(config)
+            };
+            let agents = match discovery.discover_all(offset, limit).await {
+                Ok(agents) => agents,
+                Err(e) => return HttpResponse::InternalServerError().json(ApiResponse::<()>::error(&e)),
+            };
             let total = discovery.total_agents().await.unwrap_or(0);
-            HttpResponse::Ok().json(serde_json::json!({
-                "success": true,
-                "agents": agents,
-                "total": total,
-                "offset": offset,
-                "limit": limit
-            }))
+            let result = CachedDiscovery { agents, total };
+            discovery_cache().insert(cache_key, result.clone());
+            (result, false)
         }
-        Err(e) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error(&e)),
-    }
+    };
+
+    // Pages cover agent ids, so count the ids scanned rather than the agents returned:
+    // an agent that failed to load must not shift the next page back over this one
+    let end = offset.saturating_add(limit).min(result.total);
+    let next = next_offset(end, result.total);
+
+    HttpResponse::Ok().json(serde_json::json!({
+        "success": true,
+        "agents": result.agents,
+        "total": result.total,
+        "offset": offset,
+        "limit": limit,
+        "has_more": next.is_some(),
+        "next_offset": next,
+        "cached": cached
+    }))
 }
 
 /// Search for agents with criteria
@@ -444,30 +655,52 @@ async fn search_agents(
         return HttpResponse::BadRequest().json(ApiResponse::<()>::error("Identity Registry not deployed"));
     }
 
-    let mut discovery = if let Some(ref wp) = state.wallet_provider {
-        AgentDiscovery::new_with_wallet_provider(config, wp.clone())
-    } else {
-        AgentDiscovery::new(config)
-    };
-
+    let offset = query.offset.unwrap_or(0);
+    let limit = query.limit.unwrap_or(50).min(100);
     let criteria = SearchCriteria {
         x402_required: query.x402_only.unwrap_or(false),
         active_only: true,
         required_service: query.service.clone(),
         min_reputation_count: query.min_reputation,
         sort_by_reputation: true,
-        limit: Some(query.limit.unwrap_or(50) as usize),
+        offset: Some(offset as usize),
+        limit: Some(limit as usize),
         ..Default::default()
     };
+    let cache_key = DiscoveryCache::search_key(&config.agent_registry_string(), &criteria);
+    let nocache = query.nocache.unwrap_or(false);
 
-    match discovery.search(criteria).await {
-        Ok(agents) => HttpResponse::Ok().json(serde_json::json!({
-            "success": true,
-            "agents": agents,
-            "count": agents.len()
-        })),
-        Err(e) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error(&e)),
-    }
+    let (result, cached) = match discovery_cache().get(&cache_key).filter(|_| !nocache) {
+        Some(hit) => (hit, true),
+        None => {
+            let mut discovery = if let Some(ref wp) = state.wallet_provider {
+                AgentDiscovery::new_with_wallet_provider(config, wp.clone())
+            } else {
+                AgentDiscovery::new(config)
+            };
+            let (agents, matches) = match discovery.search_with_total(criteria).await {
+                Ok(page) => page,
+                Err(e) => return HttpResponse::InternalServerError().json(ApiResponse::<()>::error(&e)),
+            };
+            let result = CachedDiscovery { agents, total: matches as u64 };
+            discovery_cache().insert(cache_key, result.clone());
+            (result, false)
+        }
+    };
+
+    let next = next_offset(offset + result.agents.len() as u64, result.total);
+
+    HttpResponse::Ok().json(serde_json::json!({
+        "success": true,
+        "count": result.agents.len(),
+        "agents": result.agents,
+        "total": result.total,
+        "offset": offset,
+        "limit": limit,
+        "has_more": next.is_some(),
+        "next_offset": next,
+        "cached": cached
+    }))
 }
 
 /// Get full agent details
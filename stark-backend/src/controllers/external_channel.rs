@@ -2,7 +2,9 @@ use actix_web::{web, HttpRequest, HttpResponse, Responder};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+use crate::channels::types::DispatchResult;
 use crate::channels::NormalizedMessage;
+use crate::gateway::protocol::GatewayEvent;
 use crate::models::chat_session::SessionScope;
 use crate::models::Channel;
 use crate::AppState;
@@ -111,7 +113,7 @@ pub fn config(cfg: &mut web::ServiceConfig) {
 // ── Auth helpers ────────────────────────────────────────────────────────
 
 /// Constant-time byte comparison to prevent timing attacks
-fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
     if a.len() != b.len() {
         return false;
     }
@@ -224,6 +226,74 @@ fn validate_web_session(
     }
 }
 
+/// Text of an event that is the agent's reply in the given conversation, if it is one.
+///
+/// Replies are `say_to_user` / `task_fully_completed` tool results and
+/// `agent.response` events. Both the channel and the chat must match, so
+/// concurrent conversations on one channel never see each other's replies.
+fn reply_text(event: &GatewayEvent, channel_id: i64, chat_id: &str) -> Option<String> {
+    let data = &event.data;
+    if data.get("channel_id").and_then(|v| v.as_i64()) != Some(channel_id)
+        || data.get("chat_id").and_then(|v| v.as_str()) != Some(chat_id)
+    {
+        return None;
+    }
+    let text = match event.event.as_str() {
+        "tool.result" => {
+            let tool_name = data.get("tool_name").and_then(|v| v.as_str()).unwrap_or("");
+            let success = data.get("success").and_then(|v| v.as_bool()).unwrap_or(false);
+            if !success || (tool_name != "say_to_user" && tool_name != "task_fully_completed") {
+                return None;
+            }
+            data.get("content")
+        }
+        "agent.response" => data.get("text"),
+        _ => None,
+    }?;
+    text.as_str().filter(|t| !t.is_empty()).map(str::to_string)
+}
+
+/// Dispatch a message and return the result plus the text the agent said back.
+///
+/// The agent's replies in the message's conversation are captured while the
+/// dispatch runs (see `reply_text`). Falls back to the dispatcher's final response.
+pub(crate) async fn dispatch_collecting_replies(
+    state: &AppState,
+    normalized: NormalizedMessage,
+) -> (DispatchResult, String) {
+    // Subscribe to events so we can capture say_to_user / agent.response
+    let broadcaster = &state.broadcaster;
+    let (client_id, mut rx) = broadcaster.subscribe();
+
+    let collected: Arc<tokio::sync::Mutex<Vec<String>>> =
+        Arc::new(tokio::sync::Mutex::new(Vec::new()));
+    let collected_clone = collected.clone();
+    let cid = normalized.channel_id;
+    let chat_id = normalized.chat_id.clone();
+    let listener = tokio::spawn(async move {
+        while let Some(event) = rx.recv().await {
+            if let Some(text) = reply_text(&event, cid, &chat_id) {
+                collected_clone.lock().await.push(text);
+            }
+        }
+    });
+
+    let result = state.dispatcher.dispatch(normalized).await;
+
+    broadcaster.unsubscribe(&client_id);
+    tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+    listener.abort();
+
+    let say_messages = collected.lock().await;
+    let response_text = if !say_messages.is_empty() {
+        say_messages.join("\n\n")
+    } else {
+        result.response.clone()
+    };
+
+    (result, response_text)
+}
+
 // ── Endpoint handlers ───────────────────────────────────────────────────
 
 /// POST /api/gateway/chat — send message, get full response
@@ -253,55 +323,6 @@ async fn gateway_chat(
         .clone()
         .unwrap_or_else(|| "gateway-user".to_string());
 
-    // Subscribe to events so we can capture say_to_user / agent.response
-    let broadcaster = &state.broadcaster;
-    let (client_id, mut rx) = broadcaster.subscribe();
-
-    let collected: Arc<tokio::sync::Mutex<Vec<String>>> =
-        Arc::new(tokio::sync::Mutex::new(Vec::new()));
-    let collected_clone = collected.clone();
-    let cid = channel_id;
-    let listener = tokio::spawn(async move {
-        while let Some(event) = rx.recv().await {
-            if event.event == "tool.result" {
-                let tool_name = event
-                    .data
-                    .get("tool_name")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("");
-                let ev_channel = event.data.get("channel_id").and_then(|v| v.as_i64());
-                let success = event
-                    .data
-                    .get("success")
-                    .and_then(|v| v.as_bool())
-                    .unwrap_or(false);
-                let content = event
-                    .data
-                    .get("content")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("");
-
-                if ev_channel == Some(cid) && success && !content.is_empty() {
-                    if tool_name == "say_to_user" || tool_name == "task_fully_completed" {
-                        collected_clone.lock().await.push(content.to_string());
-                    }
-                }
-            }
-            if event.event == "agent.response" {
-                let ev_channel = event.data.get("channel_id").and_then(|v| v.as_i64());
-                let content = event
-                    .data
-                    .get("content")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("");
-
-                if ev_channel == Some(cid) && !content.is_empty() {
-                    collected_clone.lock().await.push(content.to_string());
-                }
-            }
-        }
-    });
-
     let safe_mode = state
         .db
         .get_channel_setting(channel_id, "external_channel_safe_mode")
@@ -322,13 +343,11 @@ async fn gateway_chat(
         session_mode: None,
         selected_network: None,
         force_safe_mode: safe_mode,
+        from_admin: false,
+        attachments: Vec::new(),
     };
 
-    let result = state.dispatcher.dispatch(normalized).await;
-
-    broadcaster.unsubscribe(&client_id);
-    tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
-    listener.abort();
+    let (result, response_text) = dispatch_collecting_replies(&state, normalized).await;
 
     // Look up session
     let session_id = state
@@ -337,13 +356,6 @@ async fn gateway_chat(
         .ok()
         .map(|s| s.id);
 
-    let say_messages = collected.lock().await;
-    let response_text = if !say_messages.is_empty() {
-        say_messages.join("\n\n")
-    } else {
-        result.response.clone()
-    };
-
     if let Some(error) = result.error {
         log::error!("[EXT_CHANNEL] Dispatch error: {}", error);
         return HttpResponse::InternalServerError().json(GatewayChatResponse {
@@ -420,6 +432,8 @@ async fn gateway_chat_stream(
             session_mode: None,
             selected_network: None,
             force_safe_mode: safe_mode,
+            from_admin: false,
+            attachments: Vec::new(),
         };
         let _ = dispatcher.dispatch(normalized).await;
         tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
@@ -682,3 +696,27 @@ async fn gateway_generate_token(
         error: None,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reply_text_is_scoped_to_the_conversation() {
+        let say = |chat_id: &str| {
+            GatewayEvent::tool_result(5, Some(chat_id), "say_to_user", true, 1, "hi there", false)
+        };
+        assert_eq!(reply_text(&say("alice"), 5, "alice").as_deref(), Some("hi there"));
+        // Same channel, another user's conversation
+        assert_eq!(reply_text(&say("bob"), 5, "alice"), None);
+        // Same chat id on another channel
+        assert_eq!(reply_text(&say("alice"), 6, "alice"), None);
+
+        let response = GatewayEvent::agent_response(5, Some("alice"), "alice", "done");
+        assert_eq!(reply_text(&response, 5, "alice").as_deref(), Some("done"));
+        assert_eq!(reply_text(&response, 5, "bob"), None);
+
+        let other_tool = GatewayEvent::tool_result(5, Some("alice"), "exec", true, 1, "output", false);
+        assert_eq!(reply_text(&other_tool, 5, "alice"), None);
+    }
+}
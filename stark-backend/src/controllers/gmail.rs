@@ -233,6 +233,8 @@ async fn dispatch_email(
         session_mode: None,
         selected_network: None,
         force_safe_mode: false,
+        from_admin: false,
+        attachments: Vec::new(),
     };
 
     // Broadcast event
@@ -1,23 +1,124 @@
 use actix_web::{web, HttpResponse, Responder};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
+use crate::db::Database;
 use crate::AppState;
 
 /// Version from Cargo.toml, available at compile time
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// How long a readiness check waits on each dependency
+const READY_CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+
 pub fn config_routes(cfg: &mut web::ServiceConfig) {
+    // Unauthenticated probes for load balancers and uptime monitors
+    cfg.service(web::resource("/health").route(web::get().to(liveness)));
+    cfg.service(web::resource("/ready").route(web::get().to(readiness)));
     cfg.service(web::resource("/api/health").route(web::get().to(health_check)));
     cfg.service(web::resource("/api/version").route(web::get().to(get_version)));
     cfg.service(web::resource("/api/health/config").route(web::get().to(get_config_status)));
 }
 
-async fn health_check() -> impl Responder {
+async fn health_check(state: web::Data<AppState>) -> impl Responder {
     HttpResponse::Ok().json(serde_json::json!({
         "status": "ok",
-        "version": VERSION
+        "version": VERSION,
+        "safe_mode": state.config.safe_mode
     }))
 }
 
+/// Liveness: 200 whenever the process is serving requests
+async fn liveness() -> impl Responder {
+    HttpResponse::Ok().json(serde_json::json!({ "status": "ok" }))
+}
+
+#[derive(Deserialize)]
+struct ReadinessQuery {
+    /// Also check that the active AI endpoint answers (`/ready?ai=true`)
+    #[serde(default)]
+    ai: bool,
+}
+
+/// Outcome of one readiness check. Errors are generic descriptions only:
+/// no endpoint URLs, keys, or connection strings.
+#[derive(Serialize)]
+struct CheckResult {
+    ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl CheckResult {
+    fn from_result(result: Result<(), String>) -> Self {
+        match result {
+            Ok(()) => CheckResult { ok: true, error: None },
+            Err(e) => CheckResult { ok: false, error: Some(e) },
+        }
+    }
+}
+
+/// Readiness: 200 if the database answers (and, with `?ai=true`, the active AI
+/// endpoint is reachable), 503 with per-check details otherwise
+async fn readiness(state: web::Data<AppState>, query: web::Query<ReadinessQuery>) -> impl Responder {
+    let db = state.db.clone();
+    let database = web::block(move || db.ping(READY_CHECK_TIMEOUT))
+        .await
+        .unwrap_or_else(|_| Err("database check failed".to_string()));
+    let database = CheckResult::from_result(database);
+
+    let ai_provider = match (query.ai, database.ok) {
+        (false, _) => None,
+        (true, true) => Some(CheckResult::from_result(check_ai_endpoint(&state.db).await)),
+        // AI settings live in the database
+        (true, false) => Some(CheckResult::from_result(Err("skipped: database unavailable".to_string()))),
+    };
+
+    let ready = database.ok && ai_provider.as_ref().map_or(true, |c| c.ok);
+    let mut checks = serde_json::json!({ "database": database });
+    if let Some(ai) = ai_provider {
+        checks["ai_provider"] = serde_json::json!(ai);
+    }
+    let body = serde_json::json!({
+        "status": if ready { "ready" } else { "not_ready" },
+        "checks": checks,
+    });
+    if ready {
+        HttpResponse::Ok().json(body)
+    } else {
+        HttpResponse::ServiceUnavailable().json(body)
+    }
+}
+
+/// Cheap reachability check of the active AI endpoint: an unauthenticated GET, so no
+/// tokens are spent. Any HTTP answer below 500 counts as reachable. Also used by the
+/// Discord `status` command, so errors must stay safe to show to any user.
+pub(crate) async fn check_ai_endpoint(db: &Database) -> Result<(), String> {
+    let settings = db
+        .get_active_agent_settings()
+        .map_err(|_| "failed to load AI settings".to_string())?
+        .ok_or_else(|| "no AI endpoint configured".to_string())?;
+
+    let response = crate::http::shared_client()
+        .get(&settings.endpoint)
+        .timeout(READY_CHECK_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| {
+            if e.is_timeout() {
+                "AI endpoint timed out".to_string()
+            } else if e.is_connect() {
+                "AI endpoint unreachable".to_string()
+            } else {
+                "AI endpoint request failed".to_string()
+            }
+        })?;
+    if response.status().is_server_error() {
+        return Err(format!("AI endpoint returned {}", response.status().as_u16()));
+    }
+    Ok(())
+}
+
 async fn get_version() -> impl Responder {
     HttpResponse::Ok().json(serde_json::json!({
         "version": VERSION
@@ -39,6 +140,7 @@ async fn get_config_status(state: web::Data<AppState>) -> impl Responder {
         "wallet_configured": state.wallet_provider.is_some(),
         "guest_dashboard_enabled": guest_dashboard,
         "wallet_address": wallet_address,
-        "wallet_mode": wallet_mode
+        "wallet_mode": wallet_mode,
+        "safe_mode": state.config.safe_mode
     }))
 }
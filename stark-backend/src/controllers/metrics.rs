@@ -0,0 +1,36 @@
+//! Prometheus scrape endpoint
+
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+
+use crate::controllers::external_channel::constant_time_eq;
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("/metrics").route(web::get().to(get_metrics)));
+}
+
+/// GET /metrics - Prometheus text format. Scrapers must send STARK_METRICS_TOKEN as a
+/// Bearer token; the endpoint is disabled while no token is configured.
+async fn get_metrics(req: HttpRequest) -> impl Responder {
+    let Some(expected) = crate::config::metrics_token() else {
+        return HttpResponse::NotFound().body("metrics are disabled; set STARK_METRICS_TOKEN to enable them\n");
+    };
+    let provided = req
+        .headers()
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .unwrap_or("");
+    if !constant_time_eq(provided.trim().as_bytes(), expected.trim().as_bytes()) {
+        return HttpResponse::Unauthorized().body("invalid or missing metrics token\n");
+    }
+
+    match crate::metrics::render() {
+        Ok(body) => HttpResponse::Ok()
+            .content_type("text/plain; version=0.0.4; charset=utf-8")
+            .body(body),
+        Err(e) => {
+            log::error!("[METRICS] {}", e);
+            HttpResponse::InternalServerError().body("failed to render metrics\n")
+        }
+    }
+}
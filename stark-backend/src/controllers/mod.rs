@@ -3,6 +3,7 @@ pub mod api_keys;
 pub mod auth;
 pub mod broadcasted_transactions;
 pub mod channels;
+pub mod config_snapshot;
 pub mod chat;
 pub mod cron;
 pub mod dashboard;
@@ -17,6 +18,7 @@ pub mod intrinsic;
 pub mod journal;
 pub mod kanban;
 pub mod memory;
+pub mod metrics;
 pub mod mindmap;
 pub mod modules;
 pub mod payments;
@@ -24,6 +26,7 @@ pub mod sessions;
 pub mod skills;
 pub mod tools;
 pub mod tx_queue;
+pub mod webhook_channel;
 pub mod well_known;
 pub mod system;
 pub mod telemetry;
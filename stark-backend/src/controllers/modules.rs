@@ -97,8 +97,15 @@ async fn activate_module(data: &web::Data<AppState>, module_name: &str) {
 
     if module.has_tools() {
         for tool in module.create_tools() {
-            log::info!("[MODULE] Hot-registered tool: {} (from {})", tool.name(), module_name);
-            data.tool_registry.register(tool);
+            let name = tool.name();
+            // Module tools are registered at startup, so this is usually a no-op
+            if data.tool_registry.has_tool(&name) {
+                log::debug!("[MODULE] Tool {} (from {}) already registered", name, module_name);
+                continue;
+            }
+            if data.tool_registry.register(tool) {
+                log::info!("[MODULE] Hot-registered tool: {} (from {})", name, module_name);
+            }
         }
     }
 }
@@ -446,8 +453,10 @@ async fn reload_modules(data: web::Data<AppState>) -> HttpResponse {
                 // Re-register tools
                 if module.has_tools() {
                     for tool in module.create_tools() {
-                        log::info!("[MODULE] Reload: registered tool '{}' (from {})", tool.name(), entry.module_name);
-                        data.tool_registry.register(tool);
+                        let name = tool.name();
+                        if data.tool_registry.register(tool) {
+                            log::info!("[MODULE] Reload: registered tool '{}' (from {})", name, entry.module_name);
+                        }
                     }
                 }
                 // Ensure skill is created and enabled
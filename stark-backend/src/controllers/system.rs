@@ -1,10 +1,11 @@
-//! System controller — disk usage info and cleanup endpoints.
+//! System controller — disk usage info, cleanup, and provider request sampling endpoints.
 
 use actix_web::{web, HttpRequest, HttpResponse, Responder};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use walkdir::WalkDir;
 
+use crate::ai::request_sampler::{self, SamplerConfig};
 use crate::config;
 use crate::controllers::health::VERSION;
 use crate::qmd_memory::file_ops;
@@ -65,6 +66,25 @@ struct SystemInfoResponse {
     version: String,
 }
 
+#[derive(Debug, Serialize)]
+struct ChannelDiskUsageEntry {
+    channel_id: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    channel_name: Option<String>,
+    used_bytes: u64,
+    /// Share of the per-channel quota used (0 when there is no per-channel quota)
+    percentage: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct DiskUsageResponse {
+    enabled: bool,
+    used_bytes: u64,
+    quota_bytes: u64,
+    channel_quota_bytes: u64,
+    channels: Vec<ChannelDiskUsageEntry>,
+}
+
 #[derive(Debug, Deserialize)]
 struct CleanupMemoriesBody {
     #[serde(default = "default_older_than_days")]
@@ -79,6 +99,9 @@ fn default_older_than_days() -> u32 {
 struct CleanupWorkspaceBody {
     #[serde(default)]
     confirm: bool,
+    /// Only delete files not modified for this many days (all files when unset)
+    #[serde(default)]
+    older_than_days: Option<u32>,
 }
 
 #[derive(Debug, Serialize)]
@@ -176,6 +199,45 @@ async fn system_info(data: web::Data<AppState>, req: HttpRequest) -> impl Respon
     })
 }
 
+/// GET /api/system/disk-usage
+///
+/// Quota usage, with the on-disk size of the files attributed to each channel.
+async fn disk_usage(data: web::Data<AppState>, req: HttpRequest) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+
+    let Some(ref dq) = data.disk_quota else {
+        return HttpResponse::Ok().json(DiskUsageResponse {
+            enabled: false,
+            used_bytes: dir_size(&config::workspace_dir()),
+            quota_bytes: 0,
+            channel_quota_bytes: 0,
+            channels: Vec::new(),
+        });
+    };
+
+    let channel_quota = dq.channel_quota_bytes();
+    let channels = dq
+        .channel_usage()
+        .into_iter()
+        .map(|usage| ChannelDiskUsageEntry {
+            channel_id: usage.channel_id,
+            channel_name: data.db.get_channel(usage.channel_id).ok().flatten().map(|c| c.name),
+            used_bytes: usage.used_bytes,
+            percentage: if channel_quota > 0 { usage.used_bytes * 100 / channel_quota } else { 0 },
+        })
+        .collect();
+
+    HttpResponse::Ok().json(DiskUsageResponse {
+        enabled: dq.is_enabled(),
+        used_bytes: dq.usage_bytes(),
+        quota_bytes: dq.quota_bytes(),
+        channel_quota_bytes: channel_quota,
+        channels,
+    })
+}
+
 /// POST /api/system/cleanup/memories
 ///
 /// Delete daily log `.md` files older than N days.
@@ -249,7 +311,7 @@ async fn cleanup_memories(
 
 /// POST /api/system/cleanup/workspace
 ///
-/// Delete all files in the workspace directory.
+/// Delete all files in the workspace directory, or only those older than `older_than_days`.
 async fn cleanup_workspace(
     data: web::Data<AppState>,
     req: HttpRequest,
@@ -282,6 +344,10 @@ async fn cleanup_workspace(
     let mut deleted_count = 0usize;
     let mut freed_bytes = 0u64;
 
+    let cutoff = body
+        .older_than_days
+        .map(|days| std::time::SystemTime::now() - std::time::Duration::from_secs(days as u64 * 86_400));
+
     // Walk workspace, delete files (not directories)
     let entries: Vec<_> = WalkDir::new(workspace_path)
         .into_iter()
@@ -291,6 +357,11 @@ async fn cleanup_workspace(
 
     for entry in entries {
         if let Ok(meta) = entry.metadata() {
+            if let Some(cutoff) = cutoff {
+                if meta.modified().map(|m| m > cutoff).unwrap_or(true) {
+                    continue;
+                }
+            }
             let size = meta.len();
             if std::fs::remove_file(entry.path()).is_ok() {
                 deleted_count += 1;
@@ -311,10 +382,11 @@ async fn cleanup_workspace(
         let _ = std::fs::remove_dir(&dir); // only succeeds if empty
     }
 
-    // Refresh disk quota
+    // Refresh disk quota; channels' deleted files drop out of their usage
     if let Some(ref dq) = data.disk_quota {
         dq.refresh();
     }
+    log::info!("[DISK_QUOTA] Workspace cleanup deleted {} files ({} bytes)", deleted_count, freed_bytes);
 
     HttpResponse::Ok().json(CleanupResponse {
         success: true,
@@ -324,12 +396,59 @@ async fn cleanup_workspace(
     })
 }
 
+/// GET /api/system/request-sampling
+///
+/// Current provider request log sampling settings.
+async fn get_request_sampling(data: web::Data<AppState>, req: HttpRequest) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+
+    let config = request_sampler::global().config();
+    HttpResponse::Ok().json(serde_json::json!({
+        "success": true,
+        "enabled": config.is_enabled(),
+        "config": config,
+    }))
+}
+
+/// PUT /api/system/request-sampling
+///
+/// Replace the sampling settings at runtime (not persisted; the env vars apply again on restart).
+async fn update_request_sampling(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    body: web::Json<SamplerConfig>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+
+    let mut config = body.into_inner();
+    config.user_ids = config
+        .user_ids
+        .into_iter()
+        .map(|u| u.trim().to_string())
+        .filter(|u| !u.is_empty())
+        .collect();
+    request_sampler::global().set_config(config.clone());
+
+    HttpResponse::Ok().json(serde_json::json!({
+        "success": true,
+        "enabled": config.is_enabled(),
+        "config": config,
+    }))
+}
+
 /// Configure system routes
 pub fn config(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/api/system")
             .route("/info", web::get().to(system_info))
+            .route("/disk-usage", web::get().to(disk_usage))
             .route("/cleanup/memories", web::post().to(cleanup_memories))
-            .route("/cleanup/workspace", web::post().to(cleanup_workspace)),
+            .route("/cleanup/workspace", web::post().to(cleanup_workspace))
+            .route("/request-sampling", web::get().to(get_request_sampling))
+            .route("/request-sampling", web::put().to(update_request_sampling)),
     );
 }
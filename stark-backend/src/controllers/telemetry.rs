@@ -4,20 +4,59 @@ use actix_web::{web, HttpRequest, HttpResponse, Responder};
 use serde::{Deserialize, Serialize};
 
 use crate::AppState;
-use crate::telemetry::{Resource, ResourceType};
+use crate::telemetry::{Resource, ResourceType, RewardFilter, RewardRecord};
+
+/// Rewards returned by GET /api/telemetry/rewards when no limit is given, and the cap
+const DEFAULT_REWARDS_LIMIT: usize = 500;
+const MAX_REWARDS_LIMIT: usize = 5000;
 
 #[derive(Serialize)]
 struct ErrorResponse {
     error: String,
 }
 
+fn validate_session_from_request(
+    state: &web::Data<AppState>,
+    req: &HttpRequest,
+) -> Result<(), HttpResponse> {
+    let token = req
+        .headers()
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.trim_start_matches("Bearer ").to_string());
+
+    let token = match token {
+        Some(t) => t,
+        None => {
+            return Err(HttpResponse::Unauthorized().json(ErrorResponse {
+                error: "No authorization token provided".to_string(),
+            }));
+        }
+    };
+
+    match state.db.validate_session(&token) {
+        Ok(Some(_)) => Ok(()),
+        Ok(None) => Err(HttpResponse::Unauthorized().json(ErrorResponse {
+            error: "Invalid or expired session".to_string(),
+        })),
+        Err(e) => {
+            log::error!("Session validation error: {}", e);
+            Err(HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Internal server error".to_string(),
+            }))
+        }
+    }
+}
+
 pub fn config(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/api/telemetry")
             .route("/session/{id}/timeline", web::get().to(get_session_timeline))
             .route("/rollout/{id}/summary", web::get().to(get_rollout_summary))
             .route("/rollout/{id}/triplets", web::get().to(get_rollout_triplets))
+            .route("/rewards", web::get().to(list_rewards))
             .route("/rewards/stats", web::get().to(get_reward_stats))
+            .route("/tools/stats", web::get().to(get_tool_stats))
     );
     cfg.service(
         web::scope("/api/resources")
@@ -67,13 +106,103 @@ async fn get_reward_stats(
     query: web::Query<RewardStatsQuery>,
     _req: HttpRequest,
 ) -> impl Responder {
-    let since = query.since_hours.map(|hours| {
-        chrono::Utc::now() - chrono::Duration::hours(hours as i64)
-    });
+    let since = match query.since_hours.map(hours_ago).transpose() {
+        Ok(since) => since,
+        Err(e) => return HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+    };
     let stats = state.telemetry_store.get_reward_stats(since);
     HttpResponse::Ok().json(stats)
 }
 
+#[derive(Deserialize)]
+struct RewardsQuery {
+    name: Option<String>,
+    /// RFC 3339 lower bound; `since_hours` is accepted as a shorthand
+    since: Option<String>,
+    since_hours: Option<u64>,
+    /// RFC 3339 upper bound
+    until: Option<String>,
+    channel_id: Option<i64>,
+    limit: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct RewardsResponse {
+    count: usize,
+    rewards: Vec<RewardRecord>,
+}
+
+fn parse_time(field: &str, value: &str) -> Result<chrono::DateTime<chrono::Utc>, String> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&chrono::Utc))
+        .map_err(|e| format!("Invalid {} '{}': expected an RFC 3339 timestamp ({})", field, value, e))
+}
+
+/// `hours` before now; an error (not a panic) when that's outside chrono's range
+fn hours_ago(hours: u64) -> Result<chrono::DateTime<chrono::Utc>, String> {
+    i64::try_from(hours)
+        .ok()
+        .and_then(chrono::Duration::try_hours)
+        .and_then(|d| chrono::Utc::now().checked_sub_signed(d))
+        .ok_or_else(|| format!("Invalid since_hours '{}': too far in the past", hours))
+}
+
+impl RewardsQuery {
+    fn to_filter(&self) -> Result<RewardFilter, String> {
+        let since = match (&self.since, self.since_hours) {
+            (Some(since), _) => Some(parse_time("since", since)?),
+            (None, Some(hours)) => Some(hours_ago(hours)?),
+            (None, None) => None,
+        };
+        let until = self.until.as_deref().map(|u| parse_time("until", u)).transpose()?;
+        Ok(RewardFilter {
+            name: self.name.clone().filter(|n| !n.is_empty()),
+            since,
+            until,
+            channel_id: self.channel_id,
+            limit: Some(self.limit.unwrap_or(DEFAULT_REWARDS_LIMIT).min(MAX_REWARDS_LIMIT)),
+        })
+    }
+}
+
+/// GET /api/telemetry/rewards — recorded rewards (newest first) with their values and attributes
+async fn list_rewards(
+    state: web::Data<AppState>,
+    query: web::Query<RewardsQuery>,
+    req: HttpRequest,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&state, &req) {
+        return resp;
+    }
+    let filter = match query.to_filter() {
+        Ok(f) => f,
+        Err(e) => return HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+    };
+    let rewards = state.telemetry_store.query_rewards(&filter);
+    HttpResponse::Ok().json(RewardsResponse {
+        count: rewards.len(),
+        rewards,
+    })
+}
+
+#[derive(Deserialize)]
+struct ToolStatsQuery {
+    since_hours: Option<u64>,
+}
+
+/// GET /api/telemetry/tools/stats — per-tool success rate and durations, most-called first
+async fn get_tool_stats(
+    state: web::Data<AppState>,
+    query: web::Query<ToolStatsQuery>,
+    req: HttpRequest,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&state, &req) {
+        return resp;
+    }
+    let tools = state.telemetry_store.get_tool_stats(query.since_hours);
+    HttpResponse::Ok().json(serde_json::json!({ "tools": tools }))
+}
+
 async fn list_resources(
     state: web::Data<AppState>,
     _req: HttpRequest,
@@ -147,3 +276,15 @@ async fn rollback_resource(
         }),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hours_ago_rejects_out_of_range() {
+        assert!(hours_ago(24).is_ok());
+        assert!(hours_ago(u64::MAX).is_err());
+        assert!(hours_ago(i64::MAX as u64).is_err());
+    }
+}
@@ -1,7 +1,7 @@
 use actix_web::{web, HttpRequest, HttpResponse, Responder};
 use serde::{Deserialize, Serialize};
 
-use crate::tools::{ToolConfig, ToolDefinition, ToolExecution, ToolGroup, ToolProfile};
+use crate::tools::{ToolConfig, ToolDefinition, ToolExecution, ToolGroup, ToolInputSchema, ToolProfile};
 use crate::AppState;
 
 #[derive(Serialize)]
@@ -9,6 +9,9 @@ pub struct ToolsListResponse {
     pub success: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tools: Option<Vec<ToolInfo>>,
+    /// Set instead of `tools` when listing with `?grouped=true`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub groups: Option<Vec<ToolGroupSection>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
 }
@@ -20,6 +23,23 @@ pub struct ToolInfo {
     pub group: String,
     pub enabled: bool,
     pub safety_level: String,
+    pub input_schema: ToolInputSchema,
+}
+
+/// One `ToolGroup` and its registered tools
+#[derive(Serialize)]
+pub struct ToolGroupSection {
+    pub key: String,
+    pub label: String,
+    pub description: String,
+    pub tools: Vec<ToolInfo>,
+}
+
+#[derive(Deserialize)]
+pub struct ToolsListQuery {
+    /// Return tools in sections per `ToolGroup` instead of a flat list
+    #[serde(default)]
+    pub grouped: bool,
 }
 
 #[derive(Serialize)]
@@ -94,6 +114,8 @@ impl From<ToolConfig> for ToolConfigResponse {
 pub struct UpdateConfigRequest {
     pub profile: Option<String>,
     pub allow_list: Option<Vec<String>>,
+    /// Individual tools to disable, even when their group is enabled
+    #[serde(alias = "disabled_tools")]
     pub deny_list: Option<Vec<String>>,
     pub allowed_groups: Option<Vec<String>>,
     pub denied_groups: Option<Vec<String>>,
@@ -145,6 +167,7 @@ fn validate_session_from_request(
             return Err(HttpResponse::Unauthorized().json(ToolsListResponse {
                 success: false,
                 tools: None,
+                groups: None,
                 error: Some("No authorization token provided".to_string()),
             }));
         }
@@ -155,6 +178,7 @@ fn validate_session_from_request(
         Ok(None) => Err(HttpResponse::Unauthorized().json(ToolsListResponse {
             success: false,
             tools: None,
+            groups: None,
             error: Some("Invalid or expired session".to_string()),
         })),
         Err(e) => {
@@ -162,39 +186,78 @@ fn validate_session_from_request(
             Err(HttpResponse::InternalServerError().json(ToolsListResponse {
                 success: false,
                 tools: None,
+                groups: None,
                 error: Some("Internal server error".to_string()),
             }))
         }
     }
 }
 
-async fn list_tools(state: web::Data<AppState>, req: HttpRequest) -> impl Responder {
+/// List the tools in the live registry (the one the dispatcher uses), sorted by name.
+/// `enabled` reflects the global tool config and global safe mode.
+async fn list_tools(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<ToolsListQuery>,
+) -> impl Responder {
     if let Err(resp) = validate_session_from_request(&state, &req) {
         return resp;
     }
 
     let tool_config = state.db.get_effective_tool_config(None).unwrap_or_default();
 
-    let tools: Vec<ToolInfo> = state
+    let mut tools: Vec<(ToolGroup, ToolInfo)> = state
         .tool_registry
         .list()
         .iter()
         .map(|tool| {
             let def = tool.definition();
             let group = tool.group();
-            ToolInfo {
-                name: def.name.clone(),
-                description: def.description.clone(),
+            let enabled = tool_config.is_tool_allowed(&def.name, group)
+                && state.tool_registry.permitted_by_global_safe_mode(tool.as_ref());
+            let info = ToolInfo {
+                name: def.name,
+                description: def.description,
                 group: group.as_str().to_string(),
-                enabled: tool_config.is_tool_allowed(&def.name, group),
+                enabled,
                 safety_level: tool.safety_level().as_str().to_string(),
-            }
+                input_schema: def.input_schema,
+            };
+            (group, info)
         })
         .collect();
+    tools.sort_by(|a, b| a.1.name.cmp(&b.1.name));
+
+    if !query.grouped {
+        return HttpResponse::Ok().json(ToolsListResponse {
+            success: true,
+            tools: Some(tools.into_iter().map(|(_, info)| info).collect()),
+            groups: None,
+            error: None,
+        });
+    }
+
+    // Sections follow ToolGroup::all() order; empty groups are left out
+    let mut groups = Vec::new();
+    let mut remaining = tools;
+    for group in ToolGroup::all() {
+        let (members, rest): (Vec<_>, Vec<_>) = remaining.into_iter().partition(|(g, _)| *g == group);
+        remaining = rest;
+        if members.is_empty() {
+            continue;
+        }
+        groups.push(ToolGroupSection {
+            key: group.as_str().to_string(),
+            label: group.label().to_string(),
+            description: group.description().to_string(),
+            tools: members.into_iter().map(|(_, info)| info).collect(),
+        });
+    }
 
     HttpResponse::Ok().json(ToolsListResponse {
         success: true,
-        tools: Some(tools),
+        tools: None,
+        groups: Some(groups),
         error: None,
     })
 }
@@ -410,15 +473,21 @@ async fn update_channel_config(
 
     let channel_id = path.into_inner();
 
-    // Start with existing config or default
+    // Start with the existing channel config, or a copy of the global one so that
+    // e.g. disabling a single tool doesn't drop the global restrictions
     let mut config = state
         .db
         .get_channel_tool_config(channel_id)
         .ok()
         .flatten()
         .unwrap_or_else(|| {
-            let mut c = ToolConfig::default();
-            c.channel_id = Some(channel_id);
+            let mut c = state
+                .db
+                .get_global_tool_config()
+                .ok()
+                .flatten()
+                .unwrap_or_default();
+            c.id = None;
             c
         });
 
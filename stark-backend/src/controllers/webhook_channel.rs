@@ -0,0 +1,211 @@
+//! Generic webhook channel: `POST /api/channels/{id}/webhook`
+//!
+//! Lets any app pipe messages into the agent over HTTP without a dedicated
+//! integration. Callers authenticate with the channel's `webhook_secret`
+//! setting and get the agent's reply back synchronously.
+
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use serde::{Deserialize, Serialize};
+
+use super::external_channel::{constant_time_eq, dispatch_collecting_replies};
+use crate::channels::{ChannelType, NormalizedMessage};
+use crate::models::{Channel, ChannelSettingKey};
+use crate::AppState;
+
+#[derive(Debug, Deserialize)]
+pub struct WebhookMessageRequest {
+    pub text: String,
+    pub user_id: String,
+    pub user_name: String,
+    /// Conversation to continue; defaults to one conversation per user_id
+    #[serde(default)]
+    pub chat_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebhookMessageResponse {
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl WebhookMessageResponse {
+    fn error(message: impl Into<String>) -> Self {
+        Self {
+            success: false,
+            response: None,
+            session_id: None,
+            error: Some(message.into()),
+        }
+    }
+}
+
+/// Secret from `X-Webhook-Secret`, falling back to `Authorization: Bearer <secret>`
+fn extract_secret(req: &HttpRequest) -> Option<String> {
+    let header = |name: &str| {
+        req.headers()
+            .get(name)
+            .and_then(|h| h.to_str().ok())
+            .map(|s| s.trim().to_string())
+    };
+    header("X-Webhook-Secret")
+        .or_else(|| header("Authorization").and_then(|s| s.strip_prefix("Bearer ").map(|t| t.trim().to_string())))
+        .filter(|s| !s.is_empty())
+}
+
+/// Compare the caller's secret with the channel's stored one
+fn check_secret(req: &HttpRequest, stored: &str) -> Result<(), HttpResponse> {
+    match extract_secret(req) {
+        Some(provided) if constant_time_eq(provided.as_bytes(), stored.as_bytes()) => Ok(()),
+        Some(_) => Err(HttpResponse::Unauthorized().json(WebhookMessageResponse::error("Invalid webhook secret"))),
+        None => Err(HttpResponse::Unauthorized().json(WebhookMessageResponse::error(
+            "Missing X-Webhook-Secret header",
+        ))),
+    }
+}
+
+/// Resolve the webhook channel and check the caller's secret
+fn authorize(state: &AppState, req: &HttpRequest, channel_id: i64) -> Result<Channel, HttpResponse> {
+    let channel = match state.db.get_channel(channel_id) {
+        Ok(Some(ch)) if ch.channel_type == ChannelType::Webhook.as_str() => ch,
+        Ok(_) => {
+            return Err(HttpResponse::NotFound().json(WebhookMessageResponse::error("Webhook channel not found")));
+        }
+        Err(e) => {
+            log::error!("[WEBHOOK] Failed to load channel {}: {}", channel_id, e);
+            return Err(HttpResponse::InternalServerError().json(WebhookMessageResponse::error("Internal server error")));
+        }
+    };
+
+    let stored = state
+        .db
+        .get_channel_setting(channel_id, ChannelSettingKey::WebhookSecret.as_ref())
+        .ok()
+        .flatten()
+        .filter(|s| !s.is_empty());
+    let stored = match stored {
+        Some(s) => s,
+        None => {
+            return Err(HttpResponse::Forbidden().json(WebhookMessageResponse::error(
+                "Webhook secret is not configured for this channel",
+            )));
+        }
+    };
+
+    check_secret(req, &stored).map_err(|resp| {
+        log::warn!("[WEBHOOK] Rejected request for channel {}: bad or missing secret", channel_id);
+        resp
+    })?;
+
+    if !state.gateway.channel_manager().is_running(channel_id) {
+        return Err(HttpResponse::Forbidden().json(WebhookMessageResponse::error("Webhook channel is not running")));
+    }
+
+    Ok(channel)
+}
+
+/// POST /api/channels/{id}/webhook — run a message through the agent, return its reply
+pub async fn receive_webhook(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<i64>,
+    body: web::Json<WebhookMessageRequest>,
+) -> impl Responder {
+    let channel_id = path.into_inner();
+    let channel = match authorize(&state, &req, channel_id) {
+        Ok(ch) => ch,
+        Err(resp) => return resp,
+    };
+
+    let body = body.into_inner();
+    if body.text.trim().is_empty() {
+        return HttpResponse::BadRequest().json(WebhookMessageResponse::error("text cannot be empty"));
+    }
+    if body.user_id.trim().is_empty() {
+        return HttpResponse::BadRequest().json(WebhookMessageResponse::error("user_id cannot be empty"));
+    }
+
+    log::info!(
+        "[WEBHOOK] Message on '{}' (id={}) from {}: {} chars",
+        channel.name,
+        channel_id,
+        body.user_id,
+        body.text.len()
+    );
+
+    let safe_mode = state
+        .db
+        .get_channel_setting(channel_id, ChannelSettingKey::WebhookSafeMode.as_ref())
+        .ok()
+        .flatten()
+        .map(|v| v == "true")
+        .unwrap_or_else(|| ChannelSettingKey::WebhookSafeMode.default_value() == "true");
+
+    let chat_id = body
+        .chat_id
+        .filter(|c| !c.trim().is_empty())
+        .unwrap_or_else(|| body.user_id.clone());
+
+    let normalized = NormalizedMessage {
+        channel_id,
+        channel_type: ChannelType::Webhook.as_str().to_string(),
+        chat_id,
+        chat_name: None,
+        user_id: body.user_id,
+        user_name: body.user_name,
+        text: body.text,
+        message_id: None,
+        session_mode: None,
+        selected_network: None,
+        force_safe_mode: safe_mode,
+        from_admin: false,
+        attachments: Vec::new(),
+    };
+
+    let (result, response_text) = dispatch_collecting_replies(&state, normalized).await;
+
+    if let Some(error) = result.error {
+        log::error!("[WEBHOOK] Dispatch error on channel {}: {}", channel_id, error);
+        return HttpResponse::InternalServerError().json(WebhookMessageResponse {
+            success: false,
+            response: None,
+            session_id: result.session_id,
+            error: Some(error),
+        });
+    }
+
+    HttpResponse::Ok().json(WebhookMessageResponse {
+        success: true,
+        response: Some(response_text),
+        session_id: result.session_id,
+        error: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::http::StatusCode;
+    use actix_web::test::TestRequest;
+
+    #[test]
+    fn test_check_secret() {
+        let with_header = |name: &str, value: &str| TestRequest::default().insert_header((name, value)).to_http_request();
+
+        assert!(check_secret(&with_header("X-Webhook-Secret", "s3cret"), "s3cret").is_ok());
+        assert!(check_secret(&with_header("Authorization", "Bearer s3cret"), "s3cret").is_ok());
+
+        let wrong = check_secret(&with_header("X-Webhook-Secret", "guess"), "s3cret").unwrap_err();
+        assert_eq!(wrong.status(), StatusCode::UNAUTHORIZED);
+        let prefix = check_secret(&with_header("X-Webhook-Secret", "s3cre"), "s3cret").unwrap_err();
+        assert_eq!(prefix.status(), StatusCode::UNAUTHORIZED);
+        let empty = check_secret(&with_header("X-Webhook-Secret", "  "), "s3cret").unwrap_err();
+        assert_eq!(empty.status(), StatusCode::UNAUTHORIZED);
+        let missing = check_secret(&TestRequest::default().to_http_request(), "s3cret").unwrap_err();
+        assert_eq!(missing.status(), StatusCode::UNAUTHORIZED);
+    }
+}
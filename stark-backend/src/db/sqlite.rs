@@ -78,6 +78,18 @@ impl Database {
             .expect("Failed to get database connection from pool (timeout after 5s)")
     }
 
+    /// Readiness check: a connection can be acquired within `timeout` and answers a query.
+    /// Unlike [`Database::conn`], this never panics.
+    pub fn ping(&self, timeout: std::time::Duration) -> Result<(), String> {
+        let conn = self
+            .pool
+            .get_timeout(timeout)
+            .map_err(|_| format!("no database connection available within {}s", timeout.as_secs()))?;
+        conn.query_row("SELECT 1", [], |row| row.get::<_, i64>(0))
+            .map(|_| ())
+            .map_err(|e| format!("database query failed: {}", e))
+    }
+
     /// Initialize all database tables and run migrations
     fn init(&self) -> SqliteResult<()> {
         let conn = self.conn();
@@ -131,6 +143,19 @@ impl Database {
             [],
         )?;
 
+        // Audit log of API key changes (never stores key values)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS api_key_audit_log (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                key_name TEXT NOT NULL,
+                action TEXT NOT NULL,
+                actor TEXT NOT NULL,
+                key_hint TEXT,
+                created_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
         // External channels table (Telegram, Slack, etc.)
         conn.execute(
             "CREATE TABLE IF NOT EXISTS external_channels (
@@ -154,6 +179,12 @@ impl Database {
             [],
         );
 
+        // Migration: Soft delete for external_channels (NULL = live)
+        let _ = conn.execute(
+            "ALTER TABLE external_channels ADD COLUMN deleted_at TEXT",
+            [],
+        );
+
         // Agent settings table (AI endpoint configuration - simplified for x402)
         // Note: provider, api_key, model columns are deprecated (kept for migration compatibility)
         // max_tokens renamed to max_response_tokens, max_context_tokens added for compaction
@@ -256,6 +287,34 @@ impl Database {
             conn.execute("ALTER TABLE agent_settings ADD COLUMN secret_key TEXT", [])?;
         }
 
+        // Migration: Add weight column for weighted (A/B) model selection
+        let has_weight: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('agent_settings') WHERE name='weight'",
+                [],
+                |row| row.get::<_, i64>(0),
+            )
+            .map(|c| c > 0)
+            .unwrap_or(false);
+
+        if !has_weight {
+            conn.execute("ALTER TABLE agent_settings ADD COLUMN weight INTEGER NOT NULL DEFAULT 0", [])?;
+        }
+
+        // Migration: Add fallback_priority column for the provider fallback chain
+        let has_fallback_priority: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('agent_settings') WHERE name='fallback_priority'",
+                [],
+                |row| row.get::<_, i64>(0),
+            )
+            .map(|c| c > 0)
+            .unwrap_or(false);
+
+        if !has_fallback_priority {
+            conn.execute("ALTER TABLE agent_settings ADD COLUMN fallback_priority INTEGER NOT NULL DEFAULT 0", [])?;
+        }
+
         // Migration: Add web3_tx_requires_confirmation column to bot_settings if it doesn't exist
         let has_web3_tx_confirmation: bool = conn
             .query_row(
@@ -1310,6 +1369,69 @@ impl Database {
             [],
         )?;
 
+        // Reminders - user reminders delivered by the scheduler (set_reminder tool)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS reminders (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                channel_id INTEGER NOT NULL,
+                channel_type TEXT NOT NULL,
+                chat_id TEXT,
+                user_id TEXT NOT NULL,
+                text TEXT NOT NULL,
+                due_at TEXT NOT NULL,
+                delivery TEXT NOT NULL DEFAULT 'dm',
+                status TEXT NOT NULL DEFAULT 'pending',
+                last_error TEXT,
+                created_at TEXT NOT NULL,
+                sent_at TEXT
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_reminders_status_due ON reminders(status, due_at)",
+            [],
+        )?;
+
+        // Scheduled messages - one-shot or recurring posts/prompts from the schedule_message tool
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS scheduled_jobs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                channel_id INTEGER NOT NULL,
+                channel_type TEXT NOT NULL,
+                chat_id TEXT NOT NULL,
+                user_id TEXT NOT NULL,
+                mode TEXT NOT NULL DEFAULT 'text',
+                content TEXT NOT NULL,
+                run_at TEXT NOT NULL,
+                recurrence TEXT,
+                timezone TEXT,
+                status TEXT NOT NULL DEFAULT 'pending',
+                run_count INTEGER NOT NULL DEFAULT 0,
+                last_run_at TEXT,
+                last_error TEXT,
+                created_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_scheduled_jobs_status_run_at ON scheduled_jobs(status, run_at)",
+            [],
+        )?;
+
+        // User preferences - per-identity settings (preferred name, timezone, language, notifications)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS user_preferences (
+                identity_id TEXT NOT NULL,
+                pref_key TEXT NOT NULL,
+                pref_value TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                PRIMARY KEY (identity_id, pref_key)
+            )",
+            [],
+        )?;
+
         // Installed modules - plugin system registry
         conn.execute(
             "CREATE TABLE IF NOT EXISTS installed_modules (
@@ -1631,6 +1753,17 @@ impl Database {
         Ok(())
     }
 
+    /// Soft-delete the identity row for an agent (sets active = 0, keeps the row).
+    /// Returns false if no row has that agent_id.
+    pub fn deactivate_agent_identity(&self, agent_id: i64) -> Result<bool, rusqlite::Error> {
+        let conn = self.conn();
+        let updated = conn.execute(
+            "UPDATE agent_identity SET active = 0, updated_at = datetime('now') WHERE agent_id = ?1",
+            [agent_id],
+        )?;
+        Ok(updated > 0)
+    }
+
     /// Update a single field on the agent identity row
     pub fn update_agent_identity_field(&self, field: &str, value: &str) -> Result<(), rusqlite::Error> {
         let conn = self.conn();
@@ -1836,7 +1969,7 @@ impl AgentIdentityRow {
             serde_json::from_str(&self.supported_trust_json).unwrap_or_default();
 
         crate::eip8004::types::RegistrationFile {
-            type_url: "https://eips.ethereum.org/EIPS/eip-8004#registration-v1".to_string(),
+            type_url: crate::eip8004::types::REGISTRATION_TYPE_V1.to_string(),
             name: self.name.clone().unwrap_or_default(),
             description: self.description.clone().unwrap_or_default(),
             image: self.image.clone(),
@@ -16,7 +16,7 @@ impl Database {
         let conn = self.conn();
 
         let mut stmt = conn.prepare(
-            "SELECT id, endpoint, model_archetype, max_response_tokens, max_context_tokens, enabled, secret_key, created_at, updated_at
+            "SELECT id, endpoint, model_archetype, max_response_tokens, max_context_tokens, enabled, secret_key, created_at, updated_at, weight, fallback_priority
              FROM agent_settings WHERE enabled = 1 LIMIT 1",
         )?;
 
@@ -33,7 +33,7 @@ impl Database {
         let conn = self.conn();
 
         let mut stmt = conn.prepare(
-            "SELECT id, endpoint, model_archetype, max_response_tokens, max_context_tokens, enabled, secret_key, created_at, updated_at
+            "SELECT id, endpoint, model_archetype, max_response_tokens, max_context_tokens, enabled, secret_key, created_at, updated_at, weight, fallback_priority
              FROM agent_settings WHERE endpoint = ?1",
         )?;
 
@@ -49,7 +49,7 @@ impl Database {
         let conn = self.conn();
 
         let mut stmt = conn.prepare(
-            "SELECT id, endpoint, model_archetype, max_response_tokens, max_context_tokens, enabled, secret_key, created_at, updated_at
+            "SELECT id, endpoint, model_archetype, max_response_tokens, max_context_tokens, enabled, secret_key, created_at, updated_at, weight, fallback_priority
              FROM agent_settings ORDER BY id",
         )?;
 
@@ -111,6 +111,94 @@ impl Database {
             .map(|opt| opt.unwrap())
     }
 
+    /// List agent settings with a positive A/B traffic weight. Empty while AI is
+    /// disabled (no enabled row); weighted rows need not be the enabled one themselves.
+    pub fn list_weighted_agent_settings(&self) -> SqliteResult<Vec<AgentSettings>> {
+        let conn = self.conn();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, endpoint, model_archetype, max_response_tokens, max_context_tokens, enabled, secret_key, created_at, updated_at, weight, fallback_priority
+             FROM agent_settings
+             WHERE weight > 0 AND EXISTS (SELECT 1 FROM agent_settings WHERE enabled = 1)
+             ORDER BY id",
+        )?;
+
+        let settings = stmt
+            .query_map([], |row| Self::row_to_agent_settings(row))?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(settings)
+    }
+
+    /// Set the A/B traffic weights of agent settings rows as `(id, weight)` pairs
+    /// (0 removes a row from the split). Returns false (and changes nothing) if any id doesn't exist.
+    pub fn set_agent_settings_weights(&self, weights: &[(i64, i32)]) -> SqliteResult<bool> {
+        let conn = self.conn();
+        let now = Utc::now().to_rfc3339();
+        let tx = conn.unchecked_transaction()?;
+        for (id, weight) in weights {
+            let rows_affected = tx.execute(
+                "UPDATE agent_settings SET weight = ?1, updated_at = ?2 WHERE id = ?3",
+                rusqlite::params![(*weight).max(0), &now, id],
+            )?;
+            if rows_affected == 0 {
+                return Ok(false);
+            }
+        }
+        tx.commit()?;
+        drop(tx);
+        drop(conn);
+        self.cache.invalidate_agent_settings();
+        Ok(true)
+    }
+
+    /// List the fallback chain: rows with a positive fallback priority, first fallback first.
+    /// Empty while AI is disabled (no enabled row).
+    pub fn list_fallback_agent_settings(&self) -> SqliteResult<Vec<AgentSettings>> {
+        let conn = self.conn();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, endpoint, model_archetype, max_response_tokens, max_context_tokens, enabled, secret_key, created_at, updated_at, weight, fallback_priority
+             FROM agent_settings
+             WHERE fallback_priority > 0 AND EXISTS (SELECT 1 FROM agent_settings WHERE enabled = 1)
+             ORDER BY fallback_priority, id",
+        )?;
+
+        let settings = stmt
+            .query_map([], |row| Self::row_to_agent_settings(row))?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(settings)
+    }
+
+    /// Replace the fallback chain with `ids`, in order. Rows not listed stop being fallbacks.
+    /// Returns false (and changes nothing) if any id doesn't exist.
+    pub fn set_agent_settings_fallbacks(&self, ids: &[i64]) -> SqliteResult<bool> {
+        let conn = self.conn();
+        let now = Utc::now().to_rfc3339();
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
+            "UPDATE agent_settings SET fallback_priority = 0, updated_at = ?1 WHERE fallback_priority != 0",
+            [&now],
+        )?;
+        for (position, id) in ids.iter().enumerate() {
+            let rows_affected = tx.execute(
+                "UPDATE agent_settings SET fallback_priority = ?1, updated_at = ?2 WHERE id = ?3",
+                rusqlite::params![position as i32 + 1, &now, id],
+            )?;
+            if rows_affected == 0 {
+                return Ok(false);
+            }
+        }
+        tx.commit()?;
+        drop(tx);
+        drop(conn);
+        self.cache.invalidate_agent_settings();
+        Ok(true)
+    }
+
     /// Disable all agent settings (no AI provider active)
     pub fn disable_agent_settings(&self) -> SqliteResult<()> {
         let conn = self.conn();
@@ -138,6 +226,8 @@ impl Database {
             updated_at: DateTime::parse_from_rfc3339(&updated_at_str)
                 .unwrap()
                 .with_timezone(&Utc),
+            weight: row.get::<_, Option<i32>>(9)?.unwrap_or(0),
+            fallback_priority: row.get::<_, Option<i32>>(10)?.unwrap_or(0),
         })
     }
 }
@@ -3,7 +3,7 @@
 use chrono::{DateTime, Utc};
 use rusqlite::Result as SqliteResult;
 
-use crate::models::ApiKey;
+use crate::models::{ApiKey, ApiKeyChange};
 use super::super::Database;
 
 impl Database {
@@ -110,6 +110,44 @@ impl Database {
         Ok(rows_affected > 0)
     }
 
+    /// Record a change to an API key in the audit log
+    pub fn log_api_key_change(
+        &self,
+        key_name: &str,
+        action: &str,
+        actor: &str,
+        key_hint: Option<&str>,
+    ) -> SqliteResult<()> {
+        let conn = self.conn();
+        conn.execute(
+            "INSERT INTO api_key_audit_log (key_name, action, actor, key_hint, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+            rusqlite::params![key_name, action, actor, key_hint, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// Most recent API key changes, newest first
+    pub fn list_api_key_changes(&self, limit: i64) -> SqliteResult<Vec<ApiKeyChange>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT id, key_name, action, actor, key_hint, created_at FROM api_key_audit_log ORDER BY id DESC LIMIT ?1",
+        )?;
+        let changes = stmt
+            .query_map([limit], |row| {
+                Ok(ApiKeyChange {
+                    id: row.get(0)?,
+                    key_name: row.get(1)?,
+                    action: row.get(2)?,
+                    actor: row.get(3)?,
+                    key_hint: row.get(4)?,
+                    created_at: row.get(5)?,
+                })
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(changes)
+    }
+
     /// List all API keys with their full values (for export/backup)
     pub fn list_api_keys_with_values(&self) -> SqliteResult<Vec<(String, String)>> {
         let conn = self.conn();
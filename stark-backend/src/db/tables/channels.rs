@@ -3,6 +3,7 @@
 use chrono::{DateTime, Duration, Utc};
 use rusqlite::Result as SqliteResult;
 
+use crate::channels::ChannelType;
 use crate::models::Channel;
 use super::super::Database;
 
@@ -33,6 +34,11 @@ impl Database {
         app_token: Option<&str>,
         safe_mode: bool,
     ) -> SqliteResult<Channel> {
+        // Reject typos up front; an unknown type would be a row no listener can start
+        let channel_type = ChannelType::from_str(channel_type)
+            .map_err(rusqlite::Error::InvalidParameterName)?
+            .as_str();
+
         let conn = self.conn();
         let now = Utc::now().to_rfc3339();
 
@@ -55,6 +61,7 @@ impl Database {
             safe_mode,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            deleted_at: None,
         })
     }
 
@@ -108,7 +115,7 @@ impl Database {
     pub fn count_safe_mode_channels(&self) -> SqliteResult<usize> {
         let conn = self.conn();
         let count: i64 = conn.query_row(
-            "SELECT COUNT(*) FROM external_channels WHERE safe_mode = 1",
+            "SELECT COUNT(*) FROM external_channels WHERE safe_mode = 1 AND deleted_at IS NULL",
             [],
             |row| row.get(0),
         )?;
@@ -119,8 +126,8 @@ impl Database {
     pub fn get_oldest_safe_mode_channel(&self) -> SqliteResult<Option<Channel>> {
         let conn = self.conn();
         let mut stmt = conn.prepare(
-            "SELECT id, channel_type, name, enabled, bot_token, app_token, safe_mode, created_at, updated_at
-             FROM external_channels WHERE safe_mode = 1 ORDER BY created_at ASC LIMIT 1"
+            "SELECT id, channel_type, name, enabled, bot_token, app_token, safe_mode, created_at, updated_at, deleted_at
+             FROM external_channels WHERE safe_mode = 1 AND deleted_at IS NULL ORDER BY created_at ASC LIMIT 1"
         )?;
 
         let channel = stmt.query_row([], |row| Self::row_to_channel(row)).ok();
@@ -141,7 +148,7 @@ impl Database {
         Ok(deleted)
     }
 
-    /// Get a channel by ID
+    /// Get a channel by ID (soft-deleted channels are treated as missing)
     pub fn get_channel(&self, id: i64) -> SqliteResult<Option<Channel>> {
         if let Some(cached) = self.cache.get_channel(id) {
             return Ok(cached);
@@ -150,8 +157,8 @@ impl Database {
         let conn = self.conn();
 
         let mut stmt = conn.prepare(
-            "SELECT id, channel_type, name, enabled, bot_token, app_token, safe_mode, created_at, updated_at
-             FROM external_channels WHERE id = ?1",
+            "SELECT id, channel_type, name, enabled, bot_token, app_token, safe_mode, created_at, updated_at, deleted_at
+             FROM external_channels WHERE id = ?1 AND deleted_at IS NULL",
         )?;
 
         let channel = stmt
@@ -162,13 +169,30 @@ impl Database {
         Ok(channel)
     }
 
-    /// List all channels
+    /// List all channels, excluding soft-deleted ones
     pub fn list_channels(&self) -> SqliteResult<Vec<Channel>> {
         let conn = self.conn();
 
         let mut stmt = conn.prepare(
-            "SELECT id, channel_type, name, enabled, bot_token, app_token, safe_mode, created_at, updated_at
-             FROM external_channels ORDER BY channel_type, name",
+            "SELECT id, channel_type, name, enabled, bot_token, app_token, safe_mode, created_at, updated_at, deleted_at
+             FROM external_channels WHERE deleted_at IS NULL ORDER BY channel_type, name",
+        )?;
+
+        let channels = stmt
+            .query_map([], |row| Self::row_to_channel(row))?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(channels)
+    }
+
+    /// List soft-deleted channels, most recently deleted first
+    pub fn list_deleted_channels(&self) -> SqliteResult<Vec<Channel>> {
+        let conn = self.conn();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, channel_type, name, enabled, bot_token, app_token, safe_mode, created_at, updated_at, deleted_at
+             FROM external_channels WHERE deleted_at IS NOT NULL ORDER BY deleted_at DESC",
         )?;
 
         let channels = stmt
@@ -188,8 +212,8 @@ impl Database {
         let conn = self.conn();
 
         let mut stmt = conn.prepare(
-            "SELECT id, channel_type, name, enabled, bot_token, app_token, safe_mode, created_at, updated_at
-             FROM external_channels WHERE enabled = 1 ORDER BY channel_type, name",
+            "SELECT id, channel_type, name, enabled, bot_token, app_token, safe_mode, created_at, updated_at, deleted_at
+             FROM external_channels WHERE enabled = 1 AND deleted_at IS NULL ORDER BY channel_type, name",
         )?;
 
         let channels: Vec<Channel> = stmt
@@ -235,7 +259,7 @@ impl Database {
         }
 
         let sql = format!(
-            "UPDATE external_channels SET {} WHERE id = ?{}",
+            "UPDATE external_channels SET {} WHERE id = ?{} AND deleted_at IS NULL",
             updates.join(", "),
             param_idx
         );
@@ -271,7 +295,7 @@ impl Database {
         let now = Utc::now().to_rfc3339();
 
         let rows_affected = conn.execute(
-            "UPDATE external_channels SET enabled = ?1, updated_at = ?2 WHERE id = ?3",
+            "UPDATE external_channels SET enabled = ?1, updated_at = ?2 WHERE id = ?3 AND deleted_at IS NULL",
             rusqlite::params![if enabled { 1 } else { 0 }, &now, id],
         )?;
 
@@ -279,14 +303,46 @@ impl Database {
         Ok(rows_affected > 0)
     }
 
-    /// Delete a channel
+    /// Soft-delete a channel: it is disabled and hidden, but its row, token and
+    /// settings are kept so `restore_channel` can bring it back
     pub fn delete_channel(&self, id: i64) -> SqliteResult<bool> {
         let conn = self.conn();
+        let now = Utc::now().to_rfc3339();
         let rows_affected = conn.execute(
-            "DELETE FROM external_channels WHERE id = ?1",
+            "UPDATE external_channels SET deleted_at = ?1, enabled = 0, updated_at = ?1
+             WHERE id = ?2 AND deleted_at IS NULL",
+            rusqlite::params![&now, id],
+        )?;
+        self.cache.invalidate_channels();
+        Ok(rows_affected > 0)
+    }
+
+    /// Undo a soft delete. The channel comes back disabled.
+    pub fn restore_channel(&self, id: i64) -> SqliteResult<bool> {
+        let conn = self.conn();
+        let now = Utc::now().to_rfc3339();
+        let rows_affected = conn.execute(
+            "UPDATE external_channels SET deleted_at = NULL, updated_at = ?1
+             WHERE id = ?2 AND deleted_at IS NOT NULL",
+            rusqlite::params![&now, id],
+        )?;
+        self.cache.invalidate_channels();
+        Ok(rows_affected > 0)
+    }
+
+    /// Permanently delete a soft-deleted channel along with its settings.
+    /// Live channels must be soft-deleted first.
+    pub fn purge_channel(&self, id: i64) -> SqliteResult<bool> {
+        let conn = self.conn();
+        let rows_affected = conn.execute(
+            "DELETE FROM external_channels WHERE id = ?1 AND deleted_at IS NOT NULL",
             [id],
         )?;
+        drop(conn);
         self.cache.invalidate_channels();
+        if rows_affected > 0 {
+            self.delete_all_channel_settings(id)?;
+        }
         Ok(rows_affected > 0)
     }
 
@@ -295,7 +351,7 @@ impl Database {
         let conn = self.conn();
         let now = Utc::now().to_rfc3339();
         let rows_affected = conn.execute(
-            "UPDATE external_channels SET safe_mode = ?1, updated_at = ?2 WHERE id = ?3",
+            "UPDATE external_channels SET safe_mode = ?1, updated_at = ?2 WHERE id = ?3 AND deleted_at IS NULL",
             rusqlite::params![if safe_mode { 1 } else { 0 }, &now, id],
         )?;
         self.cache.invalidate_channels();
@@ -307,8 +363,8 @@ impl Database {
         let conn = self.conn();
 
         let mut stmt = conn.prepare(
-            "SELECT id, channel_type, name, enabled, bot_token, app_token, safe_mode, created_at, updated_at
-             FROM external_channels WHERE safe_mode = 0 ORDER BY channel_type, name",
+            "SELECT id, channel_type, name, enabled, bot_token, app_token, safe_mode, created_at, updated_at, deleted_at
+             FROM external_channels WHERE safe_mode = 0 AND deleted_at IS NULL ORDER BY channel_type, name",
         )?;
 
         let channels = stmt
@@ -329,9 +385,10 @@ impl Database {
     }
 
     fn row_to_channel(row: &rusqlite::Row) -> rusqlite::Result<Channel> {
-        // Column order: id, channel_type, name, enabled, bot_token, app_token, safe_mode, created_at, updated_at
+        // Column order: id, channel_type, name, enabled, bot_token, app_token, safe_mode, created_at, updated_at, deleted_at
         let created_at_str: String = row.get(7)?;
         let updated_at_str: String = row.get(8)?;
+        let deleted_at_str: Option<String> = row.get(9)?;
 
         Ok(Channel {
             id: row.get(0)?,
@@ -347,6 +404,44 @@ impl Database {
             updated_at: DateTime::parse_from_rfc3339(&updated_at_str)
                 .unwrap()
                 .with_timezone(&Utc),
+            deleted_at: deleted_at_str
+                .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                .map(|dt| dt.with_timezone(&Utc)),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::db::Database;
+
+    #[test]
+    fn test_soft_delete_restore_and_purge() {
+        let db = Database::new(":memory:").expect("in-memory db");
+        let ch = db.create_channel("discord", "main", "token", None).unwrap();
+        db.set_channel_enabled(ch.id, true).unwrap();
+
+        assert!(db.delete_channel(ch.id).unwrap());
+        assert!(!db.delete_channel(ch.id).unwrap());
+        assert!(db.get_channel(ch.id).unwrap().is_none());
+        assert!(db.list_channels().unwrap().is_empty());
+        assert!(db.list_enabled_channels().unwrap().is_empty());
+        let deleted = db.list_deleted_channels().unwrap();
+        assert_eq!(deleted.len(), 1);
+        assert!(deleted[0].deleted_at.is_some());
+
+        // Restored channels keep their token but come back disabled
+        assert!(db.restore_channel(ch.id).unwrap());
+        let restored = db.get_channel(ch.id).unwrap().expect("restored channel");
+        assert_eq!(restored.bot_token, "token");
+        assert!(!restored.enabled);
+        assert!(restored.deleted_at.is_none());
+
+        // Only soft-deleted channels can be purged
+        assert!(!db.purge_channel(ch.id).unwrap());
+        db.delete_channel(ch.id).unwrap();
+        assert!(db.purge_channel(ch.id).unwrap());
+        assert!(db.list_deleted_channels().unwrap().is_empty());
+        assert!(!db.restore_channel(ch.id).unwrap());
+    }
+}
@@ -252,6 +252,59 @@ impl Database {
         Ok(())
     }
 
+    /// Archive the active session for a chat if it has been idle for `ttl_minutes`, so the
+    /// next get_or_create_chat_session starts with a fresh context. The old session and its
+    /// messages are kept. Returns the id of the archived session.
+    pub fn expire_idle_chat_session(
+        &self,
+        channel_type: &str,
+        channel_id: i64,
+        platform_chat_id: &str,
+        ttl_minutes: i64,
+    ) -> SqliteResult<Option<i64>> {
+        let session_key = Self::generate_session_key(channel_type, channel_id, platform_chat_id);
+        let Some(session) = self.get_chat_session_by_key(&session_key)? else {
+            return Ok(None);
+        };
+        if !session.is_idle_for(ttl_minutes, Utc::now()) {
+            return Ok(None);
+        }
+        self.reset_chat_session(session.id)?;
+        Ok(Some(session.id))
+    }
+
+    /// Make an earlier session of a chat the active one again, so the next
+    /// get_or_create_chat_session for that chat continues it. The session currently
+    /// active for the chat (if different) is archived, exactly as a reset would.
+    pub fn resume_chat_session(&self, id: i64) -> SqliteResult<ChatSession> {
+        let Some(session) = self.get_chat_session(id)? else {
+            return Err(rusqlite::Error::QueryReturnedNoRows);
+        };
+        let session_key = Self::generate_session_key(&session.channel_type, session.channel_id, &session.platform_chat_id);
+        if session.is_active && session.session_key == session_key {
+            return Ok(session);
+        }
+
+        let now = Utc::now();
+        let now_str = now.to_rfc3339();
+        let conn = self.conn();
+        let archived_key = format!("{}:archived-{}", session_key, now.timestamp_millis());
+        // Archive and reactivate together, so a failure can't leave the chat without an active session
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
+            "UPDATE chat_sessions SET is_active = 0, session_key = ?1, updated_at = ?2 WHERE session_key = ?3 AND id != ?4",
+            rusqlite::params![&archived_key, &now_str, &session_key, id],
+        )?;
+        tx.execute(
+            "UPDATE chat_sessions SET is_active = 1, session_key = ?1, last_activity_at = ?2, updated_at = ?2 WHERE id = ?3",
+            rusqlite::params![&session_key, &now_str, id],
+        )?;
+        tx.commit()?;
+        drop(conn);
+
+        self.get_chat_session(id)?.ok_or(rusqlite::Error::QueryReturnedNoRows)
+    }
+
     /// Reset a chat session (mark old as inactive, create new)
     pub fn reset_chat_session(&self, id: i64) -> SqliteResult<ChatSession> {
         let conn = self.conn();
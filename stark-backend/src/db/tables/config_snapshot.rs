@@ -0,0 +1,156 @@
+//! Transactional import of configuration snapshots (channels, channel_settings, agent_settings)
+
+use chrono::Utc;
+use rusqlite::Result as SqliteResult;
+use std::collections::HashSet;
+
+use crate::backup::snapshot::{ChannelAction, ImportPlan, ImportReport};
+use crate::models::MIN_CONTEXT_TOKENS;
+use super::super::Database;
+
+impl Database {
+    /// Apply a validated import plan. Either every change is written or none is.
+    pub fn apply_config_import(&self, plan: &ImportPlan) -> SqliteResult<ImportReport> {
+        let conn = self.conn();
+        let tx = conn.unchecked_transaction()?;
+        let now = Utc::now().to_rfc3339();
+        let mut report = ImportReport {
+            warnings: plan.warnings.clone(),
+            ..Default::default()
+        };
+
+        for ch in &plan.channels {
+            let channel_id = match ch.action {
+                ChannelAction::Skip { existing_id } => {
+                    report.channels_skipped += 1;
+                    report.channel_id_map.insert(ch.source_id, existing_id);
+                    continue;
+                }
+                ChannelAction::Create => {
+                    tx.execute(
+                        "INSERT INTO external_channels (channel_type, name, enabled, bot_token, app_token, safe_mode, created_at, updated_at)
+                         VALUES (?1, ?2, ?3, ?4, ?5, 0, ?6, ?6)",
+                        rusqlite::params![
+                            ch.channel_type,
+                            ch.name,
+                            ch.enabled as i32,
+                            ch.bot_token.as_deref().unwrap_or(""),
+                            ch.app_token.clone().flatten(),
+                            &now,
+                        ],
+                    )?;
+                    report.channels_created += 1;
+                    tx.last_insert_rowid()
+                }
+                ChannelAction::Replace { existing_id } => {
+                    tx.execute(
+                        "UPDATE external_channels SET enabled = ?1, updated_at = ?2 WHERE id = ?3",
+                        rusqlite::params![ch.enabled as i32, &now, existing_id],
+                    )?;
+                    if let Some(token) = &ch.bot_token {
+                        tx.execute(
+                            "UPDATE external_channels SET bot_token = ?1 WHERE id = ?2",
+                            rusqlite::params![token, existing_id],
+                        )?;
+                    }
+                    if let Some(token) = &ch.app_token {
+                        tx.execute(
+                            "UPDATE external_channels SET app_token = ?1 WHERE id = ?2",
+                            rusqlite::params![token, existing_id],
+                        )?;
+                    }
+
+                    // Settings not in the snapshot are removed, except redacted ones we keep as-is
+                    let keep: HashSet<&str> = ch
+                        .settings
+                        .iter()
+                        .map(|(k, _)| k.as_str())
+                        .chain(ch.preserved_settings.iter().map(|k| k.as_str()))
+                        .collect();
+                    let current_keys: Vec<String> = {
+                        let mut stmt = tx.prepare("SELECT setting_key FROM channel_settings WHERE channel_id = ?1")?;
+                        let keys = stmt
+                            .query_map([existing_id], |row| row.get(0))?
+                            .collect::<SqliteResult<Vec<String>>>()?;
+                        keys
+                    };
+                    for key in current_keys.iter().filter(|k| !keep.contains(k.as_str())) {
+                        tx.execute(
+                            "DELETE FROM channel_settings WHERE channel_id = ?1 AND setting_key = ?2",
+                            rusqlite::params![existing_id, key],
+                        )?;
+                    }
+                    report.channels_replaced += 1;
+                    existing_id
+                }
+            };
+
+            for (key, value) in &ch.settings {
+                tx.execute(
+                    "INSERT INTO channel_settings (channel_id, setting_key, setting_value, created_at, updated_at)
+                     VALUES (?1, ?2, ?3, datetime('now'), datetime('now'))
+                     ON CONFLICT(channel_id, setting_key) DO UPDATE SET
+                        setting_value = excluded.setting_value,
+                        updated_at = datetime('now')",
+                    rusqlite::params![channel_id, key, value],
+                )?;
+            }
+            report.channel_id_map.insert(ch.source_id, channel_id);
+        }
+
+        for agent in &plan.agent_settings {
+            let max_context_tokens = agent.max_context_tokens.max(MIN_CONTEXT_TOKENS);
+            let existing: Option<i64> = tx
+                .query_row(
+                    "SELECT id FROM agent_settings WHERE endpoint = ?1",
+                    [&agent.endpoint],
+                    |row| row.get(0),
+                )
+                .ok();
+
+            match existing {
+                Some(id) => {
+                    tx.execute(
+                        "UPDATE agent_settings SET model_archetype = ?1, max_response_tokens = ?2, max_context_tokens = ?3, updated_at = ?4 WHERE id = ?5",
+                        rusqlite::params![agent.model_archetype, agent.max_response_tokens, max_context_tokens, &now, id],
+                    )?;
+                    if let Some(secret_key) = &agent.secret_key {
+                        tx.execute(
+                            "UPDATE agent_settings SET secret_key = ?1 WHERE id = ?2",
+                            rusqlite::params![secret_key, id],
+                        )?;
+                    }
+                }
+                None => {
+                    tx.execute(
+                        "INSERT INTO agent_settings (endpoint, model_archetype, max_response_tokens, max_context_tokens, secret_key, enabled, created_at, updated_at)
+                         VALUES (?1, ?2, ?3, ?4, ?5, 0, ?6, ?6)",
+                        rusqlite::params![
+                            agent.endpoint,
+                            agent.model_archetype,
+                            agent.max_response_tokens,
+                            max_context_tokens,
+                            agent.secret_key.clone().flatten(),
+                            &now,
+                        ],
+                    )?;
+                }
+            }
+            report.agent_settings_imported += 1;
+        }
+
+        if let Some(endpoint) = &plan.activate_endpoint {
+            tx.execute(
+                "UPDATE agent_settings SET enabled = CASE WHEN endpoint = ?1 THEN 1 ELSE 0 END, updated_at = ?2",
+                rusqlite::params![endpoint, &now],
+            )?;
+        }
+
+        tx.commit()?;
+
+        self.cache.invalidate_channels();
+        self.cache.invalidate_all_channel_settings();
+        self.cache.invalidate_agent_settings();
+        Ok(report)
+    }
+}
@@ -24,3 +24,7 @@ pub mod x402_payment_limits; // x402_payment_limits (per-call max amounts per to
 pub mod kanban;          // kanban_items (kanban board task management)
 pub mod modules;         // installed_modules (plugin system registry)
 pub mod telemetry;       // execution_spans, rollouts, attempts, resource_versions
+pub mod reminders;       // reminders (set_reminder tool, delivered by the scheduler)
+pub mod scheduled_jobs;  // scheduled_jobs (schedule_message tool, run by the scheduler)
+mod config_snapshot;     // external_channels, channel_settings, agent_settings (config export/import)
+mod user_preferences;    // user_preferences (per-identity preferences, `pref` command)
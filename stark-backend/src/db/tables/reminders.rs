@@ -0,0 +1,180 @@
+//! Reminder database operations (reminders)
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::Result as SqliteResult;
+use serde::{Deserialize, Serialize};
+
+use super::super::Database;
+
+/// A user reminder, delivered by the scheduler when due
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Reminder {
+    pub id: i64,
+    pub channel_id: i64,
+    pub channel_type: String,
+    /// Chat the reminder was set from (fallback delivery target)
+    pub chat_id: Option<String>,
+    pub user_id: String,
+    pub text: String,
+    pub due_at: DateTime<Utc>,
+    /// "dm" (direct message the user) or "channel" (post in the originating chat)
+    pub delivery: String,
+    /// pending, sent, cancelled, failed
+    pub status: String,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub sent_at: Option<DateTime<Utc>>,
+}
+
+/// Request to create a reminder
+#[derive(Debug, Clone)]
+pub struct CreateReminderRequest {
+    pub channel_id: i64,
+    pub channel_type: String,
+    pub chat_id: Option<String>,
+    pub user_id: String,
+    pub text: String,
+    pub due_at: DateTime<Utc>,
+    pub delivery: String,
+}
+
+/// Timestamps are stored with fixed precision so due_at compares correctly as text
+fn to_db_time(t: &DateTime<Utc>) -> String {
+    t.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn parse_db_time(s: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(s)
+        .map(|t| t.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now())
+}
+
+const REMINDER_COLUMNS: &str = "id, channel_id, channel_type, chat_id, user_id, text, due_at, delivery, \
+                                status, last_error, created_at, sent_at";
+
+impl Database {
+    /// Create a pending reminder
+    pub fn create_reminder(&self, request: &CreateReminderRequest) -> SqliteResult<Reminder> {
+        let conn = self.conn();
+        let now = Utc::now();
+
+        conn.execute(
+            "INSERT INTO reminders (channel_id, channel_type, chat_id, user_id, text, due_at, delivery, status, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 'pending', ?8)",
+            rusqlite::params![
+                request.channel_id,
+                &request.channel_type,
+                &request.chat_id,
+                &request.user_id,
+                &request.text,
+                to_db_time(&request.due_at),
+                &request.delivery,
+                to_db_time(&now),
+            ],
+        )?;
+
+        Ok(Reminder {
+            id: conn.last_insert_rowid(),
+            channel_id: request.channel_id,
+            channel_type: request.channel_type.clone(),
+            chat_id: request.chat_id.clone(),
+            user_id: request.user_id.clone(),
+            text: request.text.clone(),
+            due_at: request.due_at,
+            delivery: request.delivery.clone(),
+            status: "pending".to_string(),
+            last_error: None,
+            created_at: now,
+            sent_at: None,
+        })
+    }
+
+    /// List a user's pending reminders on a channel, soonest first
+    pub fn list_pending_reminders_for_user(&self, channel_id: i64, user_id: &str) -> SqliteResult<Vec<Reminder>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM reminders
+             WHERE channel_id = ?1 AND user_id = ?2 AND status = 'pending'
+             ORDER BY due_at ASC",
+            REMINDER_COLUMNS
+        ))?;
+
+        let reminders = stmt
+            .query_map(rusqlite::params![channel_id, user_id], |row| Self::row_to_reminder(row))?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(reminders)
+    }
+
+    /// List pending reminders whose due time has passed
+    pub fn list_due_reminders(&self) -> SqliteResult<Vec<Reminder>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM reminders
+             WHERE status = 'pending' AND due_at <= ?1
+             ORDER BY due_at ASC",
+            REMINDER_COLUMNS
+        ))?;
+
+        let reminders = stmt
+            .query_map([to_db_time(&Utc::now())], |row| Self::row_to_reminder(row))?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(reminders)
+    }
+
+    /// Cancel a pending reminder. Scoped to the owner: returns false if the reminder
+    /// doesn't exist, belongs to someone else, or is no longer pending.
+    pub fn cancel_reminder(&self, id: i64, channel_id: i64, user_id: &str) -> SqliteResult<bool> {
+        let conn = self.conn();
+        let rows = conn.execute(
+            "UPDATE reminders SET status = 'cancelled'
+             WHERE id = ?1 AND channel_id = ?2 AND user_id = ?3 AND status = 'pending'",
+            rusqlite::params![id, channel_id, user_id],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// Mark a reminder as delivered
+    pub fn mark_reminder_sent(&self, id: i64) -> SqliteResult<()> {
+        let conn = self.conn();
+        conn.execute(
+            "UPDATE reminders SET status = 'sent', sent_at = ?1, last_error = NULL WHERE id = ?2",
+            rusqlite::params![to_db_time(&Utc::now()), id],
+        )?;
+        Ok(())
+    }
+
+    /// Mark a reminder as undeliverable
+    pub fn mark_reminder_failed(&self, id: i64, error: &str) -> SqliteResult<()> {
+        let conn = self.conn();
+        conn.execute(
+            "UPDATE reminders SET status = 'failed', last_error = ?1 WHERE id = ?2",
+            rusqlite::params![error, id],
+        )?;
+        Ok(())
+    }
+
+    fn row_to_reminder(row: &rusqlite::Row) -> rusqlite::Result<Reminder> {
+        let due_at: String = row.get(6)?;
+        let created_at: String = row.get(10)?;
+        let sent_at: Option<String> = row.get(11)?;
+
+        Ok(Reminder {
+            id: row.get(0)?,
+            channel_id: row.get(1)?,
+            channel_type: row.get(2)?,
+            chat_id: row.get(3)?,
+            user_id: row.get(4)?,
+            text: row.get(5)?,
+            due_at: parse_db_time(&due_at),
+            delivery: row.get(7)?,
+            status: row.get(8)?,
+            last_error: row.get(9)?,
+            created_at: parse_db_time(&created_at),
+            sent_at: sent_at.as_deref().map(parse_db_time),
+        })
+    }
+}
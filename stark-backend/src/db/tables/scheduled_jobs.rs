@@ -0,0 +1,216 @@
+//! Scheduled message database operations (scheduled_jobs)
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::Result as SqliteResult;
+use serde::{Deserialize, Serialize};
+
+use super::super::Database;
+
+/// A message scheduled from chat, run by the scheduler when due
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledJob {
+    pub id: i64,
+    pub channel_id: i64,
+    pub channel_type: String,
+    /// Chat the job posts to (the one it was scheduled from)
+    pub chat_id: String,
+    /// User who scheduled it; prompt jobs run as this user
+    pub user_id: String,
+    /// "text" (post `content` as-is) or "prompt" (run `content` through the agent, post the reply)
+    pub mode: String,
+    pub content: String,
+    /// Next time the job runs
+    pub run_at: DateTime<Utc>,
+    /// Repeat schedule as given ("every 1d", "daily", or a cron expression); None for one-shot jobs
+    pub recurrence: Option<String>,
+    /// UTC offset for cron recurrences ("+02:00"); None means UTC
+    pub timezone: Option<String>,
+    /// pending, running, sent, cancelled, failed
+    pub status: String,
+    pub run_count: i64,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Request to create a scheduled job
+#[derive(Debug, Clone)]
+pub struct CreateScheduledJobRequest {
+    pub channel_id: i64,
+    pub channel_type: String,
+    pub chat_id: String,
+    pub user_id: String,
+    pub mode: String,
+    pub content: String,
+    pub run_at: DateTime<Utc>,
+    pub recurrence: Option<String>,
+    pub timezone: Option<String>,
+}
+
+/// Timestamps are stored with fixed precision so run_at compares correctly as text
+fn to_db_time(t: &DateTime<Utc>) -> String {
+    t.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn parse_db_time(s: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(s)
+        .map(|t| t.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now())
+}
+
+const SCHEDULED_JOB_COLUMNS: &str = "id, channel_id, channel_type, chat_id, user_id, mode, content, run_at, \
+                                     recurrence, timezone, status, run_count, last_run_at, last_error, created_at";
+
+impl Database {
+    /// Create a pending scheduled job
+    pub fn create_scheduled_job(&self, request: &CreateScheduledJobRequest) -> SqliteResult<ScheduledJob> {
+        let conn = self.conn();
+        let now = Utc::now();
+
+        conn.execute(
+            "INSERT INTO scheduled_jobs (channel_id, channel_type, chat_id, user_id, mode, content, run_at,
+                                         recurrence, timezone, status, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, 'pending', ?10)",
+            rusqlite::params![
+                request.channel_id,
+                &request.channel_type,
+                &request.chat_id,
+                &request.user_id,
+                &request.mode,
+                &request.content,
+                to_db_time(&request.run_at),
+                &request.recurrence,
+                &request.timezone,
+                to_db_time(&now),
+            ],
+        )?;
+
+        Ok(ScheduledJob {
+            id: conn.last_insert_rowid(),
+            channel_id: request.channel_id,
+            channel_type: request.channel_type.clone(),
+            chat_id: request.chat_id.clone(),
+            user_id: request.user_id.clone(),
+            mode: request.mode.clone(),
+            content: request.content.clone(),
+            run_at: request.run_at,
+            recurrence: request.recurrence.clone(),
+            timezone: request.timezone.clone(),
+            status: "pending".to_string(),
+            run_count: 0,
+            last_run_at: None,
+            last_error: None,
+            created_at: now,
+        })
+    }
+
+    /// List a user's pending scheduled jobs on a channel, soonest first
+    pub fn list_pending_scheduled_jobs_for_user(&self, channel_id: i64, user_id: &str) -> SqliteResult<Vec<ScheduledJob>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM scheduled_jobs
+             WHERE channel_id = ?1 AND user_id = ?2 AND status = 'pending'
+             ORDER BY run_at ASC",
+            SCHEDULED_JOB_COLUMNS
+        ))?;
+
+        let jobs = stmt
+            .query_map(rusqlite::params![channel_id, user_id], |row| Self::row_to_scheduled_job(row))?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(jobs)
+    }
+
+    /// List pending scheduled jobs whose run time has passed
+    pub fn list_due_scheduled_jobs(&self) -> SqliteResult<Vec<ScheduledJob>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM scheduled_jobs
+             WHERE status = 'pending' AND run_at <= ?1
+             ORDER BY run_at ASC",
+            SCHEDULED_JOB_COLUMNS
+        ))?;
+
+        let jobs = stmt
+            .query_map([to_db_time(&Utc::now())], |row| Self::row_to_scheduled_job(row))?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(jobs)
+    }
+
+    /// Claim a due job before running it: recurring jobs move on to `next_run_at`,
+    /// one-shot jobs (`None`) are marked running. Returns false if the job is no
+    /// longer pending (e.g. cancelled meanwhile).
+    pub fn claim_scheduled_job(&self, id: i64, next_run_at: Option<DateTime<Utc>>) -> SqliteResult<bool> {
+        let conn = self.conn();
+        let rows = match next_run_at {
+            Some(next) => conn.execute(
+                "UPDATE scheduled_jobs SET run_at = ?1 WHERE id = ?2 AND status = 'pending'",
+                rusqlite::params![to_db_time(&next), id],
+            )?,
+            None => conn.execute(
+                "UPDATE scheduled_jobs SET status = 'running' WHERE id = ?1 AND status = 'pending'",
+                [id],
+            )?,
+        };
+        Ok(rows > 0)
+    }
+
+    /// Record a run. One-shot jobs end up sent or failed; recurring jobs stay pending.
+    pub fn finish_scheduled_job(&self, id: i64, error: Option<&str>) -> SqliteResult<()> {
+        let conn = self.conn();
+        conn.execute(
+            "UPDATE scheduled_jobs
+             SET run_count = run_count + 1, last_run_at = ?1, last_error = ?2,
+                 status = CASE WHEN status = 'running' THEN (CASE WHEN ?2 IS NULL THEN 'sent' ELSE 'failed' END)
+                          ELSE status END
+             WHERE id = ?3",
+            rusqlite::params![to_db_time(&Utc::now()), error, id],
+        )?;
+        Ok(())
+    }
+
+    /// Put one-shot jobs left running by a restart back in the queue. Returns how many.
+    pub fn requeue_interrupted_scheduled_jobs(&self) -> SqliteResult<usize> {
+        let conn = self.conn();
+        conn.execute("UPDATE scheduled_jobs SET status = 'pending' WHERE status = 'running'", [])
+    }
+
+    /// Cancel a pending scheduled job. Scoped to the owner: returns false if the job
+    /// doesn't exist, belongs to someone else, or is no longer pending.
+    pub fn cancel_scheduled_job(&self, id: i64, channel_id: i64, user_id: &str) -> SqliteResult<bool> {
+        let conn = self.conn();
+        let rows = conn.execute(
+            "UPDATE scheduled_jobs SET status = 'cancelled'
+             WHERE id = ?1 AND channel_id = ?2 AND user_id = ?3 AND status = 'pending'",
+            rusqlite::params![id, channel_id, user_id],
+        )?;
+        Ok(rows > 0)
+    }
+
+    fn row_to_scheduled_job(row: &rusqlite::Row) -> rusqlite::Result<ScheduledJob> {
+        let run_at: String = row.get(7)?;
+        let last_run_at: Option<String> = row.get(12)?;
+        let created_at: String = row.get(14)?;
+
+        Ok(ScheduledJob {
+            id: row.get(0)?,
+            channel_id: row.get(1)?,
+            channel_type: row.get(2)?,
+            chat_id: row.get(3)?,
+            user_id: row.get(4)?,
+            mode: row.get(5)?,
+            content: row.get(6)?,
+            run_at: parse_db_time(&run_at),
+            recurrence: row.get(8)?,
+            timezone: row.get(9)?,
+            status: row.get(10)?,
+            run_count: row.get(11)?,
+            last_run_at: last_run_at.as_deref().map(parse_db_time),
+            last_error: row.get(13)?,
+            created_at: parse_db_time(&created_at),
+        })
+    }
+}
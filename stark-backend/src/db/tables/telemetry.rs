@@ -6,6 +6,7 @@ use serde_json::Value;
 
 use super::super::Database;
 use crate::telemetry::resource_version::ResourceBundle;
+use crate::telemetry::reward::RewardFilter;
 use crate::telemetry::span::{Span, SpanStatus, SpanType};
 
 impl Database {
@@ -128,6 +129,55 @@ impl Database {
         )
     }
 
+    /// Reward spans matching the filter, newest first, each with its rollout's channel
+    pub fn query_reward_spans(&self, filter: &RewardFilter) -> SqliteResult<Vec<(Span, Option<i64>)>> {
+        let conn = self.conn();
+
+        let mut sql = String::from(
+            "SELECT s.span_id, s.sequence_id, s.rollout_id, s.session_id, s.attempt_idx, s.parent_span_id,
+                    s.span_type, s.name, s.status, s.started_at, s.completed_at, s.duration_ms, s.attributes, s.error,
+                    r.channel_id
+             FROM execution_spans s
+             LEFT JOIN rollouts r ON r.rollout_id = s.rollout_id
+             WHERE s.span_type = 'reward'"
+        );
+        let mut params: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+
+        if let Some(ref name) = filter.name {
+            sql.push_str(&format!(" AND s.name = ?{}", params.len() + 1));
+            params.push(Box::new(name.clone()));
+        }
+
+        if let Some(ref since) = filter.since {
+            sql.push_str(&format!(" AND s.started_at >= ?{}", params.len() + 1));
+            params.push(Box::new(since.to_rfc3339()));
+        }
+
+        if let Some(ref until) = filter.until {
+            sql.push_str(&format!(" AND s.started_at <= ?{}", params.len() + 1));
+            params.push(Box::new(until.to_rfc3339()));
+        }
+
+        if let Some(channel_id) = filter.channel_id {
+            sql.push_str(&format!(" AND r.channel_id = ?{}", params.len() + 1));
+            params.push(Box::new(channel_id));
+        }
+
+        sql.push_str(" ORDER BY s.started_at DESC");
+
+        if let Some(lim) = filter.limit {
+            sql.push_str(&format!(" LIMIT {}", lim));
+        }
+
+        let mut stmt = conn.prepare(&sql)?;
+        let param_refs: Vec<&dyn rusqlite::types::ToSql> = params.iter().map(|p| p.as_ref()).collect();
+        let rows = stmt
+            .query_map(param_refs.as_slice(), |row| Ok((Self::row_to_span(row)?, row.get::<_, Option<i64>>(14)?)))?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(rows)
+    }
+
     fn row_to_span(row: &rusqlite::Row) -> rusqlite::Result<Span> {
         let span_type_str: String = row.get(6)?;
         let status_str: String = row.get(8)?;
@@ -0,0 +1,45 @@
+//! Per-user preference storage (user_preferences), keyed by identity
+
+use chrono::Utc;
+use rusqlite::Result as SqliteResult;
+use std::collections::BTreeMap;
+
+use super::super::Database;
+
+impl Database {
+    /// All preferences set for an identity, by key
+    pub fn get_user_preferences(&self, identity_id: &str) -> SqliteResult<BTreeMap<String, String>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT pref_key, pref_value FROM user_preferences WHERE identity_id = ?1",
+        )?;
+        let prefs = stmt
+            .query_map([identity_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<SqliteResult<BTreeMap<String, String>>>()?;
+        Ok(prefs)
+    }
+
+    /// Set (insert or replace) one preference
+    pub fn set_user_preference(&self, identity_id: &str, key: &str, value: &str) -> SqliteResult<()> {
+        let conn = self.conn();
+        conn.execute(
+            "INSERT INTO user_preferences (identity_id, pref_key, pref_value, updated_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(identity_id, pref_key) DO UPDATE SET
+                pref_value = excluded.pref_value,
+                updated_at = excluded.updated_at",
+            rusqlite::params![identity_id, key, value, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// Remove one preference; returns whether it was set
+    pub fn delete_user_preference(&self, identity_id: &str, key: &str) -> SqliteResult<bool> {
+        let conn = self.conn();
+        let rows = conn.execute(
+            "DELETE FROM user_preferences WHERE identity_id = ?1 AND pref_key = ?2",
+            rusqlite::params![identity_id, key],
+        )?;
+        Ok(rows > 0)
+    }
+}
@@ -0,0 +1,329 @@
+//! Guided AI endpoint configuration from Discord (admin only)
+//!
+//! `@starkbot configure` posts the current settings with a button. The button
+//! opens a modal (visible only to the admin who clicked it) for the endpoint,
+//! archetype, token limits, and secret key. The submission is validated and
+//! saved with `save_agent_settings`; the reply is ephemeral, so the secret key
+//! never appears in the channel.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serenity::all::{
+    ActionRowComponent, ButtonStyle, ComponentInteraction, Context, CreateActionRow, CreateButton,
+    CreateInputText, CreateInteractionResponse, CreateInteractionResponseMessage, CreateMessage,
+    CreateModal, InputTextStyle, ModalInteraction,
+};
+
+use super::DiscordHooksConfig;
+use crate::ai::ArchetypeId;
+use crate::db::Database;
+use crate::models::{AgentSettings, MIN_CONTEXT_TOKENS};
+
+/// Custom ID of the "Configure" button
+pub const OPEN_BUTTON_ID: &str = "starkbot_agent_config_open";
+/// Custom ID of the settings modal
+pub const MODAL_ID: &str = "starkbot_agent_config_modal";
+
+const FIELD_ENDPOINT: &str = "endpoint";
+const FIELD_ARCHETYPE: &str = "model_archetype";
+const FIELD_MAX_RESPONSE: &str = "max_response_tokens";
+const FIELD_MAX_CONTEXT: &str = "max_context_tokens";
+const FIELD_SECRET_KEY: &str = "secret_key";
+
+/// Upper bound for either token limit (catches typos like an extra zero or two)
+const MAX_TOKENS_LIMIT: i32 = 10_000_000;
+
+/// Validated modal submission
+#[derive(Debug, Clone, PartialEq)]
+pub struct AgentConfigInput {
+    pub endpoint: String,
+    pub model_archetype: ArchetypeId,
+    pub max_response_tokens: i32,
+    pub max_context_tokens: i32,
+    /// `None` keeps the key already stored for this endpoint
+    pub secret_key: Option<String>,
+}
+
+/// Check every field of a submission, collecting all problems at once
+pub fn validate(fields: &HashMap<String, String>) -> Result<AgentConfigInput, Vec<String>> {
+    let get = |key: &str| fields.get(key).map(|v| v.trim()).unwrap_or("");
+    let mut errors = Vec::new();
+
+    let endpoint = get(FIELD_ENDPOINT);
+    match url::Url::parse(endpoint) {
+        Ok(u) if matches!(u.scheme(), "http" | "https") && u.host().is_some() => {}
+        _ => errors.push(format!("Endpoint must be an http(s) URL, got '{}'", endpoint)),
+    }
+
+    let model_archetype = ArchetypeId::from_str(get(FIELD_ARCHETYPE));
+    if model_archetype.is_none() {
+        errors.push(format!(
+            "Unknown archetype '{}'. Must be kimi, llama, claude, openai, or minimax.",
+            get(FIELD_ARCHETYPE)
+        ));
+    }
+
+    let max_response_tokens = parse_tokens(get(FIELD_MAX_RESPONSE), "Max response tokens", 1, &mut errors);
+    let max_context_tokens = parse_tokens(get(FIELD_MAX_CONTEXT), "Max context tokens", MIN_CONTEXT_TOKENS, &mut errors);
+
+    let secret_key = Some(get(FIELD_SECRET_KEY))
+        .filter(|s| !s.is_empty())
+        .map(str::to_string);
+
+    match (model_archetype, max_response_tokens, max_context_tokens) {
+        (Some(model_archetype), Some(max_response_tokens), Some(max_context_tokens)) if errors.is_empty() => {
+            Ok(AgentConfigInput {
+                endpoint: endpoint.to_string(),
+                model_archetype,
+                max_response_tokens,
+                max_context_tokens,
+                secret_key,
+            })
+        }
+        _ => Err(errors),
+    }
+}
+
+fn parse_tokens(value: &str, label: &str, min: i32, errors: &mut Vec<String>) -> Option<i32> {
+    match value.replace(['_', ','], "").parse::<i32>() {
+        Ok(n) if (min..=MAX_TOKENS_LIMIT).contains(&n) => Some(n),
+        _ => {
+            errors.push(format!(
+                "{} must be a whole number between {} and {}, got '{}'",
+                label, min, MAX_TOKENS_LIMIT, value
+            ));
+            None
+        }
+    }
+}
+
+/// Save a validated submission and make it the active endpoint.
+/// A blank secret key keeps the one already stored for the same endpoint.
+pub fn save(db: &Database, input: &AgentConfigInput) -> Result<AgentSettings, String> {
+    let secret_key = match &input.secret_key {
+        Some(key) => Some(key.clone()),
+        None => db
+            .get_agent_settings_by_endpoint(&input.endpoint)
+            .map_err(|e| format!("Database error: {}", e))?
+            .and_then(|s| s.secret_key),
+    };
+
+    db.save_agent_settings(
+        &input.endpoint,
+        input.model_archetype.as_str(),
+        input.max_response_tokens,
+        input.max_context_tokens,
+        secret_key.as_deref(),
+    )
+    .map_err(|e| format!("Database error: {}", e))
+}
+
+fn describe(settings: Option<&AgentSettings>) -> String {
+    match settings {
+        Some(s) => format!(
+            "**Endpoint:** `{}`\n**Archetype:** `{}`\n**Max response tokens:** {}\n**Max context tokens:** {}\n**Secret key:** {}",
+            s.endpoint,
+            s.model_archetype,
+            s.max_response_tokens,
+            s.max_context_tokens,
+            if s.secret_key.is_some() { "set" } else { "not set" }
+        ),
+        None => "No AI endpoint is configured yet.".to_string(),
+    }
+}
+
+/// Message posted for `@starkbot configure`: current settings plus the button that opens the modal
+pub fn prompt_message(db: &Database) -> CreateMessage {
+    let current = db.get_active_agent_settings().ok().flatten();
+    CreateMessage::new()
+        .content(format!(
+            "**AI endpoint configuration**\n\n{}\n\nClick below to change it. \
+             The form is only visible to you, and the secret key is never posted in the channel.",
+            describe(current.as_ref())
+        ))
+        .components(vec![CreateActionRow::Buttons(vec![CreateButton::new(OPEN_BUTTON_ID)
+            .label("Configure AI endpoint")
+            .style(ButtonStyle::Primary)])])
+}
+
+/// The settings form, pre-filled with the active settings (never the secret key)
+fn build_modal(current: Option<&AgentSettings>) -> CreateModal {
+    let defaults = AgentSettings::default();
+    let current = current.unwrap_or(&defaults);
+
+    let input = |style, label: &str, id: &str, value: String, required: bool| {
+        CreateActionRow::InputText(
+            CreateInputText::new(style, label, id)
+                .value(value)
+                .required(required),
+        )
+    };
+
+    CreateModal::new(MODAL_ID, "Configure AI endpoint").components(vec![
+        input(InputTextStyle::Short, "Endpoint URL", FIELD_ENDPOINT, current.endpoint.clone(), true),
+        input(
+            InputTextStyle::Short,
+            "Archetype (kimi, llama, claude, openai, minimax)",
+            FIELD_ARCHETYPE,
+            current.model_archetype.clone(),
+            true,
+        ),
+        input(
+            InputTextStyle::Short,
+            "Max response tokens",
+            FIELD_MAX_RESPONSE,
+            current.max_response_tokens.to_string(),
+            true,
+        ),
+        input(
+            InputTextStyle::Short,
+            "Max context tokens",
+            FIELD_MAX_CONTEXT,
+            current.max_context_tokens.to_string(),
+            true,
+        ),
+        CreateActionRow::InputText(
+            CreateInputText::new(InputTextStyle::Short, "Secret key (blank keeps current)", FIELD_SECRET_KEY)
+                .required(false),
+        ),
+    ])
+}
+
+fn ephemeral(content: impl Into<String>) -> CreateInteractionResponse {
+    CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    )
+}
+
+/// Handle a click on the "Configure" button: re-check admin access and open the modal
+pub async fn handle_button(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+    db: &Arc<Database>,
+    channel_id: i64,
+) -> Result<(), String> {
+    let config = DiscordHooksConfig::from_channel_settings(db, channel_id);
+    let permissions = interaction.member.as_ref().and_then(|m| m.permissions);
+    let roles = interaction.member.as_ref().map(|m| m.roles.as_slice()).unwrap_or_default();
+    let response = if config.is_interaction_admin(
+        &interaction.user.id.to_string(),
+        interaction.guild_id.is_some(),
+        permissions,
+        roles,
+    ) {
+        let current = db.get_active_agent_settings().ok().flatten();
+        CreateInteractionResponse::Modal(build_modal(current.as_ref()))
+    } else {
+        ephemeral("Only admins can change the AI endpoint configuration.")
+    };
+
+    interaction
+        .create_response(&ctx.http, response)
+        .await
+        .map_err(|e| format!("Failed to respond to configure button: {}", e))
+}
+
+/// Handle the submitted modal: validate, save, and reply ephemerally
+pub async fn handle_modal(
+    ctx: &Context,
+    interaction: &ModalInteraction,
+    db: &Arc<Database>,
+    channel_id: i64,
+) -> Result<(), String> {
+    let config = DiscordHooksConfig::from_channel_settings(db, channel_id);
+    let user_id = interaction.user.id.to_string();
+    let permissions = interaction.member.as_ref().and_then(|m| m.permissions);
+    let roles = interaction.member.as_ref().map(|m| m.roles.as_slice()).unwrap_or_default();
+
+    let reply = if !config.is_interaction_admin(&user_id, interaction.guild_id.is_some(), permissions, roles) {
+        "Only admins can change the AI endpoint configuration.".to_string()
+    } else {
+        let fields: HashMap<String, String> = interaction
+            .data
+            .components
+            .iter()
+            .flat_map(|row| row.components.iter())
+            .filter_map(|c| match c {
+                ActionRowComponent::InputText(input) => {
+                    Some((input.custom_id.clone(), input.value.clone().unwrap_or_default()))
+                }
+                _ => None,
+            })
+            .collect();
+
+        match validate(&fields) {
+            Ok(input) => match save(db, &input) {
+                Ok(settings) => {
+                    log::info!(
+                        "Discord hooks: Admin {} updated agent settings: endpoint={}, archetype={}, has_secret_key={}",
+                        user_id,
+                        settings.endpoint,
+                        settings.model_archetype,
+                        settings.secret_key.is_some()
+                    );
+                    format!("✅ AI endpoint updated.\n\n{}", describe(Some(&settings)))
+                }
+                Err(e) => {
+                    log::error!("Discord hooks: Failed to save agent settings: {}", e);
+                    format!("❌ Failed to save settings: {}", e)
+                }
+            },
+            Err(errors) => format!(
+                "❌ Nothing was saved:\n- {}\n\nRun `@starkbot configure` to try again.",
+                errors.join("\n- ")
+            ),
+        }
+    };
+
+    interaction
+        .create_response(&ctx.http, ephemeral(reply))
+        .await
+        .map_err(|e| format!("Failed to respond to configure modal: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    fn valid() -> HashMap<String, String> {
+        fields(&[
+            (FIELD_ENDPOINT, " https://api.example.com/v1/chat/completions "),
+            (FIELD_ARCHETYPE, "Claude"),
+            (FIELD_MAX_RESPONSE, "40,000"),
+            (FIELD_MAX_CONTEXT, "100000"),
+            (FIELD_SECRET_KEY, ""),
+        ])
+    }
+
+    #[test]
+    fn test_validate_accepts_and_normalizes() {
+        let input = validate(&valid()).unwrap();
+        assert_eq!(input.endpoint, "https://api.example.com/v1/chat/completions");
+        assert_eq!(input.model_archetype, ArchetypeId::Claude);
+        assert_eq!(input.max_response_tokens, 40_000);
+        assert_eq!(input.secret_key, None);
+
+        let mut with_key = valid();
+        with_key.insert(FIELD_SECRET_KEY.to_string(), "sk-123".to_string());
+        assert_eq!(validate(&with_key).unwrap().secret_key.as_deref(), Some("sk-123"));
+    }
+
+    #[test]
+    fn test_validate_reports_every_error() {
+        let errors = validate(&fields(&[
+            (FIELD_ENDPOINT, "ftp://example.com"),
+            (FIELD_ARCHETYPE, "gpt"),
+            (FIELD_MAX_RESPONSE, "0"),
+            (FIELD_MAX_CONTEXT, "1000"),
+        ]))
+        .unwrap_err();
+        assert_eq!(errors.len(), 4);
+    }
+}
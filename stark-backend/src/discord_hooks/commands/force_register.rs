@@ -16,14 +16,6 @@ fn extract_user_id(mention: &str) -> Option<String> {
     Some(id.to_string())
 }
 
-/// Validate an Ethereum/Starknet address format
-fn is_valid_address(addr: &str) -> bool {
-    addr.starts_with("0x")
-        && addr.len() >= 42
-        && addr.len() <= 66
-        && addr[2..].chars().all(|c| c.is_ascii_hexdigit())
-}
-
 /// Parse a force_register command, returning (mention, address) if valid
 pub fn parse(text: &str) -> Option<(String, String)> {
     // Expected: "force_register <@USER_ID> 0xADDRESS"
@@ -34,9 +26,7 @@ pub fn parse(text: &str) -> Option<(String, String)> {
     let mention = parts[1];
     let address = parts[2];
     let user_id = extract_user_id(mention)?;
-    if !is_valid_address(address) {
-        return None;
-    }
+    db::validate_address(address).ok()?;
     Some((user_id, address.to_string()))
 }
 
@@ -5,11 +5,14 @@ pub fn execute() -> String {
     "**StarkBot Discord Commands**\n\n\
     **For all users:**\n\
     - `@starkbot register <address>` - Register your public address to receive tips\n\
-    - `@starkbot status` - Check your registration status\n\
+    - `@starkbot status` - Check bot health and your registration status\n\
+    - `@starkbot whoami` - Show what StarkBot has stored about you\n\
+    - `@starkbot limits` - Show your remaining queries and when they reset\n\
     - `@starkbot unregister` - Remove your registered address\n\
     - `@starkbot help` - Show this help message\n\n\
     **Admin only:**\n\
-    - `@starkbot force_register @user <address>` - Register an address for another user\n\n\
+    - `@starkbot force_register @user <address>` - Register an address for another user\n\
+    - `@starkbot configure` - Set the AI endpoint, archetype, token limits, and secret key\n\n\
     **Example:**\n\
     ```\n\
     @starkbot register 0x1234567890123456789012345678901234567890\n\
@@ -5,6 +5,7 @@ mod help;
 mod register;
 mod status;
 mod unregister;
+mod whoami;
 
 use crate::db::Database;
 
@@ -13,12 +14,24 @@ use crate::db::Database;
 pub enum Command {
     /// Register a public address: `register 0x...`
     Register(String),
-    /// Check registration status: `status`
+    /// Agent/channel health and registration status: `status`
     Status,
     /// Show help: `help`
     Help,
     /// Unregister address: `unregister`
     Unregister,
+    /// Show what StarkBot has stored about the user: `whoami`
+    Whoami,
+}
+
+impl Command {
+    /// Commands that read or write tipping profiles, so need the discord_tipping module
+    fn requires_tipping_module(&self) -> bool {
+        match self {
+            Command::Register(_) | Command::Unregister | Command::Whoami => true,
+            Command::Status | Command::Help => false,
+        }
+    }
 }
 
 /// Parse a command from text
@@ -47,7 +60,8 @@ pub fn parse(text: &str) -> Option<Command> {
             }
             result
         }
-        "status" | "whoami" | "me" => Some(Command::Status),
+        "status" => Some(Command::Status),
+        "whoami" | "me" => Some(Command::Whoami),
         "help" | "?" => Some(Command::Help),
         "unregister" | "deregister" | "remove" => Some(Command::Unregister),
         _ => {
@@ -64,7 +78,8 @@ pub fn parse(text: &str) -> Option<Command> {
 /// Execute a command and return the response
 pub async fn execute(cmd: Command, user_id: &str, db: &Database) -> Result<String, String> {
     // Guard: tipping commands require the discord_tipping module to be installed
-    if !matches!(cmd, Command::Help) {
+    // (status checks it itself, since its health overview is useful without tipping)
+    if cmd.requires_tipping_module() {
         if !db.is_module_installed("discord_tipping").unwrap_or(false) {
             return Ok(
                 "The **discord_tipping** module is not installed.\n\n\
@@ -80,6 +95,7 @@ pub async fn execute(cmd: Command, user_id: &str, db: &Database) -> Result<Strin
         Command::Status => status::execute(user_id, db).await,
         Command::Help => Ok(help::execute()),
         Command::Unregister => unregister::execute(user_id, db).await,
+        Command::Whoami => whoami::execute(user_id, db).await,
     }
 }
 
@@ -88,7 +104,9 @@ pub fn permission_denied_message() -> String {
     "You don't have permission to run that command.\n\n\
     **Available commands:**\n\
     - `@starkbot register <address>` - Register your public address for tipping\n\
-    - `@starkbot status` - Check your registration status\n\
+    - `@starkbot status` - Check bot health and your registration status\n\
+    - `@starkbot whoami` - Show what StarkBot has stored about you\n\
+    - `@starkbot limits` - Show your remaining queries and when they reset\n\
     - `@starkbot help` - Show available commands\n\
     - `@starkbot unregister` - Remove your registered address"
         .to_string()
@@ -112,8 +130,20 @@ mod tests {
     #[test]
     fn test_parse_status() {
         assert!(matches!(parse("status"), Some(Command::Status)));
-        assert!(matches!(parse("whoami"), Some(Command::Status)));
-        assert!(matches!(parse("me"), Some(Command::Status)));
+    }
+
+    #[test]
+    fn test_parse_whoami() {
+        assert!(matches!(parse("whoami"), Some(Command::Whoami)));
+        assert!(matches!(parse("WhoAmI"), Some(Command::Whoami)));
+        assert!(matches!(parse("me"), Some(Command::Whoami)));
+    }
+
+    #[tokio::test]
+    async fn test_whoami_requires_tipping_module() {
+        let db = Database::new(":memory:").unwrap();
+        let response = execute(Command::Whoami, "123", &db).await.unwrap();
+        assert!(response.contains("module is not installed"));
     }
 
     #[test]
@@ -3,32 +3,16 @@
 use crate::db::Database;
 use crate::discord_hooks::db;
 
-/// Validate an Ethereum/Starknet address format
-fn is_valid_address(addr: &str) -> bool {
-    // Must start with 0x
-    if !addr.starts_with("0x") {
-        return false;
-    }
-
-    // Must be at least 42 chars (Ethereum) and at most 66 chars (Starknet)
-    if addr.len() < 42 || addr.len() > 66 {
-        return false;
-    }
-
-    // All characters after 0x must be hex digits
-    addr[2..].chars().all(|c| c.is_ascii_hexdigit())
-}
-
 /// Execute the register command
 pub async fn execute(user_id: &str, address: &str, database: &Database) -> Result<String, String> {
     // Validate address format
-    if !is_valid_address(address) {
-        return Ok(
-            "Invalid address format. Please provide a valid Ethereum or Starknet address \
-            starting with `0x`.\n\n\
-            Example: `@starkbot register 0x1234...abcd`"
-                .to_string(),
-        );
+    if let Err(reason) = db::validate_address(address) {
+        return Ok(format!(
+            "Invalid address: {}\n\n\
+            Please provide a valid Ethereum or Starknet address.\n\
+            Example: `@starkbot register 0x1234...abcd`",
+            reason
+        ));
     }
 
     // Check if address is already registered to someone else
@@ -59,7 +43,11 @@ pub async fn execute(user_id: &str, address: &str, database: &Database) -> Resul
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use crate::discord_hooks::db::validate_address;
+
+    fn is_valid_address(addr: &str) -> bool {
+        validate_address(addr).is_ok()
+    }
 
     #[test]
     fn test_valid_eth_address() {
@@ -1,42 +1,88 @@
-//! Status command - shows user's registration status
+//! Status command - one-shot overview of agent health, channels, and the user's registration
 
+use crate::controllers::health::check_ai_endpoint;
 use crate::db::Database;
-use crate::discord_hooks::db;
+use crate::discord_hooks::db::{self, DiscordUserProfile};
 
 /// Execute the status command
 pub async fn execute(user_id: &str, database: &Database) -> Result<String, String> {
-    let profile = match db::get_profile(database, user_id).await? {
-        Some(p) => p,
-        None => {
-            return Ok(
-                "**Your StarkBot Profile**\n\n\
-                Status: Not registered\n\n\
-                Use `@starkbot register <your-address>` to register your public address for tipping."
-                    .to_string(),
-            );
-        }
+    let configured = database.get_active_agent_settings().ok().flatten().is_some();
+    let reachability = if configured {
+        Some(check_ai_endpoint(database).await)
+    } else {
+        None
+    };
+    let channels = database.list_enabled_channels().ok().map(|c| c.len());
+
+    // Registration needs the tipping module; the rest of the overview doesn't
+    let profile = if database.is_module_installed("discord_tipping").unwrap_or(false) {
+        Some(db::get_profile(database, user_id).await?)
+    } else {
+        None
     };
 
-    if let Some(addr) = profile.public_address {
-        let registered_at = profile
-            .registered_at
-            .as_deref()
-            .unwrap_or("Unknown");
+    Ok(format_status(reachability, channels, profile.as_ref().map(Option::as_ref)))
+}
+
+/// `reachability`: None when no agent is configured. `profile`: None when the
+/// tipping module isn't installed, Some(None) when the user has no profile.
+fn format_status(
+    reachability: Option<Result<(), String>>,
+    enabled_channels: Option<usize>,
+    profile: Option<Option<&DiscordUserProfile>>,
+) -> String {
+    let agent = match reachability {
+        None => "Not configured".to_string(),
+        Some(Ok(())) => "Online".to_string(),
+        Some(Err(reason)) => format!("Configured but unreachable ({})", reason),
+    };
+    let channels = match enabled_channels {
+        Some(n) => format!("{} enabled", n),
+        None => "Unknown".to_string(),
+    };
 
-        Ok(format!(
-            "**Your StarkBot Profile**\n\n\
-            **Status:** Registered\n\
+    let registration = match profile {
+        None => "Tipping is not enabled on this server.".to_string(),
+        Some(Some(DiscordUserProfile { public_address: Some(address), registered_at, .. })) => format!(
+            "**Status:** Registered\n\
             **Address:** `{}`\n\
-            **Registered:** {}\n\n\
-            You can receive tips from other users!",
-            addr, registered_at
-        ))
-    } else {
-        Ok(
-            "**Your StarkBot Profile**\n\n\
-            **Status:** Not registered\n\n\
+            **Registered:** {}",
+            address,
+            registered_at.as_deref().unwrap_or("Unknown")
+        ),
+        Some(_) => "**Status:** Not registered\n\
             Use `@starkbot register <your-address>` to register your public address for tipping."
-                .to_string(),
-        )
+            .to_string(),
+    };
+
+    format!(
+        "**StarkBot Status**\n\n\
+        **Agent:** {}\n\
+        **Channels:** {}\n\n\
+        **Your Profile**\n\
+        {}",
+        agent, channels, registration
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_status() {
+        let text = format_status(Some(Ok(())), Some(3), Some(None));
+        assert!(text.contains("**Agent:** Online"));
+        assert!(text.contains("**Channels:** 3 enabled"));
+        assert!(text.contains("Not registered"));
+
+        let text = format_status(Some(Err("AI endpoint timed out".to_string())), None, None);
+        assert!(text.contains("Configured but unreachable (AI endpoint timed out)"));
+        assert!(text.contains("**Channels:** Unknown"));
+        assert!(text.contains("Tipping is not enabled"));
+
+        let text = format_status(None, Some(0), None);
+        assert!(text.contains("**Agent:** Not configured"));
+        assert!(text.len() < 2000);
     }
 }
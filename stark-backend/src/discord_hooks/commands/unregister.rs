@@ -13,12 +13,16 @@ pub async fn execute(user_id: &str, database: &Database) -> Result<String, Strin
         }
     };
 
-    if profile.public_address.is_none() {
+    let Some(address) = profile.public_address else {
         return Ok("You don't have a registered address to remove.".to_string());
-    }
+    };
 
-    // Unregister the address
+    // Unregister the address (the profile itself is kept)
     db::unregister_address(database, user_id).await?;
 
-    Ok("Your address has been unregistered. You will no longer receive tips.".to_string())
+    Ok(format!(
+        "Your address `{}` has been unregistered. You will no longer receive tips.\n\n\
+        Registered the wrong wallet? Use `@starkbot register <your-address>` to add the right one.",
+        address
+    ))
 }
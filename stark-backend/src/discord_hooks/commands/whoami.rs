@@ -0,0 +1,44 @@
+//! Whoami command - shows everything StarkBot has stored about the user
+
+use crate::db::Database;
+use crate::discord_hooks::db::{self, DiscordUserProfile};
+
+/// Execute the whoami command
+pub async fn execute(user_id: &str, database: &Database) -> Result<String, String> {
+    let profile = db::get_profile(database, user_id).await?;
+    Ok(format_profile(user_id, profile.as_ref()))
+}
+
+fn format_profile(user_id: &str, profile: Option<&DiscordUserProfile>) -> String {
+    let Some(profile) = profile else {
+        return format!(
+            "**What StarkBot knows about you**\n\n\
+            **Discord ID:** `{}`\n\
+            **Username:** Unknown\n\
+            **Address:** not registered\n\n\
+            StarkBot has no profile stored for you yet.",
+            user_id
+        );
+    };
+
+    let username = profile.discord_username.as_deref().unwrap_or("Unknown");
+    let address = profile
+        .public_address
+        .as_deref()
+        .map(|a| format!("`{}`", a))
+        .unwrap_or_else(|| "not registered".to_string());
+    let registered_at = match (&profile.public_address, profile.registered_at.as_deref()) {
+        (Some(_), Some(at)) => at,
+        (Some(_), None) => "Unknown",
+        (None, _) => "-",
+    };
+
+    format!(
+        "**What StarkBot knows about you**\n\n\
+        **Discord ID:** `{}`\n\
+        **Username:** {}\n\
+        **Address:** {}\n\
+        **Registered:** {}",
+        profile.discord_user_id, username, address, registered_at
+    )
+}
@@ -3,57 +3,78 @@
 use std::collections::HashSet;
 use std::sync::Arc;
 
+use super::MentionSanitization;
 use crate::db::Database;
 use crate::models::ChannelSettingKey;
-use serenity::all::{Context, Message, Permissions};
+use serenity::all::{Context, Message, Permissions, RoleId};
 
 /// Configuration for the Discord hooks module
 #[derive(Debug, Clone)]
 pub struct DiscordHooksConfig {
     /// Discord user IDs that have admin access (full agentic commands)
-    /// If empty, falls back to Discord's built-in Administrator permission
+    /// If both this and `admin_role_ids` are empty, falls back to Discord's built-in Administrator permission
     admin_user_ids: HashSet<String>,
+    /// Discord role IDs whose members have admin access, in addition to `admin_user_ids`
+    admin_role_ids: HashSet<String>,
     /// Whether to require @mention in server channels (default: true)
     pub require_mention_in_servers: bool,
     /// Whether to allow DMs without @mention (default: true)
     pub allow_dm_without_mention: bool,
+    /// How mass/role/channel mentions in forwarded text are handled (default: neutralize)
+    pub mention_sanitization: MentionSanitization,
+}
+
+/// Parse a comma-separated list of Discord IDs
+fn parse_id_list(raw: &str) -> HashSet<String> {
+    raw.split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
 }
 
 impl DiscordHooksConfig {
     /// Create a new config from channel settings in the database
     ///
-    /// Reads the discord_admin_user_ids setting for the given channel
+    /// Reads the discord_admin_user_ids and discord_admin_role_ids settings for the given channel
     pub fn from_channel_settings(db: &Arc<Database>, channel_id: i64) -> Self {
-        let admin_ids: HashSet<String> = db
-            .get_channel_setting(channel_id, ChannelSettingKey::DiscordAdminUserIds.as_ref())
-            .ok()
-            .flatten()
-            .map(|ids| {
-                ids.split(',')
-                    .map(|s| s.trim().to_string())
-                    .filter(|s| !s.is_empty())
-                    .collect()
-            })
-            .unwrap_or_default();
+        let id_setting = |key: ChannelSettingKey| {
+            db.get_channel_setting(channel_id, key.as_ref())
+                .ok()
+                .flatten()
+                .map(|ids| parse_id_list(&ids))
+                .unwrap_or_default()
+        };
+        let admin_ids = id_setting(ChannelSettingKey::DiscordAdminUserIds);
+        let admin_role_ids = id_setting(ChannelSettingKey::DiscordAdminRoleIds);
 
-        if admin_ids.is_empty() {
+        if admin_ids.is_empty() && admin_role_ids.is_empty() {
             log::info!(
-                "Discord hooks: No admin user IDs configured for channel {}. \
+                "Discord hooks: No admin user or role IDs configured for channel {}. \
                 Configure in channel settings to enable admin commands.",
                 channel_id
             );
         } else {
             log::info!(
-                "Discord hooks: Configured {} admin user ID(s) for channel {}",
+                "Discord hooks: Configured {} admin user ID(s) and {} admin role ID(s) for channel {}",
                 admin_ids.len(),
+                admin_role_ids.len(),
                 channel_id
             );
         }
 
+        let mention_sanitization = db
+            .get_channel_setting(channel_id, ChannelSettingKey::DiscordMentionSanitization.as_ref())
+            .ok()
+            .flatten()
+            .map(|v| MentionSanitization::from_setting(&v))
+            .unwrap_or_default();
+
         Self {
             admin_user_ids: admin_ids,
+            admin_role_ids,
             require_mention_in_servers: true,
             allow_dm_without_mention: true,
+            mention_sanitization,
         }
     }
 
@@ -61,31 +82,31 @@ impl DiscordHooksConfig {
     ///
     /// Reads:
     /// - `DISCORD_ADMIN_USER_IDS`: Comma-separated list of Discord user IDs
+    /// - `DISCORD_ADMIN_ROLE_IDS`: Comma-separated list of Discord role IDs
     #[allow(dead_code)]
     pub fn from_env() -> Self {
-        let admin_ids: HashSet<String> = std::env::var("DISCORD_ADMIN_USER_IDS")
-            .unwrap_or_default()
-            .split(',')
-            .map(|s| s.trim().to_string())
-            .filter(|s| !s.is_empty())
-            .collect();
-
-        if admin_ids.is_empty() {
+        let admin_ids = parse_id_list(&std::env::var("DISCORD_ADMIN_USER_IDS").unwrap_or_default());
+        let admin_role_ids = parse_id_list(&std::env::var("DISCORD_ADMIN_ROLE_IDS").unwrap_or_default());
+
+        if admin_ids.is_empty() && admin_role_ids.is_empty() {
             log::warn!(
-                "Discord hooks: No admin user IDs configured. \
-                Set DISCORD_ADMIN_USER_IDS env var to enable admin commands."
+                "Discord hooks: No admin user or role IDs configured. \
+                Set DISCORD_ADMIN_USER_IDS or DISCORD_ADMIN_ROLE_IDS env var to enable admin commands."
             );
         } else {
             log::info!(
-                "Discord hooks: Configured {} admin user ID(s)",
-                admin_ids.len()
+                "Discord hooks: Configured {} admin user ID(s) and {} admin role ID(s)",
+                admin_ids.len(),
+                admin_role_ids.len()
             );
         }
 
         Self {
             admin_user_ids: admin_ids,
+            admin_role_ids,
             require_mention_in_servers: true,
             allow_dm_without_mention: true,
+            mention_sanitization: MentionSanitization::default(),
         }
     }
 
@@ -93,8 +114,10 @@ impl DiscordHooksConfig {
     pub fn empty() -> Self {
         Self {
             admin_user_ids: HashSet::new(),
+            admin_role_ids: HashSet::new(),
             require_mention_in_servers: true,
             allow_dm_without_mention: true,
+            mention_sanitization: MentionSanitization::default(),
         }
     }
 
@@ -102,8 +125,27 @@ impl DiscordHooksConfig {
     pub fn with_admins(admin_ids: Vec<String>) -> Self {
         Self {
             admin_user_ids: admin_ids.into_iter().collect(),
+            admin_role_ids: HashSet::new(),
             require_mention_in_servers: true,
             allow_dm_without_mention: true,
+            mention_sanitization: MentionSanitization::default(),
+        }
+    }
+
+    /// Also treat members of these roles as admins
+    pub fn with_admin_roles(mut self, role_ids: Vec<String>) -> Self {
+        self.admin_role_ids = role_ids.into_iter().collect();
+        self
+    }
+
+    /// Whether a message must @mention (or reply to) the bot to be handled.
+    /// DMs (no guild) follow `allow_dm_without_mention`; server channels follow
+    /// `require_mention_in_servers`.
+    pub fn requires_mention(&self, in_dm: bool) -> bool {
+        if in_dm {
+            !self.allow_dm_without_mention
+        } else {
+            self.require_mention_in_servers
         }
     }
 
@@ -112,18 +154,70 @@ impl DiscordHooksConfig {
         self.admin_user_ids.contains(user_id)
     }
 
-    /// Check if a user is admin - if explicit admin_user_ids are configured,
-    /// only those users are admins. Otherwise falls back to Discord Administrator permission.
+    /// Check if any of a member's roles is an admin role
+    pub fn is_admin_by_roles(&self, roles: &[RoleId]) -> bool {
+        roles.iter().any(|role| self.admin_role_ids.contains(&role.to_string()))
+    }
+
+    /// Check if a user is admin - if explicit admin user or role IDs are configured,
+    /// only users on the list or holding one of the roles are admins. Otherwise falls
+    /// back to Discord Administrator permission.
     pub async fn is_admin(&self, user_id: &str, msg: &Message, ctx: &Context) -> bool {
-        // If explicit admin user IDs are configured, only use that list
-        if !self.admin_user_ids.is_empty() {
-            return self.admin_user_ids.contains(user_id);
+        // If explicit admins are configured, only use those lists
+        if self.has_explicit_admins() {
+            if self.admin_user_ids.contains(user_id) {
+                return true;
+            }
+            if self.admin_role_ids.is_empty() {
+                return false;
+            }
+            return self.has_admin_role(msg, ctx).await;
         }
 
         // Fallback: no explicit admins configured, use Discord Administrator permission
         Self::has_discord_admin_permission(msg, ctx).await
     }
 
+    /// Check the message author's guild roles against `admin_role_ids`. Guild messages
+    /// carry the author's roles in `msg.member`; if it's missing the member is fetched.
+    /// DMs have no roles, so only the user ID list can grant admin there.
+    async fn has_admin_role(&self, msg: &Message, ctx: &Context) -> bool {
+        let Some(guild_id) = msg.guild_id else {
+            log::debug!("Discord hooks: DM from {} has no roles to check", msg.author.name);
+            return false;
+        };
+
+        if let Some(member) = &msg.member {
+            return self.is_admin_by_roles(&member.roles);
+        }
+        match guild_id.member(&ctx.http, msg.author.id).await {
+            Ok(member) => self.is_admin_by_roles(&member.roles),
+            Err(e) => {
+                log::warn!("Discord hooks: Failed to get member {} for role check: {}", msg.author.name, e);
+                false
+            }
+        }
+    }
+
+    /// Admin check for component/modal interactions. Discord resolves the member's
+    /// permissions and roles in the interaction payload, so no extra API calls are needed.
+    /// `permissions` is `None` outside a guild (DMs), which is treated as admin like messages are.
+    pub fn is_interaction_admin(
+        &self,
+        user_id: &str,
+        in_guild: bool,
+        permissions: Option<Permissions>,
+        roles: &[RoleId],
+    ) -> bool {
+        if self.has_explicit_admins() {
+            return self.admin_user_ids.contains(user_id) || self.is_admin_by_roles(roles);
+        }
+        if !in_guild {
+            return true;
+        }
+        permissions.map(|p| p.administrator()).unwrap_or(false)
+    }
+
     /// Check if the message author has Discord Administrator permission
     pub async fn has_discord_admin_permission(msg: &Message, ctx: &Context) -> bool {
         // DMs don't have guild permissions - treat as admin for convenience
@@ -195,14 +289,14 @@ impl DiscordHooksConfig {
         }
     }
 
-    /// Get the number of configured admins
+    /// Get the number of configured admin users (admin roles not included)
     pub fn admin_count(&self) -> usize {
         self.admin_user_ids.len()
     }
 
-    /// Check if any explicit admins are configured
+    /// Check if any explicit admin users or roles are configured
     pub fn has_explicit_admins(&self) -> bool {
-        !self.admin_user_ids.is_empty()
+        !self.admin_user_ids.is_empty() || !self.admin_role_ids.is_empty()
     }
 }
 
@@ -237,4 +331,48 @@ mod tests {
         assert_eq!(config.admin_count(), 2);
         assert!(config.has_explicit_admins());
     }
+
+    #[test]
+    fn test_requires_mention_dm_vs_guild() {
+        let mut config = DiscordHooksConfig::empty();
+        assert!(!config.requires_mention(true));
+        assert!(config.requires_mention(false));
+
+        config.allow_dm_without_mention = false;
+        assert!(config.requires_mention(true));
+
+        config.require_mention_in_servers = false;
+        assert!(!config.requires_mention(false));
+    }
+
+    #[test]
+    fn test_interaction_admin() {
+        let open = DiscordHooksConfig::empty();
+        assert!(open.is_interaction_admin("1", true, Some(Permissions::ADMINISTRATOR), &[]));
+        assert!(!open.is_interaction_admin("1", true, Some(Permissions::SEND_MESSAGES), &[]));
+        assert!(!open.is_interaction_admin("1", true, None, &[]));
+        assert!(open.is_interaction_admin("1", false, None, &[]));
+
+        // Explicit admin list wins over guild permissions
+        let explicit = DiscordHooksConfig::with_admins(vec!["42".to_string()]);
+        assert!(explicit.is_interaction_admin("42", true, None, &[]));
+        assert!(!explicit.is_interaction_admin("1", true, Some(Permissions::ADMINISTRATOR), &[]));
+    }
+
+    #[test]
+    fn test_admin_roles() {
+        let moderators = RoleId::new(555);
+        let config = DiscordHooksConfig::empty().with_admin_roles(vec!["555".to_string()]);
+        assert!(config.has_explicit_admins());
+        assert!(config.is_admin_by_roles(&[RoleId::new(1), moderators]));
+        assert!(!config.is_admin_by_roles(&[RoleId::new(1)]));
+
+        // Roles and user IDs both grant admin; a role list alone disables the permission fallback
+        let both = DiscordHooksConfig::with_admins(vec!["42".to_string()]).with_admin_roles(vec!["555".to_string()]);
+        assert!(both.is_interaction_admin("42", true, None, &[]));
+        assert!(both.is_interaction_admin("7", true, None, &[moderators]));
+        assert!(!config.is_interaction_admin("7", true, Some(Permissions::ADMINISTRATOR), &[]));
+        // DMs carry no roles
+        assert!(!config.is_interaction_admin("7", false, None, &[]));
+    }
 }
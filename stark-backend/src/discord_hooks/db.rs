@@ -36,6 +36,18 @@ pub async fn get_profile(
     client.get_profile(discord_user_id).await
 }
 
+/// Whether a Discord user has registered an address, and when — without the address itself
+pub async fn registration_status(
+    db: &crate::db::Database,
+    discord_user_id: &str,
+) -> Result<(bool, Option<String>), String> {
+    let profile = get_profile(db, discord_user_id).await?;
+    Ok(match profile {
+        Some(p) if p.public_address.is_some() => (true, p.registered_at),
+        _ => (false, None),
+    })
+}
+
 /// Get a Discord user profile by public address
 pub async fn get_profile_by_address(
     _db: &crate::db::Database,
@@ -45,6 +57,15 @@ pub async fn get_profile_by_address(
     client.get_profile_by_address(address).await
 }
 
+/// Get every registered Discord user profile for a public address (reverse lookup)
+pub async fn list_profiles_by_address(
+    _db: &crate::db::Database,
+    address: &str,
+) -> Result<Vec<DiscordUserProfile>, String> {
+    let client = make_client();
+    client.list_profiles_by_address(address).await
+}
+
 /// Register a public address for a Discord user
 pub async fn register_address(
     _db: &crate::db::Database,
@@ -80,6 +101,32 @@ pub async fn list_all_profiles(
     client.list_all_profiles().await
 }
 
+/// Starknet field prime, as 64 lowercase hex digits. Addresses are field elements, so
+/// a 64-digit address must be below it.
+const STARKNET_FIELD_PRIME: &str = "0800000000000011000000000000000000000000000000000000000000000001";
+
+/// Check that `addr` is a registrable public address: `0x` followed by 40 hex digits
+/// (Ethereum) or up to 64 (Starknet, leading zeros optional) that fit in the Starknet field.
+/// The error says what's wrong, for showing to the user.
+pub fn validate_address(addr: &str) -> Result<(), String> {
+    let Some(hex) = addr.strip_prefix("0x") else {
+        return Err("Addresses must start with `0x`.".to_string());
+    };
+    if let Some(c) = hex.chars().find(|c| !c.is_ascii_hexdigit()) {
+        return Err(format!("`{}` is not a hex character (0-9, a-f).", c));
+    }
+    if hex.len() < 40 || hex.len() > 64 {
+        return Err(format!(
+            "Addresses have 40 (Ethereum) to 64 (Starknet) hex digits after `0x`; this one has {}.",
+            hex.len()
+        ));
+    }
+    if hex.len() == 64 && hex.to_ascii_lowercase().as_str() >= STARKNET_FIELD_PRIME {
+        return Err("This value is too large to be a Starknet address.".to_string());
+    }
+    Ok(())
+}
+
 /// Clear all discord user registrations (for restore)
 pub fn clear_registrations_for_restore(_db: &crate::db::Database) -> Result<usize, String> {
     // This is handled by the backup/restore endpoint now
@@ -88,21 +135,51 @@ pub fn clear_registrations_for_restore(_db: &crate::db::Database) -> Result<usiz
 
 #[cfg(test)]
 mod tests {
-    fn is_valid_address(addr: &str) -> bool {
-        addr.starts_with("0x")
-            && addr.len() >= 42
-            && addr.len() <= 66
-            && addr[2..].chars().all(|c| c.is_ascii_hexdigit())
-    }
+    use super::validate_address;
 
     #[test]
-    fn test_address_validation() {
-        assert!(is_valid_address("0x1234567890123456789012345678901234567890"));
-        assert!(is_valid_address(
+    fn test_valid_addresses() {
+        assert!(validate_address("0x1234567890123456789012345678901234567890").is_ok());
+        assert!(validate_address("0xAbCdEf7890123456789012345678901234567890").is_ok());
+        assert!(validate_address(
             "0x0123456789012345678901234567890123456789012345678901234567890123"
-        ));
-        assert!(!is_valid_address("0x123"));
-        assert!(!is_valid_address("1234567890123456789012345678901234567890"));
-        assert!(!is_valid_address("0xGGGG567890123456789012345678901234567890"));
+        )
+        .is_ok());
+        // Starknet addresses are often shown without their leading zero
+        assert!(validate_address(
+            "0x49d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7"
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_missing_prefix() {
+        let err = validate_address("1234567890123456789012345678901234567890").unwrap_err();
+        assert!(err.contains("0x"));
+    }
+
+    #[test]
+    fn test_wrong_length() {
+        assert!(validate_address("0x123").unwrap_err().contains("has 3"));
+        assert!(validate_address(&format!("0x{}", "1".repeat(65))).is_err());
+    }
+
+    #[test]
+    fn test_non_hex_characters() {
+        let err = validate_address("0xGGGG567890123456789012345678901234567890").unwrap_err();
+        assert!(err.contains("`G`"));
+    }
+
+    #[test]
+    fn test_above_starknet_field() {
+        assert!(validate_address(&format!("0x{}", "f".repeat(64))).is_err());
+        assert!(validate_address(
+            "0x0800000000000011000000000000000000000000000000000000000000000001"
+        )
+        .is_err());
+        assert!(validate_address(
+            "0x0800000000000011000000000000000000000000000000000000000000000000"
+        )
+        .is_ok());
     }
 }
@@ -5,6 +5,7 @@
 //! - Limited command handling for regular users (register, status, help)
 //! - Discord user profile management with public address registration
 //! - Tool for resolving Discord mentions to registered public addresses
+//! - Guided AI endpoint configuration for admins (`configure`)
 //!
 //! ## Admin Flow
 //!
@@ -12,12 +13,16 @@
 //! (no safe mode), unless it matches a short-circuit keyword like "love"
 //! or "register".
 
+pub mod agent_config;
 pub mod commands;
 pub mod config;
 pub mod db;
 pub mod tools;
 
+use crate::channels::util;
+use once_cell::sync::Lazy;
 use rand::seq::SliceRandom;
+use regex::Regex;
 use serenity::all::{Context, Message, UserId};
 
 pub use config::DiscordHooksConfig;
@@ -32,6 +37,8 @@ pub struct ProcessResult {
     pub response: Option<String>,
     /// Request to forward to the agent (if admin command)
     pub forward_to_agent: Option<ForwardRequest>,
+    /// Post the guided AI endpoint configuration prompt (admin `configure` command)
+    pub agent_config_prompt: bool,
 }
 
 impl ProcessResult {
@@ -41,6 +48,7 @@ impl ProcessResult {
             handled: false,
             response: None,
             forward_to_agent: None,
+            agent_config_prompt: false,
         }
     }
 
@@ -50,6 +58,7 @@ impl ProcessResult {
             handled: true,
             response: Some(response),
             forward_to_agent: None,
+            agent_config_prompt: false,
         }
     }
 
@@ -59,6 +68,17 @@ impl ProcessResult {
             handled: true,
             response: None,
             forward_to_agent: Some(request),
+            agent_config_prompt: false,
+        }
+    }
+
+    /// Message should be answered with the AI endpoint configuration prompt
+    pub fn agent_config_prompt() -> Self {
+        Self {
+            handled: true,
+            response: None,
+            forward_to_agent: None,
+            agent_config_prompt: true,
         }
     }
 }
@@ -101,7 +121,7 @@ pub fn is_bot_mentioned(msg: &Message, bot_id: UserId) -> bool {
     if in_content && !in_mentions {
         log::warn!(
             "Discord hooks: Bot mention found in content but NOT in mentions array! content='{}', mentions={:?}",
-            if msg.content.len() > 100 { format!("{}...", &msg.content[..100]) } else { msg.content.clone() },
+            util::log_preview(&msg.content),
             msg.mentions.iter().map(|u| u.id.to_string()).collect::<Vec<_>>()
         );
     }
@@ -122,6 +142,70 @@ pub fn extract_command_text(content: &str, bot_id: UserId) -> String {
         .to_string()
 }
 
+/// How `@everyone`/`@here`, role mentions, and channel mentions in forwarded text are handled.
+/// User mentions (`<@ID>`) are always preserved — `discord_resolve_user` needs them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MentionSanitization {
+    /// Pass text through unchanged
+    Off,
+    /// Rewrite mentions so they stay readable but can't ping if the agent echoes them
+    #[default]
+    Neutralize,
+    /// Remove the mentions entirely
+    Strip,
+}
+
+impl MentionSanitization {
+    /// Parse the channel setting value (unknown values fall back to the default)
+    pub fn from_setting(value: &str) -> Self {
+        match value.trim().to_lowercase().as_str() {
+            "off" | "none" => Self::Off,
+            "strip" | "remove" => Self::Strip,
+            _ => Self::Neutralize,
+        }
+    }
+}
+
+static MASS_MENTION_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"@(everyone|here)\b").unwrap());
+static ROLE_MENTION_PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r"<@&(\d+)>").unwrap());
+static CHANNEL_MENTION_PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r"<#(\d+)>").unwrap());
+/// A run of strippable mentions plus the spaces or tabs around it
+static STRIPPED_MENTIONS_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    let mention = r"(?:@(?:everyone|here)\b|<@&\d+>|<#\d+>)";
+    Regex::new(&format!(r"[ \t]*{m}(?:[ \t]*{m})*[ \t]*", m = mention)).unwrap()
+});
+
+/// Neutralize or strip mass, role, and channel mentions in text forwarded to the agent
+pub fn sanitize_mentions(text: &str, mode: MentionSanitization) -> String {
+    match mode {
+        MentionSanitization::Off => text.to_string(),
+        MentionSanitization::Neutralize => {
+            // Zero-width space after '@' keeps the word readable but breaks the ping
+            let text = MASS_MENTION_PATTERN.replace_all(text, "@\u{200B}$1");
+            let text = ROLE_MENTION_PATTERN.replace_all(&text, "@role:$1");
+            CHANNEL_MENTION_PATTERN.replace_all(&text, "#channel:$1").to_string()
+        }
+        MentionSanitization::Strip => {
+            // Only whitespace next to a removed mention is touched: between two words it
+            // collapses to one space, at the start or end of a line it goes away
+            STRIPPED_MENTIONS_PATTERN
+                .replace_all(text, |caps: &regex::Captures| {
+                    let matched = caps.get(0).unwrap();
+                    let space_before = matched.as_str().starts_with([' ', '\t']);
+                    let space_after = matched.as_str().ends_with([' ', '\t']);
+                    let line_start = text[..matched.start()].chars().next_back().is_none_or(|c| c == '\n');
+                    if space_before && space_after && !line_start {
+                        " "
+                    } else {
+                        ""
+                    }
+                })
+                .to_string()
+        }
+    }
+}
+
 /// Process a Discord message through the hooks system
 ///
 /// Returns a ProcessResult indicating how to handle the message:
@@ -159,17 +243,21 @@ pub async fn process(
         log::info!("Discord hooks: Processing reply-to-bot from {}", msg.author.name);
     }
 
+    // DMs have no guild; by default the whole DM is the command, no mention needed
+    let is_dm = msg.guild_id.is_none();
+
     // Debug logging for mention analysis
     log::info!(
-        "Discord hooks: Message from {} - mentions={:?}, content_preview='{}', reply_to_bot={}",
+        "Discord hooks: Message from {} - mentions={:?}, content_preview='{}', reply_to_bot={}, dm={}",
         msg.author.name,
         msg.mentions.iter().map(|u| format!("{}({})", u.name, u.id)).collect::<Vec<_>>(),
-        if msg.content.len() > 100 { format!("{}...", &msg.content[..100]) } else { msg.content.clone() },
-        is_reply_to_bot
+        util::log_preview(&msg.content),
+        is_reply_to_bot,
+        is_dm
     );
 
-    // Check if bot is mentioned OR if user is replying to the bot
-    if !is_reply_to_bot && !is_bot_mentioned(msg, bot_id) {
+    // Check if bot is mentioned OR if user is replying to the bot (or no mention is needed here)
+    if config.requires_mention(is_dm) && !is_reply_to_bot && !is_bot_mentioned(msg, bot_id) {
         // Check if they mentioned a role the bot has (common mistake)
         if !msg.mention_roles.is_empty() {
             if let Some(guild_id) = msg.guild_id {
@@ -189,13 +277,14 @@ pub async fn process(
                 }
             }
         }
-        // In DMs, we might want to process without mention
-        // For now, require mention in all contexts
         return Ok(ProcessResult::not_handled());
     }
 
-    // Extract command text (remove bot mention)
-    let command_text = extract_command_text(&msg.content, bot_id);
+    // Extract command text (remove bot mention, then mass/role/channel mentions per config)
+    let command_text = sanitize_mentions(
+        &extract_command_text(&msg.content, bot_id),
+        config.mention_sanitization,
+    );
 
     if command_text.is_empty() {
         return Ok(ProcessResult::handled(
@@ -215,7 +304,7 @@ pub async fn process(
         }
     }
 
-    // Check if user is admin (explicit IDs or Discord Administrator permission)
+    // Check if user is admin (explicit user/role IDs or Discord Administrator permission)
     let is_admin = config.is_admin(&user_id, msg, ctx).await;
 
     log::info!(
@@ -224,11 +313,7 @@ pub async fn process(
         user_id,
         is_admin,
         config.has_explicit_admins(),
-        if command_text.len() > 50 {
-            format!("{}...", &command_text[..50])
-        } else {
-            command_text.clone()
-        }
+        util::log_preview(&command_text)
     );
 
     if is_admin {
@@ -274,6 +359,12 @@ pub async fn process(
             }
         }
 
+        // "configure" command - guided AI endpoint setup via button + modal
+        if cmd_lower == "configure" || cmd_lower == "config" {
+            log::info!("Discord hooks: Admin {} opening agent configuration", user_name);
+            return Ok(ProcessResult::agent_config_prompt());
+        }
+
         // "register" command - handle directly like a regular user
         if cmd_lower.starts_with("register") {
             log::info!(
@@ -297,11 +388,7 @@ pub async fn process(
         log::info!(
             "Discord hooks: Admin {} forwarding to agent: '{}'",
             user_name,
-            if command_text.len() > 50 {
-                format!("{}...", &command_text[..50])
-            } else {
-                command_text.clone()
-            }
+            util::log_preview(&command_text)
         );
         Ok(ProcessResult::forward_to_agent(ForwardRequest {
             text: command_text,
@@ -325,6 +412,10 @@ pub async fn process(
             return Ok(ProcessResult::handled(response.to_string()));
         }
 
+        if cmd_lower == "configure" || cmd_lower == "config" {
+            return Ok(ProcessResult::handled(commands::permission_denied_message()));
+        }
+
         match commands::parse(&command_text) {
             Some(cmd) => {
                 let response = commands::execute(cmd, &user_id, db).await?;
@@ -382,6 +473,69 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_sanitize_mass_mentions() {
+        assert_eq!(
+            sanitize_mentions("hey @everyone and @here", MentionSanitization::Neutralize),
+            "hey @\u{200B}everyone and @\u{200B}here"
+        );
+        assert_eq!(
+            sanitize_mentions("hey @everyone and @here look", MentionSanitization::Strip),
+            "hey and look"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_role_mentions() {
+        assert_eq!(
+            sanitize_mentions("ping <@&555> now", MentionSanitization::Neutralize),
+            "ping @role:555 now"
+        );
+        assert_eq!(
+            sanitize_mentions("ping <@&555> now", MentionSanitization::Strip),
+            "ping now"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_channel_mentions() {
+        assert_eq!(
+            sanitize_mentions("see <#777>", MentionSanitization::Neutralize),
+            "see #channel:777"
+        );
+        assert_eq!(sanitize_mentions("see <#777>", MentionSanitization::Strip), "see");
+    }
+
+    #[test]
+    fn test_strip_leaves_other_whitespace_alone() {
+        let code = "run this:\n```\nfn main() {\n    let x  =  1;\n        println!(\"{}\", x);\n}\n```";
+        assert_eq!(sanitize_mentions(code, MentionSanitization::Strip), code);
+        assert_eq!(
+            sanitize_mentions("@here fix:\n```\n    indented  line\n```", MentionSanitization::Strip),
+            "fix:\n```\n    indented  line\n```"
+        );
+        assert_eq!(sanitize_mentions("see <#1> <#2>\nok", MentionSanitization::Strip), "see\nok");
+    }
+
+    #[test]
+    fn test_sanitize_preserves_user_mentions() {
+        for mode in [MentionSanitization::Neutralize, MentionSanitization::Strip] {
+            assert_eq!(
+                sanitize_mentions("tip <@123> and <@!456> 10", mode),
+                "tip <@123> and <@!456> 10"
+            );
+        }
+    }
+
+    #[test]
+    fn test_sanitize_off_and_setting_parse() {
+        let text = "@everyone <@&1> <#2>";
+        assert_eq!(sanitize_mentions(text, MentionSanitization::Off), text);
+        assert_eq!(MentionSanitization::from_setting("strip"), MentionSanitization::Strip);
+        assert_eq!(MentionSanitization::from_setting("off"), MentionSanitization::Off);
+        assert_eq!(MentionSanitization::from_setting(""), MentionSanitization::Neutralize);
+    }
+
     #[test]
     fn test_has_love_keyword() {
         // Should match
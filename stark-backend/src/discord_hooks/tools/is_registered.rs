@@ -0,0 +1,134 @@
+//! Tool to check whether a Discord user has registered, without revealing their address
+
+use super::resolve_user::extract_user_id;
+use crate::tools::registry::Tool;
+use crate::tools::types::{
+    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+};
+use crate::tools::ToolSafetyLevel;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Minimal-disclosure companion to `discord_resolve_user`: reports registration only
+pub struct DiscordIsRegisteredTool {
+    definition: ToolDefinition,
+}
+
+impl DiscordIsRegisteredTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+
+        properties.insert(
+            "user_mention".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Discord user mention in format '<@USER_ID>' or '<@!USER_ID>', \
+                    or just the numeric user ID"
+                    .to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        Self {
+            definition: ToolDefinition {
+                name: "discord_is_registered".to_string(),
+                description: "Check whether a Discord user has registered a public address, without \
+                    revealing the address. Returns 'registered' and 'registered_at'. Use this when you \
+                    only need to know if someone is registered (e.g. 'is @user registered?', or to decide \
+                    whether to prompt them to register); use discord_resolve_user when you actually need \
+                    the address to send a tip."
+                    .to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec!["user_mention".to_string()],
+                },
+                group: ToolGroup::Messaging,
+                hidden: false,
+            },
+        }
+    }
+}
+
+impl Default for DiscordIsRegisteredTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct IsRegisteredParams {
+    user_mention: String,
+}
+
+#[async_trait]
+impl Tool for DiscordIsRegisteredTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: IsRegisteredParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        let mention = params.user_mention.trim();
+        let user_id = match extract_user_id(mention) {
+            Some(id) => id,
+            None => {
+                return ToolResult::error(format!(
+                    "Invalid Discord mention format: '{}'. \
+                    Expected '<@USER_ID>', '<@!USER_ID>', or a numeric user ID.",
+                    mention
+                ));
+            }
+        };
+
+        let db = match &context.database {
+            Some(db) => db,
+            None => {
+                return ToolResult::error(
+                    "Database not available in tool context. Cannot check Discord user.",
+                );
+            }
+        };
+
+        // Unregistered is an answer, not an error
+        match crate::discord_hooks::db::registration_status(db, &user_id).await {
+            Ok((registered, registered_at)) => ToolResult::success(
+                json!({
+                    "discord_user_id": user_id,
+                    "mention": format!("<@{}>", user_id),
+                    "registered": registered,
+                    "registered_at": registered_at,
+                })
+                .to_string(),
+            ),
+            Err(e) => ToolResult::error(format!("Database error: {}", e)),
+        }
+    }
+
+    fn safety_level(&self) -> ToolSafetyLevel {
+        ToolSafetyLevel::ReadOnly
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_invalid_mention_is_rejected_before_lookup() {
+        let tool = DiscordIsRegisteredTool::new();
+        let result = tool
+            .execute(json!({"user_mention": "@someone"}), &ToolContext::new())
+            .await;
+        assert!(!result.success);
+        assert!(result.content.contains("Invalid Discord mention"));
+    }
+}
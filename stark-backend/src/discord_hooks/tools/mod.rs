@@ -1,5 +1,9 @@
 //! Discord hooks tools for the agent
 
+mod is_registered;
 mod resolve_user;
+mod reverse_lookup;
 
+pub use is_registered::DiscordIsRegisteredTool;
 pub use resolve_user::DiscordResolveUserTool;
+pub use reverse_lookup::DiscordReverseLookupTool;
@@ -158,7 +158,7 @@ impl Tool for DiscordResolveUserTool {
 }
 
 /// Extract user ID from various mention formats
-fn extract_user_id(mention: &str) -> Option<String> {
+pub(super) fn extract_user_id(mention: &str) -> Option<String> {
     // Try to match <@123456789> or <@!123456789>
     let re = Regex::new(r"<@!?(\d+)>").unwrap();
     if let Some(caps) = re.captures(mention) {
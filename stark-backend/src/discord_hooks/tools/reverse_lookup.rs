@@ -0,0 +1,185 @@
+//! Tool to look up which Discord user(s) registered a public address
+
+use crate::tools::registry::Tool;
+use crate::tools::types::{
+    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+};
+use crate::tools::ToolSafetyLevel;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Tool for attributing a public address back to registered Discord users
+pub struct DiscordReverseLookupTool {
+    definition: ToolDefinition,
+}
+
+impl DiscordReverseLookupTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+
+        properties.insert(
+            "address".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Public address to look up (0x-prefixed hex, case-insensitive)"
+                    .to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        Self {
+            definition: ToolDefinition {
+                name: "discord_reverse_lookup".to_string(),
+                description: "Find the Discord user(s) who registered a public address — the \
+                    reverse of discord_resolve_user. Use this to attribute on-chain activity \
+                    (e.g. the sender of a transaction) to a community member. Returns every \
+                    matching user's Discord ID, username, and mention; 'found: false' if no \
+                    registered user has this address. 'ambiguous: true' means several users \
+                    registered it, so don't attribute it to any one of them without checking."
+                    .to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec!["address".to_string()],
+                },
+                group: ToolGroup::Messaging,
+                hidden: false,
+            },
+        }
+    }
+}
+
+impl Default for DiscordReverseLookupTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ReverseLookupParams {
+    #[serde(alias = "public_address")]
+    address: String,
+}
+
+#[async_trait]
+impl Tool for DiscordReverseLookupTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: ReverseLookupParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        let address = match normalize_address(&params.address) {
+            Some(a) => a,
+            None => {
+                return ToolResult::error(format!(
+                    "Invalid address: '{}'. Expected a 0x-prefixed hex address.",
+                    params.address.trim()
+                ));
+            }
+        };
+
+        let db = match &context.database {
+            Some(db) => db,
+            None => {
+                return ToolResult::error(
+                    "Database not available in tool context. Cannot look up Discord users.",
+                );
+            }
+        };
+
+        let profiles = match crate::discord_hooks::db::list_profiles_by_address(db, &address).await {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Database error: {}", e)),
+        };
+
+        if profiles.len() > 1 {
+            log::warn!(
+                "[DISCORD_REVERSE_LOOKUP] {} users share registered address {}",
+                profiles.len(),
+                address
+            );
+        }
+
+        let users: Vec<Value> = profiles
+            .iter()
+            .map(|p| {
+                json!({
+                    "discord_user_id": p.discord_user_id,
+                    "username": p.discord_username,
+                    "mention": format!("<@{}>", p.discord_user_id),
+                    "registered_at": p.registered_at,
+                })
+            })
+            .collect();
+
+        ToolResult::success(
+            json!({
+                "public_address": address,
+                "found": !users.is_empty(),
+                "ambiguous": users.len() > 1,
+                "count": users.len(),
+                "users": users,
+            })
+            .to_string(),
+        )
+    }
+
+    fn safety_level(&self) -> ToolSafetyLevel {
+        ToolSafetyLevel::ReadOnly
+    }
+}
+
+/// Lowercase a 0x-prefixed hex address (40-64 hex digits), or `None` if malformed
+fn normalize_address(address: &str) -> Option<String> {
+    let address = address.trim();
+    let hex = address
+        .strip_prefix("0x")
+        .or_else(|| address.strip_prefix("0X"))?;
+    if (40..=64).contains(&hex.len()) && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        Some(format!("0x{}", hex.to_lowercase()))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_address() {
+        assert_eq!(
+            normalize_address(" 0xABCDEF1234567890abcdef1234567890ABCDEF12 "),
+            Some("0xabcdef1234567890abcdef1234567890abcdef12".to_string())
+        );
+        assert!(normalize_address(&format!("0x{}", "a".repeat(64))).is_some());
+        assert_eq!(normalize_address("0x123"), None);
+        assert_eq!(normalize_address("abcdef1234567890abcdef1234567890abcdef12"), None);
+        assert_eq!(normalize_address("0xGGCDEF1234567890abcdef1234567890abcdef12"), None);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_address_is_rejected_before_lookup() {
+        let tool = DiscordReverseLookupTool::new();
+        let result = tool
+            .execute(json!({"address": "not-an-address"}), &ToolContext::new())
+            .await;
+        assert!(!result.success);
+        assert!(result.content.contains("Invalid address"));
+
+        // `public_address` is accepted as well
+        let result = tool
+            .execute(json!({"public_address": "0x123"}), &ToolContext::new())
+            .await;
+        assert!(result.content.contains("Invalid address: '0x123'"));
+    }
+}
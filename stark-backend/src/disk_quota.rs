@@ -2,10 +2,27 @@
 //!
 //! Scans tracked directories on startup, re-scans periodically, and provides
 //! a fast lock-free `check_quota()` via AtomicU64 for use before every write.
-
+//!
+//! Files written on behalf of a channel (tool writes, saved attachments, files an
+//! exec command created or modified) are attributed to that channel, so one channel
+//! can be capped with a per-channel quota before it fills the shared workspace for
+//! everyone. A channel's usage is the current on-disk size of its files: overwrites,
+//! deletes and expiry are reflected on the next write or periodic re-scan. The
+//! file-to-channel index is persisted so attribution survives restarts.
+//!
+//! Exec commands are attributed by modification time, so a file changed while
+//! another channel's command was also running is ambiguous and left unattributed
+//! (it still counts toward the global quota), and files another channel wrote
+//! through the file tools are never taken over by mtime alone.
+
+use dashmap::DashMap;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::SystemTime;
 use walkdir::WalkDir;
 
 /// Default disk quota in megabytes (1 GB)
@@ -20,6 +37,9 @@ pub const MAX_MEMORY_APPEND_BYTES: usize = 100 * 1024;
 /// Max skill ZIP upload size (10 MB)
 pub const MAX_SKILL_ZIP_BYTES: usize = 10 * 1024 * 1024;
 
+/// Most directory entries looked at when attributing an exec command's writes
+const MAX_ATTRIBUTION_SCAN_ENTRIES: usize = 20_000;
+
 /// Error returned when a disk quota would be exceeded.
 #[derive(Debug)]
 pub struct QuotaError {
@@ -27,14 +47,18 @@ pub struct QuotaError {
     pub remaining_bytes: u64,
     pub quota_bytes: u64,
     pub used_bytes: u64,
+    /// Set when a per-channel quota was hit rather than the global one
+    pub channel_id: Option<i64>,
 }
 
 impl fmt::Display for QuotaError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let scope = if self.channel_id.is_some() { "Channel disk quota" } else { "Disk quota" };
         write!(
             f,
-            "Disk quota exceeded: cannot write {} — only {} remaining out of {} total ({} used). \
+            "{} exceeded: cannot write {} — only {} remaining out of {} total ({} used). \
              Visit the System page to view your storage breakdown and clean up files.",
+            scope,
             format_bytes(self.requested_bytes),
             format_bytes(self.remaining_bytes),
             format_bytes(self.quota_bytes),
@@ -45,11 +69,47 @@ impl fmt::Display for QuotaError {
 
 impl std::error::Error for QuotaError {}
 
+/// A command running on behalf of a channel, from `begin_channel_command`.
+#[derive(Debug, Clone, Copy)]
+pub struct ChannelCommand {
+    id: u64,
+    channel_id: i64,
+    started: SystemTime,
+}
+
+/// When a channel's command ran (`ended` = None while it is still running)
+struct CommandWindow {
+    id: u64,
+    channel_id: i64,
+    started: SystemTime,
+    ended: Option<SystemTime>,
+}
+
+/// Bytes attributed to one channel
+#[derive(Debug, Clone, Serialize)]
+pub struct ChannelDiskUsage {
+    pub channel_id: i64,
+    pub used_bytes: u64,
+}
+
 /// Manages disk usage tracking and quota enforcement for a set of directories.
 pub struct DiskQuotaManager {
     quota_bytes: u64,
     tracked_dirs: Vec<PathBuf>,
     cached_usage: AtomicU64,
+    /// Per-channel cap (0 = only the global quota applies)
+    channel_quota_bytes: u64,
+    /// Files attributed to each channel
+    channel_files: DashMap<i64, HashSet<PathBuf>>,
+    /// On-disk size of each channel's files as of its last measurement
+    channel_usage: DashMap<i64, u64>,
+    /// Where `channel_files` is persisted (None = in memory only)
+    index_path: Option<PathBuf>,
+    /// Serializes writes of the persisted index
+    index_lock: Mutex<()>,
+    /// Running and recently finished commands, for spotting overlapping writes
+    command_windows: Mutex<Vec<CommandWindow>>,
+    next_command_id: AtomicU64,
 }
 
 impl DiskQuotaManager {
@@ -71,6 +131,13 @@ impl DiskQuotaManager {
             quota_bytes,
             tracked_dirs,
             cached_usage: AtomicU64::new(0),
+            channel_quota_bytes: 0,
+            channel_files: DashMap::new(),
+            channel_usage: DashMap::new(),
+            index_path: None,
+            index_lock: Mutex::new(()),
+            command_windows: Mutex::new(Vec::new()),
+            next_command_id: AtomicU64::new(0),
         };
 
         // Initial scan
@@ -80,6 +147,31 @@ impl DiskQuotaManager {
         manager
     }
 
+    /// Cap the bytes any one channel's files may take up (0 = no per-channel cap).
+    pub fn with_channel_quota_mb(mut self, quota_mb: u64) -> Self {
+        self.channel_quota_bytes = quota_mb * 1024 * 1024;
+        self
+    }
+
+    /// Persist the file-to-channel index at `path`, loading any index already there.
+    pub fn with_index_file(mut self, path: PathBuf) -> Self {
+        match std::fs::read_to_string(&path) {
+            Ok(json) => match serde_json::from_str::<HashMap<i64, HashSet<PathBuf>>>(&json) {
+                Ok(index) => {
+                    for (channel_id, files) in index {
+                        self.channel_files.insert(channel_id, files);
+                    }
+                }
+                Err(e) => log::warn!("[DISK_QUOTA] Ignoring unreadable channel index {}: {}", path.display(), e),
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => log::warn!("[DISK_QUOTA] Failed to read channel index {}: {}", path.display(), e),
+        }
+        self.index_path = Some(path);
+        self.refresh_channel_usage();
+        self
+    }
+
     /// Whether the quota is enabled (quota_bytes > 0).
     pub fn is_enabled(&self) -> bool {
         self.quota_bytes > 0
@@ -101,12 +193,215 @@ impl DiskQuotaManager {
                 remaining_bytes: self.quota_bytes.saturating_sub(current),
                 quota_bytes: self.quota_bytes,
                 used_bytes: current,
+                channel_id: None,
             })
         } else {
             Ok(())
         }
     }
 
+    /// Like `check_quota`, but also enforces the per-channel quota for `channel_id`.
+    /// Refusals are logged so a runaway channel shows up in the server logs.
+    pub fn check_channel_quota(&self, channel_id: i64, additional_bytes: u64) -> Result<(), QuotaError> {
+        let result = self.check_quota(additional_bytes).and_then(|()| {
+            if self.channel_quota_bytes == 0 {
+                return Ok(());
+            }
+            let current = self.channel_usage_bytes(channel_id);
+            if current.saturating_add(additional_bytes) > self.channel_quota_bytes {
+                Err(QuotaError {
+                    requested_bytes: additional_bytes,
+                    remaining_bytes: self.channel_quota_bytes.saturating_sub(current),
+                    quota_bytes: self.channel_quota_bytes,
+                    used_bytes: current,
+                    channel_id: Some(channel_id),
+                })
+            } else {
+                Ok(())
+            }
+        });
+        if let Err(ref e) = result {
+            log::warn!("[DISK_QUOTA] Refused write for channel {}: {}", channel_id, e);
+        }
+        result
+    }
+
+    /// Record a successful write of `bytes_written` to `path` made on behalf of `channel_id`.
+    pub fn record_channel_write(&self, channel_id: i64, path: &Path, bytes_written: u64) {
+        self.record_write(bytes_written);
+        self.attribute_files(channel_id, std::iter::once(path.to_path_buf()));
+    }
+
+    /// Note that a command is starting for `channel_id`; pass the result to
+    /// `finish_channel_command` once it exits.
+    pub fn begin_channel_command(&self, channel_id: i64) -> ChannelCommand {
+        let command = ChannelCommand {
+            id: self.next_command_id.fetch_add(1, Ordering::Relaxed),
+            channel_id,
+            started: SystemTime::now(),
+        };
+        self.command_windows.lock().push(CommandWindow {
+            id: command.id,
+            channel_id,
+            started: command.started,
+            ended: None,
+        });
+        command
+    }
+
+    /// Attribute the files under `dir` the command created or changed to its channel,
+    /// for writes made by commands rather than through the file tools. Walks the
+    /// directory, so call it off the async runtime.
+    pub fn finish_channel_command(&self, command: ChannelCommand, dir: &Path) {
+        let ended = SystemTime::now();
+        let modified: Vec<(PathBuf, SystemTime)> = WalkDir::new(dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .take(MAX_ATTRIBUTION_SCAN_ENTRIES)
+            .filter(|e| e.file_type().is_file())
+            .filter_map(|e| {
+                let mtime = e.metadata().ok()?.modified().ok()?;
+                (mtime >= command.started).then(|| (e.into_path(), mtime))
+            })
+            .collect();
+
+        let files: Vec<PathBuf> = {
+            let mut windows = self.command_windows.lock();
+            if let Some(window) = windows.iter_mut().find(|w| w.id == command.id) {
+                window.ended = Some(ended);
+            }
+            let files = modified
+                .into_iter()
+                .filter(|(_, mtime)| {
+                    !windows.iter().any(|w| {
+                        w.channel_id != command.channel_id
+                            && w.started <= *mtime
+                            && w.ended.is_none_or(|end| *mtime <= end)
+                    })
+                })
+                .map(|(file, _)| file.canonicalize().unwrap_or(file))
+                .filter(|file| {
+                    !self
+                        .channel_files
+                        .iter()
+                        .any(|e| *e.key() != command.channel_id && e.value().contains(file))
+                })
+                .collect();
+            // Finished windows only matter while a command that overlapped them is still running
+            let oldest_running = windows.iter().filter(|w| w.ended.is_none()).map(|w| w.started).min();
+            windows.retain(|w| match (w.ended, oldest_running) {
+                (None, _) => true,
+                (Some(end), Some(oldest)) => end >= oldest,
+                (Some(_), None) => false,
+            });
+            files
+        };
+        self.attribute_files(command.channel_id, files);
+    }
+
+    /// Add files to a channel's set (taking them from any other channel) and re-measure
+    /// the channels whose sets changed.
+    fn attribute_files(&self, channel_id: i64, files: impl IntoIterator<Item = PathBuf>) {
+        let mut changed = false;
+        let mut losers: HashSet<i64> = HashSet::new();
+        for file in files {
+            let file = file.canonicalize().unwrap_or(file);
+            for mut other in self.channel_files.iter_mut() {
+                if *other.key() != channel_id && other.value_mut().remove(&file) {
+                    losers.insert(*other.key());
+                }
+            }
+            changed |= self.channel_files.entry(channel_id).or_default().insert(file);
+        }
+        if changed || !losers.is_empty() {
+            self.save_index();
+        }
+        for other in losers {
+            self.measure_channel(other);
+        }
+        self.measure_channel(channel_id);
+    }
+
+    /// Re-measure `channel_id`'s files on disk, forgetting ones that no longer exist
+    /// (and the channel itself once it has none).
+    fn measure_channel(&self, channel_id: i64) -> u64 {
+        let mut removed = false;
+        let used = match self.channel_files.get_mut(&channel_id) {
+            Some(mut files) => {
+                let mut used = 0u64;
+                files.retain(|file| match std::fs::metadata(file) {
+                    Ok(meta) => {
+                        used += meta.len();
+                        true
+                    }
+                    Err(_) => {
+                        removed = true;
+                        false
+                    }
+                });
+                used
+            }
+            None => 0,
+        };
+        if used > 0 {
+            self.channel_usage.insert(channel_id, used);
+        } else {
+            self.channel_usage.remove(&channel_id);
+        }
+        self.channel_files.remove_if(&channel_id, |_, files| files.is_empty());
+        if removed {
+            self.save_index();
+        }
+        used
+    }
+
+    /// Re-measure every channel's files.
+    fn refresh_channel_usage(&self) {
+        let channel_ids: Vec<i64> = self.channel_files.iter().map(|e| *e.key()).collect();
+        for channel_id in channel_ids {
+            self.measure_channel(channel_id);
+        }
+        self.channel_usage.retain(|channel_id, _| self.channel_files.contains_key(channel_id));
+    }
+
+    /// Write the file-to-channel index, if persistence is configured.
+    fn save_index(&self) {
+        let Some(ref path) = self.index_path else {
+            return;
+        };
+        let _guard = self.index_lock.lock();
+        let index: HashMap<i64, HashSet<PathBuf>> =
+            self.channel_files.iter().map(|e| (*e.key(), e.value().clone())).collect();
+        let result = serde_json::to_string(&index)
+            .map_err(std::io::Error::other)
+            .and_then(|json| std::fs::write(path, json));
+        if let Err(e) = result {
+            log::warn!("[DISK_QUOTA] Failed to save channel index {}: {}", path.display(), e);
+        }
+    }
+
+    /// Bytes taken up by `channel_id`'s files as of their last measurement.
+    pub fn channel_usage_bytes(&self, channel_id: i64) -> u64 {
+        self.channel_usage.get(&channel_id).map(|v| *v).unwrap_or(0)
+    }
+
+    /// Usage for every channel that has files on disk, largest first.
+    pub fn channel_usage(&self) -> Vec<ChannelDiskUsage> {
+        let mut usage: Vec<ChannelDiskUsage> = self
+            .channel_usage
+            .iter()
+            .filter(|e| *e.value() > 0)
+            .map(|e| ChannelDiskUsage { channel_id: *e.key(), used_bytes: *e.value() })
+            .collect();
+        usage.sort_by(|a, b| b.used_bytes.cmp(&a.used_bytes));
+        usage
+    }
+
+    /// Per-channel quota limit in bytes (0 = none).
+    pub fn channel_quota_bytes(&self) -> u64 {
+        self.channel_quota_bytes
+    }
+
     /// Optimistically bump cached usage after a successful write.
     pub fn record_write(&self, bytes_written: u64) {
         if self.is_enabled() {
@@ -132,10 +427,11 @@ impl DiskQuotaManager {
         total
     }
 
-    /// Re-scan and update the cached usage. Returns new usage.
+    /// Re-scan and update the cached usage (and each channel's). Returns new usage.
     pub fn refresh(&self) -> u64 {
         let usage = self.scan_usage();
         self.cached_usage.store(usage, Ordering::Relaxed);
+        self.refresh_channel_usage();
         usage
     }
 
@@ -297,6 +593,100 @@ mod tests {
         assert!(status.contains("0%"));
     }
 
+    #[test]
+    fn test_channel_quota_is_enforced_per_channel() {
+        let dir = tempdir().unwrap();
+        let manager = DiskQuotaManager::new(Some(10), vec![dir.path().to_path_buf()]).with_channel_quota_mb(1);
+
+        let big = dir.path().join("big.bin");
+        fs::write(&big, vec![0u8; 1024 * 1024]).unwrap();
+        manager.record_channel_write(1, &big, 1024 * 1024);
+        let err = manager.check_channel_quota(1, 1).unwrap_err();
+        assert_eq!(err.channel_id, Some(1));
+        assert!(err.to_string().starts_with("Channel disk quota exceeded"));
+
+        // Other channels are unaffected, and the global figure includes channel writes
+        assert!(manager.check_channel_quota(2, 1024).is_ok());
+        assert_eq!(manager.usage_bytes(), 1024 * 1024);
+        assert_eq!(manager.channel_usage()[0].channel_id, 1);
+
+        // Deleting the file frees the channel's quota on the next re-scan
+        fs::remove_file(&big).unwrap();
+        manager.refresh();
+        assert!(manager.check_channel_quota(1, 1).is_ok());
+        assert!(manager.channel_usage().is_empty());
+    }
+
+    #[test]
+    fn test_channel_usage_tracks_overwrites() {
+        let dir = tempdir().unwrap();
+        let manager = DiskQuotaManager::new(Some(10), vec![dir.path().to_path_buf()]).with_channel_quota_mb(1);
+        let file = dir.path().join("notes.txt");
+
+        for _ in 0..3 {
+            fs::write(&file, vec![b'x'; 600 * 1024]).unwrap();
+            manager.record_channel_write(1, &file, 600 * 1024);
+        }
+        // Rewriting the same file doesn't add up
+        assert_eq!(manager.channel_usage_bytes(1), 600 * 1024);
+
+        fs::write(&file, "short").unwrap();
+        manager.record_channel_write(1, &file, 5);
+        assert_eq!(manager.channel_usage_bytes(1), 5);
+    }
+
+    #[test]
+    fn test_channel_index_survives_restart() {
+        let dir = tempdir().unwrap();
+        let index = dir.path().join("index.json");
+        let file = dir.path().join("data.csv");
+        fs::write(&file, "a,b\n1,2\n").unwrap();
+
+        let manager = DiskQuotaManager::new(Some(10), vec![]).with_index_file(index.clone());
+        manager.record_channel_write(7, &file, 8);
+        drop(manager);
+
+        let restarted = DiskQuotaManager::new(Some(10), vec![]).with_index_file(index);
+        assert_eq!(restarted.channel_usage_bytes(7), 8);
+    }
+
+    #[test]
+    fn test_channel_command_attributes_modified_files() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("old.txt"), "old").unwrap();
+        let manager = DiskQuotaManager::new(Some(10), vec![dir.path().to_path_buf()]);
+
+        let command = manager.begin_channel_command(3);
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        fs::create_dir(dir.path().join("out")).unwrap();
+        fs::write(dir.path().join("out/build.log"), "built").unwrap();
+        manager.finish_channel_command(command, dir.path());
+        // Only the file the command wrote, not the one already there
+        assert_eq!(manager.channel_usage_bytes(3), 5);
+    }
+
+    #[test]
+    fn test_channel_command_leaves_overlapping_and_owned_files_alone() {
+        let dir = tempdir().unwrap();
+        let manager = DiskQuotaManager::new(Some(10), vec![dir.path().to_path_buf()]);
+
+        let first = manager.begin_channel_command(1);
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        let owned = dir.path().join("owned.txt");
+        fs::write(&owned, "written by 2").unwrap();
+        manager.record_channel_write(2, &owned, 12);
+        let second = manager.begin_channel_command(2);
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        fs::write(dir.path().join("shared.txt"), "either").unwrap();
+        manager.finish_channel_command(second, dir.path());
+        manager.finish_channel_command(first, dir.path());
+
+        // shared.txt was written while both commands ran, owned.txt belongs to channel 2
+        assert_eq!(manager.channel_usage_bytes(1), 0);
+        assert_eq!(manager.channel_usage_bytes(2), 12);
+        assert!(manager.command_windows.lock().is_empty());
+    }
+
     #[test]
     fn test_format_bytes() {
         assert_eq!(format_bytes(0), "0B");
@@ -314,6 +704,7 @@ mod tests {
             remaining_bytes: 5 * 1024 * 1024,
             quota_bytes: 256 * 1024 * 1024,
             used_bytes: 251 * 1024 * 1024,
+            channel_id: None,
         };
         let msg = err.to_string();
         assert!(msg.contains("10.0MB"));
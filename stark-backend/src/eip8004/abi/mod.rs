@@ -5,8 +5,10 @@
 
 pub mod identity;
 pub mod reputation;
+pub mod validation;
 pub mod common;
 
 pub use identity::*;
 pub use reputation::*;
+pub use validation::*;
 pub use common::*;
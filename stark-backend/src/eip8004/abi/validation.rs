@@ -0,0 +1,122 @@
+//! Validation Registry ABI encoding
+
+use super::common::*;
+
+// Function selectors
+pub const GET_AGENT_VALIDATIONS_SELECTOR: [u8; 4] = [0x8d, 0x5d, 0x0c, 0x2d]; // getAgentValidations(uint256)
+pub const GET_VALIDATION_STATUS_SELECTOR: [u8; 4] = [0xff, 0x2f, 0xeb, 0xfc]; // getValidationStatus(bytes32)
+
+/// Encode getAgentValidations(uint256 agentId) call
+pub fn encode_get_agent_validations(agent_id: u64) -> Vec<u8> {
+    let mut calldata = Vec::new();
+    calldata.extend_from_slice(&GET_AGENT_VALIDATIONS_SELECTOR);
+    calldata.extend(encode_uint256(agent_id));
+    calldata
+}
+
+/// Encode getValidationStatus(bytes32 requestHash) call
+pub fn encode_get_validation_status(request_hash: &[u8; 32]) -> Vec<u8> {
+    let mut calldata = Vec::new();
+    calldata.extend_from_slice(&GET_VALIDATION_STATUS_SELECTOR);
+    calldata.extend(encode_bytes32(request_hash));
+    calldata
+}
+
+/// Parse a 0x-prefixed 32-byte hex string
+pub fn parse_bytes32(hex_str: &str) -> Result<[u8; 32], String> {
+    let bytes = hex::decode(hex_str.trim().trim_start_matches("0x"))
+        .map_err(|e| format!("Invalid bytes32 hex: {}", e))?;
+    if bytes.len() != 32 {
+        return Err(format!("Expected 32 bytes, got {}", bytes.len()));
+    }
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&bytes);
+    Ok(out)
+}
+
+/// Decode getAgentValidations result (bytes32[] requestHashes) as 0x-prefixed hex strings
+pub fn decode_bytes32_array_result(data: &[u8]) -> Result<Vec<String>, String> {
+    if data.len() < 64 {
+        return Err("Response too short".to_string());
+    }
+
+    let offset = decode_uint256(&data[..32]) as usize;
+    if data.len() < offset + 32 {
+        return Err("Invalid array offset".to_string());
+    }
+
+    let len = decode_uint256(&data[offset..]) as usize;
+    let start = offset + 32;
+    if data.len() < start + len * 32 {
+        return Err("Array data truncated".to_string());
+    }
+
+    Ok((0..len)
+        .map(|i| format!("0x{}", hex::encode(&data[start + i * 32..start + (i + 1) * 32])))
+        .collect())
+}
+
+/// Decode getValidationStatus result
+/// Returns (validatorAddress, agentId, response, responseHash, tag, lastUpdate)
+pub fn decode_validation_status_result(
+    data: &[u8],
+) -> Result<(String, u64, u8, String, String, u64), String> {
+    if data.len() < 192 {
+        return Err("Response too short".to_string());
+    }
+
+    let validator = decode_address(&data[..32]);
+    let agent_id = decode_uint256(&data[32..64]);
+    let response = decode_uint256(&data[64..96]) as u8;
+    let response_hash = format!("0x{}", hex::encode(&data[96..128]));
+    let tag_offset = decode_uint256(&data[128..160]) as usize;
+    let last_update = decode_uint256(&data[160..192]);
+    let tag = decode_string(data, tag_offset).unwrap_or_default();
+
+    Ok((validator, agent_id, response, response_hash, tag, last_update))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selectors_match_signatures() {
+        assert_eq!(function_selector("getAgentValidations(uint256)"), GET_AGENT_VALIDATIONS_SELECTOR);
+        assert_eq!(function_selector("getValidationStatus(bytes32)"), GET_VALIDATION_STATUS_SELECTOR);
+    }
+
+    #[test]
+    fn test_decode_validation_status() {
+        let mut data = Vec::new();
+        data.extend(encode_address("0x1234567890abcdef1234567890abcdef12345678"));
+        data.extend(encode_uint256(42));
+        data.extend(encode_uint256(100));
+        data.extend(encode_bytes32(&[0xab; 32]));
+        data.extend(encode_uint256(192));
+        data.extend(encode_uint256(1_700_000_000));
+        data.extend(encode_string("task-completion"));
+
+        let (validator, agent_id, response, _, tag, last_update) =
+            decode_validation_status_result(&data).unwrap();
+        assert_eq!(validator, "0x1234567890abcdef1234567890abcdef12345678");
+        assert_eq!(agent_id, 42);
+        assert_eq!(response, 100);
+        assert_eq!(tag, "task-completion");
+        assert_eq!(last_update, 1_700_000_000);
+    }
+
+    #[test]
+    fn test_decode_bytes32_array() {
+        let mut data = Vec::new();
+        data.extend(encode_uint256(32));
+        data.extend(encode_uint256(2));
+        data.extend(encode_bytes32(&[0x01; 32]));
+        data.extend(encode_bytes32(&[0x02; 32]));
+
+        let hashes = decode_bytes32_array_result(&data).unwrap();
+        assert_eq!(hashes.len(), 2);
+        assert_eq!(hashes[1], format!("0x{}", "02".repeat(32)));
+        assert_eq!(parse_bytes32(&hashes[0]).unwrap(), [0x01; 32]);
+    }
+}
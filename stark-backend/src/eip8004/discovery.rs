@@ -7,8 +7,10 @@ use super::identity::IdentityRegistry;
 use super::reputation::ReputationRegistry;
 use super::types::*;
 use crate::wallet::WalletProvider;
+use once_cell::sync::Lazy;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 /// Agent discovery and indexing
 pub struct AgentDiscovery {
@@ -108,11 +110,37 @@ impl AgentDiscovery {
         Ok(agents)
     }
 
-    /// Search for agents with specific criteria
+    /// Search for agents with specific criteria.
+    /// Stops scanning the registry once the requested page is filled.
     pub async fn search(
         &mut self,
         criteria: SearchCriteria,
     ) -> Result<Vec<DiscoveredAgent>, String> {
+        let wanted = criteria
+            .limit
+            .map(|limit| criteria.offset.unwrap_or(0).saturating_add(limit));
+        let results = self.collect_matches(&criteria, wanted).await;
+        Ok(Self::page(&criteria, results))
+    }
+
+    /// Search for agents, returning the requested page and the total number of matches.
+    /// Every agent is checked so that sorting and pagination are consistent across pages,
+    /// so this costs a full registry scan (use `search` when the total isn't needed).
+    pub async fn search_with_total(
+        &mut self,
+        criteria: SearchCriteria,
+    ) -> Result<(Vec<DiscoveredAgent>, usize), String> {
+        let results = self.collect_matches(&criteria, None).await;
+        let matches = results.len();
+        Ok((Self::page(&criteria, results), matches))
+    }
+
+    /// Matching agents in registry order, sorted if requested; stops after `stop_after` matches
+    async fn collect_matches(
+        &mut self,
+        criteria: &SearchCriteria,
+        stop_after: Option<usize>,
+    ) -> Vec<DiscoveredAgent> {
         // First, discover all agents (or use cached)
         let total = self.total_agents().await.unwrap_or(0);
         let mut results = Vec::new();
@@ -123,10 +151,8 @@ impl AgentDiscovery {
                     results.push(agent);
 
                     // Limit results
-                    if let Some(limit) = criteria.limit {
-                        if results.len() >= limit {
-                            break;
-                        }
+                    if stop_after.is_some_and(|n| results.len() >= n) {
+                        break;
                     }
                 }
             }
@@ -141,7 +167,16 @@ impl AgentDiscovery {
             });
         }
 
-        Ok(results)
+        results
+    }
+
+    /// The page of `results` selected by the criteria's offset and limit
+    fn page(criteria: &SearchCriteria, results: Vec<DiscoveredAgent>) -> Vec<DiscoveredAgent> {
+        results
+            .into_iter()
+            .skip(criteria.offset.unwrap_or(0))
+            .take(criteria.limit.unwrap_or(usize::MAX))
+            .collect()
     }
 
     /// Find agents with x402 support
@@ -198,6 +233,80 @@ impl AgentDiscovery {
     }
 }
 
+/// Discovery results as returned to API callers
+#[derive(Debug, Clone)]
+pub struct CachedDiscovery {
+    pub agents: Vec<DiscoveredAgent>,
+    pub total: u64,
+}
+
+/// Time-based cache for discovery pages and searches
+///
+/// `AgentDiscovery` is built per request, so its per-agent cache never outlives
+/// one call. This process-wide cache keeps whole results for a short TTL so
+/// repeated listings don't re-read every agent from the chain.
+pub struct DiscoveryCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (Instant, CachedDiscovery)>>,
+}
+
+impl DiscoveryCache {
+    /// A TTL of zero disables caching
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Key for a `discover_all(offset, limit)` page
+    pub fn page_key(registry: &str, offset: u64, limit: u64) -> String {
+        format!("page:{}:{}:{}", registry, offset, limit)
+    }
+
+    /// Key for a search with the given criteria
+    pub fn search_key(registry: &str, criteria: &SearchCriteria) -> String {
+        format!("search:{}:{:?}", registry, criteria)
+    }
+
+    /// Cached result if it hasn't expired
+    pub fn get(&self, key: &str) -> Option<CachedDiscovery> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries
+            .get(key)
+            .filter(|(at, _)| at.elapsed() < self.ttl)
+            .map(|(_, result)| result.clone())
+    }
+
+    /// Store a result, dropping expired entries
+    pub fn insert(&self, key: String, result: CachedDiscovery) {
+        if self.ttl.is_zero() {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.retain(|_, (at, _)| at.elapsed() < self.ttl);
+        entries.insert(key, (Instant::now(), result));
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+}
+
+static DISCOVERY_CACHE: Lazy<DiscoveryCache> = Lazy::new(|| {
+    DiscoveryCache::new(Duration::from_secs(crate::config::eip8004_discovery_cache_ttl_secs()))
+});
+
+/// Process-wide discovery cache (TTL from `STARK_EIP8004_DISCOVERY_CACHE_TTL_SECS`)
+pub fn discovery_cache() -> &'static DiscoveryCache {
+    &DISCOVERY_CACHE
+}
+
+/// Offset of the page after one that ends at `end` (exclusive), if there is one
+pub fn next_offset(end: u64, total: u64) -> Option<u64> {
+    (end < total).then_some(end)
+}
+
 /// Search criteria for agent discovery
 #[derive(Debug, Clone, Default)]
 pub struct SearchCriteria {
@@ -215,6 +324,8 @@ pub struct SearchCriteria {
     pub name_contains: Option<String>,
     /// Sort results by reputation score
     pub sort_by_reputation: bool,
+    /// Number of matching results to skip (for pagination)
+    pub offset: Option<usize>,
     /// Maximum number of results
     pub limit: Option<usize>,
 }
@@ -434,6 +545,33 @@ mod tests {
         assert!(criteria.matches(&agent));
     }
 
+    #[test]
+    fn test_next_offset() {
+        assert_eq!(next_offset(20, 45), Some(20));
+        assert_eq!(next_offset(45, 45), None);
+        assert_eq!(next_offset(0, 0), None);
+    }
+
+    #[test]
+    fn test_discovery_cache() {
+        let result = CachedDiscovery { agents: Vec::new(), total: 7 };
+        let key = DiscoveryCache::page_key("eip155:8453:0x1234", 0, 20);
+
+        let cache = DiscoveryCache::new(Duration::from_secs(60));
+        assert!(cache.get(&key).is_none());
+        cache.insert(key.clone(), result.clone());
+        assert_eq!(cache.get(&key).unwrap().total, 7);
+        // Pages are keyed by offset and limit
+        assert!(cache.get(&DiscoveryCache::page_key("eip155:8453:0x1234", 20, 20)).is_none());
+        cache.clear();
+        assert!(cache.get(&key).is_none());
+
+        // A zero TTL never serves from cache
+        let disabled = DiscoveryCache::new(Duration::ZERO);
+        disabled.insert(key.clone(), result);
+        assert!(disabled.get(&key).is_none());
+    }
+
     #[test]
     fn test_agent_index() {
         let mut index = AgentIndex::new();
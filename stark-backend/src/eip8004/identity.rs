@@ -12,7 +12,7 @@ use std::str::FromStr;
 use std::sync::Arc;
 
 /// Free public RPC URL for read-only eth_call (no x402 payment needed)
-fn free_base_rpc_url(chain_id: u64) -> String {
+pub(super) fn free_base_rpc_url(chain_id: u64) -> String {
     match chain_id {
         84532 => "https://sepolia.base.org".to_string(),
         _ => "https://mainnet.base.org".to_string(),
@@ -315,6 +315,12 @@ impl RegistrationBuilder {
         self.registration
     }
 
+    /// Build and check the result against the EIP-8004 registration schema
+    pub fn build_validated(self) -> Result<RegistrationFile, Vec<String>> {
+        self.registration.validate()?;
+        Ok(self.registration)
+    }
+
     pub fn to_json(&self) -> Result<String, String> {
         serde_json::to_string_pretty(&self.registration)
             .map_err(|e| format!("Failed to serialize: {}", e))
@@ -338,6 +344,21 @@ mod tests {
         assert!(reg.x402_support);
     }
 
+    #[test]
+    fn test_registration_builder_validated() {
+        let result = RegistrationBuilder::new("TestBot", "A test bot")
+            .service("chat", "api.example.com/chat", "1.0")
+            .build_validated();
+        let errors = result.unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("malformed endpoint URL"));
+
+        assert!(RegistrationBuilder::new("TestBot", "A test bot")
+            .service("chat", "https://api.example.com/chat", "1.0")
+            .build_validated()
+            .is_ok());
+    }
+
     #[test]
     fn test_resolve_uri() {
         let config = Eip8004Config::base_mainnet();
@@ -11,6 +11,7 @@ pub mod types;
 pub mod abi;
 pub mod identity;
 pub mod reputation;
+pub mod validation;
 pub mod discovery;
 pub mod config;
 
@@ -18,4 +19,5 @@ pub use types::*;
 pub use config::Eip8004Config;
 pub use identity::IdentityRegistry;
 pub use reputation::ReputationRegistry;
+pub use validation::ValidationRegistry;
 pub use discovery::AgentDiscovery;
@@ -8,7 +8,8 @@ use super::config::Eip8004Config;
 use super::types::*;
 use crate::wallet::WalletProvider;
 use crate::x402::X402EvmRpc;
-use ethers::types::Address;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::{Address, Eip1559TransactionRequest, U256};
 use std::str::FromStr;
 use std::sync::Arc;
 
@@ -145,6 +146,63 @@ impl ReputationRegistry {
         format!("0x{}", hex::encode(&calldata))
     }
 
+    /// Sign and broadcast giveFeedback with the configured wallet. Returns the transaction hash.
+    pub async fn submit_feedback(&self, params: &FeedbackParams) -> Result<String, String> {
+        if !self.is_deployed() {
+            return Err("Reputation Registry not deployed".to_string());
+        }
+        let wallet_provider = self
+            .wallet_provider
+            .as_ref()
+            .ok_or("Wallet not configured: a wallet is required to submit feedback")?;
+
+        let rpc = self.get_rpc()?;
+        let registry_addr = self.parse_registry_address()?;
+        let calldata = encode_give_feedback(
+            params.agent_id,
+            params.value as i128,
+            params.value_decimals,
+            &params.tag1,
+            &params.tag2,
+            &params.endpoint,
+            &params.feedback_uri,
+            params.feedback_content.as_deref().map(|content| keccak256(content.as_bytes())),
+        );
+
+        let from_str = wallet_provider.get_address();
+        let from: Address = from_str
+            .parse()
+            .map_err(|_| format!("Invalid wallet address: {}", from_str))?;
+        let nonce = rpc.get_transaction_count(from).await?;
+        let gas = rpc.estimate_gas(from, registry_addr, &calldata, U256::zero()).await?;
+        let gas = gas * U256::from(120) / U256::from(100); // 20% buffer
+        let (max_fee, priority_fee) = rpc.estimate_eip1559_fees().await?;
+
+        let tx: TypedTransaction = Eip1559TransactionRequest::new()
+            .from(from)
+            .to(registry_addr)
+            .data(calldata)
+            .nonce(nonce)
+            .gas(gas)
+            .max_fee_per_gas(max_fee)
+            .max_priority_fee_per_gas(priority_fee)
+            .chain_id(self.config.chain_id)
+            .into();
+        let signature = wallet_provider
+            .sign_transaction(&tx)
+            .await
+            .map_err(|e| format!("Failed to sign transaction: {}", e))?;
+
+        let tx_hash = rpc.send_raw_transaction(&tx.rlp_signed(&signature)).await?;
+        log::info!(
+            "[EIP8004] Submitted feedback {} for agent {}: {:?}",
+            params.value,
+            params.agent_id,
+            tx_hash
+        );
+        Ok(format!("{:?}", tx_hash))
+    }
+
     /// Get encoded calldata for revokeFeedback - for use with web3_tx tool
     pub fn encode_revoke_feedback(&self, agent_id: u64, feedback_index: u64) -> String {
         let calldata = encode_revoke_feedback(agent_id, feedback_index);
@@ -201,6 +259,7 @@ impl ReputationRegistry {
             tag1: tag1.map(String::from),
             tag2: tag2.map(String::from),
             endpoint: endpoint.map(String::from),
+            comment: None,
             proof_of_payment,
         }
     }
@@ -2,6 +2,12 @@
 
 use serde::{Deserialize, Serialize};
 
+/// Registration file `type` value for the only schema version we support
+pub const REGISTRATION_TYPE_V1: &str = "https://eips.ethereum.org/EIPS/eip-8004#registration-v1";
+
+/// Service names whose endpoint must be a network URL (as opposed to ENS names, DIDs, CAIP-10 accounts)
+const URL_SERVICES: &[&str] = &["web", "mcp", "a2a", "chat", "x402", "swap", "oasf"];
+
 /// Full agent identifier (agentRegistry + agentId)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct AgentIdentifier {
@@ -82,7 +88,7 @@ fn default_true() -> bool {
 impl RegistrationFile {
     pub fn new(name: &str, description: &str) -> Self {
         Self {
-            type_url: "https://eips.ethereum.org/EIPS/eip-8004#registration-v1".to_string(),
+            type_url: REGISTRATION_TYPE_V1.to_string(),
             name: name.to_string(),
             description: description.to_string(),
             image: None,
@@ -107,6 +113,109 @@ impl RegistrationFile {
         self.image = Some(url.to_string());
         self
     }
+
+    /// Check the registration against the EIP-8004 registration-v1 schema.
+    ///
+    /// Returns every problem found (not just the first) so operators can fix
+    /// them in one pass before publishing.
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+
+        if self.type_url != REGISTRATION_TYPE_V1 {
+            errors.push(format!(
+                "unsupported registration type '{}' (expected '{}')",
+                self.type_url, REGISTRATION_TYPE_V1
+            ));
+        }
+        if self.name.trim().is_empty() {
+            errors.push("missing name".to_string());
+        }
+        if self.description.trim().is_empty() {
+            errors.push("missing description".to_string());
+        }
+        if let Some(ref image) = self.image {
+            if !is_valid_uri(image) {
+                errors.push(format!("malformed image URI '{}'", image));
+            }
+        }
+
+        for (i, service) in self.services.iter().enumerate() {
+            let label = if service.name.trim().is_empty() {
+                format!("services[{}]", i)
+            } else {
+                format!("services[{}] ({})", i, service.name)
+            };
+            if service.name.trim().is_empty() {
+                errors.push(format!("{}: missing service name", label));
+            }
+            if service.version.trim().is_empty() {
+                errors.push(format!("{}: missing version", label));
+            }
+            let endpoint = service.endpoint.trim();
+            if endpoint.is_empty() {
+                errors.push(format!("{}: missing endpoint", label));
+            } else if URL_SERVICES.contains(&service.name.to_lowercase().as_str()) {
+                if !is_valid_network_url(endpoint) {
+                    errors.push(format!("{}: malformed endpoint URL '{}'", label, endpoint));
+                }
+            } else if endpoint.chars().any(char::is_whitespace) {
+                errors.push(format!("{}: endpoint '{}' contains whitespace", label, endpoint));
+            }
+        }
+
+        if let Some(ref registrations) = self.registrations {
+            for (i, entry) in registrations.iter().enumerate() {
+                let id = AgentIdentifier {
+                    agent_id: entry.agent_id,
+                    agent_registry: entry.agent_registry.clone(),
+                };
+                let valid = id
+                    .parse_registry()
+                    .map(|(_, addr)| is_hex_address(&addr))
+                    .unwrap_or(false);
+                if !valid {
+                    errors.push(format!(
+                        "registrations[{}]: malformed agentRegistry '{}' (expected eip155:<chainId>:<0x address>)",
+                        i, entry.agent_registry
+                    ));
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// http(s)/ws(s) URL with a host
+fn is_valid_network_url(value: &str) -> bool {
+    match url::Url::parse(value) {
+        Ok(u) => {
+            matches!(u.scheme(), "http" | "https" | "ws" | "wss")
+                && u.host_str().map(|h| !h.is_empty()).unwrap_or(false)
+        }
+        Err(_) => false,
+    }
+}
+
+/// Any URI a registration may reference content by (HTTP, IPFS, Arweave, inline data)
+fn is_valid_uri(value: &str) -> bool {
+    if let Some(rest) = value.strip_prefix("ipfs://").or_else(|| value.strip_prefix("ar://")) {
+        return !rest.is_empty() && !rest.chars().any(char::is_whitespace);
+    }
+    if value.starts_with("data:") {
+        return true;
+    }
+    is_valid_network_url(value)
+}
+
+fn is_hex_address(value: &str) -> bool {
+    value.len() == 42
+        && value.starts_with("0x")
+        && value[2..].chars().all(|c| c.is_ascii_hexdigit())
 }
 
 /// Service entry in registration file
@@ -155,6 +264,9 @@ pub struct FeedbackFile {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub endpoint: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+
     #[serde(rename = "proofOfPayment", skip_serializing_if = "Option::is_none")]
     pub proof_of_payment: Option<ProofOfPayment>,
 }
@@ -315,6 +427,48 @@ pub struct ValidationResponse {
     pub tag: Option<String>,
 }
 
+/// Validation record read from the Validation Registry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationRecord {
+    pub request_hash: String,
+    pub agent_id: u64,
+    pub validator_address: String,
+    pub response: u8, // 0-100
+    pub response_hash: String,
+    pub tag: String,
+    /// Unix timestamp of the validator's last response (0 = still pending)
+    pub last_update: u64,
+}
+
+impl ValidationRecord {
+    /// Whether the validator has responded yet
+    pub fn is_responded(&self) -> bool {
+        self.last_update > 0
+    }
+
+    /// Whether this record is a responded attestation at or above `min_response`
+    pub fn passes(&self, min_response: u8) -> bool {
+        self.is_responded() && self.response >= min_response
+    }
+}
+
+/// Result of checking an agent for a validation of a given type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationCheck {
+    pub agent_id: u64,
+    /// Validation type (tag) that was checked; empty matches any tag
+    pub validation_type: String,
+    pub min_response: u8,
+    pub valid: bool,
+    /// Best passing record, or the most recent matching one if none pass
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub record: Option<ValidationRecord>,
+    /// Number of records matching the validation type
+    pub matching: usize,
+    /// Total validation requests for the agent
+    pub total: usize,
+}
+
 /// x402 Payment record for database storage
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct X402PaymentRecord {
@@ -380,6 +534,34 @@ mod tests {
         assert_eq!(reg.services[0].name, "x402");
     }
 
+    #[test]
+    fn test_registration_validate_ok() {
+        let reg = RegistrationFile::new("TestAgent", "A test agent")
+            .with_service("x402", "https://api.example.com", "1.0")
+            .with_service("ENS", "testagent.eth", "v1")
+            .with_image("ipfs://QmImage");
+        assert!(reg.validate().is_ok());
+    }
+
+    #[test]
+    fn test_registration_validate_errors() {
+        let mut reg = RegistrationFile::new("  ", "A test agent")
+            .with_service("mcp", "not a url", "1.0")
+            .with_service("a2a", "https://agent.example.com/a2a", "");
+        reg.type_url = "https://eips.ethereum.org/EIPS/eip-8004#registration-v0".to_string();
+        reg.registrations = Some(vec![RegistrationEntry {
+            agent_id: 1,
+            agent_registry: "eip155:8453:nope".to_string(),
+        }]);
+
+        let errors = reg.validate().unwrap_err();
+        assert!(errors.iter().any(|e| e.contains("unsupported registration type")));
+        assert!(errors.iter().any(|e| e == "missing name"));
+        assert!(errors.iter().any(|e| e.contains("services[0] (mcp): malformed endpoint URL")));
+        assert!(errors.iter().any(|e| e.contains("services[1] (a2a): missing version")));
+        assert!(errors.iter().any(|e| e.contains("registrations[0]: malformed agentRegistry")));
+    }
+
     #[test]
     fn test_trust_levels() {
         let high = ReputationSummary {
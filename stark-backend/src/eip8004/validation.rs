@@ -0,0 +1,224 @@
+//! Validation Registry interactions
+//!
+//! Read validation requests and validator responses for an agent.
+
+use super::abi::validation::*;
+use super::config::Eip8004Config;
+use super::identity::free_base_rpc_url;
+use super::types::*;
+use crate::wallet::WalletProvider;
+use crate::x402::X402EvmRpc;
+use ethers::types::Address;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Upper bound on records fetched per check (each is one eth_call)
+pub const MAX_VALIDATIONS_SCANNED: usize = 50;
+
+/// Default minimum response (0-100) for a validation to count as passing
+pub const DEFAULT_MIN_RESPONSE: u8 = 50;
+
+/// Validation Registry client
+pub struct ValidationRegistry {
+    config: Eip8004Config,
+    wallet_provider: Option<Arc<dyn WalletProvider>>,
+}
+
+impl ValidationRegistry {
+    /// Create a new Validation Registry client
+    pub fn new(config: Eip8004Config) -> Self {
+        Self { config, wallet_provider: None }
+    }
+
+    /// Create with a wallet provider (for Flash/Privy mode)
+    pub fn new_with_wallet_provider(config: Eip8004Config, wallet_provider: Arc<dyn WalletProvider>) -> Self {
+        Self {
+            config,
+            wallet_provider: Some(wallet_provider),
+        }
+    }
+
+    /// Get a free (non-x402) RPC client for read-only eth_call operations
+    fn get_free_rpc(&self) -> Result<X402EvmRpc, String> {
+        let network = if self.config.chain_id == 1 { "mainnet" } else { "base" };
+        let free_rpc = Some(free_base_rpc_url(self.config.chain_id));
+
+        if let Some(ref wp) = self.wallet_provider {
+            return X402EvmRpc::new_with_wallet_provider(wp.clone(), network, free_rpc, false);
+        }
+
+        let private_key = crate::config::burner_wallet_private_key()
+            .ok_or("BURNER_WALLET_BOT_PRIVATE_KEY not set")?;
+        let wp: Arc<dyn WalletProvider> = Arc::new(
+            crate::wallet::EnvWalletProvider::from_private_key(&private_key)?
+        );
+        X402EvmRpc::new_with_wallet_provider(wp, network, free_rpc, false)
+    }
+
+    /// Get the registry contract address (if configured)
+    pub fn registry_address(&self) -> Option<&str> {
+        self.config.validation_registry.as_deref()
+    }
+
+    /// Check if the registry is deployed
+    pub fn is_deployed(&self) -> bool {
+        self.config.is_validation_deployed()
+    }
+
+    /// Parse registry address
+    fn parse_registry_address(&self) -> Result<Address, String> {
+        let addr = self.registry_address().ok_or("Validation Registry not configured")?;
+        Address::from_str(addr).map_err(|e| format!("Invalid registry address: {}", e))
+    }
+
+    /// Get all validation request hashes for an agent
+    pub async fn get_agent_validations(&self, agent_id: u64) -> Result<Vec<String>, String> {
+        if !self.is_deployed() {
+            return Err("Validation Registry not deployed".to_string());
+        }
+
+        let rpc = self.get_free_rpc()?;
+        let registry_addr = self.parse_registry_address()?;
+        let result = rpc.eth_call(registry_addr, &encode_get_agent_validations(agent_id)).await?;
+
+        decode_bytes32_array_result(&result)
+    }
+
+    /// Get the status of a single validation request
+    pub async fn get_validation_status(&self, request_hash: &str) -> Result<ValidationRecord, String> {
+        if !self.is_deployed() {
+            return Err("Validation Registry not deployed".to_string());
+        }
+
+        let hash = parse_bytes32(request_hash)?;
+        let rpc = self.get_free_rpc()?;
+        let registry_addr = self.parse_registry_address()?;
+        let result = rpc.eth_call(registry_addr, &encode_get_validation_status(&hash)).await?;
+
+        let (validator_address, agent_id, response, response_hash, tag, last_update) =
+            decode_validation_status_result(&result)?;
+
+        Ok(ValidationRecord {
+            request_hash: format!("0x{}", hex::encode(hash)),
+            agent_id,
+            validator_address,
+            response,
+            response_hash,
+            tag,
+            last_update,
+        })
+    }
+
+    /// Check whether an agent holds a passing validation of the given type.
+    /// Scans the most recent `MAX_VALIDATIONS_SCANNED` requests.
+    pub async fn check(
+        &self,
+        agent_id: u64,
+        validation_type: &str,
+        min_response: u8,
+    ) -> Result<ValidationCheck, String> {
+        let hashes = self.get_agent_validations(agent_id).await?;
+        let total = hashes.len();
+
+        let mut records = Vec::new();
+        for hash in hashes.iter().rev().take(MAX_VALIDATIONS_SCANNED) {
+            match self.get_validation_status(hash).await {
+                Ok(record) => records.push(record),
+                Err(e) => log::warn!("[eip8004/validation] Failed to read {}: {}", hash, e),
+            }
+        }
+
+        Ok(summarize(agent_id, validation_type, min_response, records, total))
+    }
+}
+
+/// Pick the best record matching `validation_type` (case-insensitive; empty matches any)
+pub fn summarize(
+    agent_id: u64,
+    validation_type: &str,
+    min_response: u8,
+    records: Vec<ValidationRecord>,
+    total: usize,
+) -> ValidationCheck {
+    let wanted = validation_type.trim();
+    let matching: Vec<ValidationRecord> = records
+        .into_iter()
+        .filter(|r| wanted.is_empty() || r.tag.eq_ignore_ascii_case(wanted))
+        .collect();
+
+    let best_passing = matching
+        .iter()
+        .filter(|r| r.passes(min_response))
+        .max_by_key(|r| (r.response, r.last_update))
+        .cloned();
+    let valid = best_passing.is_some();
+    let record = best_passing.or_else(|| matching.iter().max_by_key(|r| r.last_update).cloned());
+
+    ValidationCheck {
+        agent_id,
+        validation_type: wanted.to_string(),
+        min_response,
+        valid,
+        record,
+        matching: matching.len(),
+        total,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(tag: &str, response: u8, last_update: u64) -> ValidationRecord {
+        ValidationRecord {
+            request_hash: format!("0x{:064x}", last_update),
+            agent_id: 7,
+            validator_address: "0x0000000000000000000000000000000000000001".to_string(),
+            response,
+            response_hash: format!("0x{}", "00".repeat(32)),
+            tag: tag.to_string(),
+            last_update,
+        }
+    }
+
+    #[test]
+    fn test_summarize_picks_best_passing_record() {
+        let check = summarize(
+            7,
+            "Task-Completion",
+            DEFAULT_MIN_RESPONSE,
+            vec![
+                record("task-completion", 40, 300),
+                record("task-completion", 90, 100),
+                record("other", 100, 200),
+            ],
+            3,
+        );
+        assert!(check.valid);
+        assert_eq!(check.matching, 2);
+        assert_eq!(check.record.unwrap().response, 90);
+    }
+
+    #[test]
+    fn test_summarize_pending_and_failing_records_are_not_valid() {
+        let check = summarize(
+            7,
+            "task-completion",
+            DEFAULT_MIN_RESPONSE,
+            vec![record("task-completion", 100, 0), record("task-completion", 10, 50)],
+            2,
+        );
+        assert!(!check.valid);
+        assert_eq!(check.record.unwrap().last_update, 50);
+
+        let none = summarize(7, "kyc", DEFAULT_MIN_RESPONSE, vec![], 0);
+        assert!(!none.valid && none.record.is_none());
+    }
+
+    #[test]
+    fn test_not_deployed_without_address() {
+        let registry = ValidationRegistry::new(Eip8004Config::base_mainnet());
+        assert!(!registry.is_deployed());
+        assert!(registry.registry_address().is_none());
+    }
+}
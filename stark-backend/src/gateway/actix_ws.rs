@@ -393,6 +393,16 @@ async fn dispatch_method(
                 .map_err(|e| RpcError::invalid_params(format!("Invalid params: {}", e)))?;
             methods::handle_tx_queue_deny(params, tx_queue.clone(), broadcaster.clone()).await
         }
+        "sign_message.confirm" => {
+            let params: methods::SignRequestParams = serde_json::from_value(request.params.clone())
+                .map_err(|e| RpcError::invalid_params(format!("Invalid params: {}", e)))?;
+            methods::handle_sign_message_confirm(params, broadcaster.clone(), wallet_provider.clone()).await
+        }
+        "sign_message.deny" => {
+            let params: methods::SignRequestParams = serde_json::from_value(request.params.clone())
+                .map_err(|e| RpcError::invalid_params(format!("Invalid params: {}", e)))?;
+            methods::handle_sign_message_deny(params, broadcaster.clone()).await
+        }
         _ => Err(RpcError::method_not_found()),
     }
 }
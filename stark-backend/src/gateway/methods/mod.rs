@@ -1,7 +1,9 @@
 pub mod channels;
+pub mod sign_message;
 pub mod status;
 pub mod tx_queue;
 
 pub use channels::*;
+pub use sign_message::*;
 pub use status::*;
 pub use tx_queue::*;
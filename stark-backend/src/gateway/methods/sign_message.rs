@@ -0,0 +1,72 @@
+//! Message signing RPC methods for partner mode confirmation
+//!
+//! Handles user approval/denial of queued signature requests via the frontend modal.
+
+use crate::gateway::events::EventBroadcaster;
+use crate::gateway::protocol::{GatewayEvent, RpcError};
+use crate::tools::builtin::cryptocurrency::sign_message::{
+    approve_sign_request, deny_sign_request, SignRequestStatus,
+};
+use crate::wallet::WalletProvider;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+#[derive(Debug, Deserialize)]
+pub struct SignRequestParams {
+    pub uuid: String,
+    pub channel_id: i64,
+}
+
+/// Handle sign_message.confirm RPC method
+/// Signs the queued payload; the agent collects the signature by request id
+pub async fn handle_sign_message_confirm(
+    params: SignRequestParams,
+    broadcaster: Arc<EventBroadcaster>,
+    wallet_provider: Option<Arc<dyn WalletProvider>>,
+) -> Result<Value, RpcError> {
+    log::info!("[sign_message.confirm] Approving signature request {}", params.uuid);
+
+    let request = approve_sign_request(&params.uuid, wallet_provider)
+        .await
+        .map_err(|e| RpcError::new(-32000, e))?;
+    let SignRequestStatus::Signed { address, signature } = request.status else {
+        return Err(RpcError::new(-32000, format!("Signature request {} was not signed", params.uuid)));
+    };
+
+    broadcaster.broadcast(GatewayEvent::sign_message_signed(
+        params.channel_id, &params.uuid, &signature
+    ));
+
+    log::info!("[sign_message.confirm] Signature request {} signed by {}", params.uuid, address);
+
+    Ok(json!({
+        "success": true,
+        "uuid": params.uuid,
+        "address": address,
+        "signature": signature
+    }))
+}
+
+/// Handle sign_message.deny RPC method
+/// Marks the request denied so the agent learns it was rejected
+pub async fn handle_sign_message_deny(
+    params: SignRequestParams,
+    broadcaster: Arc<EventBroadcaster>,
+) -> Result<Value, RpcError> {
+    log::info!("[sign_message.deny] Denying signature request {}", params.uuid);
+
+    if deny_sign_request(&params.uuid).is_none() {
+        return Err(RpcError::new(-32000, format!("Signature request {} is not pending", params.uuid)));
+    }
+
+    broadcaster.broadcast(GatewayEvent::sign_message_denied(
+        params.channel_id, &params.uuid
+    ));
+
+    Ok(json!({
+        "success": true,
+        "uuid": params.uuid,
+        "action": "denied"
+    }))
+}
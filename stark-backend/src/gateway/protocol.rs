@@ -8,20 +8,25 @@ pub enum EventType {
     // Channel events
     ChannelStarted,
     ChannelStopped,
+    ChannelReconnecting, // Listener lost its connection and is retrying
+    ChannelReconnected,  // Listener's connection is back after a drop
     ChannelError,
     ChannelMessage,
     // Agent events
     AgentResponse,
+    AgentPartialResponse, // Assistant text written between tool rounds, before the final reply
     AgentToolCall,     // Real-time tool call notification for chat display
     AgentModeChange,   // Multi-agent mode transition (Explore/Plan/Perform)
     AgentSubtypeChange, // Agent subtype change (Finance/CodeEngineer)
     AgentThinking,     // Progress update during long AI calls
     AgentError,        // Error notification (timeout, etc.)
     AgentWarning,      // Warning when agent tries to skip tool calls
+    AgentHeartbeat,    // Periodic liveness signal while a dispatch is running
     // Tool events
     ToolExecution,
     ToolResult,
     ToolWaiting,  // Tool is waiting for retry after transient error
+    ToolOutputChunk,  // Live output lines from a running tool (e.g. foreground exec)
     // Skill events
     SkillInvoked,
     // Execution progress events
@@ -54,6 +59,7 @@ pub enum EventType {
     SubagentSpawned,
     SubagentCompleted,
     SubagentFailed,
+    SubagentProgress,  // Sub-agent is calling / finished a tool (streamed to parent channel)
     // Streaming events
     StreamStart,
     StreamContentDelta,
@@ -81,6 +87,10 @@ pub enum EventType {
     TxQueueConfirmationRequired,  // Pending tx needs user confirmation
     TxQueueConfirmed,             // User confirmed, tx broadcast
     TxQueueDenied,                // User denied, tx deleted
+    // Message signing confirmation events (partner mode)
+    SignMessageConfirmationRequired,  // Signature request needs user approval
+    SignMessageSigned,                // User approved, message signed
+    SignMessageDenied,                // User denied the signature request
     // Context management events
     ContextCompacting,  // Session context is being compacted to reduce token usage
     // Telemetry events
@@ -93,18 +103,23 @@ impl EventType {
         match self {
             Self::ChannelStarted => "channel.started",
             Self::ChannelStopped => "channel.stopped",
+            Self::ChannelReconnecting => "channel.reconnecting",
+            Self::ChannelReconnected => "channel.reconnected",
             Self::ChannelError => "channel.error",
             Self::ChannelMessage => "channel.message",
             Self::AgentResponse => "agent.response",
+            Self::AgentPartialResponse => "agent.partial_response",
             Self::AgentToolCall => "agent.tool_call",
             Self::AgentModeChange => "agent.mode_change",
             Self::AgentSubtypeChange => "agent.subtype_change",
             Self::AgentThinking => "agent.thinking",
             Self::AgentError => "agent.error",
             Self::AgentWarning => "agent.warning",
+            Self::AgentHeartbeat => "agent.heartbeat",
             Self::ToolExecution => "tool.execution",
             Self::ToolResult => "tool.result",
             Self::ToolWaiting => "tool.waiting",
+            Self::ToolOutputChunk => "tool.output_chunk",
             Self::SkillInvoked => "skill.invoked",
             Self::ExecutionStarted => "execution.started",
             Self::ExecutionThinking => "execution.thinking",
@@ -128,6 +143,7 @@ impl EventType {
             Self::SubagentSpawned => "subagent.spawned",
             Self::SubagentCompleted => "subagent.completed",
             Self::SubagentFailed => "subagent.failed",
+            Self::SubagentProgress => "subagent.progress",
             Self::StreamStart => "stream.start",
             Self::StreamContentDelta => "stream.content_delta",
             Self::StreamToolStart => "stream.tool_start",
@@ -149,6 +165,9 @@ impl EventType {
             Self::TxQueueConfirmationRequired => "tx_queue.confirmation_required",
             Self::TxQueueConfirmed => "tx_queue.confirmed",
             Self::TxQueueDenied => "tx_queue.denied",
+            Self::SignMessageConfirmationRequired => "sign_message.confirmation_required",
+            Self::SignMessageSigned => "sign_message.signed",
+            Self::SignMessageDenied => "sign_message.denied",
             Self::ContextCompacting => "context.compacting",
             Self::SpanEmitted => "telemetry.span_emitted",
             Self::RolloutStatusChange => "telemetry.rollout_status",
@@ -284,6 +303,31 @@ impl GatewayEvent {
         )
     }
 
+    /// `attempt` counts reconnect attempts since the connection was last healthy
+    pub fn channel_reconnecting(channel_id: i64, channel_type: &str, attempt: u32, reason: &str) -> Self {
+        Self::new(
+            EventType::ChannelReconnecting,
+            serde_json::json!({
+                "channel_id": channel_id,
+                "channel_type": channel_type,
+                "attempt": attempt,
+                "reason": reason
+            }),
+        )
+    }
+
+    /// `attempts` is how many reconnect attempts it took
+    pub fn channel_reconnected(channel_id: i64, channel_type: &str, attempts: u32) -> Self {
+        Self::new(
+            EventType::ChannelReconnected,
+            serde_json::json!({
+                "channel_id": channel_id,
+                "channel_type": channel_type,
+                "attempts": attempts
+            }),
+        )
+    }
+
     pub fn channel_error(channel_id: i64, error: &str) -> Self {
         Self::new(
             EventType::ChannelError,
@@ -311,17 +355,46 @@ impl GatewayEvent {
         )
     }
 
-    pub fn agent_response(channel_id: i64, to: &str, text: &str) -> Self {
+    pub fn agent_response(channel_id: i64, chat_id: Option<&str>, to: &str, text: &str) -> Self {
         Self::new(
             EventType::AgentResponse,
             serde_json::json!({
                 "channel_id": channel_id,
+                "chat_id": chat_id,
                 "to": to,
                 "text": text
             }),
         )
     }
 
+    /// Assistant text produced alongside a round of tool calls, sent before the final response.
+    /// `round` counts tool rounds within the dispatch, starting at 1.
+    pub fn agent_partial_response(channel_id: i64, chat_id: Option<&str>, text: &str, round: usize) -> Self {
+        Self::new(
+            EventType::AgentPartialResponse,
+            serde_json::json!({
+                "channel_id": channel_id,
+                "chat_id": chat_id,
+                "text": text,
+                "round": round
+            }),
+        )
+    }
+
+    /// Liveness signal sent periodically while a dispatch runs. `steps` counts the tool
+    /// calls made so far; when these stop arriving the dispatch has stalled or ended.
+    pub fn agent_heartbeat(channel_id: i64, chat_id: Option<&str>, elapsed_secs: u64, steps: u64) -> Self {
+        Self::new(
+            EventType::AgentHeartbeat,
+            serde_json::json!({
+                "channel_id": channel_id,
+                "chat_id": chat_id,
+                "elapsed_secs": elapsed_secs,
+                "steps": steps
+            }),
+        )
+    }
+
     /// Emit a tool call notification for real-time display in chat
     /// The `chat_id` is the platform-specific conversation ID (e.g., Discord channel snowflake)
     pub fn agent_tool_call(channel_id: i64, chat_id: Option<&str>, tool_name: &str, parameters: &Value) -> Self {
@@ -445,6 +518,20 @@ impl GatewayEvent {
         )
     }
 
+    /// Live output from a tool that is still running (one or more lines, newline-separated)
+    pub fn tool_output_chunk(channel_id: i64, tool_name: &str, lines: &str, stream: &str) -> Self {
+        Self::new(
+            EventType::ToolOutputChunk,
+            serde_json::json!({
+                "channel_id": channel_id,
+                "tool_name": tool_name,
+                "lines": lines,
+                "stream": stream,  // "stdout" or "stderr"
+                "timestamp": chrono::Utc::now().to_rfc3339()
+            }),
+        )
+    }
+
     pub fn skill_invoked(channel_id: i64, skill_name: &str) -> Self {
         Self::new(
             EventType::SkillInvoked,
@@ -760,6 +847,52 @@ impl GatewayEvent {
         )
     }
 
+    /// Signature request needs approval - partner mode
+    pub fn sign_message_confirmation_required(
+        channel_id: i64,
+        uuid: &str,
+        scheme: &str,
+        address: &str,
+        payload: &Value,
+    ) -> Self {
+        Self::new(
+            EventType::SignMessageConfirmationRequired,
+            serde_json::json!({
+                "channel_id": channel_id,
+                "uuid": uuid,
+                "scheme": scheme,
+                "address": address,
+                "payload": payload,
+                "timestamp": chrono::Utc::now().to_rfc3339()
+            }),
+        )
+    }
+
+    /// Signature request approved - user confirmed, message was signed
+    pub fn sign_message_signed(channel_id: i64, uuid: &str, signature: &str) -> Self {
+        Self::new(
+            EventType::SignMessageSigned,
+            serde_json::json!({
+                "channel_id": channel_id,
+                "uuid": uuid,
+                "signature": signature,
+                "timestamp": chrono::Utc::now().to_rfc3339()
+            }),
+        )
+    }
+
+    /// Signature request denied - user rejected it
+    pub fn sign_message_denied(channel_id: i64, uuid: &str) -> Self {
+        Self::new(
+            EventType::SignMessageDenied,
+            serde_json::json!({
+                "channel_id": channel_id,
+                "uuid": uuid,
+                "timestamp": chrono::Utc::now().to_rfc3339()
+            }),
+        )
+    }
+
     /// x402 payment made
     pub fn x402_payment(
         channel_id: i64,
@@ -65,6 +65,18 @@ impl DiscordTippingClient {
         }
     }
 
+    pub async fn list_profiles_by_address(
+        &self,
+        address: &str,
+    ) -> Result<Vec<DiscordUserProfile>, String> {
+        let req = GetProfileByAddressRequest {
+            address: address.to_string(),
+        };
+        let resp: RpcResponse<Vec<DiscordUserProfile>> =
+            self.post("/rpc/profiles/by_address", &req).await?;
+        resp.data.ok_or_else(|| resp.error.unwrap_or_else(|| "Unknown error".to_string()))
+    }
+
     pub async fn register_address(
         &self,
         discord_user_id: &str,
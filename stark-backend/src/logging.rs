@@ -0,0 +1,122 @@
+//! Logger setup: env_logger's text format by default, single-line JSON with `LOG_FORMAT=json`.
+//!
+//! In JSON mode each record carries `timestamp`, `level`, `target` and `message`,
+//! plus `channel_id` / `user_id` while the record is logged from inside a dispatch.
+//! The context is task-local, so concurrent dispatches sharing a worker thread
+//! never see each other's channel or user.
+
+use std::future::Future;
+use std::io::Write;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::{json, Map, Value};
+
+use crate::config;
+
+#[derive(Debug, Clone)]
+struct LogContext {
+    channel_id: i64,
+    user_id: String,
+}
+
+tokio::task_local! {
+    /// Channel/user of the dispatch running in this task, attached to JSON log lines.
+    static LOG_CONTEXT: LogContext;
+}
+
+/// Run `future` with `channel_id` / `user_id` attached to the log lines it emits.
+/// Tasks it spawns don't inherit the context.
+pub async fn with_log_context<F: Future>(channel_id: i64, user_id: &str, future: F) -> F::Output {
+    let context = LogContext {
+        channel_id,
+        user_id: user_id.to_string(),
+    };
+    LOG_CONTEXT.scope(context, future).await
+}
+
+/// Install the global logger. `RUST_LOG` filtering works the same in both formats.
+pub fn init() {
+    if !config::log_format_json() {
+        env_logger::init();
+        return;
+    }
+
+    env_logger::Builder::from_default_env()
+        .format(|buf, record| writeln!(buf, "{}", render_json(record, Utc::now())))
+        .init();
+}
+
+/// Render one record as a single-line JSON object
+fn render_json(record: &log::Record, now: DateTime<Utc>) -> String {
+    let mut line = Map::new();
+    line.insert("timestamp".into(), json!(now.to_rfc3339_opts(SecondsFormat::Millis, true)));
+    line.insert("level".into(), json!(record.level().as_str()));
+    line.insert("target".into(), json!(record.target()));
+    line.insert("message".into(), json!(record.args().to_string()));
+    let _ = LOG_CONTEXT.try_with(|ctx| {
+        line.insert("channel_id".into(), json!(ctx.channel_id));
+        line.insert("user_id".into(), json!(ctx.user_id));
+    });
+    Value::Object(line).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use log::{Level, Record};
+
+    fn ts() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-03-02T09:30:00Z").unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_render_json_fields() {
+        let line = render_json(
+            &Record::builder()
+                .args(format_args!("hello \"{}\"\nworld", 42))
+                .level(Level::Warn)
+                .target("stark::test")
+                .build(),
+            ts(),
+        );
+        assert!(!line.contains('\n'));
+        let parsed: Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(parsed["timestamp"], "2026-03-02T09:30:00.000Z");
+        assert_eq!(parsed["level"], "WARN");
+        assert_eq!(parsed["target"], "stark::test");
+        assert_eq!(parsed["message"], "hello \"42\"\nworld");
+        assert!(parsed.get("channel_id").is_none());
+    }
+
+    fn render_dispatching() -> Value {
+        let line = render_json(
+            &Record::builder().args(format_args!("dispatching")).level(Level::Info).target("t").build(),
+            ts(),
+        );
+        serde_json::from_str(&line).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_render_json_includes_context() {
+        let parsed = with_log_context(7, "user-1", async { render_dispatching() }).await;
+        assert_eq!(parsed["channel_id"], 7);
+        assert_eq!(parsed["user_id"], "user-1");
+        assert!(render_dispatching().get("channel_id").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_context_stays_with_its_task() {
+        let (a, b) = tokio::join!(
+            with_log_context(1, "alice", async {
+                tokio::task::yield_now().await;
+                render_dispatching()
+            }),
+            with_log_context(2, "bob", async {
+                tokio::task::yield_now().await;
+                render_dispatching()
+            }),
+        );
+        assert_eq!(a["user_id"], "alice");
+        assert_eq!(b["user_id"], "bob");
+    }
+}
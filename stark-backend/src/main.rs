@@ -1,6 +1,5 @@
-use actix_cors::Cors;
 use actix_files::{Files, NamedFile};
-use actix_web::{middleware::Logger, web, App, HttpServer};
+use actix_web::{dev::Service, middleware::Logger, web, App, HttpServer};
 use dotenv::dotenv;
 use std::sync::Arc;
 
@@ -18,6 +17,7 @@ mod domain_types;
 mod execution;
 mod gateway;
 mod integrations;
+mod logging;
 mod middleware;
 mod models;
 mod qmd_memory;
@@ -38,6 +38,7 @@ mod keystore_client;
 mod identity_client;
 mod modules;
 mod telemetry;
+mod metrics;
 
 use channels::{ChannelManager, MessageDispatcher, SafeModeChannelRateLimiter};
 use tx_queue::TxQueueManager;
@@ -849,7 +850,7 @@ fn start_service_binary(exe_path: &std::path::Path, name: &str, port: u16, envs:
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     dotenv().ok();
-    env_logger::init();
+    logging::init();
 
     // Load presets and tokens from config directory
     // Check ./config first, then ../config (for running from subdirectory)
@@ -887,20 +888,25 @@ async fn main() -> std::io::Result<()> {
     // Initialize disk quota manager
     let disk_quota_mb = config::disk_quota_mb();
     let disk_quota: Option<Arc<disk_quota::DiskQuotaManager>> = if disk_quota_mb > 0 {
+        let db_dir = {
+            let db_url = std::env::var("DATABASE_URL")
+                .unwrap_or_else(|_| config::defaults::DATABASE_URL.to_string());
+            let db_path = std::path::PathBuf::from(&db_url);
+            db_path.parent().map(|p| p.to_path_buf()).unwrap_or_else(|| config::backend_dir().join(".db"))
+        };
         let tracked_dirs = vec![
             std::path::PathBuf::from(config::workspace_dir()),
             std::path::PathBuf::from(config::journal_dir()),
             std::path::PathBuf::from(config::memory_config().memory_dir),
             std::path::PathBuf::from(config::soul_dir()),
             // Include the database directory
-            {
-                let db_url = std::env::var("DATABASE_URL")
-                    .unwrap_or_else(|_| config::defaults::DATABASE_URL.to_string());
-                let db_path = std::path::PathBuf::from(&db_url);
-                db_path.parent().map(|p| p.to_path_buf()).unwrap_or_else(|| config::backend_dir().join(".db"))
-            },
+            db_dir.clone(),
         ];
-        let manager = Arc::new(disk_quota::DiskQuotaManager::new(Some(disk_quota_mb), tracked_dirs));
+        let manager = Arc::new(
+            disk_quota::DiskQuotaManager::new(Some(disk_quota_mb), tracked_dirs)
+                .with_channel_quota_mb(config::channel_disk_quota_mb())
+                .with_index_file(db_dir.join("disk_quota_channels.json")),
+        );
         log::info!("{}", manager.status_line());
         Some(manager)
     } else {
@@ -992,12 +998,31 @@ async fn main() -> std::io::Result<()> {
     for module in module_registry.available_modules() {
         if module.has_tools() {
             for tool in module.create_tools() {
-                log::info!("[MODULE] Registered tool: {} (from {})", tool.name(), module.name());
-                tool_registry_mut.register(tool);
+                let name = tool.name();
+                if tool_registry_mut.register(tool) {
+                    log::info!("[MODULE] Registered tool: {} (from {})", name, module.name());
+                }
             }
         }
     }
 
+    if config.safe_mode {
+        log::warn!(
+            "SAFE MODE: side-effecting tools (exec, posting, transfers, messaging) are disabled on every channel. \
+             Unset {} / drop {} and restart to leave safe mode.",
+            config::env_vars::SAFE_MODE,
+            config::SAFE_MODE_FLAG
+        );
+        tool_registry_mut.set_global_safe_mode(true);
+    }
+
+    for (group_name, secs) in config::tool_group_timeouts() {
+        match tools::ToolGroup::from_str(&group_name) {
+            Some(group) => tool_registry_mut.set_group_timeout(group, std::time::Duration::from_secs(secs)),
+            None => log::warn!("Ignoring tool timeout for unknown group '{}'", group_name),
+        }
+    }
+
     let tool_registry = Arc::new(tool_registry_mut);
     log::info!("Registered {} tools", tool_registry.len());
 
@@ -1271,12 +1296,17 @@ async fn main() -> std::io::Result<()> {
     let frontend_dist = frontend_dist.to_string();
     let dev_mode = dev_mode;
 
+    let cors_policy = middleware::cors::CorsPolicy::from_env();
+    if cors_policy.allow_any_origin {
+        log::warn!("CORS: any origin may call the API (STARK_CORS_ALLOWED_ORIGINS=*)");
+    } else if cors_policy.allows_cross_origin() {
+        log::info!("CORS: allowed origins: {}", cors_policy.allowed_origins.join(", "));
+    } else {
+        log::info!("CORS: cross-origin requests disabled (set STARK_CORS_ALLOWED_ORIGINS to allow a frontend on another origin)");
+    }
+
     let server = HttpServer::new(move || {
-        let cors = Cors::default()
-            .allow_any_origin()
-            .allow_any_method()
-            .allow_any_header()
-            .max_age(3600);
+        let cors = cors_policy.build();
 
         let mut app = App::new()
             .app_data(web::Data::new(AppState {
@@ -1307,9 +1337,22 @@ async fn main() -> std::io::Result<()> {
             .app_data(web::Data::new(Arc::clone(&bcast)))
             .app_data(web::Data::new(Arc::clone(&tx_q)))
             .app_data(web::Data::new(wallet_prov.clone()))
+            .wrap_fn(|req, srv| {
+                // Request latency by matched route, for GET /metrics
+                let started = std::time::Instant::now();
+                let method = req.method().to_string();
+                let response = srv.call(req);
+                async move {
+                    let response = response.await?;
+                    let route = response.request().match_pattern().unwrap_or_else(|| "unmatched".to_string());
+                    metrics::record_http(&method, &route, response.status().as_u16(), started.elapsed());
+                    Ok(response)
+                }
+            })
             .wrap(Logger::default())
             .wrap(cors)
             .configure(controllers::health::config_routes)
+            .configure(controllers::metrics::config)
             .configure(controllers::auth::config)
             .configure(controllers::dashboard::config)
             .configure(controllers::chat::config)
@@ -1334,6 +1377,7 @@ async fn main() -> std::io::Result<()> {
             .configure(controllers::modules::config)
             .configure(controllers::memory::config)
             .configure(controllers::system::config)
+            .configure(controllers::config_snapshot::config)
             .configure(controllers::well_known::config)
             .configure(controllers::x402_limits::config)
             .configure(controllers::telemetry::config)
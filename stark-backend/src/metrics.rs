@@ -0,0 +1,145 @@
+//! Operational metrics, exposed in Prometheus text format at `GET /metrics`
+//!
+//! Every metric is registered once in [`METRICS`]; call sites use the `record_*`
+//! helpers below rather than touching the collectors directly. Labels are kept to
+//! small, bounded sets (channel types, tool names from the registry, matched route
+//! patterns) so a scrape never grows with user input.
+//!
+//! These complement the telemetry spans in `crate::telemetry`, which record
+//! per-rollout detail for rewards rather than aggregate service health.
+
+use once_cell::sync::Lazy;
+use prometheus::{Encoder, HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry, TextEncoder};
+use std::time::Duration;
+
+/// Latency buckets in seconds, from fast HTTP calls up to long agent runs
+const LATENCY_BUCKETS: &[f64] = &[0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0];
+
+pub struct Metrics {
+    registry: Registry,
+    dispatches: IntCounterVec,
+    dispatch_duration: HistogramVec,
+    ai_request_duration: HistogramVec,
+    tool_executions: IntCounterVec,
+    tool_duration: HistogramVec,
+    http_request_duration: HistogramVec,
+}
+
+pub static METRICS: Lazy<Metrics> = Lazy::new(Metrics::new);
+
+fn outcome(ok: bool) -> &'static str {
+    if ok { "ok" } else { "error" }
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new();
+
+        let counter = |name: &str, help: &str, labels: &[&str]| {
+            let c = IntCounterVec::new(Opts::new(name, help), labels).expect("valid counter");
+            registry.register(Box::new(c.clone())).expect("unique metric name");
+            c
+        };
+        let histogram = |name: &str, help: &str, labels: &[&str]| {
+            let opts = HistogramOpts::new(name, help).buckets(LATENCY_BUCKETS.to_vec());
+            let h = HistogramVec::new(opts, labels).expect("valid histogram");
+            registry.register(Box::new(h.clone())).expect("unique metric name");
+            h
+        };
+
+        Metrics {
+            dispatches: counter(
+                "stark_dispatches_total",
+                "Messages dispatched to the agent",
+                &["channel_type", "outcome"],
+            ),
+            dispatch_duration: histogram(
+                "stark_dispatch_duration_seconds",
+                "Time to handle a dispatched message end to end",
+                &["channel_type"],
+            ),
+            ai_request_duration: histogram(
+                "stark_ai_request_duration_seconds",
+                "Latency of individual AI provider requests",
+                &["provider", "outcome"],
+            ),
+            tool_executions: counter(
+                "stark_tool_executions_total",
+                "Tool executions",
+                &["tool", "outcome"],
+            ),
+            tool_duration: histogram(
+                "stark_tool_duration_seconds",
+                "Tool execution time",
+                &["tool"],
+            ),
+            http_request_duration: histogram(
+                "stark_http_request_duration_seconds",
+                "HTTP request latency by route",
+                &["method", "route", "status"],
+            ),
+            registry,
+        }
+    }
+}
+
+/// A message went through `MessageDispatcher::dispatch`
+pub fn record_dispatch(channel_type: &str, ok: bool, elapsed: Duration) {
+    METRICS.dispatches.with_label_values(&[channel_type, outcome(ok)]).inc();
+    METRICS
+        .dispatch_duration
+        .with_label_values(&[channel_type])
+        .observe(elapsed.as_secs_f64());
+}
+
+/// One request to an AI provider (each provider a fallback chain tries counts separately)
+pub fn record_ai_request(provider: &str, ok: bool, elapsed: Duration) {
+    METRICS
+        .ai_request_duration
+        .with_label_values(&[provider, outcome(ok)])
+        .observe(elapsed.as_secs_f64());
+}
+
+/// A tool ran through the registry. Pass "unknown" for names the registry doesn't have.
+pub fn record_tool(tool: &str, ok: bool, elapsed: Duration) {
+    METRICS.tool_executions.with_label_values(&[tool, outcome(ok)]).inc();
+    METRICS
+        .tool_duration
+        .with_label_values(&[tool])
+        .observe(elapsed.as_secs_f64());
+}
+
+/// An HTTP request finished. `route` is the matched pattern (e.g. `/api/channels/{id}`),
+/// never the raw path.
+pub fn record_http(method: &str, route: &str, status: u16, elapsed: Duration) {
+    METRICS
+        .http_request_duration
+        .with_label_values(&[method, route, &status.to_string()])
+        .observe(elapsed.as_secs_f64());
+}
+
+/// Everything registered, in Prometheus text exposition format
+pub fn render() -> Result<String, String> {
+    let mut buffer = Vec::new();
+    TextEncoder::new()
+        .encode(&METRICS.registry.gather(), &mut buffer)
+        .map_err(|e| format!("Failed to encode metrics: {}", e))?;
+    String::from_utf8(buffer).map_err(|e| format!("Metrics output is not UTF-8: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_includes_recorded_metrics() {
+        record_dispatch("discord", true, Duration::from_millis(1200));
+        record_tool("web_fetch", false, Duration::from_millis(30));
+        record_http("GET", "/api/channels/{id}", 200, Duration::from_millis(5));
+
+        let output = render().unwrap();
+        assert!(output.contains(r#"stark_dispatches_total{channel_type="discord",outcome="ok"}"#));
+        assert!(output.contains(r#"stark_tool_executions_total{outcome="error",tool="web_fetch"}"#));
+        assert!(output.contains(r#"route="/api/channels/{id}""#));
+    }
+}
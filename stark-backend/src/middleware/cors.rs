@@ -0,0 +1,187 @@
+// CORS policy for the HTTP API
+// Built from STARK_CORS_* environment variables and applied to every route in main.rs.
+// With no allowed origins configured, cross-origin browser requests are rejected; the bundled
+// frontend is served from the same origin (or proxied in dev) and is unaffected.
+
+use actix_cors::Cors;
+use actix_web::http::{header::HeaderName, Method};
+
+use crate::config;
+
+/// Parsed CORS settings
+#[derive(Debug, Clone, PartialEq)]
+pub struct CorsPolicy {
+    /// Exact origins allowed to call the API (scheme://host[:port])
+    pub allowed_origins: Vec<String>,
+    /// `*` was configured: any origin is allowed
+    pub allow_any_origin: bool,
+    pub allowed_methods: Vec<Method>,
+    pub allowed_headers: Vec<HeaderName>,
+    pub allow_credentials: bool,
+    pub max_age_secs: usize,
+}
+
+/// Normalize an origin, rejecting anything that isn't a bare http(s) origin
+fn parse_origin(value: &str) -> Result<String, String> {
+    let url = url::Url::parse(value).map_err(|e| format!("'{}': {}", value, e))?;
+    if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+        return Err(format!("'{}': origin must be http(s)://host[:port]", value));
+    }
+    if url.path() != "/" || url.query().is_some() || url.fragment().is_some() {
+        return Err(format!("'{}': origin must not include a path, query, or fragment", value));
+    }
+    Ok(url.origin().ascii_serialization())
+}
+
+fn split_list(value: &str) -> impl Iterator<Item = &str> {
+    value.split(',').map(str::trim).filter(|s| !s.is_empty())
+}
+
+impl CorsPolicy {
+    /// Build the policy from raw setting values; invalid entries are skipped and reported as warnings
+    pub fn parse(
+        origins: Option<&str>,
+        methods: Option<&str>,
+        headers: Option<&str>,
+        allow_credentials: bool,
+        max_age_secs: usize,
+    ) -> (Self, Vec<String>) {
+        let mut warnings = Vec::new();
+        let mut allowed_origins = Vec::new();
+        let mut allow_any_origin = false;
+
+        for origin in split_list(origins.unwrap_or("")) {
+            if origin == "*" {
+                allow_any_origin = true;
+                continue;
+            }
+            match parse_origin(origin) {
+                Ok(o) if !allowed_origins.contains(&o) => allowed_origins.push(o),
+                Ok(_) => {}
+                Err(e) => warnings.push(format!("Ignoring invalid CORS origin {}", e)),
+            }
+        }
+
+        let allowed_methods = split_list(methods.unwrap_or(config::defaults::CORS_ALLOWED_METHODS))
+            .filter_map(|m| match Method::from_bytes(m.to_uppercase().as_bytes()) {
+                Ok(method) => Some(method),
+                Err(_) => {
+                    warnings.push(format!("Ignoring invalid CORS method '{}'", m));
+                    None
+                }
+            })
+            .collect();
+
+        let allowed_headers = split_list(headers.unwrap_or(config::defaults::CORS_ALLOWED_HEADERS))
+            .filter_map(|h| match HeaderName::from_bytes(h.as_bytes()) {
+                Ok(name) => Some(name),
+                Err(_) => {
+                    warnings.push(format!("Ignoring invalid CORS header '{}'", h));
+                    None
+                }
+            })
+            .collect();
+
+        // Browsers refuse credentialed responses for a wildcard origin, so don't pretend to allow it
+        let allow_credentials = if allow_credentials && allow_any_origin {
+            warnings.push("CORS credentials are disabled because allowed origins is '*'".to_string());
+            false
+        } else {
+            allow_credentials
+        };
+
+        (
+            Self {
+                allowed_origins,
+                allow_any_origin,
+                allowed_methods,
+                allowed_headers,
+                allow_credentials,
+                max_age_secs,
+            },
+            warnings,
+        )
+    }
+
+    /// Load the policy from the environment, logging any ignored entries
+    pub fn from_env() -> Self {
+        let (policy, warnings) = Self::parse(
+            config::cors_allowed_origins().as_deref(),
+            config::cors_allowed_methods().as_deref(),
+            config::cors_allowed_headers().as_deref(),
+            config::cors_allow_credentials(),
+            config::cors_max_age_secs(),
+        );
+        for warning in warnings {
+            log::warn!("{}", warning);
+        }
+        policy
+    }
+
+    /// Whether any cross-origin caller is allowed at all
+    pub fn allows_cross_origin(&self) -> bool {
+        self.allow_any_origin || !self.allowed_origins.is_empty()
+    }
+
+    /// Build the actix middleware for this policy
+    pub fn build(&self) -> Cors {
+        let mut cors = Cors::default()
+            .allowed_methods(self.allowed_methods.clone())
+            .allowed_headers(self.allowed_headers.clone())
+            .max_age(self.max_age_secs);
+
+        if self.allow_any_origin {
+            cors = cors.allow_any_origin();
+        } else {
+            for origin in &self.allowed_origins {
+                cors = cors.allowed_origin(origin);
+            }
+        }
+
+        if self.allow_credentials {
+            cors = cors.supports_credentials();
+        }
+
+        cors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_policy_is_same_origin_only() {
+        let (policy, warnings) = CorsPolicy::parse(None, None, None, false, 3600);
+        assert!(!policy.allows_cross_origin());
+        assert!(warnings.is_empty());
+        assert!(policy.allowed_methods.contains(&Method::POST));
+        assert!(policy.allowed_headers.iter().any(|h| h == "authorization"));
+    }
+
+    #[test]
+    fn test_origins_are_normalized_and_validated() {
+        let (policy, warnings) = CorsPolicy::parse(
+            Some("https://app.example.com, https://APP.example.com/, http://localhost:5173, example.com, https://x.io/path"),
+            Some("get, post, BAD METHOD"),
+            None,
+            true,
+            600,
+        );
+        assert_eq!(
+            policy.allowed_origins,
+            vec!["https://app.example.com".to_string(), "http://localhost:5173".to_string()]
+        );
+        assert_eq!(policy.allowed_methods, vec![Method::GET, Method::POST]);
+        assert!(policy.allow_credentials);
+        assert_eq!(warnings.len(), 3);
+    }
+
+    #[test]
+    fn test_wildcard_disables_credentials() {
+        let (policy, warnings) = CorsPolicy::parse(Some("*"), None, None, true, 3600);
+        assert!(policy.allow_any_origin);
+        assert!(!policy.allow_credentials);
+        assert_eq!(warnings.len(), 1);
+    }
+}
@@ -1 +1,2 @@
+pub mod cors;
 pub mod session_auth;
@@ -13,6 +13,14 @@ pub struct AgentSettings {
     pub secret_key: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// A/B traffic weight. When two or more rows have a positive weight, each
+    /// dispatch picks one of them at random in proportion to the weights.
+    #[serde(default)]
+    pub weight: i32,
+    /// Position in the fallback chain (1 = first fallback, 0 = not a fallback).
+    /// When the dispatch's model fails, fallbacks are tried in ascending order.
+    #[serde(default)]
+    pub fallback_priority: i32,
 }
 
 /// Minimum allowed context tokens (ensures compaction has room to work)
@@ -34,6 +42,8 @@ impl Default for AgentSettings {
             secret_key: None,
             created_at: now,
             updated_at: now,
+            weight: 0,
+            fallback_priority: 0,
         }
     }
 }
@@ -48,6 +58,8 @@ pub struct AgentSettingsResponse {
     pub max_context_tokens: i32,
     pub enabled: bool,
     pub has_secret_key: bool,
+    pub weight: i32,
+    pub fallback_priority: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -62,6 +74,8 @@ impl From<AgentSettings> for AgentSettingsResponse {
             max_context_tokens: settings.max_context_tokens,
             enabled: settings.enabled,
             has_secret_key: settings.secret_key.is_some(),
+            weight: settings.weight,
+            fallback_priority: settings.fallback_priority,
             created_at: settings.created_at,
             updated_at: settings.updated_at,
         }
@@ -81,6 +95,26 @@ pub struct UpdateAgentSettingsRequest {
     pub secret_key: Option<String>,
 }
 
+/// Request type for setting A/B traffic weights on agent settings rows
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateAgentWeightsRequest {
+    pub weights: Vec<AgentWeight>,
+}
+
+/// Traffic weight for one agent settings row (0 removes it from the split)
+#[derive(Debug, Clone, Deserialize)]
+pub struct AgentWeight {
+    pub id: i64,
+    pub weight: i32,
+}
+
+/// Request type for setting the provider fallback chain
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateAgentFallbacksRequest {
+    /// Agent settings ids to fall back to, in the order they should be tried (empty clears the chain)
+    pub fallbacks: Vec<i64>,
+}
+
 fn default_archetype() -> String {
     "kimi".to_string()
 }
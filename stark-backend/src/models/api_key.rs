@@ -51,6 +51,30 @@ fn mask_key(value: &str) -> String {
     }
 }
 
+/// Last four characters of a key for identifying it without revealing it
+pub fn key_last_four(value: &str) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    if chars.len() > 8 {
+        format!("****{}", chars[chars.len() - 4..].iter().collect::<String>())
+    } else {
+        "****".to_string()
+    }
+}
+
+/// One recorded change to an API key (the key value itself is never stored here)
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiKeyChange {
+    pub id: i64,
+    pub key_name: String,
+    /// "set", "rotate" or "delete"
+    pub action: String,
+    /// Who made the change, e.g. "api" or "discord:<user id>"
+    pub actor: String,
+    /// Last four characters of the new value, for set/rotate
+    pub key_hint: Option<String>,
+    pub created_at: String,
+}
+
 /// Response version with masked key
 #[derive(Debug, Clone, Serialize)]
 pub struct ApiKeyResponse {
@@ -61,3 +85,14 @@ pub struct ApiKeyResponse {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_last_four() {
+        assert_eq!(key_last_four("sk-abcdefgh1234"), "****1234");
+        assert_eq!(key_last_four("short"), "****");
+    }
+}
@@ -9,6 +9,7 @@ pub enum ChannelType {
     Discord,
     Twitter,
     ExternalChannel,
+    Webhook,
 }
 
 impl ChannelType {
@@ -19,6 +20,7 @@ impl ChannelType {
             ChannelType::Discord => "discord",
             ChannelType::Twitter => "twitter",
             ChannelType::ExternalChannel => "external_channel",
+            ChannelType::Webhook => "webhook",
         }
     }
 
@@ -29,6 +31,7 @@ impl ChannelType {
             "discord" => Some(ChannelType::Discord),
             "twitter" => Some(ChannelType::Twitter),
             "external_channel" => Some(ChannelType::ExternalChannel),
+            "webhook" => Some(ChannelType::Webhook),
             _ => None,
         }
     }
@@ -55,6 +58,9 @@ pub struct Channel {
     pub safe_mode: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Set when the channel has been soft-deleted (restorable until purged)
+    #[serde(default)]
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 impl Channel {
@@ -78,6 +84,8 @@ pub struct ChannelResponse {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub running: Option<bool>,
 }
 
@@ -93,6 +101,7 @@ impl From<Channel> for ChannelResponse {
             safe_mode: channel.safe_mode,
             created_at: channel.created_at,
             updated_at: channel.updated_at,
+            deleted_at: channel.deleted_at,
             running: None,
         }
     }
@@ -4,6 +4,7 @@
 //! defines what settings are available, and values are stored per-channel.
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use strum::{AsRefStr, EnumIter, EnumString};
 
 use super::channel::ChannelType;
@@ -45,6 +46,57 @@ impl ToolOutputVerbosity {
     }
 }
 
+/// Which gateway events a channel forwards to chat while the agent works
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum ForwardedEvents {
+    /// Forward every event the channel knows how to render
+    #[default]
+    All,
+    /// Forward nothing; only the final reply is posted
+    None,
+    /// Forward only the listed event names (e.g. `agent.tool_call`)
+    Only(HashSet<String>),
+}
+
+impl ForwardedEvents {
+    /// Parse a setting value: blank or `all`, `none`, or comma-separated event names
+    pub fn parse(s: &str) -> Self {
+        let s = s.trim();
+        if s.is_empty() || s.eq_ignore_ascii_case("all") {
+            return Self::All;
+        }
+        if s.eq_ignore_ascii_case("none") {
+            return Self::None;
+        }
+        Self::Only(
+            s.split(',')
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty())
+                .collect(),
+        )
+    }
+
+    /// Whether an event with this name should be forwarded
+    pub fn allows(&self, event: &str) -> bool {
+        match self {
+            Self::All => true,
+            Self::None => false,
+            Self::Only(names) => names.contains(event),
+        }
+    }
+}
+
+/// Upper bound for `rate_limit_per_minute` overrides
+pub const MAX_RATE_LIMIT_PER_MINUTE: u32 = 600;
+/// Upper bound for `rate_limit_burst` overrides
+pub const MAX_RATE_LIMIT_BURST: u32 = 100;
+/// Upper bound for `discord_debounce_ms`
+pub const MAX_DISCORD_DEBOUNCE_MS: u32 = 5_000;
+/// Upper bound on the length of a `system_prompt` override, in characters
+pub const MAX_SYSTEM_PROMPT_CHARS: usize = 8_000;
+/// Upper bound for `max_message_chars` overrides
+pub const MAX_MESSAGE_CHARS_LIMIT: u32 = 200_000;
+
 /// Available setting keys for channels.
 /// Each variant maps to a specific channel type's configurable option.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, EnumString, AsRefStr, EnumIter)]
@@ -53,11 +105,48 @@ impl ToolOutputVerbosity {
 pub enum ChannelSettingKey {
     /// Common: Auto-start this channel when the server boots (after restore from backup)
     AutoStartOnBoot,
+    /// Common: Messages each user may send per minute before being told to slow down
+    /// (0 = unlimited, blank = the server default)
+    RateLimitPerMinute,
+    /// Common: Messages a user may send in a quick burst before the per-minute rate applies
+    RateLimitBurst,
+    /// Common: Start a fresh conversation context after this many idle minutes (0 = never)
+    SessionTtlMinutes,
+    /// Common: Longest message a user may send, in characters (0 = no limit, blank = the server default)
+    MaxMessageChars,
+    /// Common: What happens to a message over `max_message_chars`: `truncate` or `reject`
+    OversizedMessageAction,
+    /// Common: Persona/instructions for this channel, added to (or replacing) the default SOUL.md prompt
+    SystemPrompt,
+    /// Common: How `system_prompt` combines with the default prompt: `prepend` or `replace`
+    SystemPromptMode,
     /// Discord: Bot authentication token
     DiscordBotToken,
     /// Discord: Comma-separated list of Discord user IDs with admin access
-    /// If empty, falls back to Discord's built-in Administrator permission
+    /// If this and `discord_admin_role_ids` are empty, falls back to Discord's built-in Administrator permission
     DiscordAdminUserIds,
+    /// Discord: Comma-separated list of Discord role IDs whose members have admin access
+    DiscordAdminRoleIds,
+    /// Discord: How @everyone/@here, role, and channel mentions in forwarded text are handled
+    DiscordMentionSanitization,
+    /// Discord: Allow pinning important bot responses (pin_response tool and 📌 reactions)
+    DiscordAutoPin,
+    /// Discord: Maximum tool-event messages forwarded per dispatch (0 = unlimited)
+    DiscordMaxToolEvents,
+    /// Discord: Activity shown in the member list (e.g. "Watching for @mentions")
+    DiscordPresence,
+    /// Discord: Show a "Busy processing..." status while a message is being handled
+    DiscordBusyPresence,
+    /// Discord: Prefix each part of a multi-message reply with "(part i/n)"
+    DiscordNumberChunks,
+    /// Discord: Collapse tool updates into one status message that is edited in place
+    DiscordEditMode,
+    /// Discord: Which event types are posted while the agent works (comma-separated, `all` or `none`)
+    DiscordForwardedEvents,
+    /// Discord: Wait this many milliseconds for follow-up messages and dispatch them together (0 = off)
+    DiscordDebounceMs,
+    /// Discord: Send the agent's narration between tool rounds as it happens instead of only at the end
+    DiscordPartialResponses,
     /// Telegram: Bot authentication token from @BotFather
     TelegramBotToken,
     /// Slack: Bot OAuth token (xoxb-...)
@@ -84,6 +173,12 @@ pub enum ChannelSettingKey {
     ExternalChannelApiToken,
     /// External Gateway: Enable safe mode (restricts tool access for untrusted input)
     ExternalChannelSafeMode,
+    /// Webhook: Shared secret callers must send to POST /api/channels/{id}/webhook
+    WebhookSecret,
+    /// Webhook: Enable safe mode (restricts tool access for untrusted input)
+    WebhookSafeMode,
+    /// Discord/Telegram/Slack: Chat that receives operator announcements (broadcast_announcement)
+    AnnouncementChatId,
 }
 
 impl ChannelSettingKey {
@@ -91,8 +186,26 @@ impl ChannelSettingKey {
     pub fn label(&self) -> &'static str {
         match self {
             Self::AutoStartOnBoot => "Auto-Start on Boot",
+            Self::RateLimitPerMinute => "Messages Per User Per Minute",
+            Self::RateLimitBurst => "Message Burst Allowance",
+            Self::SessionTtlMinutes => "Conversation Reset After (Minutes Idle)",
+            Self::MaxMessageChars => "Max Message Length (Characters)",
+            Self::OversizedMessageAction => "Long Message Handling",
+            Self::SystemPrompt => "Channel System Prompt",
+            Self::SystemPromptMode => "System Prompt Mode",
             Self::DiscordBotToken => "Bot Token",
             Self::DiscordAdminUserIds => "Admin User IDs (Optional)",
+            Self::DiscordAdminRoleIds => "Admin Role IDs (Optional)",
+            Self::DiscordMentionSanitization => "Mass & Role Mention Handling",
+            Self::DiscordAutoPin => "Pin Important Responses",
+            Self::DiscordMaxToolEvents => "Max Tool Updates Per Reply",
+            Self::DiscordPresence => "Bot Status (Optional)",
+            Self::DiscordBusyPresence => "Show Busy Status",
+            Self::DiscordNumberChunks => "Number Multi-Part Replies",
+            Self::DiscordEditMode => "Edit Status Message In Place",
+            Self::DiscordForwardedEvents => "Forwarded Events",
+            Self::DiscordDebounceMs => "Message Debounce (ms)",
+            Self::DiscordPartialResponses => "Show Progress Between Tool Rounds",
             Self::TelegramBotToken => "Bot Token",
             Self::SlackBotToken => "Bot Token",
             Self::SlackAppToken => "App Token (Socket Mode)",
@@ -106,6 +219,9 @@ impl ChannelSettingKey {
             Self::SlackAdminUserIds => "Admin User IDs (Optional)",
             Self::ExternalChannelApiToken => "API Token",
             Self::ExternalChannelSafeMode => "Safe Mode",
+            Self::WebhookSecret => "Webhook Secret",
+            Self::WebhookSafeMode => "Safe Mode",
+            Self::AnnouncementChatId => "Announcement Chat ID (Optional)",
         }
     }
 
@@ -116,16 +232,102 @@ impl ChannelSettingKey {
                 "Automatically start this channel when the server boots or restores from backup. \
                  Useful for ensuring your bot is always running after container updates."
             }
+            Self::RateLimitPerMinute => {
+                "How many messages each user may send per minute in this channel. Short bursts up to the limit \
+                 are allowed; beyond it the user is asked to slow down and no AI call is made. Set to 0 for unlimited, \
+                 or leave blank to use the server default (STARK_RATE_LIMIT_PER_MINUTE). Admins are never limited."
+            }
+            Self::RateLimitBurst => {
+                "How many messages a user may send back-to-back before the per-minute rate kicks in. \
+                 Leave blank to allow bursts up to the per-minute limit."
+            }
+            Self::SessionTtlMinutes => {
+                "When someone writes after this many minutes without activity, the agent starts a fresh \
+                 conversation instead of continuing the old context. Earlier sessions are kept, not deleted. \
+                 Set to 0 to keep continuing the previous conversation."
+            }
+            Self::MaxMessageChars => {
+                "Messages longer than this are cut down (or refused) before reaching the AI, so a pasted wall \
+                 of text can't overflow the model's context. Set to 0 for no limit, or leave blank to use the \
+                 server default (STARK_MAX_MESSAGE_CHARS)."
+            }
+            Self::OversizedMessageAction => {
+                "Truncate keeps the start of a long message and tells the agent the rest was cut; \
+                 Reject asks the user to shorten it and makes no AI call."
+            }
+            Self::SystemPrompt => {
+                "Persona or standing instructions for this channel only (e.g. \"You are the support desk for ...\"). \
+                 Supports the same {{variables}} as SOUL.md. Leave blank to use the default prompt unchanged."
+            }
+            Self::SystemPromptMode => {
+                "Prepend adds the channel prompt ahead of the default SOUL.md persona; Replace uses it instead \
+                 of SOUL.md. Safety rules, memory, and request context are always kept."
+            }
             Self::DiscordBotToken => {
                 "Your Discord bot token from the Discord Developer Portal. \
                  Found under Bot > Token in your application settings."
             }
             Self::DiscordAdminUserIds => {
                 "Comma-separated Discord user IDs that have full agent access. \
-                 If left empty, Discord's Administrator permission is used. \
-                 If any IDs are set, ONLY those users have admin access (Discord admin role is ignored). \
+                 If this and Admin Role IDs are both empty, Discord's Administrator permission is used. \
+                 If any user or role IDs are set, ONLY those users and role members have admin access \
+                 (Discord's Administrator permission is ignored). \
                  Get your ID: enable Developer Mode in Discord settings, then right-click your username."
             }
+            Self::DiscordAdminRoleIds => {
+                "Comma-separated Discord role IDs whose members have full agent access, in addition to Admin User IDs. \
+                 Useful when admins change often. Roles only apply in servers; in DMs only Admin User IDs count. \
+                 Get a role ID: enable Developer Mode, then right-click the role in Server Settings > Roles."
+            }
+            Self::DiscordMentionSanitization => {
+                "How @everyone, @here, role mentions, and channel mentions in messages are passed to the agent. \
+                 Neutralize keeps them readable but unable to ping if the agent echoes them; Strip removes them. \
+                 User mentions are always kept so tipping and user lookups keep working."
+            }
+            Self::DiscordAutoPin => {
+                "Let the agent pin important responses (e.g. transaction confirmations) and let users \
+                 pin a bot message by reacting with 📌. The bot needs the Manage Messages permission. \
+                 When the channel hits Discord's pin limit, the oldest pin made by the bot is unpinned to make room."
+            }
+            Self::DiscordMaxToolEvents => {
+                "Maximum number of tool call/result updates forwarded to Discord while the agent works on one message. \
+                 Once the limit is reached, further updates are suppressed and a single \"N more tool calls…\" \
+                 summary is posted before the final answer. Set to 0 for unlimited."
+            }
+            Self::DiscordPresence => {
+                "Activity shown under the bot's name in the member list. Start with Playing, Watching, \
+                 Listening to, or Competing in to pick the activity type (e.g. \"Watching for @mentions\"); \
+                 any other text becomes a custom status. Applied when the bot connects."
+            }
+            Self::DiscordBusyPresence => {
+                "Switch the bot to an idle \"Busy processing...\" status while it is working on a message, \
+                 then restore the normal status when it replies."
+            }
+            Self::DiscordNumberChunks => {
+                "When a reply is too long for one Discord message and is split, prefix each part with \
+                 \"(part 1/3)\", \"(part 2/3)\", ... so the parts stay readable when other messages interleave. \
+                 Single-message replies are never prefixed."
+            }
+            Self::DiscordEditMode => {
+                "Keep a running log of tool updates in a single status message that is edited as the agent works, \
+                 instead of replacing it on every update. A new status message is started when the log outgrows \
+                 Discord's 2000-character limit, and the log is left in the channel after the reply."
+            }
+            Self::DiscordForwardedEvents => {
+                "Which updates are posted while the agent works: \"all\" (the default), \"none\" for only the \
+                 final reply, or a comma-separated list of event names such as \
+                 \"agent.tool_call, tool.result, agent.partial_response\". Unlisted events are not posted."
+            }
+            Self::DiscordDebounceMs => {
+                "When a user sends several messages in quick succession, wait this long after their latest \
+                 message and answer them all in one reply (e.g. 1500). Waiting is capped at 10 seconds from \
+                 the first message. Set to 0 to answer every message separately."
+            }
+            Self::DiscordPartialResponses => {
+                "During long multi-tool tasks, post the text the agent writes between tool rounds as soon as \
+                 it's generated, so users can follow its progress instead of waiting for everything at the end. \
+                 Leave off for quieter channels."
+            }
             Self::TelegramBotToken => {
                 "Your Telegram bot token from @BotFather. \
                  Create a bot with /newbot and copy the token provided."
@@ -187,6 +389,21 @@ impl ChannelSettingKey {
                  tool access is restricted to a safe subset. Disable for full agent access \
                  (only if you trust the clients connecting to this channel)."
             }
+            Self::WebhookSecret => {
+                "Shared secret callers must send in the X-Webhook-Secret header \
+                 (or as Authorization: Bearer <secret>) when posting to /api/channels/<id>/webhook. \
+                 Requests are rejected while this is empty."
+            }
+            Self::WebhookSafeMode => {
+                "When enabled, webhook messages are treated as untrusted input — \
+                 tool access is restricted to a safe subset. Disable only if every app \
+                 holding the secret is trusted."
+            }
+            Self::AnnouncementChatId => {
+                "Where operator announcements are posted for this channel: a Discord channel ID, \
+                 Telegram chat ID, or Slack channel ID. Leave empty to exclude this channel \
+                 from broadcast announcements."
+            }
         }
     }
 
@@ -194,8 +411,26 @@ impl ChannelSettingKey {
     pub fn input_type(&self) -> SettingInputType {
         match self {
             Self::AutoStartOnBoot => SettingInputType::Toggle,
+            Self::RateLimitPerMinute => SettingInputType::Number,
+            Self::RateLimitBurst => SettingInputType::Number,
+            Self::SessionTtlMinutes => SettingInputType::Number,
+            Self::MaxMessageChars => SettingInputType::Number,
+            Self::OversizedMessageAction => SettingInputType::Select,
+            Self::SystemPrompt => SettingInputType::TextArea,
+            Self::SystemPromptMode => SettingInputType::Select,
             Self::DiscordBotToken => SettingInputType::Text,
             Self::DiscordAdminUserIds => SettingInputType::Text,
+            Self::DiscordAdminRoleIds => SettingInputType::Text,
+            Self::DiscordMentionSanitization => SettingInputType::Select,
+            Self::DiscordAutoPin => SettingInputType::Toggle,
+            Self::DiscordMaxToolEvents => SettingInputType::Number,
+            Self::DiscordPresence => SettingInputType::Text,
+            Self::DiscordBusyPresence => SettingInputType::Toggle,
+            Self::DiscordNumberChunks => SettingInputType::Toggle,
+            Self::DiscordEditMode => SettingInputType::Toggle,
+            Self::DiscordForwardedEvents => SettingInputType::Text,
+            Self::DiscordDebounceMs => SettingInputType::Number,
+            Self::DiscordPartialResponses => SettingInputType::Toggle,
             Self::TelegramBotToken => SettingInputType::Text,
             Self::SlackBotToken => SettingInputType::Text,
             Self::SlackAppToken => SettingInputType::Text,
@@ -209,6 +444,9 @@ impl ChannelSettingKey {
             Self::SlackAdminUserIds => SettingInputType::Text,
             Self::ExternalChannelApiToken => SettingInputType::Text,
             Self::ExternalChannelSafeMode => SettingInputType::Toggle,
+            Self::WebhookSecret => SettingInputType::Text,
+            Self::WebhookSafeMode => SettingInputType::Toggle,
+            Self::AnnouncementChatId => SettingInputType::Text,
         }
     }
 
@@ -216,8 +454,26 @@ impl ChannelSettingKey {
     pub fn placeholder(&self) -> &'static str {
         match self {
             Self::AutoStartOnBoot => "",
+            Self::RateLimitPerMinute => "Server default",
+            Self::RateLimitBurst => "Same as per-minute limit",
+            Self::SessionTtlMinutes => "60",
+            Self::MaxMessageChars => "Server default",
+            Self::OversizedMessageAction => "",
+            Self::SystemPrompt => "You are the support assistant for ... Keep answers short and friendly.",
+            Self::SystemPromptMode => "",
             Self::DiscordBotToken => "MTIz...abc",
             Self::DiscordAdminUserIds => "123456789012345678, 987654321098765432",
+            Self::DiscordAdminRoleIds => "112233445566778899",
+            Self::DiscordMentionSanitization => "",
+            Self::DiscordAutoPin => "",
+            Self::DiscordMaxToolEvents => "0",
+            Self::DiscordPresence => "Watching for @mentions",
+            Self::DiscordBusyPresence => "",
+            Self::DiscordNumberChunks => "",
+            Self::DiscordEditMode => "",
+            Self::DiscordForwardedEvents => "all",
+            Self::DiscordDebounceMs => "1500",
+            Self::DiscordPartialResponses => "",
             Self::TelegramBotToken => "123456:ABC-DEF...",
             Self::SlackBotToken => "xoxb-...",
             Self::SlackAppToken => "xapp-...",
@@ -231,6 +487,9 @@ impl ChannelSettingKey {
             Self::SlackAdminUserIds => "U12345678,U87654321",
             Self::ExternalChannelApiToken => "Click dice to generate a secure token",
             Self::ExternalChannelSafeMode => "",
+            Self::WebhookSecret => "Click dice to generate a secure secret",
+            Self::WebhookSafeMode => "",
+            Self::AnnouncementChatId => "123456789012345678",
         }
     }
 
@@ -245,6 +504,19 @@ impl ChannelSettingKey {
                 ("5", "5%"),
                 ("1", "1%"),
             ]),
+            Self::DiscordMentionSanitization => Some(vec![
+                ("neutralize", "Neutralize (can't ping)"),
+                ("strip", "Strip"),
+                ("off", "Off (pass through unchanged)"),
+            ]),
+            Self::OversizedMessageAction => Some(vec![
+                ("truncate", "Truncate with a notice"),
+                ("reject", "Ask the user to shorten it"),
+            ]),
+            Self::SystemPromptMode => Some(vec![
+                ("prepend", "Prepend to default prompt"),
+                ("replace", "Replace default persona"),
+            ]),
             _ => None,
         }
     }
@@ -253,8 +525,26 @@ impl ChannelSettingKey {
     pub fn default_value(&self) -> &'static str {
         match self {
             Self::AutoStartOnBoot => "false",
+            Self::RateLimitPerMinute => "",
+            Self::RateLimitBurst => "",
+            Self::SessionTtlMinutes => "0",
+            Self::MaxMessageChars => "",
+            Self::OversizedMessageAction => "truncate",
+            Self::SystemPrompt => "",
+            Self::SystemPromptMode => "prepend",
             Self::DiscordBotToken => "",
             Self::DiscordAdminUserIds => "",
+            Self::DiscordAdminRoleIds => "",
+            Self::DiscordMentionSanitization => "neutralize",
+            Self::DiscordAutoPin => "false",
+            Self::DiscordMaxToolEvents => "0",
+            Self::DiscordPresence => "",
+            Self::DiscordBusyPresence => "false",
+            Self::DiscordNumberChunks => "false",
+            Self::DiscordEditMode => "false",
+            Self::DiscordForwardedEvents => "all",
+            Self::DiscordDebounceMs => "0",
+            Self::DiscordPartialResponses => "false",
             Self::TelegramBotToken => "",
             Self::SlackBotToken => "",
             Self::SlackAppToken => "",
@@ -268,12 +558,70 @@ impl ChannelSettingKey {
             Self::SlackAdminUserIds => "",
             Self::ExternalChannelApiToken => "",
             Self::ExternalChannelSafeMode => "false",
+            Self::WebhookSecret => "",
+            Self::WebhookSafeMode => "true",
+            Self::AnnouncementChatId => "",
         }
     }
 
     /// Check if this setting applies to all channel types (common setting)
     pub fn is_common(&self) -> bool {
-        matches!(self, Self::AutoStartOnBoot)
+        matches!(
+            self,
+            Self::AutoStartOnBoot
+                | Self::RateLimitPerMinute
+                | Self::RateLimitBurst
+                | Self::SessionTtlMinutes
+                | Self::MaxMessageChars
+                | Self::OversizedMessageAction
+                | Self::SystemPrompt
+                | Self::SystemPromptMode
+        )
+    }
+
+    /// Check a value before it's saved. Blank always passes (it clears the setting).
+    pub fn validate_value(&self, value: &str) -> Result<(), String> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Ok(());
+        }
+        if *self == Self::SystemPrompt {
+            let len = value.chars().count();
+            if len > MAX_SYSTEM_PROMPT_CHARS {
+                return Err(format!(
+                    "{} must be at most {} characters (got {})",
+                    self.label(),
+                    MAX_SYSTEM_PROMPT_CHARS,
+                    len
+                ));
+            }
+            return Ok(());
+        }
+        let (max, unit) = match self {
+            Self::RateLimitPerMinute => (MAX_RATE_LIMIT_PER_MINUTE, "messages per minute"),
+            Self::RateLimitBurst => (MAX_RATE_LIMIT_BURST, "messages"),
+            Self::DiscordDebounceMs => (MAX_DISCORD_DEBOUNCE_MS, "milliseconds"),
+            Self::MaxMessageChars => (MAX_MESSAGE_CHARS_LIMIT, "characters"),
+            _ => return Ok(()),
+        };
+        match value.parse::<u32>() {
+            Ok(n) if n <= max => Ok(()),
+            Ok(_) => Err(format!("{} must be at most {} {}", self.label(), max, unit)),
+            Err(_) => Err(format!("{} must be a whole number", self.label())),
+        }
+    }
+
+    /// Check if this setting holds a credential (redacted or encrypted in config exports)
+    pub fn is_secret(&self) -> bool {
+        matches!(
+            self,
+            Self::DiscordBotToken
+                | Self::TelegramBotToken
+                | Self::SlackBotToken
+                | Self::SlackAppToken
+                | Self::ExternalChannelApiToken
+                | Self::WebhookSecret
+        )
     }
 }
 
@@ -374,6 +722,9 @@ pub struct SettingUpdate {
 fn get_common_settings() -> Vec<ChannelSettingDefinition> {
     vec![
         ChannelSettingKey::AutoStartOnBoot.into(),
+        ChannelSettingKey::RateLimitPerMinute.into(),
+        ChannelSettingKey::RateLimitBurst.into(),
+        ChannelSettingKey::SessionTtlMinutes.into(),
     ]
 }
 
@@ -385,15 +736,29 @@ pub fn get_settings_for_channel_type(channel_type: ChannelType) -> Vec<ChannelSe
         ChannelType::Discord => vec![
             ChannelSettingKey::DiscordBotToken.into(),
             ChannelSettingKey::DiscordAdminUserIds.into(),
+            ChannelSettingKey::DiscordAdminRoleIds.into(),
+            ChannelSettingKey::DiscordMentionSanitization.into(),
+            ChannelSettingKey::DiscordAutoPin.into(),
+            ChannelSettingKey::DiscordMaxToolEvents.into(),
+            ChannelSettingKey::DiscordPresence.into(),
+            ChannelSettingKey::DiscordBusyPresence.into(),
+            ChannelSettingKey::DiscordNumberChunks.into(),
+            ChannelSettingKey::DiscordEditMode.into(),
+            ChannelSettingKey::DiscordForwardedEvents.into(),
+            ChannelSettingKey::DiscordDebounceMs.into(),
+            ChannelSettingKey::DiscordPartialResponses.into(),
+            ChannelSettingKey::AnnouncementChatId.into(),
         ],
         ChannelType::Telegram => vec![
             ChannelSettingKey::TelegramBotToken.into(),
             ChannelSettingKey::TelegramAdminUserId.into(),
+            ChannelSettingKey::AnnouncementChatId.into(),
         ],
         ChannelType::Slack => vec![
             ChannelSettingKey::SlackBotToken.into(),
             ChannelSettingKey::SlackAppToken.into(),
             ChannelSettingKey::SlackAdminUserIds.into(),
+            ChannelSettingKey::AnnouncementChatId.into(),
         ],
         ChannelType::Twitter => vec![
             ChannelSettingKey::TwitterBotHandle.into(),
@@ -407,9 +772,18 @@ pub fn get_settings_for_channel_type(channel_type: ChannelType) -> Vec<ChannelSe
             ChannelSettingKey::ExternalChannelApiToken.into(),
             ChannelSettingKey::ExternalChannelSafeMode.into(),
         ],
+        ChannelType::Webhook => vec![
+            ChannelSettingKey::WebhookSecret.into(),
+            ChannelSettingKey::WebhookSafeMode.into(),
+        ],
     };
 
     settings.extend(type_specific);
+    settings.push(ChannelSettingKey::MaxMessageChars.into());
+    settings.push(ChannelSettingKey::OversizedMessageAction.into());
+    // The prompt override is a long text area, so it goes at the bottom of the form
+    settings.push(ChannelSettingKey::SystemPrompt.into());
+    settings.push(ChannelSettingKey::SystemPromptMode.into());
     settings
 }
 
@@ -426,32 +800,104 @@ mod tests {
     #[test]
     fn test_discord_settings() {
         let settings = get_settings_for_channel_type(ChannelType::Discord);
-        // 1 common + 2 Discord-specific (bot_token, admin_user_ids)
-        assert_eq!(settings.len(), 3);
+        // 4 common + 14 Discord-specific (bot_token, admin_user_ids, admin_role_ids, mention_sanitization, auto_pin,
+        // max_tool_events, presence, busy_presence, number_chunks, edit_mode, forwarded_events, debounce_ms,
+        // partial_responses, announcement_chat_id) + 4 message settings (max_message_chars, oversized_message_action,
+        // system_prompt, system_prompt_mode)
+        assert_eq!(settings.len(), 22);
         assert_eq!(settings[0].key, "auto_start_on_boot");
-        assert_eq!(settings[1].key, "discord_bot_token");
-        assert_eq!(settings[2].key, "discord_admin_user_ids");
+        assert_eq!(settings[1].key, "rate_limit_per_minute");
+        assert_eq!(settings[2].key, "rate_limit_burst");
+        assert_eq!(settings[3].key, "session_ttl_minutes");
+        assert_eq!(settings[4].key, "discord_bot_token");
+        assert_eq!(settings[5].key, "discord_admin_user_ids");
+        assert_eq!(settings[6].key, "discord_admin_role_ids");
+        assert_eq!(settings[7].key, "discord_mention_sanitization");
+        assert_eq!(settings[8].key, "discord_auto_pin");
+        assert_eq!(settings[9].key, "discord_max_tool_events");
+        assert_eq!(settings[10].key, "discord_presence");
+        assert_eq!(settings[11].key, "discord_busy_presence");
+        assert_eq!(settings[12].key, "discord_number_chunks");
+        assert_eq!(settings[13].key, "discord_edit_mode");
+        assert_eq!(settings[14].key, "discord_forwarded_events");
+        assert_eq!(settings[15].key, "discord_debounce_ms");
+        assert_eq!(settings[16].key, "discord_partial_responses");
+        assert_eq!(settings[17].key, "announcement_chat_id");
+        assert_eq!(settings[18].key, "max_message_chars");
+        assert_eq!(settings[19].key, "oversized_message_action");
+        assert_eq!(settings[20].key, "system_prompt");
+        assert_eq!(settings[21].key, "system_prompt_mode");
     }
 
     #[test]
     fn test_telegram_settings() {
         let settings = get_settings_for_channel_type(ChannelType::Telegram);
-        // 1 common + 2 Telegram-specific (bot_token, admin_user_id)
-        assert_eq!(settings.len(), 3);
+        // 4 common + 3 Telegram-specific (bot_token, admin_user_id, announcement_chat_id) + 4 message settings
+        assert_eq!(settings.len(), 11);
         assert_eq!(settings[0].key, "auto_start_on_boot");
-        assert_eq!(settings[1].key, "telegram_bot_token");
-        assert_eq!(settings[2].key, "telegram_admin_user_id");
+        assert_eq!(settings[1].key, "rate_limit_per_minute");
+        assert_eq!(settings[2].key, "rate_limit_burst");
+        assert_eq!(settings[3].key, "session_ttl_minutes");
+        assert_eq!(settings[4].key, "telegram_bot_token");
+        assert_eq!(settings[5].key, "telegram_admin_user_id");
+        assert_eq!(settings[6].key, "announcement_chat_id");
     }
 
     #[test]
     fn test_slack_settings() {
         let settings = get_settings_for_channel_type(ChannelType::Slack);
-        // 1 common + 3 Slack-specific (bot_token, app_token, admin_user_ids)
-        assert_eq!(settings.len(), 4);
+        // 4 common + 4 Slack-specific (bot_token, app_token, admin_user_ids, announcement_chat_id) + 4 message settings
+        assert_eq!(settings.len(), 12);
         assert_eq!(settings[0].key, "auto_start_on_boot");
-        assert_eq!(settings[1].key, "slack_bot_token");
-        assert_eq!(settings[2].key, "slack_app_token");
-        assert_eq!(settings[3].key, "slack_admin_user_ids");
+        assert_eq!(settings[1].key, "rate_limit_per_minute");
+        assert_eq!(settings[2].key, "rate_limit_burst");
+        assert_eq!(settings[3].key, "session_ttl_minutes");
+        assert_eq!(settings[4].key, "slack_bot_token");
+        assert_eq!(settings[5].key, "slack_app_token");
+        assert_eq!(settings[6].key, "slack_admin_user_ids");
+        assert_eq!(settings[7].key, "announcement_chat_id");
+    }
+
+    #[test]
+    fn test_webhook_settings() {
+        let settings = get_settings_for_channel_type(ChannelType::Webhook);
+        // 4 common + 2 Webhook-specific (secret, safe_mode) + 4 message settings
+        assert_eq!(settings.len(), 10);
+        assert_eq!(settings[4].key, "webhook_secret");
+        assert_eq!(settings[5].key, "webhook_safe_mode");
+        assert_eq!(settings[5].default_value, "true");
+        assert!(ChannelSettingKey::WebhookSecret.is_secret());
+    }
+
+    #[test]
+    fn test_rate_limit_validation() {
+        assert!(ChannelSettingKey::RateLimitPerMinute.validate_value("").is_ok());
+        assert!(ChannelSettingKey::RateLimitPerMinute.validate_value("0").is_ok());
+        assert!(ChannelSettingKey::RateLimitPerMinute.validate_value("600").is_ok());
+        assert!(ChannelSettingKey::RateLimitPerMinute.validate_value("601").is_err());
+        assert!(ChannelSettingKey::RateLimitPerMinute.validate_value("-1").is_err());
+        assert!(ChannelSettingKey::RateLimitBurst.validate_value("2.5").is_err());
+        assert!(ChannelSettingKey::RateLimitBurst.validate_value("101").is_err());
+        assert!(ChannelSettingKey::DiscordDebounceMs.validate_value("1500").is_ok());
+        assert!(ChannelSettingKey::DiscordDebounceMs.validate_value("60000").is_err());
+        assert!(ChannelSettingKey::SessionTtlMinutes.validate_value("anything").is_ok());
+        assert!(ChannelSettingKey::MaxMessageChars.validate_value("0").is_ok());
+        assert!(ChannelSettingKey::MaxMessageChars.validate_value("500000").is_err());
+        assert!(ChannelSettingKey::SystemPrompt.validate_value("Be brief.").is_ok());
+        assert!(ChannelSettingKey::SystemPrompt
+            .validate_value(&"x".repeat(MAX_SYSTEM_PROMPT_CHARS + 1))
+            .is_err());
+    }
+
+    #[test]
+    fn test_forwarded_events_parsing() {
+        assert_eq!(ForwardedEvents::parse(""), ForwardedEvents::All);
+        assert_eq!(ForwardedEvents::parse(" ALL "), ForwardedEvents::All);
+        assert!(!ForwardedEvents::parse("none").allows("agent.tool_call"));
+        let only = ForwardedEvents::parse("agent.tool_call, tool.result,");
+        assert!(only.allows("agent.tool_call"));
+        assert!(only.allows("tool.result"));
+        assert!(!only.allows("agent.partial_response"));
     }
 
     #[test]
@@ -164,6 +164,13 @@ pub struct ChatSession {
     pub safe_mode: bool,
 }
 
+impl ChatSession {
+    /// Whether the session has seen no activity for at least `minutes`
+    pub fn is_idle_for(&self, minutes: i64, now: DateTime<Utc>) -> bool {
+        now.signed_duration_since(self.last_activity_at).num_minutes() >= minutes
+    }
+}
+
 /// Request to get or create a chat session
 #[derive(Debug, Clone, Deserialize)]
 pub struct GetOrCreateSessionRequest {
@@ -10,13 +10,13 @@ pub mod identity;
 pub mod session;
 pub mod session_message;
 
-pub use agent_settings::{AgentSettings, AgentSettingsResponse, UpdateAgentSettingsRequest, MIN_CONTEXT_TOKENS, DEFAULT_CONTEXT_TOKENS};
+pub use agent_settings::{AgentSettings, AgentSettingsResponse, UpdateAgentFallbacksRequest, UpdateAgentSettingsRequest, UpdateAgentWeightsRequest, MIN_CONTEXT_TOKENS, DEFAULT_CONTEXT_TOKENS};
 pub use bot_settings::{BotSettings, UpdateBotSettingsRequest, DEFAULT_MAX_TOOL_ITERATIONS, DEFAULT_SAFE_MODE_MAX_QUERIES_PER_10MIN};
-pub use api_key::{ApiKey, ApiKeyResponse};
+pub use api_key::{key_last_four, ApiKey, ApiKeyChange, ApiKeyResponse};
 pub use channel::{Channel, ChannelResponse, ChannelType, CreateChannelRequest, CreateSafeModeChannelRequest, UpdateChannelRequest};
 pub use channel_settings::{
     get_settings_for_channel_type, ChannelSetting, ChannelSettingDefinition, ChannelSettingKey,
-    ChannelSettingsResponse, ChannelSettingsSchemaResponse, SelectOption, SettingInputType,
+    ChannelSettingsResponse, ForwardedEvents, ChannelSettingsSchemaResponse, SelectOption, SettingInputType,
     SettingUpdate, ToolOutputVerbosity, UpdateChannelSettingsRequest,
 };
 pub use chat_session::{
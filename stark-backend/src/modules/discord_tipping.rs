@@ -55,9 +55,11 @@ impl super::Module for DiscordTippingModule {
     }
 
     fn create_tools(&self) -> Vec<Arc<dyn Tool>> {
-        vec![Arc::new(
-            crate::discord_hooks::tools::DiscordResolveUserTool::new(),
-        )]
+        vec![
+            Arc::new(crate::discord_hooks::tools::DiscordResolveUserTool::new()),
+            Arc::new(crate::discord_hooks::tools::DiscordIsRegisteredTool::new()),
+            Arc::new(crate::discord_hooks::tools::DiscordReverseLookupTool::new()),
+        ]
     }
 
     fn skill_content(&self) -> Option<&'static str> {
@@ -0,0 +1,247 @@
+//! A uniform view of pending scheduled work
+//!
+//! Reminders, scheduled messages, and cron jobs are stored and run by different
+//! schedulers. Each one converts into a [`ScheduledItem`] with an id of the form
+//! `<kind>:<row id>` (e.g. `reminder:12`, `job:5`, `cron:3`), so a channel's pending work can be listed and
+//! cancelled in one place.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::db::tables::reminders::Reminder;
+use crate::db::tables::scheduled_jobs::ScheduledJob;
+use crate::db::Database;
+use crate::models::{CronJob, JobStatus};
+
+/// Which scheduler owns an item
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduledKind {
+    Reminder,
+    Job,
+    Cron,
+}
+
+impl ScheduledKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScheduledKind::Reminder => "reminder",
+            ScheduledKind::Job => "job",
+            ScheduledKind::Cron => "cron",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "reminder" => Some(ScheduledKind::Reminder),
+            "job" => Some(ScheduledKind::Job),
+            "cron" => Some(ScheduledKind::Cron),
+            _ => None,
+        }
+    }
+}
+
+/// One pending piece of scheduled work, whatever scheduler it belongs to
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduledItem {
+    /// Uniform id, `<kind>:<row id>`
+    pub id: String,
+    pub kind: ScheduledKind,
+    /// What will happen (reminder text, scheduled message, or job name)
+    pub title: String,
+    /// Next time it fires, if known
+    pub due_at: Option<DateTime<Utc>>,
+    /// Human-readable schedule ("once", "every 30 min", "cron 0 9 * * *")
+    pub schedule: String,
+    pub channel_id: Option<i64>,
+}
+
+impl ScheduledItem {
+    pub fn summary(&self) -> String {
+        let due = self
+            .due_at
+            .map(|t| format!("{} UTC", t.format("%Y-%m-%d %H:%M")))
+            .unwrap_or_else(|| "unscheduled".to_string());
+        format!("{} — {} ({}): {}", self.id, due, self.schedule, self.title)
+    }
+}
+
+impl From<&Reminder> for ScheduledItem {
+    fn from(r: &Reminder) -> Self {
+        ScheduledItem {
+            id: format!("{}:{}", ScheduledKind::Reminder.as_str(), r.id),
+            kind: ScheduledKind::Reminder,
+            title: r.text.clone(),
+            due_at: Some(r.due_at),
+            schedule: format!("once, via {}", r.delivery),
+            channel_id: Some(r.channel_id),
+        }
+    }
+}
+
+impl From<&ScheduledJob> for ScheduledItem {
+    fn from(job: &ScheduledJob) -> Self {
+        let title = match job.mode.as_str() {
+            "prompt" => format!("run prompt: {}", job.content),
+            _ => format!("post: {}", job.content),
+        };
+        ScheduledItem {
+            id: format!("{}:{}", ScheduledKind::Job.as_str(), job.id),
+            kind: ScheduledKind::Job,
+            title,
+            due_at: Some(job.run_at),
+            schedule: match &job.recurrence {
+                Some(repeat) => format!("repeats {}", repeat),
+                None => "once".to_string(),
+            },
+            channel_id: Some(job.channel_id),
+        }
+    }
+}
+
+impl From<&CronJob> for ScheduledItem {
+    fn from(job: &CronJob) -> Self {
+        let schedule = match job.schedule_type.as_str() {
+            "at" => "once".to_string(),
+            "every" => match job.schedule_value.parse::<u64>() {
+                Ok(ms) if ms % 3_600_000 == 0 => format!("every {} h", ms / 3_600_000),
+                Ok(ms) => format!("every {} min", (ms / 60_000).max(1)),
+                Err(_) => format!("every {}", job.schedule_value),
+            },
+            other => format!("{} {}", other, job.schedule_value),
+        };
+        ScheduledItem {
+            id: format!("{}:{}", ScheduledKind::Cron.as_str(), job.id),
+            kind: ScheduledKind::Cron,
+            title: job.name.clone(),
+            due_at: job
+                .next_run_at
+                .as_deref()
+                .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+                .map(|t| t.with_timezone(&Utc)),
+            schedule,
+            channel_id: job.channel_id,
+        }
+    }
+}
+
+/// Split a uniform id into its kind and row id
+pub fn parse_item_id(id: &str) -> Result<(ScheduledKind, i64), String> {
+    let (kind, row_id) = id
+        .trim()
+        .split_once(':')
+        .ok_or_else(|| format!("Invalid scheduled item id '{}' (expected e.g. reminder:12, job:5, or cron:3)", id))?;
+    let kind = ScheduledKind::from_str(kind)
+        .ok_or_else(|| format!("Unknown scheduled item kind '{}' (expected reminder, job, or cron)", kind))?;
+    let row_id = row_id
+        .parse::<i64>()
+        .map_err(|_| format!("Invalid scheduled item id '{}'", id))?;
+    Ok((kind, row_id))
+}
+
+/// Pending items for a channel: the user's reminders and scheduled messages, and the
+/// channel's active cron jobs, soonest first
+pub fn list_pending(db: &Database, channel_id: i64, user_id: Option<&str>) -> Result<Vec<ScheduledItem>, String> {
+    let mut items: Vec<ScheduledItem> = Vec::new();
+    if let Some(user_id) = user_id {
+        let reminders = db
+            .list_pending_reminders_for_user(channel_id, user_id)
+            .map_err(|e| format!("Failed to list reminders: {}", e))?;
+        items.extend(reminders.iter().map(ScheduledItem::from));
+        let jobs = db
+            .list_pending_scheduled_jobs_for_user(channel_id, user_id)
+            .map_err(|e| format!("Failed to list scheduled messages: {}", e))?;
+        items.extend(jobs.iter().map(ScheduledItem::from));
+    }
+    let jobs = db
+        .list_cron_jobs()
+        .map_err(|e| format!("Failed to list scheduled jobs: {}", e))?;
+    items.extend(
+        jobs.iter()
+            .filter(|j| j.status == JobStatus::Active.as_str() && j.channel_id == Some(channel_id))
+            .map(ScheduledItem::from),
+    );
+    items.sort_by_key(|i| (i.due_at.is_none(), i.due_at));
+    Ok(items)
+}
+
+/// Cancel a pending item. Reminders and scheduled messages are cancelled (only the user's own); cron jobs on
+/// this channel are paused so the job definition is kept and can be resumed from the
+/// dashboard. Returns a confirmation message.
+pub fn cancel(db: &Database, id: &str, channel_id: i64, user_id: Option<&str>) -> Result<String, String> {
+    let (kind, row_id) = parse_item_id(id)?;
+    match kind {
+        ScheduledKind::Reminder => {
+            let user_id = user_id.ok_or_else(|| "No user in context".to_string())?;
+            match db.cancel_reminder(row_id, channel_id, user_id) {
+                Ok(true) => Ok(format!("Reminder {} cancelled.", id)),
+                Ok(false) => Err(format!("No pending reminder {} found for you on this channel", id)),
+                Err(e) => Err(format!("Failed to cancel reminder: {}", e)),
+            }
+        }
+        ScheduledKind::Job => {
+            let user_id = user_id.ok_or_else(|| "No user in context".to_string())?;
+            match db.cancel_scheduled_job(row_id, channel_id, user_id) {
+                Ok(true) => Ok(format!("Scheduled message {} cancelled.", id)),
+                Ok(false) => Err(format!("No pending scheduled message {} found for you on this channel", id)),
+                Err(e) => Err(format!("Failed to cancel scheduled message: {}", e)),
+            }
+        }
+        ScheduledKind::Cron => {
+            let job = db
+                .get_cron_job(row_id)
+                .map_err(|e| format!("Failed to load scheduled job: {}", e))?
+                .filter(|j| j.channel_id == Some(channel_id) && j.status == JobStatus::Active.as_str())
+                .ok_or_else(|| format!("No active scheduled job {} found on this channel", id))?;
+            db.update_cron_job(
+                job.id, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None,
+                Some(JobStatus::Paused.as_str()),
+            )
+            .map_err(|e| format!("Failed to pause scheduled job: {}", e))?;
+            Ok(format!("Scheduled job {} ({}) paused.", id, job.name))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_item_id() {
+        assert_eq!(parse_item_id("reminder:12").unwrap(), (ScheduledKind::Reminder, 12));
+        assert_eq!(parse_item_id(" cron:3 ").unwrap(), (ScheduledKind::Cron, 3));
+        assert!(parse_item_id("12").is_err());
+        assert!(parse_item_id("tweet:1").is_err());
+        assert!(parse_item_id("cron:abc").is_err());
+        assert_eq!(parse_item_id("job:5").unwrap(), (ScheduledKind::Job, 5));
+    }
+
+    #[test]
+    fn test_reminders_are_scoped_to_their_user() {
+        let db = Database::new(":memory:").expect("in-memory db");
+        let due = Utc::now() + chrono::Duration::hours(2);
+        db.create_reminder(&crate::db::tables::reminders::CreateReminderRequest {
+            channel_id: 7,
+            channel_type: "discord".to_string(),
+            chat_id: None,
+            user_id: "u1".to_string(),
+            text: "stretch".to_string(),
+            due_at: due,
+            delivery: "dm".to_string(),
+        })
+        .expect("create reminder");
+
+        let items = list_pending(&db, 7, Some("u1")).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].kind, ScheduledKind::Reminder);
+        assert_eq!(items[0].title, "stretch");
+
+        // Someone else's reminders aren't listed or cancellable
+        assert!(list_pending(&db, 7, Some("u2")).unwrap().is_empty());
+        assert!(cancel(&db, &items[0].id, 7, Some("u2")).is_err());
+
+        cancel(&db, &items[0].id, 7, Some("u1")).unwrap();
+        assert!(list_pending(&db, 7, Some("u1")).unwrap().is_empty());
+    }
+}
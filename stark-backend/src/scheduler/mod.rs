@@ -1,3 +1,6 @@
+pub mod items;
+pub mod reminders;
+pub mod scheduled_jobs;
 pub mod runner;
 
 pub use runner::{Scheduler, SchedulerConfig};
@@ -0,0 +1,308 @@
+//! User reminders: time parsing and delivery
+//!
+//! Reminders are created by the `set_reminder` tool, persisted in the `reminders`
+//! table, and delivered by the scheduler tick once due — as a DM to the user when
+//! possible, falling back to the chat the reminder was set from.
+
+use crate::channels::announce;
+use crate::channels::util;
+use crate::db::tables::reminders::Reminder;
+use crate::db::Database;
+use crate::models::ChannelType;
+use chrono::{DateTime, Duration, FixedOffset, NaiveDateTime, TimeZone, Utc};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::{json, Value};
+
+/// Furthest ahead a reminder may be scheduled
+pub const MAX_REMINDER_DAYS: i64 = 365;
+
+/// One relative-duration component, e.g. "2h", "30 minutes"
+static DURATION_PART: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(\d+)\s*(weeks?|w|days?|d|hours?|hrs?|h|minutes?|mins?|m|seconds?|secs?|s)").unwrap()
+});
+
+/// Parse a relative duration like "in 2h", "90 minutes", "1 day and 3 hours"
+pub fn parse_relative(when: &str) -> Option<Duration> {
+    let text = when.trim().to_lowercase().replace(',', " ").replace(" and ", " ");
+    let text = text.strip_prefix("in ").unwrap_or(&text).trim();
+
+    let mut total = Duration::zero();
+    let mut consumed = 0;
+    for cap in DURATION_PART.captures_iter(text) {
+        let whole = cap.get(0)?;
+        // Only whitespace may separate parts ("2h foo" or "2 months" is not a duration)
+        if !text[consumed..whole.start()].trim().is_empty() {
+            return None;
+        }
+        consumed = whole.end();
+
+        // Amounts too large to represent are rejected rather than overflowing
+        let amount: i64 = cap[1].parse().ok()?;
+        let part = match cap[2].chars().next()? {
+            'w' => Duration::try_weeks(amount),
+            'd' => Duration::try_days(amount),
+            'h' => Duration::try_hours(amount),
+            'm' => Duration::try_minutes(amount),
+            _ => Duration::try_seconds(amount),
+        }?;
+        total = total.checked_add(&part)?;
+    }
+
+    if consumed == 0 || !text[consumed..].trim().is_empty() {
+        return None;
+    }
+    Some(total)
+}
+
+/// Parse a timezone given as "UTC", "Z", or a UTC offset ("+02:00", "-0530", "UTC+2")
+pub fn parse_utc_offset(tz: &str) -> Result<FixedOffset, String> {
+    let tz = tz.trim();
+    let upper = tz.to_uppercase();
+    let offset = upper
+        .strip_prefix("UTC")
+        .or_else(|| upper.strip_prefix("GMT"))
+        .unwrap_or(&upper)
+        .trim();
+    if offset.is_empty() || offset == "Z" {
+        return Ok(FixedOffset::east_opt(0).unwrap());
+    }
+
+    let (sign, rest) = match offset.chars().next() {
+        Some('+') => (1, &offset[1..]),
+        Some('-') => (-1, &offset[1..]),
+        _ => {
+            return Err(format!(
+                "Unsupported timezone '{}'. Use UTC or an offset like +02:00 or -05:30",
+                tz
+            ))
+        }
+    };
+    let digits: String = rest.chars().filter(|c| c.is_ascii_digit()).collect();
+    let (hours, minutes) = match digits.len() {
+        1 | 2 => (digits.parse::<i32>().ok(), Some(0)),
+        3 | 4 => {
+            let split = digits.len() - 2;
+            (digits[..split].parse::<i32>().ok(), digits[split..].parse::<i32>().ok())
+        }
+        _ => (None, None),
+    };
+    match (hours, minutes) {
+        (Some(h), Some(m)) if h <= 14 && m < 60 => FixedOffset::east_opt(sign * (h * 3600 + m * 60))
+            .ok_or_else(|| format!("Invalid UTC offset '{}'", tz)),
+        _ => Err(format!("Invalid UTC offset '{}'", tz)),
+    }
+}
+
+/// Resolve a reminder time: relative ("in 2h", "45 minutes") or absolute
+/// (RFC 3339, or "YYYY-MM-DD HH:MM" interpreted in `timezone`, default UTC).
+pub fn parse_reminder_time(
+    when: &str,
+    timezone: Option<&str>,
+    now: DateTime<Utc>,
+) -> Result<DateTime<Utc>, String> {
+    let due = if let Some(duration) = parse_relative(when) {
+        now.checked_add_signed(duration)
+            .ok_or_else(|| format!("Reminders can be set at most {} days ahead", MAX_REMINDER_DAYS))?
+    } else if let Ok(t) = DateTime::parse_from_rfc3339(when.trim()) {
+        t.with_timezone(&Utc)
+    } else {
+        let naive = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M"]
+            .iter()
+            .find_map(|fmt| NaiveDateTime::parse_from_str(when.trim(), fmt).ok())
+            .ok_or_else(|| {
+                format!(
+                    "Couldn't understand '{}'. Use a relative time like 'in 2h' or '30 minutes', \
+                     or an absolute time like '2026-01-31 09:00'",
+                    when
+                )
+            })?;
+        let offset = parse_utc_offset(timezone.unwrap_or("UTC"))?;
+        offset
+            .from_local_datetime(&naive)
+            .single()
+            .ok_or_else(|| format!("Ambiguous time '{}'", when))?
+            .with_timezone(&Utc)
+    };
+
+    if due <= now {
+        return Err("That time is in the past".to_string());
+    }
+    if due > now + Duration::days(MAX_REMINDER_DAYS) {
+        return Err(format!("Reminders can be set at most {} days ahead", MAX_REMINDER_DAYS));
+    }
+    Ok(due)
+}
+
+/// Discord needs a DM channel opened before messaging a user
+async fn open_discord_dm(client: &reqwest::Client, token: &str, user_id: &str) -> Result<String, String> {
+    let response = client
+        .post("https://discord.com/api/v10/users/@me/channels")
+        .header("Authorization", format!("Bot {}", token))
+        .json(&json!({ "recipient_id": user_id }))
+        .send()
+        .await
+        .map_err(|e| format!("Discord request failed: {}", e))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("Discord API error opening DM ({}): {}", status, body));
+    }
+    let body: Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Invalid Discord response: {}", e))?;
+    body.get("id")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .ok_or_else(|| "Discord DM channel response missing id".to_string())
+}
+
+/// Post `text` to a chat, split to the platform's message limit
+pub async fn send_to(
+    client: &reqwest::Client,
+    channel_type: ChannelType,
+    token: &str,
+    target: &str,
+    text: &str,
+) -> Result<(), String> {
+    let limit = announce::message_limit(channel_type).unwrap_or(2000);
+    for part in util::split_message(text, limit) {
+        match channel_type {
+            ChannelType::Discord => announce::send_discord(client, token, target, &part).await?,
+            ChannelType::Telegram => announce::send_telegram(client, token, target, &part).await?,
+            ChannelType::Slack => announce::send_slack(client, token, target, &part).await?,
+            ChannelType::Twitter | ChannelType::ExternalChannel | ChannelType::Webhook => {
+                return Err("Scheduled delivery is not supported on this channel type".to_string())
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The platform and bot token to deliver with for a channel
+pub fn delivery_target(db: &Database, channel_id: i64, channel_type: &str) -> Result<(ChannelType, String), String> {
+    let channel = db
+        .get_channel(channel_id)
+        .map_err(|e| format!("Failed to load channel: {}", e))?
+        .ok_or_else(|| "Channel no longer exists".to_string())?;
+    let channel_type =
+        ChannelType::from_str(channel_type).ok_or_else(|| format!("Unknown channel type '{}'", channel_type))?;
+    let token = announce::resolve_bot_token(db, &channel, channel_type)
+        .ok_or_else(|| "Bot token not configured".to_string())?;
+    Ok((channel_type, token))
+}
+
+/// Deliver one due reminder. DM first (if requested), then fall back to the originating chat.
+pub async fn deliver_reminder(db: &Database, client: &reqwest::Client, reminder: &Reminder) -> Result<(), String> {
+    let (channel_type, token) = delivery_target(db, reminder.channel_id, &reminder.channel_type)?;
+
+    let dm_text = format!("⏰ Reminder: {}", reminder.text);
+
+    if reminder.delivery == "dm" {
+        // Telegram and Slack accept a user ID as the chat for direct messages
+        let dm_result = match channel_type {
+            ChannelType::Discord => match open_discord_dm(client, &token, &reminder.user_id).await {
+                Ok(dm_channel) => send_to(client, channel_type, &token, &dm_channel, &dm_text).await,
+                Err(e) => Err(e),
+            },
+            _ => send_to(client, channel_type, &token, &reminder.user_id, &dm_text).await,
+        };
+        match dm_result {
+            Ok(()) => return Ok(()),
+            Err(e) if reminder.chat_id.is_some() => {
+                log::warn!(
+                    "[REMINDER] DM for reminder {} failed ({}), posting in the original chat instead",
+                    reminder.id, e
+                );
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    let chat_id = reminder
+        .chat_id
+        .as_deref()
+        .ok_or_else(|| "No chat to deliver the reminder to".to_string())?;
+    let mention = match channel_type {
+        ChannelType::Discord | ChannelType::Slack => format!("<@{}> ", reminder.user_id),
+        _ => String::new(),
+    };
+    send_to(
+        client,
+        channel_type,
+        &token,
+        chat_id,
+        &format!("⏰ {}Reminder: {}", mention, reminder.text),
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 15, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_relative_times() {
+        assert_eq!(parse_reminder_time("in 2h", None, now()).unwrap(), now() + Duration::hours(2));
+        assert_eq!(
+            parse_reminder_time("1 day 3 hours", None, now()).unwrap(),
+            now() + Duration::days(1) + Duration::hours(3)
+        );
+        assert_eq!(
+            parse_reminder_time("in 2 hours and 30 minutes", None, now()).unwrap(),
+            now() + Duration::minutes(150)
+        );
+        assert_eq!(parse_reminder_time("45m", None, now()).unwrap(), now() + Duration::minutes(45));
+        assert_eq!(parse_reminder_time("2h30m", None, now()).unwrap(), now() + Duration::minutes(150));
+    }
+
+    #[test]
+    fn test_absolute_times_with_timezone() {
+        assert_eq!(
+            parse_reminder_time("2026-01-15 18:00", None, now()).unwrap(),
+            Utc.with_ymd_and_hms(2026, 1, 15, 18, 0, 0).unwrap()
+        );
+        assert_eq!(
+            parse_reminder_time("2026-01-15 18:00", Some("+02:00"), now()).unwrap(),
+            Utc.with_ymd_and_hms(2026, 1, 15, 16, 0, 0).unwrap()
+        );
+        assert_eq!(
+            parse_reminder_time("2026-01-16T09:00:00-05:00", None, now()).unwrap(),
+            Utc.with_ymd_and_hms(2026, 1, 16, 14, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_rejects_bad_times() {
+        assert!(parse_reminder_time("2026-01-15 11:00", None, now()).is_err()); // past
+        assert!(parse_reminder_time("in 400 days", None, now()).is_err());
+        assert!(parse_reminder_time("in 2h to check the pool", None, now()).is_err());
+        assert!(parse_reminder_time("tomorrowish", None, now()).is_err());
+        assert!(parse_reminder_time("in 2 months", None, now()).is_err());
+        assert!(parse_reminder_time("2026-01-15 18:00", Some("Europe/Paris"), now()).is_err());
+    }
+
+    #[test]
+    fn test_huge_relative_times_do_not_overflow() {
+        assert_eq!(parse_relative("in 99999999999999 days"), None);
+        assert_eq!(parse_relative("9223372036854775807 weeks"), None);
+        assert_eq!(parse_relative("99999999999999999999 seconds"), None);
+        // Representable as a duration, but not once added to now
+        assert!(parse_relative("in 99999999999 hours").is_some());
+        assert!(parse_reminder_time("in 99999999999 hours", None, now()).is_err());
+        assert!(parse_reminder_time("in 99999999999999 days", None, now()).is_err());
+    }
+
+    #[test]
+    fn test_parse_utc_offset() {
+        assert_eq!(parse_utc_offset("UTC").unwrap().local_minus_utc(), 0);
+        assert_eq!(parse_utc_offset("-0530").unwrap().local_minus_utc(), -(5 * 3600 + 30 * 60));
+        assert_eq!(parse_utc_offset("UTC+2").unwrap().local_minus_utc(), 2 * 3600);
+        assert!(parse_utc_offset("+25:00").is_err());
+    }
+}
@@ -115,6 +115,13 @@ impl Scheduler {
             self.config.poll_interval_secs
         );
 
+        // One-shot scheduled messages that were mid-run when we stopped get another go
+        match self.db.requeue_interrupted_scheduled_jobs() {
+            Ok(0) => {}
+            Ok(n) => log::info!("Requeued {} interrupted scheduled message(s)", n),
+            Err(e) => log::error!("Failed to requeue interrupted scheduled messages: {}", e),
+        }
+
         let mut poll_interval = interval(TokioDuration::from_secs(self.config.poll_interval_secs));
 
         loop {
@@ -151,6 +158,16 @@ impl Scheduler {
             log::error!("Error processing heartbeats: {}", e);
         }
 
+        // Deliver due user reminders
+        if let Err(e) = self.process_reminders().await {
+            log::error!("Error processing reminders: {}", e);
+        }
+
+        // Run due scheduled messages (schedule_message tool)
+        if let Err(e) = self.process_scheduled_jobs() {
+            log::error!("Error processing scheduled messages: {}", e);
+        }
+
         // Run periodic cleanup tasks once per hour (at minute 0, within first poll window)
         let now = Local::now();
         if now.minute() == 0 && now.second() < self.config.poll_interval_secs as u32 {
@@ -206,6 +223,74 @@ impl Scheduler {
         Ok(())
     }
 
+    /// Deliver due reminders. Runs inline (not spawned) so a slow delivery can't
+    /// be picked up again by the next tick and sent twice.
+    async fn process_reminders(&self) -> Result<(), String> {
+        let due = self
+            .db
+            .list_due_reminders()
+            .map_err(|e| format!("Failed to list due reminders: {}", e))?;
+
+        let client = crate::http::shared_client();
+        for reminder in due {
+            let outcome = super::reminders::deliver_reminder(&self.db, client, &reminder).await;
+            let marked = match outcome {
+                Ok(()) => {
+                    log::info!("Delivered reminder #{} to user {}", reminder.id, reminder.user_id);
+                    self.db.mark_reminder_sent(reminder.id)
+                }
+                Err(e) => {
+                    log::warn!("Reminder #{} could not be delivered: {}", reminder.id, e);
+                    self.db.mark_reminder_failed(reminder.id, &e)
+                }
+            };
+            if let Err(e) = marked {
+                log::error!("Failed to update reminder #{}: {}", reminder.id, e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Start due scheduled messages. Each job is claimed first (moved to its next run,
+    /// or marked running if it's one-shot) so a slow prompt isn't picked up again by
+    /// the next tick.
+    fn process_scheduled_jobs(&self) -> Result<(), String> {
+        let due = self
+            .db
+            .list_due_scheduled_jobs()
+            .map_err(|e| format!("Failed to list due scheduled messages: {}", e))?;
+
+        let now = Utc::now();
+        for job in due {
+            let next = super::scheduled_jobs::next_run(&job, now);
+            match self.db.claim_scheduled_job(job.id, next) {
+                Ok(true) => {}
+                Ok(false) => continue,
+                Err(e) => {
+                    log::error!("Failed to claim scheduled message #{}: {}", job.id, e);
+                    continue;
+                }
+            }
+
+            let db = Arc::clone(&self.db);
+            let dispatcher = Arc::clone(&self.dispatcher);
+            tokio::spawn(async move {
+                let client = crate::http::shared_client();
+                let outcome = super::scheduled_jobs::run_job(&db, &dispatcher, client, &job).await;
+                match &outcome {
+                    Ok(()) => log::info!("Ran scheduled message #{} ({}) on channel {}", job.id, job.mode, job.channel_id),
+                    Err(e) => log::warn!("Scheduled message #{} failed: {}", job.id, e),
+                }
+                if let Err(e) = db.finish_scheduled_job(job.id, outcome.err().as_deref()) {
+                    log::error!("Failed to update scheduled message #{}: {}", job.id, e);
+                }
+            });
+        }
+
+        Ok(())
+    }
+
     /// Process kanban tasks that are in "ready" status (auto-execute)
     async fn process_kanban_tasks(&self) -> Result<(), String> {
         // Check if auto-execute is enabled in bot settings
@@ -273,6 +358,8 @@ impl Scheduler {
             session_mode: Some("isolated".to_string()),
             selected_network: None,
             force_safe_mode: false,
+            from_admin: false,
+            attachments: Vec::new(),
         };
 
         // Execute with 10-minute timeout (same as cron default)
@@ -430,6 +517,8 @@ impl Scheduler {
             session_mode: Some(job.session_mode.clone()),
             selected_network: None,
             force_safe_mode: false,
+            from_admin: false,
+            attachments: Vec::new(),
         };
 
         // Execute the job with timeout
@@ -770,6 +859,8 @@ impl Scheduler {
             session_mode: Some("isolated".to_string()), // Isolated to prevent state corruption
             selected_network: None,
             force_safe_mode: false,
+            from_admin: false,
+            attachments: Vec::new(),
         };
 
         // Execute the heartbeat
@@ -1020,6 +1111,8 @@ async fn execute_heartbeat_isolated(
         session_mode: Some("isolated".to_string()),
         selected_network: None,
         force_safe_mode: false,
+        from_admin: false,
+        attachments: Vec::new(),
     };
 
     // === DEFERRED AI CALL (fire and forget) ===
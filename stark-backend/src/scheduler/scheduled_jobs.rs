@@ -0,0 +1,204 @@
+//! Scheduled messages: recurrence parsing and execution
+//!
+//! Jobs are created by the `schedule_message` tool, persisted in the `scheduled_jobs`
+//! table, and run by the scheduler tick once due. A `text` job posts its text to the
+//! chat it was scheduled from; a `prompt` job runs its prompt through the
+//! `MessageDispatcher` as the user who scheduled it and posts the reply there.
+
+use super::reminders::{delivery_target, parse_relative, parse_utc_offset, send_to};
+use crate::channels::dispatcher::MessageDispatcher;
+use crate::channels::types::NormalizedMessage;
+use crate::db::tables::scheduled_jobs::ScheduledJob;
+use crate::db::Database;
+use chrono::{DateTime, Duration, FixedOffset, Utc};
+use cron::Schedule;
+use std::str::FromStr;
+use tokio::time::{timeout, Duration as TokioDuration};
+
+/// Shortest allowed gap between runs of a recurring job
+pub const MIN_REPEAT_MINUTES: i64 = 5;
+
+/// Longest a prompt job may run before it's abandoned
+const PROMPT_TIMEOUT_SECS: u64 = 10 * 60;
+
+/// How often a recurring job runs
+#[derive(Debug, Clone)]
+pub enum Recurrence {
+    Every(Duration),
+    Cron(Schedule),
+}
+
+/// Parse a repeat schedule: "hourly", "daily", "weekly", "every <duration>"
+/// ("every 30 minutes", "every 2h", "every day"), or a cron expression (5 fields,
+/// or 6 with seconds first).
+pub fn parse_recurrence(repeat: &str) -> Result<Recurrence, String> {
+    let text = repeat.trim().to_lowercase();
+    let every = match text.as_str() {
+        "hourly" => Some(Duration::hours(1)),
+        "daily" => Some(Duration::days(1)),
+        "weekly" => Some(Duration::weeks(1)),
+        _ => text
+            .strip_prefix("every ")
+            .map(str::trim)
+            .and_then(|rest| parse_relative(rest).or_else(|| parse_relative(&format!("1 {}", rest)))),
+    };
+
+    let recurrence = match every {
+        Some(interval) => Recurrence::Every(interval),
+        None => {
+            let expr = repeat.trim();
+            let expr = match expr.split_whitespace().count() {
+                5 => format!("0 {}", expr),
+                6 | 7 => expr.to_string(),
+                _ => {
+                    return Err(format!(
+                        "Couldn't understand repeat '{}'. Use hourly, daily, weekly, 'every 30 minutes', \
+                         or a cron expression like '0 9 * * MON-FRI'",
+                        repeat
+                    ))
+                }
+            };
+            Recurrence::Cron(
+                Schedule::from_str(&expr).map_err(|e| format!("Invalid cron expression '{}': {}", repeat, e))?,
+            )
+        }
+    };
+
+    let min_gap = Duration::minutes(MIN_REPEAT_MINUTES);
+    let gap = match &recurrence {
+        Recurrence::Every(interval) => Some(*interval),
+        Recurrence::Cron(schedule) => {
+            let mut upcoming = schedule.upcoming(Utc);
+            match (upcoming.next(), upcoming.next()) {
+                (Some(a), Some(b)) => Some(b - a),
+                (Some(_), None) => None,
+                _ => return Err(format!("Cron expression '{}' never runs", repeat)),
+            }
+        }
+    };
+    if gap.is_some_and(|g| g < min_gap) {
+        return Err(format!("Recurring messages can run at most every {} minutes", MIN_REPEAT_MINUTES));
+    }
+    Ok(recurrence)
+}
+
+impl Recurrence {
+    /// The first run strictly after `now`. Interval schedules keep their phase from
+    /// `last_due`, skipping runs missed while the bot was down; cron schedules are
+    /// evaluated in `offset`.
+    pub fn next_after(&self, last_due: DateTime<Utc>, now: DateTime<Utc>, offset: FixedOffset) -> Option<DateTime<Utc>> {
+        match self {
+            Recurrence::Every(interval) => {
+                if *interval <= Duration::zero() {
+                    return None;
+                }
+                let mut next = last_due.checked_add_signed(*interval)?;
+                if next <= now {
+                    let missed = (now - next).num_seconds() / interval.num_seconds().max(1) + 1;
+                    next = next.checked_add_signed(interval.checked_mul(i32::try_from(missed).ok()?)?)?;
+                }
+                Some(next)
+            }
+            Recurrence::Cron(schedule) => schedule
+                .after(&now.with_timezone(&offset))
+                .next()
+                .map(|t| t.with_timezone(&Utc)),
+        }
+    }
+}
+
+/// When a job should run after the current run; None for one-shot jobs (or a
+/// recurrence that no longer parses, which then runs once more and stops)
+pub fn next_run(job: &ScheduledJob, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let repeat = job.recurrence.as_deref()?;
+    let recurrence = match parse_recurrence(repeat) {
+        Ok(r) => r,
+        Err(e) => {
+            log::warn!("[SCHEDULED] Job #{} has an unusable repeat schedule: {}", job.id, e);
+            return None;
+        }
+    };
+    let offset = job
+        .timezone
+        .as_deref()
+        .and_then(|tz| parse_utc_offset(tz).ok())
+        .unwrap_or_else(|| FixedOffset::east_opt(0).unwrap());
+    recurrence.next_after(job.run_at, now, offset)
+}
+
+/// Run one due job and post the result to its chat
+pub async fn run_job(
+    db: &Database,
+    dispatcher: &MessageDispatcher,
+    client: &reqwest::Client,
+    job: &ScheduledJob,
+) -> Result<(), String> {
+    let (channel_type, token) = delivery_target(db, job.channel_id, &job.channel_type)?;
+
+    let text = if job.mode == "prompt" {
+        let message = NormalizedMessage {
+            channel_id: job.channel_id,
+            channel_type: job.channel_type.clone(),
+            chat_id: job.chat_id.clone(),
+            chat_name: None,
+            user_id: job.user_id.clone(),
+            user_name: format!("Scheduled job #{}", job.id),
+            text: job.content.clone(),
+            message_id: Some(format!("scheduled-{}-{}", job.id, Utc::now().timestamp())),
+            session_mode: None,
+            selected_network: None,
+            force_safe_mode: false,
+            from_admin: false,
+            attachments: Vec::new(),
+        };
+        let result = timeout(TokioDuration::from_secs(PROMPT_TIMEOUT_SECS), dispatcher.dispatch(message))
+            .await
+            .map_err(|_| format!("Prompt timed out after {}s", PROMPT_TIMEOUT_SECS))?;
+        if let Some(error) = result.error {
+            return Err(error);
+        }
+        result.response
+    } else {
+        job.content.clone()
+    };
+
+    if text.trim().is_empty() {
+        return Ok(());
+    }
+    send_to(client, channel_type, &token, &job.chat_id, &text).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(h: u32, m: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 15, h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn test_parse_recurrence() {
+        assert!(matches!(parse_recurrence("daily"), Ok(Recurrence::Every(d)) if d == Duration::days(1)));
+        assert!(matches!(parse_recurrence("every 2h"), Ok(Recurrence::Every(d)) if d == Duration::hours(2)));
+        assert!(matches!(parse_recurrence("Every day"), Ok(Recurrence::Every(d)) if d == Duration::days(1)));
+        assert!(matches!(parse_recurrence("0 9 * * MON-FRI"), Ok(Recurrence::Cron(_))));
+        assert!(parse_recurrence("every 1 minute").is_err());
+        assert!(parse_recurrence("* * * * *").is_err());
+        assert!(parse_recurrence("sometimes").is_err());
+    }
+
+    #[test]
+    fn test_next_after() {
+        let utc = FixedOffset::east_opt(0).unwrap();
+        let hourly = Recurrence::Every(Duration::hours(1));
+        assert_eq!(hourly.next_after(at(9, 0), at(9, 0), utc), Some(at(10, 0)));
+        // Runs missed while the bot was down are skipped, keeping the original phase
+        assert_eq!(hourly.next_after(at(9, 0), at(12, 30), utc), Some(at(13, 0)));
+
+        let nine_am = parse_recurrence("0 9 * * *").unwrap();
+        assert_eq!(nine_am.next_after(at(9, 0), at(9, 0), utc), Some(at(9, 0) + Duration::days(1)));
+        let plus_two = FixedOffset::east_opt(2 * 3600).unwrap();
+        assert_eq!(nine_am.next_after(at(6, 0), at(6, 0), plus_two), Some(at(7, 0)));
+    }
+}
@@ -1,10 +1,72 @@
 //! RewardEmitter with auto-scoring for tool success/failure,
-//! session efficiency, and loop detection.
+//! session efficiency, and loop detection, plus the filter/record types
+//! used to read recorded rewards back out.
 
-use serde_json::json;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::{json, Value};
 use std::sync::Arc;
 
-use super::span::{SpanCollector, SpanType};
+use super::span::{Span, SpanCollector, SpanType};
+
+/// Criteria for listing recorded rewards. Unset fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct RewardFilter {
+    /// Reward name, e.g. "tool_completed"
+    pub name: Option<String>,
+    /// Only rewards recorded at or after this time
+    pub since: Option<DateTime<Utc>>,
+    /// Only rewards recorded at or before this time
+    pub until: Option<DateTime<Utc>>,
+    /// Only rewards from rollouts on this channel
+    pub channel_id: Option<i64>,
+    /// Maximum number of rewards to return
+    pub limit: Option<usize>,
+}
+
+impl RewardFilter {
+    /// Whether a span is a reward matching this filter. `channel_id` is the
+    /// channel of the span's rollout, when known.
+    pub fn matches(&self, span: &Span, channel_id: Option<i64>) -> bool {
+        span.span_type == SpanType::Reward
+            && self.name.as_deref().map_or(true, |name| span.name == name)
+            && self.since.map_or(true, |since| span.started_at >= since)
+            && self.until.map_or(true, |until| span.started_at <= until)
+            && self.channel_id.map_or(true, |wanted| channel_id == Some(wanted))
+    }
+}
+
+/// A recorded reward: its value plus the attributes it was emitted with.
+#[derive(Debug, Clone, Serialize)]
+pub struct RewardRecord {
+    pub span_id: String,
+    pub rollout_id: String,
+    pub session_id: i64,
+    pub channel_id: Option<i64>,
+    pub name: String,
+    pub value: f64,
+    pub attributes: Value,
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl RewardRecord {
+    /// Build a record from a reward span; `None` for any other span type.
+    pub fn from_span(span: &Span, channel_id: Option<i64>) -> Option<Self> {
+        if span.span_type != SpanType::Reward {
+            return None;
+        }
+        Some(Self {
+            span_id: span.span_id.clone(),
+            rollout_id: span.rollout_id.clone(),
+            session_id: span.session_id,
+            channel_id,
+            name: span.name.clone(),
+            value: span.attributes.get("reward_value").and_then(|v| v.as_f64()).unwrap_or(0.0),
+            attributes: span.attributes.clone(),
+            recorded_at: span.started_at,
+        })
+    }
+}
 
 /// Emits structured reward signals based on execution outcomes.
 pub struct RewardEmitter {
@@ -135,3 +197,38 @@ impl RewardEmitter {
         self.collector.record(span);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collector_rewards_filtering() {
+        let collector = Arc::new(SpanCollector::new("rollout-1".to_string(), 3).with_channel(9));
+        let emitter = RewardEmitter::new(Arc::clone(&collector));
+        emitter.tool_completed("web_fetch", true, 200);
+        emitter.tool_completed("exec", false, 5000);
+        emitter.loop_detected(&[], 4);
+        collector.record(collector.start_span(SpanType::ToolCall, "web_fetch"));
+
+        let all = collector.rewards(&RewardFilter::default());
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].channel_id, Some(9));
+        assert!((all[0].value - 1.2).abs() < 1e-9);
+        assert_eq!(all[1].attributes["tool_name"], "exec");
+
+        let tools = collector.rewards(&RewardFilter {
+            name: Some("tool_completed".to_string()),
+            limit: Some(1),
+            ..Default::default()
+        });
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].attributes["tool_name"], "web_fetch");
+
+        let other_channel = RewardFilter { channel_id: Some(10), ..Default::default() };
+        assert!(collector.rewards(&other_channel).is_empty());
+
+        let future = RewardFilter { since: Some(Utc::now() + chrono::Duration::hours(1)), ..Default::default() };
+        assert!(collector.rewards(&future).is_empty());
+    }
+}
@@ -301,7 +301,7 @@ impl RolloutManager {
         let attempt = Attempt::new(0);
         rollout.attempts.push(attempt);
 
-        let collector = SpanCollector::new(rollout.rollout_id.clone(), session_id);
+        let collector = SpanCollector::new(rollout.rollout_id.clone(), session_id).with_channel(channel_id);
 
         // Persist the new rollout
         if let Err(e) = self.db.create_rollout(&rollout) {
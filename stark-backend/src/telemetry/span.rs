@@ -10,6 +10,8 @@ use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
 use std::sync::Arc;
 use parking_lot::Mutex;
 
+use super::reward::{RewardFilter, RewardRecord};
+
 /// The kind of operation a span represents.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -178,6 +180,8 @@ pub struct SpanCollector {
     session_id: AtomicI64,
     /// Current attempt index
     attempt_idx: AtomicU64,
+    /// The channel the rollout was triggered from, if known
+    channel_id: Option<i64>,
     /// Collected spans (thread-safe)
     spans: Mutex<Vec<Span>>,
 }
@@ -190,10 +194,22 @@ impl SpanCollector {
             rollout_id,
             session_id: AtomicI64::new(session_id),
             attempt_idx: AtomicU64::new(0),
+            channel_id: None,
             spans: Mutex::new(Vec::new()),
         }
     }
 
+    /// Associate the collector with the channel its rollout runs on.
+    pub fn with_channel(mut self, channel_id: i64) -> Self {
+        self.channel_id = Some(channel_id);
+        self
+    }
+
+    /// Get the channel ID, if set.
+    pub fn channel_id(&self) -> Option<i64> {
+        self.channel_id
+    }
+
     /// Get the rollout ID.
     pub fn rollout_id(&self) -> &str {
         &self.rollout_id
@@ -266,6 +282,19 @@ impl SpanCollector {
     pub fn snapshot(&self) -> Vec<Span> {
         self.spans.lock().clone()
     }
+
+    /// List the reward spans recorded so far that match the filter, oldest first.
+    pub fn rewards(&self, filter: &RewardFilter) -> Vec<RewardRecord> {
+        let spans = self.spans.lock();
+        let matching = spans
+            .iter()
+            .filter(|span| filter.matches(span, self.channel_id))
+            .filter_map(|span| RewardRecord::from_span(span, self.channel_id));
+        match filter.limit {
+            Some(limit) => matching.take(limit).collect(),
+            None => matching.collect(),
+        }
+    }
 }
 
 /// RAII guard that automatically completes a span when dropped.
@@ -4,10 +4,14 @@
 //! and prune old telemetry.
 
 use chrono::{DateTime, Duration, Utc};
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Instant;
 
 use super::adapter::{Adapter, ExecutionSummary, SpansToSummary, SpansToTimeline, SpansToTriplets, Timeline, Triplet};
+use super::reward::{RewardFilter, RewardRecord};
 use super::span::{Span, SpanCollector, SpanType};
 
 /// Retention policy for telemetry data.
@@ -49,10 +53,71 @@ pub struct RewardTypeStat {
     pub avg_value: f64,
 }
 
+/// How long an aggregated tool stats result is reused before re-scanning spans.
+const TOOL_STATS_CACHE_TTL_SECS: u64 = 30;
+
+/// Most distinct windows kept in the tool stats cache.
+const TOOL_STATS_CACHE_MAX_ENTRIES: usize = 32;
+
+/// Longest tool stats window; larger `since_hours` are clamped to it (about ten years).
+const MAX_TOOL_STATS_SINCE_HOURS: u64 = 24 * 365 * 10;
+
+/// Per-tool usage aggregated from `tool_completed` reward spans.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolStat {
+    pub tool_name: String,
+    pub calls: usize,
+    pub successes: usize,
+    pub failures: usize,
+    pub success_rate: f64,
+    pub mean_duration_ms: f64,
+    pub p95_duration_ms: u64,
+}
+
+/// Aggregate `tool_completed` rewards per tool, most-called first (ties by name).
+pub fn aggregate_tool_stats(rewards: &[RewardRecord]) -> Vec<ToolStat> {
+    let mut by_tool: HashMap<&str, (usize, Vec<u64>)> = HashMap::new();
+    for reward in rewards.iter().filter(|r| r.name == "tool_completed") {
+        let Some(tool_name) = reward.attributes.get("tool_name").and_then(|v| v.as_str()) else {
+            continue;
+        };
+        let success = reward.attributes.get("success").and_then(|v| v.as_bool()).unwrap_or(false);
+        let duration = reward.attributes.get("duration_ms").and_then(|v| v.as_u64()).unwrap_or(0);
+        let entry = by_tool.entry(tool_name).or_default();
+        if success {
+            entry.0 += 1;
+        }
+        entry.1.push(duration);
+    }
+
+    let mut stats: Vec<ToolStat> = by_tool
+        .into_iter()
+        .map(|(tool_name, (successes, mut durations))| {
+            durations.sort_unstable();
+            let calls = durations.len();
+            // Nearest-rank percentile
+            let p95_idx = ((calls as f64 * 0.95).ceil() as usize).clamp(1, calls) - 1;
+            ToolStat {
+                tool_name: tool_name.to_string(),
+                calls,
+                successes,
+                failures: calls - successes,
+                success_rate: successes as f64 / calls as f64,
+                mean_duration_ms: durations.iter().sum::<u64>() as f64 / calls as f64,
+                p95_duration_ms: durations[p95_idx],
+            }
+        })
+        .collect();
+    stats.sort_by(|a, b| b.calls.cmp(&a.calls).then_with(|| a.tool_name.cmp(&b.tool_name)));
+    stats
+}
+
 /// The telemetry store provides high-level persistence and query operations.
 pub struct TelemetryStore {
     db: Arc<crate::db::Database>,
     retention: RetentionPolicy,
+    /// Recent `get_tool_stats` results keyed by their `since_hours` window
+    tool_stats_cache: Mutex<HashMap<Option<u64>, (Instant, Vec<ToolStat>)>>,
 }
 
 impl TelemetryStore {
@@ -60,6 +125,7 @@ impl TelemetryStore {
         Self {
             db,
             retention: RetentionPolicy::default(),
+            tool_stats_cache: Mutex::new(HashMap::new()),
         }
     }
 
@@ -127,6 +193,47 @@ impl TelemetryStore {
         }
     }
 
+    /// List persisted rewards matching the filter, newest first.
+    pub fn query_rewards(&self, filter: &RewardFilter) -> Vec<RewardRecord> {
+        match self.db.query_reward_spans(filter) {
+            Ok(rows) => rows
+                .iter()
+                .filter_map(|(span, channel_id)| RewardRecord::from_span(span, *channel_id))
+                .collect(),
+            Err(e) => {
+                log::error!("[TELEMETRY] Failed to query rewards: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Per-tool call count, success rate and durations over the last `since_hours`
+    /// (all time when `None`). Results are cached briefly since this scans every
+    /// matching reward span.
+    pub fn get_tool_stats(&self, since_hours: Option<u64>) -> Vec<ToolStat> {
+        let since_hours = since_hours.map(|hours| hours.min(MAX_TOOL_STATS_SINCE_HOURS));
+        if let Some((computed_at, stats)) = self.tool_stats_cache.lock().get(&since_hours) {
+            if computed_at.elapsed().as_secs() < TOOL_STATS_CACHE_TTL_SECS {
+                return stats.clone();
+            }
+        }
+
+        let filter = RewardFilter {
+            name: Some("tool_completed".to_string()),
+            // The clamp keeps this in chrono's range
+            since: since_hours.map(|hours| Utc::now() - Duration::hours(hours as i64)),
+            ..Default::default()
+        };
+        let stats = aggregate_tool_stats(&self.query_rewards(&filter));
+        let mut cache = self.tool_stats_cache.lock();
+        cache.retain(|_, (computed_at, _)| computed_at.elapsed().as_secs() < TOOL_STATS_CACHE_TTL_SECS);
+        if cache.len() >= TOOL_STATS_CACHE_MAX_ENTRIES {
+            cache.clear();
+        }
+        cache.insert(since_hours, (Instant::now(), stats.clone()));
+        stats
+    }
+
     /// Get a timeline view for a session.
     pub fn get_session_timeline(&self, session_id: i64) -> Timeline {
         let spans = self.get_session_spans(session_id);
@@ -228,3 +335,60 @@ impl TelemetryStore {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn tool_reward(tool: &str, success: bool, duration_ms: u64) -> RewardRecord {
+        RewardRecord {
+            span_id: String::new(),
+            rollout_id: String::new(),
+            session_id: 0,
+            channel_id: None,
+            name: "tool_completed".to_string(),
+            value: 0.0,
+            attributes: json!({ "tool_name": tool, "success": success, "duration_ms": duration_ms }),
+            recorded_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_aggregate_tool_stats() {
+        let mut rewards: Vec<RewardRecord> = (1..=20).map(|i| tool_reward("exec", i % 4 != 0, i * 100)).collect();
+        rewards.push(tool_reward("web_fetch", true, 300));
+        rewards.push(tool_reward("web_fetch", false, 100));
+        let mut other = tool_reward("ignored", true, 1);
+        other.name = "loop_detected".to_string();
+        rewards.push(other);
+
+        let stats = aggregate_tool_stats(&rewards);
+        assert_eq!(stats.len(), 2);
+
+        let exec = &stats[0];
+        assert_eq!(exec.tool_name, "exec");
+        assert_eq!(exec.calls, 20);
+        assert_eq!(exec.failures, 5);
+        assert!((exec.success_rate - 0.75).abs() < 1e-9);
+        assert!((exec.mean_duration_ms - 1050.0).abs() < 1e-9);
+        assert_eq!(exec.p95_duration_ms, 1900);
+
+        let web = &stats[1];
+        assert_eq!(web.calls, 2);
+        assert_eq!(web.p95_duration_ms, 300);
+    }
+
+    #[test]
+    fn test_tool_stats_window_and_cache_are_bounded() {
+        let store = TelemetryStore::new(Arc::new(crate::db::Database::new(":memory:").unwrap()));
+        // Huge windows are clamped instead of overflowing chrono
+        assert!(store.get_tool_stats(Some(u64::MAX)).is_empty());
+        assert!(store.tool_stats_cache.lock().contains_key(&Some(MAX_TOOL_STATS_SINCE_HOURS)));
+
+        for hours in 0..(TOOL_STATS_CACHE_MAX_ENTRIES as u64 * 3) {
+            store.get_tool_stats(Some(hours));
+        }
+        assert!(store.tool_stats_cache.lock().len() <= TOOL_STATS_CACHE_MAX_ENTRIES);
+    }
+}
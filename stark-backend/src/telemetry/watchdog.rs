@@ -4,8 +4,9 @@
 //! Integrates with rollout retry on timeout.
 
 use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use chrono::Utc;
 use parking_lot::Mutex;
@@ -27,6 +28,9 @@ pub struct WatchdogConfig {
     pub heartbeat_interval_secs: u64,
     /// Maximum time without a heartbeat before marking as unresponsive (seconds)
     pub heartbeat_max_silence_secs: u64,
+    /// Interval between `agent.heartbeat` liveness events while a dispatch runs (seconds)
+    #[serde(default = "default_liveness_interval_secs")]
+    pub liveness_interval_secs: u64,
     /// Per-tool timeout overrides (tool_name → timeout_secs)
     pub tool_overrides: std::collections::HashMap<String, u64>,
 }
@@ -39,17 +43,24 @@ impl Default for WatchdogConfig {
         tool_overrides.insert("exec".to_string(), 300);
         tool_overrides.insert("x402_fetch".to_string(), 120);
         tool_overrides.insert("deploy".to_string(), 600);
+        // summarize makes one AI call per chunk
+        tool_overrides.insert("summarize".to_string(), 300);
 
         Self {
             tool_timeout_secs: 60,
             llm_timeout_secs: 180,
             heartbeat_interval_secs: 30,
             heartbeat_max_silence_secs: 120,
+            liveness_interval_secs: default_liveness_interval_secs(),
             tool_overrides,
         }
     }
 }
 
+fn default_liveness_interval_secs() -> u64 {
+    10
+}
+
 impl WatchdogConfig {
     /// Get the timeout for a specific tool, with override support.
     pub fn timeout_for_tool(&self, tool_name: &str) -> Duration {
@@ -97,6 +108,10 @@ pub struct Watchdog {
     reward_emitter: Arc<RewardEmitter>,
     /// Tracks the last heartbeat time for the current execution
     last_heartbeat: Arc<Mutex<chrono::DateTime<Utc>>>,
+    /// When the watched execution started
+    started_at: Instant,
+    /// Tool calls started so far
+    steps: AtomicU64,
 }
 
 impl Watchdog {
@@ -110,9 +125,16 @@ impl Watchdog {
             collector,
             reward_emitter,
             last_heartbeat: Arc::new(Mutex::new(Utc::now())),
+            started_at: Instant::now(),
+            steps: AtomicU64::new(0),
         }
     }
 
+    /// Number of tool calls started under this watchdog.
+    pub fn steps(&self) -> u64 {
+        self.steps.load(Ordering::Relaxed)
+    }
+
     /// Get the watchdog configuration.
     pub fn config(&self) -> &WatchdogConfig {
         &self.config
@@ -150,6 +172,7 @@ impl Watchdog {
     {
         let tool_timeout = self.config.timeout_for_tool(tool_name);
         let timeout_ms = tool_timeout.as_millis() as u64;
+        self.steps.fetch_add(1, Ordering::Relaxed);
 
         let mut span = self.collector.start_span(SpanType::Watchdog, format!("guard_tool:{}", tool_name));
         span.attributes = json!({
@@ -191,6 +214,7 @@ impl Watchdog {
     {
         let tool_timeout = self.config.timeout_for_tool(tool_name);
         let timeout_ms = tool_timeout.as_millis() as u64;
+        self.steps.fetch_add(1, Ordering::Relaxed);
 
         let mut span = self.collector.start_span(SpanType::Watchdog, format!("guard_tool:{}", tool_name));
         span.attributes = json!({
@@ -282,34 +306,51 @@ impl Watchdog {
 
     /// Start a background heartbeat monitor task.
     ///
+    /// Emits an `agent.heartbeat` event every `liveness_interval_secs` so clients
+    /// can tell a long run from a hung one, and warns when the execution goes silent.
     /// The monitor only observes — it does NOT reset the heartbeat. Only actual
     /// execution (guard_tool_call, guard_tool, guard_llm) registers heartbeats.
     /// Returns a JoinHandle that should be aborted when the dispatch completes.
     pub fn start_heartbeat_monitor(
         self: &Arc<Self>,
         channel_id: i64,
+        chat_id: &str,
         broadcaster: Arc<crate::gateway::events::EventBroadcaster>,
     ) -> tokio::task::JoinHandle<()> {
         let watchdog = Arc::clone(self);
+        let chat_id = chat_id.to_string();
         let interval = Duration::from_secs(watchdog.config.heartbeat_interval_secs);
+        let liveness_interval = Duration::from_secs(watchdog.config.liveness_interval_secs.max(1));
 
         tokio::spawn(async move {
             let mut ticker = tokio::time::interval(interval);
             ticker.tick().await; // skip first immediate tick
+            let mut liveness = tokio::time::interval(liveness_interval);
+            liveness.tick().await;
 
             loop {
-                ticker.tick().await;
-
-                if watchdog.is_unresponsive() {
-                    log::warn!(
-                        "[WATCHDOG] Channel {} execution appears unresponsive (no heartbeat for >{}s)",
-                        channel_id,
-                        watchdog.config.heartbeat_max_silence_secs
-                    );
-                    broadcaster.broadcast(crate::gateway::protocol::GatewayEvent::agent_error(
-                        channel_id,
-                        "Execution may be unresponsive. Monitoring...",
-                    ));
+                tokio::select! {
+                    _ = ticker.tick() => {
+                        if watchdog.is_unresponsive() {
+                            log::warn!(
+                                "[WATCHDOG] Channel {} execution appears unresponsive (no heartbeat for >{}s)",
+                                channel_id,
+                                watchdog.config.heartbeat_max_silence_secs
+                            );
+                            broadcaster.broadcast(crate::gateway::protocol::GatewayEvent::agent_error(
+                                channel_id,
+                                "Execution may be unresponsive. Monitoring...",
+                            ));
+                        }
+                    }
+                    _ = liveness.tick() => {
+                        broadcaster.broadcast(crate::gateway::protocol::GatewayEvent::agent_heartbeat(
+                            channel_id,
+                            Some(&chat_id),
+                            watchdog.started_at.elapsed().as_secs(),
+                            watchdog.steps(),
+                        ));
+                    }
                 }
                 // Note: We intentionally do NOT call heartbeat() here.
                 // Only actual tool/LLM execution registers heartbeats.
@@ -0,0 +1,368 @@
+use crate::tools::registry::Tool;
+use crate::tools::types::{
+    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+};
+use crate::tools::ToolSafetyLevel;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Maximum output size in characters to prevent context bloat
+const MAX_OUTPUT_SIZE: usize = 12000;
+
+/// Largest LCS table (changed lines on one side × the other) we are willing to build
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// Diff tool - unified diff between two workspace files or text blobs
+pub struct DiffTool {
+    definition: ToolDefinition,
+}
+
+impl DiffTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+        properties.insert(
+            "path_a".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Original file (relative to workspace directory). Use this or text_a.".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+        properties.insert(
+            "path_b".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Modified file (relative to workspace directory). Use this or text_b.".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+        properties.insert(
+            "text_a".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Original text, instead of path_a".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+        properties.insert(
+            "text_b".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Modified text, instead of path_b".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+        properties.insert(
+            "context_lines".to_string(),
+            PropertySchema {
+                schema_type: "integer".to_string(),
+                description: "Unchanged lines shown around each change (default: 3)".to_string(),
+                default: Some(json!(3)),
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        DiffTool {
+            definition: ToolDefinition {
+                name: "diff".to_string(),
+                description: "Show a unified diff between two files or two text blobs, with a count of lines added and removed. \
+                    Use it to show the user what changed before they accept an edit. Paths must be within the workspace directory."
+                    .to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec![],
+                },
+                group: ToolGroup::Filesystem,
+                hidden: false,
+            },
+        }
+    }
+}
+
+impl Default for DiffTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DiffParams {
+    path_a: Option<String>,
+    path_b: Option<String>,
+    text_a: Option<String>,
+    text_b: Option<String>,
+    context_lines: Option<usize>,
+}
+
+/// Read a file, refusing paths that resolve outside the workspace
+async fn read_workspace_file(workspace: &Path, path: &str) -> Result<String, String> {
+    let requested = Path::new(path);
+    let full_path = if requested.is_absolute() {
+        requested.to_path_buf()
+    } else {
+        workspace.join(requested)
+    };
+
+    let canonical_base = workspace
+        .canonicalize()
+        .map_err(|e| format!("Cannot resolve workspace directory: {}", e))?;
+    let canonical_path = full_path
+        .canonicalize()
+        .map_err(|e| format!("Cannot resolve file path '{}': {}", path, e))?;
+    if !canonical_path.starts_with(&canonical_base) {
+        return Err(format!(
+            "Access denied: path '{}' is outside the allowed directory",
+            path
+        ));
+    }
+    if !canonical_path.is_file() {
+        return Err(format!("Path is not a file: {}", path));
+    }
+
+    tokio::fs::read_to_string(&canonical_path)
+        .await
+        .map_err(|e| format!("Failed to read '{}': {}", path, e))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Line-level edit script from `a` to `b`: (op, index into a, index into b)
+fn edit_script(a: &[&str], b: &[&str]) -> Result<Vec<(Op, usize, usize)>, String> {
+    // Common prefix/suffix are cheap and keep the LCS table small for typical edits
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let (mid_a, mid_b) = (&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]);
+    let (n, m) = (mid_a.len(), mid_b.len());
+
+    if n.saturating_mul(m) > MAX_DIFF_CELLS {
+        return Err(format!(
+            "Inputs differ in too many lines to diff ({} × {} changed lines)",
+            n, m
+        ));
+    }
+
+    // lcs[i][j] = LCS length of mid_a[i..] and mid_b[j..]
+    let mut lcs = vec![0u32; (n + 1) * (m + 1)];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i * (m + 1) + j] = if mid_a[i] == mid_b[j] {
+                lcs[(i + 1) * (m + 1) + j + 1] + 1
+            } else {
+                lcs[(i + 1) * (m + 1) + j].max(lcs[i * (m + 1) + j + 1])
+            };
+        }
+    }
+
+    let mut ops: Vec<(Op, usize, usize)> = (0..prefix).map(|k| (Op::Equal, k, k)).collect();
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && mid_a[i] == mid_b[j] {
+            ops.push((Op::Equal, prefix + i, prefix + j));
+            i += 1;
+            j += 1;
+        } else if i < n && (j == m || lcs[(i + 1) * (m + 1) + j] >= lcs[i * (m + 1) + j + 1]) {
+            // Prefer deletions first so replaced lines read as "-old" then "+new"
+            ops.push((Op::Delete, prefix + i, prefix + j));
+            i += 1;
+        } else {
+            ops.push((Op::Insert, prefix + i, prefix + j));
+            j += 1;
+        }
+    }
+    ops.extend((0..suffix).map(|k| (Op::Equal, a.len() - suffix + k, b.len() - suffix + k)));
+    Ok(ops)
+}
+
+/// Unified diff plus (lines added, lines removed)
+fn unified_diff(
+    a: &str,
+    b: &str,
+    label_a: &str,
+    label_b: &str,
+    context: usize,
+) -> Result<(String, usize, usize), String> {
+    let lines_a: Vec<&str> = a.lines().collect();
+    let lines_b: Vec<&str> = b.lines().collect();
+    let ops = edit_script(&lines_a, &lines_b)?;
+
+    let added = ops.iter().filter(|(op, _, _)| *op == Op::Insert).count();
+    let removed = ops.iter().filter(|(op, _, _)| *op == Op::Delete).count();
+    if added == 0 && removed == 0 {
+        return Ok((String::new(), 0, 0));
+    }
+
+    // Group changes into hunks, merging those separated by at most 2×context unchanged lines
+    let changed: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, (op, _, _))| *op != Op::Equal)
+        .map(|(k, _)| k)
+        .collect();
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for &k in &changed {
+        let start = k.saturating_sub(context);
+        let end = (k + context + 1).min(ops.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut out = format!("--- {}\n+++ {}\n", label_a, label_b);
+    for (start, end) in hunks {
+        let slice = &ops[start..end];
+        let count_a = slice.iter().filter(|(op, _, _)| *op != Op::Insert).count();
+        let count_b = slice.iter().filter(|(op, _, _)| *op != Op::Delete).count();
+        let (_, first_a, first_b) = slice[0];
+        // Unified diff line numbers are 1-based; empty ranges point at the line before
+        let start_a = if count_a == 0 { first_a } else { first_a + 1 };
+        let start_b = if count_b == 0 { first_b } else { first_b + 1 };
+        out.push_str(&format!("@@ -{},{} +{},{} @@\n", start_a, count_a, start_b, count_b));
+        for &(op, ia, ib) in slice {
+            match op {
+                Op::Equal => out.push_str(&format!(" {}\n", lines_a[ia])),
+                Op::Delete => out.push_str(&format!("-{}\n", lines_a[ia])),
+                Op::Insert => out.push_str(&format!("+{}\n", lines_b[ib])),
+            }
+        }
+    }
+    Ok((out, added, removed))
+}
+
+#[async_trait]
+impl Tool for DiffTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: DiffParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        let workspace = context
+            .workspace_dir
+            .as_ref()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+
+        let mut sides = Vec::with_capacity(2);
+        for (side, path, text) in [
+            ("a", params.path_a, params.text_a),
+            ("b", params.path_b, params.text_b),
+        ] {
+            let resolved = match (path, text) {
+                (Some(path), None) => match read_workspace_file(&workspace, &path).await {
+                    Ok(content) => (path, content),
+                    Err(e) => return ToolResult::error(e),
+                },
+                (None, Some(text)) => (format!("text_{}", side), text),
+                (Some(_), Some(_)) => {
+                    return ToolResult::error(format!("Provide path_{} or text_{}, not both", side, side))
+                }
+                (None, None) => {
+                    return ToolResult::error(format!("Provide path_{} or text_{}", side, side))
+                }
+            };
+            sides.push(resolved);
+        }
+        let (label_b, content_b) = sides.pop().unwrap();
+        let (label_a, content_a) = sides.pop().unwrap();
+
+        let context_lines = params.context_lines.unwrap_or(3).min(50);
+        let (diff, added, removed) =
+            match unified_diff(&content_a, &content_b, &label_a, &label_b, context_lines) {
+                Ok(d) => d,
+                Err(e) => return ToolResult::error(e),
+            };
+
+        if diff.is_empty() {
+            return ToolResult::success(format!("No differences between {} and {}.", label_a, label_b))
+                .with_metadata(json!({ "added": 0, "removed": 0, "truncated": false }));
+        }
+
+        let mut output = format!("{} line(s) added, {} line(s) removed\n\n{}", added, removed, diff);
+        let truncated = output.len() > MAX_OUTPUT_SIZE;
+        if truncated {
+            let mut cut = MAX_OUTPUT_SIZE;
+            while !output.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            output.truncate(cut);
+            output.push_str("\n\n⚠️ [Diff truncated due to size - diff smaller sections or use fewer context_lines]");
+        }
+
+        ToolResult::success(output).with_metadata(json!({
+            "a": label_a,
+            "b": label_b,
+            "added": added,
+            "removed": removed,
+            "truncated": truncated
+        }))
+    }
+
+    fn safety_level(&self) -> ToolSafetyLevel {
+        ToolSafetyLevel::ReadOnly
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_unified_diff_hunk() {
+        let a = "one\ntwo\nthree\nfour\n";
+        let b = "one\ntwo\n3\nfour\nfive\n";
+        let (diff, added, removed) = unified_diff(a, b, "a.txt", "b.txt", 1).unwrap();
+        assert_eq!((added, removed), (2, 1));
+        assert_eq!(
+            diff,
+            "--- a.txt\n+++ b.txt\n@@ -2,3 +2,4 @@\n two\n-three\n+3\n four\n+five\n"
+        );
+    }
+
+    #[test]
+    fn test_unified_diff_identical_and_empty() {
+        assert_eq!(unified_diff("x\n", "x\n", "a", "b", 3).unwrap().0, "");
+        let (diff, added, removed) = unified_diff("", "new\n", "a", "b", 3).unwrap();
+        assert_eq!((added, removed), (1, 0));
+        assert!(diff.contains("@@ -0,0 +1,1 @@\n+new\n"));
+    }
+
+    #[tokio::test]
+    async fn test_diff_rejects_outside_workspace() {
+        let temp_dir = TempDir::new().unwrap();
+        let context = ToolContext::new().with_workspace(temp_dir.path().to_string_lossy().to_string());
+        let result = DiffTool::new()
+            .execute(json!({ "path_a": "/etc/passwd", "text_b": "" }), &context)
+            .await;
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("outside the allowed directory"));
+    }
+}
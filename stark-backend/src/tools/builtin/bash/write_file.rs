@@ -229,7 +229,7 @@ impl Tool for WriteFileTool {
         match result {
             Ok(_) => {
                 let bytes_written = params.content.len();
-                context.record_disk_write(bytes_written);
+                context.record_file_write(&final_path, bytes_written);
                 let lines_written = params.content.lines().count();
                 let mode = if append { "appended to" } else { "written to" };
 
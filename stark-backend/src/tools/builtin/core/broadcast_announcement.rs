@@ -0,0 +1,163 @@
+use crate::channels::announce;
+use crate::tools::registry::Tool;
+use crate::tools::types::{
+    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Tool for posting one announcement to every enabled channel at once.
+/// Each channel receives it in the chat configured by its `announcement_chat_id` setting.
+pub struct BroadcastAnnouncementTool {
+    definition: ToolDefinition,
+}
+
+impl BroadcastAnnouncementTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+
+        properties.insert(
+            "message".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "The announcement text to post on every channel".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "confirm".to_string(),
+            PropertySchema {
+                schema_type: "boolean".to_string(),
+                description: "Must be true. Broadcasting reaches every enabled channel, so only confirm after the user explicitly asked for it.".to_string(),
+                default: Some(json!(false)),
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        BroadcastAnnouncementTool {
+            definition: ToolDefinition {
+                name: "broadcast_announcement".to_string(),
+                description: "Post an announcement to all enabled channels (Discord, Telegram, Slack) in their configured announcement chat. \
+                    Returns which channels received it and which failed or were skipped."
+                    .to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec!["message".to_string(), "confirm".to_string()],
+                },
+                group: ToolGroup::Messaging,
+                hidden: false,
+            },
+        }
+    }
+}
+
+impl Default for BroadcastAnnouncementTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct BroadcastAnnouncementParams {
+    message: String,
+    #[serde(default)]
+    confirm: bool,
+}
+
+#[async_trait]
+impl Tool for BroadcastAnnouncementTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: BroadcastAnnouncementParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        if !params.confirm {
+            return ToolResult::error(
+                "Broadcast not sent: set confirm=true once the user has approved posting to every channel.",
+            );
+        }
+        if let Err(e) = announce::validate_announcement(&params.message) {
+            return ToolResult::error(e);
+        }
+
+        let db = match &context.database {
+            Some(db) => db,
+            None => return ToolResult::error("Database not available"),
+        };
+
+        let results = match announce::broadcast_announcement(db, &context.http_client(), &params.message).await {
+            Ok(r) => r,
+            Err(e) => return ToolResult::error(e),
+        };
+
+        if results.is_empty() {
+            return ToolResult::error("No enabled channels to announce to");
+        }
+
+        let delivered = results.iter().filter(|r| r.success).count();
+        let mut output = format!(
+            "Announcement delivered to {}/{} channels:\n",
+            delivered,
+            results.len()
+        );
+        for r in &results {
+            if r.success {
+                output.push_str(&format!("- {} ({}): sent\n", r.channel_name, r.channel_type));
+            } else {
+                output.push_str(&format!(
+                    "- {} ({}): {}\n",
+                    r.channel_name,
+                    r.channel_type,
+                    r.error.as_deref().unwrap_or("failed")
+                ));
+            }
+        }
+
+        let metadata = json!({
+            "delivered": delivered,
+            "total": results.len(),
+            "results": results,
+        });
+        if delivered == 0 {
+            ToolResult::error(output).with_metadata(metadata)
+        } else {
+            ToolResult::success(output).with_metadata(metadata)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_requires_confirmation() {
+        let tool = BroadcastAnnouncementTool::new();
+        let result = tool
+            .execute(json!({ "message": "Maintenance tonight" }), &ToolContext::default())
+            .await;
+        assert!(!result.success);
+        assert!(result.content.contains("confirm=true"));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_empty_message() {
+        let tool = BroadcastAnnouncementTool::new();
+        let result = tool
+            .execute(json!({ "message": "  ", "confirm": true }), &ToolContext::default())
+            .await;
+        assert!(!result.success);
+    }
+}
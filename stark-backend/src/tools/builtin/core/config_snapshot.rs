@@ -0,0 +1,308 @@
+use crate::backup::snapshot::{self, ConfigSnapshot, ConflictStrategy, ImportOptions, SecretMode};
+use crate::tools::registry::Tool;
+use crate::tools::types::{
+    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Workspace subdirectory snapshots are written to and read from
+const SNAPSHOT_DIR: &str = "config_snapshots";
+
+/// Tool for exporting the channel/agent configuration to a snapshot file and restoring it
+pub struct ConfigSnapshotTool {
+    definition: ToolDefinition,
+}
+
+impl ConfigSnapshotTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+
+        properties.insert(
+            "action".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "'export' writes a snapshot file; 'import' validates a snapshot file and restores it".to_string(),
+                default: None,
+                items: None,
+                enum_values: Some(vec!["export".to_string(), "import".to_string()]),
+            },
+        );
+
+        properties.insert(
+            "file".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: format!(
+                    "Snapshot file name inside the workspace '{}' directory (required for import; generated for export if omitted)",
+                    SNAPSHOT_DIR
+                ),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "secrets".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Export only: 'redact' replaces tokens with a placeholder, 'encrypt' encrypts them with the wallet key".to_string(),
+                default: Some(json!("redact")),
+                items: None,
+                enum_values: Some(vec!["redact".to_string(), "encrypt".to_string()]),
+            },
+        );
+
+        properties.insert(
+            "conflict".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Import only: what to do with channels that already exist (same type and name)".to_string(),
+                default: Some(json!("fail")),
+                items: None,
+                enum_values: Some(vec!["fail".to_string(), "skip".to_string(), "replace".to_string()]),
+            },
+        );
+
+        properties.insert(
+            "dry_run".to_string(),
+            PropertySchema {
+                schema_type: "boolean".to_string(),
+                description: "Import only: validate and describe the changes without applying them".to_string(),
+                default: Some(json!(true)),
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        ConfigSnapshotTool {
+            definition: ToolDefinition {
+                name: "config_snapshot".to_string(),
+                description: "Snapshot or restore the bot's configuration (channels, channel settings, AI endpoint settings) \
+                    as a portable JSON file in the workspace. Import validates the whole file first and applies it in one \
+                    transaction; run it with dry_run=true before applying."
+                    .to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec!["action".to_string()],
+                },
+                group: ToolGroup::System,
+                hidden: false,
+            },
+        }
+    }
+}
+
+impl Default for ConfigSnapshotTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ConfigSnapshotParams {
+    action: String,
+    #[serde(default)]
+    file: Option<String>,
+    #[serde(default)]
+    secrets: Option<String>,
+    #[serde(default)]
+    conflict: ConflictStrategy,
+    #[serde(default = "default_dry_run")]
+    dry_run: bool,
+}
+
+fn default_dry_run() -> bool {
+    true
+}
+
+/// Resolve a bare file name inside the snapshot directory (no path components allowed)
+fn snapshot_path(context: &ToolContext, file: &str) -> Result<PathBuf, String> {
+    let workspace = context
+        .workspace_dir
+        .as_ref()
+        .ok_or("Workspace directory not available")?;
+    let valid = !file.is_empty()
+        && file != "."
+        && file != ".."
+        && !file.contains(['/', '\\']);
+    if !valid {
+        return Err(format!("Invalid snapshot file name '{}': use a plain file name", file));
+    }
+    Ok(PathBuf::from(workspace).join(SNAPSHOT_DIR).join(file))
+}
+
+#[async_trait]
+impl Tool for ConfigSnapshotTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: ConfigSnapshotParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        let db = match &context.database {
+            Some(db) => db,
+            None => return ToolResult::error("Database not available"),
+        };
+
+        match params.action.as_str() {
+            "export" => {
+                // Plaintext credentials are only available through the authenticated API
+                let mode = match params.secrets.as_deref() {
+                    Some("encrypt") => SecretMode::Encrypt,
+                    _ => SecretMode::Redact,
+                };
+                let key = if mode == SecretMode::Encrypt {
+                    let wallet_provider = match &context.wallet_provider {
+                        Some(wp) => wp,
+                        None => return ToolResult::error("No wallet configured; use secrets='redact'"),
+                    };
+                    match wallet_provider.get_encryption_key().await {
+                        Ok(k) => Some(k),
+                        Err(e) => return ToolResult::error(format!("Failed to get encryption key: {}", e)),
+                    }
+                } else {
+                    None
+                };
+
+                let snapshot = match snapshot::export_snapshot(db, mode, key.as_deref()) {
+                    Ok(s) => s,
+                    Err(e) => return ToolResult::error(e),
+                };
+                let file = params
+                    .file
+                    .unwrap_or_else(|| format!("config-{}.json", snapshot.created_at.format("%Y%m%d-%H%M%S")));
+                let path = match snapshot_path(context, &file) {
+                    Ok(p) => p,
+                    Err(e) => return ToolResult::error(e),
+                };
+                let json = match serde_json::to_string_pretty(&snapshot) {
+                    Ok(j) => j,
+                    Err(e) => return ToolResult::error(format!("Failed to serialize snapshot: {}", e)),
+                };
+                if let Some(parent) = path.parent() {
+                    if let Err(e) = tokio::fs::create_dir_all(parent).await {
+                        return ToolResult::error(format!("Failed to create snapshot directory: {}", e));
+                    }
+                }
+                if let Err(e) = tokio::fs::write(&path, json).await {
+                    return ToolResult::error(format!("Failed to write snapshot: {}", e));
+                }
+
+                ToolResult::success(format!(
+                    "Exported {} channels and {} agent settings to {}/{} (credentials {}).",
+                    snapshot.channels.len(),
+                    snapshot.agent_settings.len(),
+                    SNAPSHOT_DIR,
+                    file,
+                    if mode == SecretMode::Encrypt { "encrypted" } else { "redacted" }
+                ))
+                .with_metadata(json!({
+                    "file": format!("{}/{}", SNAPSHOT_DIR, file),
+                    "channels": snapshot.channels.len(),
+                    "agent_settings": snapshot.agent_settings.len(),
+                    "secrets": mode,
+                }))
+            }
+
+            "import" => {
+                let file = match params.file.as_deref() {
+                    Some(f) => f,
+                    None => return ToolResult::error("'file' is required for import"),
+                };
+                let path = match snapshot_path(context, file) {
+                    Ok(p) => p,
+                    Err(e) => return ToolResult::error(e),
+                };
+                let content = match tokio::fs::read_to_string(&path).await {
+                    Ok(c) => c,
+                    Err(e) => return ToolResult::error(format!("Failed to read {}/{}: {}", SNAPSHOT_DIR, file, e)),
+                };
+                let snapshot: ConfigSnapshot = match serde_json::from_str(&content) {
+                    Ok(s) => s,
+                    Err(e) => return ToolResult::error(format!("Not a valid config snapshot: {}", e)),
+                };
+
+                let key = match (&context.wallet_provider, snapshot.secrets) {
+                    (Some(wp), SecretMode::Encrypt) => wp.get_encryption_key().await.ok(),
+                    _ => None,
+                };
+                let (channels, agent_settings) = match (db.list_channels(), db.list_agent_settings()) {
+                    (Ok(c), Ok(a)) => (c, a),
+                    (Err(e), _) | (_, Err(e)) => return ToolResult::error(format!("Failed to read current config: {}", e)),
+                };
+                let options = ImportOptions {
+                    conflict: params.conflict,
+                    ..Default::default()
+                };
+                let plan = match snapshot::plan_import(&snapshot, &channels, &agent_settings, options, key.as_deref()) {
+                    Ok(p) => p,
+                    Err(errors) => {
+                        return ToolResult::error(format!(
+                            "Snapshot failed validation; nothing was imported:\n- {}",
+                            errors.join("\n- ")
+                        ))
+                        .with_metadata(json!({ "errors": errors }));
+                    }
+                };
+
+                if params.dry_run {
+                    let mut summary = format!(
+                        "Dry run: {} channels to create, {} to replace, {} to skip; {} agent settings. Run again with dry_run=false to apply.",
+                        plan.channels.iter().filter(|c| matches!(c.action, snapshot::ChannelAction::Create)).count(),
+                        plan.channels.iter().filter(|c| matches!(c.action, snapshot::ChannelAction::Replace { .. })).count(),
+                        plan.channels.iter().filter(|c| matches!(c.action, snapshot::ChannelAction::Skip { .. })).count(),
+                        plan.agent_settings.len()
+                    );
+                    for warning in &plan.warnings {
+                        summary.push_str(&format!("\nWarning: {}", warning));
+                    }
+                    return ToolResult::success(summary).with_metadata(json!({ "dry_run": true, "plan": plan }));
+                }
+
+                match db.apply_config_import(&plan) {
+                    Ok(report) => {
+                        let mut summary = format!(
+                            "Imported configuration: {} channels created, {} replaced, {} skipped; {} agent settings. \
+                             Restart replaced channels that are running to pick up the changes.",
+                            report.channels_created,
+                            report.channels_replaced,
+                            report.channels_skipped,
+                            report.agent_settings_imported
+                        );
+                        for warning in &report.warnings {
+                            summary.push_str(&format!("\nWarning: {}", warning));
+                        }
+                        ToolResult::success(summary).with_metadata(json!({ "dry_run": false, "report": report }))
+                    }
+                    Err(e) => ToolResult::error(format!("Import failed and was rolled back: {}", e)),
+                }
+            }
+
+            other => ToolResult::error(format!("Unknown action '{}'. Use 'export' or 'import'.", other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_path_rejects_traversal() {
+        let ctx = ToolContext::new().with_workspace("/tmp/ws".to_string());
+        assert!(snapshot_path(&ctx, "config.json").unwrap().ends_with("config_snapshots/config.json"));
+        assert!(snapshot_path(&ctx, "../secrets.json").is_err());
+        assert!(snapshot_path(&ctx, "..").is_err());
+        assert!(snapshot_path(&ctx, "").is_err());
+    }
+}
@@ -0,0 +1,162 @@
+use crate::tools::registry::Tool;
+use crate::tools::types::{
+    ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult, ToolSafetyLevel,
+};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Key in `ToolContext::extra` holding the tool definitions currently visible to the model.
+/// Populated by the dispatcher right before `list_capabilities` executes, so the answer
+/// reflects the live toolset (subtype, skill requires_tools, per-channel config, safe mode).
+pub const AVAILABLE_TOOLS_EXTRA_KEY: &str = "available_tools";
+
+/// Maximum length of the per-tool summary returned to the model
+const SUMMARY_MAX_CHARS: usize = 140;
+
+/// Tool that lets the agent truthfully describe its own capabilities
+pub struct ListCapabilitiesTool {
+    definition: ToolDefinition,
+}
+
+impl ListCapabilitiesTool {
+    pub fn new() -> Self {
+        ListCapabilitiesTool {
+            definition: ToolDefinition {
+                name: "list_capabilities".to_string(),
+                description: "List the tools you can use right now (names and short descriptions). \
+                    Call this when the user asks what you can do, instead of guessing."
+                    .to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties: HashMap::new(),
+                    required: vec![],
+                },
+                group: ToolGroup::System,
+                hidden: false,
+            },
+        }
+    }
+
+    /// Build the `extra` payload for a set of tool definitions
+    pub fn available_tools_value(tools: &[ToolDefinition]) -> Value {
+        Value::Array(
+            tools
+                .iter()
+                .map(|t| {
+                    json!({
+                        "name": t.name,
+                        "description": summarize(&t.description),
+                        "group": t.group.as_str(),
+                    })
+                })
+                .collect(),
+        )
+    }
+}
+
+impl Default for ListCapabilitiesTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// First sentence (or line) of a tool description, capped to `SUMMARY_MAX_CHARS`
+fn summarize(description: &str) -> String {
+    let first_line = description.lines().next().unwrap_or("").trim();
+    let first_sentence = match first_line.find(". ") {
+        Some(idx) => &first_line[..=idx],
+        None => first_line,
+    };
+    if first_sentence.chars().count() > SUMMARY_MAX_CHARS {
+        let truncated: String = first_sentence.chars().take(SUMMARY_MAX_CHARS).collect();
+        format!("{}…", truncated.trim_end())
+    } else {
+        first_sentence.to_string()
+    }
+}
+
+#[async_trait]
+impl Tool for ListCapabilitiesTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, _params: Value, context: &ToolContext) -> ToolResult {
+        let tools = match context
+            .extra
+            .get(AVAILABLE_TOOLS_EXTRA_KEY)
+            .and_then(|v| v.as_array())
+        {
+            Some(t) => t,
+            None => return ToolResult::error("Capability list is not available in this context"),
+        };
+
+        let mut entries: Vec<(String, String, String)> = tools
+            .iter()
+            .filter_map(|t| {
+                let name = t.get("name")?.as_str()?.to_string();
+                let description = t.get("description").and_then(|v| v.as_str()).unwrap_or("").to_string();
+                let group = t.get("group").and_then(|v| v.as_str()).unwrap_or("").to_string();
+                Some((group, name, description))
+            })
+            .collect();
+        entries.sort();
+
+        let mut output = format!("You currently have {} tools available:\n", entries.len());
+        let mut current_group = "";
+        for (group, name, description) in &entries {
+            if group != current_group {
+                let label = ToolGroup::from_str(group).map(|g| g.label()).unwrap_or("Other Tools");
+                output.push_str(&format!("\n{}:\n", label));
+                current_group = group;
+            }
+            output.push_str(&format!("- {}: {}\n", name, description));
+        }
+
+        ToolResult::success(output).with_metadata(json!({
+            "count": entries.len(),
+            "tools": entries.iter().map(|(_, name, _)| name).collect::<Vec<_>>(),
+        }))
+    }
+
+    fn safety_level(&self) -> ToolSafetyLevel {
+        ToolSafetyLevel::SafeMode
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize() {
+        assert_eq!(summarize("Read a file. Supports offsets."), "Read a file.");
+        assert_eq!(summarize("Line one\nLine two"), "Line one");
+        let long = "x".repeat(300);
+        assert_eq!(summarize(&long).chars().count(), SUMMARY_MAX_CHARS + 1);
+    }
+
+    #[tokio::test]
+    async fn test_lists_injected_tools() {
+        let tool = ListCapabilitiesTool::new();
+        let mut context = ToolContext::default();
+        let defs = vec![tool.definition()];
+        context.extra.insert(
+            AVAILABLE_TOOLS_EXTRA_KEY.to_string(),
+            ListCapabilitiesTool::available_tools_value(&defs),
+        );
+
+        let result = tool.execute(json!({}), &context).await;
+        assert!(result.success);
+        assert!(result.content.contains("list_capabilities"));
+        assert!(result.content.contains("System Tools"));
+    }
+
+    #[tokio::test]
+    async fn test_errors_without_injected_tools() {
+        let tool = ListCapabilitiesTool::new();
+        let result = tool.execute(json!({}), &ToolContext::default()).await;
+        assert!(!result.success);
+    }
+}
@@ -0,0 +1,123 @@
+//! List scheduled tool - one view of pending reminders, scheduled messages, and cron jobs
+//!
+//! Items from every scheduler share the `<kind>:<id>` ids of `scheduler::items`,
+//! so anything listed here can be cancelled with the same tool.
+
+use crate::scheduler::items::{self, ScheduledItem};
+use crate::tools::registry::Tool;
+use crate::tools::types::{
+    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Tool for listing and cancelling scheduled items on the current channel
+pub struct ListScheduledTool {
+    definition: ToolDefinition,
+}
+
+impl ListScheduledTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+
+        properties.insert(
+            "action".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "'list' to show pending items, 'cancel' to cancel one by id".to_string(),
+                default: Some(json!("list")),
+                items: None,
+                enum_values: Some(vec!["list".to_string(), "cancel".to_string()]),
+            },
+        );
+        properties.insert(
+            "id".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Item id from the list, e.g. 'reminder:12', 'job:5', or 'cron:3' (required for cancel)".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        ListScheduledTool {
+            definition: ToolDefinition {
+                name: "list_scheduled".to_string(),
+                description: "List everything scheduled on this channel — the user's pending reminders and \
+                    scheduled messages, and the channel's active cron jobs — with due times, or cancel one by its id. \
+                    Cancelling a cron job pauses it."
+                    .to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec![],
+                },
+                group: ToolGroup::Messaging,
+                hidden: false,
+            },
+        }
+    }
+}
+
+impl Default for ListScheduledTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ListScheduledParams {
+    action: Option<String>,
+    id: Option<String>,
+}
+
+#[async_trait]
+impl Tool for ListScheduledTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: ListScheduledParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+        let Some(db) = context.database.as_ref() else {
+            return ToolResult::error("Database not available");
+        };
+        let Some(channel_id) = context.channel_id else {
+            return ToolResult::error("No channel in context");
+        };
+        let user_id = context.user_id.as_deref().filter(|u| !u.is_empty());
+
+        match params.action.as_deref().unwrap_or("list") {
+            "list" => {
+                let pending: Vec<ScheduledItem> = match items::list_pending(db, channel_id, user_id) {
+                    Ok(i) => i,
+                    Err(e) => return ToolResult::error(e),
+                };
+                if pending.is_empty() {
+                    return ToolResult::success("Nothing scheduled on this channel.");
+                }
+                let mut output = format!("{} scheduled item(s):\n", pending.len());
+                for item in &pending {
+                    output.push_str(&format!("- {}\n", item.summary()));
+                }
+                ToolResult::success(output).with_metadata(json!({ "items": pending }))
+            }
+            "cancel" => {
+                let Some(id) = params.id.as_deref().filter(|i| !i.trim().is_empty()) else {
+                    return ToolResult::error("'id' is required for cancel (e.g. reminder:12)");
+                };
+                match items::cancel(db, id, channel_id, user_id) {
+                    Ok(message) => ToolResult::success(message),
+                    Err(e) => ToolResult::error(e),
+                }
+            }
+            other => ToolResult::error(format!("Unknown action '{}'. Use 'list' or 'cancel'.", other)),
+        }
+    }
+}
@@ -0,0 +1,385 @@
+use crate::controllers::api_keys::ApiKeyId;
+use crate::models::key_last_four;
+use crate::tools::builtin::social_media::{verify_credentials, TwitterAuth, TwitterCredentials};
+use crate::tools::registry::Tool;
+use crate::tools::types::{
+    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Tool for operators to set, rotate and delete built-in API keys from chat.
+/// Only runs for senders the channel verified as admins and never in safe mode.
+/// Never returns key values (only whether a key is set and its last four characters),
+/// and records every change in the API key audit log.
+pub struct ManageApiKeyTool {
+    definition: ToolDefinition,
+}
+
+impl ManageApiKeyTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+
+        properties.insert(
+            "action".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "'status' lists built-in keys, 'set' stores a key, 'rotate' replaces an existing key, 'delete' removes one.".to_string(),
+                default: Some(json!("status")),
+                items: None,
+                enum_values: Some(vec![
+                    "status".to_string(),
+                    "set".to_string(),
+                    "rotate".to_string(),
+                    "delete".to_string(),
+                ]),
+            },
+        );
+
+        properties.insert(
+            "key_name".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: format!(
+                    "Built-in key to change (required for set/rotate/delete). One of: {}",
+                    ApiKeyId::all_names().join(", ")
+                ),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "api_key".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "The new key value (required for set/rotate).".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        ManageApiKeyTool {
+            definition: ToolDefinition {
+                name: "manage_api_key".to_string(),
+                description: "Admin only: view, set, rotate or delete built-in API keys (GitHub, Twitter, Alchemy, ...). \
+                    Values are never shown, only whether a key is set and its last four characters. \
+                    Twitter credentials are validated before they're stored. For custom keys use install_api_key."
+                    .to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec![],
+                },
+                group: ToolGroup::System,
+                hidden: false,
+            },
+        }
+    }
+}
+
+impl Default for ManageApiKeyTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ManageApiKeyParams {
+    action: Option<String>,
+    key_name: Option<String>,
+    api_key: Option<String>,
+}
+
+fn is_twitter_key(key_id: ApiKeyId) -> bool {
+    matches!(
+        key_id,
+        ApiKeyId::TwitterConsumerKey
+            | ApiKeyId::TwitterConsumerSecret
+            | ApiKeyId::TwitterAccessToken
+            | ApiKeyId::TwitterAccessTokenSecret
+            | ApiKeyId::TwitterOauth2AccessToken
+    )
+}
+
+/// Who is changing the key, for the audit log
+fn actor(context: &ToolContext) -> String {
+    format!(
+        "{}:{}",
+        context.channel_type.as_deref().unwrap_or("unknown"),
+        context.user_id.as_deref().unwrap_or("unknown")
+    )
+}
+
+/// Check a Twitter credential before storing it: an OAuth 2.0 token on its own, an
+/// OAuth 1.0a key together with the other three. Returns Ok(Some(username)) when
+/// validated, Ok(None) when the 1.0a set is still incomplete.
+async fn validate_twitter_key(
+    context: &ToolContext,
+    key_id: ApiKeyId,
+    value: &str,
+) -> Result<Option<String>, String> {
+    if key_id == ApiKeyId::TwitterOauth2AccessToken {
+        let auth = TwitterAuth::Bearer(value.to_string());
+        return verify_credentials(&context.http_client(), &auth).await.map(Some);
+    }
+
+    let current = |id: ApiKeyId| {
+        if id == key_id {
+            Some(value.to_string())
+        } else {
+            context.get_api_key_by_id(id)
+        }
+    };
+    let (Some(consumer_key), Some(consumer_secret), Some(access_token), Some(access_token_secret)) = (
+        current(ApiKeyId::TwitterConsumerKey),
+        current(ApiKeyId::TwitterConsumerSecret),
+        current(ApiKeyId::TwitterAccessToken),
+        current(ApiKeyId::TwitterAccessTokenSecret),
+    ) else {
+        return Ok(None);
+    };
+
+    let credentials = TwitterCredentials::new(consumer_key, consumer_secret, access_token, access_token_secret);
+    verify_credentials(&context.http_client(), &TwitterAuth::OAuth1(credentials)).await.map(Some)
+}
+
+#[async_trait]
+impl Tool for ManageApiKeyTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        // Block in safe mode (untrusted users never get to touch credentials)
+        if context
+            .extra
+            .get("safe_mode")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+        {
+            return ToolResult::error("manage_api_key is not available in safe mode");
+        }
+        // Only senders the channel verified as admins (see NormalizedMessage::from_admin)
+        if !context.extra.get("admin").and_then(|v| v.as_bool()).unwrap_or(false) {
+            return ToolResult::error("manage_api_key is only available to admins");
+        }
+
+        let params: ManageApiKeyParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+        let Some(db) = context.database.as_ref() else {
+            return ToolResult::error("Database not available");
+        };
+        let action = params.action.as_deref().unwrap_or("status");
+
+        if action == "status" {
+            let keys: Vec<Value> = ApiKeyId::iter()
+                .map(|id| {
+                    let stored = db.get_api_key(id.as_str()).ok().flatten();
+                    json!({
+                        "key_name": id.as_str(),
+                        "configured": stored.is_some(),
+                        "last_four": stored.as_ref().map(|k| key_last_four(&k.api_key)),
+                        "updated_at": stored.map(|k| k.updated_at.to_rfc3339()),
+                    })
+                })
+                .collect();
+            return ToolResult::success(json!({ "keys": keys }).to_string());
+        }
+
+        let Some(key_name) = params.key_name.as_deref().map(|k| k.trim().to_ascii_uppercase()) else {
+            return ToolResult::error("key_name is required for set, rotate and delete");
+        };
+        let Ok(key_id) = ApiKeyId::from_str(&key_name) else {
+            return ToolResult::error(format!(
+                "'{}' is not a built-in API key. Built-in keys: {}. Use install_api_key for custom keys.",
+                key_name,
+                ApiKeyId::all_names().join(", ")
+            ));
+        };
+        let existing = match db.get_api_key(key_id.as_str()) {
+            Ok(k) => k,
+            Err(e) => return ToolResult::error(format!("Failed to read API key: {}", e)),
+        };
+
+        match action {
+            "set" | "rotate" => {
+                let Some(value) = params.api_key.as_deref().map(str::trim).filter(|v| !v.is_empty()) else {
+                    return ToolResult::error("api_key is required for set and rotate");
+                };
+                if action == "rotate" {
+                    match &existing {
+                        None => {
+                            return ToolResult::error(format!("{} is not set; use action 'set' instead", key_id.as_str()))
+                        }
+                        Some(k) if k.api_key == value => {
+                            return ToolResult::error("The new value is the same as the current key")
+                        }
+                        Some(_) => {}
+                    }
+                }
+
+                let mut validated_as = None;
+                if is_twitter_key(key_id) {
+                    match validate_twitter_key(context, key_id, value).await {
+                        Ok(username) => validated_as = username,
+                        Err(e) => {
+                            return ToolResult::error(format!(
+                                "{} was not stored: validation failed: {}",
+                                key_id.as_str(),
+                                e
+                            ))
+                        }
+                    }
+                }
+
+                if let Err(e) = db.upsert_api_key(key_id.as_str(), value) {
+                    return ToolResult::error(format!("Failed to store API key: {}", e));
+                }
+                let hint = key_last_four(value);
+                if let Err(e) = db.log_api_key_change(key_id.as_str(), action, &actor(context), Some(&hint)) {
+                    log::error!("[API_KEYS] Failed to audit {} of {}: {}", action, key_id.as_str(), e);
+                }
+                log::info!("[API_KEYS] {} {} by {}", action, key_id.as_str(), actor(context));
+
+                // Make the new value available to the rest of this session
+                context.install_api_key_runtime(key_id.as_str(), value.to_string());
+
+                let mut message = format!("{} {} ({}).", key_id.as_str(), if action == "rotate" { "rotated" } else { "set" }, hint);
+                if let Some(username) = &validated_as {
+                    message.push_str(&format!(" Twitter credentials verified for @{}.", username));
+                } else if is_twitter_key(key_id) {
+                    message.push_str(" Twitter credentials will be validated once all four are set.");
+                }
+                ToolResult::success(
+                    json!({
+                        "key_name": key_id.as_str(),
+                        "action": action,
+                        "last_four": hint,
+                        "validated": validated_as.is_some(),
+                        "message": message,
+                    })
+                    .to_string(),
+                )
+            }
+            "delete" => {
+                match db.delete_api_key(key_id.as_str()) {
+                    Ok(true) => {}
+                    Ok(false) => return ToolResult::error(format!("{} is not set", key_id.as_str())),
+                    Err(e) => return ToolResult::error(format!("Failed to delete API key: {}", e)),
+                }
+                if let Err(e) = db.log_api_key_change(key_id.as_str(), "delete", &actor(context), None) {
+                    log::error!("[API_KEYS] Failed to audit delete of {}: {}", key_id.as_str(), e);
+                }
+                log::info!("[API_KEYS] delete {} by {}", key_id.as_str(), actor(context));
+
+                // An empty runtime value reads as unset for the rest of this session
+                context.install_api_key_runtime(key_id.as_str(), String::new());
+
+                ToolResult::success(
+                    json!({
+                        "key_name": key_id.as_str(),
+                        "action": "delete",
+                        "message": format!("{} deleted.", key_id.as_str()),
+                    })
+                    .to_string(),
+                )
+            }
+            other => ToolResult::error(format!(
+                "Unknown action '{}'. Use status, set, rotate or delete.",
+                other
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use std::sync::Arc;
+
+    fn context_with_db() -> ToolContext {
+        let mut context = ToolContext::new();
+        context.database = Some(Arc::new(Database::new(":memory:").unwrap()));
+        context.extra.insert("admin".to_string(), json!(true));
+        context
+    }
+
+    #[tokio::test]
+    async fn test_safe_mode_blocked() {
+        let tool = ManageApiKeyTool::new();
+        let mut context = context_with_db();
+        context.extra.insert("safe_mode".to_string(), json!(true));
+
+        let result = tool.execute(json!({"action": "status"}), &context).await;
+        assert!(!result.success);
+        assert!(result.content.contains("safe mode"));
+    }
+
+    #[tokio::test]
+    async fn test_non_admins_are_refused() {
+        let tool = ManageApiKeyTool::new();
+        let mut context = context_with_db();
+        context.extra.remove("admin");
+
+        let result = tool.execute(json!({"action": "status"}), &context).await;
+        assert!(!result.success);
+        assert!(result.content.contains("only available to admins"));
+    }
+
+    #[tokio::test]
+    async fn test_set_rotate_delete_are_audited_without_values() {
+        let tool = ManageApiKeyTool::new();
+        let context = context_with_db();
+        let db = context.database.clone().unwrap();
+
+        let result = tool
+            .execute(json!({"action": "set", "key_name": "alchemy_api_key", "api_key": "alchemy-secret-1111"}), &context)
+            .await;
+        assert!(result.success, "{}", result.content);
+        assert!(result.content.contains("****1111"));
+        assert!(!result.content.contains("alchemy-secret"));
+
+        // Rotating to the same value is refused
+        let result = tool
+            .execute(json!({"action": "rotate", "key_name": "ALCHEMY_API_KEY", "api_key": "alchemy-secret-1111"}), &context)
+            .await;
+        assert!(!result.success);
+
+        let result = tool
+            .execute(json!({"action": "rotate", "key_name": "ALCHEMY_API_KEY", "api_key": "alchemy-secret-2222"}), &context)
+            .await;
+        assert!(result.success, "{}", result.content);
+        assert_eq!(db.get_api_key("ALCHEMY_API_KEY").unwrap().unwrap().api_key, "alchemy-secret-2222");
+
+        let result = tool
+            .execute(json!({"action": "delete", "key_name": "ALCHEMY_API_KEY"}), &context)
+            .await;
+        assert!(result.success);
+        assert!(db.get_api_key("ALCHEMY_API_KEY").unwrap().is_none());
+
+        let changes = db.list_api_key_changes(10).unwrap();
+        let actions: Vec<&str> = changes.iter().map(|c| c.action.as_str()).collect();
+        assert_eq!(actions, vec!["delete", "rotate", "set"]);
+        assert_eq!(changes[1].key_hint.as_deref(), Some("****2222"));
+    }
+
+    #[tokio::test]
+    async fn test_custom_keys_are_rejected() {
+        let tool = ManageApiKeyTool::new();
+        let context = context_with_db();
+        let result = tool
+            .execute(json!({"action": "set", "key_name": "MY_CUSTOM_KEY", "api_key": "x"}), &context)
+            .await;
+        assert!(!result.success);
+        assert!(result.content.contains("install_api_key"));
+    }
+}
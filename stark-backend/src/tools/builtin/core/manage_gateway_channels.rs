@@ -20,7 +20,7 @@ impl ManageGatewayChannelsTool {
             "action".to_string(),
             PropertySchema {
                 schema_type: "string".to_string(),
-                description: "Action to perform: 'list' (show all channels), 'get' (show one channel), 'create' (add new), 'update' (edit existing), 'delete' (remove channel)".to_string(),
+                description: "Action to perform: 'list' (show all channels), 'get' (show one channel), 'create' (add new), 'update' (edit existing), 'delete' (remove channel; restorable), 'restore' (undo a delete)".to_string(),
                 default: None,
                 items: None,
                 enum_values: Some(vec![
@@ -29,6 +29,7 @@ impl ManageGatewayChannelsTool {
                     "create".to_string(),
                     "update".to_string(),
                     "delete".to_string(),
+                    "restore".to_string(),
                 ]),
             },
         );
@@ -37,7 +38,7 @@ impl ManageGatewayChannelsTool {
             "channel_id".to_string(),
             PropertySchema {
                 schema_type: "integer".to_string(),
-                description: "Channel ID (required for get/update/delete). Use 'list' to find IDs.".to_string(),
+                description: "Channel ID (required for get/update/delete/restore). Use 'list' to find IDs.".to_string(),
                 default: None,
                 items: None,
                 enum_values: None,
@@ -57,6 +58,7 @@ impl ManageGatewayChannelsTool {
                     "discord".to_string(),
                     "twitter".to_string(),
                     "external_channel".to_string(),
+                    "webhook".to_string(),
                 ]),
             },
         );
@@ -108,7 +110,7 @@ impl ManageGatewayChannelsTool {
         ManageGatewayChannelsTool {
             definition: ToolDefinition {
                 name: "manage_gateway_channels".to_string(),
-                description: "Manage messaging gateway channels: list, view, create, update, or delete channels (Telegram, Slack, Discord, Twitter, External).".to_string(),
+                description: "Manage messaging gateway channels: list, view, create, update, delete, or restore channels (Telegram, Slack, Discord, Twitter, External).".to_string(),
                 input_schema: ToolInputSchema {
                     schema_type: "object".to_string(),
                     properties,
@@ -165,6 +167,7 @@ const VALID_CHANNEL_TYPES: &[&str] = &[
     "discord",
     "twitter",
     "external_channel",
+    "webhook",
 ];
 
 #[async_trait]
@@ -245,8 +248,8 @@ impl Tool for ManageGatewayChannelsTool {
                 let name = params.name.as_deref().unwrap_or(&channel_type);
                 let bot_token = params.bot_token.as_deref().unwrap_or("");
 
-                // External channels default to safe_mode
-                let safe_mode = channel_type == "external_channel";
+                // External and webhook channels default to safe_mode
+                let safe_mode = channel_type == "external_channel" || channel_type == "webhook";
 
                 match db.create_channel_with_safe_mode(
                     &channel_type,
@@ -308,15 +311,42 @@ impl Tool for ManageGatewayChannelsTool {
                 };
 
                 match db.delete_channel(id) {
-                    Ok(true) => ToolResult::success(format!("Channel #{} deleted.", id))
-                        .with_metadata(json!({ "deleted_id": id })),
+                    Ok(true) => ToolResult::success(format!(
+                        "Channel #{} deleted. Use action 'restore' to bring it back.",
+                        id
+                    ))
+                    .with_metadata(json!({ "deleted_id": id })),
                     Ok(false) => ToolResult::error(format!("Channel #{} not found", id)),
                     Err(e) => ToolResult::error(format!("Failed to delete channel: {}", e)),
                 }
             }
 
+            "restore" => {
+                let id = match params.channel_id {
+                    Some(id) => id,
+                    None => {
+                        return ToolResult::error(
+                            "'channel_id' is required for 'restore' action.",
+                        )
+                    }
+                };
+
+                match db.restore_channel(id) {
+                    Ok(true) => match db.get_channel(id) {
+                        Ok(Some(ch)) => ToolResult::success(format!(
+                            "Channel restored (disabled until started):\n\n{}",
+                            format_channel(&ch)
+                        ))
+                        .with_metadata(channel_to_json(&ch)),
+                        _ => ToolResult::success(format!("Channel #{} restored.", id)),
+                    },
+                    Ok(false) => ToolResult::error(format!("No deleted channel #{}", id)),
+                    Err(e) => ToolResult::error(format!("Failed to restore channel: {}", e)),
+                }
+            }
+
             _ => ToolResult::error(format!(
-                "Unknown action: '{}'. Valid actions: list, get, create, update, delete",
+                "Unknown action: '{}'. Valid actions: list, get, create, update, delete, restore",
                 params.action
             )),
         }
@@ -7,23 +7,32 @@ mod define_tasks;
 mod agent_send;
 mod api_keys_check;
 mod ask_user;
+mod broadcast_announcement;
 mod heartbeat_config;
 mod import_identity;
 mod install_api_key;
+mod list_capabilities;
+mod list_scheduled;
+mod manage_api_key;
 mod manage_modules;
 mod manage_skills;
 mod mindmap_manage;
 mod read_skill;
+mod reminders;
 mod register_new_identity;
 mod modify_kanban;
 mod modify_soul;
 mod say_to_user;
+mod schedule_message;
 mod set_agent_subtype;
 mod subagent;
+mod summarize;
 mod task_complete;
+mod validation_check;
 
 // Meta tools (self-management)
 mod cloud_backup;
+mod config_snapshot;
 mod manage_gateway_channels;
 mod read_operating_mode;
 mod read_recent_transactions;
@@ -34,23 +43,32 @@ pub use define_tasks::DefineTasksTool;
 pub use agent_send::AgentSendTool;
 pub use api_keys_check::ApiKeysCheckTool;
 pub use ask_user::AskUserTool;
+pub use broadcast_announcement::BroadcastAnnouncementTool;
 pub use heartbeat_config::HeartbeatConfigTool;
 pub use import_identity::ImportIdentityTool;
 pub use install_api_key::InstallApiKeyTool;
+pub use list_capabilities::{ListCapabilitiesTool, AVAILABLE_TOOLS_EXTRA_KEY};
+pub use list_scheduled::ListScheduledTool;
+pub use manage_api_key::ManageApiKeyTool;
 pub use manage_modules::ManageModulesTool;
 pub use manage_skills::ManageSkillsTool;
 pub use mindmap_manage::MindmapManageTool;
 pub use read_skill::ReadSkillTool;
+pub use reminders::{CancelReminderTool, ListRemindersTool, SetReminderTool};
 pub use register_new_identity::RegisterNewIdentityTool;
 pub use modify_kanban::WorkstreamTool;
 pub use modify_soul::ModifySoulTool;
 pub use say_to_user::SayToUserTool;
+pub use schedule_message::ScheduleMessageTool;
 pub use set_agent_subtype::SetAgentSubtypeTool;
 pub use subagent::{SubagentStatusTool, SubagentTool};
+pub use summarize::SummarizeTool;
 pub use task_complete::TaskFullyCompletedTool;
+pub use validation_check::ValidationCheckTool;
 
 // Meta tools (self-management)
 pub use cloud_backup::CloudBackupTool;
+pub use config_snapshot::ConfigSnapshotTool;
 pub use manage_gateway_channels::ManageGatewayChannelsTool;
 pub use read_operating_mode::ReadOperatingModeTool;
 pub use read_recent_transactions::ReadRecentTransactionsTool;
@@ -128,6 +128,14 @@ impl Tool for RegisterNewIdentityTool {
             reg.image = Some(img.clone());
         }
 
+        // Refuse to write a file that discovery tools won't be able to parse once registered
+        if let Err(errors) = reg.validate() {
+            return ToolResult::error(format!(
+                "Identity does not conform to the EIP-8004 registration schema:\n- {}",
+                errors.join("\n- ")
+            ));
+        }
+
         // Write to IDENTITY.json on disk (for upload to defirelay)
         let identity_path = crate::config::identity_document_path();
         let json_content = match serde_json::to_string_pretty(&reg) {
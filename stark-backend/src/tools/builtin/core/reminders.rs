@@ -0,0 +1,381 @@
+use crate::channels::preferences;
+use crate::db::tables::reminders::{CreateReminderRequest, Reminder};
+use crate::db::Database;
+use crate::models::ChannelType;
+use crate::scheduler::reminders::parse_reminder_time;
+use crate::tools::registry::Tool;
+use crate::tools::types::{
+    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Longest reminder text accepted
+const MAX_REMINDER_TEXT_CHARS: usize = 1000;
+
+/// Maximum pending reminders per user per channel
+const MAX_PENDING_PER_USER: usize = 25;
+
+/// Who set the reminder and where: (database, channel_id, channel_type, user_id).
+/// Reminders are only available on platforms the scheduler can deliver to.
+fn reminder_scope(context: &ToolContext) -> Result<(&Arc<Database>, i64, ChannelType, &str), String> {
+    let channel_type = context
+        .channel_type
+        .as_deref()
+        .and_then(ChannelType::from_str)
+        .filter(|ct| matches!(ct, ChannelType::Discord | ChannelType::Telegram | ChannelType::Slack))
+        .ok_or_else(|| "Reminders are only available on Discord, Telegram, and Slack channels".to_string())?;
+    let channel_id = context
+        .channel_id
+        .ok_or_else(|| "No channel in context".to_string())?;
+    let user_id = context
+        .user_id
+        .as_deref()
+        .filter(|u| !u.is_empty())
+        .ok_or_else(|| "No user in context".to_string())?;
+    let db = context
+        .database
+        .as_ref()
+        .ok_or_else(|| "Database not available".to_string())?;
+    Ok((db, channel_id, channel_type, user_id))
+}
+
+fn format_reminder(r: &Reminder) -> String {
+    format!(
+        "#{} — {} UTC ({}): {}",
+        r.id,
+        r.due_at.format("%Y-%m-%d %H:%M"),
+        r.delivery,
+        r.text
+    )
+}
+
+fn string_property(description: &str) -> PropertySchema {
+    PropertySchema {
+        schema_type: "string".to_string(),
+        description: description.to_string(),
+        default: None,
+        items: None,
+        enum_values: None,
+    }
+}
+
+/// Tool for scheduling a reminder that is delivered to the user later
+pub struct SetReminderTool {
+    definition: ToolDefinition,
+}
+
+impl SetReminderTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+
+        properties.insert(
+            "text".to_string(),
+            string_property("What to remind the user about"),
+        );
+        properties.insert(
+            "when".to_string(),
+            string_property(
+                "When to send it: relative ('in 2h', '30 minutes', '1 day 3 hours') or absolute \
+                 ('2026-03-01 09:00', or RFC 3339 like '2026-03-01T09:00:00+02:00')",
+            ),
+        );
+        properties.insert(
+            "timezone".to_string(),
+            string_property(
+                "Timezone for absolute times without an offset, as UTC or an offset like '+02:00' or '-05:00'. \
+                 Defaults to the user's timezone preference, or UTC.",
+            ),
+        );
+        properties.insert(
+            "delivery".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "'dm' to direct-message the user (falls back to this chat if DMs fail), or 'channel' to post in this chat".to_string(),
+                default: Some(json!("dm")),
+                items: None,
+                enum_values: Some(vec!["dm".to_string(), "channel".to_string()]),
+            },
+        );
+
+        SetReminderTool {
+            definition: ToolDefinition {
+                name: "set_reminder".to_string(),
+                description: "Schedule a reminder for the current user. It is delivered at the given time even if the bot restarts in between.".to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec!["text".to_string(), "when".to_string()],
+                },
+                group: ToolGroup::Messaging,
+                hidden: false,
+            },
+        }
+    }
+}
+
+impl Default for SetReminderTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SetReminderParams {
+    text: String,
+    when: String,
+    #[serde(default)]
+    timezone: Option<String>,
+    #[serde(default)]
+    delivery: Option<String>,
+}
+
+#[async_trait]
+impl Tool for SetReminderTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: SetReminderParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        let text = params.text.trim();
+        if text.is_empty() {
+            return ToolResult::error("Reminder text is empty");
+        }
+        if text.chars().count() > MAX_REMINDER_TEXT_CHARS {
+            return ToolResult::error(format!(
+                "Reminder text is too long (max {} characters)",
+                MAX_REMINDER_TEXT_CHARS
+            ));
+        }
+        let delivery = params.delivery.as_deref().unwrap_or("dm");
+        if delivery != "dm" && delivery != "channel" {
+            return ToolResult::error("delivery must be 'dm' or 'channel'");
+        }
+        // Fall back to the user's timezone preference (`pref set timezone ...`)
+        let timezone = params.timezone.clone().or_else(|| {
+            let db = context.database.as_ref()?;
+            preferences::timezone_for(db, context.identity_id.as_deref()?)
+        });
+        let due_at = match parse_reminder_time(&params.when, timezone.as_deref(), Utc::now()) {
+            Ok(t) => t,
+            Err(e) => return ToolResult::error(e),
+        };
+
+        let (db, channel_id, channel_type, user_id) = match reminder_scope(context) {
+            Ok(scope) => scope,
+            Err(e) => return ToolResult::error(e),
+        };
+        if delivery == "channel" && context.platform_chat_id.is_none() {
+            return ToolResult::error("No chat in context to post the reminder in; use delivery 'dm'");
+        }
+
+        match db.list_pending_reminders_for_user(channel_id, user_id) {
+            Ok(pending) if pending.len() >= MAX_PENDING_PER_USER => {
+                return ToolResult::error(format!(
+                    "You already have {} pending reminders; cancel some first",
+                    pending.len()
+                ));
+            }
+            Ok(_) => {}
+            Err(e) => return ToolResult::error(format!("Failed to check reminders: {}", e)),
+        }
+
+        let request = CreateReminderRequest {
+            channel_id,
+            channel_type: channel_type.as_str().to_string(),
+            chat_id: context.platform_chat_id.clone(),
+            user_id: user_id.to_string(),
+            text: text.to_string(),
+            due_at,
+            delivery: delivery.to_string(),
+        };
+        match db.create_reminder(&request) {
+            Ok(reminder) => ToolResult::success(format!(
+                "Reminder #{} set for {} UTC ({}).",
+                reminder.id,
+                reminder.due_at.format("%Y-%m-%d %H:%M"),
+                if delivery == "dm" { "via DM" } else { "in this chat" }
+            ))
+            .with_metadata(json!({ "reminder": reminder })),
+            Err(e) => ToolResult::error(format!("Failed to save reminder: {}", e)),
+        }
+    }
+}
+
+/// Tool for listing the current user's pending reminders
+pub struct ListRemindersTool {
+    definition: ToolDefinition,
+}
+
+impl ListRemindersTool {
+    pub fn new() -> Self {
+        ListRemindersTool {
+            definition: ToolDefinition {
+                name: "list_reminders".to_string(),
+                description: "List the current user's pending reminders on this channel.".to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties: HashMap::new(),
+                    required: vec![],
+                },
+                group: ToolGroup::Messaging,
+                hidden: false,
+            },
+        }
+    }
+}
+
+impl Default for ListRemindersTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Tool for ListRemindersTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, _params: Value, context: &ToolContext) -> ToolResult {
+        let (db, channel_id, _, user_id) = match reminder_scope(context) {
+            Ok(scope) => scope,
+            Err(e) => return ToolResult::error(e),
+        };
+
+        let reminders = match db.list_pending_reminders_for_user(channel_id, user_id) {
+            Ok(r) => r,
+            Err(e) => return ToolResult::error(format!("Failed to list reminders: {}", e)),
+        };
+        if reminders.is_empty() {
+            return ToolResult::success("No pending reminders.");
+        }
+
+        let mut output = format!("{} pending reminder(s):\n", reminders.len());
+        for r in &reminders {
+            output.push_str(&format!("- {}\n", format_reminder(r)));
+        }
+        ToolResult::success(output).with_metadata(json!({ "reminders": reminders }))
+    }
+}
+
+/// Tool for cancelling one of the current user's pending reminders
+pub struct CancelReminderTool {
+    definition: ToolDefinition,
+}
+
+impl CancelReminderTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+        properties.insert(
+            "id".to_string(),
+            PropertySchema {
+                schema_type: "integer".to_string(),
+                description: "The reminder ID (from list_reminders or set_reminder)".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        CancelReminderTool {
+            definition: ToolDefinition {
+                name: "cancel_reminder".to_string(),
+                description: "Cancel one of the current user's pending reminders by ID.".to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec!["id".to_string()],
+                },
+                group: ToolGroup::Messaging,
+                hidden: false,
+            },
+        }
+    }
+}
+
+impl Default for CancelReminderTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CancelReminderParams {
+    id: i64,
+}
+
+#[async_trait]
+impl Tool for CancelReminderTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: CancelReminderParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+        let (db, channel_id, _, user_id) = match reminder_scope(context) {
+            Ok(scope) => scope,
+            Err(e) => return ToolResult::error(e),
+        };
+
+        match db.cancel_reminder(params.id, channel_id, user_id) {
+            Ok(true) => ToolResult::success(format!("Reminder #{} cancelled.", params.id)),
+            Ok(false) => ToolResult::error(format!(
+                "No pending reminder #{} found for you on this channel",
+                params.id
+            )),
+            Err(e) => ToolResult::error(format!("Failed to cancel reminder: {}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_set_reminder_rejects_bad_input() {
+        let tool = SetReminderTool::new();
+        let ctx = ToolContext::default();
+
+        let result = tool.execute(json!({ "text": " ", "when": "in 1h" }), &ctx).await;
+        assert!(!result.success);
+
+        let result = tool.execute(json!({ "text": "stretch", "when": "someday" }), &ctx).await;
+        assert!(!result.success);
+
+        let result = tool
+            .execute(json!({ "text": "stretch", "when": "in 1h", "delivery": "email" }), &ctx)
+            .await;
+        assert!(!result.success);
+    }
+
+    #[tokio::test]
+    async fn test_requires_supported_channel() {
+        let mut ctx = ToolContext::default();
+        ctx.channel_type = Some("twitter".to_string());
+        ctx.channel_id = Some(1);
+        ctx.user_id = Some("42".to_string());
+
+        let result = SetReminderTool::new()
+            .execute(json!({ "text": "stretch", "when": "in 1h" }), &ctx)
+            .await;
+        assert!(!result.success);
+        assert!(result.content.contains("Discord, Telegram, and Slack"));
+
+        let result = ListRemindersTool::new().execute(json!({}), &ctx).await;
+        assert!(!result.success);
+    }
+}
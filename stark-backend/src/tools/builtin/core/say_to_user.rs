@@ -89,7 +89,9 @@ impl Tool for SayToUserTool {
             Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
         };
 
-        let mut result = ToolResult::success(params.message);
+        // Post-process here too, so the live tool.result event never carries raw secrets
+        let message = crate::channels::post_process::global().apply(&params.message);
+        let mut result = ToolResult::success(message);
 
         // Signal to the orchestrator that this completes the task
         if params.finished_task {
@@ -0,0 +1,332 @@
+//! Schedule message tool - post a message (or run a prompt) later, once or on repeat
+//!
+//! Jobs are stored in the `scheduled_jobs` table and run by the scheduler, so they
+//! survive restarts. They show up in `list_scheduled` as `job:<id>`.
+
+use crate::channels::preferences;
+use crate::db::tables::scheduled_jobs::{CreateScheduledJobRequest, ScheduledJob};
+use crate::models::ChannelType;
+use crate::scheduler::items;
+use crate::scheduler::reminders::parse_reminder_time;
+use crate::scheduler::scheduled_jobs::{next_run, parse_recurrence};
+use crate::tools::registry::Tool;
+use crate::tools::types::{
+    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Longest message or prompt accepted
+const MAX_CONTENT_CHARS: usize = 2000;
+
+/// Maximum pending scheduled messages per user per channel
+const MAX_PENDING_PER_USER: usize = 25;
+
+fn string_property(description: &str, enum_values: Option<&[&str]>, default: Option<&str>) -> PropertySchema {
+    PropertySchema {
+        schema_type: "string".to_string(),
+        description: description.to_string(),
+        default: default.map(|d| json!(d)),
+        items: None,
+        enum_values: enum_values.map(|v| v.iter().map(|s| s.to_string()).collect()),
+    }
+}
+
+/// Tool for scheduling a delayed or recurring message in the current chat
+pub struct ScheduleMessageTool {
+    definition: ToolDefinition,
+}
+
+impl ScheduleMessageTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+
+        properties.insert(
+            "action".to_string(),
+            string_property(
+                "'schedule' to create a scheduled message, 'cancel' to cancel one by id",
+                Some(&["schedule", "cancel"]),
+                Some("schedule"),
+            ),
+        );
+        properties.insert(
+            "message".to_string(),
+            string_property(
+                "The text to post, or with mode 'prompt' the instruction to run each time (e.g. 'Summarize today's ETH price action')",
+                None,
+                None,
+            ),
+        );
+        properties.insert(
+            "mode".to_string(),
+            string_property(
+                "'text' posts the message as-is; 'prompt' runs it through the agent and posts the reply",
+                Some(&["text", "prompt"]),
+                Some("text"),
+            ),
+        );
+        properties.insert(
+            "when".to_string(),
+            string_property(
+                "First run: relative ('in 1h', '30 minutes') or absolute ('2026-03-01 09:00', or RFC 3339)",
+                None,
+                None,
+            ),
+        );
+        properties.insert(
+            "repeat".to_string(),
+            string_property(
+                "Optional repeat schedule: 'hourly', 'daily', 'weekly', 'every 6h', or a cron expression \
+                 like '0 9 * * MON-FRI'. Omit for a one-time message.",
+                None,
+                None,
+            ),
+        );
+        properties.insert(
+            "timezone".to_string(),
+            string_property(
+                "Timezone for absolute times and cron expressions, as UTC or an offset like '+02:00'. \
+                 Defaults to the user's timezone preference, or UTC.",
+                None,
+                None,
+            ),
+        );
+        properties.insert(
+            "id".to_string(),
+            string_property("Scheduled message id to cancel, e.g. 'job:5' (required for cancel)", None, None),
+        );
+
+        ScheduleMessageTool {
+            definition: ToolDefinition {
+                name: "schedule_message".to_string(),
+                description: "Schedule a message in this chat for later — once ('remind the channel in an hour') or on \
+                    repeat ('post a daily summary'). With mode 'prompt' the agent runs the prompt each time and posts its \
+                    reply. Scheduled messages survive restarts; cancel them here or with list_scheduled."
+                    .to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec![],
+                },
+                group: ToolGroup::Messaging,
+                hidden: false,
+            },
+        }
+    }
+}
+
+impl Default for ScheduleMessageTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ScheduleMessageParams {
+    #[serde(default)]
+    action: Option<String>,
+    #[serde(default)]
+    message: Option<String>,
+    #[serde(default)]
+    mode: Option<String>,
+    #[serde(default)]
+    when: Option<String>,
+    #[serde(default)]
+    repeat: Option<String>,
+    #[serde(default)]
+    timezone: Option<String>,
+    #[serde(default)]
+    id: Option<String>,
+}
+
+fn format_job(job: &ScheduledJob) -> String {
+    format!(
+        "Scheduled message job:{} — first run {} UTC{}{}.",
+        job.id,
+        job.run_at.format("%Y-%m-%d %H:%M"),
+        job.recurrence
+            .as_deref()
+            .map(|r| format!(", repeats {}", r))
+            .unwrap_or_default(),
+        if job.mode == "prompt" { ", runs as a prompt" } else { "" }
+    )
+}
+
+#[async_trait]
+impl Tool for ScheduleMessageTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: ScheduleMessageParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        let channel_type = match context
+            .channel_type
+            .as_deref()
+            .and_then(ChannelType::from_str)
+            .filter(|ct| matches!(ct, ChannelType::Discord | ChannelType::Telegram | ChannelType::Slack))
+        {
+            Some(ct) => ct,
+            None => {
+                return ToolResult::error("Scheduled messages are only available on Discord, Telegram, and Slack channels")
+            }
+        };
+        let Some(db) = context.database.as_ref() else {
+            return ToolResult::error("Database not available");
+        };
+        let Some(channel_id) = context.channel_id else {
+            return ToolResult::error("No channel in context");
+        };
+        let Some(user_id) = context.user_id.as_deref().filter(|u| !u.is_empty()) else {
+            return ToolResult::error("No user in context");
+        };
+
+        match params.action.as_deref().unwrap_or("schedule") {
+            "schedule" => {}
+            "cancel" => {
+                let Some(id) = params.id.as_deref().map(str::trim).filter(|i| !i.is_empty()) else {
+                    return ToolResult::error("'id' is required for cancel (e.g. job:5)");
+                };
+                // Accept a bare row id as well as the `job:<id>` form
+                let id = if id.contains(':') { id.to_string() } else { format!("job:{}", id) };
+                return match items::cancel(db, &id, channel_id, Some(user_id)) {
+                    Ok(message) => ToolResult::success(message),
+                    Err(e) => ToolResult::error(e),
+                };
+            }
+            other => return ToolResult::error(format!("Unknown action '{}'. Use 'schedule' or 'cancel'.", other)),
+        }
+
+        let content = params.message.as_deref().unwrap_or("").trim();
+        if content.is_empty() {
+            return ToolResult::error("'message' is required");
+        }
+        if content.chars().count() > MAX_CONTENT_CHARS {
+            return ToolResult::error(format!("Message is too long (max {} characters)", MAX_CONTENT_CHARS));
+        }
+        let mode = params.mode.as_deref().unwrap_or("text");
+        if mode != "text" && mode != "prompt" {
+            return ToolResult::error("mode must be 'text' or 'prompt'");
+        }
+        let Some(chat_id) = context.platform_chat_id.clone() else {
+            return ToolResult::error("No chat in context to post the message in");
+        };
+        let Some(when) = params.when.as_deref().filter(|w| !w.trim().is_empty()) else {
+            return ToolResult::error("'when' is required (e.g. 'in 1h' or '2026-03-01 09:00')");
+        };
+
+        // Fall back to the user's timezone preference (`pref set timezone ...`)
+        let timezone = params.timezone.clone().or_else(|| {
+            preferences::timezone_for(db, context.identity_id.as_deref()?)
+        });
+        let run_at = match parse_reminder_time(when, timezone.as_deref(), Utc::now()) {
+            Ok(t) => t,
+            Err(e) => return ToolResult::error(e),
+        };
+        let repeat = params.repeat.as_deref().map(str::trim).filter(|r| !r.is_empty());
+        if let Some(repeat) = repeat {
+            if let Err(e) = parse_recurrence(repeat) {
+                return ToolResult::error(e);
+            }
+        }
+
+        match db.list_pending_scheduled_jobs_for_user(channel_id, user_id) {
+            Ok(pending) if pending.len() >= MAX_PENDING_PER_USER => {
+                return ToolResult::error(format!(
+                    "You already have {} scheduled messages here; cancel some first",
+                    pending.len()
+                ));
+            }
+            Ok(_) => {}
+            Err(e) => return ToolResult::error(format!("Failed to check scheduled messages: {}", e)),
+        }
+
+        let request = CreateScheduledJobRequest {
+            channel_id,
+            channel_type: channel_type.as_str().to_string(),
+            chat_id,
+            user_id: user_id.to_string(),
+            mode: mode.to_string(),
+            content: content.to_string(),
+            run_at,
+            recurrence: repeat.map(String::from),
+            timezone,
+        };
+        match db.create_scheduled_job(&request) {
+            Ok(job) => {
+                let following = next_run(&job, job.run_at);
+                ToolResult::success(format_job(&job))
+                    .with_metadata(json!({ "job": job, "following_run": following }))
+            }
+            Err(e) => ToolResult::error(format!("Failed to save scheduled message: {}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use std::sync::Arc;
+
+    fn discord_context(db: Arc<Database>) -> ToolContext {
+        let mut ctx = ToolContext::default();
+        ctx.channel_type = Some("discord".to_string());
+        ctx.channel_id = Some(7);
+        ctx.user_id = Some("u1".to_string());
+        ctx.platform_chat_id = Some("123".to_string());
+        ctx.database = Some(db);
+        ctx
+    }
+
+    #[tokio::test]
+    async fn test_schedule_and_cancel() {
+        let db = Arc::new(Database::new(":memory:").expect("in-memory db"));
+        let ctx = discord_context(db.clone());
+        let tool = ScheduleMessageTool::new();
+
+        let result = tool
+            .execute(json!({ "message": "Standup in 5!", "when": "in 1h", "repeat": "0 9 * * MON-FRI" }), &ctx)
+            .await;
+        assert!(result.success, "{}", result.content);
+        let pending = db.list_pending_scheduled_jobs_for_user(7, "u1").unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].recurrence.as_deref(), Some("0 9 * * MON-FRI"));
+
+        let result = tool
+            .execute(json!({ "action": "cancel", "id": pending[0].id.to_string() }), &ctx)
+            .await;
+        assert!(result.success, "{}", result.content);
+        assert!(db.list_pending_scheduled_jobs_for_user(7, "u1").unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_bad_input() {
+        let db = Arc::new(Database::new(":memory:").expect("in-memory db"));
+        let ctx = discord_context(db);
+        let tool = ScheduleMessageTool::new();
+
+        let result = tool.execute(json!({ "message": "hi", "when": "someday" }), &ctx).await;
+        assert!(!result.success);
+        let result = tool
+            .execute(json!({ "message": "hi", "when": "in 1h", "repeat": "every 30 seconds" }), &ctx)
+            .await;
+        assert!(!result.success);
+        let result = tool
+            .execute(json!({ "message": "hi", "when": "in 1h", "mode": "email" }), &ctx)
+            .await;
+        assert!(!result.success);
+
+        let mut ctx = ToolContext::default();
+        ctx.channel_type = Some("twitter".to_string());
+        let result = tool.execute(json!({ "message": "hi", "when": "in 1h" }), &ctx).await;
+        assert!(result.content.contains("Discord, Telegram, and Slack"));
+    }
+}
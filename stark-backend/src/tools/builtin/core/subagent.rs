@@ -691,13 +691,23 @@ impl Tool for SubagentStatusTool {
                 } else {
                     // List all subagents for this channel
                     let channel_id = context.channel_id.unwrap_or(0);
+                    let counts = manager.counts(Some(channel_id));
+                    let max = manager.config().max_concurrent_per_channel;
+                    let capacity = format!(
+                        "{}/{} running, {} queued in this channel",
+                        counts.running, max, counts.queued
+                    );
                     match manager.list_by_channel(channel_id) {
                         Ok(agents) => {
                             if agents.is_empty() {
-                                return ToolResult::success("No subagents found.");
+                                return ToolResult::success(format!("No subagents found ({}).", capacity));
                             }
 
-                            let mut result = format!("## Subagents ({} total)\n\n", agents.len());
+                            let mut result = format!(
+                                "## Subagents ({} total; {})\n\n",
+                                agents.len(),
+                                capacity
+                            );
 
                             for status in &agents {
                                 result.push_str(&format!(
@@ -715,6 +725,9 @@ impl Tool for SubagentStatusTool {
 
                             return ToolResult::success(result).with_metadata(json!({
                                 "count": agents.len(),
+                                "running": counts.running,
+                                "queued": counts.queued,
+                                "max_concurrent_per_channel": max,
                                 "subagents": agents.iter().map(|s| json!({
                                     "id": s.id,
                                     "label": s.label,
@@ -0,0 +1,454 @@
+//! Summarize tool
+//!
+//! Condenses a text blob, a workspace file, or a fetched URL with the configured
+//! AI provider. Inputs bigger than one chunk are summarized map-reduce style:
+//! each chunk is summarized on its own, then the partial summaries are combined
+//! (repeating until they fit in one chunk). Every provider call is charged against
+//! a token budget so a huge input can't run up an unbounded bill.
+
+use crate::ai::{AiClient, Message, MessageRole};
+use crate::context::estimate_tokens;
+use crate::tools::builtin::bash::read_sandboxed_file;
+use crate::tools::registry::Tool;
+use crate::tools::types::{
+    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+};
+use crate::tools::ToolSafetyLevel;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Characters per chunk sent to the provider in one call (~6k tokens)
+const CHUNK_CHARS: usize = 24_000;
+const DEFAULT_MAX_WORDS: u32 = 200;
+const MAX_WORDS_LIMIT: u32 = 2_000;
+/// Default cap on estimated tokens (prompt + output) spent on one summary
+const DEFAULT_TOKEN_BUDGET: u32 = 60_000;
+/// Hard cap callers cannot raise the budget beyond
+const MAX_TOKEN_BUDGET: u32 = 250_000;
+/// Characters requested from web_fetch for a URL input
+const MAX_FETCH_CHARS: usize = 500_000;
+
+pub struct SummarizeTool {
+    definition: ToolDefinition,
+}
+
+impl SummarizeTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+
+        properties.insert(
+            "text".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Text to summarize (a pasted document, a conversation transcript, ...).".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "path".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Workspace file to summarize instead of text (same paths as read_file).".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "url".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Web page to fetch and summarize instead of text.".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "max_words".to_string(),
+            PropertySchema {
+                schema_type: "integer".to_string(),
+                description: format!(
+                    "Target length of the summary in words (default: {}, max: {}).",
+                    DEFAULT_MAX_WORDS, MAX_WORDS_LIMIT
+                ),
+                default: Some(json!(DEFAULT_MAX_WORDS)),
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "focus".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Optional aspect to focus on, e.g. \"decisions and action items\".".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "max_total_tokens".to_string(),
+            PropertySchema {
+                schema_type: "integer".to_string(),
+                description: format!(
+                    "Cap on estimated tokens spent across all AI calls (default: {}, max: {}). \
+                     The tool fails instead of exceeding it.",
+                    DEFAULT_TOKEN_BUDGET, MAX_TOKEN_BUDGET
+                ),
+                default: Some(json!(DEFAULT_TOKEN_BUDGET)),
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        SummarizeTool {
+            definition: ToolDefinition {
+                name: "summarize".to_string(),
+                description: "Summarize a long conversation or document. Provide exactly one of \
+                    text, path (workspace file) or url. Handles inputs larger than the context window \
+                    by summarizing chunks and then combining the partial summaries."
+                    .to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec![],
+                },
+                group: ToolGroup::Web,
+                hidden: false,
+            },
+        }
+    }
+}
+
+impl Default for SummarizeTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SummarizeParams {
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    max_words: Option<u32>,
+    #[serde(default)]
+    focus: Option<String>,
+    #[serde(default)]
+    max_total_tokens: Option<u32>,
+}
+
+#[async_trait]
+impl Tool for SummarizeTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: SummarizeParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        let (source, text) = match (&params.text, &params.path, &params.url) {
+            (Some(text), None, None) => ("text".to_string(), text.clone()),
+            (None, Some(path), None) => match read_sandboxed_file(path, context).await {
+                Ok(content) => (format!("file {}", path), content),
+                Err(e) => return ToolResult::error(e),
+            },
+            (None, None, Some(url)) => {
+                let fetched = context
+                    .execute_tool("web_fetch", json!({"url": url, "max_chars": MAX_FETCH_CHARS}))
+                    .await;
+                if !fetched.success {
+                    return ToolResult::error(format!("Failed to fetch {}: {}", url, fetched.content));
+                }
+                (url.clone(), fetched.content)
+            }
+            _ => return ToolResult::error("Provide exactly one of: text, path, url"),
+        };
+
+        if text.trim().is_empty() {
+            return ToolResult::error(format!("Nothing to summarize: {} is empty", source));
+        }
+
+        let max_words = params.max_words.unwrap_or(DEFAULT_MAX_WORDS).clamp(20, MAX_WORDS_LIMIT);
+        let limit = params.max_total_tokens.unwrap_or(DEFAULT_TOKEN_BUDGET).min(MAX_TOKEN_BUDGET);
+        let mut budget = TokenBudget::new(limit);
+
+        let client = match build_client_from_db(context) {
+            Some(c) => c,
+            None => return ToolResult::error("No AI provider configured — cannot summarize."),
+        };
+
+        match summarize(&client, &text, max_words, params.focus.as_deref(), &mut budget).await {
+            Ok(outcome) => ToolResult::success(outcome.summary).with_metadata(json!({
+                "source": source,
+                "input_chars": text.len(),
+                "chunks": outcome.chunks,
+                "ai_calls": outcome.calls,
+                "estimated_tokens": budget.spent,
+                "token_budget": budget.limit,
+            })),
+            Err(e) => ToolResult::error(e),
+        }
+    }
+
+    fn safety_level(&self) -> ToolSafetyLevel {
+        ToolSafetyLevel::ReadOnly
+    }
+}
+
+/// Running total of estimated tokens against a cap
+struct TokenBudget {
+    limit: u32,
+    spent: u32,
+}
+
+impl TokenBudget {
+    fn new(limit: u32) -> Self {
+        Self { limit, spent: 0 }
+    }
+
+    /// Refuse a call whose prompt plus expected output would exceed the cap
+    fn reserve(&self, tokens: u32) -> Result<(), String> {
+        if self.spent.saturating_add(tokens) > self.limit {
+            return Err(format!(
+                "Token budget exceeded: summarizing needs more than {} tokens ({} already spent). \
+                 Raise max_total_tokens (max {}) or summarize a smaller excerpt.",
+                self.limit, self.spent, MAX_TOKEN_BUDGET
+            ));
+        }
+        Ok(())
+    }
+
+    fn charge(&mut self, tokens: u32) {
+        self.spent = self.spent.saturating_add(tokens);
+    }
+}
+
+struct SummaryOutcome {
+    summary: String,
+    chunks: usize,
+    calls: usize,
+}
+
+/// Map-reduce summarization: summarize chunks until everything fits in one final call
+async fn summarize(
+    client: &AiClient,
+    text: &str,
+    max_words: u32,
+    focus: Option<&str>,
+    budget: &mut TokenBudget,
+) -> Result<SummaryOutcome, String> {
+    // Rough check up front so an oversized input fails before any money is spent
+    let input_tokens = estimate_tokens(text).max(0) as u32;
+    budget.reserve(input_tokens)?;
+
+    let mut current = text.to_string();
+    let mut calls = 0;
+    let mut first_chunks = 0;
+    loop {
+        let chunks = chunk_text(&current, CHUNK_CHARS);
+        if first_chunks == 0 {
+            first_chunks = chunks.len();
+        }
+
+        if chunks.len() <= 1 {
+            let instruction = format!(
+                "Summarize the following in at most {} words.{}",
+                max_words,
+                focus_clause(focus)
+            );
+            let summary = complete(client, &instruction, &current, max_words, budget).await?;
+            return Ok(SummaryOutcome { summary, chunks: first_chunks, calls: calls + 1 });
+        }
+
+        // Partial summaries get enough room to keep detail for the reduce step
+        let partial_words = (max_words * 2).clamp(150, 600);
+        let mut partials = Vec::with_capacity(chunks.len());
+        for (i, chunk) in chunks.iter().enumerate() {
+            let instruction = format!(
+                "This is part {} of {} of a longer document. Summarize this part in at most {} words, \
+                 keeping names, numbers and decisions.{}",
+                i + 1,
+                chunks.len(),
+                partial_words,
+                focus_clause(focus)
+            );
+            partials.push(complete(client, &instruction, chunk, partial_words, budget).await?);
+            calls += 1;
+        }
+
+        let combined = partials.join("\n\n");
+        if combined.len() >= current.len() {
+            return Err("Summarization is not converging: partial summaries are as long as the input".to_string());
+        }
+        current = combined;
+    }
+}
+
+fn focus_clause(focus: Option<&str>) -> String {
+    match focus.map(str::trim).filter(|f| !f.is_empty()) {
+        Some(f) => format!(" Focus on: {}.", f),
+        None => String::new(),
+    }
+}
+
+/// One provider call, charged against the budget
+async fn complete(
+    client: &AiClient,
+    instruction: &str,
+    content: &str,
+    max_words: u32,
+    budget: &mut TokenBudget,
+) -> Result<String, String> {
+    let messages = vec![
+        Message {
+            role: MessageRole::System,
+            content: "You write faithful, concise summaries. Only use information from the provided content. \
+                      Reply with the summary only."
+                .to_string(),
+        },
+        Message {
+            role: MessageRole::User,
+            content: format!("{}\n\n---\n{}", instruction, content),
+        },
+    ];
+
+    let prompt_tokens: u32 = messages.iter().map(|m| estimate_tokens(&m.content).max(0) as u32).sum();
+    // ~1.4 tokens per word of output
+    let expected_output = max_words * 7 / 5;
+    budget.reserve(prompt_tokens + expected_output)?;
+
+    let summary = client
+        .generate_text(messages)
+        .await
+        .map_err(|e| format!("AI summarization failed: {}", e))?;
+    budget.charge(prompt_tokens + estimate_tokens(&summary).max(0) as u32);
+    Ok(summary.trim().to_string())
+}
+
+/// Split text into chunks of at most `max_chars`, preferring line boundaries
+fn chunk_text(text: &str, max_chars: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+
+    for line in text.split_inclusive('\n') {
+        if current.len() + line.len() > max_chars && !current.is_empty() {
+            chunks.push(std::mem::take(&mut current));
+        }
+        if line.len() <= max_chars {
+            current.push_str(line);
+            continue;
+        }
+        // A single oversized line: hard-split on char boundaries
+        let mut rest = line;
+        while rest.len() > max_chars {
+            let mut cut = max_chars;
+            while !rest.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            chunks.push(rest[..cut].to_string());
+            rest = &rest[cut..];
+        }
+        current.push_str(rest);
+    }
+    if !current.trim().is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// Build an AiClient from DB settings (same pattern as verify_intent).
+fn build_client_from_db(context: &ToolContext) -> Option<AiClient> {
+    let db = context.database.as_ref()?;
+    let settings = db.get_active_agent_settings().ok()??;
+    AiClient::from_settings(&settings).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::{AiResponse, MockAiClient};
+
+    fn mock_client(responses: Vec<&str>) -> AiClient {
+        AiClient::Mock(MockAiClient::new(
+            responses
+                .into_iter()
+                .map(|text| Ok(AiResponse::text(text.to_string())))
+                .collect(),
+        ))
+    }
+
+    #[test]
+    fn test_chunk_text() {
+        let text = "aaaa\nbbbb\ncccc\n";
+        assert_eq!(chunk_text(text, 10), vec!["aaaa\nbbbb\n", "cccc\n"]);
+        assert_eq!(chunk_text(text, 100), vec![text]);
+
+        // Oversized lines are split without breaking UTF-8
+        let chunks = chunk_text(&"é".repeat(10), 5);
+        assert!(chunks.iter().all(|c| c.len() <= 5));
+        assert_eq!(chunks.concat(), "é".repeat(10));
+    }
+
+    #[tokio::test]
+    async fn test_map_reduce_over_multiple_chunks() {
+        let paragraph = format!("{}\n", "word ".repeat(1000));
+        let text = paragraph.repeat(12); // ~60k chars -> 3 chunks
+        let client = mock_client(vec!["part one", "part two", "part three", "final summary"]);
+        let mut budget = TokenBudget::new(MAX_TOKEN_BUDGET);
+
+        let outcome = summarize(&client, &text, 100, None, &mut budget).await.unwrap();
+        assert_eq!(outcome.summary, "final summary");
+        assert_eq!(outcome.chunks, 3);
+        assert_eq!(outcome.calls, 4);
+        assert!(budget.spent > 0);
+    }
+
+    #[tokio::test]
+    async fn test_budget_stops_before_calling_provider() {
+        let client = mock_client(vec![]);
+        let mut budget = TokenBudget::new(100);
+
+        let err = summarize(&client, &"word ".repeat(5000), 100, None, &mut budget)
+            .await
+            .err()
+            .unwrap();
+        assert!(err.contains("Token budget exceeded"), "{}", err);
+        assert_eq!(budget.spent, 0);
+    }
+
+    #[tokio::test]
+    async fn test_url_fetch_obeys_the_callers_tool_config() {
+        use crate::tools::registry::ToolRegistry;
+        use crate::tools::types::ToolConfig;
+        use std::sync::Arc;
+
+        let registry = Arc::new(ToolRegistry::new());
+        registry.register(Arc::new(crate::tools::builtin::WebFetchTool::new()));
+        let config = ToolConfig { deny_list: vec!["web_fetch".to_string()], ..ToolConfig::default() };
+        let context = ToolContext::new().with_tool_registry(registry, config);
+
+        let result = SummarizeTool::new()
+            .execute(json!({"url": "https://example.com"}), &context)
+            .await;
+        assert!(!result.success);
+        assert!(result.content.contains("'web_fetch' is not allowed"), "{}", result.content);
+    }
+}
@@ -0,0 +1,178 @@
+//! Validation Check tool
+//!
+//! Reads the EIP-8004 Validation Registry to confirm whether an agent holds a
+//! passing validation of a given type (e.g. a completed task validation).
+//! Returns a clear "not deployed" result when no validation registry is configured.
+
+use crate::eip8004::config::Eip8004Config;
+use crate::eip8004::validation::{ValidationRegistry, DEFAULT_MIN_RESPONSE};
+use crate::tools::registry::Tool;
+use crate::tools::types::{
+    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+};
+use crate::tools::ToolSafetyLevel;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+pub struct ValidationCheckTool {
+    definition: ToolDefinition,
+}
+
+impl ValidationCheckTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+
+        properties.insert(
+            "agent_id".to_string(),
+            PropertySchema {
+                schema_type: "integer".to_string(),
+                description: "EIP-8004 agent ID to check.".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "validation_type".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Validation type (the registry tag), e.g. \"task-completion\". Omit to accept any type.".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "min_response".to_string(),
+            PropertySchema {
+                schema_type: "integer".to_string(),
+                description: format!(
+                    "Minimum validator response (0-100) to count as valid. Default: {}.",
+                    DEFAULT_MIN_RESPONSE
+                ),
+                default: Some(json!(DEFAULT_MIN_RESPONSE)),
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        ValidationCheckTool {
+            definition: ToolDefinition {
+                name: "validation_check".to_string(),
+                description: "Check the EIP-8004 Validation Registry for an agent's attestations. \
+                    Returns whether the agent has a responded validation of the given type at or above \
+                    min_response, plus the record details (validator, response, tag, request hash)."
+                    .to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec!["agent_id".to_string()],
+                },
+                group: ToolGroup::Finance,
+                hidden: false,
+            },
+        }
+    }
+}
+
+impl Default for ValidationCheckTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ValidationCheckParams {
+    agent_id: u64,
+    #[serde(default)]
+    validation_type: Option<String>,
+    #[serde(default)]
+    min_response: Option<u8>,
+}
+
+#[async_trait]
+impl Tool for ValidationCheckTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: ValidationCheckParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        let min_response = params.min_response.unwrap_or(DEFAULT_MIN_RESPONSE);
+        if min_response > 100 {
+            return ToolResult::error("min_response must be between 0 and 100");
+        }
+        let validation_type = params.validation_type.unwrap_or_default();
+
+        let config = Eip8004Config::from_env();
+        if !config.is_validation_deployed() {
+            return ToolResult::success(format!(
+                "No Validation Registry is deployed on {} (set EIP8004_VALIDATION_REGISTRY to enable). \
+                 Agent {} cannot be verified through validations.",
+                config.chain_name, params.agent_id
+            ))
+            .with_metadata(json!({
+                "agent_id": params.agent_id,
+                "deployed": false,
+                "valid": false,
+            }));
+        }
+
+        let registry = match &context.wallet_provider {
+            Some(wp) => ValidationRegistry::new_with_wallet_provider(config, wp.clone()),
+            None => ValidationRegistry::new(config),
+        };
+
+        let check = match registry.check(params.agent_id, &validation_type, min_response).await {
+            Ok(c) => c,
+            Err(e) => return ToolResult::error(format!("Failed to read Validation Registry: {}", e)),
+        };
+
+        let type_label = if check.validation_type.is_empty() {
+            "any type".to_string()
+        } else {
+            format!("type '{}'", check.validation_type)
+        };
+
+        let mut output = format!(
+            "Agent {}: {} validation of {} (min response {}). {} matching of {} total request(s).",
+            check.agent_id,
+            if check.valid { "HAS a passing" } else { "has NO passing" },
+            type_label,
+            check.min_response,
+            check.matching,
+            check.total,
+        );
+        if let Some(ref r) = check.record {
+            output.push_str(&format!(
+                "\n\n{} record:\n  request_hash: {}\n  validator: {}\n  response: {}\n  tag: {}\n  status: {}",
+                if check.valid { "Passing" } else { "Most recent" },
+                r.request_hash,
+                r.validator_address,
+                r.response,
+                if r.tag.is_empty() { "(none)" } else { &r.tag },
+                if r.is_responded() {
+                    format!("responded (last_update {})", r.last_update)
+                } else {
+                    "pending".to_string()
+                },
+            ));
+        }
+
+        let mut metadata = json!(check);
+        metadata["deployed"] = json!(true);
+        ToolResult::success(output).with_metadata(metadata)
+    }
+
+    fn safety_level(&self) -> ToolSafetyLevel {
+        ToolSafetyLevel::ReadOnly
+    }
+}
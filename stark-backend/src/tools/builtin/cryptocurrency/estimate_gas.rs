@@ -0,0 +1,415 @@
+//! Estimate gas tool - preview the network fee of an on-chain action
+//!
+//! Simulates the action with eth_estimateGas from the bot wallet and prices it
+//! with the current EIP-1559 fees, so the agent can tell the user what an action
+//! will cost before asking for confirmation. Nothing is signed or queued.
+//!
+//! Fiat conversion is opt-in via STARK_FIAT_PRICE_SOURCE (currently "coingecko").
+
+use super::web3_tx::{parse_u256, SendEthTool};
+use crate::eip8004::abi::identity::encode_register;
+use crate::eip8004::config::Eip8004Config;
+use crate::tools::registry::Tool;
+use crate::tools::rpc_config::{resolve_rpc_from_context, Network};
+use crate::tools::types::{
+    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+};
+use crate::tools::ToolSafetyLevel;
+use crate::x402::X402EvmRpc;
+use async_trait::async_trait;
+use ethers::abi::Token;
+use ethers::prelude::*;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// ERC-20 transfer(address,uint256) selector
+const ERC20_TRANSFER_SELECTOR: [u8; 4] = [0xa9, 0x05, 0x9c, 0xbb];
+
+/// Buffer applied to the raw estimate, matching what the signing path uses
+const GAS_BUFFER_PERCENT: u64 = 120;
+
+pub struct EstimateGasTool {
+    definition: ToolDefinition,
+}
+
+impl EstimateGasTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+
+        let string_prop = |description: &str| PropertySchema {
+            schema_type: "string".to_string(),
+            description: description.to_string(),
+            default: None,
+            items: None,
+            enum_values: None,
+        };
+
+        properties.insert(
+            "action".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "What to estimate: 'transfer' (native ETH/MATIC to 'to' with 'value'), \
+                    'token_transfer' (ERC-20 'amount' of 'token' to 'to'), \
+                    'register_identity' (EIP-8004 registration with 'agent_uri'), \
+                    or 'contract_call' (raw 'data' to 'to' with optional 'value')."
+                    .to_string(),
+                default: None,
+                items: None,
+                enum_values: Some(vec![
+                    "transfer".to_string(),
+                    "token_transfer".to_string(),
+                    "register_identity".to_string(),
+                    "contract_call".to_string(),
+                ]),
+            },
+        );
+        properties.insert("to".to_string(), string_prop("Recipient or contract address (0x...)."));
+        properties.insert("value".to_string(), string_prop("Native value in wei (decimal or 0x hex). Default: 0."));
+        properties.insert("token".to_string(), string_prop("ERC-20 token contract address (token_transfer only)."));
+        properties.insert("amount".to_string(), string_prop("Raw token amount in base units (token_transfer only)."));
+        properties.insert("data".to_string(), string_prop("Hex calldata (contract_call only)."));
+        properties.insert("agent_uri".to_string(), string_prop("Registration URI (register_identity only)."));
+        properties.insert(
+            "network".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Network: 'base', 'mainnet', or 'polygon'. Defaults to the selected network.".to_string(),
+                default: None,
+                items: None,
+                enum_values: Some(vec!["base".to_string(), "mainnet".to_string(), "polygon".to_string()]),
+            },
+        );
+
+        EstimateGasTool {
+            definition: ToolDefinition {
+                name: "estimate_gas".to_string(),
+                description: "Estimate the network fee of an on-chain action before doing it. \
+                    Simulates the transaction from the bot wallet and returns gas, fee in the native token, \
+                    and USD if a price source is configured. Read-only: nothing is signed or queued. \
+                    Use this to tell the user the cost before asking them to confirm."
+                    .to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec!["action".to_string()],
+                },
+                group: ToolGroup::Finance,
+                hidden: false,
+            },
+        }
+    }
+}
+
+impl Default for EstimateGasTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct EstimateGasParams {
+    action: String,
+    to: Option<String>,
+    value: Option<String>,
+    token: Option<String>,
+    amount: Option<String>,
+    data: Option<String>,
+    agent_uri: Option<String>,
+    network: Option<String>,
+}
+
+/// The transaction an action would send
+#[derive(Debug, PartialEq)]
+struct PlannedTx {
+    to: Address,
+    data: Vec<u8>,
+    value: U256,
+    description: String,
+}
+
+fn parse_address(label: &str, value: Option<&str>) -> Result<Address, String> {
+    let value = value.ok_or_else(|| format!("'{}' is required for this action", label))?;
+    Address::from_str(value.trim()).map_err(|_| format!("Invalid '{}' address: {}", label, value))
+}
+
+/// Build the transaction for an action without touching the network
+fn plan_transaction(params: &EstimateGasParams, network: Network) -> Result<PlannedTx, String> {
+    let value = params.value.as_deref().map(parse_u256).transpose()?.unwrap_or_default();
+
+    match params.action.as_str() {
+        "transfer" => {
+            let to = parse_address("to", params.to.as_deref())?;
+            Ok(PlannedTx {
+                to,
+                data: Vec::new(),
+                value,
+                description: format!(
+                    "Send {} to {:?}",
+                    SendEthTool::format_eth(&value.to_string()),
+                    to
+                ),
+            })
+        }
+        "token_transfer" => {
+            let token = parse_address("token", params.token.as_deref())?;
+            let to = parse_address("to", params.to.as_deref())?;
+            let amount = parse_u256(params.amount.as_deref().ok_or("'amount' is required for token_transfer")?)?;
+            let mut data = ERC20_TRANSFER_SELECTOR.to_vec();
+            data.extend(ethers::abi::encode(&[Token::Address(to), Token::Uint(amount)]));
+            Ok(PlannedTx {
+                to: token,
+                data,
+                value: U256::zero(),
+                description: format!("Transfer {} base units of token {:?} to {:?}", amount, token, to),
+            })
+        }
+        "register_identity" => {
+            let agent_uri = params
+                .agent_uri
+                .as_deref()
+                .filter(|u| !u.trim().is_empty())
+                .ok_or("'agent_uri' is required for register_identity")?;
+            let config = Eip8004Config::from_env();
+            if !config.is_identity_deployed() {
+                return Err("Identity Registry not deployed".to_string());
+            }
+            if config.chain_id != network.chain_id() {
+                return Err(format!(
+                    "The Identity Registry is on {} (chain {}); select that network to estimate registration",
+                    config.chain_name, config.chain_id
+                ));
+            }
+            Ok(PlannedTx {
+                to: Address::from_str(&config.identity_registry)
+                    .map_err(|e| format!("Invalid registry address: {}", e))?,
+                data: encode_register(agent_uri),
+                value: U256::zero(),
+                description: format!("Register EIP-8004 identity with URI {}", agent_uri),
+            })
+        }
+        "contract_call" => {
+            let to = parse_address("to", params.to.as_deref())?;
+            let data_hex = params.data.as_deref().unwrap_or("0x");
+            let data = hex::decode(data_hex.trim().trim_start_matches("0x"))
+                .map_err(|e| format!("Invalid 'data' hex: {}", e))?;
+            Ok(PlannedTx {
+                to,
+                data,
+                value,
+                description: format!("Call {:?} with {} bytes of calldata", to, data_hex.len().saturating_sub(2) / 2),
+            })
+        }
+        other => Err(format!(
+            "Unknown action '{}'. Use transfer, token_transfer, register_identity, or contract_call.",
+            other
+        )),
+    }
+}
+
+/// Convert a wei amount to whole native units (lossy, display only)
+fn wei_to_native(wei: U256) -> f64 {
+    wei.to_string().parse::<f64>().unwrap_or(0.0) / 1e18
+}
+
+/// Native token USD price from the configured price source, if any
+async fn native_usd_price(client: &reqwest::Client, network: Network) -> Option<f64> {
+    let source = crate::config::fiat_price_source()?;
+    if source != "coingecko" {
+        log::warn!("[estimate_gas] Unsupported fiat price source '{}'", source);
+        return None;
+    }
+
+    let coin_id = match network.native_currency() {
+        "MATIC" => "matic-network",
+        _ => "ethereum",
+    };
+    let url = format!(
+        "https://api.coingecko.com/api/v3/simple/price?ids={}&vs_currencies=usd",
+        coin_id
+    );
+
+    let response = client
+        .get(&url)
+        .timeout(std::time::Duration::from_secs(5))
+        .send()
+        .await
+        .ok()?;
+    let body: Value = response.json().await.ok()?;
+    body.get(coin_id)?.get("usd")?.as_f64()
+}
+
+#[async_trait]
+impl Tool for EstimateGasTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: EstimateGasParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        let network_str = params
+            .network
+            .as_deref()
+            .or(context.selected_network.as_deref())
+            .unwrap_or("base");
+        let network = match Network::from_str(network_str) {
+            Ok(n) => n,
+            Err(_) => {
+                return ToolResult::error(format!(
+                    "Invalid network '{}'. Must be one of: base, mainnet, polygon",
+                    network_str
+                ))
+            }
+        };
+
+        let planned = match plan_transaction(&params, network) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(e),
+        };
+
+        let wallet_provider = match &context.wallet_provider {
+            Some(wp) => wp,
+            None => return ToolResult::error("Wallet not configured. Cannot simulate transactions."),
+        };
+        let from_str = wallet_provider.get_address();
+        let from: Address = match from_str.parse() {
+            Ok(a) => a,
+            Err(_) => return ToolResult::error(format!("Invalid wallet address: {}", from_str)),
+        };
+
+        let rpc_config = resolve_rpc_from_context(&context.extra, network.as_ref());
+        let rpc = match X402EvmRpc::new_with_wallet_provider(
+            wallet_provider.clone(),
+            network.as_ref(),
+            Some(rpc_config.url.clone()),
+            rpc_config.use_x402,
+        ) {
+            Ok(r) => r,
+            Err(e) => return ToolResult::error(e),
+        };
+
+        let gas = match rpc.estimate_gas(from, planned.to, &planned.data, planned.value).await {
+            Ok(g) => g,
+            Err(e) => {
+                return ToolResult::error(format!(
+                    "Simulation failed — the transaction would likely revert or the wallet can't cover it:\n{}\n\nAction: {}",
+                    e, planned.description
+                ))
+            }
+        };
+        let gas_limit = gas * U256::from(GAS_BUFFER_PERCENT) / U256::from(100u64);
+
+        let (max_fee, priority_fee) = match rpc.estimate_eip1559_fees().await {
+            Ok(fees) => fees,
+            Err(e) => return ToolResult::error(format!("Failed to fetch gas prices: {}", e)),
+        };
+
+        // Expected cost uses the simulated gas; the max is what the signed tx could pay at most
+        let expected_fee = gas * max_fee;
+        let max_total_fee = gas_limit * max_fee;
+        let symbol = network.native_currency();
+        let expected_native = wei_to_native(expected_fee);
+        let max_native = wei_to_native(max_total_fee);
+
+        let usd_price = native_usd_price(&context.http_client(), network).await;
+
+        let mut output = format!(
+            "Fee estimate on {} for: {}\n\
+             Gas: {} (limit with buffer: {})\n\
+             Gas price: {} (priority {})\n\
+             Estimated fee: {:.8} {}",
+            network,
+            planned.description,
+            gas,
+            gas_limit,
+            SendEthTool::format_gwei(&max_fee.to_string()),
+            SendEthTool::format_gwei(&priority_fee.to_string()),
+            expected_native,
+            symbol,
+        );
+        if let Some(price) = usd_price {
+            output.push_str(&format!(" (~${:.4})", expected_native * price));
+        }
+        output.push_str(&format!("\nMax fee: {:.8} {}", max_native, symbol));
+        if let Some(price) = usd_price {
+            output.push_str(&format!(" (~${:.4})", max_native * price));
+        }
+        if !planned.value.is_zero() {
+            output.push_str(&format!(
+                "\nValue sent (not included above): {}",
+                SendEthTool::format_eth(&planned.value.to_string())
+            ));
+        }
+
+        ToolResult::success(output).with_metadata(json!({
+            "network": network.as_ref(),
+            "action": params.action,
+            "from": from_str,
+            "to": format!("{:?}", planned.to),
+            "value": planned.value.to_string(),
+            "gas": gas.to_string(),
+            "gas_limit": gas_limit.to_string(),
+            "max_fee_per_gas": max_fee.to_string(),
+            "max_priority_fee_per_gas": priority_fee.to_string(),
+            "estimated_fee_wei": expected_fee.to_string(),
+            "max_fee_wei": max_total_fee.to_string(),
+            "native_symbol": symbol,
+            "native_usd_price": usd_price,
+            "estimated_fee_usd": usd_price.map(|p| expected_native * p),
+            "max_fee_usd": usd_price.map(|p| max_native * p),
+        }))
+    }
+
+    fn safety_level(&self) -> ToolSafetyLevel {
+        ToolSafetyLevel::ReadOnly
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(value: Value) -> EstimateGasParams {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_plan_token_transfer_encodes_erc20_call() {
+        let planned = plan_transaction(
+            &params(json!({
+                "action": "token_transfer",
+                "token": "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913",
+                "to": "0x1234567890abcdef1234567890abcdef12345678",
+                "amount": "1000000"
+            })),
+            Network::Base,
+        )
+        .unwrap();
+        assert_eq!(&planned.data[..4], &ERC20_TRANSFER_SELECTOR);
+        assert_eq!(planned.data.len(), 4 + 64);
+        assert!(planned.value.is_zero());
+    }
+
+    #[test]
+    fn test_plan_rejects_missing_or_bad_fields() {
+        assert!(plan_transaction(&params(json!({"action": "transfer"})), Network::Base).is_err());
+        assert!(plan_transaction(
+            &params(json!({"action": "contract_call", "to": "0x1234567890abcdef1234567890abcdef12345678", "data": "0xzz"})),
+            Network::Base
+        )
+        .is_err());
+        assert!(plan_transaction(&params(json!({"action": "bridge"})), Network::Base).is_err());
+
+        let transfer = plan_transaction(
+            &params(json!({"action": "transfer", "to": "0x1234567890abcdef1234567890abcdef12345678", "value": "0x10"})),
+            Network::Base,
+        )
+        .unwrap();
+        assert_eq!(transfer.value, U256::from(16u64));
+    }
+}
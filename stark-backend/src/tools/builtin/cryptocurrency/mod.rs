@@ -9,19 +9,23 @@ pub mod verify_intent;
 mod verify_tx_broadcast;
 mod decode_calldata;
 mod dexscreener;
+mod estimate_gas;
 mod geckoterminal;
 mod list_queued_web3_tx;
 pub mod network_lookup;
 mod polymarket_trade;
+mod price_lookup;
 mod select_web3_network;
 mod set_address;
 mod to_raw_amount;
+mod tx_manage;
 pub mod token_lookup;
 mod web3_function_call;
 mod web3_preset_function_call;
 pub mod web3_tx;
 mod erc8128_fetch;
 mod siwa_auth;
+pub mod sign_message;
 mod x402_agent_invoke;
 mod x402_fetch;
 mod x402_post;
@@ -30,18 +34,22 @@ mod x402_rpc;
 
 pub use erc8128_fetch::Erc8128FetchTool;
 pub use siwa_auth::SiwaAuthTool;
+pub use sign_message::SignMessageTool;
 pub use bridge_usdc::BridgeUsdcTool;
 pub use broadcast_web3_tx::BroadcastWeb3TxTool;
 pub use decode_calldata::DecodeCalldataTool;
 pub use dexscreener::DexScreenerTool;
+pub use estimate_gas::EstimateGasTool;
 pub use geckoterminal::GeckoTerminalTool;
 pub use list_queued_web3_tx::ListQueuedWeb3TxTool;
 pub use network_lookup::load_networks;
 pub use polymarket_trade::PolymarketTradeTool;
+pub use price_lookup::PriceLookupTool;
 pub use set_address::SetAddressTool;
 pub use select_web3_network::SelectWeb3NetworkTool;
 pub use to_raw_amount::ToRawAmountTool;
 pub use token_lookup::{load_tokens, TokenLookupTool};
+pub use tx_manage::TxManageTool;
 pub use web3_preset_function_call::Web3PresetFunctionCallTool;
 pub use verify_tx_broadcast::VerifyTxBroadcastTool;
 pub use web3_tx::SendEthTool;
@@ -0,0 +1,372 @@
+//! Price lookup tool - current token price in a fiat currency
+//!
+//! Resolves a ticker, name or CoinGecko id via the price API's search endpoint,
+//! then fetches the spot price and 24h change. The API base URL is configurable
+//! (STARK_PRICE_API_URL, CoinGecko-compatible) and an optional COINGECKO_API_KEY
+//! raises rate limits. Quotes are cached briefly so repeated questions in a
+//! conversation don't hit the API each time.
+
+use crate::controllers::api_keys::ApiKeyId;
+use crate::tools::registry::Tool;
+use crate::tools::types::{
+    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+};
+use crate::tools::ToolSafetyLevel;
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How long a quote is served from cache
+const CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// How many close matches to suggest for an unknown token
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Cached quotes keyed by (lowercased query, currency)
+static QUOTE_CACHE: Lazy<Mutex<HashMap<(String, String), (Instant, PriceQuote)>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+pub struct PriceLookupTool {
+    definition: ToolDefinition,
+}
+
+impl PriceLookupTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+
+        properties.insert(
+            "token".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Ticker, name or CoinGecko id of the token (e.g. 'ETH', 'bitcoin', 'usd-coin')".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "currency".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Fiat currency code for the price (e.g. usd, eur, gbp). Defaults to usd.".to_string(),
+                default: Some(json!("usd")),
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        PriceLookupTool {
+            definition: ToolDefinition {
+                name: "price_lookup".to_string(),
+                description: "Get the current price of a token or cryptocurrency in a fiat currency, with its 24h change. \
+                    Use this to show fiat equivalents of tips and balances or to answer 'how much is X worth'. \
+                    For DEX pools, liquidity and charts use dexscreener or geckoterminal instead."
+                    .to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec!["token".to_string()],
+                },
+                group: ToolGroup::Finance,
+                hidden: false,
+            },
+        }
+    }
+}
+
+impl Default for PriceLookupTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    token: String,
+    currency: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    #[serde(default)]
+    coins: Vec<Coin>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Coin {
+    id: String,
+    name: String,
+    symbol: String,
+    market_cap_rank: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct PriceQuote {
+    id: String,
+    name: String,
+    symbol: String,
+    currency: String,
+    price: f64,
+    change_24h: Option<f64>,
+}
+
+/// Pick the coin the user most likely meant: an exact id, then an exact ticker,
+/// then an exact name. Tickers are shared by many tokens, so the highest market
+/// cap wins. Otherwise returns the closest search results as suggestions.
+fn pick_coin<'a>(query: &str, coins: &'a [Coin]) -> Result<&'a Coin, Vec<String>> {
+    let q = query.trim().to_lowercase();
+    let by_rank = |matches: Vec<&'a Coin>| {
+        matches
+            .into_iter()
+            .min_by_key(|c| c.market_cap_rank.unwrap_or(u32::MAX))
+    };
+
+    if let Some(coin) = coins.iter().find(|c| c.id == q) {
+        return Ok(coin);
+    }
+    if let Some(coin) = by_rank(coins.iter().filter(|c| c.symbol.to_lowercase() == q).collect()) {
+        return Ok(coin);
+    }
+    if let Some(coin) = by_rank(coins.iter().filter(|c| c.name.to_lowercase() == q).collect()) {
+        return Ok(coin);
+    }
+
+    Err(coins
+        .iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|c| format!("{} ({}, id: {})", c.name, c.symbol.to_uppercase(), c.id))
+        .collect())
+}
+
+fn cached_quote(key: &(String, String)) -> Option<PriceQuote> {
+    let cache = QUOTE_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    cache
+        .get(key)
+        .filter(|(at, _)| at.elapsed() < CACHE_TTL)
+        .map(|(_, quote)| quote.clone())
+}
+
+fn store_quote(key: (String, String), quote: PriceQuote) {
+    let mut cache = QUOTE_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    cache.retain(|_, (at, _)| at.elapsed() < CACHE_TTL);
+    cache.insert(key, (Instant::now(), quote));
+}
+
+fn format_price(price: f64) -> String {
+    if price >= 1.0 || price == 0.0 {
+        format!("{:.2}", price)
+    } else if price < 0.00000001 {
+        // Too small for 8 decimals, which would print as "0."
+        format!("{:.2e}", price)
+    } else {
+        // Keep significant digits for sub-cent tokens
+        format!("{:.8}", price).trim_end_matches('0').to_string()
+    }
+}
+
+fn format_quote(quote: &PriceQuote) -> String {
+    let change = quote
+        .change_24h
+        .map(|c| format!(" ({:+.2}% 24h)", c))
+        .unwrap_or_default();
+    format!(
+        "{} ({}): {} {}{}",
+        quote.name,
+        quote.symbol.to_uppercase(),
+        format_price(quote.price),
+        quote.currency.to_uppercase(),
+        change
+    )
+}
+
+/// GET a path on the price API, with the API key header when one is configured
+async fn api_get(context: &ToolContext, path_and_query: &str) -> Result<Value, String> {
+    let base = crate::config::price_api_url();
+    let url = format!("{}{}", base.trim_end_matches('/'), path_and_query);
+
+    let mut request = context
+        .http_client()
+        .get(&url)
+        .timeout(Duration::from_secs(15))
+        .header("User-Agent", "StarkBot/1.0");
+    if let Some(key) = context.get_api_key_by_id(ApiKeyId::CoingeckoApiKey) {
+        // Paid plans use a different host and header than the free demo keys
+        let header = if base.contains("pro-api.") { "x-cg-pro-api-key" } else { "x-cg-demo-api-key" };
+        request = request.header(header, key);
+    }
+
+    let resp = request.send().await.map_err(|e| format!("Request failed: {}", e))?;
+    if resp.status().as_u16() == 429 {
+        return Err("Price API rate limit reached. Try again in a minute.".to_string());
+    }
+    if !resp.status().is_success() {
+        return Err(format!("Price API error: {}", resp.status()));
+    }
+    resp.json().await.map_err(|e| format!("Parse error: {}", e))
+}
+
+async fn fetch_quote(context: &ToolContext, token: &str, currency: &str) -> Result<PriceQuote, String> {
+    let search: SearchResponse = serde_json::from_value(
+        api_get(context, &format!("/search?query={}", urlencoding::encode(token))).await?,
+    )
+    .map_err(|e| format!("Parse error: {}", e))?;
+
+    let coin = pick_coin(token, &search.coins).map_err(|suggestions| {
+        if suggestions.is_empty() {
+            format!("Unknown token '{}'. No similar tokens found.", token)
+        } else {
+            format!(
+                "Unknown token '{}'. Did you mean one of these? {}. Retry with the exact id.",
+                token,
+                suggestions.join("; ")
+            )
+        }
+    })?;
+
+    let prices = api_get(
+        context,
+        &format!(
+            "/simple/price?ids={}&vs_currencies={}&include_24hr_change=true",
+            urlencoding::encode(&coin.id),
+            urlencoding::encode(currency)
+        ),
+    )
+    .await?;
+    let entry = prices
+        .get(&coin.id)
+        .ok_or_else(|| format!("No price available for {} ({})", coin.name, coin.id))?;
+    let price = entry
+        .get(currency)
+        .and_then(|v| v.as_f64())
+        .ok_or_else(|| format!("Unsupported currency '{}'", currency))?;
+    // The API reports 0 for coins it has no market data for
+    if price <= 0.0 {
+        return Err(format!("No price available for {} ({})", coin.name, coin.id));
+    }
+
+    Ok(PriceQuote {
+        id: coin.id.clone(),
+        name: coin.name.clone(),
+        symbol: coin.symbol.clone(),
+        currency: currency.to_string(),
+        price,
+        change_24h: entry.get(format!("{}_24h_change", currency)).and_then(|v| v.as_f64()),
+    })
+}
+
+#[async_trait]
+impl Tool for PriceLookupTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: Params = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        let token = params.token.trim();
+        if token.is_empty() {
+            return ToolResult::error("'token' is required");
+        }
+        let currency = params
+            .currency
+            .as_deref()
+            .map(|c| c.trim().to_lowercase())
+            .filter(|c| !c.is_empty())
+            .unwrap_or_else(|| "usd".to_string());
+        if !currency.chars().all(|c| c.is_ascii_alphabetic()) {
+            return ToolResult::error(format!("Invalid currency '{}'. Use a code like usd or eur.", currency));
+        }
+
+        let key = (token.to_lowercase(), currency.clone());
+        let (quote, cached) = match cached_quote(&key) {
+            Some(quote) => (quote, true),
+            None => match fetch_quote(context, token, &currency).await {
+                Ok(quote) => {
+                    store_quote(key, quote.clone());
+                    (quote, false)
+                }
+                Err(e) => return ToolResult::error(e),
+            },
+        };
+
+        ToolResult::success(format_quote(&quote)).with_metadata(json!({
+            "id": quote.id,
+            "symbol": quote.symbol,
+            "currency": quote.currency,
+            "price": quote.price,
+            "change_24h": quote.change_24h,
+            "cached": cached,
+        }))
+    }
+
+    fn safety_level(&self) -> ToolSafetyLevel {
+        ToolSafetyLevel::ReadOnly
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coin(id: &str, name: &str, symbol: &str, rank: Option<u32>) -> Coin {
+        Coin {
+            id: id.to_string(),
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            market_cap_rank: rank,
+        }
+    }
+
+    #[test]
+    fn test_pick_coin() {
+        let coins = vec![
+            coin("ethereum-wormhole", "Ethereum (Wormhole)", "eth", Some(900)),
+            coin("ethereum", "Ethereum", "eth", Some(2)),
+            coin("ethereum-classic", "Ethereum Classic", "etc", Some(30)),
+        ];
+
+        // Shared ticker resolves to the largest market cap
+        assert_eq!(pick_coin("ETH", &coins).unwrap().id, "ethereum");
+        // Exact ids and names work too
+        assert_eq!(pick_coin("ethereum-classic", &coins).unwrap().id, "ethereum-classic");
+        assert_eq!(pick_coin("Ethereum Classic", &coins).unwrap().id, "ethereum-classic");
+
+        // Near misses return suggestions instead of guessing
+        let suggestions = pick_coin("etherium", &coins).unwrap_err();
+        assert_eq!(suggestions.len(), 3);
+        assert_eq!(suggestions[1], "Ethereum (ETH, id: ethereum)");
+        assert!(pick_coin("zzz", &[]).unwrap_err().is_empty());
+    }
+
+    #[test]
+    fn test_format_quote() {
+        let mut quote = PriceQuote {
+            id: "pepe".to_string(),
+            name: "Pepe".to_string(),
+            symbol: "pepe".to_string(),
+            currency: "usd".to_string(),
+            price: 0.00001234,
+            change_24h: Some(-3.456),
+        };
+        assert_eq!(format_quote(&quote), "Pepe (PEPE): 0.00001234 USD (-3.46% 24h)");
+
+        quote.price = 2500.5;
+        quote.change_24h = None;
+        assert_eq!(format_quote(&quote), "Pepe (PEPE): 2500.50 USD");
+    }
+
+    #[test]
+    fn test_format_price_edges() {
+        assert_eq!(format_price(0.0), "0.00");
+        assert_eq!(format_price(0.5), "0.5");
+        assert_eq!(format_price(0.000000001234), "1.23e-9");
+    }
+}
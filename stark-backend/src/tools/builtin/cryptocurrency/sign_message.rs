@@ -0,0 +1,567 @@
+//! Message signing tool
+//!
+//! Signs a login challenge with the configured wallet so the agent can prove
+//! control of it to services that use wallet-signature login. Anything that
+//! looks like a transaction, a raw hash or a token approval is refused: those
+//! signatures can move funds, a login challenge never needs one.
+//!
+//! Like queued transactions, signing needs the operator's approval outside rogue
+//! mode: the request is queued, `sign_message.confirmation_required` opens a
+//! modal, and the `sign_message.confirm` / `sign_message.deny` gateway methods
+//! resolve it. The agent collects the signature with `request_id`.
+
+use crate::gateway::protocol::GatewayEvent;
+use crate::tools::registry::Tool;
+use crate::tools::types::{
+    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+    ToolSafetyLevel,
+};
+use crate::wallet::WalletProvider;
+use async_trait::async_trait;
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// EIP-712 struct types (lowercased substrings) that authorize spending or execution
+const BLOCKED_TYPED_DATA: &[&str] = &[
+    "permit",
+    "order",
+    "transfer",
+    "transaction",
+    "safetx",
+    "forwardrequest",
+    "delegat",
+    "approval",
+    "withdraw",
+];
+
+/// EIP-712 message fields that only show up in spending/execution payloads
+const BLOCKED_TYPED_FIELDS: &[&str] = &["spender", "calldata", "data", "value", "amount"];
+
+/// How long a queued signature request (or its result) is kept
+const SIGN_REQUEST_TTL: Duration = Duration::from_secs(600);
+
+/// Where a queued signature request stands
+#[derive(Debug, Clone, PartialEq)]
+pub enum SignRequestStatus {
+    Pending,
+    Signed { address: String, signature: String },
+    Denied,
+}
+
+/// A signature request awaiting (or resolved by) the operator
+#[derive(Debug, Clone)]
+pub struct SignRequest {
+    pub uuid: String,
+    pub channel_id: Option<i64>,
+    pub scheme: String,
+    /// The eip191 message string or the eip712 typed data
+    pub payload: Value,
+    pub status: SignRequestStatus,
+    created_at: Instant,
+}
+
+/// Queued signature requests by uuid
+static SIGN_REQUESTS: Lazy<DashMap<String, SignRequest>> = Lazy::new(DashMap::new);
+
+fn queue_sign_request(channel_id: Option<i64>, scheme: &str, payload: Value) -> SignRequest {
+    SIGN_REQUESTS.retain(|_, r| r.created_at.elapsed() < SIGN_REQUEST_TTL);
+    let request = SignRequest {
+        uuid: uuid::Uuid::new_v4().to_string(),
+        channel_id,
+        scheme: scheme.to_string(),
+        payload,
+        status: SignRequestStatus::Pending,
+        created_at: Instant::now(),
+    };
+    SIGN_REQUESTS.insert(request.uuid.clone(), request.clone());
+    request
+}
+
+/// A queued signature request, unless it is unknown or expired
+pub fn get_sign_request(uuid: &str) -> Option<SignRequest> {
+    SIGN_REQUESTS
+        .get(uuid)
+        .filter(|r| r.created_at.elapsed() < SIGN_REQUEST_TTL)
+        .map(|r| r.clone())
+}
+
+/// Sign a pending request after the operator approved it
+pub async fn approve_sign_request(
+    uuid: &str,
+    wallet_provider: Option<Arc<dyn WalletProvider>>,
+) -> Result<SignRequest, String> {
+    // Taken out of the map while signing so a second approval can't sign it twice
+    let (_, mut request) = SIGN_REQUESTS
+        .remove_if(uuid, |_, r| r.status == SignRequestStatus::Pending)
+        .ok_or_else(|| format!("Signature request {} is not pending", uuid))?;
+    if request.created_at.elapsed() >= SIGN_REQUEST_TTL {
+        return Err(format!("Signature request {} has expired", uuid));
+    }
+    let signed = match resolve_wallet(wallet_provider) {
+        Ok(wallet) => sign_payload(wallet.as_ref(), &request.payload)
+            .await
+            .map(|signature| (wallet.get_address(), signature)),
+        Err(e) => Err(e),
+    };
+    match signed {
+        Ok((address, signature)) => {
+            request.status = SignRequestStatus::Signed { address, signature };
+            SIGN_REQUESTS.insert(request.uuid.clone(), request.clone());
+            Ok(request)
+        }
+        Err(e) => {
+            SIGN_REQUESTS.insert(request.uuid.clone(), request);
+            Err(e)
+        }
+    }
+}
+
+/// Reject a pending request
+pub fn deny_sign_request(uuid: &str) -> Option<SignRequest> {
+    let mut entry = SIGN_REQUESTS.get_mut(uuid)?;
+    if entry.status != SignRequestStatus::Pending {
+        return None;
+    }
+    entry.status = SignRequestStatus::Denied;
+    Some(entry.clone())
+}
+
+/// The context's wallet, or one built from the burner key
+fn resolve_wallet(wallet_provider: Option<Arc<dyn WalletProvider>>) -> Result<Arc<dyn WalletProvider>, String> {
+    if let Some(wp) = wallet_provider {
+        return Ok(wp);
+    }
+    let pk = crate::config::burner_wallet_private_key().ok_or(
+        "No wallet provider available. Set BURNER_WALLET_BOT_PRIVATE_KEY or configure a wallet provider.",
+    )?;
+    crate::wallet::EnvWalletProvider::from_private_key(&pk)
+        .map(|p| Arc::new(p) as Arc<dyn WalletProvider>)
+        .map_err(|e| format!("Failed to create wallet: {}", e))
+}
+
+/// Sign an eip191 message (string payload) or eip712 typed data; returns the hex signature
+async fn sign_payload(wallet: &dyn WalletProvider, payload: &Value) -> Result<String, String> {
+    let signed = match payload {
+        Value::String(message) => wallet.sign_message(message.as_bytes()).await,
+        typed_data => wallet.sign_typed_data(typed_data).await,
+    };
+    signed
+        .map(|sig| format!("0x{}", hex::encode(sig.to_vec())))
+        .map_err(|e| format!("Failed to sign message: {}", e))
+}
+
+pub struct SignMessageTool {
+    definition: ToolDefinition,
+}
+
+impl SignMessageTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+
+        properties.insert(
+            "scheme".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Signature scheme: 'eip191' (personal_sign of `message`) or 'eip712' \
+                    (typed data in `typed_data`). Default: eip191."
+                    .to_string(),
+                default: Some(json!("eip191")),
+                items: None,
+                enum_values: Some(vec!["eip191".to_string(), "eip712".to_string()]),
+            },
+        );
+
+        properties.insert(
+            "request_id".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Id of an earlier request that is awaiting the operator's approval: \
+                    returns its signature once approved. Other parameters are ignored."
+                    .to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "message".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "The challenge text to sign, exactly as the service issued it (eip191)."
+                    .to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "typed_data".to_string(),
+            PropertySchema {
+                schema_type: "object".to_string(),
+                description: "EIP-712 typed data with domain, types, primaryType and message (eip712)."
+                    .to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        SignMessageTool {
+            definition: ToolDefinition {
+                name: "sign_message".to_string(),
+                description: "Sign a login challenge with the bot's wallet to prove control of its address \
+                    to an external service. Only for authentication: transactions, raw hashes, permits and \
+                    orders are refused. Unless rogue mode is on, the operator must approve the request first: \
+                    call again with the returned request_id to collect the signature."
+                    .to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec![],
+                },
+                group: ToolGroup::Finance,
+                hidden: false,
+            },
+        }
+    }
+}
+
+impl Default for SignMessageTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SignMessageParams {
+    #[serde(default = "default_scheme")]
+    scheme: String,
+    message: Option<String>,
+    typed_data: Option<Value>,
+    request_id: Option<String>,
+}
+
+fn default_scheme() -> String {
+    "eip191".to_string()
+}
+
+/// Reason a personal_sign message must not be signed, if any
+fn check_plain_message(message: &str) -> Result<(), String> {
+    let trimmed = message.trim();
+    if trimmed.is_empty() {
+        return Err("Message is empty".to_string());
+    }
+    let Some(hex_body) = trimmed.strip_prefix("0x").or_else(|| trimmed.strip_prefix("0X")) else {
+        return Ok(());
+    };
+    if hex_body.is_empty() || !hex_body.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Ok(());
+    }
+    if hex_body.len() == 64 {
+        return Err("Refusing to sign a raw 32-byte hash: it could be a transaction or permit digest".to_string());
+    }
+    // RLP lists (legacy txs) start at 0xc0; typed transactions are 0x01-0x04 followed by a list
+    let first = u8::from_str_radix(&hex_body[..2.min(hex_body.len())], 16).unwrap_or(0);
+    let second = hex_body
+        .get(2..4)
+        .and_then(|b| u8::from_str_radix(b, 16).ok())
+        .unwrap_or(0);
+    if first >= 0xc0 || ((0x01..=0x04).contains(&first) && second >= 0xc0) {
+        return Err("Refusing to sign: the message looks like an encoded transaction".to_string());
+    }
+    Ok(())
+}
+
+/// Reason EIP-712 typed data must not be signed, if any. Every struct type is checked,
+/// not just the primary one, and message fields are checked at every depth, so a
+/// `Permit` nested inside an innocuous-looking `Login` is refused too.
+fn check_typed_data(typed_data: &Value) -> Result<(), String> {
+    let obj = typed_data
+        .as_object()
+        .ok_or("typed_data must be an object")?;
+    // The wallet provider signs `_hash` blindly, so never accept one from the caller
+    if obj.contains_key("_hash") {
+        return Err("Refusing to sign a precomputed hash; pass the full typed data".to_string());
+    }
+    let primary_type = obj
+        .get("primaryType")
+        .and_then(|v| v.as_str())
+        .ok_or("typed_data is missing primaryType")?;
+    let type_names = obj
+        .get("types")
+        .and_then(|v| v.as_object())
+        .into_iter()
+        .flat_map(|types| types.keys().map(String::as_str))
+        .filter(|name| *name != "EIP712Domain");
+    for type_name in std::iter::once(primary_type).chain(type_names) {
+        let lower = type_name.to_lowercase();
+        if let Some(blocked) = BLOCKED_TYPED_DATA.iter().find(|b| lower.contains(*b)) {
+            return Err(format!(
+                "Refusing to sign typed data containing '{}': '{}' payloads can authorize spending",
+                type_name, blocked
+            ));
+        }
+    }
+    if let Some(message) = obj.get("message") {
+        if let Some(field) = find_blocked_field(message) {
+            return Err(format!(
+                "Refusing to sign typed data with a '{}' field: it looks like a spending or execution payload",
+                field
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// First blocked field name anywhere in a typed-data message (nested structs and arrays included)
+fn find_blocked_field(value: &Value) -> Option<&str> {
+    match value {
+        Value::Object(fields) => fields.iter().find_map(|(key, nested)| {
+            if BLOCKED_TYPED_FIELDS.contains(&key.to_lowercase().as_str()) {
+                Some(key.as_str())
+            } else {
+                find_blocked_field(nested)
+            }
+        }),
+        Value::Array(items) => items.iter().find_map(find_blocked_field),
+        _ => None,
+    }
+}
+
+/// Result for a request that is waiting on, or was resolved by, the operator
+fn sign_request_result(request: &SignRequest) -> ToolResult {
+    match &request.status {
+        SignRequestStatus::Pending => ToolResult::success(format!(
+            "Signature request {} is still awaiting the operator's approval. Check again later.",
+            request.uuid
+        ))
+        .with_metadata(json!({ "request_id": request.uuid, "status": "awaiting_confirmation" })),
+        SignRequestStatus::Signed { address, signature } => ToolResult::success(format!(
+            "Signed with {} ({}).\nSignature: {}",
+            address, request.scheme, signature
+        ))
+        .with_metadata(json!({
+            "request_id": request.uuid,
+            "status": "signed",
+            "scheme": request.scheme,
+            "address": address,
+            "signature": signature,
+        })),
+        SignRequestStatus::Denied => {
+            ToolResult::error(format!("The operator denied signature request {}.", request.uuid))
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for SignMessageTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: SignMessageParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        if let Some(request_id) = params.request_id.as_deref() {
+            return match get_sign_request(request_id.trim()) {
+                Some(request) if request.channel_id == context.channel_id => sign_request_result(&request),
+                _ => ToolResult::error(format!("No signature request '{}' (unknown or expired)", request_id)),
+            };
+        }
+
+        let scheme = params.scheme.trim().to_lowercase();
+        if scheme != "eip191" && scheme != "eip712" {
+            return ToolResult::error(format!("Unknown scheme '{}'. Use eip191 or eip712.", params.scheme));
+        }
+
+        // Validate the payload before touching the wallet
+        let payload = if scheme == "eip191" {
+            let Some(message) = params.message.as_deref() else {
+                return ToolResult::error("eip191 signing needs `message`");
+            };
+            if let Err(reason) = check_plain_message(message) {
+                log::warn!("[SIGN_MESSAGE] Refused eip191 message: {}", reason);
+                return ToolResult::error(reason);
+            }
+            json!(message)
+        } else {
+            let Some(typed_data) = params.typed_data.as_ref() else {
+                return ToolResult::error("eip712 signing needs `typed_data`");
+            };
+            if let Err(reason) = check_typed_data(typed_data) {
+                log::warn!("[SIGN_MESSAGE] Refused eip712 payload: {}", reason);
+                return ToolResult::error(reason);
+            }
+            typed_data.clone()
+        };
+
+        let wallet_provider = match resolve_wallet(context.wallet_provider.clone()) {
+            Ok(wp) => wp,
+            Err(e) => return ToolResult::error(e),
+        };
+        let address = wallet_provider.get_address();
+
+        // Partner mode: queue the request for the operator instead of signing
+        let is_rogue_mode = context
+            .extra
+            .get("rogue_mode_enabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        if !is_rogue_mode {
+            let request = queue_sign_request(context.channel_id, &scheme, payload);
+            if let (Some(broadcaster), Some(ch_id)) = (&context.broadcaster, context.channel_id) {
+                broadcaster.broadcast(GatewayEvent::sign_message_confirmation_required(
+                    ch_id,
+                    &request.uuid,
+                    &scheme,
+                    &address,
+                    &request.payload,
+                ));
+            }
+            log::info!(
+                "[SIGN_MESSAGE] Queued {} request {} for operator approval (channel {:?})",
+                scheme,
+                request.uuid,
+                context.channel_id
+            );
+            return ToolResult::success(format!(
+                "PARTNER MODE - Signature request queued for the operator's approval.\n\n\
+                Request ID: {}\n\
+                Signer: {}\n\n\
+                Call sign_message with request_id once they have approved it to get the signature.",
+                request.uuid, address
+            ))
+            .with_metadata(json!({
+                "request_id": request.uuid,
+                "status": "awaiting_confirmation",
+                "scheme": scheme,
+                "address": address,
+            }));
+        }
+
+        let signature = match sign_payload(wallet_provider.as_ref(), &payload).await {
+            Ok(sig) => sig,
+            Err(e) => return ToolResult::error(e),
+        };
+        log::info!(
+            "[SIGN_MESSAGE] Signed {} payload with {} (channel {:?})",
+            scheme,
+            address,
+            context.channel_id
+        );
+
+        ToolResult::success(format!(
+            "Signed with {} ({}).\nSignature: {}",
+            address, scheme, signature
+        ))
+        .with_metadata(json!({
+            "scheme": scheme,
+            "address": address,
+            "signature": signature,
+        }))
+    }
+
+    fn safety_level(&self) -> ToolSafetyLevel {
+        // Never in safe mode or read-only subagents: only the operator's own sessions may sign
+        ToolSafetyLevel::Standard
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_messages_allowed() {
+        assert!(check_plain_message("Sign in to example.com\nNonce: 8f2a91").is_ok());
+        assert!(check_plain_message("0xdeadbeef is my favourite word").is_ok());
+        assert!(check_plain_message("").is_err());
+    }
+
+    #[test]
+    fn test_hashes_and_transactions_refused() {
+        let hash = format!("0x{}", "ab".repeat(32));
+        assert!(check_plain_message(&hash).is_err());
+        // EIP-1559 transaction
+        assert!(check_plain_message("0x02f86c0182012a8405f5e100").is_err());
+        // Legacy RLP transaction
+        assert!(check_plain_message("0xf86b808504a817c800825208").is_err());
+    }
+
+    #[test]
+    fn test_typed_data_guard() {
+        let login = json!({
+            "domain": {"name": "Example", "chainId": 8453},
+            "types": {"Login": [{"name": "nonce", "type": "string"}]},
+            "primaryType": "Login",
+            "message": {"nonce": "abc123"}
+        });
+        assert!(check_typed_data(&login).is_ok());
+
+        let permit = json!({"primaryType": "Permit", "message": {}});
+        assert!(check_typed_data(&permit).is_err());
+
+        let sneaky = json!({"primaryType": "Login", "message": {"spender": "0x00"}});
+        assert!(check_typed_data(&sneaky).is_err());
+
+        let prehashed = json!({"primaryType": "Login", "_hash": "0x00"});
+        assert!(check_typed_data(&prehashed).is_err());
+    }
+
+    #[test]
+    fn test_typed_data_guard_checks_nested_structs() {
+        let nested_type = json!({
+            "types": {
+                "Login": [{"name": "grant", "type": "Permit"}],
+                "Permit": [{"name": "owner", "type": "address"}]
+            },
+            "primaryType": "Login",
+            "message": {"grant": {"owner": "0x00"}}
+        });
+        assert!(check_typed_data(&nested_type).unwrap_err().contains("Permit"));
+
+        let nested_field = json!({
+            "primaryType": "Login",
+            "message": {"session": {"grants": [{"spender": "0x00"}]}}
+        });
+        assert!(check_typed_data(&nested_field).unwrap_err().contains("spender"));
+    }
+
+    #[tokio::test]
+    async fn test_partner_mode_queues_until_approved() {
+        let wallet: Arc<dyn WalletProvider> = Arc::new(
+            crate::wallet::EnvWalletProvider::from_private_key(
+                "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+            )
+            .unwrap(),
+        );
+        let mut context = ToolContext::new().with_channel(7, "web".to_string());
+        context.wallet_provider = Some(wallet.clone());
+        let tool = SignMessageTool::new();
+
+        let queued = tool.execute(json!({ "message": "Sign in to example.com" }), &context).await;
+        assert!(queued.success);
+        assert!(!queued.content.contains("Signature:"));
+        let request_id = queued.metadata.as_ref().unwrap()["request_id"].as_str().unwrap().to_string();
+
+        let waiting = tool.execute(json!({ "request_id": request_id }), &context).await;
+        assert!(waiting.content.contains("awaiting"));
+
+        approve_sign_request(&request_id, Some(wallet)).await.unwrap();
+        let signed = tool.execute(json!({ "request_id": request_id }), &context).await;
+        assert!(signed.success);
+        assert!(signed.content.contains("Signature: 0x"));
+        // Approving twice doesn't sign again
+        assert!(approve_sign_request(&request_id, None).await.is_err());
+    }
+}
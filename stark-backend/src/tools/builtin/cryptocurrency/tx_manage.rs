@@ -0,0 +1,533 @@
+//! Manage a pending (possibly stuck) on-chain transaction
+//!
+//! Given a tx hash, reports its status, or signs a replacement with the same
+//! nonce and higher fees:
+//! - `speed_up` resubmits the same transaction (to, value, data)
+//! - `cancel` replaces it with a zero-value self-send
+//!
+//! Replacements are QUEUED like any other transaction and must go through
+//! `broadcast_web3_tx`, so the partner-mode confirmation gate still applies.
+
+use super::verify_intent::{self, TransactionIntent};
+use super::web3_tx::SendEthTool;
+use crate::tools::registry::Tool;
+use crate::tools::rpc_config::{resolve_rpc_from_context, Network};
+use crate::tools::types::{
+    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+};
+use crate::tx_queue::QueuedTransaction;
+use crate::x402::{RpcTransaction, X402EvmRpc};
+use async_trait::async_trait;
+use ethers::prelude::*;
+use ethers::types::transaction::eip1559::Eip1559TransactionRequest;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Nodes reject replacements that don't raise fees by at least ~10%
+const MIN_GAS_BUMP_PERCENT: u64 = 10;
+const DEFAULT_GAS_BUMP_PERCENT: u64 = 20;
+/// Guard against fat-fingered bumps that would overpay massively
+const MAX_GAS_BUMP_PERCENT: u64 = 300;
+
+/// Gas limit for the zero-value self-send used to cancel
+const CANCEL_GAS_LIMIT: u64 = 21_000;
+
+/// Tool to check, speed up, or cancel a pending transaction
+pub struct TxManageTool {
+    definition: ToolDefinition,
+}
+
+impl TxManageTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+
+        properties.insert(
+            "tx_hash".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Hash of the transaction to check or replace (0x-prefixed)".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "action".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "'status' reports where the transaction is; 'speed_up' re-signs it with higher gas; \
+                    'cancel' replaces it with a zero-value self-send at higher gas"
+                    .to_string(),
+                default: Some(json!("status")),
+                items: None,
+                enum_values: Some(vec!["status".to_string(), "speed_up".to_string(), "cancel".to_string()]),
+            },
+        );
+
+        properties.insert(
+            "gas_bump_percent".to_string(),
+            PropertySchema {
+                schema_type: "integer".to_string(),
+                description: format!(
+                    "How much to raise the original fees for speed_up/cancel ({}-{}%, default {}%). \
+                     The current network estimate is used instead if it is higher.",
+                    MIN_GAS_BUMP_PERCENT, MAX_GAS_BUMP_PERCENT, DEFAULT_GAS_BUMP_PERCENT
+                ),
+                default: Some(json!(DEFAULT_GAS_BUMP_PERCENT)),
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "network".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Network: 'base', 'mainnet', or 'polygon'. If not specified, uses the user's selected network from the UI.".to_string(),
+                default: None,
+                items: None,
+                enum_values: Some(vec!["base".to_string(), "mainnet".to_string(), "polygon".to_string()]),
+            },
+        );
+
+        TxManageTool {
+            definition: ToolDefinition {
+                name: "tx_manage".to_string(),
+                description: "Check on a pending transaction by hash, or unstick it: 'speed_up' resubmits it with higher gas, \
+                    'cancel' replaces it with a zero-value self-send at higher gas. Replacements are QUEUED - use \
+                    broadcast_web3_tx with the returned UUID to send them."
+                    .to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec!["tx_hash".to_string()],
+                },
+                group: ToolGroup::Finance,
+                hidden: false,
+            },
+        }
+    }
+}
+
+impl Default for TxManageTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TxManageParams {
+    tx_hash: String,
+    #[serde(default = "default_action")]
+    action: String,
+    #[serde(default = "default_bump")]
+    gas_bump_percent: u64,
+    network: Option<String>,
+}
+
+fn default_action() -> String {
+    "status".to_string()
+}
+
+fn default_bump() -> u64 {
+    DEFAULT_GAS_BUMP_PERCENT
+}
+
+/// Where a transaction currently is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TxState {
+    Confirmed,
+    Reverted,
+    Pending,
+    /// Another transaction with the same nonce was mined
+    Replaced,
+    /// The node doesn't know the hash (dropped, or never propagated)
+    NotFound,
+}
+
+impl TxState {
+    fn as_str(&self) -> &'static str {
+        match self {
+            TxState::Confirmed => "confirmed",
+            TxState::Reverted => "reverted",
+            TxState::Pending => "pending",
+            TxState::Replaced => "replaced",
+            TxState::NotFound => "not_found",
+        }
+    }
+}
+
+/// Raise a fee by `percent`, rounding up so the bump is never below the node's minimum
+fn bump_fee(fee: U256, percent: u64) -> U256 {
+    (fee * U256::from(100 + percent) + U256::from(99u64)) / U256::from(100u64)
+}
+
+/// Fees for the replacement: the original fees bumped, or the current estimate if higher.
+/// Legacy transactions only carry `gas_price`, which stands in for both fees.
+fn replacement_fees(original: &RpcTransaction, current: (U256, U256), percent: u64) -> (U256, U256) {
+    let old_max = original.max_fee_per_gas.or(original.gas_price).unwrap_or_default();
+    let old_priority = original.max_priority_fee_per_gas.or(original.gas_price).unwrap_or_default();
+
+    let priority = std::cmp::max(bump_fee(old_priority, percent), current.1);
+    let max_fee = std::cmp::max(std::cmp::max(bump_fee(old_max, percent), current.0), priority);
+    (max_fee, priority)
+}
+
+/// Resolve the network from params, context, or default
+fn resolve_network(param_network: Option<&str>, context_network: Option<&str>) -> Result<Network, String> {
+    let network_str = param_network.or(context_network).unwrap_or("base");
+    Network::from_str(network_str)
+        .map_err(|_| format!("Invalid network '{}'. Must be one of: base, mainnet, polygon", network_str))
+}
+
+impl TxManageTool {
+    async fn lookup_state(
+        rpc: &X402EvmRpc,
+        hash: H256,
+    ) -> Result<(TxState, Option<RpcTransaction>, Option<U64>), String> {
+        if let Some(receipt) = rpc.get_transaction_receipt(hash).await? {
+            let state = if receipt.status == Some(U64::from(1)) {
+                TxState::Confirmed
+            } else {
+                TxState::Reverted
+            };
+            let tx = rpc.get_transaction_by_hash(hash).await.ok().flatten();
+            return Ok((state, tx, receipt.block_number));
+        }
+
+        let tx = match rpc.get_transaction_by_hash(hash).await? {
+            Some(tx) => tx,
+            None => return Ok((TxState::NotFound, None, None)),
+        };
+        // A mined nonce at or above ours means this hash can no longer confirm
+        let mined_nonce = rpc.get_confirmed_transaction_count(tx.from).await?;
+        let state = if mined_nonce > tx.nonce {
+            TxState::Replaced
+        } else {
+            TxState::Pending
+        };
+        Ok((state, Some(tx), None))
+    }
+}
+
+#[async_trait]
+impl Tool for TxManageTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: TxManageParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        let hash: H256 = match params.tx_hash.trim().parse() {
+            Ok(h) => h,
+            Err(_) => return ToolResult::error(format!("Invalid transaction hash: {}", params.tx_hash)),
+        };
+        let replace = match params.action.as_str() {
+            "status" => false,
+            "speed_up" | "cancel" => true,
+            other => {
+                return ToolResult::error(format!(
+                    "Unknown action '{}'. Use 'status', 'speed_up', or 'cancel'.",
+                    other
+                ))
+            }
+        };
+        if replace && !(MIN_GAS_BUMP_PERCENT..=MAX_GAS_BUMP_PERCENT).contains(&params.gas_bump_percent) {
+            return ToolResult::error(format!(
+                "gas_bump_percent must be between {} and {}",
+                MIN_GAS_BUMP_PERCENT, MAX_GAS_BUMP_PERCENT
+            ));
+        }
+
+        let network = match resolve_network(params.network.as_deref(), context.selected_network.as_deref()) {
+            Ok(n) => n,
+            Err(e) => return ToolResult::error(e),
+        };
+
+        // Same restriction as the other signing tools
+        if replace {
+            let is_gateway_channel = context
+                .channel_type
+                .as_ref()
+                .map(|ct| {
+                    let ct_lower = ct.to_lowercase();
+                    ct_lower == "discord" || ct_lower == "telegram" || ct_lower == "slack"
+                })
+                .unwrap_or(false);
+            let is_rogue_mode = context
+                .extra
+                .get("rogue_mode_enabled")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            if is_gateway_channel && !is_rogue_mode {
+                return ToolResult::error(
+                    "Transactions cannot be executed in Discord/Telegram/Slack channels unless Rogue Mode is enabled.",
+                );
+            }
+        }
+
+        let wallet_provider = match &context.wallet_provider {
+            Some(wp) => wp,
+            None => return ToolResult::error("Wallet not configured."),
+        };
+        let rpc_config = resolve_rpc_from_context(&context.extra, network.as_ref());
+        let rpc = match X402EvmRpc::new_with_wallet_provider(
+            wallet_provider.clone(),
+            network.as_ref(),
+            Some(rpc_config.url.clone()),
+            rpc_config.use_x402,
+        ) {
+            Ok(r) => r,
+            Err(e) => return ToolResult::error(format!("Failed to initialize RPC: {}", e)),
+        };
+
+        let (state, tx, block) = match Self::lookup_state(&rpc, hash).await {
+            Ok(s) => s,
+            Err(e) => return ToolResult::error(format!("Failed to look up transaction: {}", e)),
+        };
+        let tx_hash_str = format!("{:?}", hash);
+        let explorer_url = format!("{}/tx/{}", network.explorer_url(), tx_hash_str);
+
+        if !replace || state != TxState::Pending {
+            let mut msg = format!(
+                "Transaction {} on {}: {}\nExplorer: {}\n",
+                tx_hash_str,
+                network,
+                state.as_str().to_uppercase(),
+                explorer_url
+            );
+            if let Some(tx) = &tx {
+                msg.push_str(&format!("Nonce: {}\n", tx.nonce));
+                if let Some(fee) = tx.max_fee_per_gas.or(tx.gas_price) {
+                    msg.push_str(&format!("Max Fee: {}\n", SendEthTool::format_gwei(&fee.to_string())));
+                }
+            }
+            if let Some(b) = block {
+                msg.push_str(&format!("Block: {}\n", b));
+            }
+            match state {
+                TxState::Pending => msg.push_str(
+                    "\nStill waiting to be mined. If it stays stuck, use action 'speed_up' or 'cancel'.",
+                ),
+                TxState::Replaced => msg.push_str("\nAnother transaction with the same nonce was mined; this one will never confirm."),
+                TxState::NotFound => msg.push_str("\nThe node doesn't know this transaction. It may have been dropped, or be on another network."),
+                _ => {}
+            }
+            let result = json!({
+                "tx_hash": tx_hash_str,
+                "network": network.to_string(),
+                "status": state.as_str(),
+                "nonce": tx.as_ref().map(|t| t.nonce.as_u64()),
+                "block_number": block.map(|b| b.as_u64()),
+                "explorer_url": explorer_url,
+            });
+            return if replace {
+                ToolResult::error(format!(
+                    "Only pending transactions can be replaced.\n\n{}",
+                    msg
+                ))
+                .with_metadata(result)
+            } else {
+                ToolResult::success(msg).with_metadata(result)
+            };
+        }
+
+        // Pending and a replacement was requested
+        let original = match tx {
+            Some(tx) => tx,
+            None => return ToolResult::error("Transaction details unavailable"),
+        };
+        let from_str = wallet_provider.get_address();
+        let from_address: Address = match from_str.parse() {
+            Ok(a) => a,
+            Err(_) => return ToolResult::error(format!("Invalid wallet address: {}", from_str)),
+        };
+        if original.from != from_address {
+            return ToolResult::error(format!(
+                "Transaction was sent by {:?}, not this wallet ({}); only our own transactions can be replaced.",
+                original.from, from_str
+            ));
+        }
+
+        let current_fees = match rpc.estimate_eip1559_fees().await {
+            Ok(f) => f,
+            Err(e) => return ToolResult::error(format!("Failed to estimate current gas prices: {}", e)),
+        };
+        let (max_fee, priority_fee) = replacement_fees(&original, current_fees, params.gas_bump_percent);
+
+        let cancel = params.action == "cancel";
+        let (to, value, data, gas) = if cancel {
+            (from_address, U256::zero(), Bytes::default(), U256::from(CANCEL_GAS_LIMIT))
+        } else {
+            match original.to {
+                Some(to) => (to, original.value, original.input.clone(), original.gas),
+                None => return ToolResult::error("Contract deployments can't be sped up with this tool; use 'cancel' instead."),
+            }
+        };
+
+        let tx_request = Eip1559TransactionRequest::new()
+            .from(from_address)
+            .to(to)
+            .value(value)
+            .data(data.clone())
+            .nonce(original.nonce)
+            .gas(gas)
+            .max_fee_per_gas(max_fee)
+            .max_priority_fee_per_gas(priority_fee)
+            .chain_id(network.chain_id());
+        let typed_tx: TypedTransaction = tx_request.into();
+
+        let to_str = format!("{:?}", to);
+        let intent = TransactionIntent {
+            tx_type: if cancel { "tx_cancel" } else { "tx_speed_up" }.to_string(),
+            to: to_str.clone(),
+            value: value.to_string(),
+            value_display: SendEthTool::format_eth(&value.to_string()),
+            network: network.to_string(),
+            function_name: None,
+            abi_name: None,
+            preset_name: None,
+            destination_chain: None,
+            calldata: (!data.is_empty()).then(|| format!("0x{}", hex::encode(&data))),
+            description: if cancel {
+                format!("Cancel pending transaction {} (nonce {}) on {}", tx_hash_str, original.nonce, network)
+            } else {
+                format!("Speed up pending transaction {} (nonce {}) on {} with higher gas", tx_hash_str, original.nonce, network)
+            },
+        };
+        if let Err(reason) = verify_intent::verify_intent(&intent, context, None).await {
+            return ToolResult::error(reason);
+        }
+
+        let signature = match wallet_provider.sign_transaction(&typed_tx).await {
+            Ok(s) => s,
+            Err(e) => return ToolResult::error(format!("Failed to sign transaction: {}", e)),
+        };
+        let signed_tx_hex = format!("0x{}", hex::encode(typed_tx.rlp_signed(&signature)));
+
+        let tx_queue = match &context.tx_queue {
+            Some(q) => q,
+            None => return ToolResult::error("Transaction queue not available."),
+        };
+        let uuid = Uuid::new_v4().to_string();
+        let queued_tx = QueuedTransaction::new(
+            uuid.clone(),
+            network.to_string(),
+            from_str.clone(),
+            to_str.clone(),
+            value.to_string(),
+            format!("0x{}", hex::encode(&data)),
+            gas.to_string(),
+            max_fee.to_string(),
+            priority_fee.to_string(),
+            original.nonce.as_u64(),
+            signed_tx_hex,
+            context.channel_id,
+        )
+        .with_preset(Some(if cancel { "tx_cancel" } else { "tx_speed_up" }));
+        tx_queue.queue(queued_tx);
+
+        log::info!(
+            "[tx_manage] Queued {} replacement for {} (nonce {}) as {}",
+            params.action, tx_hash_str, original.nonce, uuid
+        );
+
+        let old_fee = original.max_fee_per_gas.or(original.gas_price).unwrap_or_default();
+        let mut msg = String::new();
+        msg.push_str(if cancel {
+            "CANCELLATION QUEUED (not yet broadcast)\n\n"
+        } else {
+            "SPEED-UP QUEUED (not yet broadcast)\n\n"
+        });
+        msg.push_str(&format!("UUID: {}\n", uuid));
+        msg.push_str(&format!("Replaces: {}\n", tx_hash_str));
+        msg.push_str(&format!("Network: {}\n", network));
+        msg.push_str(&format!("Nonce: {}\n", original.nonce));
+        msg.push_str(&format!("To: {}\n", to_str));
+        msg.push_str(&format!("Value: {}\n", SendEthTool::format_eth(&value.to_string())));
+        msg.push_str(&format!(
+            "Max Fee: {} -> {}\n",
+            SendEthTool::format_gwei(&old_fee.to_string()),
+            SendEthTool::format_gwei(&max_fee.to_string())
+        ));
+        msg.push_str(&format!("Priority Fee: {}\n", SendEthTool::format_gwei(&priority_fee.to_string())));
+        msg.push_str("\n--- Next Steps ---\n");
+        msg.push_str(&format!("To broadcast: use `broadcast_web3_tx` with uuid: {}\n", uuid));
+        msg.push_str("Whichever of the two transactions is mined first wins; the other is discarded.");
+
+        ToolResult::success(msg).with_metadata(json!({
+            "uuid": uuid,
+            "status": "queued",
+            "action": params.action,
+            "replaces": tx_hash_str,
+            "network": network.to_string(),
+            "nonce": original.nonce.as_u64(),
+            "to": to_str,
+            "value": value.to_string(),
+            "gas_limit": gas.to_string(),
+            "max_fee_per_gas": max_fee.to_string(),
+            "max_priority_fee_per_gas": priority_fee.to_string(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending_tx(max_fee: Option<u64>, priority: Option<u64>, gas_price: Option<u64>) -> RpcTransaction {
+        RpcTransaction {
+            hash: H256::zero(),
+            nonce: U256::from(5u64),
+            from: Address::zero(),
+            to: Some(Address::zero()),
+            value: U256::zero(),
+            input: Bytes::default(),
+            gas: U256::from(21_000u64),
+            gas_price: gas_price.map(U256::from),
+            max_fee_per_gas: max_fee.map(U256::from),
+            max_priority_fee_per_gas: priority.map(U256::from),
+            block_number: None,
+        }
+    }
+
+    #[test]
+    fn test_bump_fee_rounds_up() {
+        assert_eq!(bump_fee(U256::from(100u64), 10), U256::from(110u64));
+        assert_eq!(bump_fee(U256::from(101u64), 10), U256::from(112u64));
+        assert_eq!(bump_fee(U256::zero(), 20), U256::zero());
+    }
+
+    #[test]
+    fn test_replacement_fees_bumps_original() {
+        let tx = pending_tx(Some(1_000), Some(100), None);
+        let (max_fee, priority) = replacement_fees(&tx, (U256::from(500u64), U256::from(50u64)), 20);
+        assert_eq!(max_fee, U256::from(1_200u64));
+        assert_eq!(priority, U256::from(120u64));
+    }
+
+    #[test]
+    fn test_replacement_fees_uses_higher_network_estimate() {
+        let tx = pending_tx(Some(1_000), Some(100), None);
+        let (max_fee, priority) = replacement_fees(&tx, (U256::from(5_000u64), U256::from(300u64)), 20);
+        assert_eq!(max_fee, U256::from(5_000u64));
+        assert_eq!(priority, U256::from(300u64));
+    }
+
+    #[test]
+    fn test_replacement_fees_legacy_tx() {
+        let tx = pending_tx(None, None, Some(1_000));
+        let (max_fee, priority) = replacement_fees(&tx, (U256::zero(), U256::zero()), 10);
+        assert_eq!(max_fee, U256::from(1_100u64));
+        assert_eq!(priority, U256::from(1_100u64));
+    }
+}
@@ -23,27 +23,28 @@ mod web_fetch;
 
 // Re-exports from submodules
 pub use bash::{
-    ApplyPatchTool, ClaudeCodeRemoteTool, DeleteFileTool, EditFileTool, ExecTool, GitTool,
+    ApplyPatchTool, ClaudeCodeRemoteTool, DeleteFileTool, DiffTool, EditFileTool, ExecTool, GitTool,
     GlobTool, GrepTool, ListFilesTool, ReadFileTool, ReadSymbolTool, RenameFileTool, WriteFileTool,
 };
 pub use code::{CommitterTool, DeployTool, IndexProjectTool, PrQualityTool, VerifyChangesTool};
 pub use core::{
-    AddTaskTool, DefineTasksTool, AgentSendTool, ApiKeysCheckTool, AskUserTool, HeartbeatConfigTool,
-    ImportIdentityTool, InstallApiKeyTool, ManageModulesTool, ManageSkillsTool, MindmapManageTool,
-    ReadSkillTool, RegisterNewIdentityTool, WorkstreamTool, ModifySoulTool, SayToUserTool,
-    SetAgentSubtypeTool, SubagentStatusTool, SubagentTool, TaskFullyCompletedTool,
+    AddTaskTool, DefineTasksTool, AgentSendTool, ApiKeysCheckTool, AskUserTool, BroadcastAnnouncementTool,
+    HeartbeatConfigTool,
+    ImportIdentityTool, InstallApiKeyTool, ListCapabilitiesTool, ManageApiKeyTool, ManageModulesTool, ManageSkillsTool, MindmapManageTool,
+    ReadSkillTool, RegisterNewIdentityTool, SetReminderTool, ListRemindersTool, CancelReminderTool, ListScheduledTool, WorkstreamTool, ModifySoulTool, SayToUserTool, ScheduleMessageTool,
+    SetAgentSubtypeTool, SubagentStatusTool, SubagentTool, SummarizeTool, TaskFullyCompletedTool, ValidationCheckTool,
     // Meta tools (self-management)
-    CloudBackupTool, ManageGatewayChannelsTool, ReadOperatingModeTool, ReadRecentTransactionsTool,
+    CloudBackupTool, ConfigSnapshotTool, ManageGatewayChannelsTool, ReadOperatingModeTool, ReadRecentTransactionsTool,
     SetThemeAccentTool,
 };
 pub use cryptocurrency::{
     load_networks, load_tokens, BridgeUsdcTool, BroadcastWeb3TxTool, DecodeCalldataTool,
-    DexScreenerTool, Erc8128FetchTool, GeckoTerminalTool, ListQueuedWeb3TxTool, PolymarketTradeTool,
-    SelectWeb3NetworkTool, SendEthTool, SetAddressTool, SiwaAuthTool, ToRawAmountTool, TokenLookupTool,
-    VerifyTxBroadcastTool, Web3PresetFunctionCallTool, X402AgentInvokeTool, X402FetchTool,
+    DexScreenerTool, Erc8128FetchTool, EstimateGasTool, GeckoTerminalTool, ListQueuedWeb3TxTool, PolymarketTradeTool,
+    PriceLookupTool, SelectWeb3NetworkTool, SendEthTool, SetAddressTool, SignMessageTool, SiwaAuthTool, ToRawAmountTool, TokenLookupTool,
+    TxManageTool, VerifyTxBroadcastTool, Web3PresetFunctionCallTool, X402AgentInvokeTool, X402FetchTool,
     X402PostTool, X402RpcTool,
 };
-pub use social_media::{DiscordLookupTool, DiscordReadTool, DiscordWriteTool, FigmaTool, GithubUserTool, TelegramReadTool, TwitterPostTool};
+pub use social_media::{DiscordLookupTool, DiscordPinTool, DiscordReadTool, DiscordWriteTool, FigmaTool, GithubUserTool, TelegramReadTool, TwitterPostTool};
 
 // Re-exports from individual tools
 pub use local_rpc::LocalRpcTool;
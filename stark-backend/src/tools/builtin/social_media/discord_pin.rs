@@ -0,0 +1,105 @@
+use crate::channels::discord_pins;
+use crate::tools::registry::Tool;
+use crate::tools::types::{
+    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Tool for requesting that the agent's final response be pinned in the Discord channel.
+/// The pin itself happens in the Discord listener once the response has been sent.
+pub struct DiscordPinTool {
+    definition: ToolDefinition,
+}
+
+impl DiscordPinTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+
+        properties.insert(
+            "reason".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Why this response is worth pinning (e.g. 'transaction confirmation')".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        DiscordPinTool {
+            definition: ToolDefinition {
+                name: discord_pins::PIN_RESPONSE_TOOL.to_string(),
+                description: "Pin your final response in the current Discord channel. Use sparingly, only for information \
+                    people will need to find again (transaction confirmations, addresses, decisions). \
+                    Only available where pinning is enabled for the channel."
+                    .to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec![],
+                },
+                group: ToolGroup::Messaging,
+                hidden: false,
+            },
+        }
+    }
+}
+
+impl Default for DiscordPinTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DiscordPinParams {
+    #[serde(default)]
+    reason: Option<String>,
+}
+
+#[async_trait]
+impl Tool for DiscordPinTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: DiscordPinParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        if context.channel_type.as_deref() != Some("discord") {
+            return ToolResult::error("Pinning is only available in Discord channels");
+        }
+        let (db, channel_id) = match (&context.database, context.channel_id) {
+            (Some(db), Some(id)) => (db, id),
+            _ => return ToolResult::error("No Discord channel in context"),
+        };
+        if !discord_pins::auto_pin_enabled(db, channel_id) {
+            return ToolResult::error(
+                "Pinning is disabled for this channel. An admin can enable 'Pin Important Responses' in the channel settings.",
+            );
+        }
+
+        ToolResult::success("Your final response will be pinned in this channel once it is sent.")
+            .with_metadata(json!({ "reason": params.reason }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_rejects_non_discord_channels() {
+        let mut ctx = ToolContext::default();
+        ctx.channel_type = Some("telegram".to_string());
+        let result = DiscordPinTool::new().execute(json!({}), &ctx).await;
+        assert!(!result.success);
+        assert!(result.content.contains("only available in Discord"));
+    }
+}
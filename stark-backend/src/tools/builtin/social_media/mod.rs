@@ -3,6 +3,7 @@
 //! Tools for interacting with Twitter, Discord, GitHub, and other platforms.
 
 mod discord_lookup;
+mod discord_pin;
 mod discord_read;
 mod discord_write;
 mod figma;
@@ -13,12 +14,13 @@ mod twitter_post;
 pub mod twitter_oauth;
 
 pub use discord_lookup::DiscordLookupTool;
+pub use discord_pin::DiscordPinTool;
 pub use figma::FigmaTool;
 pub use discord_read::DiscordReadTool;
 pub use discord_write::DiscordWriteTool;
 pub use github_user::GithubUserTool;
 pub use twitter_oauth::{
-    check_subscription_tier, generate_oauth_header, percent_encode, TwitterCredentials,
+    check_subscription_tier, generate_oauth_header, percent_encode, verify_credentials, TwitterAuth, TwitterCredentials,
     XSubscriptionTier, TWITTER_MAX_CHARS, TWITTER_PREMIUM_MAX_CHARS,
 };
 pub use telegram_read::TelegramReadTool;
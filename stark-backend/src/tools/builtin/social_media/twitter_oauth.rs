@@ -1,7 +1,20 @@
-//! Twitter OAuth 1.0a utilities for API authentication
+//! Twitter OAuth utilities for API authentication
 //!
 //! Provides shared OAuth functionality for Twitter API v2 access,
 //! used by both the TwitterPostTool and the Twitter mention listener.
+//!
+//! Two auth modes are supported, see [`TwitterAuth`]:
+//!
+//! | Endpoint                          | OAuth 1.0a | OAuth 2.0 user token        |
+//! |-----------------------------------|------------|-----------------------------|
+//! | `POST /2/tweets`                  | yes        | yes (`tweet.write`)         |
+//! | `GET /2/users/me`                 | yes        | yes (`users.read`)          |
+//! | `POST 1.1/media/upload.json`      | yes        | no                          |
+//! | Mention listener (search, reply)  | yes        | no (channel uses 1.0a only) |
+//!
+//! The OAuth 2.0 token must be a user-context token from the Authorization Code
+//! with PKCE flow; an app-only bearer token can't post. Such tokens expire (after
+//! two hours unless refreshed), so a 401 usually means it needs replacing.
 
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use hmac::{Hmac, Mac};
@@ -145,6 +158,39 @@ pub fn generate_oauth_header(
     format!("OAuth {}", auth_string)
 }
 
+/// How requests to the Twitter API are authorized
+#[derive(Debug, Clone)]
+pub enum TwitterAuth {
+    /// Per-request HMAC-SHA1 signing with the four OAuth 1.0a keys
+    OAuth1(TwitterCredentials),
+    /// OAuth 2.0 user access token, sent as `Authorization: Bearer`
+    Bearer(String),
+}
+
+impl TwitterAuth {
+    /// Authorization header value for a request. `extra_params` only matter for
+    /// OAuth 1.0a, where query and form parameters are part of the signature.
+    pub fn authorization_header(
+        &self,
+        method: &str,
+        url: &str,
+        extra_params: Option<&[(&str, &str)]>,
+    ) -> String {
+        match self {
+            Self::OAuth1(credentials) => generate_oauth_header(method, url, credentials, extra_params),
+            Self::Bearer(token) => format!("Bearer {}", token),
+        }
+    }
+
+    /// Short name for logs and error messages
+    pub fn mode(&self) -> &'static str {
+        match self {
+            Self::OAuth1(_) => "OAuth 1.0a",
+            Self::Bearer(_) => "OAuth 2.0",
+        }
+    }
+}
+
 /// Maximum characters per tweet (standard / free accounts)
 pub const TWITTER_MAX_CHARS: usize = 280;
 
@@ -196,15 +242,47 @@ struct UsersMeData {
     subscription_type: Option<String>,
 }
 
+#[derive(serde::Deserialize)]
+struct VerifyResponse {
+    data: Option<VerifyUser>,
+}
+
+#[derive(serde::Deserialize)]
+struct VerifyUser {
+    username: String,
+}
+
+/// Verify credentials via GET /2/users/me, returning the account's username
+pub async fn verify_credentials(client: &reqwest::Client, auth: &TwitterAuth) -> Result<String, String> {
+    let url = "https://api.twitter.com/2/users/me";
+    let auth_header = auth.authorization_header("GET", url, None);
+
+    let response = client
+        .get(url)
+        .header("Authorization", auth_header)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("Twitter rejected the credentials ({})", status));
+    }
+
+    let body = response.text().await.unwrap_or_default();
+    serde_json::from_str::<VerifyResponse>(&body)
+        .ok()
+        .and_then(|r| r.data)
+        .map(|u| u.username)
+        .ok_or_else(|| "No user data returned".to_string())
+}
+
 /// Check the authenticated user's X subscription tier via GET /2/users/me.
 /// Returns the tier on success, or falls back to `None` (basic/free) on any error.
-pub async fn check_subscription_tier(
-    client: &reqwest::Client,
-    credentials: &TwitterCredentials,
-) -> XSubscriptionTier {
+pub async fn check_subscription_tier(client: &reqwest::Client, auth: &TwitterAuth) -> XSubscriptionTier {
     let base_url = "https://api.twitter.com/2/users/me";
     let query_params = [("user.fields", "subscription_type")];
-    let auth_header = generate_oauth_header("GET", base_url, credentials, Some(&query_params));
+    let auth_header = auth.authorization_header("GET", base_url, Some(&query_params));
 
     let result = client
         .get(format!("{}?user.fields=subscription_type", base_url))
@@ -246,6 +324,45 @@ pub async fn check_subscription_tier(
     }
 }
 
+#[derive(serde::Deserialize)]
+struct MediaUploadResponse {
+    media_id_string: String,
+}
+
+/// Upload an image via the v1.1 simple media upload, returning its `media_id_string`.
+///
+/// Uses the form-encoded `media_data` variant so the base64 body is covered by
+/// the OAuth 1.0a signature like any other request parameter. This endpoint
+/// doesn't accept OAuth 2.0 tokens, hence the 1.0a-only credentials.
+pub async fn upload_media(
+    client: &reqwest::Client,
+    credentials: &TwitterCredentials,
+    data: &[u8],
+) -> Result<String, String> {
+    let url = "https://upload.twitter.com/1.1/media/upload.json";
+    let media_data = BASE64.encode(data);
+    let form = [("media_data", media_data.as_str())];
+    let auth_header = generate_oauth_header("POST", url, credentials, Some(&form));
+
+    let response = client
+        .post(url)
+        .header("Authorization", auth_header)
+        .form(&form)
+        .send()
+        .await
+        .map_err(|e| format!("Media upload request failed: {}", e))?;
+
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    if !status.is_success() {
+        return Err(format!("Media upload failed ({}): {}", status, body));
+    }
+
+    serde_json::from_str::<MediaUploadResponse>(&body)
+        .map(|r| r.media_id_string)
+        .map_err(|e| format!("Failed to parse media upload response: {}", e))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -268,4 +385,24 @@ mod tests {
         assert_eq!(creds.consumer_key, "key");
         assert_eq!(creds.consumer_secret, "secret");
     }
+
+    #[test]
+    fn test_authorization_header_per_mode() {
+        let bearer = TwitterAuth::Bearer("abc123".to_string());
+        assert_eq!(
+            bearer.authorization_header("POST", "https://api.twitter.com/2/tweets", None),
+            "Bearer abc123"
+        );
+
+        let oauth1 = TwitterAuth::OAuth1(TwitterCredentials::new(
+            "key".to_string(),
+            "secret".to_string(),
+            "token".to_string(),
+            "token_secret".to_string(),
+        ));
+        let header = oauth1.authorization_header("POST", "https://api.twitter.com/2/tweets", None);
+        assert!(header.starts_with("OAuth "));
+        assert!(header.contains("oauth_consumer_key=\"key\""));
+        assert!(header.contains("oauth_signature="));
+    }
 }
@@ -1,9 +1,12 @@
-//! Twitter posting tool using OAuth 1.0a
+//! Twitter posting tool
 //!
-//! Posts tweets on behalf of a user using their OAuth 1.0a credentials.
+//! Posts tweets on behalf of a user with their OAuth 2.0 access token when one is
+//! configured, otherwise with their OAuth 1.0a credentials. Image uploads always
+//! use OAuth 1.0a; see `twitter_oauth` for which endpoints each mode supports.
 
+use super::super::web_fetch::validate_public_url;
 use super::twitter_oauth::{
-    check_subscription_tier, generate_oauth_header, TwitterCredentials, TWITTER_MAX_CHARS,
+    check_subscription_tier, upload_media, TwitterAuth, TwitterCredentials, TWITTER_MAX_CHARS,
 };
 use crate::controllers::api_keys::ApiKeyId;
 use crate::tools::registry::Tool;
@@ -15,6 +18,15 @@ use serde::Deserialize;
 use serde_json::{json, Value};
 use std::collections::HashMap;
 
+/// Twitter allows at most 4 images per tweet
+const MAX_TWEET_MEDIA: usize = 4;
+
+/// Size limit for images on the simple upload endpoint
+const MAX_MEDIA_BYTES: usize = 5 * 1024 * 1024;
+
+/// Image formats accepted by the simple upload endpoint
+const SUPPORTED_MEDIA_TYPES: &[&str] = &["image/jpeg", "image/png", "image/gif", "image/webp"];
+
 /// Tool for posting tweets via Twitter API v2
 pub struct TwitterPostTool {
     definition: ToolDefinition,
@@ -28,7 +40,7 @@ impl TwitterPostTool {
             "text".to_string(),
             PropertySchema {
                 schema_type: "string".to_string(),
-                description: "The text content of the tweet".to_string(),
+                description: "The text content of the tweet. Omit when posting a thread.".to_string(),
                 default: None,
                 items: None,
                 enum_values: None,
@@ -57,14 +69,51 @@ impl TwitterPostTool {
             },
         );
 
+        properties.insert(
+            "media_urls".to_string(),
+            PropertySchema {
+                schema_type: "array".to_string(),
+                description: format!(
+                    "Optional: Up to {} public image URLs (JPEG, PNG, GIF or WebP, max 5MB each) to attach to the tweet.",
+                    MAX_TWEET_MEDIA
+                ),
+                default: None,
+                items: Some(Box::new(PropertySchema {
+                    schema_type: "string".to_string(),
+                    description: "Public http(s) URL of an image".to_string(),
+                    default: None,
+                    items: None,
+                    enum_values: None,
+                })),
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "thread".to_string(),
+            PropertySchema {
+                schema_type: "array".to_string(),
+                description: "Optional: Post a thread instead of a single tweet. The first entry is posted as a normal tweet (with any reply_to, quote_tweet_id and media_urls), each following entry as a reply to the previous one. Use instead of text.".to_string(),
+                default: None,
+                items: Some(Box::new(PropertySchema {
+                    schema_type: "string".to_string(),
+                    description: "Text of one tweet in the thread".to_string(),
+                    default: None,
+                    items: None,
+                    enum_values: None,
+                })),
+                enum_values: None,
+            },
+        );
+
         TwitterPostTool {
             definition: ToolDefinition {
                 name: "twitter_post".to_string(),
-                description: "Post a tweet to Twitter/X. Requires Twitter OAuth credentials to be configured in Settings > API Keys.".to_string(),
+                description: "Post a tweet to Twitter/X. Requires a Twitter OAuth 2.0 access token or OAuth 1.0a credentials to be configured in Settings > API Keys.".to_string(),
                 input_schema: ToolInputSchema {
                     schema_type: "object".to_string(),
                     properties,
-                    required: vec!["text".to_string()],
+                    required: vec![],
                 },
                 group: ToolGroup::Messaging,
                 hidden: false,
@@ -75,6 +124,83 @@ impl TwitterPostTool {
     fn get_credential(&self, key_id: ApiKeyId, context: &ToolContext) -> Option<String> {
         context.get_api_key_by_id(key_id).filter(|k| !k.is_empty())
     }
+
+    /// The four OAuth 1.0a keys, or the names of those that are missing
+    fn oauth1_credentials(&self, context: &ToolContext) -> Result<TwitterCredentials, Vec<&'static str>> {
+        let keys = [
+            ApiKeyId::TwitterConsumerKey,
+            ApiKeyId::TwitterConsumerSecret,
+            ApiKeyId::TwitterAccessToken,
+            ApiKeyId::TwitterAccessTokenSecret,
+        ];
+        match keys.map(|id| self.get_credential(id, context)) {
+            [Some(consumer_key), Some(consumer_secret), Some(access_token), Some(access_token_secret)] => Ok(
+                TwitterCredentials::new(consumer_key, consumer_secret, access_token, access_token_secret),
+            ),
+            values => Err(keys
+                .iter()
+                .zip(values)
+                .filter(|(_, v)| v.is_none())
+                .map(|(id, _)| id.as_str())
+                .collect()),
+        }
+    }
+
+    /// Download an image and check it is something Twitter will accept
+    async fn fetch_media(client: &reqwest::Client, raw_url: &str) -> Result<Vec<u8>, String> {
+        let url = url::Url::parse(raw_url).map_err(|e| format!("Invalid media URL '{}': {}", raw_url, e))?;
+        if url.scheme() != "http" && url.scheme() != "https" {
+            return Err(format!("Media URL must be http or https: {}", raw_url));
+        }
+        validate_public_url(&url).map_err(|e| format!("Media URL '{}' rejected: {}", raw_url, e))?;
+
+        let response = client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to download media '{}': {}", raw_url, e))?;
+        if !response.status().is_success() {
+            return Err(format!("Failed to download media '{}' ({})", raw_url, response.status()));
+        }
+        if response.content_length().map_or(false, |len| len as usize > MAX_MEDIA_BYTES) {
+            return Err(format!("Media '{}' exceeds the 5MB image limit", raw_url));
+        }
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string());
+        let data = response
+            .bytes()
+            .await
+            .map_err(|e| format!("Failed to read media '{}': {}", raw_url, e))?;
+        if data.len() > MAX_MEDIA_BYTES {
+            return Err(format!("Media '{}' exceeds the 5MB image limit", raw_url));
+        }
+
+        check_media_type(content_type.as_deref())
+            .map_err(|e| format!("Media '{}': {}", raw_url, e))?;
+        Ok(data.to_vec())
+    }
+}
+
+/// Accept only the image formats supported by the simple media upload
+fn check_media_type(content_type: Option<&str>) -> Result<(), String> {
+    let mime = content_type
+        .and_then(|ct| ct.split(';').next())
+        .map(|ct| ct.trim().to_ascii_lowercase())
+        .unwrap_or_default();
+    if SUPPORTED_MEDIA_TYPES.contains(&mime.as_str()) {
+        Ok(())
+    } else if mime.is_empty() {
+        Err("server did not report a content type".to_string())
+    } else {
+        Err(format!(
+            "unsupported content type '{}' (supported: {})",
+            mime,
+            SUPPORTED_MEDIA_TYPES.join(", ")
+        ))
+    }
 }
 
 impl Default for TwitterPostTool {
@@ -85,9 +211,14 @@ impl Default for TwitterPostTool {
 
 #[derive(Debug, Deserialize)]
 struct TwitterPostParams {
+    #[serde(default)]
     text: String,
     reply_to: Option<String>,
     quote_tweet_id: Option<String>,
+    #[serde(default)]
+    media_urls: Vec<String>,
+    #[serde(default)]
+    thread: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -121,72 +252,82 @@ impl Tool for TwitterPostTool {
             Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
         };
 
-        // Validate tweet text is not empty
-        if params.text.is_empty() {
-            return ToolResult::error("Tweet text cannot be empty");
-        }
-
-        // Get all 4 OAuth credentials
-        let consumer_key = match self.get_credential(ApiKeyId::TwitterConsumerKey, context) {
-            Some(k) => k,
-            None => {
+        // Either a single tweet (`text`) or a thread, never both
+        let is_thread = !params.thread.is_empty();
+        let tweets: Vec<String> = match (params.text.is_empty(), is_thread) {
+            (false, false) => vec![params.text.clone()],
+            (true, true) => params.thread.clone(),
+            (true, false) => return ToolResult::error("Tweet text cannot be empty"),
+            (false, true) => {
                 return ToolResult::error(
-                    "TWITTER_CONSUMER_KEY not configured. Add it in Settings > API Keys.",
+                    "Provide either text or thread, not both. Put the first tweet of a thread in thread[0].",
                 )
             }
         };
+        if let Some(index) = tweets.iter().position(|t| t.trim().is_empty()) {
+            return ToolResult::error(format!("Thread tweet at index {} is empty", index));
+        }
 
-        let consumer_secret = match self.get_credential(ApiKeyId::TwitterConsumerSecret, context) {
-            Some(k) => k,
-            None => {
-                return ToolResult::error(
-                    "TWITTER_CONSUMER_SECRET not configured. Add it in Settings > API Keys.",
-                )
+        if params.media_urls.len() > MAX_TWEET_MEDIA {
+            return ToolResult::error(format!(
+                "At most {} images can be attached to a tweet, got {}",
+                MAX_TWEET_MEDIA,
+                params.media_urls.len()
+            ));
+        }
+
+        // Prefer the OAuth 2.0 token for posting; the 1.0a keys are still needed
+        // for image uploads and are the fallback when no token is set
+        let oauth1 = self.oauth1_credentials(context);
+        let auth = match (self.get_credential(ApiKeyId::TwitterOauth2AccessToken, context), &oauth1) {
+            (Some(token), _) => TwitterAuth::Bearer(token),
+            (None, Ok(credentials)) => TwitterAuth::OAuth1(credentials.clone()),
+            (None, Err(missing)) => {
+                return ToolResult::error(format!(
+                    "Twitter credentials not configured. Add TWITTER_OAUTH2_ACCESS_TOKEN, or the missing \
+                     OAuth 1.0a keys ({}), in Settings > API Keys.",
+                    missing.join(", ")
+                ))
             }
         };
-
-        let access_token = match self.get_credential(ApiKeyId::TwitterAccessToken, context) {
-            Some(k) => k,
-            None => {
-                return ToolResult::error(
-                    "TWITTER_ACCESS_TOKEN not configured. Add it in Settings > API Keys.",
-                )
+        let media_credentials = match (&oauth1, params.media_urls.is_empty()) {
+            (_, true) => None,
+            (Ok(credentials), false) => Some(credentials),
+            (Err(missing), false) => {
+                return ToolResult::error(format!(
+                    "Attaching images requires the OAuth 1.0a keys (media upload doesn't accept OAuth 2.0 tokens). \
+                     Missing: {}",
+                    missing.join(", ")
+                ))
             }
         };
-
-        let access_token_secret =
-            match self.get_credential(ApiKeyId::TwitterAccessTokenSecret, context) {
-                Some(k) => k,
-                None => {
-                    return ToolResult::error(
-                        "TWITTER_ACCESS_TOKEN_SECRET not configured. Add it in Settings > API Keys.",
-                    )
-                }
-            };
+        log::debug!("twitter_post: using {}", auth.mode());
 
         // Check subscription tier to enforce correct character limit
-        let credentials = TwitterCredentials::new(
-            consumer_key.clone(),
-            consumer_secret.clone(),
-            access_token.clone(),
-            access_token_secret.clone(),
-        );
         let client = context.http_client();
-        let tier = check_subscription_tier(&client, &credentials).await;
+        let tier = check_subscription_tier(&client, &auth).await;
         let max_chars = tier.max_tweet_chars();
-        let char_count = params.text.chars().count();
 
-        if char_count > max_chars {
+        for (index, tweet_text) in tweets.iter().enumerate() {
+            let char_count = tweet_text.chars().count();
+            if char_count <= max_chars {
+                continue;
+            }
+            let which = if is_thread {
+                format!("Thread tweet at index {}", index)
+            } else {
+                "Tweet".to_string()
+            };
             if max_chars == TWITTER_MAX_CHARS {
                 return ToolResult::error(format!(
-                    "Tweet is {} characters but this account is limited to {} (standard). \
+                    "{} is {} characters but this account is limited to {} (standard). \
                      X Premium is required for longer tweets.",
-                    char_count, max_chars
+                    which, char_count, max_chars
                 ));
             } else {
                 return ToolResult::error(format!(
-                    "Tweet exceeds maximum character limit ({} > {})",
-                    char_count, max_chars
+                    "{} exceeds maximum character limit ({} > {})",
+                    which, char_count, max_chars
                 ));
             }
         }
@@ -209,80 +350,162 @@ impl Tool for TwitterPostTool {
             }
         }
 
-        // Build request body
-        let mut body = json!({
-            "text": params.text
-        });
+        // Download and upload any attached images before posting
+        let mut media_ids = Vec::with_capacity(params.media_urls.len());
+        if let Some(credentials) = media_credentials {
+            for media_url in &params.media_urls {
+                let data = match Self::fetch_media(&client, media_url).await {
+                    Ok(d) => d,
+                    Err(e) => return ToolResult::error(e),
+                };
+                match upload_media(&client, credentials, &data).await {
+                    Ok(id) => media_ids.push(id),
+                    Err(e) => return ToolResult::error(format!("Twitter {}", e)),
+                }
+            }
+        }
 
-        if let Some(reply_to) = &params.reply_to {
-            body["reply"] = json!({
-                "in_reply_to_tweet_id": reply_to
-            });
+        // Post the tweets, chaining each thread entry as a reply to the previous one
+        let mut posted: Vec<TwitterTweetData> = Vec::with_capacity(tweets.len());
+        for (index, tweet_text) in tweets.iter().enumerate() {
+            let body = match posted.last() {
+                None => build_tweet_body(
+                    tweet_text,
+                    params.reply_to.as_deref(),
+                    params.quote_tweet_id.as_deref(),
+                    &media_ids,
+                ),
+                Some(prev) => build_tweet_body(tweet_text, Some(&prev.id), None, &[]),
+            };
+
+            match post_tweet(&client, &auth, &body).await {
+                Ok(data) => posted.push(data),
+                Err(e) if posted.is_empty() => return ToolResult::error(e),
+                Err(e) => {
+                    let posted_ids: Vec<&str> = posted.iter().map(|t| t.id.as_str()).collect();
+                    return ToolResult::error(format!(
+                        "Thread failed at index {} of {}: {}. Already posted tweet IDs: {}",
+                        index,
+                        tweets.len(),
+                        e,
+                        posted_ids.join(", ")
+                    ))
+                    .with_metadata(json!({
+                        "failed_index": index,
+                        "posted_tweet_ids": posted_ids,
+                    }));
+                }
+            }
         }
 
-        if let Some(quote_id) = &params.quote_tweet_id {
-            body["quote_tweet_id"] = json!(quote_id);
+        if !is_thread {
+            let data = &posted[0];
+            return ToolResult::success(
+                json!({
+                    "success": true,
+                    "tweet_id": data.id,
+                    "text": data.text,
+                    "media_ids": media_ids,
+                    "url": tweet_url(&data.id)
+                })
+                .to_string(),
+            );
         }
 
-        // Twitter API v2 endpoint
-        let url = "https://api.twitter.com/2/tweets";
+        let tweet_ids: Vec<&str> = posted.iter().map(|t| t.id.as_str()).collect();
+        ToolResult::success(
+            json!({
+                "success": true,
+                "tweet_ids": tweet_ids,
+                "thread_length": tweet_ids.len(),
+                "media_ids": media_ids,
+                "url": tweet_url(&posted[0].id)
+            })
+            .to_string(),
+        )
+    }
+}
 
-        // Generate OAuth header using shared module
-        let auth_header = generate_oauth_header("POST", url, &credentials, None);
+fn tweet_url(id: &str) -> String {
+    format!("https://twitter.com/i/web/status/{}", id)
+}
 
-        // Make the request
-        let response = match client
-            .post(url)
-            .header("Authorization", auth_header)
-            .header("Content-Type", "application/json")
-            .json(&body)
-            .send()
-            .await
-        {
-            Ok(r) => r,
-            Err(e) => return ToolResult::error(format!("Failed to send request: {}", e)),
-        };
+/// Build the POST /2/tweets request body
+fn build_tweet_body(
+    text: &str,
+    reply_to: Option<&str>,
+    quote_tweet_id: Option<&str>,
+    media_ids: &[String],
+) -> Value {
+    let mut body = json!({
+        "text": text
+    });
+
+    if let Some(reply_to) = reply_to {
+        body["reply"] = json!({
+            "in_reply_to_tweet_id": reply_to
+        });
+    }
 
-        let status = response.status();
-        let response_text = response.text().await.unwrap_or_default();
-
-        if !status.is_success() {
-            // Try to parse error response
-            if let Ok(error_resp) = serde_json::from_str::<TwitterApiResponse>(&response_text) {
-                if let Some(errors) = error_resp.errors {
-                    let error_msg = errors
-                        .iter()
-                        .map(|e| e.message.clone())
-                        .collect::<Vec<_>>()
-                        .join("; ");
-                    return ToolResult::error(format!("Twitter API error: {}", error_msg));
-                }
-            }
-            return ToolResult::error(format!(
-                "Twitter API error ({}): {}",
-                status, response_text
-            ));
-        }
+    if let Some(quote_id) = quote_tweet_id {
+        body["quote_tweet_id"] = json!(quote_id);
+    }
 
-        // Parse success response
-        match serde_json::from_str::<TwitterApiResponse>(&response_text) {
-            Ok(resp) => {
-                if let Some(data) = resp.data {
-                    ToolResult::success(
-                        json!({
-                            "success": true,
-                            "tweet_id": data.id,
-                            "text": data.text,
-                            "url": format!("https://twitter.com/i/web/status/{}", data.id)
-                        })
-                        .to_string(),
-                    )
-                } else {
-                    ToolResult::error("Unexpected response format from Twitter API")
-                }
+    if !media_ids.is_empty() {
+        body["media"] = json!({
+            "media_ids": media_ids
+        });
+    }
+
+    body
+}
+
+/// Post a single tweet via Twitter API v2
+async fn post_tweet(
+    client: &reqwest::Client,
+    auth: &TwitterAuth,
+    body: &Value,
+) -> Result<TwitterTweetData, String> {
+    // Twitter API v2 endpoint
+    let url = "https://api.twitter.com/2/tweets";
+
+    // Generate the Authorization header using shared module
+    let auth_header = auth.authorization_header("POST", url, None);
+
+    // Make the request
+    let response = client
+        .post(url)
+        .header("Authorization", auth_header)
+        .header("Content-Type", "application/json")
+        .json(body)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to send request: {}", e))?;
+
+    let status = response.status();
+    let response_text = response.text().await.unwrap_or_default();
+
+    if !status.is_success() {
+        // Try to parse error response
+        if let Ok(error_resp) = serde_json::from_str::<TwitterApiResponse>(&response_text) {
+            if let Some(errors) = error_resp.errors {
+                let error_msg = errors
+                    .iter()
+                    .map(|e| e.message.clone())
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                return Err(format!("Twitter API error: {}", error_msg));
             }
-            Err(e) => ToolResult::error(format!("Failed to parse Twitter response: {}", e)),
         }
+        return Err(format!("Twitter API error ({}): {}", status, response_text));
+    }
+
+    // Parse success response
+    match serde_json::from_str::<TwitterApiResponse>(&response_text) {
+        Ok(resp) => resp
+            .data
+            .ok_or_else(|| "Unexpected response format from Twitter API".to_string()),
+        Err(e) => Err(format!("Failed to parse Twitter response: {}", e)),
     }
 }
 
@@ -303,6 +526,81 @@ mod tests {
         let tool = TwitterPostTool::new();
         let def = tool.definition();
         assert_eq!(def.name, "twitter_post");
-        assert!(def.input_schema.required.contains(&"text".to_string()));
+        assert!(def.input_schema.properties.contains_key("text"));
+        assert!(def.input_schema.properties.contains_key("media_urls"));
+        assert!(def.input_schema.properties.contains_key("thread"));
+    }
+
+    #[test]
+    fn test_check_media_type() {
+        assert!(check_media_type(Some("image/png")).is_ok());
+        assert!(check_media_type(Some("Image/JPEG; charset=binary")).is_ok());
+        assert!(check_media_type(Some("video/mp4")).is_err());
+        assert!(check_media_type(Some("text/html")).is_err());
+        assert!(check_media_type(None).is_err());
+    }
+
+    #[test]
+    fn test_build_tweet_body() {
+        let first = build_tweet_body("1/2", None, Some("42"), &["7".to_string()]);
+        assert_eq!(first["quote_tweet_id"], "42");
+        assert_eq!(first["media"]["media_ids"][0], "7");
+        assert!(first.get("reply").is_none());
+
+        let next = build_tweet_body("2/2", Some("100"), None, &[]);
+        assert_eq!(next["reply"]["in_reply_to_tweet_id"], "100");
+        assert!(next.get("media").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_text_and_thread_are_exclusive() {
+        let tool = TwitterPostTool::new();
+        let ctx = ToolContext::default();
+
+        let both = tool
+            .execute(json!({ "text": "hi", "thread": ["a", "b"] }), &ctx)
+            .await;
+        assert!(both.content.contains("not both"));
+
+        let neither = tool.execute(json!({}), &ctx).await;
+        assert!(!neither.success);
+
+        let blank = tool.execute(json!({ "thread": ["a", " "] }), &ctx).await;
+        assert!(blank.content.contains("index 1 is empty"));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_too_many_media_urls() {
+        let tool = TwitterPostTool::new();
+        let urls: Vec<String> = (0..5).map(|i| format!("https://example.com/{}.png", i)).collect();
+        let result = tool
+            .execute(json!({ "text": "hi", "media_urls": urls }), &ToolContext::default())
+            .await;
+        assert!(!result.success);
+        assert!(result.content.contains("At most 4 images"));
+    }
+
+    #[tokio::test]
+    async fn test_credential_selection() {
+        let tool = TwitterPostTool::new();
+
+        let none = tool.execute(json!({ "text": "hi" }), &ToolContext::default()).await;
+        assert!(none.content.contains("TWITTER_OAUTH2_ACCESS_TOKEN"));
+        assert!(none.content.contains("TWITTER_CONSUMER_KEY"));
+
+        let partial = ToolContext::default()
+            .with_api_key_id(ApiKeyId::TwitterConsumerKey, "key".to_string())
+            .with_api_key_id(ApiKeyId::TwitterAccessToken, "token".to_string());
+        assert_eq!(
+            tool.oauth1_credentials(&partial).unwrap_err(),
+            vec!["TWITTER_CONSUMER_SECRET", "TWITTER_ACCESS_TOKEN_SECRET"]
+        );
+
+        // An OAuth 2.0 token alone can post, but not upload images
+        let oauth2_only = ToolContext::default().with_api_key_id(ApiKeyId::TwitterOauth2AccessToken, "abc".to_string());
+        let result = tool
+            .execute(json!({ "text": "hi", "media_urls": ["https://example.com/a.png"] }), &oauth2_only)
+            .await;
+        assert!(result.content.contains("requires the OAuth 1.0a keys"));
     }
 }
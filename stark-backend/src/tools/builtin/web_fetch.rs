@@ -634,7 +634,7 @@ impl Tool for WebFetchTool {
 }
 
 /// Validate that a URL points to a public host (not private/internal)
-fn validate_public_url(url: &url::Url) -> Result<(), String> {
+pub(crate) fn validate_public_url(url: &url::Url) -> Result<(), String> {
     let host = url.host_str().ok_or("URL has no host")?;
 
     // Block localhost and common internal hostnames
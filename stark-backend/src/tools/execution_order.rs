@@ -0,0 +1,124 @@
+//! Execution order for tool calls within one model response
+//!
+//! When the model returns several tool calls at once they run one after another, and
+//! some of them only make sense in a particular order (look up a token or set the
+//! recipient address before transferring; finish the work before `say_to_user` or
+//! `task_fully_completed`). Each tool name has a priority — lower runs first — and a
+//! batch is stably sorted by it, so tools with equal priority keep the model's order.
+//!
+//! Tool calls in a batch are executed sequentially; there is no concurrent execution
+//! of a batch, so a tool always sees the effects of every lower-priority tool before it.
+//! If batches are ever run concurrently, only calls with the same priority may be
+//! grouped together: priority levels must still run one level at a time.
+//!
+//! Defaults can be overridden with `STARK_TOOL_PRIORITIES` (e.g. `token_lookup=5,exec=60`).
+
+use crate::ai::ToolCall;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+
+/// Priority for tools without an explicit entry
+pub const DEFAULT_PRIORITY: i32 = 50;
+
+/// Per-tool execution priorities (lower runs first)
+#[derive(Debug, Clone)]
+pub struct ToolExecutionOrder {
+    default_priority: i32,
+    priorities: HashMap<String, i32>,
+}
+
+impl Default for ToolExecutionOrder {
+    fn default() -> Self {
+        let mut priorities = HashMap::new();
+        // Planning first, so tasks exist before the work that completes them
+        priorities.insert("define_tasks".to_string(), 0);
+        priorities.insert("add_task".to_string(), 0);
+        // Resolve networks, tokens and addresses before anything uses them
+        priorities.insert("select_web3_network".to_string(), 10);
+        priorities.insert("token_lookup".to_string(), 10);
+        priorities.insert("price_lookup".to_string(), 10);
+        priorities.insert("set_address".to_string(), 10);
+        priorities.insert("to_raw_amount".to_string(), 20);
+        // Broadcasting needs the transaction queued by the calls before it
+        priorities.insert("broadcast_web3_tx".to_string(), 80);
+        // Report and finish only after the work in the same batch is done
+        priorities.insert("say_to_user".to_string(), 90);
+        priorities.insert("ask_user".to_string(), 90);
+        priorities.insert("task_fully_completed".to_string(), 100);
+
+        Self {
+            default_priority: DEFAULT_PRIORITY,
+            priorities,
+        }
+    }
+}
+
+impl ToolExecutionOrder {
+    /// Defaults with `STARK_TOOL_PRIORITIES` entries applied on top
+    pub fn from_env() -> Self {
+        let mut order = Self::default();
+        for (name, priority) in crate::config::tool_priorities() {
+            order.priorities.insert(name, priority);
+        }
+        order
+    }
+
+    /// Set the priority of a tool
+    pub fn with_priority(mut self, tool_name: &str, priority: i32) -> Self {
+        self.priorities.insert(tool_name.to_string(), priority);
+        self
+    }
+
+    /// Priority of a tool, falling back to the default
+    pub fn priority(&self, tool_name: &str) -> i32 {
+        self.priorities
+            .get(tool_name)
+            .copied()
+            .unwrap_or(self.default_priority)
+    }
+
+    /// The calls in the order they should run (stable: ties keep the model's order)
+    pub fn order<'a>(&self, calls: &'a [ToolCall]) -> Vec<&'a ToolCall> {
+        let mut ordered: Vec<&ToolCall> = calls.iter().collect();
+        ordered.sort_by_key(|call| self.priority(&call.name));
+        ordered
+    }
+}
+
+static GLOBAL_ORDER: Lazy<ToolExecutionOrder> = Lazy::new(ToolExecutionOrder::from_env);
+
+/// Process-wide execution order configured from the environment
+pub fn global() -> &'static ToolExecutionOrder {
+    &GLOBAL_ORDER
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn call(id: &str, name: &str) -> ToolCall {
+        ToolCall {
+            id: id.to_string(),
+            name: name.to_string(),
+            arguments: json!({}),
+        }
+    }
+
+    #[test]
+    fn test_order_is_by_priority_then_model_order() {
+        let order = ToolExecutionOrder::default().with_priority("exec", 60);
+        let calls = vec![
+            call("1", "say_to_user"),
+            call("2", "send_eth"),
+            call("3", "exec"),
+            call("4", "token_lookup"),
+            call("5", "web_fetch"),
+            call("6", "task_fully_completed"),
+        ];
+
+        let ids: Vec<&str> = order.order(&calls).iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["4", "2", "5", "3", "1", "6"]);
+        assert_eq!(order.priority("unknown_tool"), DEFAULT_PRIORITY);
+    }
+}
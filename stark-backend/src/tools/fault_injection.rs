@@ -0,0 +1,228 @@
+//! Tool fault injection for chaos testing
+//!
+//! Lets maintainers force specific tools to fail, hang, or slow down so the
+//! agent's retry, circuit-breaker, and abort paths can be exercised
+//! deterministically. Enabled only when `STARK_TOOL_FAULT_INJECTION` is set,
+//! either to inline JSON or to the path of a JSON file:
+//!
+//! ```json
+//! {
+//!   "twitter_post": { "fault": "error", "status": 429, "times": 2 },
+//!   "web_fetch":    { "fault": "timeout" },
+//!   "exec":         { "fault": "delay", "delay_ms": 5000, "after": 1 }
+//! }
+//! ```
+//!
+//! Faults are keyed on per-tool call counts (not randomness), so the same spec
+//! replays the same failure sequence on every run.
+
+use crate::tools::types::ToolResult;
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// How long a `timeout` fault hangs when no `delay_ms` is given.
+/// Longer than any watchdog tool timeout, so the watchdog is what ends the call.
+const DEFAULT_TIMEOUT_HANG_MS: u64 = 600_000;
+
+/// Kind of fault to inject
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FaultKind {
+    /// Return an error result without running the tool
+    Error,
+    /// Hang (for `delay_ms`, default 10 minutes) and then return a timeout error
+    Timeout,
+    /// Sleep for `delay_ms`, then run the tool normally
+    Delay,
+}
+
+/// A single tool's fault rule
+#[derive(Debug, Clone, Deserialize)]
+pub struct FaultRule {
+    pub fault: FaultKind,
+    /// HTTP-style status reported in the error (e.g. 429 to simulate rate limiting)
+    #[serde(default)]
+    pub status: Option<u16>,
+    /// Custom error message (defaults to one derived from `status`)
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub delay_ms: Option<u64>,
+    /// Number of calls to let through before the fault starts
+    #[serde(default)]
+    pub after: u64,
+    /// Number of calls to fault once started (None = every call)
+    #[serde(default)]
+    pub times: Option<u64>,
+}
+
+impl FaultRule {
+    /// Whether the 0-based `call_index` falls inside this rule's fault window
+    fn applies_to(&self, call_index: u64) -> bool {
+        if call_index < self.after {
+            return false;
+        }
+        match self.times {
+            Some(times) => call_index - self.after < times,
+            None => true,
+        }
+    }
+
+    fn error_message(&self, tool_name: &str) -> String {
+        if let Some(ref msg) = self.message {
+            return msg.clone();
+        }
+        match self.status {
+            Some(429) => format!("{}: HTTP 429 Too Many Requests — rate limited", tool_name),
+            Some(status) => format!("{}: HTTP {} error", tool_name, status),
+            None => format!("{}: injected failure", tool_name),
+        }
+    }
+}
+
+/// Outcome of consulting the injector before a tool runs
+pub enum InjectedFault {
+    /// Return this result instead of running the tool
+    Fail(ToolResult),
+    /// Hang for this long, then return a timeout error
+    Hang(Duration),
+    /// Sleep for this long, then run the tool
+    Delay(Duration),
+}
+
+/// Deterministic per-tool fault injector
+pub struct FaultInjector {
+    rules: HashMap<String, FaultRule>,
+    calls: HashMap<String, AtomicU64>,
+}
+
+impl FaultInjector {
+    pub fn new(rules: HashMap<String, FaultRule>) -> Self {
+        let calls = rules.keys().map(|k| (k.clone(), AtomicU64::new(0))).collect();
+        FaultInjector { rules, calls }
+    }
+
+    /// Parse a spec: inline JSON object, or a path to a file containing one
+    pub fn from_spec(spec: &str) -> Result<Self, String> {
+        let spec = spec.trim();
+        let json = if spec.starts_with('{') {
+            spec.to_string()
+        } else {
+            std::fs::read_to_string(spec)
+                .map_err(|e| format!("Failed to read fault injection file '{}': {}", spec, e))?
+        };
+        let rules: HashMap<String, FaultRule> = serde_json::from_str(&json)
+            .map_err(|e| format!("Invalid fault injection spec: {}", e))?;
+        Ok(Self::new(rules))
+    }
+
+    /// Load from `STARK_TOOL_FAULT_INJECTION`; None when unset or invalid
+    pub fn from_env() -> Option<Self> {
+        let spec = crate::config::tool_fault_injection()?;
+        match Self::from_spec(&spec) {
+            Ok(injector) => {
+                log::warn!(
+                    "[FAULT_INJECTION] Enabled for tools: {:?} — do not run this in production",
+                    injector.rules.keys().collect::<Vec<_>>()
+                );
+                Some(injector)
+            }
+            Err(e) => {
+                log::error!("[FAULT_INJECTION] {} — fault injection disabled", e);
+                None
+            }
+        }
+    }
+
+    /// Record a call to `tool_name` and return the fault to apply, if any
+    pub fn check(&self, tool_name: &str) -> Option<InjectedFault> {
+        let rule = self.rules.get(tool_name)?;
+        let call_index = self.calls.get(tool_name)?.fetch_add(1, Ordering::SeqCst);
+        if !rule.applies_to(call_index) {
+            return None;
+        }
+
+        log::warn!(
+            "[FAULT_INJECTION] Injecting {:?} into '{}' (call #{})",
+            rule.fault, tool_name, call_index + 1
+        );
+        Some(match rule.fault {
+            FaultKind::Error => InjectedFault::Fail(
+                ToolResult::error(rule.error_message(tool_name)).with_metadata(json!({
+                    "injected_fault": true,
+                    "status": rule.status,
+                    "call": call_index + 1,
+                })),
+            ),
+            FaultKind::Timeout => InjectedFault::Hang(Duration::from_millis(
+                rule.delay_ms.unwrap_or(DEFAULT_TIMEOUT_HANG_MS),
+            )),
+            FaultKind::Delay => InjectedFault::Delay(Duration::from_millis(rule.delay_ms.unwrap_or(0))),
+        })
+    }
+}
+
+static GLOBAL_INJECTOR: Lazy<Option<FaultInjector>> = Lazy::new(FaultInjector::from_env);
+
+/// Process-wide injector configured from the environment (None in normal runs)
+pub fn global() -> Option<&'static FaultInjector> {
+    GLOBAL_INJECTOR.as_ref()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_fault_window_replays_deterministically() {
+        let injector = FaultInjector::from_spec(
+            r#"{"twitter_post": {"fault": "error", "status": 429, "after": 1, "times": 2}}"#,
+        )
+        .unwrap();
+
+        let outcomes: Vec<bool> = (0..4)
+            .map(|_| matches!(injector.check("twitter_post"), Some(InjectedFault::Fail(_))))
+            .collect();
+        assert_eq!(outcomes, vec![false, true, true, false]);
+        assert!(injector.check("web_fetch").is_none());
+    }
+
+    #[test]
+    fn test_error_fault_result() {
+        let injector =
+            FaultInjector::from_spec(r#"{"twitter_post": {"fault": "error", "status": 429}}"#).unwrap();
+        match injector.check("twitter_post") {
+            Some(InjectedFault::Fail(result)) => {
+                assert!(!result.success);
+                assert!(result.content.contains("429"));
+            }
+            _ => panic!("expected an injected failure"),
+        }
+    }
+
+    #[test]
+    fn test_timeout_and_delay_faults() {
+        let injector = FaultInjector::from_spec(
+            r#"{"web_fetch": {"fault": "timeout"}, "exec": {"fault": "delay", "delay_ms": 50}}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            injector.check("web_fetch"),
+            Some(InjectedFault::Hang(d)) if d == Duration::from_millis(DEFAULT_TIMEOUT_HANG_MS)
+        ));
+        assert!(matches!(
+            injector.check("exec"),
+            Some(InjectedFault::Delay(d)) if d == Duration::from_millis(50)
+        ));
+    }
+
+    #[test]
+    fn test_invalid_spec() {
+        assert!(FaultInjector::from_spec(r#"{"exec": {"fault": "explode"}}"#).is_err());
+        assert!(FaultInjector::from_spec("/nonexistent/faults.json").is_err());
+    }
+}
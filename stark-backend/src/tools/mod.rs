@@ -1,6 +1,9 @@
 pub mod builtin;
 pub mod context_bank;
+pub mod execution_order;
+pub mod fault_injection;
 pub mod http_retry;
+pub mod injection_guard;
 pub mod presets;
 pub mod register;
 pub mod registry;
@@ -31,6 +34,7 @@ fn register_all_tools(registry: &mut ToolRegistry) {
     registry.register(Arc::new(builtin::ModifySoulTool::new()));
     registry.register(Arc::new(builtin::RegisterNewIdentityTool::new()));
     registry.register(Arc::new(builtin::ImportIdentityTool::new()));
+    registry.register(Arc::new(builtin::ValidationCheckTool::new()));
     registry.register(Arc::new(builtin::ApiKeysCheckTool::new()));
     registry.register(Arc::new(builtin::TaskFullyCompletedTool::new()));
     registry.register(Arc::new(builtin::AddTaskTool::new()));
@@ -40,11 +44,14 @@ fn register_all_tools(registry: &mut ToolRegistry) {
     registry.register(Arc::new(builtin::ManageModulesTool::new()));
     registry.register(Arc::new(builtin::WorkstreamTool::new()));
     registry.register(Arc::new(builtin::InstallApiKeyTool::new()));
+    registry.register(Arc::new(builtin::ManageApiKeyTool::new()));
     registry.register(Arc::new(builtin::HeartbeatConfigTool::new()));
     registry.register(Arc::new(builtin::MindmapManageTool::new()));
+    registry.register(Arc::new(builtin::ListCapabilitiesTool::new()));
 
     // Meta tools (self-management)
     registry.register(Arc::new(builtin::CloudBackupTool::new()));
+    registry.register(Arc::new(builtin::ConfigSnapshotTool::new()));
     registry.register(Arc::new(builtin::SetThemeAccentTool::new()));
     registry.register(Arc::new(builtin::ReadOperatingModeTool::new()));
     registry.register(Arc::new(builtin::ReadRecentTransactionsTool::new()));
@@ -52,6 +59,8 @@ fn register_all_tools(registry: &mut ToolRegistry) {
 
     // Web tools (shared)
     registry.register(Arc::new(builtin::WebFetchTool::new()));
+    // Summarize text, files or URLs via the AI provider (map-reduce for long inputs)
+    registry.register(Arc::new(builtin::SummarizeTool::new()));
     // Local RPC — localhost-only HTTP for microservice APIs
     registry.register(Arc::new(builtin::LocalRpcTool::new()));
 
@@ -62,6 +71,8 @@ fn register_all_tools(registry: &mut ToolRegistry) {
     registry.register(Arc::new(builtin::X402PostTool::new()));
     // send_eth for simple native ETH transfers (no ABI needed)
     registry.register(Arc::new(builtin::SendEthTool::new()));
+    // Fee preview before transfers/registration (simulation only)
+    registry.register(Arc::new(builtin::EstimateGasTool::new()));
     registry.register(Arc::new(builtin::BroadcastWeb3TxTool::new()));
     registry.register(Arc::new(builtin::ListQueuedWeb3TxTool::new()));
     registry.register(Arc::new(builtin::Web3PresetFunctionCallTool::new()));
@@ -71,6 +82,8 @@ fn register_all_tools(registry: &mut ToolRegistry) {
     registry.register(Arc::new(builtin::SetAddressTool::new()));
     // Post-broadcast transaction verification (AI-based)
     registry.register(Arc::new(builtin::VerifyTxBroadcastTool::new()));
+    // Status / speed-up / cancel for stuck transactions (replacements go through the queue)
+    registry.register(Arc::new(builtin::TxManageTool::new()));
     // Network selection for chain-specific operations
     registry.register(Arc::new(builtin::SelectWeb3NetworkTool::new()));
     // Polymarket prediction market trading
@@ -79,16 +92,21 @@ fn register_all_tools(registry: &mut ToolRegistry) {
     registry.register(Arc::new(builtin::DexScreenerTool::new()));
     // GeckoTerminal interactive price charts
     registry.register(Arc::new(builtin::GeckoTerminalTool::new()));
+    // Spot prices in fiat (CoinGecko-compatible API)
+    registry.register(Arc::new(builtin::PriceLookupTool::new()));
     // Cross-chain USDC bridging via Across Protocol
     registry.register(Arc::new(builtin::BridgeUsdcTool::new()));
     // ERC-8128 signed HTTP requests (Ethereum identity)
     registry.register(Arc::new(builtin::Erc8128FetchTool::new()));
     // SIWA/SIWE authentication (Sign In With Agent/Ethereum)
     registry.register(Arc::new(builtin::SiwaAuthTool::new()));
+    // Wallet-signature login challenges (EIP-191 / EIP-712, never transactions)
+    registry.register(Arc::new(builtin::SignMessageTool::new()));
 
     // Filesystem tools (read-only, shared)
     registry.register(Arc::new(builtin::ReadFileTool::new()));
     registry.register(Arc::new(builtin::ListFilesTool::new()));
+    registry.register(Arc::new(builtin::DiffTool::new()));
 
     // Development tools (code editing, git, search)
     registry.register(Arc::new(builtin::WriteFileTool::new()));
@@ -111,15 +129,22 @@ fn register_all_tools(registry: &mut ToolRegistry) {
     registry.register(Arc::new(builtin::IndexProjectTool::new()));
 
     // Exec tool (Development mode)
-    registry.register(Arc::new(builtin::ExecTool::new()));
+    registry.register(Arc::new(builtin::ExecTool::from_env()));
     // Claude Code Remote — SSH into remote machine running Claude Code CLI
     registry.register(Arc::new(builtin::ClaudeCodeRemoteTool::new()));
 
     // Messaging tools
     registry.register(Arc::new(builtin::AgentSendTool::new()));
+    registry.register(Arc::new(builtin::BroadcastAnnouncementTool::new()));
+    registry.register(Arc::new(builtin::SetReminderTool::new()));
+    registry.register(Arc::new(builtin::ListRemindersTool::new()));
+    registry.register(Arc::new(builtin::CancelReminderTool::new()));
+    registry.register(Arc::new(builtin::ScheduleMessageTool::new()));
+    registry.register(Arc::new(builtin::ListScheduledTool::new()));
     registry.register(Arc::new(builtin::DiscordReadTool::new()));
     registry.register(Arc::new(builtin::DiscordWriteTool::new()));
     registry.register(Arc::new(builtin::DiscordLookupTool::new()));
+    registry.register(Arc::new(builtin::DiscordPinTool::new()));
     registry.register(Arc::new(builtin::TwitterPostTool::new()));
     registry.register(Arc::new(builtin::TelegramReadTool::new()));
 
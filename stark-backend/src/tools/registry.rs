@@ -1,10 +1,17 @@
 use crate::ai::multi_agent::types::AgentSubtype;
+use crate::gateway::protocol::GatewayEvent;
+use crate::tools::fault_injection::InjectedFault;
 use crate::tools::types::{ToolConfig, ToolContext, ToolDefinition, ToolGroup, ToolProfile, ToolResult, ToolSafetyLevel};
 use async_trait::async_trait;
 use parking_lot::RwLock;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Metadata key set on results produced by the registry's execution timeout.
+/// The registry has already broadcast `tool.result` for these.
+pub const REGISTRY_TIMEOUT_KEY: &str = "registry_timeout";
 
 /// Trait that all tools must implement
 #[async_trait]
@@ -40,6 +47,11 @@ pub trait Tool: Send + Sync {
 pub struct ToolRegistry {
     tools: RwLock<HashMap<String, Arc<dyn Tool>>>,
     default_config: ToolConfig,
+    /// Process-wide safe mode (set at startup): only ReadOnly/SafeMode tools are
+    /// offered or executed, regardless of channel config
+    global_safe_mode: bool,
+    /// Execution time limit overrides per group (zero = no limit); others use the group default
+    group_timeouts: HashMap<ToolGroup, Duration>,
 }
 
 impl ToolRegistry {
@@ -47,6 +59,8 @@ impl ToolRegistry {
         ToolRegistry {
             tools: RwLock::new(HashMap::new()),
             default_config: ToolConfig::default(),
+            global_safe_mode: false,
+            group_timeouts: HashMap::new(),
         }
     }
 
@@ -54,13 +68,32 @@ impl ToolRegistry {
         ToolRegistry {
             tools: RwLock::new(HashMap::new()),
             default_config: config,
+            global_safe_mode: false,
+            group_timeouts: HashMap::new(),
         }
     }
 
-    /// Register a tool (thread-safe, takes &self via interior mutability)
-    pub fn register(&self, tool: Arc<dyn Tool>) {
+    /// Register a tool (thread-safe, takes &self via interior mutability).
+    ///
+    /// Tool names must be unique: if a tool with the same name is already registered,
+    /// the new one is rejected (the existing tool keeps serving calls) and an error is
+    /// logged, so collisions between built-in and dynamic tools are visible instead of
+    /// silently shadowing each other. Returns true if the tool was registered.
+    /// To swap a tool on purpose, `unregister` it first.
+    pub fn register(&self, tool: Arc<dyn Tool>) -> bool {
         let name = tool.definition().name.clone();
-        self.tools.write().insert(name, tool);
+        let mut tools = self.tools.write();
+        if let Some(existing) = tools.get(&name) {
+            log::error!(
+                "[REGISTRY] Duplicate tool name '{}' (group {:?}) rejected — already registered by a {:?} tool",
+                name,
+                tool.group(),
+                existing.group()
+            );
+            return false;
+        }
+        tools.insert(name, tool);
+        true
     }
 
     /// Unregister a tool by name. Returns true if it was present.
@@ -78,6 +111,36 @@ impl ToolRegistry {
         self.tools.read().values().cloned().collect()
     }
 
+    /// Whether side-effecting tools are disabled process-wide
+    pub fn global_safe_mode(&self) -> bool {
+        self.global_safe_mode
+    }
+
+    /// Enable/disable process-wide safe mode (startup only; there is no runtime toggle)
+    pub fn set_global_safe_mode(&mut self, enabled: bool) {
+        self.global_safe_mode = enabled;
+    }
+
+    /// Override the execution time limit for a tool group (zero = no limit; startup only)
+    pub fn set_group_timeout(&mut self, group: ToolGroup, timeout: Duration) {
+        self.group_timeouts.insert(group, timeout);
+    }
+
+    /// Execution time limit for tools in `group`, or None if unlimited
+    pub fn timeout_for_group(&self, group: ToolGroup) -> Option<Duration> {
+        let timeout = self
+            .group_timeouts
+            .get(&group)
+            .copied()
+            .unwrap_or_else(|| Duration::from_secs(group.default_timeout_secs()));
+        (!timeout.is_zero()).then_some(timeout)
+    }
+
+    /// Global safe mode only lets through tools without side effects
+    pub fn permitted_by_global_safe_mode(&self, tool: &dyn Tool) -> bool {
+        !self.global_safe_mode || tool.safety_level() >= ToolSafetyLevel::ReadOnly
+    }
+
     /// Get tools at or above a minimum safety level, filtered by config.
     /// - ReadOnly: tools with safety_level >= ReadOnly (ReadOnly + SafeMode)
     /// - SafeMode: tools with safety_level >= SafeMode (SafeMode only)
@@ -87,6 +150,7 @@ impl ToolRegistry {
             .values()
             .filter(|tool| {
                 tool.safety_level() >= min_level
+                    && self.permitted_by_global_safe_mode(tool.as_ref())
                     && config.is_tool_allowed(&tool.definition().name, tool.group())
             })
             .cloned()
@@ -109,7 +173,9 @@ impl ToolRegistry {
             .filter(|tool| {
                 let def = tool.definition();
                 // Hidden tools are skill-only — excluded from normal lists
-                !def.hidden && config.is_tool_allowed(&def.name, tool.group())
+                !def.hidden
+                    && self.permitted_by_global_safe_mode(tool.as_ref())
+                    && config.is_tool_allowed(&def.name, tool.group())
             })
             .cloned()
             .collect()
@@ -129,7 +195,7 @@ impl ToolRegistry {
             .filter(|tool| {
                 let def = tool.definition();
                 // Hidden tools are skill-only — excluded from normal lists
-                if def.hidden {
+                if def.hidden || !self.permitted_by_global_safe_mode(tool.as_ref()) {
                     return false;
                 }
                 let group = tool.group();
@@ -176,7 +242,9 @@ impl ToolRegistry {
         for tool_name in required_tools {
             if !tool_names.contains(tool_name) {
                 if let Some(tool) = self.get(tool_name) {
-                    let should_include = if is_safe_mode {
+                    let should_include = if !self.permitted_by_global_safe_mode(tool.as_ref()) {
+                        false
+                    } else if is_safe_mode {
                         // Safe mode: respect full config restrictions
                         config.is_tool_allowed(&tool.definition().name, tool.group())
                     } else {
@@ -194,7 +262,7 @@ impl ToolRegistry {
                         log::warn!(
                             "[REGISTRY] Skipping required tool '{}' - blocked by {} config",
                             tool_name,
-                            if is_safe_mode { "safe mode" } else { "deny_list" }
+                            if is_safe_mode || self.global_safe_mode { "safe mode" } else { "deny_list" }
                         );
                     }
                 } else {
@@ -229,6 +297,21 @@ impl ToolRegistry {
         params: Value,
         context: &ToolContext,
         config: Option<&ToolConfig>,
+    ) -> ToolResult {
+        let start = Instant::now();
+        let result = self.execute_unrecorded(name, params, context, config).await;
+        // Names come from the model, so only registered tools get their own label
+        let label = if self.has_tool(name) { name } else { "unknown" };
+        crate::metrics::record_tool(label, result.success, start.elapsed());
+        result
+    }
+
+    async fn execute_unrecorded(
+        &self,
+        name: &str,
+        params: Value,
+        context: &ToolContext,
+        config: Option<&ToolConfig>,
     ) -> ToolResult {
         let effective_config = config.unwrap_or(&self.default_config);
 
@@ -238,13 +321,67 @@ impl ToolRegistry {
             None => return ToolResult::error(format!("Tool '{}' not found", name)),
         };
 
+        if !self.permitted_by_global_safe_mode(tool.as_ref()) {
+            return ToolResult::error(format!(
+                "Tool '{}' is disabled: the bot is running in safe mode (side-effecting tools are off)",
+                name
+            ));
+        }
+
         // Check if tool is allowed
         if !effective_config.is_tool_allowed(name, tool.group()) {
             return ToolResult::error(format!("Tool '{}' is not allowed", name));
         }
 
-        // Execute the tool
-        tool.execute(params, context).await
+        // Chaos testing: apply any configured fault before running the tool
+        if let Some(injector) = crate::tools::fault_injection::global() {
+            match injector.check(name) {
+                Some(InjectedFault::Fail(result)) => return result,
+                Some(InjectedFault::Hang(duration)) => {
+                    tokio::time::sleep(duration).await;
+                    return ToolResult::error(format!(
+                        "Tool '{}' timed out (injected fault)",
+                        name
+                    ));
+                }
+                Some(InjectedFault::Delay(duration)) => tokio::time::sleep(duration).await,
+                None => {}
+            }
+        }
+
+        // Execute the tool, bounded by its group's time limit so a hung call can't stall the agent
+        let Some(limit) = self.timeout_for_group(tool.group()) else {
+            return tool.execute(params, context).await;
+        };
+        let start = Instant::now();
+        match tokio::time::timeout(limit, tool.execute(params, context)).await {
+            Ok(result) => result,
+            Err(_) => {
+                log::warn!("[REGISTRY] Tool '{}' timed out after {:?}", name, limit);
+                let result = ToolResult::error(format!(
+                    "Tool '{}' timed out after {}s and was cancelled",
+                    name,
+                    limit.as_secs()
+                ))
+                .with_metadata(serde_json::json!({
+                    REGISTRY_TIMEOUT_KEY: true,
+                    "timeout_secs": limit.as_secs(),
+                    "duration_ms": start.elapsed().as_millis() as i64,
+                }));
+                if let (Some(broadcaster), Some(channel_id)) = (&context.broadcaster, context.channel_id) {
+                    broadcaster.broadcast(GatewayEvent::tool_result(
+                        channel_id,
+                        context.platform_chat_id.as_deref(),
+                        name,
+                        false,
+                        start.elapsed().as_millis() as i64,
+                        result.error.as_deref().unwrap_or_default(),
+                        context.extra.get("safe_mode").and_then(|v| v.as_bool()).unwrap_or(false),
+                    ));
+                }
+                result
+            }
+        }
     }
 
     /// Get default configuration
@@ -279,11 +416,75 @@ impl Default for ToolRegistry {
     }
 }
 
+/// Error for a call to a tool that isn't registered (usually a hallucinated name).
+/// Lists the tools the model can actually call so it can correct itself next turn,
+/// leading with the closest name when one is a likely typo.
+pub fn unknown_tool_message(name: &str, available: &[ToolDefinition]) -> String {
+    let mut names: Vec<&str> = available.iter().map(|t| t.name.as_str()).collect();
+    names.sort_unstable();
+    names.dedup();
+
+    let wanted = name.to_lowercase();
+    let closest = names
+        .iter()
+        .map(|n| (edit_distance(&wanted, &n.to_lowercase()), *n))
+        .filter(|(d, _)| *d <= 3)
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, n)| n);
+
+    let mut message = format!("Unknown tool '{}': no tool with that name exists.", name);
+    if let Some(suggestion) = closest {
+        message.push_str(&format!(" Did you mean '{}'?", suggestion));
+    }
+    if names.is_empty() {
+        message.push_str(" No tools are available right now.");
+    } else {
+        message.push_str(&format!(
+            " Call one of the available tools instead: {}",
+            names.join(", ")
+        ));
+    }
+    message
+}
+
+/// Levenshtein distance, for suggesting the intended tool name
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::tools::types::{PropertySchema, ToolInputSchema};
 
+    #[test]
+    fn test_unknown_tool_message() {
+        let available = vec![
+            MockTool::new("web_fetch", ToolGroup::Web).definition,
+            MockTool::new("say_to_user", ToolGroup::System).definition,
+        ];
+
+        let message = unknown_tool_message("web_fetc", &available);
+        assert!(message.contains("Unknown tool 'web_fetc'"));
+        assert!(message.contains("Did you mean 'web_fetch'?"));
+        assert!(message.ends_with("say_to_user, web_fetch"));
+
+        // No suggestion when nothing is close
+        let message = unknown_tool_message("launch_rocket", &available);
+        assert!(!message.contains("Did you mean"));
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
     struct MockTool {
         definition: ToolDefinition,
     }
@@ -302,6 +503,38 @@ mod tests {
         }
     }
 
+    struct SlowTool;
+
+    #[async_trait]
+    impl Tool for SlowTool {
+        fn definition(&self) -> ToolDefinition {
+            MockTool::new("slow_fetch", ToolGroup::Web).definition
+        }
+
+        async fn execute(&self, _params: Value, _context: &ToolContext) -> ToolResult {
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            ToolResult::success("too late")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_times_out_per_group() {
+        let mut registry = ToolRegistry::new();
+        registry.register(Arc::new(SlowTool));
+        registry.set_group_timeout(ToolGroup::Web, Duration::from_millis(20));
+
+        let result = registry
+            .execute("slow_fetch", serde_json::json!({}), &ToolContext::new(), Some(&ToolConfig::default()))
+            .await;
+        assert!(!result.success);
+        assert!(result.error.as_ref().unwrap().contains("timed out"));
+        assert_eq!(result.metadata.unwrap()[REGISTRY_TIMEOUT_KEY], true);
+
+        registry.set_group_timeout(ToolGroup::Web, Duration::ZERO);
+        assert_eq!(registry.timeout_for_group(ToolGroup::Web), None);
+        assert_eq!(registry.timeout_for_group(ToolGroup::Exec), Some(Duration::from_secs(900)));
+    }
+
     #[async_trait]
     impl Tool for MockTool {
         fn definition(&self) -> ToolDefinition {
@@ -324,6 +557,24 @@ mod tests {
         assert_eq!(registry.len(), 1);
     }
 
+    #[test]
+    fn test_registry_rejects_duplicate_name() {
+        let registry = ToolRegistry::new();
+        assert!(registry.register(Arc::new(MockTool::new("dup_tool", ToolGroup::Web))));
+        assert!(!registry.register(Arc::new(MockTool::new("dup_tool", ToolGroup::Finance))));
+
+        // The original registration wins and only one definition is exposed
+        assert_eq!(registry.len(), 1);
+        assert_eq!(registry.get("dup_tool").unwrap().group(), ToolGroup::Web);
+        let defs = registry.get_tool_definitions(&ToolConfig::default());
+        assert_eq!(defs.iter().filter(|d| d.name == "dup_tool").count(), 1);
+
+        // Explicit replacement still works via unregister + register
+        assert!(registry.unregister("dup_tool"));
+        assert!(registry.register(Arc::new(MockTool::new("dup_tool", ToolGroup::Finance))));
+        assert_eq!(registry.get("dup_tool").unwrap().group(), ToolGroup::Finance);
+    }
+
     #[test]
     fn test_tool_config_allows() {
         let config = ToolConfig {
@@ -387,6 +638,7 @@ mod tests {
         registry.register(Arc::new(MockTool::new("discord_lookup", ToolGroup::Messaging)));
         registry.register(Arc::new(MockTool::new("telegram_read", ToolGroup::Messaging)));
         registry.register(Arc::new(MockTool::new("define_tasks", ToolGroup::System)));
+        registry.register(Arc::new(MockTool::new("list_capabilities", ToolGroup::System)));
         registry
     }
 
@@ -814,4 +1066,89 @@ mod tests {
         // Allowed groups must be only "web"
         assert_eq!(config.allowed_groups, vec!["web".to_string()]);
     }
+
+    #[test]
+    fn test_disabled_tool_removed_while_group_siblings_remain() {
+        let mut registry = ToolRegistry::new();
+        registry.register(Arc::new(MockTool::new("exec", ToolGroup::Exec)));
+        registry.register(Arc::new(MockTool::new("process_status", ToolGroup::Exec)));
+        registry.register(Arc::new(MockTool::new("web_fetch", ToolGroup::Web)));
+
+        // Exec group enabled, but the channel disables the single `exec` tool
+        let config = ToolConfig {
+            allow_list: vec!["exec".to_string()],
+            deny_list: vec!["exec".to_string()],
+            ..Default::default()
+        };
+
+        let names: Vec<String> = registry
+            .get_tool_definitions(&config)
+            .into_iter()
+            .map(|t| t.name)
+            .collect();
+        assert!(!names.contains(&"exec".to_string()), "disabled tool must be removed, got: {:?}", names);
+        assert!(names.contains(&"process_status".to_string()), "sibling must remain, got: {:?}", names);
+        assert!(names.contains(&"web_fetch".to_string()));
+    }
+
+    #[test]
+    fn test_into_safe_mode_keeps_channel_disabled_tools() {
+        let channel_config = ToolConfig {
+            deny_list: vec!["discord_read".to_string()],
+            allowed_groups: vec!["exec".to_string()],
+            profile: ToolProfile::Custom,
+            ..Default::default()
+        };
+
+        let config = channel_config.into_safe_mode();
+        assert_eq!(config.profile, ToolProfile::SafeMode);
+        assert_eq!(config.allowed_groups, vec!["web".to_string()]);
+        assert!(!config.is_tool_allowed("discord_read", ToolGroup::Messaging));
+        assert!(config.is_tool_allowed("token_lookup", ToolGroup::Finance));
+    }
+
+    /// Mock tool with no side effects
+    struct ReadOnlyMockTool(MockTool);
+
+    #[async_trait]
+    impl Tool for ReadOnlyMockTool {
+        fn definition(&self) -> ToolDefinition {
+            self.0.definition()
+        }
+
+        async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+            self.0.execute(params, context).await
+        }
+
+        fn safety_level(&self) -> ToolSafetyLevel {
+            ToolSafetyLevel::ReadOnly
+        }
+    }
+
+    #[tokio::test]
+    async fn test_global_safe_mode_disables_side_effecting_tools() {
+        let mut registry = ToolRegistry::new();
+        registry.register(Arc::new(MockTool::new("exec", ToolGroup::Exec)));
+        registry.register(Arc::new(MockTool::new("twitter_post", ToolGroup::Social)));
+        registry.register(Arc::new(ReadOnlyMockTool(MockTool::new("read_file", ToolGroup::Filesystem))));
+        registry.set_global_safe_mode(true);
+
+        // Even a fully permissive channel config can't re-enable them
+        let config = ToolConfig {
+            profile: ToolProfile::Full,
+            ..Default::default()
+        };
+        let names: Vec<String> = registry
+            .get_tool_definitions(&config)
+            .into_iter()
+            .map(|t| t.name)
+            .collect();
+        assert_eq!(names, vec!["read_file".to_string()]);
+
+        let result = registry.execute("exec", serde_json::json!({}), &ToolContext::new(), Some(&config)).await;
+        assert!(!result.success);
+        assert!(result.content.contains("safe mode"));
+        let result = registry.execute("read_file", serde_json::json!({}), &ToolContext::new(), Some(&config)).await;
+        assert!(result.success);
+    }
 }
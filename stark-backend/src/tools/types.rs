@@ -1,19 +1,21 @@
 use crate::ai::multi_agent::SubAgentManager;
 use crate::controllers::api_keys::ApiKeyId;
 use crate::db::Database;
-use crate::disk_quota::DiskQuotaManager;
+use crate::disk_quota::{ChannelCommand, DiskQuotaManager};
 use crate::execution::ProcessManager;
 use crate::gateway::events::EventBroadcaster;
 use crate::gateway::protocol::GatewayEvent;
 use crate::qmd_memory::MemoryStore;
 use crate::skills::SkillRegistry;
 use crate::tools::register::RegisterStore;
+use crate::tools::registry::ToolRegistry;
 use crate::tx_queue::TxQueueManager;
 use crate::wallet::WalletProvider;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
+use tokio_util::sync::CancellationToken;
 use strum::{EnumIter, IntoEnumIterator};
 
 /// Describes what kind of rich content a channel can render
@@ -115,6 +117,16 @@ impl ToolGroup {
         }
     }
 
+    /// Built-in execution time limit for tools in this group, enforced by the registry.
+    /// System covers `subagent` with wait (up to an hour); Exec/Development cover builds and deploys.
+    pub fn default_timeout_secs(&self) -> u64 {
+        match self {
+            ToolGroup::System => 3700,
+            ToolGroup::Exec | ToolGroup::Development => 900,
+            _ => 300,
+        }
+    }
+
     /// Parse from string (case-insensitive, with aliases)
     pub fn from_str(s: &str) -> Option<ToolGroup> {
         match s.to_lowercase().as_str() {
@@ -415,6 +427,12 @@ pub struct ToolContext {
     pub tool_http_client: Option<reqwest::Client>,
     /// Disk quota manager for enforcing disk usage limits
     pub disk_quota: Option<Arc<DiskQuotaManager>>,
+    /// Cancelled when the execution is stopped, so long-running tools can bail out early
+    pub cancellation_token: Option<CancellationToken>,
+    /// Tool registry, for tools that call other tools (see `execute_tool`)
+    pub tool_registry: Option<Arc<ToolRegistry>>,
+    /// Tool config of the dispatch, applied to tools run through `execute_tool`
+    pub tool_config: Option<Arc<ToolConfig>>,
 }
 
 impl std::fmt::Debug for ToolContext {
@@ -443,6 +461,9 @@ impl std::fmt::Debug for ToolContext {
             .field("proxy_url", &self.proxy_url)
             .field("tool_http_client", &self.tool_http_client.is_some())
             .field("disk_quota", &self.disk_quota.is_some())
+            .field("cancellation_token", &self.cancellation_token.is_some())
+            .field("tool_registry", &self.tool_registry.is_some())
+            .field("tool_config", &self.tool_config)
             .finish()
     }
 }
@@ -474,6 +495,9 @@ impl Default for ToolContext {
             proxy_url: None,
             tool_http_client: None,
             disk_quota: None,
+            cancellation_token: None,
+            tool_registry: None,
+            tool_config: None,
         }
     }
 }
@@ -582,6 +606,27 @@ impl ToolContext {
             .unwrap_or_default()
     }
 
+    /// Values of every API key in the context (runtime store and legacy `extra` entries).
+    /// Used to scrub secrets out of tool output before it's logged or returned.
+    pub fn api_key_values(&self) -> Vec<String> {
+        let mut values: Vec<String> = self
+            .api_keys
+            .read()
+            .ok()
+            .map(|store| store.values().cloned().collect())
+            .unwrap_or_default();
+        values.extend(
+            self.extra
+                .iter()
+                .filter(|(k, _)| k.starts_with("api_key_"))
+                .filter_map(|(_, v)| v.as_str().map(str::to_string)),
+        );
+        values.retain(|v| !v.is_empty());
+        values.sort();
+        values.dedup();
+        values
+    }
+
     /// Find a bot token from channel settings for a given channel type.
     /// First checks the current channel (if it matches the type), then falls back to any channel of that type.
     /// `setting_key` is the channel setting name (e.g. "discord_bot_token", "telegram_bot_token", "slack_bot_token").
@@ -660,6 +705,22 @@ impl ToolContext {
         self
     }
 
+    /// Add the ToolRegistry and the tool config it runs tools under (for tools that call other tools)
+    pub fn with_tool_registry(mut self, registry: Arc<ToolRegistry>, config: ToolConfig) -> Self {
+        self.tool_registry = Some(registry);
+        self.tool_config = Some(Arc::new(config));
+        self
+    }
+
+    /// Run another tool through the registry with this context, so the caller's tool
+    /// config, safe mode and timeouts apply to it as if the model had called it.
+    pub async fn execute_tool(&self, name: &str, params: Value) -> ToolResult {
+        match self.tool_registry {
+            Some(ref registry) => registry.execute(name, params, self, self.tool_config.as_deref()).await,
+            None => ToolResult::error(format!("Tool '{}' is not available here", name)),
+        }
+    }
+
     /// Add a TxQueueManager to the context (for web3 transaction queuing)
     pub fn with_tx_queue(mut self, tx_queue: Arc<TxQueueManager>) -> Self {
         self.tx_queue = Some(tx_queue);
@@ -678,28 +739,74 @@ impl ToolContext {
         self
     }
 
+    /// Add the execution's cancellation token (for stopping long-running tools)
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+
+    /// Whether the execution this context belongs to has been stopped
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation_token.as_ref().is_some_and(|token| token.is_cancelled())
+    }
+
     /// Add a DiskQuotaManager to the context (for enforcing disk usage limits)
     pub fn with_disk_quota(mut self, dq: Arc<DiskQuotaManager>) -> Self {
         self.disk_quota = Some(dq);
         self
     }
 
-    /// Check disk quota before a write. Returns Ok(()) or a human-readable error string.
+    /// Check disk quota (and the channel's quota, if any) before a write.
+    /// Returns Ok(()) or a human-readable error string.
     pub fn check_disk_quota(&self, bytes: usize) -> Result<(), String> {
-        if let Some(ref dq) = self.disk_quota {
-            dq.check_quota(bytes as u64).map_err(|e| e.to_string())
-        } else {
-            Ok(())
+        let Some(ref dq) = self.disk_quota else {
+            return Ok(());
+        };
+        match self.channel_id {
+            Some(channel_id) => dq.check_channel_quota(channel_id, bytes as u64),
+            None => dq.check_quota(bytes as u64),
         }
+        .map_err(|e| e.to_string())
     }
 
-    /// Record a successful write with the disk quota manager.
+    /// Record a successful write with the disk quota manager (not attributed to any channel).
     pub fn record_disk_write(&self, bytes: usize) {
         if let Some(ref dq) = self.disk_quota {
             dq.record_write(bytes as u64);
         }
     }
 
+    /// Record a successful write to `path` with the disk quota manager, attributing the file to the channel.
+    pub fn record_file_write(&self, path: &std::path::Path, bytes: usize) {
+        if let Some(ref dq) = self.disk_quota {
+            match self.channel_id {
+                Some(channel_id) => dq.record_channel_write(channel_id, path, bytes as u64),
+                None => dq.record_write(bytes as u64),
+            }
+        }
+    }
+
+    /// Start tracking a command (e.g. exec) whose writes count toward the channel's quota.
+    /// Only done when a per-channel quota is set, since finishing it walks the directory.
+    pub fn begin_channel_command(&self) -> Option<ChannelCommand> {
+        match (self.disk_quota.as_ref(), self.channel_id) {
+            (Some(dq), Some(channel_id)) if dq.channel_quota_bytes() > 0 => Some(dq.begin_channel_command(channel_id)),
+            _ => None,
+        }
+    }
+
+    /// Attribute files under `dir` the command created or changed to the channel.
+    /// The directory walk runs on the blocking pool.
+    pub async fn finish_channel_command(&self, command: Option<ChannelCommand>, dir: &std::path::Path) {
+        let (Some(command), Some(dq)) = (command, self.disk_quota.clone()) else {
+            return;
+        };
+        let dir = dir.to_path_buf();
+        if let Err(e) = tokio::task::spawn_blocking(move || dq.finish_channel_command(command, &dir)).await {
+            log::warn!("[DISK_QUOTA] Attributing command writes failed: {}", e);
+        }
+    }
+
     /// Set an HTTP proxy URL for tool requests. Builds a proxy-configured HTTP client.
     /// Does not affect AI model API calls (those use the global shared client directly).
     pub fn with_proxy_url(mut self, url: String) -> Self {
@@ -848,12 +955,14 @@ pub const SAFE_MODE_ALLOW_LIST: &[&str] = &[
     "discord_read",         // Read-only Discord operations (safe)
     "discord_lookup",       // Read-only Discord server/channel lookup (safe)
     "telegram_read",        // Read-only Telegram operations (safe)
+    "list_capabilities",    // Lists the tools already visible to the model (safe, no side effects)
 ];
 
 /// Tools whose sessions must NEVER be written to memory files.
 /// SECURITY: Prevents API keys and secrets from persisting in memory markdown files.
 pub const MEMORY_EXCLUDE_TOOL_LIST: &[&str] = &[
     "install_api_key",
+    "manage_api_key",
     "api_keys_check",
 ];
 
@@ -877,6 +986,15 @@ impl ToolConfig {
         }
     }
 
+    /// Switch to the safe mode config while keeping this config's explicitly
+    /// disabled tools. A channel's deny_list can only narrow safe mode, never widen it.
+    pub fn into_safe_mode(self) -> Self {
+        ToolConfig {
+            deny_list: self.deny_list,
+            ..Self::safe_mode()
+        }
+    }
+
     /// Check if a tool is allowed by this configuration
     pub fn is_tool_allowed(&self, tool_name: &str, tool_group: ToolGroup) -> bool {
         // Explicit deny takes precedence
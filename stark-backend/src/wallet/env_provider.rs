@@ -7,47 +7,12 @@
 use async_trait::async_trait;
 use ethers::core::k256::ecdsa::SigningKey;
 use ethers::signers::{LocalWallet, Signer};
-use ethers::types::{Address, H256, Signature, U256, transaction::eip2718::TypedTransaction};
-use ethers::utils::keccak256;
+use ethers::types::transaction::eip712::{Eip712, TypedData};
+use ethers::types::{H256, Signature, transaction::eip2718::TypedTransaction};
 
 use super::WalletProvider;
 use crate::config::env_vars;
 
-/// Compute EIP-712 domain separator from domain object
-fn compute_domain_separator(domain: &serde_json::Value) -> Result<H256, String> {
-    let type_hash = keccak256(
-        b"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)"
-    );
-
-    let name = domain.get("name").and_then(|v| v.as_str()).unwrap_or("");
-    let version = domain.get("version").and_then(|v| v.as_str()).unwrap_or("1");
-    let chain_id = domain.get("chainId")
-        .and_then(|v| v.as_u64())
-        .unwrap_or(1);
-    let verifying_contract = domain.get("verifyingContract")
-        .and_then(|v| v.as_str())
-        .unwrap_or("0x0000000000000000000000000000000000000000");
-
-    let name_hash = keccak256(name.as_bytes());
-    let version_hash = keccak256(version.as_bytes());
-
-    let contract_addr: Address = verifying_contract.parse()
-        .map_err(|e| format!("Invalid verifying contract address: {}", e))?;
-
-    let mut encoded = Vec::new();
-    encoded.extend_from_slice(&type_hash);
-    encoded.extend_from_slice(&name_hash);
-    encoded.extend_from_slice(&version_hash);
-    encoded.extend_from_slice(&ethers::abi::encode(&[
-        ethers::abi::Token::Uint(U256::from(chain_id)),
-    ]));
-    encoded.extend_from_slice(&ethers::abi::encode(&[
-        ethers::abi::Token::Address(contract_addr),
-    ]));
-
-    Ok(H256::from(keccak256(&encoded)))
-}
-
 /// Wallet provider that loads from environment variable
 pub struct EnvWalletProvider {
     wallet: LocalWallet,
@@ -132,36 +97,15 @@ impl WalletProvider for EnvWalletProvider {
             return self.sign_hash(hash).await;
         }
 
-        // Otherwise, compute EIP-712 hash from the typed data
-        // This requires domain, types, primaryType, and message
-        let domain = typed_data.get("domain")
-            .ok_or("Missing 'domain' in typed data")?;
-        let primary_type = typed_data.get("primaryType")
-            .and_then(|v| v.as_str())
-            .ok_or("Missing 'primaryType' in typed data")?;
-        let message = typed_data.get("message")
-            .ok_or("Missing 'message' in typed data")?;
-
-        // Compute domain separator
-        let domain_separator = compute_domain_separator(domain)?;
-
-        // Compute struct hash (simplified - just hash the JSON for now)
-        // In production, this should properly encode according to EIP-712
-        let struct_hash = H256::from(keccak256(
-            serde_json::to_string(message)
-                .map_err(|e| format!("Failed to serialize message: {}", e))?
-                .as_bytes()
-        ));
-
-        // Final hash: keccak256("\x19\x01" ++ domainSeparator ++ structHash)
-        let mut to_sign = Vec::with_capacity(66);
-        to_sign.push(0x19);
-        to_sign.push(0x01);
-        to_sign.extend_from_slice(domain_separator.as_bytes());
-        to_sign.extend_from_slice(struct_hash.as_bytes());
-        let digest = H256::from(keccak256(&to_sign));
-
-        self.sign_hash(digest).await
+        // Otherwise hash the typed data per EIP-712:
+        // keccak256("\x19\x01" ++ domainSeparator ++ hashStruct(message))
+        let typed: TypedData = serde_json::from_value(typed_data.clone())
+            .map_err(|e| format!("Invalid EIP-712 typed data: {}", e))?;
+        let digest = typed
+            .encode_eip712()
+            .map_err(|e| format!("Failed to hash typed data: {}", e))?;
+
+        self.sign_hash(H256::from(digest)).await
     }
 
     fn get_address(&self) -> String {
@@ -217,4 +161,37 @@ mod tests {
         assert!(signature.r != ethers::types::U256::zero());
         assert!(signature.s != ethers::types::U256::zero());
     }
+
+    #[tokio::test]
+    async fn test_sign_typed_data_uses_eip712_digest() {
+        let test_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+        let provider = EnvWalletProvider::from_private_key(test_key).unwrap();
+
+        let typed_data = serde_json::json!({
+            "types": {
+                "EIP712Domain": [
+                    {"name": "name", "type": "string"},
+                    {"name": "version", "type": "string"},
+                    {"name": "chainId", "type": "uint256"},
+                    {"name": "verifyingContract", "type": "address"}
+                ],
+                "Message": [{"name": "data", "type": "string"}]
+            },
+            "primaryType": "Message",
+            "domain": {
+                "name": "example.metamask.io",
+                "version": "1",
+                "chainId": "1",
+                "verifyingContract": "0x0000000000000000000000000000000000000000"
+            },
+            "message": {"data": "Hello!"}
+        });
+        // Reference digest for this payload (eth-sig-util / ethers test vector)
+        let digest: H256 = "0x232cd3ec058eb935a709f093e3536ce26cc9e8e193584b0881992525f6236eef"
+            .parse()
+            .unwrap();
+
+        let signature = provider.sign_typed_data(&typed_data).await.unwrap();
+        assert_eq!(signature, provider.sign_hash(digest).await.unwrap());
+    }
 }
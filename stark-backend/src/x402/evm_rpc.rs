@@ -67,6 +67,25 @@ pub struct TransactionReceipt {
     pub logs: Vec<TxLog>,
 }
 
+/// Transaction from eth_getTransactionByHash
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcTransaction {
+    pub hash: H256,
+    pub nonce: U256,
+    pub from: Address,
+    pub to: Option<Address>,
+    pub value: U256,
+    pub input: Bytes,
+    pub gas: U256,
+    /// Set for legacy transactions (and as the effective price on some nodes)
+    pub gas_price: Option<U256>,
+    pub max_fee_per_gas: Option<U256>,
+    pub max_priority_fee_per_gas: Option<U256>,
+    /// None while the transaction is pending
+    pub block_number: Option<U64>,
+}
+
 /// A single log entry from a transaction receipt
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -148,6 +167,13 @@ impl X402EvmRpc {
 
     /// Make a JSON-RPC call via x402 or regular HTTP depending on config
     async fn rpc_call(&self, method: &str, params: Value) -> Result<Value, String> {
+        self.rpc_call_nullable(method, params)
+            .await?
+            .ok_or_else(|| "RPC returned null result".to_string())
+    }
+
+    /// Make a JSON-RPC call where a null result is meaningful (e.g. unknown tx hash)
+    async fn rpc_call_nullable(&self, method: &str, params: Value) -> Result<Option<Value>, String> {
         let request = JsonRpcRequest {
             jsonrpc: "2.0",
             method: method.to_string(),
@@ -179,7 +205,7 @@ impl X402EvmRpc {
             return Err(format!("RPC error {}: {}", error.code, error.message));
         }
 
-        rpc_response.result.ok_or_else(|| "RPC returned null result".to_string())
+        Ok(rpc_response.result.filter(|v| !v.is_null()))
     }
 
     /// Get ETH balance of an address
@@ -297,11 +323,10 @@ impl X402EvmRpc {
     pub async fn get_transaction_receipt(&self, tx_hash: H256) -> Result<Option<TransactionReceipt>, String> {
         let params = json!([format!("{:?}", tx_hash)]);
 
-        let result = self.rpc_call("eth_getTransactionReceipt", params).await?;
-
-        if result.is_null() {
-            return Ok(None);
-        }
+        let result = match self.rpc_call_nullable("eth_getTransactionReceipt", params).await? {
+            Some(r) => r,
+            None => return Ok(None),
+        };
 
         let receipt: TransactionReceipt = serde_json::from_value(result)
             .map_err(|e| format!("Failed to parse receipt: {}", e))?;
@@ -309,6 +334,34 @@ impl X402EvmRpc {
         Ok(Some(receipt))
     }
 
+    /// Get a transaction by hash (None if the node doesn't know it, e.g. dropped from the mempool)
+    pub async fn get_transaction_by_hash(&self, tx_hash: H256) -> Result<Option<RpcTransaction>, String> {
+        let params = json!([format!("{:?}", tx_hash)]);
+
+        let result = match self.rpc_call_nullable("eth_getTransactionByHash", params).await? {
+            Some(r) => r,
+            None => return Ok(None),
+        };
+
+        let tx: RpcTransaction = serde_json::from_value(result)
+            .map_err(|e| format!("Failed to parse transaction: {}", e))?;
+
+        Ok(Some(tx))
+    }
+
+    /// Get the number of mined transactions for an address (excludes pending ones)
+    pub async fn get_confirmed_transaction_count(&self, address: Address) -> Result<U256, String> {
+        let params = json!([format!("{:?}", address), "latest"]);
+
+        let result = self.rpc_call("eth_getTransactionCount", params).await?;
+
+        let hex_str = result.as_str()
+            .ok_or_else(|| "Invalid getTransactionCount response".to_string())?;
+
+        U256::from_str_radix(hex_str.trim_start_matches("0x"), 16)
+            .map_err(|e| format!("Failed to parse nonce: {}", e))
+    }
+
     /// Get transaction count (nonce) for an address
     pub async fn get_transaction_count(&self, address: Address) -> Result<U256, String> {
         let params = json!([format!("{:?}", address), "pending"]);
@@ -21,4 +21,4 @@ pub mod payment_limits;
 pub use types::*;
 pub use client::{X402Client, X402Response, X402RetryResult, is_x402_endpoint, sign_402_payment, retry_with_x402_payment, check_usdc_balance};
 pub use signer::X402Signer;
-pub use evm_rpc::{TxLog, X402EvmRpc};
+pub use evm_rpc::{RpcTransaction, TxLog, X402EvmRpc};
@@ -0,0 +1,123 @@
+import { useState, useEffect } from 'react';
+import Modal from '../ui/Modal';
+import Button from '../ui/Button';
+import { AlertTriangle, Check, X, Loader2 } from 'lucide-react';
+import { getGateway } from '@/lib/gateway-client';
+
+export interface SignMessageRequest {
+  uuid: string;
+  scheme: string;
+  address: string;
+  payload: unknown;
+}
+
+interface SignMessageConfirmationModalProps {
+  isOpen: boolean;
+  onClose: () => void;
+  channelId: number;
+  request: SignMessageRequest | null;
+}
+
+export default function SignMessageConfirmationModal({
+  isOpen,
+  onClose,
+  channelId,
+  request
+}: SignMessageConfirmationModalProps) {
+  const [isLoading, setIsLoading] = useState<'confirm' | 'deny' | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  // Auto-clear error after 3 seconds
+  useEffect(() => {
+    if (error) {
+      const timer = setTimeout(() => setError(null), 3000);
+      return () => clearTimeout(timer);
+    }
+  }, [error]);
+
+  const resolve = async (action: 'confirm' | 'deny') => {
+    if (!request) return;
+    setIsLoading(action);
+    setError(null);
+    try {
+      await getGateway().call(`sign_message.${action}`, {
+        uuid: request.uuid,
+        channel_id: channelId
+      });
+      onClose();
+    } catch (err) {
+      setError(err instanceof Error ? err.message : `Failed to ${action} signature request`);
+    } finally {
+      setIsLoading(null);
+    }
+  };
+
+  if (!request) return null;
+
+  // eip191 payloads are the message itself; eip712 payloads are typed data
+  const payloadText = typeof request.payload === 'string'
+    ? request.payload
+    : JSON.stringify(request.payload, null, 2);
+
+  return (
+    <Modal isOpen={isOpen} onClose={() => {}} title="Confirm Signature" size="md">
+      <div className="space-y-4">
+        <div className="flex items-start gap-3">
+          <AlertTriangle className="w-6 h-6 text-amber-400 flex-shrink-0 mt-0.5" />
+          <div>
+            <h3 className="text-white font-medium">Sign Message?</h3>
+            <p className="text-slate-400 text-sm mt-1">
+              Partner mode requires your approval before the wallet signs anything.
+            </p>
+          </div>
+        </div>
+
+        <div className="bg-slate-700/50 rounded-lg p-4 space-y-3">
+          <div className="flex justify-between text-sm">
+            <span className="text-slate-400">Signer</span>
+            <span className="text-white font-mono">{request.address}</span>
+          </div>
+          <div className="flex justify-between text-sm">
+            <span className="text-slate-400">Scheme</span>
+            <span className="text-white">{request.scheme}</span>
+          </div>
+          <pre className="text-xs text-slate-300 bg-slate-900/50 rounded p-2 max-h-64 overflow-auto whitespace-pre-wrap break-all">
+            {payloadText}
+          </pre>
+        </div>
+
+        {error && (
+          <div className="text-red-400 text-sm bg-red-900/20 p-2 rounded">{error}</div>
+        )}
+
+        <div className="flex gap-3 pt-2">
+          <Button
+            onClick={() => resolve('confirm')}
+            disabled={isLoading !== null}
+            className="flex-1 bg-green-600 hover:bg-green-700"
+          >
+            {isLoading === 'confirm' ? (
+              <Loader2 className="w-4 h-4 animate-spin mr-2" />
+            ) : (
+              <Check className="w-4 h-4 mr-2" />
+            )}
+            Sign
+          </Button>
+          <Button
+            onClick={() => resolve('deny')}
+            disabled={isLoading !== null}
+            variant="secondary"
+            className="flex-1 border border-red-600 text-red-400 hover:bg-red-900/20"
+          >
+            {isLoading === 'deny' ? (
+              <Loader2 className="w-4 h-4 animate-spin mr-2" />
+            ) : (
+              <X className="w-4 h-4 mr-2" />
+            )}
+            Deny
+          </Button>
+        </div>
+      </div>
+    </Modal>
+  );
+}
@@ -104,6 +104,11 @@ export default function SubagentBadge({ subagents, onSubagentCancelled }: Subage
                     <p className="text-xs text-slate-400 mt-0.5 line-clamp-2">
                       {subagent.task}
                     </p>
+                    {subagent.activity && (
+                      <p className="text-xs text-blue-400 mt-0.5 truncate">
+                        {subagent.activity}
+                      </p>
+                    )}
                     <p className="text-xs text-slate-500 mt-1">
                       Started {formatTime(subagent.started_at)}
                     </p>
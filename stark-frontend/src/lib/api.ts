@@ -151,11 +151,24 @@ interface ToolInfo {
   group: string;
   enabled: boolean;
   safety_level: string;
+  input_schema: {
+    type: string;
+    properties: Record<string, unknown>;
+    required: string[];
+  };
+}
+
+export interface ToolGroupSection {
+  key: string;
+  label: string;
+  description: string;
+  tools: ToolInfo[];
 }
 
 interface ToolsListResponse {
   success: boolean;
   tools?: ToolInfo[];
+  groups?: ToolGroupSection[];
   error?: string;
 }
 
@@ -164,6 +177,11 @@ export async function getTools(): Promise<ToolInfo[]> {
   return response.tools || [];
 }
 
+export async function getToolsByGroup(): Promise<ToolGroupSection[]> {
+  const response = await apiFetch<ToolsListResponse>('/tools?grouped=true');
+  return response.groups || [];
+}
+
 export interface ToolGroupInfo {
   key: string;
   label: string;
@@ -661,6 +679,7 @@ export interface ChannelInfo {
   app_token?: string;
   created_at: string;
   updated_at: string;
+  deleted_at?: string;
   running?: boolean;
 }
 
@@ -760,6 +779,29 @@ export async function deleteChannel(id: number): Promise<void> {
   }
 }
 
+export async function getDeletedChannels(): Promise<ChannelInfo[]> {
+  const response = await apiFetch<ChannelsListResponse>('/channels/deleted');
+  return response.channels || [];
+}
+
+export async function restoreChannel(id: number): Promise<void> {
+  const response = await apiFetch<ChannelOperationResponse>(`/channels/${id}/restore`, {
+    method: 'POST',
+  });
+  if (!response.success) {
+    throw new Error(response.error || 'Failed to restore channel');
+  }
+}
+
+export async function purgeChannel(id: number): Promise<void> {
+  const response = await apiFetch<ChannelOperationResponse>(`/channels/${id}/purge`, {
+    method: 'DELETE',
+  });
+  if (!response.success) {
+    throw new Error(response.error || 'Failed to purge channel');
+  }
+}
+
 export async function startChannel(id: number): Promise<ChannelInfo> {
   const response = await apiFetch<ChannelOperationResponse>(`/channels/${id}/start`, {
     method: 'POST',
@@ -1356,6 +1398,11 @@ export type SubagentInfo = Subagent;
 export interface SubagentListResponse {
   success: boolean;
   subagents: SubagentInfo[];
+  running: number;
+  queued: number;
+  total_running: number;
+  max_concurrent_per_channel: number;
+  max_total_concurrent: number;
 }
 
 export interface SubagentResponse {
@@ -1368,6 +1415,45 @@ export async function listSubagents(): Promise<SubagentListResponse> {
   return apiFetch('/chat/subagents');
 }
 
+export interface SubagentResultResponse {
+  success: boolean;
+  id?: string;
+  label?: string;
+  status?: SubagentStatus;
+  // True while the subagent is queued or running
+  pending: boolean;
+  result?: string;
+  started_at?: string;
+  completed_at?: string;
+  error?: string;
+}
+
+export async function getSubagent(subagentId: string): Promise<SubagentResultResponse> {
+  return apiFetch(`/chat/subagents/${encodeURIComponent(subagentId)}`);
+}
+
+export interface WebSystemPrompt {
+  system_prompt: string;
+  mode?: 'prepend' | 'replace';
+}
+
+export interface WebSystemPromptResponse {
+  success: boolean;
+  prompt?: WebSystemPrompt;
+  error?: string;
+}
+
+export async function getWebSystemPrompt(): Promise<WebSystemPromptResponse> {
+  return apiFetch('/chat/system-prompt');
+}
+
+export async function updateWebSystemPrompt(prompt: WebSystemPrompt): Promise<WebSystemPromptResponse> {
+  return apiFetch('/chat/system-prompt', {
+    method: 'PUT',
+    body: JSON.stringify(prompt),
+  });
+}
+
 export async function cancelSubagent(subagentId: string): Promise<SubagentResponse> {
   return apiFetch('/chat/subagents/cancel', {
     method: 'POST',
@@ -1822,6 +1908,23 @@ export async function getSystemInfo(): Promise<SystemInfoResponse> {
   return apiFetch('/system/info');
 }
 
+export interface DiskUsageResponse {
+  enabled: boolean;
+  used_bytes: number;
+  quota_bytes: number;
+  channel_quota_bytes: number;
+  channels: {
+    channel_id: number;
+    channel_name?: string;
+    used_bytes: number;
+    percentage: number;
+  }[];
+}
+
+export async function getDiskUsage(): Promise<DiskUsageResponse> {
+  return apiFetch('/system/disk-usage');
+}
+
 export async function cleanupMemories(olderThanDays: number): Promise<CleanupResult> {
   return apiFetch('/system/cleanup/memories', {
     method: 'POST',
@@ -1829,10 +1932,10 @@ export async function cleanupMemories(olderThanDays: number): Promise<CleanupRes
   });
 }
 
-export async function cleanupWorkspace(): Promise<CleanupResult> {
+export async function cleanupWorkspace(olderThanDays?: number): Promise<CleanupResult> {
   return apiFetch('/system/cleanup/workspace', {
     method: 'POST',
-    body: JSON.stringify({ confirm: true }),
+    body: JSON.stringify({ confirm: true, older_than_days: olderThanDays }),
   });
 }
 
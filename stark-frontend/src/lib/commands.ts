@@ -1,4 +1,4 @@
-import { LucideIcon, HelpCircle, Activity, Plus, RefreshCw, Trash2, Wand2, Wrench, Cpu, Download, Bug, Check, X, Square } from 'lucide-react';
+import { LucideIcon, HelpCircle, Activity, Plus, RefreshCw, Trash2, Wand2, Wrench, Cpu, Download, Bug, Check, X, Square, FastForward } from 'lucide-react';
 
 // Command enum - single source of truth for all command names
 export enum Command {
@@ -8,6 +8,7 @@ export enum Command {
   Reset = 'reset',
   Clear = 'clear',
   Stop = 'stop',
+  Continue = 'continue',
   Skills = 'skills',
   Tools = 'tools',
   Model = 'model',
@@ -69,6 +70,13 @@ export const COMMAND_DEFINITIONS: Record<Command, CommandDefinition> = {
     icon: Square,
     category: 'session',
   },
+  [Command.Continue]: {
+    command: Command.Continue,
+    name: 'continue',
+    description: 'Get the rest of a reply that was cut off',
+    icon: FastForward,
+    category: 'session',
+  },
   [Command.Skills]: {
     command: Command.Skills,
     name: 'skills',
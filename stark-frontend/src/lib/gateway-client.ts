@@ -226,7 +226,7 @@ export class GatewayClient {
 
   private emitEvent(event: string, data: unknown): void {
     // Log all events for debugging (except high-frequency ones)
-    if (!['agent.thinking'].includes(event)) {
+    if (!['agent.thinking', 'agent.heartbeat'].includes(event)) {
       console.log(`[Gateway] Event received: ${event}`, data);
     }
 
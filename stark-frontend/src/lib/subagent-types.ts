@@ -15,6 +15,8 @@ export interface Subagent {
   task: string;
   status: SubagentStatus;
   started_at: string;
+  // Latest tool activity streamed via subagent.progress (e.g. "calling web_search")
+  activity?: string;
 }
 
 // Helper to check if a subagent is active (running or pending)
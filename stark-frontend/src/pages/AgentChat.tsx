@@ -11,6 +11,7 @@ import CommandMenu from '@/components/chat/CommandMenu';
 import TransactionTracker from '@/components/chat/TransactionTracker';
 import { ConfirmationPrompt } from '@/components/chat/ConfirmationPrompt';
 import TxQueueConfirmationModal, { TxQueueTransaction } from '@/components/chat/TxQueueConfirmationModal';
+import SignMessageConfirmationModal, { SignMessageRequest } from '@/components/chat/SignMessageConfirmationModal';
 import SubagentBadge from '@/components/chat/SubagentBadge';
 import { Subagent, SubagentStatus } from '@/lib/subagent-types';
 import { useGateway } from '@/hooks/useGateway';
@@ -34,6 +35,9 @@ const STORAGE_KEY_SESSION_ID = 'agentChat_sessionId';
 // Web channel ID - must match backend WEB_CHANNEL_ID
 const WEB_CHANNEL_ID = 0;
 
+// A running dispatch with no agent.heartbeat for this long is shown as stalled
+const HEARTBEAT_STALL_MS = 30000;
+
 // Helper to check if an event is for the web channel
 function isWebChannelEvent(data: unknown): boolean {
   if (typeof data !== 'object' || data === null) return true; // Allow events without channel_id
@@ -100,6 +104,8 @@ export default function AgentChat() {
   );
   const [input, setInput] = useState('');
   const [isLoading, setIsLoading] = useState(false);
+  const [heartbeatStalled, setHeartbeatStalled] = useState(false);
+  const lastHeartbeatRef = useRef<number | null>(null);
   const [activeExecutionId, setActiveExecutionId] = useState<string | null>(null);
   const [isStopping, setIsStopping] = useState(false);
   const [showAutocomplete, setShowAutocomplete] = useState(false);
@@ -110,6 +116,7 @@ export default function AgentChat() {
   const [trackedTxs, setTrackedTxs] = useState<TrackedTransaction[]>([]);
   const [pendingConfirmation, setPendingConfirmation] = useState<PendingConfirmation | null>(null);
   const [txQueueConfirmation, setTxQueueConfirmation] = useState<TxQueueTransaction | null>(null);
+  const [signMessageConfirmation, setSignMessageConfirmation] = useState<SignMessageRequest | null>(null);
   const [subagents, setSubagents] = useState<Subagent[]>([]);
   const [plannerTasks, setPlannerTasks] = useState<PlannerTask[]>([]);
   const [cronExecutionActive, setCronExecutionActive] = useState<{
@@ -672,6 +679,37 @@ export default function AgentChat() {
     };
   }, [on, off, sessionId, dbSessionId]);
 
+  // Heartbeats arrive every ~10s while the backend is working on a dispatch
+  useEffect(() => {
+    const handleHeartbeat = (data: unknown) => {
+      if (!isCurrentSessionEvent(data, dbSessionId)) return;
+      lastHeartbeatRef.current = Date.now();
+      setHeartbeatStalled(false);
+    };
+
+    on('agent.heartbeat', handleHeartbeat);
+    return () => {
+      off('agent.heartbeat', handleHeartbeat);
+    };
+  }, [on, off, dbSessionId]);
+
+  // Flag the run as stalled when heartbeats stop while it's still loading
+  useEffect(() => {
+    if (!isLoading) {
+      lastHeartbeatRef.current = null;
+      setHeartbeatStalled(false);
+      return;
+    }
+
+    const interval = setInterval(() => {
+      const last = lastHeartbeatRef.current;
+      if (last !== null && Date.now() - last > HEARTBEAT_STALL_MS) {
+        setHeartbeatStalled(true);
+      }
+    }, 5000);
+    return () => clearInterval(interval);
+  }, [isLoading]);
+
   // Listen for execution lifecycle events to track loading state
   useEffect(() => {
     const handleExecutionStarted = (data: unknown) => {
@@ -839,6 +877,43 @@ export default function AgentChat() {
     };
   }, [on, off, dbSessionId]);
 
+  // Listen for sign_message confirmation events (partner mode)
+  useEffect(() => {
+    const handleSignMessageConfirmationRequired = (data: unknown) => {
+      if (!isCurrentSessionEvent(data, dbSessionId)) return;
+      const event = data as {
+        channel_id: number;
+        uuid: string;
+        scheme: string;
+        address: string;
+        payload: unknown;
+      };
+      if (event.channel_id === WEB_CHANNEL_ID) {
+        setSignMessageConfirmation({
+          uuid: event.uuid,
+          scheme: event.scheme,
+          address: event.address,
+          payload: event.payload,
+        });
+      }
+    };
+
+    const handleSignMessageResolved = (data: unknown) => {
+      if (!isCurrentSessionEvent(data, dbSessionId)) return;
+      setSignMessageConfirmation(null);
+    };
+
+    on('sign_message.confirmation_required', handleSignMessageConfirmationRequired);
+    on('sign_message.signed', handleSignMessageResolved);
+    on('sign_message.denied', handleSignMessageResolved);
+
+    return () => {
+      off('sign_message.confirmation_required', handleSignMessageConfirmationRequired);
+      off('sign_message.signed', handleSignMessageResolved);
+      off('sign_message.denied', handleSignMessageResolved);
+    };
+  }, [on, off, dbSessionId]);
+
   // Listen for subagent events
   useEffect(() => {
     const handleSubagentSpawned = (data: unknown) => {
@@ -881,14 +956,28 @@ export default function AgentChat() {
       ));
     };
 
+    const handleSubagentProgress = (data: unknown) => {
+      if (!isCurrentSessionEvent(data, dbSessionId)) return;
+
+      const event = data as { subagent_id: string; phase: string; tool_name: string; success?: boolean };
+      const activity = event.phase === 'tool_call'
+        ? `calling ${event.tool_name}`
+        : `${event.tool_name} ${event.success ? 'done' : 'failed'}`;
+      setSubagents((prev) => prev.map(s =>
+        s.id === event.subagent_id ? { ...s, activity } : s
+      ));
+    };
+
     on('subagent.spawned', handleSubagentSpawned);
     on('subagent.completed', handleSubagentCompleted);
     on('subagent.failed', handleSubagentFailed);
+    on('subagent.progress', handleSubagentProgress);
 
     return () => {
       off('subagent.spawned', handleSubagentSpawned);
       off('subagent.completed', handleSubagentCompleted);
       off('subagent.failed', handleSubagentFailed);
+      off('subagent.progress', handleSubagentProgress);
     };
   }, [on, off, dbSessionId]);
 
@@ -1031,6 +1120,77 @@ export default function AgentChat() {
     }
   }, [sessionId]);
 
+  // Send text to the agent and show the reply. `showAsUser` is off for commands
+  // the backend handles (e.g. /continue), which are already shown as a command.
+  const sendToAgent = useCallback(async (text: string, showAsUser = true) => {
+    if (showAsUser) addMessage('user', text);
+    setIsLoading(true);
+
+    try {
+      const response = await sendChatMessage(text, conversationHistory.current, currentNetwork?.name);
+      // Remove "still thinking" progress messages before adding the response
+      setMessages((prev) => prev.filter(
+        (m) => !(m.role === 'system' && m.content.startsWith('Still thinking'))
+      ));
+      // Skip empty responses and responses already delivered via say_to_user WebSocket event
+      if (response.response.trim()) {
+        // Check if say_to_user already delivered this content via real-time event
+        setMessages((prev) => {
+          const alreadyDelivered = prev.some(
+            (m) => m.role === 'assistant' && m.content === response.response.trim()
+          );
+          if (alreadyDelivered) return prev;
+          return [...prev, {
+            id: crypto.randomUUID(),
+            role: 'assistant' as MessageRole,
+            content: response.response,
+            timestamp: new Date(),
+            sessionId,
+          }];
+        });
+      }
+    } catch (error) {
+      const errorMsg = error instanceof Error ? error.message : 'Failed to send message';
+      addMessage('error', errorMsg);
+
+      // Detect AI endpoint errors that warrant a hint
+      const errorLower = errorMsg.toLowerCase();
+      const isServerError = /\b(50[0-9]|502|503|504)\b/.test(errorMsg)
+        || errorLower.includes('bad gateway')
+        || errorLower.includes('service unavailable')
+        || errorLower.includes('gateway timeout')
+        || errorLower.includes('internal server error');
+      const isConnectionError = errorLower.includes('connection refused')
+        || errorLower.includes('connection reset')
+        || errorLower.includes('econnrefused')
+        || errorLower.includes('timed out');
+      const isX402Failure = errorLower.includes('x402')
+        || errorLower.includes('402')
+        || errorLower.includes('payment');
+      const isAuthError = errorLower.includes('401')
+        || errorLower.includes('403')
+        || errorLower.includes('unauthorized')
+        || errorLower.includes('forbidden');
+
+      if (isServerError || isConnectionError || isX402Failure || isAuthError) {
+        const balanceNum = usdcBalance ? parseFloat(usdcBalance) : null;
+        const isLowBalance = balanceNum !== null && balanceNum < 0.1;
+
+        let hint = '**Tip:** Go to **Agent Settings** and try switching to a different AI model or endpoint.';
+        if (isX402Failure || isLowBalance) {
+          const balanceStr = balanceNum !== null ? ` (current balance: ${balanceNum.toFixed(4)} USDC)` : '';
+          hint += `\n\nAlso make sure you have enough **USDC on Base** to cover x402 endpoint micropayments${balanceStr}.`;
+        } else if (isServerError || isConnectionError) {
+          hint += '\n\nIf you\'re using an x402 pay-per-call endpoint, also check your **USDC balance on Base** — insufficient funds can cause failures.';
+        }
+
+        addMessage('hint' as MessageRole, hint);
+      }
+    } finally {
+      setIsLoading(false);
+    }
+  }, [addMessage, sessionId, currentNetwork, usdcBalance]);
+
   // Command handlers map - uses Command enum for type safety
   const commandHandlers: Record<Command, () => void | Promise<void>> = {
     [Command.Help]: () => {
@@ -1225,6 +1385,13 @@ export default function AgentChat() {
         addMessage('error', error instanceof Error ? error.message : 'Failed to cancel transaction');
       }
     },
+    [Command.Continue]: async () => {
+      if (isLoading) {
+        addMessage('system', 'Wait for the current reply to finish first.');
+        return;
+      }
+      await sendToAgent('/continue', false);
+    },
     [Command.Stop]: async () => {
       const hasRunningSubagents = subagents.some(s => s.status === SubagentStatus.Running);
       if (!isLoading && !hasRunningSubagents) {
@@ -1287,73 +1454,8 @@ export default function AgentChat() {
     }
 
     // Regular message
-    addMessage('user', trimmedInput);
-    setIsLoading(true);
-
-    try {
-      const response = await sendChatMessage(trimmedInput, conversationHistory.current, currentNetwork?.name);
-      // Remove "still thinking" progress messages before adding the response
-      setMessages((prev) => prev.filter(
-        (m) => !(m.role === 'system' && m.content.startsWith('Still thinking'))
-      ));
-      // Skip empty responses and responses already delivered via say_to_user WebSocket event
-      if (response.response.trim()) {
-        // Check if say_to_user already delivered this content via real-time event
-        setMessages((prev) => {
-          const alreadyDelivered = prev.some(
-            (m) => m.role === 'assistant' && m.content === response.response.trim()
-          );
-          if (alreadyDelivered) return prev;
-          return [...prev, {
-            id: crypto.randomUUID(),
-            role: 'assistant' as MessageRole,
-            content: response.response,
-            timestamp: new Date(),
-            sessionId,
-          }];
-        });
-      }
-    } catch (error) {
-      const errorMsg = error instanceof Error ? error.message : 'Failed to send message';
-      addMessage('error', errorMsg);
-
-      // Detect AI endpoint errors that warrant a hint
-      const errorLower = errorMsg.toLowerCase();
-      const isServerError = /\b(50[0-9]|502|503|504)\b/.test(errorMsg)
-        || errorLower.includes('bad gateway')
-        || errorLower.includes('service unavailable')
-        || errorLower.includes('gateway timeout')
-        || errorLower.includes('internal server error');
-      const isConnectionError = errorLower.includes('connection refused')
-        || errorLower.includes('connection reset')
-        || errorLower.includes('econnrefused')
-        || errorLower.includes('timed out');
-      const isX402Failure = errorLower.includes('x402')
-        || errorLower.includes('402')
-        || errorLower.includes('payment');
-      const isAuthError = errorLower.includes('401')
-        || errorLower.includes('403')
-        || errorLower.includes('unauthorized')
-        || errorLower.includes('forbidden');
-
-      if (isServerError || isConnectionError || isX402Failure || isAuthError) {
-        const balanceNum = usdcBalance ? parseFloat(usdcBalance) : null;
-        const isLowBalance = balanceNum !== null && balanceNum < 0.1;
-
-        let hint = '**Tip:** Go to **Agent Settings** and try switching to a different AI model or endpoint.';
-        if (isX402Failure || isLowBalance) {
-          const balanceStr = balanceNum !== null ? ` (current balance: ${balanceNum.toFixed(4)} USDC)` : '';
-          hint += `\n\nAlso make sure you have enough **USDC on Base** to cover x402 endpoint micropayments${balanceStr}.`;
-        } else if (isServerError || isConnectionError) {
-          hint += '\n\nIf you\'re using an x402 pay-per-call endpoint, also check your **USDC balance on Base** — insufficient funds can cause failures.';
-        }
-
-        addMessage('hint' as MessageRole, hint);
-      }
-    } finally {
-      setIsLoading(false);
-    }
-  }, [input, isLoading, addMessage, handleCommand]);
+    await sendToAgent(trimmedInput);
+  }, [input, isLoading, handleCommand, sendToAgent]);
 
   const handleKeyDown = useCallback((e: KeyboardEvent<HTMLTextAreaElement>) => {
     // Handle autocomplete navigation
@@ -1723,6 +1825,11 @@ export default function AgentChat() {
                 />
               ))}
             {isLoading && <TypingIndicator />}
+            {isLoading && heartbeatStalled && (
+              <div className="text-xs text-amber-400 px-2 py-1">
+                No heartbeat from the backend for over {HEARTBEAT_STALL_MS / 1000}s, the run may be stalled.
+              </div>
+            )}
           </>
         )}
         <div ref={messagesEndRef} />
@@ -1774,6 +1881,14 @@ export default function AgentChat() {
         transaction={txQueueConfirmation}
       />
 
+      {/* Signature Confirmation Modal (Partner Mode) */}
+      <SignMessageConfirmationModal
+        isOpen={signMessageConfirmation !== null}
+        onClose={() => setSignMessageConfirmation(null)}
+        channelId={WEB_CHANNEL_ID}
+        request={signMessageConfirmation}
+      />
+
       {/* Pending Transaction Indicator Bar (Partner Mode) */}
       {txQueueConfirmation && (
         <div className="mx-6 mb-2 p-3 bg-amber-500/10 border border-amber-500/50 rounded-lg">
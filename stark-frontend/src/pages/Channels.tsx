@@ -1,5 +1,5 @@
 import { useState, useEffect } from 'react';
-import { MessageSquare, Hash, Plus, Play, Square, Trash2, Save, Pencil, Twitter, AlertTriangle, Terminal, Dices, Copy, Check } from 'lucide-react';
+import { MessageSquare, Hash, Plus, Play, Square, Trash2, Save, Pencil, Twitter, AlertTriangle, Terminal, Dices, Copy, Check, Webhook, RotateCcw } from 'lucide-react';
 import Card, { CardContent, CardHeader, CardTitle } from '@/components/ui/Card';
 import Button from '@/components/ui/Button';
 import Input from '@/components/ui/Input';
@@ -8,6 +8,9 @@ import {
   createChannel,
   updateChannel,
   deleteChannel,
+  getDeletedChannels,
+  restoreChannel,
+  purgeChannel,
   startChannel,
   stopChannel,
   getChannelSettings,
@@ -26,6 +29,7 @@ const CHANNEL_TYPES = [
   { value: 'discord', label: 'Discord', icon: MessageSquare, color: 'indigo' },
   { value: 'twitter', label: 'Twitter / X', icon: Twitter, color: 'sky' },
   { value: 'external_channel', label: 'External Channel', icon: Terminal, color: 'emerald' },
+  { value: 'webhook', label: 'Webhook', icon: Webhook, color: 'amber' },
 ];
 
 function getChannelHints(channelType: string): string[] {
@@ -51,6 +55,12 @@ function getChannelHints(channelType: string): string[] {
         'Generate a secure API Token in settings after creation. The token authenticates external clients.',
         'Safe mode is off by default — enable it in settings to restrict tool access for untrusted input.',
       ];
+    case 'webhook':
+      return [
+        'Webhook lets any app POST JSON (<code>text</code>, <code>user_id</code>, <code>user_name</code>, optional <code>chat_id</code>) to <code>/api/channels/&lt;id&gt;/webhook</code> and get the agent\'s reply in the response.',
+        'Generate a Webhook Secret in settings after creation and send it in the <code>X-Webhook-Secret</code> header. Requests are rejected until a secret is set.',
+        'Safe mode is on by default — disable it only if every app holding the secret is trusted.',
+      ];
     default:
       return [];
   }
@@ -268,6 +278,7 @@ function TwitterKeyWarning({ apiKeys }: { apiKeys: ApiKey[] }) {
 
 export default function Channels() {
   const [channels, setChannels] = useState<ChannelInfo[]>([]);
+  const [deletedChannels, setDeletedChannels] = useState<ChannelInfo[]>([]);
   const [isLoading, setIsLoading] = useState(true);
   const [error, setError] = useState<string | null>(null);
   const [showAddForm, setShowAddForm] = useState(false);
@@ -282,8 +293,9 @@ export default function Channels() {
 
   const fetchChannels = async () => {
     try {
-      const data = await getChannels();
+      const [data, deleted] = await Promise.all([getChannels(), getDeletedChannels()]);
       setChannels(data);
+      setDeletedChannels(deleted);
       setError(null);
     } catch (e) {
       setError(e instanceof Error ? e.message : 'Failed to load channels');
@@ -366,7 +378,7 @@ export default function Channels() {
   };
 
   const handleDelete = async (id: number) => {
-    if (!confirm('Are you sure you want to delete this channel?')) return;
+    if (!confirm('Delete this channel? It can be restored from "Recently Deleted" until purged.')) return;
 
     setActionLoading(id);
     try {
@@ -379,6 +391,32 @@ export default function Channels() {
     }
   };
 
+  const handleRestore = async (id: number) => {
+    setActionLoading(id);
+    try {
+      await restoreChannel(id);
+      await fetchChannels();
+    } catch (e) {
+      setError(e instanceof Error ? e.message : 'Failed to restore channel');
+    } finally {
+      setActionLoading(null);
+    }
+  };
+
+  const handlePurge = async (id: number) => {
+    if (!confirm('Permanently delete this channel, its bot token and settings? This cannot be undone.')) return;
+
+    setActionLoading(id);
+    try {
+      await purgeChannel(id);
+      await fetchChannels();
+    } catch (e) {
+      setError(e instanceof Error ? e.message : 'Failed to purge channel');
+    } finally {
+      setActionLoading(null);
+    }
+  };
+
   const handleStart = async (id: number) => {
     setActionLoading(id);
     try {
@@ -572,7 +610,27 @@ export default function Channels() {
                           </select>
                           <p className="mt-1 text-xs text-slate-500">{setting.description}</p>
                         </>
-                      ) : setting.key === 'external_channel_api_token' ? (
+                      ) : setting.input_type === 'text_area' ? (
+                        <>
+                          <label className="block text-sm font-medium text-slate-300 mb-1">{setting.label}</label>
+                          <textarea
+                            value={newChannel.settings[setting.key] || ''}
+                            onChange={(e) =>
+                              setNewChannel({
+                                ...newChannel,
+                                settings: {
+                                  ...newChannel.settings,
+                                  [setting.key]: e.target.value,
+                                },
+                              })
+                            }
+                            placeholder={setting.placeholder}
+                            rows={5}
+                            className="w-full px-3 py-2 bg-slate-800 border border-slate-700 rounded-lg text-white text-sm focus:ring-2 focus:ring-stark-500 focus:border-transparent"
+                          />
+                          <p className="mt-1 text-xs text-slate-500">{setting.description}</p>
+                        </>
+                      ) : (setting.key === 'external_channel_api_token' || setting.key === 'webhook_secret') ? (
                         <TokenInput
                           value={newChannel.settings[setting.key] || ''}
                           onChange={(value) =>
@@ -772,7 +830,27 @@ export default function Channels() {
                                       </select>
                                       <p className="mt-1 text-xs text-slate-500">{setting.description}</p>
                                     </>
-                                  ) : setting.key === 'external_channel_api_token' ? (
+                                  ) : setting.input_type === 'text_area' ? (
+                                    <>
+                                      <label className="block text-sm font-medium text-slate-300 mb-1">{setting.label}</label>
+                                      <textarea
+                                        value={editForm.settings[setting.key] || ''}
+                                        onChange={(e) =>
+                                          setEditForm({
+                                            ...editForm,
+                                            settings: {
+                                              ...editForm.settings,
+                                              [setting.key]: e.target.value,
+                                            },
+                                          })
+                                        }
+                                        placeholder={setting.placeholder}
+                                        rows={5}
+                                        className="w-full px-3 py-2 bg-slate-800 border border-slate-700 rounded-lg text-white text-sm focus:ring-2 focus:ring-stark-500 focus:border-transparent"
+                                      />
+                                      <p className="mt-1 text-xs text-slate-500">{setting.description}</p>
+                                    </>
+                                  ) : (setting.key === 'external_channel_api_token' || setting.key === 'webhook_secret') ? (
                                     <TokenInput
                                       value={editForm.settings[setting.key] || ''}
                                       onChange={(value) =>
@@ -862,6 +940,61 @@ export default function Channels() {
           })
         )}
       </div>
+
+      {/* Recently Deleted */}
+      {deletedChannels.length > 0 && (
+        <Card className="mt-6">
+          <CardHeader>
+            <CardTitle>Recently Deleted</CardTitle>
+          </CardHeader>
+          <CardContent>
+            <div className="space-y-2">
+              {deletedChannels.map((channel) => {
+                const { Icon, color } = getChannelIcon(channel.channel_type);
+                const isActionLoading = actionLoading === channel.id;
+                return (
+                  <div
+                    key={channel.id}
+                    className="flex items-center justify-between gap-3 px-3 py-2 bg-slate-700/30 rounded-lg"
+                  >
+                    <div className="flex items-center gap-3 min-w-0">
+                      <Icon className={`w-4 h-4 text-${color}-400 shrink-0`} />
+                      <div className="min-w-0">
+                        <p className="text-sm text-slate-200 truncate">{channel.name}</p>
+                        <p className="text-xs text-slate-500">
+                          {channel.channel_type}
+                          {channel.deleted_at && ` · deleted ${new Date(channel.deleted_at).toLocaleString()}`}
+                        </p>
+                      </div>
+                    </div>
+                    <div className="flex gap-2 shrink-0">
+                      <Button
+                        variant="secondary"
+                        size="sm"
+                        onClick={() => handleRestore(channel.id)}
+                        disabled={isActionLoading}
+                      >
+                        <RotateCcw className="w-4 h-4 mr-1" />
+                        Restore
+                      </Button>
+                      <Button
+                        variant="secondary"
+                        size="sm"
+                        onClick={() => handlePurge(channel.id)}
+                        disabled={isActionLoading}
+                        className="text-red-400 hover:text-red-300"
+                      >
+                        <Trash2 className="w-4 h-4 mr-1" />
+                        Purge
+                      </Button>
+                    </div>
+                  </div>
+                );
+              })}
+            </div>
+          </CardContent>
+        </Card>
+      )}
     </div>
   );
 }
@@ -73,6 +73,10 @@ function summarize(event: string, data: Record<string, unknown>): string {
         return `${data.channel_type || '?'} "${data.name || ''}" started`;
       case 'channel.stopped':
         return `${data.channel_type || '?'} "${data.name || ''}" stopped`;
+      case 'channel.reconnecting':
+        return `${data.channel_type || '?'} ${data.channel_id ?? '?'} connection lost, reconnecting (attempt ${data.attempt ?? '?'})`;
+      case 'channel.reconnected':
+        return `${data.channel_type || '?'} ${data.channel_id ?? '?'} reconnected after ${data.attempts ?? '?'} attempt(s)`;
       case 'channel.error':
         return `${data.channel_id || '?'}: ${truncate(String(data.error || data.message || ''), 200)}`;
       case 'agent.response':
@@ -85,6 +89,8 @@ function summarize(event: string, data: Record<string, unknown>): string {
         return `→ ${data.label || data.subtype || '?'}`;
       case 'agent.thinking':
         return truncate(String(data.message || ''), 120);
+      case 'agent.heartbeat':
+        return `alive ${data.elapsed_secs ?? '?'}s, ${data.steps ?? 0} step(s)`;
       case 'agent.error':
         return truncate(String(data.error || ''), 200);
       case 'agent.warning':